@@ -0,0 +1,46 @@
+extern crate indystrgpostgres;
+extern crate serde_json;
+
+use std::env;
+use std::process;
+
+use indystrgpostgres::postgres_storage::PostgresStorageType;
+
+const USAGE: &str = "Usage: convert_wallet_scheme <from_config> <to_config> <credentials> [wallet_ids]
+
+Copies wallets of DatabasePerWallet deployment into the database of
+MultiWalletSingleTable or MultiWalletSingleTableSharedPool scheme.
+
+  from_config   storage config of the existing deployment, e.g. '{\"url\":\"localhost:5432\"}'
+  to_config     storage config of the target deployment,
+                e.g. '{\"url\":\"localhost:5432\",\"wallet_scheme\":\"MultiWalletSingleTable\"}'
+  credentials   storage credentials with admin account
+  wallet_ids    json array of wallets to convert, all wallets found on the server by default";
+
+fn main() {
+    let args: Vec<String> = env::args().skip(1).collect();
+
+    if args.len() < 3 || args.len() > 4 {
+        eprintln!("{}", USAGE);
+        process::exit(2);
+    }
+
+    let wallet_ids = match args.get(3) {
+        Some(wallet_ids) => match serde_json::from_str::<Vec<String>>(wallet_ids) {
+            Ok(wallet_ids) => Some(wallet_ids),
+            Err(err) => {
+                eprintln!("Invalid wallet_ids: {}", err);
+                process::exit(2);
+            }
+        },
+        None => None
+    };
+
+    match PostgresStorageType::new().convert_scheme(&args[0], &args[1], &args[2], wallet_ids) {
+        Ok(report) => println!("{}", serde_json::to_string_pretty(&report).unwrap()),
+        Err(err) => {
+            eprintln!("Conversion failed: {}", err);
+            process::exit(1);
+        }
+    }
+}
@@ -24,14 +24,20 @@ extern crate time;
 extern crate rand;
 extern crate postgres;
 
+#[cfg(feature = "native")]
+extern crate indy;
+
 pub mod libindy;
 
 // Note that to use macroses from util inside of other modules it must me loaded first!
 #[macro_use]
 pub mod utils;
 pub mod errors;
+pub mod migrations;
 pub mod postgres_storage;
 pub mod wql;
+#[cfg(feature = "native")]
+pub mod native;
 
 use libindy::ErrorCode;
 use utils::sequence::SequenceUtils;
@@ -94,6 +100,42 @@ pub extern fn init_storagetype(config: *const c_char, credentials: *const c_char
     return PostgresWallet::init(config, credentials);
 }
 
+/// Copies wallets of `DatabasePerWallet` deployment into the database of `MultiWalletSingleTable`
+/// or `MultiWalletSingleTableSharedPool` scheme, source databases are left untouched.
+/// `wallet_ids` is a json array of wallet ids to convert, all wallets found on the server are converted if it is null.
+#[no_mangle]
+pub extern fn postgresstorage_convert_scheme(from_config: *const c_char,
+                                             to_config: *const c_char,
+                                             credentials: *const c_char,
+                                             wallet_ids: *const c_char) -> libindy::ErrorCode {
+    check_useful_c_str!(from_config, ErrorCode::CommonInvalidParam1);
+    check_useful_c_str!(to_config, ErrorCode::CommonInvalidParam2);
+    check_useful_c_str!(credentials, ErrorCode::CommonInvalidParam3);
+
+    let wallet_ids = match ctypes::c_str_to_string(wallet_ids) {
+        Ok(Some(wallet_ids)) => match serde_json::from_str::<Vec<String>>(wallet_ids) {
+            Ok(wallet_ids) => Some(wallet_ids),
+            Err(_) => return ErrorCode::CommonInvalidParam4
+        },
+        Ok(None) => None,
+        Err(_) => return ErrorCode::CommonInvalidParam4
+    };
+
+    let storage_type = ::postgres_storage::PostgresStorageType::new();
+    match storage_type.convert_scheme(&from_config, &to_config, &credentials, wallet_ids) {
+        Ok(report) => {
+            info!("Wallet scheme conversion finished: {:?}", report);
+            ErrorCode::Success
+        }
+        Err(WalletStorageError::ConfigError) => ErrorCode::CommonInvalidStructure,
+        Err(WalletStorageError::NotFound) => ErrorCode::WalletNotFoundError,
+        Err(err) => {
+            error!("Wallet scheme conversion failed: {:?}", err);
+            ErrorCode::WalletStorageError
+        }
+    }
+}
+
 struct PostgresStorageContext {
     // TODO save handle, config and credentials in case we need to re-connect to database
     _xhandle: i32,        // reference returned to client to track open wallet connection
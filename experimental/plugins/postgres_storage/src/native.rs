@@ -0,0 +1,229 @@
+//! Registration of the storage through Rust API of libindy.
+//!
+//! Applications that link libindy as a crate can plug Postgres storage without C handlers.
+//! Plugin keeps its own copies of libindy storage types, so this module only converts between them.
+
+use indy::api::wallet as indy_wallet;
+use indy::api::wallet::{err_msg, IndyError, IndyErrorKind, IndyResult};
+
+use errors::wallet::WalletStorageError;
+use postgres_storage::{PostgresStorage, PostgresStorageType, WalletStorageType};
+use wql::language;
+use wql::storage::{EncryptedValue, StorageIterator, StorageRecord, Tag, TagName, WalletStorage};
+use POSTGRES_STORAGE_NAME;
+
+/// Initializes Postgres storage with `config` and `credentials` (the same as for `init_storagetype`)
+/// and registers it in libindy as "postgres_storage" wallet storage type.
+pub fn register_wallet_storage(config: &str, credentials: &str) -> IndyResult<()> {
+    PostgresStorageType::new()
+        .init_storage(Some(config), Some(credentials))
+        .map_err(_indy_error)?;
+
+    indy_wallet::register_wallet_storage_type(POSTGRES_STORAGE_NAME, Box::new(NativeStorageType {}))
+}
+
+struct NativeStorageType {}
+
+impl indy_wallet::WalletStorageType for NativeStorageType {
+    fn create_storage(&self, id: &str, config: Option<&str>, credentials: Option<&str>, metadata: &[u8]) -> IndyResult<()> {
+        PostgresStorageType::new()
+            .create_storage(id, config, credentials, metadata)
+            .map_err(_indy_error)
+    }
+
+    fn open_storage(&self, id: &str, config: Option<&str>, credentials: Option<&str>) -> IndyResult<Box<dyn indy_wallet::WalletStorage>> {
+        let storage = PostgresStorageType::new()
+            .open_storage(id, config, credentials)
+            .map_err(_indy_error)?;
+
+        Ok(Box::new(NativeStorage { storage }))
+    }
+
+    fn delete_storage(&self, id: &str, config: Option<&str>, credentials: Option<&str>) -> IndyResult<()> {
+        PostgresStorageType::new()
+            .delete_storage(id, config, credentials)
+            .map_err(_indy_error)
+    }
+}
+
+struct NativeStorage {
+    storage: Box<PostgresStorage>,
+}
+
+impl indy_wallet::WalletStorage for NativeStorage {
+    fn get(&self, type_: &[u8], id: &[u8], options: &str) -> IndyResult<indy_wallet::StorageRecord> {
+        self.storage.get(type_, id, options)
+            .map(_indy_record)
+            .map_err(_indy_error)
+    }
+
+    fn add(&self, type_: &[u8], id: &[u8], value: &indy_wallet::EncryptedValue, tags: &[indy_wallet::Tag]) -> IndyResult<()> {
+        self.storage.add(type_, id, &_value(value), &_tags(tags))
+            .map_err(_indy_error)
+    }
+
+    fn update(&self, type_: &[u8], id: &[u8], value: &indy_wallet::EncryptedValue) -> IndyResult<()> {
+        self.storage.update(type_, id, &_value(value))
+            .map_err(_indy_error)
+    }
+
+    fn add_tags(&self, type_: &[u8], id: &[u8], tags: &[indy_wallet::Tag]) -> IndyResult<()> {
+        self.storage.add_tags(type_, id, &_tags(tags))
+            .map_err(_indy_error)
+    }
+
+    fn update_tags(&self, type_: &[u8], id: &[u8], tags: &[indy_wallet::Tag]) -> IndyResult<()> {
+        self.storage.update_tags(type_, id, &_tags(tags))
+            .map_err(_indy_error)
+    }
+
+    fn delete_tags(&self, type_: &[u8], id: &[u8], tag_names: &[indy_wallet::TagName]) -> IndyResult<()> {
+        let tag_names: Vec<TagName> = tag_names
+            .iter()
+            .map(|tag_name| match *tag_name {
+                indy_wallet::TagName::OfEncrypted(ref name) => TagName::OfEncrypted(name.clone()),
+                indy_wallet::TagName::OfPlain(ref name) => TagName::OfPlain(name.clone()),
+            })
+            .collect();
+
+        self.storage.delete_tags(type_, id, &tag_names)
+            .map_err(_indy_error)
+    }
+
+    fn delete(&self, type_: &[u8], id: &[u8]) -> IndyResult<()> {
+        self.storage.delete(type_, id)
+            .map_err(_indy_error)
+    }
+
+    fn get_storage_metadata(&self) -> IndyResult<Vec<u8>> {
+        self.storage.get_storage_metadata()
+            .map_err(_indy_error)
+    }
+
+    fn set_storage_metadata(&self, metadata: &[u8]) -> IndyResult<()> {
+        self.storage.set_storage_metadata(metadata)
+            .map_err(_indy_error)
+    }
+
+    fn get_all(&self) -> IndyResult<Box<dyn indy_wallet::StorageIterator>> {
+        let iterator = self.storage.get_all()
+            .map_err(_indy_error)?;
+
+        Ok(Box::new(NativeStorageIterator { iterator }))
+    }
+
+    fn search(&self, type_: &[u8], query: &indy_wallet::language::Operator, options: Option<&str>) -> IndyResult<Box<dyn indy_wallet::StorageIterator>> {
+        let iterator = self.storage.search(type_, &_operator(query), options)
+            .map_err(_indy_error)?;
+
+        Ok(Box::new(NativeStorageIterator { iterator }))
+    }
+
+    fn close(&mut self) -> IndyResult<()> {
+        self.storage.close()
+            .map_err(_indy_error)
+    }
+
+    // Postgres schema is checked by its own constraints, so there is nothing to check here.
+    fn check_consistency(&self, _repair: bool) -> IndyResult<indy_wallet::StorageCheckReport> {
+        Ok(indy_wallet::StorageCheckReport::default())
+    }
+}
+
+struct NativeStorageIterator {
+    iterator: Box<dyn StorageIterator>,
+}
+
+impl indy_wallet::StorageIterator for NativeStorageIterator {
+    fn next(&mut self) -> IndyResult<Option<indy_wallet::StorageRecord>> {
+        self.iterator.next()
+            .map(|record| record.map(_indy_record))
+            .map_err(_indy_error)
+    }
+
+    fn get_total_count(&self) -> IndyResult<Option<usize>> {
+        self.iterator.get_total_count()
+            .map_err(_indy_error)
+    }
+}
+
+fn _indy_error(err: WalletStorageError) -> IndyError {
+    let kind = match err {
+        WalletStorageError::AlreadyExists => IndyErrorKind::WalletAlreadyExists,
+        WalletStorageError::NotFound => IndyErrorKind::WalletNotFound,
+        WalletStorageError::ConfigError => IndyErrorKind::InvalidStructure,
+        WalletStorageError::ItemNotFound => IndyErrorKind::WalletItemNotFound,
+        WalletStorageError::ItemAlreadyExists => IndyErrorKind::WalletItemAlreadyExists,
+        WalletStorageError::IOError(_) => IndyErrorKind::IOError,
+        WalletStorageError::QueryError(_) => IndyErrorKind::WalletQueryError,
+        _ => IndyErrorKind::WalletStorageError,
+    };
+
+    err_msg(kind, err.to_string())
+}
+
+fn _indy_record(record: StorageRecord) -> indy_wallet::StorageRecord {
+    indy_wallet::StorageRecord::new(
+        record.id,
+        record.value.map(|value| indy_wallet::EncryptedValue { data: value.data, key: value.key }),
+        record.type_,
+        record.tags.map(|tags| tags
+            .into_iter()
+            .map(|tag| match tag {
+                Tag::Encrypted(name, value) => indy_wallet::Tag::Encrypted(name, value),
+                Tag::PlainText(name, value) => indy_wallet::Tag::PlainText(name, value),
+            })
+            .collect()),
+    )
+}
+
+fn _value(value: &indy_wallet::EncryptedValue) -> EncryptedValue {
+    EncryptedValue::new(value.data.clone(), value.key.clone())
+}
+
+fn _tags(tags: &[indy_wallet::Tag]) -> Vec<Tag> {
+    tags
+        .iter()
+        .map(|tag| match *tag {
+            indy_wallet::Tag::Encrypted(ref name, ref value) => Tag::Encrypted(name.clone(), value.clone()),
+            indy_wallet::Tag::PlainText(ref name, ref value) => Tag::PlainText(name.clone(), value.clone()),
+        })
+        .collect()
+}
+
+fn _operator(op: &indy_wallet::language::Operator) -> language::Operator {
+    use indy::api::wallet::language::Operator as IndyOperator;
+
+    match *op {
+        IndyOperator::And(ref ops) => language::Operator::And(ops.iter().map(_operator).collect()),
+        IndyOperator::Or(ref ops) => language::Operator::Or(ops.iter().map(_operator).collect()),
+        IndyOperator::Not(ref op) => language::Operator::Not(Box::new(_operator(op))),
+        IndyOperator::Eq(ref name, ref value) => language::Operator::Eq(_tag_name(name), _target_value(value)),
+        IndyOperator::Neq(ref name, ref value) => language::Operator::Neq(_tag_name(name), _target_value(value)),
+        IndyOperator::Gt(ref name, ref value) => language::Operator::Gt(_tag_name(name), _target_value(value)),
+        IndyOperator::Gte(ref name, ref value) => language::Operator::Gte(_tag_name(name), _target_value(value)),
+        IndyOperator::Lt(ref name, ref value) => language::Operator::Lt(_tag_name(name), _target_value(value)),
+        IndyOperator::Lte(ref name, ref value) => language::Operator::Lte(_tag_name(name), _target_value(value)),
+        IndyOperator::Like(ref name, ref value) => language::Operator::Like(_tag_name(name), _target_value(value)),
+        IndyOperator::In(ref name, ref values) => language::Operator::In(_tag_name(name), values.iter().map(_target_value).collect()),
+        IndyOperator::NumGt(ref name, ref value) => language::Operator::NumGt(_tag_name(name), _target_value(value)),
+        IndyOperator::NumGte(ref name, ref value) => language::Operator::NumGte(_tag_name(name), _target_value(value)),
+        IndyOperator::NumLt(ref name, ref value) => language::Operator::NumLt(_tag_name(name), _target_value(value)),
+        IndyOperator::NumLte(ref name, ref value) => language::Operator::NumLte(_tag_name(name), _target_value(value)),
+        IndyOperator::Exist(ref name) => language::Operator::Exist(_tag_name(name)),
+    }
+}
+
+fn _tag_name(name: &indy_wallet::language::TagName) -> language::TagName {
+    match *name {
+        indy_wallet::language::TagName::EncryptedTagName(ref name) => language::TagName::EncryptedTagName(name.clone()),
+        indy_wallet::language::TagName::PlainTagName(ref name) => language::TagName::PlainTagName(name.clone()),
+    }
+}
+
+fn _target_value(value: &indy_wallet::language::TargetValue) -> language::TargetValue {
+    match *value {
+        indy_wallet::language::TargetValue::Unencrypted(ref value) => language::TargetValue::Unencrypted(value.clone()),
+        indy_wallet::language::TargetValue::Encrypted(ref value) => language::TargetValue::Encrypted(value.clone()),
+    }
+}
@@ -51,6 +51,15 @@ impl From<String> for TargetValue {
     }
 }
 
+impl TargetValue {
+    fn to_number_string(&self) -> String {
+        match *self {
+            TargetValue::Unencrypted(ref s) => s.clone(),
+            TargetValue::Encrypted(_) => self.to_string(),
+        }
+    }
+}
+
 impl string::ToString for TargetValue {
     fn to_string(&self) -> String {
         match *self {
@@ -75,6 +84,11 @@ pub enum Operator {
     Lte(TagName, TargetValue),
     Like(TagName, TargetValue),
     In(TagName, Vec<TargetValue>),
+    NumGt(TagName, TargetValue),
+    NumGte(TagName, TargetValue),
+    NumLt(TagName, TargetValue),
+    NumLte(TagName, TargetValue),
+    Exist(TagName),
 }
 
 
@@ -144,6 +158,11 @@ impl string::ToString for Operator {
                     tag_values.iter().map(|v| v.to_string()).collect::<Vec<String>>().join(",")
                 )
             }
+            Operator::NumGt(ref tag_name, ref tag_value) => format!(r#"{{{}:{{"$gt":{}}}}}"#, tag_name.to_string(), tag_value.to_number_string()),
+            Operator::NumGte(ref tag_name, ref tag_value) => format!(r#"{{{}:{{"$gte":{}}}}}"#, tag_name.to_string(), tag_value.to_number_string()),
+            Operator::NumLt(ref tag_name, ref tag_value) => format!(r#"{{{}:{{"$lt":{}}}}}"#, tag_name.to_string(), tag_value.to_number_string()),
+            Operator::NumLte(ref tag_name, ref tag_value) => format!(r#"{{{}:{{"$lte":{}}}}}"#, tag_name.to_string(), tag_value.to_number_string()),
+            Operator::Exist(ref tag_name) => format!(r#"{{{}:{{"$exist":true}}}}"#, tag_name.to_string()),
             Operator::And(ref operators) => {
                 if operators.len() > 0 {
                     format!(
@@ -266,7 +285,14 @@ fn parse_single_operator(operator_name: String, key: String, value: serde_json::
                 TagName::EncryptedTagName(_) => Err(WalletQueryError::StructureErr("$gt must be used only for nonencrypted tag".to_string()))
             }
         }
-        ("$gt", _) => Err(WalletQueryError::ValueErr("$gt must be used with string".to_string())),
+        ("$gt", serde_json::Value::Number(n)) => {
+            let target_name = _build_target_name(&key, encrypted);
+            match target_name {
+                TagName::PlainTagName(_) => Ok(Operator::NumGt(target_name, TargetValue::Unencrypted(n.to_string()))),
+                TagName::EncryptedTagName(_) => Err(WalletQueryError::StructureErr("$gt must be used only for nonencrypted tag".to_string()))
+            }
+        }
+        ("$gt", _) => Err(WalletQueryError::ValueErr("$gt must be used with string or number".to_string())),
         ("$gte", serde_json::Value::String(s)) => {
             let target_name = _build_target_name(&key, encrypted);
             match target_name {
@@ -277,7 +303,14 @@ fn parse_single_operator(operator_name: String, key: String, value: serde_json::
                 TagName::EncryptedTagName(_) => Err(WalletQueryError::StructureErr("$gte must be used only for nonencrypted tag".to_string()))
             }
         }
-        ("$gte", _) => Err(WalletQueryError::ValueErr("$gte must be used with string".to_string())),
+        ("$gte", serde_json::Value::Number(n)) => {
+            let target_name = _build_target_name(&key, encrypted);
+            match target_name {
+                TagName::PlainTagName(_) => Ok(Operator::NumGte(target_name, TargetValue::Unencrypted(n.to_string()))),
+                TagName::EncryptedTagName(_) => Err(WalletQueryError::StructureErr("$gte must be used only for nonencrypted tag".to_string()))
+            }
+        }
+        ("$gte", _) => Err(WalletQueryError::ValueErr("$gte must be used with string or number".to_string())),
         ("$lt", serde_json::Value::String(s)) => {
             let target_name = _build_target_name(&key, encrypted);
             match target_name {
@@ -288,7 +321,14 @@ fn parse_single_operator(operator_name: String, key: String, value: serde_json::
                 TagName::EncryptedTagName(_) => Err(WalletQueryError::StructureErr("$lt must be used only for nonencrypted tag".to_string()))
             }
         }
-        ("$lt", _) => Err(WalletQueryError::ValueErr("$lt must be used with string".to_string())),
+        ("$lt", serde_json::Value::Number(n)) => {
+            let target_name = _build_target_name(&key, encrypted);
+            match target_name {
+                TagName::PlainTagName(_) => Ok(Operator::NumLt(target_name, TargetValue::Unencrypted(n.to_string()))),
+                TagName::EncryptedTagName(_) => Err(WalletQueryError::StructureErr("$lt must be used only for nonencrypted tag".to_string()))
+            }
+        }
+        ("$lt", _) => Err(WalletQueryError::ValueErr("$lt must be used with string or number".to_string())),
         ("$lte", serde_json::Value::String(s)) => {
             let target_name = _build_target_name(&key, encrypted);
             match target_name {
@@ -299,7 +339,14 @@ fn parse_single_operator(operator_name: String, key: String, value: serde_json::
                 TagName::EncryptedTagName(_) => Err(WalletQueryError::StructureErr("$lte must be used only for nonencrypted tag".to_string()))
             }
         }
-        ("$lte", _) => Err(WalletQueryError::ValueErr("$lte must be used with string".to_string())),
+        ("$lte", serde_json::Value::Number(n)) => {
+            let target_name = _build_target_name(&key, encrypted);
+            match target_name {
+                TagName::PlainTagName(_) => Ok(Operator::NumLte(target_name, TargetValue::Unencrypted(n.to_string()))),
+                TagName::EncryptedTagName(_) => Err(WalletQueryError::StructureErr("$lte must be used only for nonencrypted tag".to_string()))
+            }
+        }
+        ("$lte", _) => Err(WalletQueryError::ValueErr("$lte must be used with string or number".to_string())),
         ("$like", serde_json::Value::String(s)) => {
             let target_name = _build_target_name(&key, encrypted);
             match target_name {
@@ -328,6 +375,11 @@ fn parse_single_operator(operator_name: String, key: String, value: serde_json::
             Ok(Operator::In(target_name, target_values))
         }
         ("$in", _) => Err(WalletQueryError::ValueErr("$in must be used with array of strings".to_string())),
+        ("$exist", serde_json::Value::Bool(exist)) => {
+            let operator = Operator::Exist(_build_target_name(&key, encrypted));
+            Ok(if exist { operator } else { Operator::Not(Box::new(operator)) })
+        }
+        ("$exist", _) => Err(WalletQueryError::ValueErr("$exist must be used with boolean".to_string())),
         (_, _) => Err(WalletQueryError::ValueErr(format!("Bad operator: {}", operator_name)))
     }
 }
@@ -398,12 +450,17 @@ mod tests {
                 | (Operator::Gte(name, value), Operator::Gte(other_name, other_value))
                 | (Operator::Lt(name, value), Operator::Lt(other_name, other_value))
                 | (Operator::Lte(name, value), Operator::Lte(other_name, other_value))
-                | (Operator::Like(name, value), Operator::Like(other_name, other_value)) => {
+                | (Operator::Like(name, value), Operator::Like(other_name, other_value))
+                | (Operator::NumGt(name, value), Operator::NumGt(other_name, other_value))
+                | (Operator::NumGte(name, value), Operator::NumGte(other_name, other_value))
+                | (Operator::NumLt(name, value), Operator::NumLt(other_name, other_value))
+                | (Operator::NumLte(name, value), Operator::NumLte(other_name, other_value)) => {
                     name == other_name && value == other_value
                 },
                 (Operator::In(name, values), Operator::In(other_name, other_values)) => {
                     name == other_name && vec_to_set(values) == vec_to_set(other_values)
                 },
+                (Operator::Exist(name), Operator::Exist(other_name)) => name == other_name,
                 (Operator::Not(operator), Operator::Not(other_operator)) => operator == other_operator,
                 (Operator::And(operators), Operator::And(other_operators))
                 | (Operator::Or(operators), Operator::Or(other_operators)) => {
@@ -436,6 +493,31 @@ mod tests {
         assert_eq!(encrypted_query, query2);
     }
 
+    #[test]
+    fn test_numeric_json_parse_unparse_plain() {
+        let json = r#"{"~age": {"$gte": 18}}"#;
+        let query = parse_from_json(json).unwrap();
+        assert_eq!(query, Operator::NumGte(TagName::PlainTagName("age".to_string().to_vec()), TargetValue::Unencrypted("18".to_string())));
+        let json2 = query.to_string();
+        let query2 = parse_from_json_encrypted(&json2).unwrap();
+        assert_eq!(query, query2);
+    }
+
+    #[test]
+    fn test_numeric_json_parse_fails_for_encrypted_tag() {
+        let json = r#"{"age": {"$gte": 18}}"#;
+        assert!(parse_from_json(json).is_err());
+    }
+
+    #[test]
+    fn test_exist_json_parse() {
+        let query = parse_from_json(r#"{"email": {"$exist": true}}"#).unwrap();
+        assert_eq!(query, Operator::Exist(TagName::EncryptedTagName("email".to_string().to_vec())));
+
+        let query = parse_from_json(r#"{"~email": {"$exist": false}}"#).unwrap();
+        assert_eq!(query, Operator::Not(Box::new(Operator::Exist(TagName::PlainTagName("email".to_string().to_vec())))));
+    }
+
     #[test]
     fn test_simple_operator_empty_json_parse() {
         let json = "{}";
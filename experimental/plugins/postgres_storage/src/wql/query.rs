@@ -10,7 +10,7 @@ use language::{Operator,TagName,TargetValue};
 pub fn wql_to_sql<'a>(class: &'a Vec<u8>, op: &'a Operator, _options: Option<&str>) -> Result<(String, Vec<&'a dyn ToSql>), WalletQueryError> {
     let mut arguments: Vec<&dyn ToSql> = Vec::new();
     arguments.push(class);
-    let clause_string = operator_to_sql(op, &mut arguments)?;
+    let clause_string = operator_to_sql(op, None, &mut arguments)?;
     let mut query_string = "SELECT i.id, i.name, i.value, i.key, i.type FROM items as i WHERE i.type = $$".to_string();
     if !clause_string.is_empty() {
         query_string.push_str(" AND ");
@@ -20,18 +20,61 @@ pub fn wql_to_sql<'a>(class: &'a Vec<u8>, op: &'a Operator, _options: Option<&st
 }
 
 
-pub fn wql_to_sql_count<'a>(class: &'a Vec<u8>, op: &'a Operator) -> Result<(String, Vec<&'a dyn ToSql>), WalletQueryError> {
+pub fn wql_to_sql_count<'a>(class: &'a Vec<u8>, op: &'a Operator, wallet_id: Option<&'a String>) -> Result<(String, Vec<&'a dyn ToSql>), WalletQueryError> {
     let mut arguments: Vec<&dyn ToSql> = Vec::new();
     arguments.push(class);
-    let clause_string = operator_to_sql(op, &mut arguments)?;
-    let mut query_string = "SELECT count(*) FROM items as i WHERE i.type = $$".to_string();
-    if !clause_string.is_empty() {
-        query_string.push_str(" AND ");
-        query_string.push_str(&clause_string);
+    let mut conditions = vec!["i.type = $$".to_string()];
+    push_wql_conditions(op, wallet_id, &mut conditions, &mut arguments)?;
+    let query_string = format!("SELECT count(*) FROM items as i WHERE {}", conditions.join(" AND "));
+    Ok((convert_query_to_psql_args(&query_string), arguments))
+}
+
+
+// Translates WQL to the query of a single page of records ordered by item id.
+// Keyset pagination is used instead of OFFSET, so each page costs the same regardless of its position:
+// the next page is requested with `after_id` set to the id of the last fetched record.
+pub fn wql_to_sql_page<'a>(class: Option<&'a Vec<u8>>,
+                           op: &'a Operator,
+                           wallet_id: Option<&'a String>,
+                           after_id: Option<&'a i64>,
+                           limit: &'a i64) -> Result<(String, Vec<&'a dyn ToSql>), WalletQueryError> {
+    let mut arguments: Vec<&dyn ToSql> = Vec::new();
+    let mut conditions = Vec::new();
+    if let Some(class) = class {
+        conditions.push("i.type = $$".to_string());
+        arguments.push(class);
     }
+    if let Some(after_id) = after_id {
+        conditions.push("i.id > $$".to_string());
+        arguments.push(after_id);
+    }
+    push_wql_conditions(op, wallet_id, &mut conditions, &mut arguments)?;
+
+    let mut query_string = "SELECT i.id, i.name, i.value, i.key, i.type FROM items as i".to_string();
+    if !conditions.is_empty() {
+        query_string.push_str(" WHERE ");
+        query_string.push_str(&conditions.join(" AND "));
+    }
+    query_string.push_str(" ORDER BY i.id LIMIT $$");
+    arguments.push(limit);
     Ok((convert_query_to_psql_args(&query_string), arguments))
 }
 
+
+// Adds conditions of WQL query, restricted to the wallet for multi-wallet schemes
+fn push_wql_conditions<'a>(op: &'a Operator, wallet_id: Option<&'a String>, conditions: &mut Vec<String>, arguments: &mut Vec<&'a dyn ToSql>) -> Result<(), WalletQueryError> {
+    if let Some(wallet_id) = wallet_id {
+        conditions.push("i.wallet_id = $$".to_string());
+        arguments.push(wallet_id);
+    }
+
+    let clause_string = operator_to_sql(op, wallet_id, arguments)?;
+    if !clause_string.is_empty() {
+        conditions.push(clause_string);
+    }
+    Ok(())
+}
+
 fn convert_query_to_psql_args(query: &str) -> String {
     let mut index = 1;
     let mut s: String = query.to_owned();
@@ -43,123 +86,145 @@ fn convert_query_to_psql_args(query: &str) -> String {
     s
 }
 
-fn operator_to_sql<'a>(op: &'a Operator, arguments: &mut Vec<&'a dyn ToSql>) -> Result<String, WalletQueryError> {
+fn operator_to_sql<'a>(op: &'a Operator, wallet_id: Option<&'a String>, arguments: &mut Vec<&'a dyn ToSql>) -> Result<String, WalletQueryError> {
     match *op {
-        Operator::Eq(ref tag_name, ref target_value) => eq_to_sql(tag_name, target_value, arguments),
-        Operator::Neq(ref tag_name, ref target_value) => neq_to_sql(tag_name, target_value, arguments),
-        Operator::Gt(ref tag_name, ref target_value) => gt_to_sql(tag_name, target_value, arguments),
-        Operator::Gte(ref tag_name, ref target_value) => gte_to_sql(tag_name, target_value, arguments),
-        Operator::Lt(ref tag_name, ref target_value) => lt_to_sql(tag_name, target_value, arguments),
-        Operator::Lte(ref tag_name, ref target_value) => lte_to_sql(tag_name, target_value, arguments),
-        Operator::Like(ref tag_name, ref target_value) => like_to_sql(tag_name, target_value, arguments),
-        Operator::In(ref tag_name, ref target_values) => in_to_sql(tag_name, target_values, arguments),
-        Operator::And(ref suboperators) => and_to_sql(suboperators, arguments),
-        Operator::Or(ref suboperators) => or_to_sql(suboperators, arguments),
-        Operator::Not(ref suboperator) => not_to_sql(suboperator, arguments),
+        Operator::Eq(ref tag_name, ref target_value) => eq_to_sql(tag_name, target_value, wallet_id, arguments),
+        Operator::Neq(ref tag_name, ref target_value) => neq_to_sql(tag_name, target_value, wallet_id, arguments),
+        Operator::Gt(ref tag_name, ref target_value) => gt_to_sql(tag_name, target_value, wallet_id, arguments),
+        Operator::Gte(ref tag_name, ref target_value) => gte_to_sql(tag_name, target_value, wallet_id, arguments),
+        Operator::Lt(ref tag_name, ref target_value) => lt_to_sql(tag_name, target_value, wallet_id, arguments),
+        Operator::Lte(ref tag_name, ref target_value) => lte_to_sql(tag_name, target_value, wallet_id, arguments),
+        Operator::Like(ref tag_name, ref target_value) => like_to_sql(tag_name, target_value, wallet_id, arguments),
+        Operator::In(ref tag_name, ref target_values) => in_to_sql(tag_name, target_values, wallet_id, arguments),
+        Operator::NumGt(ref tag_name, ref target_value) => num_cmp_to_sql(tag_name, target_value, ">", "$gt", wallet_id, arguments),
+        Operator::NumGte(ref tag_name, ref target_value) => num_cmp_to_sql(tag_name, target_value, ">=", "$gte", wallet_id, arguments),
+        Operator::NumLt(ref tag_name, ref target_value) => num_cmp_to_sql(tag_name, target_value, "<", "$lt", wallet_id, arguments),
+        Operator::NumLte(ref tag_name, ref target_value) => num_cmp_to_sql(tag_name, target_value, "<=", "$lte", wallet_id, arguments),
+        Operator::Exist(ref tag_name) => exist_to_sql(tag_name, wallet_id, arguments),
+        Operator::And(ref suboperators) => and_to_sql(suboperators, wallet_id, arguments),
+        Operator::Or(ref suboperators) => or_to_sql(suboperators, wallet_id, arguments),
+        Operator::Not(ref suboperator) => not_to_sql(suboperator, wallet_id, arguments),
     }
 }
 
 
-fn eq_to_sql<'a>(name: &'a TagName, value: &'a TargetValue, arguments: &mut Vec<&'a dyn ToSql>) -> Result<String, WalletQueryError> {
+// Starts subquery of item ids by tag name. For multi-wallet schemes the subquery is restricted to the wallet,
+// so the planner can use (wallet_id, name, item_id) indexes of tag tables instead of scanning tags of all wallets.
+fn tag_subquery<'a>(table: &str, name: &'a Vec<u8>, wallet_id: Option<&'a String>, arguments: &mut Vec<&'a dyn ToSql>) -> String {
+    match wallet_id {
+        Some(wallet_id) => {
+            arguments.push(wallet_id);
+            arguments.push(name);
+            format!("SELECT item_id FROM {} WHERE wallet_id = $$ AND name = $$", table)
+        }
+        None => {
+            arguments.push(name);
+            format!("SELECT item_id FROM {} WHERE name = $$", table)
+        }
+    }
+}
+
+
+fn eq_to_sql<'a>(name: &'a TagName, value: &'a TargetValue, wallet_id: Option<&'a String>, arguments: &mut Vec<&'a dyn ToSql>) -> Result<String, WalletQueryError> {
     match (name, value) {
         (&TagName::PlainTagName(ref queried_name), &TargetValue::Unencrypted(ref queried_value)) => {
-            arguments.push(queried_name);
+            let subquery = tag_subquery("tags_plaintext", queried_name, wallet_id, arguments);
             arguments.push(queried_value);
-            Ok("(i.id in (SELECT item_id FROM tags_plaintext WHERE name = $$ AND value = $$))".to_string())
+            Ok(format!("(i.id in ({} AND value = $$))", subquery))
         },
         (&TagName::EncryptedTagName(ref queried_name), &TargetValue::Encrypted(ref queried_value)) => {
-            arguments.push(queried_name);
+            let subquery = tag_subquery("tags_encrypted", queried_name, wallet_id, arguments);
             arguments.push(queried_value);
-            Ok("(i.id in (SELECT item_id FROM tags_encrypted WHERE name = $$ AND value = $$))".to_string())
+            Ok(format!("(i.id in ({} AND value = $$))", subquery))
         },
         _ => Err(WalletQueryError::StructureErr("Invalid combination of tag name and value for equality operator".to_string()))
     }
 }
 
 
-fn neq_to_sql<'a>(name: &'a TagName, value: &'a TargetValue, arguments: &mut Vec<&'a dyn ToSql>) -> Result<String, WalletQueryError> {
+fn neq_to_sql<'a>(name: &'a TagName, value: &'a TargetValue, wallet_id: Option<&'a String>, arguments: &mut Vec<&'a dyn ToSql>) -> Result<String, WalletQueryError> {
     match (name, value) {
         (&TagName::PlainTagName(ref queried_name), &TargetValue::Unencrypted(ref queried_value)) => {
-            arguments.push(queried_name);
+            let subquery = tag_subquery("tags_plaintext", queried_name, wallet_id, arguments);
             arguments.push(queried_value);
-            Ok("(i.id in (SELECT item_id FROM tags_plaintext WHERE name = $$ AND value != $$))".to_string())
+            Ok(format!("(i.id in ({} AND value != $$))", subquery))
         },
         (&TagName::EncryptedTagName(ref queried_name), &TargetValue::Encrypted(ref queried_value)) => {
-            arguments.push(queried_name);
+            let subquery = tag_subquery("tags_encrypted", queried_name, wallet_id, arguments);
             arguments.push(queried_value);
-            Ok("(i.id in (SELECT item_id FROM tags_encrypted WHERE name = $$ AND value != $$))".to_string())
+            Ok(format!("(i.id in ({} AND value != $$))", subquery))
         },
         _ => Err(WalletQueryError::StructureErr("Invalid combination of tag name and value for inequality operator".to_string()))
     }
 }
 
 
-fn gt_to_sql<'a>(name: &'a TagName, value: &'a TargetValue, arguments: &mut Vec<&'a dyn ToSql>) -> Result<String, WalletQueryError> {
+fn gt_to_sql<'a>(name: &'a TagName, value: &'a TargetValue, wallet_id: Option<&'a String>, arguments: &mut Vec<&'a dyn ToSql>) -> Result<String, WalletQueryError> {
     match (name, value) {
         (&TagName::PlainTagName(ref queried_name), &TargetValue::Unencrypted(ref queried_value)) => {
-            arguments.push(queried_name);
+            let subquery = tag_subquery("tags_plaintext", queried_name, wallet_id, arguments);
             arguments.push(queried_value);
-            Ok("(i.id in (SELECT item_id FROM tags_plaintext WHERE name = $$ AND value > $$))".to_string())
+            Ok(format!("(i.id in ({} AND value > $$))", subquery))
         },
         _ => Err(WalletQueryError::StructureErr("Invalid combination of tag name and value for $gt operator".to_string()))
     }
 }
 
 
-fn gte_to_sql<'a>(name: &'a TagName, value: &'a TargetValue, arguments: &mut Vec<&'a dyn ToSql>) -> Result<String, WalletQueryError> {
+fn gte_to_sql<'a>(name: &'a TagName, value: &'a TargetValue, wallet_id: Option<&'a String>, arguments: &mut Vec<&'a dyn ToSql>) -> Result<String, WalletQueryError> {
     match (name, value) {
         (&TagName::PlainTagName(ref queried_name), &TargetValue::Unencrypted(ref queried_value)) => {
-            arguments.push(queried_name);
+            let subquery = tag_subquery("tags_plaintext", queried_name, wallet_id, arguments);
             arguments.push(queried_value);
-            Ok("(i.id in (SELECT item_id FROM tags_plaintext WHERE name = $$ AND value >= $$))".to_string())
+            Ok(format!("(i.id in ({} AND value >= $$))", subquery))
         },
         _ => Err(WalletQueryError::StructureErr("Invalid combination of tag name and value for $gte operator".to_string()))
     }
 }
 
 
-fn lt_to_sql<'a>(name: &'a TagName, value: &'a TargetValue, arguments: &mut Vec<&'a dyn ToSql>) -> Result<String, WalletQueryError> {
+fn lt_to_sql<'a>(name: &'a TagName, value: &'a TargetValue, wallet_id: Option<&'a String>, arguments: &mut Vec<&'a dyn ToSql>) -> Result<String, WalletQueryError> {
     match (name, value) {
         (&TagName::PlainTagName(ref queried_name), &TargetValue::Unencrypted(ref queried_value)) => {
-            arguments.push(queried_name);
+            let subquery = tag_subquery("tags_plaintext", queried_name, wallet_id, arguments);
             arguments.push(queried_value);
-            Ok("(i.id in (SELECT item_id FROM tags_plaintext WHERE name = $$ AND value < $$))".to_string())
+            Ok(format!("(i.id in ({} AND value < $$))", subquery))
         },
         _ => Err(WalletQueryError::StructureErr("Invalid combination of tag name and value for $lte operator".to_string()))
     }
 }
 
 
-fn lte_to_sql<'a>(name: &'a TagName, value: &'a TargetValue, arguments: &mut Vec<&'a dyn ToSql>) -> Result<String, WalletQueryError> {
+fn lte_to_sql<'a>(name: &'a TagName, value: &'a TargetValue, wallet_id: Option<&'a String>, arguments: &mut Vec<&'a dyn ToSql>) -> Result<String, WalletQueryError> {
     match (name, value) {
         (&TagName::PlainTagName(ref queried_name), &TargetValue::Unencrypted(ref queried_value)) => {
-            arguments.push(queried_name);
+            let subquery = tag_subquery("tags_plaintext", queried_name, wallet_id, arguments);
             arguments.push(queried_value);
-            Ok("(i.id in (SELECT item_id FROM tags_plaintext WHERE name = $$ AND value <= $$))".to_string())
+            Ok(format!("(i.id in ({} AND value <= $$))", subquery))
         },
         _ => Err(WalletQueryError::StructureErr("Invalid combination of tag name and value for $lte operator".to_string()))
     }
 }
 
 
-fn like_to_sql<'a>(name: &'a TagName, value: &'a TargetValue, arguments: &mut Vec<&'a dyn ToSql>) -> Result<String, WalletQueryError> {
+fn like_to_sql<'a>(name: &'a TagName, value: &'a TargetValue, wallet_id: Option<&'a String>, arguments: &mut Vec<&'a dyn ToSql>) -> Result<String, WalletQueryError> {
     match (name, value) {
         (&TagName::PlainTagName(ref queried_name), &TargetValue::Unencrypted(ref queried_value)) => {
-            arguments.push(queried_name);
+            let subquery = tag_subquery("tags_plaintext", queried_name, wallet_id, arguments);
             arguments.push(queried_value);
-            Ok("(i.id in (SELECT item_id FROM tags_plaintext WHERE name = $$ AND value LIKE $$))".to_string())
+            Ok(format!("(i.id in ({} AND value LIKE $$))", subquery))
         },
         _ => Err(WalletQueryError::StructureErr("Invalid combination of tag name and value for $like operator".to_string()))
     }
 }
 
 
-fn in_to_sql<'a>(name: &'a TagName, values: &'a Vec<TargetValue>, arguments: &mut Vec<&'a dyn ToSql>) -> Result<String, WalletQueryError> {
+fn in_to_sql<'a>(name: &'a TagName, values: &'a Vec<TargetValue>, wallet_id: Option<&'a String>, arguments: &mut Vec<&'a dyn ToSql>) -> Result<String, WalletQueryError> {
     let mut in_string = String::new();
     match name {
         &TagName::PlainTagName(ref queried_name) => {
-            in_string.push_str("(i.id in (SELECT item_id FROM tags_plaintext WHERE name = $$ AND value IN (");
-            arguments.push(queried_name);
+            let subquery = tag_subquery("tags_plaintext", queried_name, wallet_id, arguments);
+            in_string.push_str(&format!("(i.id in ({} AND value IN (", subquery));
 
             for (index, value) in values.iter().enumerate() {
                 if let &TargetValue::Unencrypted(ref target) = value {
@@ -176,8 +241,8 @@ fn in_to_sql<'a>(name: &'a TagName, values: &'a Vec<TargetValue>, arguments: &mu
             Ok(in_string + ")))")
         },
         &TagName::EncryptedTagName(ref queried_name) => {
-            in_string.push_str("(i.id in (SELECT item_id FROM tags_encrypted WHERE name = $$ AND value IN (");
-            arguments.push(queried_name);
+            let subquery = tag_subquery("tags_encrypted", queried_name, wallet_id, arguments);
+            in_string.push_str(&format!("(i.id in ({} AND value IN (", subquery));
             let index_before_last = values.len() - 2;
 
             for (index, value) in values.iter().enumerate() {
@@ -198,28 +263,55 @@ fn in_to_sql<'a>(name: &'a TagName, values: &'a Vec<TargetValue>, arguments: &mu
 }
 
 
-fn and_to_sql<'a>(suboperators: &'a [Operator], arguments: &mut Vec<&'a dyn ToSql>) -> Result<String, WalletQueryError> {
-    join_operators(suboperators, " AND ", arguments)
+// CASE guarantees that only decimal values ([+-]digits[.digits]) are casted,
+// so non numeric values are skipped instead of failing the whole query.
+fn num_cmp_to_sql<'a>(name: &'a TagName, value: &'a TargetValue, sql_op: &str, wql_op: &str, wallet_id: Option<&'a String>, arguments: &mut Vec<&'a dyn ToSql>) -> Result<String, WalletQueryError> {
+    match (name, value) {
+        (&TagName::PlainTagName(ref queried_name), &TargetValue::Unencrypted(ref queried_value)) => {
+            let subquery = tag_subquery("tags_plaintext", queried_name, wallet_id, arguments);
+            arguments.push(queried_value);
+            Ok(format!("(i.id in ({} AND \
+                        (CASE WHEN value ~ '^[+-]?[0-9]+(\\.[0-9]+)?$' THEN CAST(value AS NUMERIC) END) {} CAST($$::text AS NUMERIC)))", subquery, sql_op))
+        },
+        _ => Err(WalletQueryError::StructureErr(format!("Invalid combination of tag name and value for numeric {} operator", wql_op)))
+    }
 }
 
 
-fn or_to_sql<'a>(suboperators: &'a [Operator], arguments: &mut Vec<&'a dyn ToSql>) -> Result<String, WalletQueryError> {
-    join_operators(suboperators, " OR ", arguments)
+fn exist_to_sql<'a>(name: &'a TagName, wallet_id: Option<&'a String>, arguments: &mut Vec<&'a dyn ToSql>) -> Result<String, WalletQueryError> {
+    match name {
+        &TagName::PlainTagName(ref queried_name) => {
+            Ok(format!("(i.id in ({}))", tag_subquery("tags_plaintext", queried_name, wallet_id, arguments)))
+        },
+        &TagName::EncryptedTagName(ref queried_name) => {
+            Ok(format!("(i.id in ({}))", tag_subquery("tags_encrypted", queried_name, wallet_id, arguments)))
+        },
+    }
+}
+
+
+fn and_to_sql<'a>(suboperators: &'a [Operator], wallet_id: Option<&'a String>, arguments: &mut Vec<&'a dyn ToSql>) -> Result<String, WalletQueryError> {
+    join_operators(suboperators, " AND ", wallet_id, arguments)
 }
 
 
-fn not_to_sql<'a>(suboperator: &'a Operator, arguments: &mut Vec<&'a dyn ToSql>) -> Result<String, WalletQueryError> {
-    let suboperator_string = operator_to_sql(suboperator, arguments)?;
+fn or_to_sql<'a>(suboperators: &'a [Operator], wallet_id: Option<&'a String>, arguments: &mut Vec<&'a dyn ToSql>) -> Result<String, WalletQueryError> {
+    join_operators(suboperators, " OR ", wallet_id, arguments)
+}
+
+
+fn not_to_sql<'a>(suboperator: &'a Operator, wallet_id: Option<&'a String>, arguments: &mut Vec<&'a dyn ToSql>) -> Result<String, WalletQueryError> {
+    let suboperator_string = operator_to_sql(suboperator, wallet_id, arguments)?;
     Ok("NOT (".to_string() + &suboperator_string + ")")
 }
 
 
-fn join_operators<'a>(operators: &'a [Operator], join_str: &str, arguments: &mut Vec<&'a dyn ToSql>) -> Result<String, WalletQueryError> {
+fn join_operators<'a>(operators: &'a [Operator], join_str: &str, wallet_id: Option<&'a String>, arguments: &mut Vec<&'a dyn ToSql>) -> Result<String, WalletQueryError> {
     let mut s = String::new();
     if operators.len() > 0 {
         s.push('(');
         for (index, operator) in operators.iter().enumerate() {
-            let operator_string = operator_to_sql(operator, arguments)?;
+            let operator_string = operator_to_sql(operator, wallet_id, arguments)?;
             s.push_str(&operator_string);
             if index < operators.len() - 1 {
                 s.push_str(join_str);
@@ -256,4 +348,52 @@ mod tests {
         let (query, _arguments) = wql_to_sql(&class, &query, None).unwrap();
         assert_eq!(query, "SELECT i.id, i.name, i.value, i.key, i.type FROM items as i WHERE i.type = $1 AND (((i.id in (SELECT item_id FROM tags_encrypted WHERE name = $2 AND value = $3)) AND (i.id in (SELECT item_id FROM tags_plaintext WHERE name = $4 AND value = $5))) OR ((i.id in (SELECT item_id FROM tags_encrypted WHERE name = $6 AND value = $7)) AND NOT ((i.id in (SELECT item_id FROM tags_plaintext WHERE name = $8 AND value = $9)))))")
     }
+
+    #[test]
+    fn exist_and_numeric() {
+        let query = Operator::And(vec![
+            Operator::Exist(TagName::EncryptedTagName(vec![1,2,3])),
+            Operator::NumGte(TagName::PlainTagName(vec![4,5,6]), TargetValue::Unencrypted("18".to_string())),
+        ]);
+        let class = vec![100,100,100];
+        let (query, arguments) = wql_to_sql(&class, &query, None).unwrap();
+        assert!(query.contains("(i.id in (SELECT item_id FROM tags_encrypted WHERE name = $2))"));
+        assert!(query.contains("WHERE name = $3 AND (CASE WHEN"));
+        assert!(query.contains(">= CAST($4::text AS NUMERIC)"));
+        assert_eq!(4, arguments.len());
+    }
+
+    #[test]
+    fn page_for_wallet() {
+        let query = Operator::And(vec![
+            Operator::Eq(TagName::EncryptedTagName(vec![1,2,3]), TargetValue::Encrypted(vec![4,5,6])),
+            Operator::Not(Box::new(Operator::Exist(TagName::PlainTagName(vec![7,8,9])))),
+        ]);
+        let class = vec![100,100,100];
+        let wallet_id = "wallet".to_string();
+        let after_id = 10;
+        let limit = 100;
+        let (query, arguments) = wql_to_sql_page(Some(&class), &query, Some(&wallet_id), Some(&after_id), &limit).unwrap();
+        assert_eq!(query, "SELECT i.id, i.name, i.value, i.key, i.type FROM items as i WHERE i.type = $1 AND i.id > $2 AND i.wallet_id = $3 AND ((i.id in (SELECT item_id FROM tags_encrypted WHERE wallet_id = $4 AND name = $5 AND value = $6)) AND NOT ((i.id in (SELECT item_id FROM tags_plaintext WHERE wallet_id = $7 AND name = $8)))) ORDER BY i.id LIMIT $9");
+        assert_eq!(9, arguments.len());
+    }
+
+    #[test]
+    fn page_for_all_records() {
+        let query = Operator::And(vec![]);
+        let limit = 100;
+        let (query, arguments) = wql_to_sql_page(None, &query, None, None, &limit).unwrap();
+        assert_eq!(query, "SELECT i.id, i.name, i.value, i.key, i.type FROM items as i ORDER BY i.id LIMIT $1");
+        assert_eq!(1, arguments.len());
+    }
+
+    #[test]
+    fn count_for_wallet() {
+        let query = Operator::Eq(TagName::PlainTagName(vec![1,2,3]), TargetValue::Unencrypted("spam".to_string()));
+        let class = vec![100,100,100];
+        let wallet_id = "wallet".to_string();
+        let (query, arguments) = wql_to_sql_count(&class, &query, Some(&wallet_id)).unwrap();
+        assert_eq!(query, "SELECT count(*) FROM items as i WHERE i.type = $1 AND i.wallet_id = $2 AND (i.id in (SELECT item_id FROM tags_plaintext WHERE wallet_id = $3 AND name = $4 AND value = $5))");
+        assert_eq!(5, arguments.len());
+    }
 }
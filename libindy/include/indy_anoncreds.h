@@ -65,6 +65,36 @@ extern "C" {
                                                                                     const char*   revoc_reg_entry_json)
                                                                );
 
+    extern indy_error_t indy_issuer_register_revoc_reg_created_hook(indy_handle_t command_handle,
+
+                                                                    indy_error_t (*hook)(const char*   revoc_reg_id,
+                                                                                         const char*   revoc_reg_def_json,
+                                                                                         const char*   revoc_reg_entry_json),
+
+                                                                    void           (*cb)(indy_handle_t command_handle_,
+                                                                                         indy_error_t  err)
+                                                                    );
+
+    extern indy_error_t indy_issuer_create_revoc_reg_pool(indy_handle_t command_handle,
+                                                          indy_handle_t wallet_handle,
+                                                          const char *  issuer_did,
+                                                          const char *  cred_def_id,
+                                                          const char *  config_json,
+
+                                                          void           (*cb)(indy_handle_t command_handle_,
+                                                                               indy_error_t  err,
+                                                                               const char*   revoc_reg_pool_json)
+                                                          );
+
+    extern indy_error_t indy_issuer_get_revoc_reg_pool(indy_handle_t command_handle,
+                                                       indy_handle_t wallet_handle,
+                                                       const char *  cred_def_id,
+
+                                                       void           (*cb)(indy_handle_t command_handle_,
+                                                                            indy_error_t  err,
+                                                                            const char*   revoc_reg_pool_json)
+                                                       );
+
     extern indy_error_t indy_issuer_create_credential_offer(indy_handle_t command_handle,
                                                             indy_handle_t wallet_handle,
                                                             const char *  cred_def_id,
@@ -89,6 +119,18 @@ extern "C" {
                                                                            const char*   revoc_reg_delta_json)
                                                       );
     
+    extern indy_error_t indy_issuer_create_credentials(indy_handle_t command_handle,
+                                                       indy_handle_t wallet_handle,
+                                                       const char *  cred_requests_json,
+                                                       const char *  rev_reg_id,
+                                                       indy_handle_t blob_storage_reader_handle,
+
+                                                       void           (*cb)(indy_handle_t command_handle_,
+                                                                            indy_error_t  err,
+                                                                            const char*   creds_json,
+                                                                            const char*   revoc_reg_delta_json)
+                                                       );
+
     extern indy_error_t indy_issuer_revoke_credential(indy_handle_t command_handle,
                                                       indy_handle_t wallet_handle,
                                                       indy_handle_t blob_storage_reader_handle,
@@ -100,6 +142,17 @@ extern "C" {
                                                                            const char*   revoc_reg_delta_json)
                                                       );
 
+    extern indy_error_t indy_issuer_revoke_credentials(indy_handle_t command_handle,
+                                                       indy_handle_t wallet_handle,
+                                                       indy_handle_t blob_storage_reader_handle,
+                                                       const char *  rev_reg_id,
+                                                       const char *  cred_revoc_ids_json,
+
+                                                       void           (*cb)(indy_handle_t command_handle_,
+                                                                            indy_error_t  err,
+                                                                            const char*   revoc_reg_delta_json)
+                                                       );
+
 /*    extern indy_error_t indy_issuer_recover_credential(indy_handle_t command_handle,
                                                        indy_handle_t wallet_handle,
                                                        indy_handle_t blob_storage_reader_handle,
@@ -112,6 +165,26 @@ extern "C" {
                                                        );*/
 
 
+    extern indy_error_t indy_issuer_get_revoc_reg_status(indy_handle_t command_handle,
+                                                         indy_handle_t wallet_handle,
+                                                         const char *  rev_reg_id,
+
+                                                         void           (*cb)(indy_handle_t command_handle_,
+                                                                              indy_error_t  err,
+                                                                              const char*   revoc_reg_status_json)
+                                                         );
+
+    extern indy_error_t indy_issuer_check_revoc_reg_drift(indy_handle_t command_handle,
+                                                          indy_handle_t pool_handle,
+                                                          indy_handle_t wallet_handle,
+                                                          const char *  submitter_did,
+                                                          const char *  rev_reg_id,
+
+                                                          void           (*cb)(indy_handle_t command_handle_,
+                                                                               indy_error_t  err,
+                                                                               const char*   revoc_reg_drift_json)
+                                                          );
+
     extern indy_error_t indy_issuer_merge_revocation_registry_deltas(indy_handle_t command_handle,
                                                                      const char *  rev_reg_delta_json,
                                                                      const char *  other_rev_reg_delta_json,
@@ -246,6 +319,16 @@ extern "C" {
                                                                                                 indy_error_t  err)
                                                                            );
 
+    extern indy_error_t indy_prover_auto_select_credentials(indy_handle_t command_handle,
+                                                            indy_handle_t wallet_handle,
+                                                            const char *  proof_request_json,
+                                                            const char *  policy_json,
+
+                                                            void           (*cb)(indy_handle_t command_handle_,
+                                                                                 indy_error_t  err,
+                                                                                 const char*   selection_json)
+                                                            );
+
     extern indy_error_t indy_prover_create_proof(indy_handle_t command_handle,
                                                  indy_handle_t wallet_handle,
                                                  const char *  proof_req_json,
@@ -275,6 +358,34 @@ extern "C" {
                                                    );
 
 
+    extern indy_error_t indy_verifier_verify_proof_with_report(indy_handle_t command_handle,
+                                                               const char *  proof_request_json,
+                                                               const char *  proof_json,
+                                                               const char *  schemas_json,
+                                                               const char *  credential_defs_jsons,
+                                                               const char *  rev_reg_defs_json,
+                                                               const char *  rev_regs_json,
+
+                                                               void           (*cb)(indy_handle_t command_handle_,
+                                                                                    indy_error_t  err,
+                                                                                    const char*   report_json)
+                                                               );
+
+
+    extern indy_error_t indy_verifier_verify_proof_with_ledger(indy_handle_t command_handle,
+                                                               indy_handle_t pool_handle,
+                                                               indy_handle_t wallet_handle,
+                                                               const char *  submitter_did,
+                                                               const char *  proof_request_json,
+                                                               const char *  proof_json,
+                                                               const char *  options_json,
+
+                                                               void           (*cb)(indy_handle_t command_handle_,
+                                                                                    indy_error_t  err,
+                                                                                    const char*   report_json)
+                                                               );
+
+
     extern indy_error_t indy_create_revocation_state(indy_handle_t command_handle,
                                                      indy_handle_t blob_storage_reader_handle,
                                                      const char *  rev_reg_def_json,
@@ -315,6 +426,30 @@ extern "C" {
                                                                  const char*   res)
                                             );
 
+    extern indy_error_t indy_encode_credential_values(indy_handle_t command_handle,
+                                                      const char *  raw_values_json,
+                                                      void           (*cb)(indy_handle_t command_handle_,
+                                                                           indy_error_t  err,
+                                                                           const char*   cred_values_json)
+                                                      );
+
+    extern indy_error_t indy_anoncreds_validate(indy_handle_t command_handle,
+                                                const char *  object_type,
+                                                const char *  object_json,
+                                                const char *  schema_json,
+                                                const char *  cred_def_json,
+                                                void           (*cb)(indy_handle_t command_handle_,
+                                                                     indy_error_t  err,
+                                                                     const char*   problems_json)
+                                                );
+
+    extern indy_error_t indy_encode_date(indy_handle_t command_handle,
+                                         const char *  date,
+                                         void           (*cb)(indy_handle_t command_handle_,
+                                                              indy_error_t  err,
+                                                              const char*   encoded_date)
+                                         );
+
 #ifdef __cplusplus
 }
 #endif
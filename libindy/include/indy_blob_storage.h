@@ -18,6 +18,117 @@ extern "C" {
                                                       void           (*fn)(indy_handle_t command_handle_, indy_error_t err, indy_handle_t handle)
                                                      );
 
+    /// Open blob storage reader for the tails file of the revocation registry.
+    ///
+    /// Tails file is looked up in the local content-addressed tails directory first.
+    /// If it is missing it's fetched from `tailsLocation` of the revocation registry definition,
+    /// checked against `tailsHash` and stored in the tails directory, so it's downloaded only once.
+    /// Files are stored only after the check, so already stored files are trusted and not hashed again.
+    ///
+    /// #Params
+    /// command_handle: Command handle to map callback to caller context.
+    /// rev_reg_def_json: revocation registry definition json
+    /// config_json: tails reader config json
+    ///     {
+    ///         "tails_dir": Optional<string> - directory to store tails files in (<indy home>/tails by default),
+    ///         "fetcher_type": Optional<string> - blob storage reader type used to fetch missing tails file.
+    ///                         By default "http" type is used for http:// locations and local file is read otherwise.
+    ///                         Built-in "http" type supports plain http:// only: no HTTPS and redirects aren't followed.
+    ///         "fetcher_config": Optional<object> - config of the fetcher reader type (empty object by default)
+    ///     }
+    /// cb: Callback that takes command result as parameter.
+    ///
+    /// #Returns
+    /// handle: blob storage reader handle to use in revocation related anoncreds calls
+
+    extern indy_error_t indy_open_tails_reader(indy_handle_t  command_handle,
+                                               const char*    rev_reg_def_json,
+                                               const char*    config_json,
+                                               void           (*fn)(indy_handle_t command_handle_, indy_error_t err, indy_handle_t handle)
+                                              );
+
+    /// Register custom blob storage reader implementation.
+    ///
+    /// Registered reader type can be used in indy_open_blob_storage_reader call
+    /// the same way as built-in "default" (local files) and "http" (read-through HTTP download with local cache) types.
+    ///
+    /// #Params
+    /// command_handle: Command handle to map callback to caller context.
+    /// type_: Blob storage reader type name.
+    /// openFn: Reader open operation handler
+    /// openBlobFn: Reader open blob operation handler
+    /// readFn: Reader read operation handler
+    /// verifyFn: Reader verify operation handler
+    /// closeBlobFn: Reader close blob operation handler
+    ///
+    /// #Returns
+    /// Error code
+
+    extern indy_error_t indy_register_blob_storage_reader(indy_handle_t  command_handle,
+                                                          const char*    type_,
+                                                          indy_error_t (*openFn)(const char* config,
+                                                                                 indy_handle_t* reader_handle_p),
+
+                                                          indy_error_t (*openBlobFn)(indy_handle_t reader_handle,
+                                                                                     const indy_u8_t* hash,
+                                                                                     indy_u32_t hash_len,
+                                                                                     const char* location,
+                                                                                     indy_handle_t* blob_handle_p),
+
+                                                          indy_error_t (*readFn)(indy_handle_t blob_handle,
+                                                                                 indy_u32_t offset,
+                                                                                 indy_u8_t* buf,
+                                                                                 indy_u32_t buf_len,
+                                                                                 indy_u32_t* read_len_p),
+
+                                                          indy_error_t (*verifyFn)(indy_handle_t blob_handle,
+                                                                                   indy_bool_t* valid_p),
+
+                                                          indy_error_t (*closeBlobFn)(indy_handle_t blob_handle),
+
+                                                          void           (*fn)(indy_handle_t command_handle_, indy_error_t err)
+                                                         );
+
+    /// Register custom blob storage writer implementation.
+    ///
+    /// Registered writer type can be used in indy_open_blob_storage_writer call
+    /// the same way as built-in "default" (local files) type.
+    ///
+    /// #Params
+    /// command_handle: Command handle to map callback to caller context.
+    /// type_: Blob storage writer type name.
+    /// openFn: Writer open operation handler
+    /// createBlobFn: Writer create blob operation handler
+    /// appendFn: Writer append operation handler
+    /// finalizeFn: Writer finalize operation handler
+    /// freeBlobFn: Writer free blob operation handler
+    ///
+    /// #Returns
+    /// Error code
+
+    extern indy_error_t indy_register_blob_storage_writer(indy_handle_t  command_handle,
+                                                          const char*    type_,
+                                                          indy_error_t (*openFn)(const char* config,
+                                                                                 indy_handle_t* writer_handle_p),
+
+                                                          indy_error_t (*createBlobFn)(indy_handle_t writer_handle,
+                                                                                       indy_handle_t* blob_handle_p),
+
+                                                          indy_error_t (*appendFn)(indy_handle_t blob_handle,
+                                                                                   const indy_u8_t* data,
+                                                                                   indy_u32_t data_len,
+                                                                                   indy_u32_t* written_len_p),
+
+                                                          indy_error_t (*finalizeFn)(indy_handle_t blob_handle,
+                                                                                     const indy_u8_t* hash,
+                                                                                     indy_u32_t hash_len,
+                                                                                     const char** location_p),
+
+                                                          indy_error_t (*freeBlobFn)(indy_handle_t blob_handle),
+
+                                                          void           (*fn)(indy_handle_t command_handle_, indy_error_t err)
+                                                         );
+
 
 #ifdef __cplusplus
 }
@@ -22,6 +22,7 @@ extern "C" {
     ///    noCache: (bool, optional, false by default) Skip usage of cache,
     ///    noUpdate: (bool, optional, false by default) Use only cached data, do not try to update.
     ///    noStore: (bool, optional, false by default) Skip storing fresh data if updated,
+    ///    ttl: (int, optional) Time to live of stored data in seconds. Expired data is not used and can be removed by indy_purge_expired_records.
     ///    minFresh: (int, optional, -1 by default) Return cached data if not older than this many seconds. -1 means do not check age.
     ///  }
     /// #Returns
@@ -61,6 +62,7 @@ extern "C" {
     ///    noCache: (bool, optional, false by default) Skip usage of cache,
     ///    noUpdate: (bool, optional, false by default) Use only cached data, do not try to update.
     ///    noStore: (bool, optional, false by default) Skip storing fresh data if updated,
+    ///    ttl: (int, optional) Time to live of stored data in seconds. Expired data is not used and can be removed by indy_purge_expired_records.
     ///    minFresh: (int, optional, -1 by default) Return cached data if not older than this many seconds. -1 means do not check age.
     ///  }
     ///
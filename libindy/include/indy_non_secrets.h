@@ -24,6 +24,10 @@ extern "C" {
     ///   If tag name starts with "~" the tag will be stored un-encrypted that will allow
     ///   usage of this tag in complex search queries (comparison, predicates)
     ///   Encrypted tags can be searched only for exact matching
+    ///  Reserved "~ttl": <str> tag sets time to live of the record in seconds. It is stored as
+    ///  "~Indy::ExpiresAt" tag with expiration time (seconds since epoch, zero padded to 20 digits).
+    ///  "~Indy::ExpiresAt" tag itself can't be set or removed directly.
+    ///  Expired records are not returned by get and search and can be removed by indy_purge_expired_records
 
     extern indy_error_t indy_add_wallet_record(indy_handle_t  command_handle,
                                                indy_handle_t  wallet_handle,
@@ -144,6 +148,27 @@ extern "C" {
                                                                        indy_error_t err)
                                                  );
 
+    /// Delete expired wallet records of the type
+    ///
+    /// Records expire when time to live set by reserved "~ttl" tag passes (see indy_add_wallet_record).
+    ///
+    /// #Params
+    /// command_handle: command handle to map callback to caller context
+    /// wallet_handle: wallet handle (created by open_wallet)
+    /// type_: record type
+    /// cb: Callback that takes command result as parameter.
+    ///
+    /// #Returns
+    /// purged_count: number of deleted records
+
+    extern indy_error_t indy_purge_expired_records(indy_handle_t  command_handle,
+                                                   indy_handle_t  wallet_handle,
+                                                   const char*    type_,
+                                                   void           (*fn)(indy_handle_t command_handle_,
+                                                                        indy_error_t err,
+                                                                        indy_u32_t   purged_count)
+                                                  );
+
     /// Get an wallet record by id
     ///
     /// #Params
@@ -144,6 +144,76 @@ extern "C" {
                                                   void         (*fn)(indy_handle_t command_handle_, indy_error_t err)
                                                   );
 
+    /// Registers statistics handlers for custom wallet storage implementation.
+    ///
+    /// Handlers are optional. Without them indy_get_wallet_stats counts records and tags
+    /// by iterating over all records of the storage, so the call cost grows with wallet size
+    /// and storage size is unknown. Handlers are used by wallets opened after this call.
+    ///
+    /// #Params
+    /// command_handle: Command handle to map callback to caller context.
+    /// type_: Storage type name registered with indy_register_wallet_storage call.
+    /// get_storage_stats: WalletType get storage statistics operation handler.
+    ///                    Statistics json: {"types": {<record type as base64 value>: {"records": int, "tags": int}},
+    ///                                      "storage_size": optional<int>, "last_modified": optional<int>}
+    /// free_storage_stats: WalletType free storage statistics operation handler
+    ///
+    /// #Returns
+    /// Error code
+
+    extern indy_error_t indy_register_wallet_storage_stats(indy_handle_t  command_handle,
+                                                           const char*    type_,
+                                                           indy_error_t (*getStorageStatsFn)(indy_handle_t handle,
+                                                                                             const char** stats_json_p,
+                                                                                             indy_handle_t* stats_handle_p),
+
+                                                           indy_error_t (*freeStorageStatsFn)(indy_handle_t handle,
+                                                                                              indy_handle_t stats_handle),
+
+                                                           void         (*fn)(indy_handle_t command_handle_, indy_error_t err)
+                                                           );
+
+    /// Registers custom key-encryption key (KEK) provider implementation.
+    ///
+    /// KEK provider wraps random wallet master key on wallet creation (and rekey)
+    /// and unwraps it on wallet opening. Wallets use registered provider if "key_derivation_method"
+    /// of wallet credentials is set to {"KEK": "<provider type>"}. In this case "key" field
+    /// contains the identifier of the key-encryption key.
+    ///
+    /// Note "file" provider type is built-in. It reads base58 encoded key-encryption key
+    /// from the file with "key" path.
+    ///
+    /// #Params
+    /// command_handle: Command handle to map callback to caller context.
+    /// type_: KEK provider type name.
+    /// wrap_key: KEK provider wrap key operation handler
+    /// unwrap_key: KEK provider unwrap key operation handler
+    /// free_key: KEK provider free key buffer operation handler
+    ///
+    /// #Returns
+    /// Error code
+
+    extern indy_error_t indy_register_wallet_kek_provider(indy_handle_t  command_handle,
+                                                          const char*    type_,
+                                                          indy_error_t (*wrapKeyFn)(const char* key_id,
+                                                                                    const indy_u8_t* key,
+                                                                                    indy_u32_t key_len,
+                                                                                    const indy_u8_t** wrapped_key_p,
+                                                                                    indy_u32_t* wrapped_key_len_p,
+                                                                                    indy_handle_t* key_handle_p),
+
+                                                          indy_error_t (*unwrapKeyFn)(const char* key_id,
+                                                                                      const indy_u8_t* wrapped_key,
+                                                                                      indy_u32_t wrapped_key_len,
+                                                                                      const indy_u8_t** key_p,
+                                                                                      indy_u32_t* key_len_p,
+                                                                                      indy_handle_t* key_handle_p),
+
+                                                          indy_error_t (*freeKeyFn)(indy_handle_t key_handle),
+
+                                                          void         (*fn)(indy_handle_t command_handle_, indy_error_t err)
+                                                          );
+
     /// Create a new secure wallet.
     ///
     /// #Params
@@ -153,6 +223,7 @@ extern "C" {
     ///         Configured storage uses this identifier to lookup exact wallet data placement.
     ///   "storage_type": optional<string>, Type of the wallet storage. Defaults to 'default'.
     ///                  'Default' storage type allows to store wallet data in the local file.
+    ///                  'inmem' storage type keeps wallet data in the process memory only, it is lost when the process exits.
     ///                  Custom storage types can be registered with indy_register_wallet_storage call.
     ///   "storage_config": optional<object>, Storage configuration json. Storage type defines set of supported keys.
     ///                     Can be optional if storage supports default configuration.
@@ -175,6 +246,10 @@ extern "C" {
     ///                          ARGON2I_INT - derive secured wallet master key (less secured but faster)
     ///                          RAW - raw wallet key master provided (skip derivation).
     ///                                RAW keys can be generated with indy_generate_wallet_key call
+    ///                          {"KEK": "<provider type>"} - random wallet master key wrapped by key-encryption key provider.
+    ///                                "key" is the identifier of the key-encryption key for the provider.
+    ///                                Built-in "file" provider reads base58 encoded key from the file with "key" path.
+    ///                                Custom providers can be registered with indy_register_wallet_kek_provider call
     /// }
     ///
     /// #Returns
@@ -201,6 +276,7 @@ extern "C" {
     ///             Configured storage uses this identifier to lookup exact wallet data placement.
     ///       "storage_type": optional<string>, Type of the wallet storage. Defaults to 'default'.
     ///                       'Default' storage type allows to store wallet data in the local file.
+    ///                       'inmem' storage type is described in indy_create_wallet.
     ///                       Custom storage types can be registered with indy_register_wallet_storage call.
     ///       "storage_config": optional<object>, Storage configuration json. Storage type defines set of supported keys.
     ///                         Can be optional if storage supports default configuration.
@@ -209,7 +285,17 @@ extern "C" {
     ///              "path": optional<string>, Path to the directory with wallet files.
     ///                      Defaults to $HOME/.indy_client/wallet.
     ///                      Wallet will be stored in the file {path}/{id}/sqlite.db
+    ///              "shared": optional<bool>, If true the wallet can be opened by several processes at the same time.
+    ///                        Writes are serialized by SQLite locks, every operation is atomic
+    ///                        and sees all changes committed by other processes before it started.
+    ///                        Note that libindy still allows to open the wallet only once within a process.
+    ///                        Defaults to false (the wallet is expected to be used by the single process).
+    ///              "busy_timeout": optional<number>, Time in milliseconds to wait for a lock held by
+    ///                              other process in shared mode. Defaults to 5000.
     ///           }
+    ///       "audit_log": optional<bool>, If true changes of wallet records will be stored in encrypted
+    ///                    append-only audit log (See indy_get_wallet_audit_log). Defaults to false.
+    ///                    Once enabled it can't be disabled for the wallet.
     ///
     ///   }
     /// credentials: Wallet credentials json
@@ -225,11 +311,13 @@ extern "C" {
     ///                          ARGON2I_INT - derive secured wallet master key (less secured but faster)
     ///                          RAW - raw wallet key master provided (skip derivation).
     ///                                RAW keys can be generated with indy_generate_wallet_key call
+    ///                          {"KEK": "<provider type>"} - random wallet master key wrapped by key-encryption key provider.
     ///       "rekey_derivation_method": optional<string> Algorithm to use for wallet rekey derivation:
     ///                          ARGON2I_MOD - derive secured wallet master rekey (used by default)
     ///                          ARGON2I_INT - derive secured wallet master rekey (less secured but faster)
     ///                          RAW - raw wallet key master provided (skip derivation).
     ///                                RAW keys can be generated with indy_generate_wallet_key call
+    ///                          {"KEK": "<provider type>"} - new random wallet master key wrapped by key-encryption key provider.
     ///   }
     ///
     /// #Returns
@@ -260,6 +348,16 @@ extern "C" {
     ///                              ARGON2I_INT - derive secured export key (less secured but faster)
     ///                              RAW - raw export key provided (skip derivation).
     ///                                RAW keys can be generated with indy_generate_wallet_key call
+    ///     "incremental": optional<bool> If true only changes since the previous incremental export are exported
+    ///                    (all records for the first one). Such exports have to be imported in the same order:
+    ///                    the first one with indy_import_wallet and the next ones with indy_import_wallet_increment.
+    ///                    Defaults to false.
+    ///     "filter": optional<array> Export only records matching any of the filters (all records by default).
+    ///               Can't be used with incremental export.
+    ///               [{
+    ///                   "type": string, Record type, e.g. "Indy::Credential", "Indy::MasterSecret" or type of non-secret records
+    ///                   "query": optional<object> WQL query for record tags (all records of the type by default)
+    ///               }]
     ///   }
     ///
     /// #Returns
@@ -287,6 +385,7 @@ extern "C" {
     ///         Configured storage uses this identifier to lookup exact wallet data placement.
     ///   "storage_type": optional<string>, Type of the wallet storage. Defaults to 'default'.
     ///                  'Default' storage type allows to store wallet data in the local file.
+    ///                  'inmem' storage type is described in indy_create_wallet.
     ///                  Custom storage types can be registered with indy_register_wallet_storage call.
     ///   "storage_config": optional<object>, Storage configuration json. Storage type defines set of supported keys.
     ///                     Can be optional if storage supports default configuration.
@@ -330,6 +429,230 @@ extern "C" {
                                            void           (*fn)(indy_handle_t command_handle_, indy_error_t err)
                                            );
 
+    /// Imports content of the incremental export into opened wallet.
+    ///
+    /// Increments have to be imported in the same order as they were exported.
+    /// The first incremental export of the wallet contains all records and has to be imported with indy_import_wallet.
+    /// Increments exported to a buffer or sink can be imported with indy_import_wallet_increment_from_buffer
+    /// and indy_import_wallet_increment_from_source.
+    ///
+    /// #Params
+    /// wallet_handle: wallet handle returned by indy_open_wallet
+    /// import_config: Import settings json.
+    /// {
+    ///   "path": <string>, path of the file that contains exported wallet increment
+    ///   "key": <string>, key used for export of the wallet
+    /// }
+    ///
+    /// #Returns
+    /// Error code
+    ///
+    /// #Errors
+    /// Common*
+    /// Wallet*
+
+    extern indy_error_t indy_import_wallet_increment(indy_handle_t  command_handle,
+                                                     indy_handle_t  wallet_handle,
+                                                     const char*    import_config_json,
+                                                     void           (*fn)(indy_handle_t command_handle_, indy_error_t err)
+                                                     );
+
+    /// Exports opened wallet into a memory buffer.
+    ///
+    /// #Params:
+    /// wallet_handle: wallet handle returned by indy_open_wallet
+    /// export_config: JSON containing settings for input operation.
+    ///   {
+    ///     "key": <string>, Key or passphrase used for wallet export key derivation.
+    ///                     Look to key_derivation_method param for information about supported key derivation methods.
+    ///     "key_derivation_method": optional<string> Algorithm to use for wallet export key derivation:
+    ///                              ARGON2I_MOD - derive secured export key (used by default)
+    ///                              ARGON2I_INT - derive secured export key (less secured but faster)
+    ///                              RAW - raw export key provided (skip derivation).
+    ///                                RAW keys can be generated with indy_generate_wallet_key call
+    ///     "incremental": optional<bool> The same as for indy_export_wallet. Defaults to false.
+    ///     "filter": optional<array> The same as for indy_export_wallet.
+    ///   }
+    /// cb: Callback that takes command result as parameter.
+    ///
+    /// #Returns
+    /// Exported wallet content (the same format as indy_export_wallet writes to the file)
+    ///
+    /// #Errors
+    /// Common*
+    /// Wallet*
+
+    extern indy_error_t indy_export_wallet_to_buffer(indy_handle_t  command_handle,
+                                                     indy_handle_t  wallet_handle,
+                                                     const char*    export_config_json,
+                                                     void           (*fn)(indy_handle_t    command_handle_,
+                                                                          indy_error_t     err,
+                                                                          const indy_u8_t* data_raw,
+                                                                          indy_u32_t       data_len)
+                                                     );
+
+    /// Exports opened wallet into an application provided sink.
+    ///
+    /// Exported content is passed to write_cb chunk by chunk in the same format as indy_export_wallet writes to the file.
+    /// Note that write_cb is called from libindy thread, so it must not block on other libindy calls.
+    ///
+    /// #Params:
+    /// wallet_handle: wallet handle returned by indy_open_wallet
+    /// export_config: JSON containing settings for input operation (See indy_export_wallet_to_buffer).
+    /// sink_handle: application defined handle passed to write_cb
+    /// write_cb: callback that writes the next chunk of the exported wallet.
+    ///           Any error code except Success aborts export.
+    /// cb: Callback that takes command result as parameter.
+    ///
+    /// #Returns
+    /// Error code
+    ///
+    /// #Errors
+    /// Common*
+    /// Wallet*
+
+    extern indy_error_t indy_export_wallet_to_sink(indy_handle_t  command_handle,
+                                                   indy_handle_t  wallet_handle,
+                                                   const char*    export_config_json,
+                                                   indy_handle_t  sink_handle,
+                                                   indy_error_t   (*write_cb)(indy_handle_t    sink_handle,
+                                                                              const indy_u8_t* data,
+                                                                              uintptr_t        data_len),
+                                                   void           (*fn)(indy_handle_t command_handle_, indy_error_t err)
+                                                   );
+
+    /// Creates a new secure wallet and then imports its content from a memory buffer
+    /// produced by indy_export_wallet_to_buffer (or read from the file written by indy_export_wallet).
+    ///
+    /// #Params
+    /// config: Wallet configuration json (See indy_import_wallet).
+    /// credentials: Wallet credentials json (See indy_import_wallet).
+    /// import_config: Import settings json.
+    /// {
+    ///   "key": <string>, key used for export of the wallet
+    /// }
+    /// data_raw: a pointer to first byte of exported wallet content
+    /// data_len: exported wallet content length
+    /// cb: Callback that takes command result as parameter.
+    ///
+    /// #Returns
+    /// Error code
+    ///
+    /// #Errors
+    /// Common*
+    /// Wallet*
+
+    extern indy_error_t indy_import_wallet_from_buffer(indy_handle_t     command_handle,
+                                                       const char*       config,
+                                                       const char*       credentials,
+                                                       const char*       import_config_json,
+                                                       const indy_u8_t*  data_raw,
+                                                       indy_u32_t        data_len,
+                                                       void              (*fn)(indy_handle_t command_handle_, indy_error_t err)
+                                                       );
+
+    /// Creates a new secure wallet and then imports its content from an application provided source.
+    ///
+    /// Exported content is requested from read_cb chunk by chunk until it reports 0 read bytes.
+    /// Note that read_cb is called from libindy thread, so it must not block on other libindy calls.
+    ///
+    /// #Params
+    /// config: Wallet configuration json (See indy_import_wallet).
+    /// credentials: Wallet credentials json (See indy_import_wallet).
+    /// import_config: Import settings json.
+    /// {
+    ///   "key": <string>, key used for export of the wallet
+    /// }
+    /// source_handle: application defined handle passed to read_cb
+    /// read_cb: callback that reads the next chunk of the exported wallet.
+    ///          Any error code except Success aborts import.
+    /// cb: Callback that takes command result as parameter.
+    ///
+    /// #Returns
+    /// Error code
+    ///
+    /// #Errors
+    /// Common*
+    /// Wallet*
+
+    extern indy_error_t indy_import_wallet_from_source(indy_handle_t  command_handle,
+                                                       const char*    config,
+                                                       const char*    credentials,
+                                                       const char*    import_config_json,
+                                                       indy_handle_t  source_handle,
+                                                       indy_error_t   (*read_cb)(indy_handle_t source_handle,
+                                                                                 indy_u8_t*    buf,
+                                                                                 uintptr_t     buf_len,
+                                                                                 uintptr_t*    read_len_p),
+                                                       void           (*fn)(indy_handle_t command_handle_, indy_error_t err)
+                                                       );
+
+    /// Imports content of the incremental export from a memory buffer into opened wallet
+    /// (See indy_import_wallet_increment).
+    ///
+    /// The first increment can be imported with indy_import_wallet_from_buffer.
+    ///
+    /// #Params
+    /// wallet_handle: wallet handle returned by indy_open_wallet
+    /// import_config: Import settings json.
+    /// {
+    ///   "key": <string>, key used for export of the wallet
+    /// }
+    /// data_raw: a pointer to first byte of exported wallet increment
+    /// data_len: exported wallet increment length
+    /// cb: Callback that takes command result as parameter.
+    ///
+    /// #Returns
+    /// Error code
+    ///
+    /// #Errors
+    /// Common*
+    /// Wallet*
+
+    extern indy_error_t indy_import_wallet_increment_from_buffer(indy_handle_t     command_handle,
+                                                                 indy_handle_t     wallet_handle,
+                                                                 const char*       import_config_json,
+                                                                 const indy_u8_t*  data_raw,
+                                                                 indy_u32_t        data_len,
+                                                                 void              (*fn)(indy_handle_t command_handle_, indy_error_t err)
+                                                                 );
+
+    /// Imports content of the incremental export from an application provided source into opened wallet
+    /// (See indy_import_wallet_increment).
+    ///
+    /// The first increment can be imported with indy_import_wallet_from_source.
+    /// Exported content is requested from read_cb chunk by chunk until it reports 0 read bytes.
+    /// Note that read_cb is called from libindy thread, so it must not block on other libindy calls.
+    ///
+    /// #Params
+    /// wallet_handle: wallet handle returned by indy_open_wallet
+    /// import_config: Import settings json.
+    /// {
+    ///   "key": <string>, key used for export of the wallet
+    /// }
+    /// source_handle: application defined handle passed to read_cb
+    /// read_cb: callback that reads the next chunk of the exported wallet increment.
+    ///          Any error code except Success aborts import.
+    /// cb: Callback that takes command result as parameter.
+    ///
+    /// #Returns
+    /// Error code
+    ///
+    /// #Errors
+    /// Common*
+    /// Wallet*
+
+    extern indy_error_t indy_import_wallet_increment_from_source(indy_handle_t  command_handle,
+                                                                 indy_handle_t  wallet_handle,
+                                                                 const char*    import_config_json,
+                                                                 indy_handle_t  source_handle,
+                                                                 indy_error_t   (*read_cb)(indy_handle_t source_handle,
+                                                                                           indy_u8_t*    buf,
+                                                                                           uintptr_t     buf_len,
+                                                                                           uintptr_t*    read_len_p),
+                                                                 void           (*fn)(indy_handle_t command_handle_, indy_error_t err)
+                                                                 );
+
     /// Closes opened wallet and frees allocated resources.
     ///
     /// #Params
@@ -347,6 +670,108 @@ extern "C" {
                                           void           (*fn)(indy_handle_t command_handle_, indy_error_t err)
                                          );
 
+    /// Get entries of the wallet audit log.
+    ///
+    /// Audit log is maintained only for wallets opened with "audit_log": true in wallet config.
+    /// Once enabled the option is stored in wallet metadata and stays on for all subsequent opens.
+    /// Entries are written in the same storage transaction as the change itself
+    /// (plugged storages without transaction support write them sequentially).
+    /// Each entry describes one change of wallet record: additions, modifications and deletions.
+    /// Note record names are not stored in the log, only SHA-256 hashes of them.
+    ///
+    /// #Params
+    /// command_handle: Command handle to map callback to caller context.
+    /// wallet_handle: wallet handle returned by indy_open_wallet.
+    /// query_json: (optional) time range of the requested entries
+    ///   {
+    ///       "from": optional<int>, Return entries created after or at this time (seconds since epoch)
+    ///       "to": optional<int>, Return entries created before or at this time (seconds since epoch)
+    ///   }
+    /// cb: Callback that takes command result as parameter.
+    ///
+    /// #Returns
+    /// entries_json: list of audit log entries ordered by time
+    ///   [{
+    ///       "type": string, Type of the changed record
+    ///       "name_hash": string, Base58 encoded SHA-256 hash of the changed record name
+    ///       "operation": string, One of "add", "update", "update_tags", "delete"
+    ///       "timestamp": int, Time of the change (seconds since epoch)
+    ///   }]
+    ///
+    /// #Errors
+    /// Common*
+    /// Wallet*
+
+    extern indy_error_t indy_get_wallet_audit_log(indy_handle_t  command_handle,
+                                                  indy_handle_t  wallet_handle,
+                                                  const char*    query_json,
+                                                  void           (*fn)(indy_handle_t command_handle_, indy_error_t err, const char* entries_json)
+                                                 );
+
+    /// Get usage statistics of the opened wallet.
+    ///
+    /// Statistics are computed by wallet storage without decryption of record values and tags.
+    ///
+    /// Built-in storages keep counters in indexes, so the call doesn't read records.
+    /// Custom storages without statistics handlers (See indy_register_wallet_storage_stats)
+    /// are scanned through all records, so the call cost grows with wallet size.
+    ///
+    /// #Params
+    /// command_handle: Command handle to map callback to caller context.
+    /// wallet_handle: wallet handle returned by indy_open_wallet.
+    /// cb: Callback that takes command result as parameter.
+    ///
+    /// #Returns
+    /// stats_json: wallet statistics
+    ///   {
+    ///       "total_records": int, Number of records in the wallet (internal records are not counted)
+    ///       "total_tags": int, Number of tags of all records (internal records are not counted)
+    ///       "types": { Number of records and tags per record type ("Indy::Did", "Indy::Key", non-secrets types etc.)
+    ///           <type>: {"records": int, "tags": int},
+    ///       },
+    ///       "internal": { Number of records and tags per type of records maintained by the wallet itself
+    ///           (export journal, export and import checkpoints, audit log)
+    ///           <type>: {"records": int, "tags": int},
+    ///       },
+    ///       "corrupted_records": int, Number of records with type that can't be decrypted (See indy_check_wallet)
+    ///       "storage_size": optional<int>, Size of wallet storage in bytes if storage type can provide it
+    ///       "last_modified": optional<int>, Time of last storage change (seconds since epoch) if storage type can provide it
+    ///   }
+    ///
+    /// #Errors
+    /// Common*
+    /// Wallet*
+
+    extern indy_error_t indy_get_wallet_stats(indy_handle_t  command_handle,
+                                              indy_handle_t  wallet_handle,
+                                              void           (*fn)(indy_handle_t command_handle_, indy_error_t err, const char* stats_json)
+                                             );
+
+    /// Set callback to be notified about changes of wallet records.
+    ///
+    /// Callback is called after each successful addition, modification or deletion of wallet record
+    /// with change description json (the same as entry of indy_get_wallet_audit_log).
+    /// It works regardless of "audit_log" wallet config option.
+    ///
+    /// #Params
+    /// command_handle: Command handle to map callback to caller context.
+    /// wallet_handle: wallet handle returned by indy_open_wallet.
+    /// change_cb: (optional) Change notification callback. Pass NULL to unsubscribe.
+    /// cb: Callback that takes command result as parameter.
+    ///
+    /// #Returns
+    /// Error code
+    ///
+    /// #Errors
+    /// Common*
+    /// Wallet*
+
+    extern indy_error_t indy_set_wallet_change_callback(indy_handle_t  command_handle,
+                                                        indy_handle_t  wallet_handle,
+                                                        void           (*changeFn)(indy_handle_t wallet_handle, const char* change_json),
+                                                        void           (*fn)(indy_handle_t command_handle_, indy_error_t err)
+                                                       );
+
     /// Deletes created wallet.
     ///
     /// #Params
@@ -356,6 +781,7 @@ extern "C" {
     ///         Configured storage uses this identifier to lookup exact wallet data placement.
     ///   "storage_type": optional<string>, Type of the wallet storage. Defaults to 'default'.
     ///                  'Default' storage type allows to store wallet data in the local file.
+    ///                  'inmem' storage type is described in indy_create_wallet.
     ///                  Custom storage types can be registered with indy_register_wallet_storage call.
     ///   "storage_config": optional<object>, Storage configuration json. Storage type defines set of supported keys.
     ///                     Can be optional if storage supports default configuration.
@@ -393,6 +819,51 @@ extern "C" {
                                            void           (*fn)(indy_handle_t command_handle_, indy_error_t err)
                                           );
 
+    /// Checks integrity of created wallet and optionally repairs it.
+    ///
+    /// Walks every wallet record and verifies that its name, type, value and tags can be decrypted
+    /// with wallet keys. For 'default' storage type also checks database integrity and consistency of tags.
+    /// In repair mode corrupted records are moved to "Indy::Quarantine" record type
+    /// and tags not linked to any record are removed.
+    ///
+    /// Wallet must be closed.
+    ///
+    /// #Params
+    /// command_handle: Command handle to map callback to caller context.
+    /// config: Wallet configuration json (the same as for indy_delete_wallet).
+    /// credentials: Wallet credentials json (the same as for indy_delete_wallet).
+    /// options_json: (optional) check options
+    ///   {
+    ///       "repair": optional<bool>, Quarantine corrupted records. Defaults to false.
+    ///   }
+    /// cb: Callback that takes command result as parameter.
+    ///
+    /// #Returns
+    /// report_json: wallet check report
+    ///   {
+    ///       "total_items": int, Count of checked records
+    ///       "corrupted_items": [{
+    ///           "type": optional<string>, Type of the corrupted record if it can be decrypted
+    ///           "id": optional<string>, Id of the corrupted record if it can be decrypted
+    ///           "reason": string, Description of the problem
+    ///       }],
+    ///       "quarantined_items": int, Count of quarantined records
+    ///       "orphaned_tags": int, Count of tags not linked to any record
+    ///       "integrity_errors": [string], Problems reported by the storage
+    ///       "storage_error": optional<string>, Error that interrupted walking through the storage
+    ///   }
+    ///
+    /// #Errors
+    /// Common*
+    /// Wallet*
+
+    extern indy_error_t indy_check_wallet(indy_handle_t  command_handle,
+                                          const char*    config,
+                                          const char*    credentials,
+                                          const char*    options_json,
+                                          void           (*fn)(indy_handle_t command_handle_, indy_error_t err, const char* report_json)
+                                         );
+
     /// Generate wallet master key.
     /// Returned key is compatible with "RAW" key derivation method.
     /// It allows to avoid expensive key derivation for use cases when wallet keys can be stored in a secure enclave.
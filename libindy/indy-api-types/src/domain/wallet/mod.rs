@@ -8,6 +8,7 @@ pub struct Config {
     pub id: String,
     pub storage_type: Option<String>,
     pub storage_config: Option<Value>,
+    pub audit_log: Option<bool>,
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
@@ -26,19 +27,52 @@ pub struct Credentials {
 pub enum KeyDerivationMethod {
     RAW,
     ARGON2I_MOD,
-    ARGON2I_INT
+    ARGON2I_INT,
+    // Wallet master key is random and wrapped by registered key-encryption key provider
+    KEK(String /* provider type */)
 }
 
 fn default_key_derivation_method() -> KeyDerivationMethod {
     KeyDerivationMethod::ARGON2I_MOD
 }
 
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+pub struct AuditLogQuery {
+    pub from: Option<u64>,
+    pub to: Option<u64>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+pub struct CheckWalletOptions {
+    pub repair: Option<bool>,
+}
+
 #[derive(Debug, Serialize, Deserialize, Clone)]
 pub struct ExportConfig {
     pub key: String,
     pub path: String,
     #[serde(default = "default_key_derivation_method")]
-    pub key_derivation_method: KeyDerivationMethod
+    pub key_derivation_method: KeyDerivationMethod,
+    pub incremental: Option<bool>,
+    pub filter: Option<Vec<ExportFilter>>,
+}
+
+// The same as ExportConfig, but for exports to and imports from buffers or callbacks.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct StreamExportConfig {
+    pub key: String,
+    #[serde(default = "default_key_derivation_method")]
+    pub key_derivation_method: KeyDerivationMethod,
+    pub incremental: Option<bool>,
+    pub filter: Option<Vec<ExportFilter>>,
+}
+
+// Selects records of the type matching WQL query (all records of the type if query is omitted).
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct ExportFilter {
+    #[serde(rename = "type")]
+    pub type_: String,
+    pub query: Option<Value>,
 }
 
 #[derive(Debug, Deserialize)]
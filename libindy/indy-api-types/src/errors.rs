@@ -271,6 +271,9 @@ impl From<rusqlite::Error> for IndyError {
                 err.to_indy(IndyErrorKind::WalletItemAlreadyExists, "Wallet item already exists"),
             rusqlite::Error::SqliteFailure(rusqlite::ffi::Error { code: rusqlite::ffi::ErrorCode::SystemIoFailure, .. }, _) =>
                 err.to_indy(IndyErrorKind::IOError, "IO error during access sqlite database"),
+            rusqlite::Error::SqliteFailure(rusqlite::ffi::Error { code: rusqlite::ffi::ErrorCode::DatabaseBusy, .. }, _) |
+            rusqlite::Error::SqliteFailure(rusqlite::ffi::Error { code: rusqlite::ffi::ErrorCode::DatabaseLocked, .. }, _) =>
+                err.to_indy(IndyErrorKind::IOError, "Sqlite database is locked by other connection"),
             _ => err.to_indy(IndyErrorKind::InvalidState, "Unexpected sqlite error"),
         }
     }
@@ -586,4 +586,203 @@ pub mod wallet {
     pub type WalletFreeSearch = extern fn(storage_handle: StorageHandle,
                                           search_handle: i32) -> ErrorCode;
 
+    /// Get storage usage statistics
+    ///
+    /// #Params
+    /// storage_handle: opened storage handle (See open handler)
+    /// stats_json_p: pointer to store statistics json
+    /// {
+    ///   "types": {
+    ///     "<record type as base64 value>": {"records": int, "tags": int},
+    ///     ...
+    ///   },
+    ///   "storage_size": optional<int>, bytes occupied by the storage,
+    ///   "last_modified": optional<int>, seconds since unix epoch
+    /// }
+    /// stats_handle_p: pointer to store statistics handle (See free_storage_stats handler)
+    pub type WalletGetStorageStats = extern fn(storage_handle: StorageHandle,
+                                               stats_json_p: *mut *const c_char,
+                                               stats_handle_p: *mut IndyHandle) -> ErrorCode;
+
+    /// Free retrieved storage statistics (make statistics handle invalid)
+    ///
+    /// #Params
+    /// storage_handle: opened storage handle (See open handler)
+    /// stats_handle: retrieved statistics handle (See get_storage_stats handler)
+    pub type WalletFreeStorageStats = extern fn(storage_handle: StorageHandle,
+                                                stats_handle: IndyHandle) -> ErrorCode;
+
+    /// Wrap (encrypt) wallet master key with the key-encryption key (KEK) held by the provider
+    ///
+    /// #Params
+    /// key_id: identifier of the key-encryption key (the value of "key" field of wallet credentials)
+    /// key: raw wallet master key (pointer to buffer)
+    /// key_len: raw wallet master key (buffer size)
+    /// wrapped_key_p: pointer to store wrapped master key buffer
+    /// wrapped_key_len_p: pointer to store wrapped master key buffer size
+    /// key_handle_p: pointer to store wrapped master key handle (See free_key handler)
+    pub type WalletKekWrapKey = extern fn(key_id: *const c_char,
+                                          key: *const u8,
+                                          key_len: usize,
+                                          wrapped_key_p: *mut *const u8,
+                                          wrapped_key_len_p: *mut usize,
+                                          key_handle_p: *mut IndyHandle) -> ErrorCode;
+
+    /// Unwrap (decrypt) wallet master key with the key-encryption key (KEK) held by the provider
+    ///
+    /// #Params
+    /// key_id: identifier of the key-encryption key (the value of "key" field of wallet credentials)
+    /// wrapped_key: wrapped wallet master key (pointer to buffer)
+    /// wrapped_key_len: wrapped wallet master key (buffer size)
+    /// key_p: pointer to store raw master key buffer
+    /// key_len_p: pointer to store raw master key buffer size
+    /// key_handle_p: pointer to store raw master key handle (See free_key handler)
+    ///
+    /// Note WalletAccessFailed error should be returned if key can't be unwrapped with this key-encryption key
+    pub type WalletKekUnwrapKey = extern fn(key_id: *const c_char,
+                                            wrapped_key: *const u8,
+                                            wrapped_key_len: usize,
+                                            key_p: *mut *const u8,
+                                            key_len_p: *mut usize,
+                                            key_handle_p: *mut IndyHandle) -> ErrorCode;
+
+    /// Free key buffer returned by wrap_key or unwrap_key handlers (make key handle invalid)
+    ///
+    /// #Params
+    /// key_handle: key handle (See wrap_key and unwrap_key handlers)
+    pub type WalletKekFreeKey = extern fn(key_handle: IndyHandle) -> ErrorCode;
+
+    /// Notify about the change of wallet record (See indy_set_wallet_change_callback)
+    ///
+    /// #Params
+    /// wallet_handle: handle of the changed wallet
+    /// change_json: change description json (the same as audit log entry)
+    pub type WalletChangeCB = extern fn(wallet_handle: WalletHandle,
+                                        change_json: *const c_char);
+
+    /// Write the next chunk of the exported wallet (See indy_export_wallet_to_sink)
+    ///
+    /// #Params
+    /// sink_handle: sink handle passed to indy_export_wallet_to_sink
+    /// data: chunk data (pointer to buffer)
+    /// data_len: chunk data (buffer size)
+    pub type WalletExportWrite = extern fn(sink_handle: IndyHandle,
+                                           data: *const u8,
+                                           data_len: usize) -> ErrorCode;
+
+    /// Read the next chunk of the exported wallet (See indy_import_wallet_from_source)
+    ///
+    /// #Params
+    /// source_handle: source handle passed to indy_import_wallet_from_source
+    /// buf: buffer to store read data
+    /// buf_len: buffer size
+    /// read_len_p: pointer to store count of read bytes (0 means the end of the stream)
+    pub type WalletImportRead = extern fn(source_handle: IndyHandle,
+                                          buf: *mut u8,
+                                          buf_len: usize,
+                                          read_len_p: *mut usize) -> ErrorCode;
+
+}
+
+pub mod blob_storage {
+    use super::*;
+    use libc::c_char;
+
+    /// Open the blob storage writer (For example, connect to tails server)
+    ///
+    /// #Params
+    /// config: writer config json (the same as passed to indy_open_blob_storage_writer)
+    /// writer_handle_p: pointer to store opened writer handle
+    pub type BlobStorageWriterOpen = extern fn(config: *const c_char,
+                                               writer_handle_p: *mut IndyHandle) -> ErrorCode;
+
+    /// Create a new blob to write data into
+    ///
+    /// #Params
+    /// writer_handle: opened writer handle (See open handler)
+    /// blob_handle_p: pointer to store created blob handle
+    pub type BlobStorageWriterCreateBlob = extern fn(writer_handle: IndyHandle,
+                                                     blob_handle_p: *mut IndyHandle) -> ErrorCode;
+
+    /// Append the next chunk of data to the blob
+    ///
+    /// #Params
+    /// blob_handle: created blob handle (See create_blob handler)
+    /// data: chunk data (pointer to buffer)
+    /// data_len: chunk data (buffer size)
+    /// written_len_p: pointer to store count of written bytes
+    pub type BlobStorageWriterAppend = extern fn(blob_handle: IndyHandle,
+                                                 data: *const u8,
+                                                 data_len: usize,
+                                                 written_len_p: *mut usize) -> ErrorCode;
+
+    /// Complete the blob and return its location
+    ///
+    /// #Params
+    /// blob_handle: created blob handle (See create_blob handler)
+    /// hash: sha256 hash of the blob data (pointer to buffer)
+    /// hash_len: sha256 hash of the blob data (buffer size)
+    /// location_p: pointer to store blob location (For example, tails file URL)
+    ///
+    /// Note location buffer must remain valid until free_blob handler is called
+    pub type BlobStorageWriterFinalize = extern fn(blob_handle: IndyHandle,
+                                                   hash: *const u8,
+                                                   hash_len: usize,
+                                                   location_p: *mut *const c_char) -> ErrorCode;
+
+    /// Free blob resources (make blob handle invalid)
+    ///
+    /// #Params
+    /// blob_handle: created blob handle (See create_blob handler)
+    pub type BlobStorageWriterFreeBlob = extern fn(blob_handle: IndyHandle) -> ErrorCode;
+
+    /// Open the blob storage reader (For example, connect to tails server)
+    ///
+    /// #Params
+    /// config: reader config json (the same as passed to indy_open_blob_storage_reader)
+    /// reader_handle_p: pointer to store opened reader handle
+    pub type BlobStorageReaderOpen = extern fn(config: *const c_char,
+                                               reader_handle_p: *mut IndyHandle) -> ErrorCode;
+
+    /// Open the blob to read data from
+    ///
+    /// #Params
+    /// reader_handle: opened reader handle (See open handler)
+    /// hash: sha256 hash of the blob data (pointer to buffer)
+    /// hash_len: sha256 hash of the blob data (buffer size)
+    /// location: blob location (For example, tails file URL from revocation registry definition)
+    /// blob_handle_p: pointer to store opened blob handle
+    pub type BlobStorageReaderOpenBlob = extern fn(reader_handle: IndyHandle,
+                                                   hash: *const u8,
+                                                   hash_len: usize,
+                                                   location: *const c_char,
+                                                   blob_handle_p: *mut IndyHandle) -> ErrorCode;
+
+    /// Read the chunk of the blob data
+    ///
+    /// #Params
+    /// blob_handle: opened blob handle (See open_blob handler)
+    /// offset: position of the chunk in the blob
+    /// buf: buffer to store read data
+    /// buf_len: buffer size
+    /// read_len_p: pointer to store count of read bytes (less than buf_len means the end of the blob)
+    pub type BlobStorageReaderRead = extern fn(blob_handle: IndyHandle,
+                                               offset: usize,
+                                               buf: *mut u8,
+                                               buf_len: usize,
+                                               read_len_p: *mut usize) -> ErrorCode;
+
+    /// Check the blob data matches the hash it was opened with
+    ///
+    /// #Params
+    /// blob_handle: opened blob handle (See open_blob handler)
+    /// valid_p: pointer to store verification result
+    pub type BlobStorageReaderVerify = extern fn(blob_handle: IndyHandle,
+                                                 valid_p: *mut bool) -> ErrorCode;
+
+    /// Close the blob (make blob handle invalid)
+    ///
+    /// #Params
+    /// blob_handle: opened blob handle (See open_blob handler)
+    pub type BlobStorageReaderCloseBlob = extern fn(blob_handle: IndyHandle) -> ErrorCode;
 }
\ No newline at end of file
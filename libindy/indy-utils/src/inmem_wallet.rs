@@ -84,6 +84,10 @@ lazy_static! {
     static ref ACTIVE_METADATAS: Mutex<HashMap<i32, CString>> = Default::default();
 }
 
+lazy_static! {
+    static ref ACTIVE_STATS: Mutex<HashMap<i32, CString>> = Default::default();
+}
+
 lazy_static! {
     static ref ACTIVE_RECORDS: Mutex<HashMap<i32, InmemWalletRecord>> = Default::default();
 }
@@ -727,6 +731,72 @@ impl InmemWallet {
         ErrorCode::Success
     }
 
+    pub extern "C" fn get_storage_stats(xhandle: i32, stats_json_ptr: *mut *const c_char, stats_handle: *mut i32) -> ErrorCode {
+        let handles = INMEM_OPEN_WALLETS.lock().unwrap();
+
+        if !handles.contains_key(&xhandle) {
+            return ErrorCode::CommonInvalidState;
+        }
+
+        let wallet_context = handles.get(&xhandle).unwrap();
+
+        let wallets = INMEM_WALLETS.lock().unwrap();
+
+        if !wallets.contains_key(&wallet_context.id) {
+            return ErrorCode::CommonInvalidState;
+        }
+
+        let wallet = wallets.get(&wallet_context.id).unwrap();
+
+        let mut types: HashMap<String, (usize, usize)> = HashMap::new();
+        let mut storage_size = 0;
+
+        for record in wallet.records.values() {
+            let tags: HashMap<String, String> = serde_json::from_str(record.tags.to_str().unwrap()).unwrap();
+
+            let type_stats = types.entry(record.type_.to_str().unwrap().to_string()).or_insert((0, 0));
+            type_stats.0 += 1;
+            type_stats.1 += tags.len();
+
+            storage_size += record.value.len();
+        }
+
+        let types: HashMap<String, serde_json::Value> = types
+            .into_iter()
+            .map(|(type_, (records, tags))| (type_, json!({"records": records, "tags": tags})))
+            .collect();
+
+        let stats_json = CString::new(json!({"types": types, "storage_size": storage_size}).to_string()).unwrap();
+        let stats_json_pointer = stats_json.as_ptr();
+
+        let handle = sequence::get_next_id();
+
+        let mut stats = ACTIVE_STATS.lock().unwrap();
+        stats.insert(handle, stats_json);
+
+        unsafe { *stats_json_ptr = stats_json_pointer; }
+        unsafe { *stats_handle = handle };
+
+        ErrorCode::Success
+    }
+
+    pub extern "C" fn free_storage_stats(xhandle: i32, stats_handle: i32) -> ErrorCode {
+        let handles = INMEM_OPEN_WALLETS.lock().unwrap();
+
+        if !handles.contains_key(&xhandle) {
+            return ErrorCode::CommonInvalidState;
+        }
+
+        let mut handles = ACTIVE_STATS.lock().unwrap();
+
+        if !handles.contains_key(&stats_handle) {
+            return ErrorCode::CommonInvalidState;
+        }
+        handles.remove(&stats_handle);
+
+        ErrorCode::Success
+    }
+
     pub extern "C" fn close(xhandle: i32) -> ErrorCode {
         let mut handles = INMEM_OPEN_WALLETS.lock().unwrap();
 
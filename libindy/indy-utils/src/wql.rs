@@ -18,6 +18,12 @@ pub enum Query {
     Lte(String, String),
     Like(String, String),
     In(String, Vec<String>),
+    // Typed numeric comparisons. Values are kept in the JSON number representation.
+    NumGt(String, String),
+    NumGte(String, String),
+    NumLt(String, String),
+    NumLte(String, String),
+    Exist(String),
 }
 
 impl Serialize for Query {
@@ -131,6 +137,11 @@ impl Query {
             Query::Lte(ref tag_name, ref tag_value) => json!({tag_name: {"$lte": tag_value}}),
             Query::Like(ref tag_name, ref tag_value) => json!({tag_name: {"$like": tag_value}}),
             Query::In(ref tag_name, ref tag_values) => json!({tag_name: {"$in": tag_values}}),
+            Query::NumGt(ref tag_name, ref tag_value) => json!({tag_name: {"$gt": _number_value(tag_value)}}),
+            Query::NumGte(ref tag_name, ref tag_value) => json!({tag_name: {"$gte": _number_value(tag_value)}}),
+            Query::NumLt(ref tag_name, ref tag_value) => json!({tag_name: {"$lt": _number_value(tag_value)}}),
+            Query::NumLte(ref tag_name, ref tag_value) => json!({tag_name: {"$lte": _number_value(tag_value)}}),
+            Query::Exist(ref tag_name) => json!({tag_name: {"$exist": true}}),
             Query::And(ref operators) => {
                 if !operators.is_empty() {
                     json!({
@@ -154,6 +165,12 @@ impl Query {
     }
 }
 
+fn _number_value(value: &str) -> serde_json::Value {
+    serde_json::from_str::<serde_json::Number>(value)
+        .map(serde_json::Value::Number)
+        .unwrap_or_else(|_| serde_json::Value::String(value.to_string()))
+}
+
 impl Default for Query {
     fn default() -> Self {
         Query::And(Vec::new())
@@ -239,13 +256,20 @@ fn parse_single_operator(operator_name: String, key: String, value: serde_json::
         ("$neq", serde_json::Value::String(value_)) => Ok(Query::Neq(key, value_)),
         ("$neq", _) => Err("$neq must be used with string"),
         ("$gt", serde_json::Value::String(value_)) => Ok(Query::Gt(key, value_)),
-        ("$gt", _) => Err("$gt must be used with string"),
+        ("$gt", serde_json::Value::Number(value_)) => Ok(Query::NumGt(key, value_.to_string())),
+        ("$gt", _) => Err("$gt must be used with string or number"),
         ("$gte", serde_json::Value::String(value_)) => Ok(Query::Gte(key, value_)),
-        ("$gte", _) => Err("$gte must be used with string"),
+        ("$gte", serde_json::Value::Number(value_)) => Ok(Query::NumGte(key, value_.to_string())),
+        ("$gte", _) => Err("$gte must be used with string or number"),
         ("$lt", serde_json::Value::String(value_)) => Ok(Query::Lt(key, value_)),
-        ("$lt", _) => Err("$lt must be used with string"),
+        ("$lt", serde_json::Value::Number(value_)) => Ok(Query::NumLt(key, value_.to_string())),
+        ("$lt", _) => Err("$lt must be used with string or number"),
         ("$lte", serde_json::Value::String(value_)) => Ok(Query::Lte(key, value_)),
-        ("$lte", _) => Err("$lte must be used with string"),
+        ("$lte", serde_json::Value::Number(value_)) => Ok(Query::NumLte(key, value_.to_string())),
+        ("$lte", _) => Err("$lte must be used with string or number"),
+        ("$exist", serde_json::Value::Bool(true)) => Ok(Query::Exist(key)),
+        ("$exist", serde_json::Value::Bool(false)) => Ok(Query::Not(Box::new(Query::Exist(key)))),
+        ("$exist", _) => Err("$exist must be used with boolean"),
         ("$like", serde_json::Value::String(value_)) => Ok(Query::Like(key, value_)),
         ("$like", _) => Err("$like must be used with string"),
         ("$in", serde_json::Value::Array(values)) => {
@@ -419,6 +443,69 @@ mod tests {
         assert_eq!(query, expected);
     }
 
+    #[test]
+    fn test_simple_operator_gte_number_parse() {
+        let name1 = _random_string(10);
+
+        let json = format!(r#"{{"{}":{{"$gte":18}}}}"#, name1);
+
+        let query: Query = ::serde_json::from_str(&json).unwrap();
+
+        let expected = Query::NumGte(name1, "18".to_string());
+
+        assert_eq!(query, expected);
+    }
+
+    #[test]
+    fn test_simple_operator_lt_float_number_parse() {
+        let name1 = _random_string(10);
+
+        let json = format!(r#"{{"{}":{{"$lt":1.5}}}}"#, name1);
+
+        let query: Query = ::serde_json::from_str(&json).unwrap();
+
+        let expected = Query::NumLt(name1, "1.5".to_string());
+
+        assert_eq!(query, expected);
+    }
+
+    #[test]
+    fn test_simple_operator_exist_parse() {
+        let name1 = _random_string(10);
+
+        let json = format!(r#"{{"{}":{{"$exist":true}}}}"#, name1);
+
+        let query: Query = ::serde_json::from_str(&json).unwrap();
+
+        let expected = Query::Exist(name1);
+
+        assert_eq!(query, expected);
+    }
+
+    #[test]
+    fn test_simple_operator_not_exist_parse() {
+        let name1 = _random_string(10);
+
+        let json = format!(r#"{{"{}":{{"$exist":false}}}}"#, name1);
+
+        let query: Query = ::serde_json::from_str(&json).unwrap();
+
+        let expected = Query::Not(Box::new(Query::Exist(name1)));
+
+        assert_eq!(query, expected);
+    }
+
+    #[test]
+    fn test_simple_operator_exist_parse_fails_for_not_boolean() {
+        let name1 = _random_string(10);
+
+        let json = format!(r#"{{"{}":{{"$exist":"true"}}}}"#, name1);
+
+        let res = ::serde_json::from_str::<Query>(&json);
+
+        assert!(res.is_err());
+    }
+
     #[test]
     fn test_simple_operator_in_plaintext_parse() {
         let name1 = _random_string(10);
@@ -1810,6 +1897,32 @@ mod tests {
         assert_eq!(json, expected);
     }
 
+    #[test]
+    fn test_simple_operator_gte_number_to_string() {
+        let name1 = _random_string(10);
+
+        let query = Query::NumGte(name1.clone(), "18".to_string());
+
+        let json = ::serde_json::to_string(&query).unwrap();
+
+        let expected = format!(r#"{{"{}":{{"$gte":18}}}}"#, name1);
+
+        assert_eq!(json, expected);
+    }
+
+    #[test]
+    fn test_simple_operator_exist_to_string() {
+        let name1 = _random_string(10);
+
+        let query = Query::Exist(name1.clone());
+
+        let json = ::serde_json::to_string(&query).unwrap();
+
+        let expected = format!(r#"{{"{}":{{"$exist":true}}}}"#, name1);
+
+        assert_eq!(json, expected);
+    }
+
     #[test]
     fn test_simple_operator_in_to_string() {
         let name1 = _random_string(10);
@@ -0,0 +1,100 @@
+use std::collections::HashMap;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use rust_base58::ToBase58;
+use serde_json::{Map, Value};
+
+use indy_api_types::errors::prelude::*;
+use indy_utils::crypto::{hash::hash, randombytes};
+
+// Audit log entries are stored as regular encrypted wallet records of this type.
+pub const AUDIT_LOG_TYPE: &str = "Indy::AuditLog";
+
+const TIMESTAMP_TAG: &str = "~timestamp";
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum AuditLogOperation {
+    Add,
+    Update,
+    UpdateTags,
+    Delete,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct AuditLogEntry {
+    #[serde(rename = "type")]
+    pub type_: String,
+    pub name_hash: String,
+    pub operation: AuditLogOperation,
+    pub timestamp: u64,
+}
+
+impl AuditLogEntry {
+    pub fn new(type_: &str, name: &str, operation: AuditLogOperation) -> IndyResult<AuditLogEntry> {
+        Ok(AuditLogEntry {
+            type_: type_.to_string(),
+            name_hash: hash(name.as_bytes())?.to_base58(),
+            operation,
+            timestamp: SystemTime::now().duration_since(UNIX_EPOCH).unwrap().as_secs(),
+        })
+    }
+
+    // Timestamp goes first to keep names unique and ordered in time.
+    pub(crate) fn record_name(&self) -> String {
+        format!("{}:{}", _format_timestamp(self.timestamp), randombytes::randombytes(16).to_base58())
+    }
+
+    // Timestamp is stored in unencrypted tag to allow range queries.
+    pub(crate) fn record_tags(&self) -> HashMap<String, String> {
+        let mut tags = HashMap::new();
+        tags.insert(TIMESTAMP_TAG.to_string(), _format_timestamp(self.timestamp));
+        tags
+    }
+}
+
+pub(crate) fn build_query(from: Option<u64>, to: Option<u64>) -> String {
+    let mut range = Map::new();
+
+    if let Some(from) = from {
+        range.insert("$gte".to_string(), Value::String(_format_timestamp(from)));
+    }
+
+    if let Some(to) = to {
+        range.insert("$lte".to_string(), Value::String(_format_timestamp(to)));
+    }
+
+    let mut query = Map::new();
+
+    if !range.is_empty() {
+        query.insert(TIMESTAMP_TAG.to_string(), Value::Object(range));
+    }
+
+    Value::Object(query).to_string()
+}
+
+// Zero padded timestamps can be compared as strings.
+fn _format_timestamp(timestamp: u64) -> String {
+    format!("{:020}", timestamp)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn audit_log_entry_new_works() {
+        let entry = AuditLogEntry::new("type", "name", AuditLogOperation::Add).unwrap();
+
+        assert_eq!("type", entry.type_);
+        assert_eq!(hash(b"name").unwrap().to_base58(), entry.name_hash);
+        assert!(entry.record_name().starts_with(&_format_timestamp(entry.timestamp)));
+    }
+
+    #[test]
+    fn build_query_works() {
+        assert_eq!(json!({}).to_string(), build_query(None, None));
+        assert_eq!(json!({"~timestamp": {"$gte": "00000000000000000010"}}).to_string(), build_query(Some(10), None));
+        assert_eq!(json!({"~timestamp": {"$gte": "00000000000000000010", "$lte": "00000000000000000020"}}).to_string(), build_query(Some(10), Some(20)));
+    }
+}
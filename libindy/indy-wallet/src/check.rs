@@ -0,0 +1,252 @@
+use indy_api_types::errors::prelude::*;
+use indy_utils::crypto::chacha20poly1305_ietf;
+
+use crate::encryption::{decrypt_merged, decrypt_storage_record, encrypt_as_searchable};
+use crate::storage::{StorageRecord, WalletStorage};
+use crate::wallet::Keys;
+
+// Corrupt records are moved under this type instead of being deleted.
+pub const QUARANTINE_TYPE: &str = "Indy::Quarantine";
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CorruptedItem {
+    #[serde(rename = "type")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub type_: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub id: Option<String>,
+    pub reason: String,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct WalletCheckReport {
+    pub total_items: usize,
+    pub corrupted_items: Vec<CorruptedItem>,
+    pub quarantined_items: usize,
+    pub orphaned_tags: usize,
+    pub integrity_errors: Vec<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub storage_error: Option<String>,
+}
+
+pub(crate) fn check_storage(storage: &dyn WalletStorage, keys: &Keys, repair: bool) -> IndyResult<WalletCheckReport> {
+    trace!("check_storage >>> repair: {:?}", repair);
+
+    let consistency = storage.check_consistency(repair)?;
+
+    let mut report = WalletCheckReport {
+        orphaned_tags: consistency.orphaned_tags,
+        integrity_errors: consistency.integrity_errors,
+        ..WalletCheckReport::default()
+    };
+
+    let quarantine_type = encrypt_as_searchable(QUARANTINE_TYPE.as_bytes(), &keys.type_key, &keys.item_hmac_key);
+
+    let mut corrupted: Vec<StorageRecord> = Vec::new();
+
+    {
+        let mut iterator = storage.get_all()?;
+
+        loop {
+            let record = match iterator.next() {
+                Ok(Some(record)) => record,
+                Ok(None) => break,
+                Err(err) => {
+                    // The rest of the storage can't be walked, so just report what was found so far.
+                    report.storage_error = Some(err.to_string());
+                    break;
+                }
+            };
+
+            report.total_items += 1;
+
+            if record.type_.as_ref() == Some(&quarantine_type) {
+                continue;
+            }
+
+            if let Err(err) = decrypt_storage_record(&record, keys) {
+                report.corrupted_items.push(CorruptedItem {
+                    type_: _decrypt_string(&record.type_, &keys.type_key),
+                    id: _decrypt_string(&Some(record.id.clone()), &keys.name_key),
+                    reason: err.to_string(),
+                });
+
+                corrupted.push(record);
+            }
+        }
+    }
+
+    if repair {
+        let mut quarantined_items = 0;
+
+        // Repair is done as a whole, so a failure never leaves a record both quarantined and live.
+        storage.atomic(&mut || {
+            quarantined_items = 0;
+
+            for record in corrupted.iter() {
+                let (type_, value) = match (record.type_.as_ref(), record.value.as_ref()) {
+                    (Some(type_), Some(value)) => (type_, value),
+                    _ => continue,
+                };
+
+                // Records of different types may share the id, so the type is a part of the quarantine id.
+                let quarantine_id = [type_.as_slice(), record.id.as_slice()].concat();
+
+                storage.add(&quarantine_type, &quarantine_id, value, record.tags.as_ref().map(Vec::as_slice).unwrap_or(&[]))?;
+                storage.delete(type_, &record.id)?;
+
+                quarantined_items += 1;
+            }
+
+            Ok(())
+        })?;
+
+        report.quarantined_items = quarantined_items;
+    }
+
+    trace!("check_storage <<< report: {:?}", report);
+
+    Ok(report)
+}
+
+fn _decrypt_string(data: &Option<Vec<u8>>, key: &chacha20poly1305_ietf::Key) -> Option<String> {
+    data.as_ref()
+        .and_then(|data| decrypt_merged(data, key).ok())
+        .and_then(|data| String::from_utf8(data).ok())
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use indy_utils::test;
+
+    use crate::encryption::encrypt_as_not_searchable;
+    use crate::storage::WalletStorageType;
+    use crate::storage::default::SQLiteStorageType;
+    use crate::wallet::EncryptedValue;
+
+    use super::*;
+
+    #[test]
+    fn check_storage_works_for_healthy_storage() {
+        _cleanup("check_storage_works_for_healthy_storage");
+        let (storage, keys) = _storage("check_storage_works_for_healthy_storage");
+
+        _add_record(&*storage, &keys, "type", "name1", "value1");
+        _add_record(&*storage, &keys, "type", "name2", "value2");
+
+        let report = check_storage(&*storage, &keys, false).unwrap();
+
+        assert_eq!(2, report.total_items);
+        assert!(report.corrupted_items.is_empty());
+        assert!(report.integrity_errors.is_empty());
+        assert_eq!(0, report.orphaned_tags);
+        assert_eq!(None, report.storage_error);
+
+        _cleanup("check_storage_works_for_healthy_storage");
+    }
+
+    #[test]
+    fn check_storage_works_for_corrupted_value() {
+        _cleanup("check_storage_works_for_corrupted_value");
+        let (storage, keys) = _storage("check_storage_works_for_corrupted_value");
+
+        _add_record(&*storage, &keys, "type", "name1", "value1");
+        _add_corrupted_record(&*storage, &keys, "type", "name2");
+
+        let report = check_storage(&*storage, &keys, false).unwrap();
+
+        assert_eq!(2, report.total_items);
+        assert_eq!(1, report.corrupted_items.len());
+        assert_eq!(Some("type".to_string()), report.corrupted_items[0].type_);
+        assert_eq!(Some("name2".to_string()), report.corrupted_items[0].id);
+        assert_eq!(0, report.quarantined_items);
+
+        _cleanup("check_storage_works_for_corrupted_value");
+    }
+
+    #[test]
+    fn check_storage_works_for_repair() {
+        _cleanup("check_storage_works_for_repair");
+        let (storage, keys) = _storage("check_storage_works_for_repair");
+
+        _add_record(&*storage, &keys, "type", "name1", "value1");
+        _add_corrupted_record(&*storage, &keys, "type", "name2");
+
+        let report = check_storage(&*storage, &keys, true).unwrap();
+        assert_eq!(1, report.corrupted_items.len());
+        assert_eq!(1, report.quarantined_items);
+
+        let etype = encrypt_as_searchable(b"type", &keys.type_key, &keys.item_hmac_key);
+        let ename = encrypt_as_searchable(b"name2", &keys.name_key, &keys.item_hmac_key);
+        let res = storage.get(&etype, &ename, r##"{"retrieveType": false, "retrieveValue": true, "retrieveTags": false}"##);
+        assert_kind!(IndyErrorKind::WalletItemNotFound, res);
+
+        // Quarantined records are not reported twice.
+        let report = check_storage(&*storage, &keys, true).unwrap();
+        assert_eq!(2, report.total_items);
+        assert!(report.corrupted_items.is_empty());
+        assert_eq!(0, report.quarantined_items);
+
+        _cleanup("check_storage_works_for_repair");
+    }
+
+    #[test]
+    fn check_storage_works_for_repair_same_ids() {
+        _cleanup("check_storage_works_for_repair_same_ids");
+        let (storage, keys) = _storage("check_storage_works_for_repair_same_ids");
+
+        _add_corrupted_record(&*storage, &keys, "type1", "name");
+        _add_corrupted_record(&*storage, &keys, "type2", "name");
+
+        let report = check_storage(&*storage, &keys, true).unwrap();
+        assert_eq!(2, report.corrupted_items.len());
+        assert_eq!(2, report.quarantined_items);
+
+        let ename = encrypt_as_searchable(b"name", &keys.name_key, &keys.item_hmac_key);
+
+        for type_ in &["type1", "type2"] {
+            let etype = encrypt_as_searchable(type_.as_bytes(), &keys.type_key, &keys.item_hmac_key);
+            let res = storage.get(&etype, &ename, r##"{"retrieveType": false, "retrieveValue": true, "retrieveTags": false}"##);
+            assert_kind!(IndyErrorKind::WalletItemNotFound, res);
+        }
+
+        _cleanup("check_storage_works_for_repair_same_ids");
+    }
+
+    fn _storage(name: &str) -> (Box<dyn WalletStorage>, Keys) {
+        let storage_type = SQLiteStorageType::new();
+        storage_type.create_storage(name, None, None, &_metadata()).unwrap();
+        (storage_type.open_storage(name, None, None).unwrap(), Keys::new())
+    }
+
+    fn _add_record(storage: &dyn WalletStorage, keys: &Keys, type_: &str, name: &str, value: &str) {
+        let etype = encrypt_as_searchable(type_.as_bytes(), &keys.type_key, &keys.item_hmac_key);
+        let ename = encrypt_as_searchable(name.as_bytes(), &keys.name_key, &keys.item_hmac_key);
+        let evalue = EncryptedValue::encrypt(value, &keys.value_key);
+        let etags = crate::encryption::encrypt_tags(&HashMap::new(), &keys.tag_name_key, &keys.tag_value_key, &keys.tags_hmac_key);
+        storage.add(&etype, &ename, &evalue, &etags).unwrap();
+    }
+
+    fn _add_corrupted_record(storage: &dyn WalletStorage, keys: &Keys, type_: &str, name: &str) {
+        let etype = encrypt_as_searchable(type_.as_bytes(), &keys.type_key, &keys.item_hmac_key);
+        let ename = encrypt_as_searchable(name.as_bytes(), &keys.name_key, &keys.item_hmac_key);
+
+        // Value key is encrypted with a wrong key, so value can't be decrypted.
+        let evalue = EncryptedValue::new(
+            encrypt_as_not_searchable(b"value", &keys.value_key),
+            encrypt_as_not_searchable(b"key", &keys.value_key),
+        );
+
+        storage.add(&etype, &ename, &evalue, &[]).unwrap();
+    }
+
+    fn _metadata() -> Vec<u8> {
+        vec![1, 2, 3, 4, 5, 6, 7, 8]
+    }
+
+    fn _cleanup(name: &str) {
+        test::cleanup_wallet(name)
+    }
+}
@@ -8,6 +8,9 @@ use indy_utils::crypto::{chacha20poly1305_ietf, hmacsha256, pwhash_argon2i13};
 use super::{Keys, WalletRecord, Metadata};
 use super::storage::{StorageRecord, Tag, TagName};
 use rust_base58::FromBase58;
+use zeroize::Zeroize;
+
+use super::kek;
 
 #[cfg(test)]
 pub(super) fn gen_master_key_salt() -> IndyResult<pwhash_argon2i13::Salt> {
@@ -27,6 +30,8 @@ pub enum KeyDerivationData {
     Raw(String),
     Argon2iMod(String, pwhash_argon2i13::Salt),
     Argon2iInt(String, pwhash_argon2i13::Salt),
+    // KEK provider type, key id and wrapped master key (absent for new master key)
+    Kek(String, String, Option<Vec<u8>>),
 }
 
 impl KeyDerivationData {
@@ -39,7 +44,9 @@ impl KeyDerivationData {
             KeyDerivationMethod::ARGON2I_MOD =>
                 KeyDerivationData::Argon2iMod(passphrase, salt),
             KeyDerivationMethod::RAW =>
-                KeyDerivationData::Raw(passphrase)
+                KeyDerivationData::Raw(passphrase),
+            KeyDerivationMethod::KEK(ref provider) =>
+                KeyDerivationData::Kek(provider.to_owned(), passphrase, None),
         }
     }
 
@@ -58,6 +65,9 @@ impl KeyDerivationData {
                 let master_key_salt = master_key_salt_from_slice(&metadata.master_key_salt)?;
                 KeyDerivationData::Argon2iMod(passphrase, master_key_salt)
             }
+            (KeyDerivationMethod::KEK(provider), &Metadata::MetadataKek(ref metadata)) => {
+                KeyDerivationData::Kek(provider.to_owned(), passphrase, Some(metadata.wrapped_master_key.clone()))
+            }
             _ => return Err(err_msg(IndyErrorKind::WalletAccessFailed, "Invalid combination of KeyDerivationMethod and Metadata"))
         };
 
@@ -69,6 +79,8 @@ impl KeyDerivationData {
             KeyDerivationData::Raw(passphrase) => _raw_master_key(passphrase),
             KeyDerivationData::Argon2iInt(passphrase, salt) => _derive_master_key(passphrase, &salt, &KeyDerivationMethod::ARGON2I_INT),
             KeyDerivationData::Argon2iMod(passphrase, salt) => _derive_master_key(passphrase, &salt, &KeyDerivationMethod::ARGON2I_MOD),
+            KeyDerivationData::Kek(_, _, None) => Ok(chacha20poly1305_ietf::gen_key()),
+            KeyDerivationData::Kek(provider, key_id, Some(wrapped_key)) => _unwrap_master_key(provider, key_id, wrapped_key),
         }
     }
 }
@@ -85,6 +97,16 @@ fn _raw_master_key(passphrase: &str) -> IndyResult<chacha20poly1305_ietf::Key> {
         .map_err(|err| err.extend("Invalid mastery key"))
 }
 
+fn _unwrap_master_key(provider: &str, key_id: &str, wrapped_key: &[u8]) -> IndyResult<chacha20poly1305_ietf::Key> {
+    let mut bytes = kek::get_kek_provider(provider)?.unwrap_key(key_id, wrapped_key)?;
+
+    let res = chacha20poly1305_ietf::Key::from_slice(&bytes)
+        .map_err(|err| err.map(IndyErrorKind::WalletAccessFailed, "KEK provider returned invalid master key"));
+
+    bytes.zeroize();
+    res
+}
+
 pub(super) fn encrypt_tag_names(tag_names: &[&str], tag_name_key: &chacha20poly1305_ietf::Key, tags_hmac_key: &hmacsha256::Key) -> Vec<TagName> {
     tag_names
         .iter()
@@ -147,6 +169,10 @@ pub(super) fn decrypt(data: &[u8], key: &chacha20poly1305_ietf::Key, nonce: &cha
 }
 
 pub(super) fn decrypt_merged(joined_data: &[u8], key: &chacha20poly1305_ietf::Key) -> IndyResult<Vec<u8>> {
+    if joined_data.len() < chacha20poly1305_ietf::NONCEBYTES {
+        return Err(err_msg(IndyErrorKind::InvalidStructure, "Encrypted data is too short"));
+    }
+
     let nonce = chacha20poly1305_ietf::Nonce::from_slice(&joined_data[..chacha20poly1305_ietf::NONCEBYTES]).unwrap(); // We can safety unwrap here
     let data = &joined_data[chacha20poly1305_ietf::NONCEBYTES..];
     let res = decrypt(data, key, &nonce)?;
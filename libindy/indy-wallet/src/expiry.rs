@@ -0,0 +1,124 @@
+use std::collections::HashMap;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use indy_api_types::errors::prelude::*;
+use indy_utils::wql::Query;
+
+// Record TTL in seconds can be passed on record addition as this reserved tag.
+pub const TTL_TAG: &str = "~ttl";
+
+// TTL is stored as expiration time in this reserved unencrypted tag.
+pub const EXPIRES_AT_TAG: &str = "~Indy::ExpiresAt";
+
+// Internal Indy records can't have TTL, so expiration time isn't checked for them.
+const NON_EXPIRABLE_TYPE_PREFIX: &str = "Indy::";
+
+pub(crate) fn now() -> String {
+    _format_timestamp(SystemTime::now().duration_since(UNIX_EPOCH).unwrap().as_secs())
+}
+
+pub(crate) fn is_expirable(type_: &str) -> bool {
+    !type_.starts_with(NON_EXPIRABLE_TYPE_PREFIX)
+}
+
+// Expiration time tag can be set only through TTL tag.
+pub(crate) fn check_not_reserved<'a, I>(tag_names: I) -> IndyResult<()> where I: IntoIterator<Item=&'a str> {
+    if tag_names.into_iter().any(|tag_name| tag_name == EXPIRES_AT_TAG) {
+        return Err(err_msg(IndyErrorKind::InvalidStructure, format!("Tag {} is reserved and can't be modified", EXPIRES_AT_TAG)));
+    }
+    Ok(())
+}
+
+// Replaces TTL tag with expiration time tag.
+pub(crate) fn apply_ttl(type_: &str, tags: &HashMap<String, String>) -> IndyResult<HashMap<String, String>> {
+    let mut tags = tags.clone();
+
+    if let Some(ttl) = tags.remove(TTL_TAG) {
+        if !is_expirable(type_) {
+            return Err(err_msg(IndyErrorKind::InvalidStructure, format!("Records of type {} can't have {} tag", type_, TTL_TAG)));
+        }
+
+        let ttl = ttl.parse::<u64>()
+            .to_indy(IndyErrorKind::InvalidStructure, format!("Invalid {} tag: {:?}, expected number of seconds", TTL_TAG, ttl))?;
+
+        let expires_at = SystemTime::now().duration_since(UNIX_EPOCH).unwrap().as_secs() + ttl;
+        tags.insert(EXPIRES_AT_TAG.to_string(), _format_timestamp(expires_at));
+    }
+
+    Ok(tags)
+}
+
+pub(crate) fn is_expired(expires_at: Option<&str>, now: &str) -> bool {
+    expires_at
+        .map(|expires_at| expires_at <= now)
+        .unwrap_or(false)
+}
+
+pub(crate) fn exclude_expired(query: Query, now: &str) -> Query {
+    Query::And(vec![
+        query,
+        Query::Or(vec![
+            Query::Not(Box::new(Query::Exist(EXPIRES_AT_TAG.to_string()))),
+            Query::Gt(EXPIRES_AT_TAG.to_string(), now.to_string()),
+        ]),
+    ])
+}
+
+// Zero padded timestamps can be compared as strings.
+fn _format_timestamp(timestamp: u64) -> String {
+    format!("{:020}", timestamp)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn apply_ttl_works() {
+        let mut tags = HashMap::new();
+        tags.insert("tag".to_string(), "value".to_string());
+        tags.insert(TTL_TAG.to_string(), "10".to_string());
+
+        let tags = apply_ttl("type", &tags).unwrap();
+
+        assert_eq!(2, tags.len());
+        assert!(!tags.contains_key(TTL_TAG));
+        assert!(tags[EXPIRES_AT_TAG].as_str() > now().as_str());
+        assert!(!is_expired(Some(tags[EXPIRES_AT_TAG].as_str()), &now()));
+    }
+
+    #[test]
+    fn apply_ttl_works_for_invalid_ttl() {
+        let mut tags = HashMap::new();
+        tags.insert(TTL_TAG.to_string(), "ten".to_string());
+
+        let res = apply_ttl("type", &tags);
+        assert_kind!(IndyErrorKind::InvalidStructure, res);
+    }
+
+    #[test]
+    fn apply_ttl_works_for_indy_type() {
+        let mut tags = HashMap::new();
+        tags.insert(TTL_TAG.to_string(), "10".to_string());
+
+        let res = apply_ttl("Indy::Did", &tags);
+        assert_kind!(IndyErrorKind::InvalidStructure, res);
+    }
+
+    #[test]
+    fn check_not_reserved_works() {
+        check_not_reserved(vec!["tag", TTL_TAG]).unwrap();
+
+        let res = check_not_reserved(vec!["tag", EXPIRES_AT_TAG]);
+        assert_kind!(IndyErrorKind::InvalidStructure, res);
+    }
+
+    #[test]
+    fn is_expired_works() {
+        assert!(!is_expired(None, &now()));
+
+        let expires_at = _format_timestamp(10);
+        assert!(is_expired(Some(expires_at.as_str()), &now()));
+        assert!(!is_expired(Some(expires_at.as_str()), &_format_timestamp(9)));
+    }
+}
@@ -1,21 +1,41 @@
+use std::collections::{HashMap, HashSet};
 use std::io;
 use std::io::{BufReader, BufWriter, Read, Write};
 use std::time::{SystemTime, UNIX_EPOCH};
 
 use byteorder::{LittleEndian, ReadBytesExt, WriteBytesExt};
 use rmp_serde;
+use rust_base58::ToBase58;
 
-use indy_api_types::domain::wallet::Record;
+use indy_api_types::domain::wallet::{ExportFilter, Record};
 use indy_api_types::domain::wallet::KeyDerivationMethod;
 use indy_api_types::errors::prelude::*;
 use crate::encryption::KeyDerivationData;
-use indy_utils::crypto::{chacha20poly1305_ietf, pwhash_argon2i13};
+use indy_utils::crypto::{chacha20poly1305_ietf, pwhash_argon2i13, randombytes};
 use indy_utils::crypto::hash::{hash, HASHBYTES};
 
-use super::{Wallet, WalletRecord};
+use super::{RecordOptions, SearchOptions, Wallet, WalletRecord};
 
 const CHUNK_SIZE: usize = 1024;
 
+// Version of the export that contains all records.
+pub const FULL_EXPORT_VERSION: u32 = 0;
+
+// Version of the export that contains only changes since the previous checkpoint.
+pub const INCREMENTAL_EXPORT_VERSION: u32 = 1;
+
+// Id of the last checkpoint written by incremental export.
+const EXPORT_CHECKPOINT_TYPE: &str = "Indy::ExportCheckpoint";
+
+// Id of the last checkpoint applied by import.
+const IMPORT_CHECKPOINT_TYPE: &str = "Indy::ImportCheckpoint";
+
+// Records changed since the last incremental export. Names are json arrays of type and name of changed record.
+// Journal is maintained only after the first incremental export, see Wallet::enable_export_journal.
+pub(super) const EXPORT_JOURNAL_TYPE: &str = "Indy::ExportJournal";
+
+const CHECKPOINT_ID: &str = "checkpoint";
+
 #[derive(Debug, Serialize, Deserialize)]
 pub enum EncryptionMethod {
     // **ChaCha20-Poly1305-IETF** cypher in blocks per chunk_size bytes
@@ -55,6 +75,25 @@ pub struct Header {
     pub version: u32
 }
 
+// Written right after header hash in the incremental exports.
+// Import checks that `previous` matches the last applied checkpoint so increments
+// can be applied only in the same order as they were exported.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct Checkpoint {
+    pub id: String,
+    pub previous: Option<String>,
+}
+
+// Records of the incremental export.
+#[derive(Debug, Serialize, Deserialize)]
+pub enum RecordOperation {
+    Upsert(Record),
+    Delete {
+        type_: String,
+        id: String,
+    },
+}
+
 // Note that we use externally tagged enum serialization and header will be represented as:
 //
 // {
@@ -69,7 +108,7 @@ pub struct Header {
 //   "version": ..,
 // }
 
-pub(super) fn export_continue(wallet: &Wallet, writer: &mut dyn Write, version: u32, key: chacha20poly1305_ietf::Key, key_data: &KeyDerivationData) -> IndyResult<()> {
+pub(super) fn export_continue(wallet: &Wallet, writer: &mut dyn Write, version: u32, filter: Option<&[ExportFilter]>, key: chacha20poly1305_ietf::Key, key_data: &KeyDerivationData) -> IndyResult<()> {
     let nonce = chacha20poly1305_ietf::gen_nonce();
     let chunk_size = CHUNK_SIZE;
 
@@ -87,7 +126,8 @@ pub(super) fn export_continue(wallet: &Wallet, writer: &mut dyn Write, version:
         KeyDerivationData::Raw(_) => EncryptionMethod::ChaCha20Poly1305IETFRaw {
             nonce: nonce[..].to_vec(),
             chunk_size,
-        }
+        },
+        KeyDerivationData::Kek(..) => return Err(err_msg(IndyErrorKind::InvalidStructure, "KEK key derivation method is not supported for wallet export"))
     };
 
     let header = Header {
@@ -112,21 +152,42 @@ pub(super) fn export_continue(wallet: &Wallet, writer: &mut dyn Write, version:
 
     writer.write_all(&hash(&header)?)?;
 
+    if version == INCREMENTAL_EXPORT_VERSION {
+        let (checkpoint, journal) = _export_increment(wallet, &mut writer)?;
+
+        writer.write_u32::<LittleEndian>(0)?; // END message
+        writer.flush()?;
+
+        // Checkpoint is moved only when the increment is completely written.
+        // Journal entries left after failure are exported again with the next increment.
+        _set_checkpoint_record(wallet, EXPORT_CHECKPOINT_TYPE, &checkpoint)?;
+        wallet.enable_export_journal();
+
+        for name in journal {
+            wallet.delete(EXPORT_JOURNAL_TYPE, &name)?;
+        }
+
+        return Ok(());
+    }
+
+    if let Some(filter) = filter {
+        _export_filtered(wallet, &mut writer, filter)?;
+
+        writer.write_u32::<LittleEndian>(0)?; // END message
+        writer.flush()?;
+        return Ok(());
+    }
+
     let mut records = wallet.get_all()?;
 
-    while let Some(WalletRecord { type_, id, value, tags }) = records.next()? {
-        let record = Record {
-            type_: type_.ok_or_else(|| err_msg(IndyErrorKind::InvalidState, "No type fetched for exported record"))?,
-            id,
-            value: value.ok_or_else(|| err_msg(IndyErrorKind::InvalidState, "No value fetched for exported record"))?,
-            tags: tags.ok_or_else(|| err_msg(IndyErrorKind::InvalidState, "No tags fetched for exported record"))?,
-        };
+    while let Some(record) = records.next()? {
+        let record = _to_export_record(record)?;
 
-        let record = rmp_serde::to_vec(&record)
-            .to_indy(IndyErrorKind::InvalidState, "Can't serialize record")?;
+        if is_internal_type(&record.type_) {
+            continue;
+        }
 
-        writer.write_u32::<LittleEndian>(record.len() as u32)?;
-        writer.write_all(&record)?;
+        _write_message(&mut writer, &record)?;
     }
 
     writer.write_u32::<LittleEndian>(0)?; // END message
@@ -134,6 +195,162 @@ pub(super) fn export_continue(wallet: &Wallet, writer: &mut dyn Write, version:
     Ok(())
 }
 
+// Records matching several filters are exported once.
+fn _export_filtered(wallet: &Wallet, writer: &mut dyn Write, filter: &[ExportFilter]) -> IndyResult<()> {
+    let options = SearchOptions {
+        retrieve_records: true,
+        retrieve_total_count: false,
+        retrieve_type: true,
+        retrieve_value: true,
+        retrieve_tags: true,
+    };
+
+    let options = serde_json::to_string(&options)
+        .to_indy(IndyErrorKind::InvalidState, "Can't serialize search options")?;
+
+    let mut exported: HashSet<(String, String)> = HashSet::new();
+
+    for ExportFilter { type_, query } in filter {
+        if is_internal_type(type_) {
+            continue;
+        }
+
+        let query = query.as_ref().map(|query| query.to_string()).unwrap_or_else(|| "{}".to_string());
+        let mut records = wallet.search(type_, &query, Some(&options))?;
+
+        while let Some(record) = records.next()? {
+            let record = _to_export_record(record)?;
+
+            if exported.insert((record.type_.clone(), record.id.clone())) {
+                _write_message(writer, &record)?;
+            }
+        }
+    }
+
+    Ok(())
+}
+
+// The first increment contains all records, next ones contain records of the export journal.
+// Returns id of the written checkpoint and names of the exported journal entries.
+fn _export_increment(wallet: &Wallet, writer: &mut dyn Write) -> IndyResult<(String, Vec<String>)> {
+    let checkpoint = Checkpoint {
+        id: randombytes::randombytes(16).to_base58(),
+        previous: _get_checkpoint_record(wallet, EXPORT_CHECKPOINT_TYPE)?,
+    };
+
+    _write_message(writer, &checkpoint)?;
+
+    let mut journal = Vec::new();
+
+    if checkpoint.previous.is_none() {
+        let mut records = wallet.get_all()?;
+
+        while let Some(record) = records.next()? {
+            let record = _to_export_record(record)?;
+
+            if !is_internal_type(&record.type_) {
+                _write_message(writer, &RecordOperation::Upsert(record))?;
+            }
+        }
+
+        return Ok((checkpoint.id, journal));
+    }
+
+    let mut entries = wallet.search(EXPORT_JOURNAL_TYPE, "{}", Some(&SearchOptions::id_value()))?;
+
+    while let Some(entry) = entries.next()? {
+        journal.push(entry.id);
+    }
+
+    let options = RecordOptions {
+        retrieve_type: true,
+        retrieve_value: true,
+        retrieve_tags: true,
+    };
+
+    let options = serde_json::to_string(&options)
+        .to_indy(IndyErrorKind::InvalidState, "Can't serialize record options")?;
+
+    for name in journal.iter() {
+        let (type_, id): (String, String) = serde_json::from_str(name)
+            .to_indy(IndyErrorKind::InvalidState, "Can't deserialize export journal entry")?;
+
+        match wallet.get(&type_, &id, &options) {
+            Ok(record) => _write_message(writer, &RecordOperation::Upsert(_to_export_record(record)?))?,
+            Err(ref err) if err.kind() == IndyErrorKind::WalletItemNotFound => _write_message(writer, &RecordOperation::Delete { type_, id })?,
+            Err(err) => return Err(err)
+        }
+    }
+
+    Ok((checkpoint.id, journal))
+}
+
+// Name of the export journal entry for the changed record.
+pub(super) fn journal_entry_name(type_: &str, name: &str) -> IndyResult<String> {
+    serde_json::to_string(&(type_, name))
+        .to_indy(IndyErrorKind::InvalidState, "Can't serialize export journal entry")
+}
+
+pub(super) fn has_export_checkpoint(wallet: &Wallet) -> IndyResult<bool> {
+    Ok(_get_checkpoint_record(wallet, EXPORT_CHECKPOINT_TYPE)?.is_some())
+}
+
+fn _to_export_record(record: WalletRecord) -> IndyResult<Record> {
+    let WalletRecord { type_, id, value, tags } = record;
+
+    Ok(Record {
+        type_: type_.ok_or_else(|| err_msg(IndyErrorKind::InvalidState, "No type fetched for exported record"))?,
+        id,
+        value: value.ok_or_else(|| err_msg(IndyErrorKind::InvalidState, "No value fetched for exported record"))?,
+        tags: tags.ok_or_else(|| err_msg(IndyErrorKind::InvalidState, "No tags fetched for exported record"))?,
+    })
+}
+
+fn _write_message<T>(writer: &mut dyn Write, message: &T) -> IndyResult<()> where T: serde::Serialize {
+    let message = rmp_serde::to_vec(message)
+        .to_indy(IndyErrorKind::InvalidState, "Can't serialize record")?;
+
+    writer.write_u32::<LittleEndian>(message.len() as u32)?;
+    writer.write_all(&message)?;
+    Ok(())
+}
+
+fn _read_message<T>(reader: &mut dyn Read) -> IndyResult<Option<T>> where T: serde::de::DeserializeOwned {
+    let message_len = reader.read_u32::<LittleEndian>().map_err(_map_io_err)? as usize;
+
+    if message_len == 0 {
+        return Ok(None);
+    }
+
+    let mut message = vec![0u8; message_len];
+    reader.read_exact(&mut message).map_err(_map_io_err)?;
+
+    let message = rmp_serde::from_slice(&message)
+        .to_indy(IndyErrorKind::InvalidStructure, "Record is malformed msgpack")?;
+
+    Ok(Some(message))
+}
+
+// Records maintained by export and import themselves. They aren't exported and can't be accessed through records API.
+pub(super) fn is_internal_type(type_: &str) -> bool {
+    type_ == EXPORT_CHECKPOINT_TYPE || type_ == IMPORT_CHECKPOINT_TYPE || type_ == EXPORT_JOURNAL_TYPE
+}
+
+fn _get_checkpoint_record(wallet: &Wallet, type_: &str) -> IndyResult<Option<String>> {
+    match wallet.get(type_, CHECKPOINT_ID, &RecordOptions::id_value()) {
+        Ok(record) => Ok(record.get_value().map(String::from)),
+        Err(ref err) if err.kind() == IndyErrorKind::WalletItemNotFound => Ok(None),
+        Err(err) => Err(err)
+    }
+}
+
+fn _set_checkpoint_record(wallet: &Wallet, type_: &str, value: &str) -> IndyResult<()> {
+    match wallet.update(type_, CHECKPOINT_ID, value) {
+        Err(ref err) if err.kind() == IndyErrorKind::WalletItemNotFound => wallet.add(type_, CHECKPOINT_ID, value, &HashMap::new()),
+        res => res
+    }
+}
+
 #[cfg(test)]
 fn import<T>(wallet: &Wallet, reader: T, passphrase: &str) -> IndyResult<()> where T: Read {
     let (reader, import_key_derivation_data, nonce, chunk_size, header_bytes) = preparse_file_to_import(reader, passphrase)?;
@@ -157,7 +374,7 @@ pub(super) fn preparse_file_to_import<T>(reader: T, passphrase: &str) -> IndyRes
     let header: Header = rmp_serde::from_slice(&header_bytes)
         .to_indy(IndyErrorKind::InvalidStructure, "Header is malformed json")?;
 
-    if header.version != 0 {
+    if header.version != FULL_EXPORT_VERSION && header.version != INCREMENTAL_EXPORT_VERSION {
         return Err(err_msg(IndyErrorKind::InvalidStructure, "Unsupported version"));
     }
 
@@ -211,25 +428,54 @@ pub(super) fn finish_import<T>(wallet: &Wallet, reader: BufReader<T>, key: chach
         return Err(err_msg(IndyErrorKind::InvalidStructure, "Invalid header hash"));
     }
 
-    loop {
-        let record_len = reader.read_u32::<LittleEndian>().map_err(_map_io_err)? as usize;
-
-        if record_len == 0 {
-            break;
-        }
-
-        let mut record = vec![0u8; record_len];
-        reader.read_exact(&mut record).map_err(_map_io_err)?;
+    let header: Header = rmp_serde::from_slice(&header_bytes)
+        .to_indy(IndyErrorKind::InvalidStructure, "Header is malformed json")?;
 
-        let record: Record = rmp_serde::from_slice(&record)
-            .to_indy(IndyErrorKind::InvalidStructure, "Record is malformed msgpack")?;
+    if header.version == INCREMENTAL_EXPORT_VERSION {
+        return _import_increment(wallet, &mut reader);
+    }
 
+    while let Some(record) = _read_message::<Record>(&mut reader)? {
         wallet.add(&record.type_, &record.id, &record.value, &record.tags)?;
     }
 
     Ok(())
 }
 
+fn _import_increment(wallet: &Wallet, reader: &mut dyn Read) -> IndyResult<()> {
+    let checkpoint: Checkpoint = _read_message(reader)?
+        .ok_or_else(|| err_msg(IndyErrorKind::InvalidStructure, "No checkpoint found in incremental export"))?;
+
+    let last_checkpoint = _get_checkpoint_record(wallet, IMPORT_CHECKPOINT_TYPE)?;
+
+    if checkpoint.previous != last_checkpoint {
+        return Err(err_msg(IndyErrorKind::InvalidStructure,
+                           format!("Export increment doesn't follow the last imported checkpoint: {:?}", last_checkpoint)));
+    }
+
+    while let Some(operation) = _read_message::<RecordOperation>(reader)? {
+        match operation {
+            RecordOperation::Upsert(record) => {
+                match wallet.add(&record.type_, &record.id, &record.value, &record.tags) {
+                    Err(ref err) if err.kind() == IndyErrorKind::WalletItemAlreadyExists => {
+                        wallet.update(&record.type_, &record.id, &record.value)?;
+                        wallet.update_tags(&record.type_, &record.id, &record.tags)?;
+                    }
+                    res => res?
+                }
+            }
+            RecordOperation::Delete { type_, id } => {
+                match wallet.delete(&type_, &id) {
+                    Err(ref err) if err.kind() == IndyErrorKind::WalletItemNotFound => {}
+                    res => res?
+                }
+            }
+        }
+    }
+
+    _set_checkpoint_record(wallet, IMPORT_CHECKPOINT_TYPE, &checkpoint.id)
+}
+
 fn _map_io_err(e: io::Error) -> IndyError {
     match e {
         ref e if e.kind() == io::ErrorKind::UnexpectedEof
@@ -256,7 +502,7 @@ mod tests {
     use super::*;
 
     fn export(wallet: &Wallet, writer: &mut dyn Write, passphrase: &str, version: u32, key_derivation_method: &KeyDerivationMethod) -> IndyResult<()> {
-        if version != 0 {
+        if version != FULL_EXPORT_VERSION && version != INCREMENTAL_EXPORT_VERSION {
             Err(err_msg(IndyErrorKind::InvalidState, "Unsupported version"))?;
         }
 
@@ -347,6 +593,96 @@ mod tests {
         _cleanup("export_import_works_for_multiple_items2");
     }
 
+    #[test]
+    fn export_import_works_for_increments() {
+        _cleanup("export_import_works_for_increments1");
+        _cleanup("export_import_works_for_increments2");
+        {
+            let wallet1 = _add_2_records(_wallet("export_import_works_for_increments1"));
+
+            let mut base: Vec<u8> = Vec::new();
+            export(&wallet1, &mut base, _passphrase(), INCREMENTAL_EXPORT_VERSION, &KeyDerivationMethod::ARGON2I_INT).unwrap();
+
+            wallet1.update(&_type1(), &_id1(), &_value2()).unwrap();
+            wallet1.delete(&_type2(), &_id2()).unwrap();
+            wallet1.add(&_type2(), &_id3(), &_value1(), &_tags1()).unwrap();
+
+            let mut increment: Vec<u8> = Vec::new();
+            export(&wallet1, &mut increment, _passphrase(), INCREMENTAL_EXPORT_VERSION, &KeyDerivationMethod::ARGON2I_INT).unwrap();
+
+            // Unchanged record isn't exported again
+            let mut empty_increment: Vec<u8> = Vec::new();
+            export(&wallet1, &mut empty_increment, _passphrase(), INCREMENTAL_EXPORT_VERSION, &KeyDerivationMethod::ARGON2I_INT).unwrap();
+            assert!(empty_increment.len() < increment.len());
+
+            let wallet2 = _wallet("export_import_works_for_increments2");
+
+            import(&wallet2, &mut base.as_slice(), _passphrase()).unwrap();
+            _assert_has_2_records(&wallet2);
+
+            import(&wallet2, &mut increment.as_slice(), _passphrase()).unwrap();
+            import(&wallet2, &mut empty_increment.as_slice(), _passphrase()).unwrap();
+
+            let record = wallet2.get(&_type1(), &_id1(), _options()).unwrap();
+            assert_eq!(record.value.unwrap(), _value2());
+
+            let res = wallet2.get(&_type2(), &_id2(), _options());
+            assert_kind!(IndyErrorKind::WalletItemNotFound, res);
+
+            let record = wallet2.get(&_type2(), &_id3(), _options()).unwrap();
+            assert_eq!(record.value.unwrap(), _value1());
+            assert_eq!(record.tags.unwrap(), _tags1());
+        }
+        _cleanup("export_import_works_for_increments1");
+        _cleanup("export_import_works_for_increments2");
+    }
+
+    #[test]
+    fn export_increment_works_for_journal() {
+        _cleanup("export_increment_works_for_journal");
+        {
+            let wallet = _add_2_records(_wallet("export_increment_works_for_journal"));
+
+            // Changes aren't journaled before the first incremental export
+            export(&wallet, &mut Vec::new(), _passphrase(), FULL_EXPORT_VERSION, &KeyDerivationMethod::RAW).unwrap();
+            assert_eq!(0, _journal_len(&wallet));
+
+            export(&wallet, &mut Vec::new(), _passphrase(), INCREMENTAL_EXPORT_VERSION, &KeyDerivationMethod::RAW).unwrap();
+            assert_eq!(0, _journal_len(&wallet));
+
+            wallet.update(&_type1(), &_id1(), &_value2()).unwrap();
+            wallet.update(&_type1(), &_id1(), &_value1()).unwrap();
+            wallet.delete(&_type2(), &_id2()).unwrap();
+            assert_eq!(2, _journal_len(&wallet));
+
+            export(&wallet, &mut Vec::new(), _passphrase(), INCREMENTAL_EXPORT_VERSION, &KeyDerivationMethod::RAW).unwrap();
+            assert_eq!(0, _journal_len(&wallet));
+        }
+        _cleanup("export_increment_works_for_journal");
+    }
+
+    #[test]
+    fn import_works_for_increment_out_of_order() {
+        _cleanup("import_works_for_increment_out_of_order1");
+        _cleanup("import_works_for_increment_out_of_order2");
+        {
+            let wallet1 = _add_2_records(_wallet("import_works_for_increment_out_of_order1"));
+
+            let mut base: Vec<u8> = Vec::new();
+            export(&wallet1, &mut base, _passphrase(), INCREMENTAL_EXPORT_VERSION, &KeyDerivationMethod::ARGON2I_INT).unwrap();
+
+            let mut increment: Vec<u8> = Vec::new();
+            export(&wallet1, &mut increment, _passphrase(), INCREMENTAL_EXPORT_VERSION, &KeyDerivationMethod::ARGON2I_INT).unwrap();
+
+            let wallet2 = _wallet("import_works_for_increment_out_of_order2");
+
+            let res = import(&wallet2, &mut increment.as_slice(), _passphrase());
+            assert_kind!(IndyErrorKind::InvalidStructure, res);
+        }
+        _cleanup("import_works_for_increment_out_of_order1");
+        _cleanup("import_works_for_increment_out_of_order2");
+    }
+
     #[test]
     fn import_works_for_empty() {
         _cleanup("import_works_for_empty");
@@ -480,6 +816,7 @@ mod tests {
             let metadata = Metadata::MetadataArgon(MetadataArgon {
                 master_key_salt: master_key_salt[..].to_vec(),
                 keys: keys.serialize_encrypted(&master_key).unwrap(),
+                audit_log: false,
             });
 
             serde_json::to_vec(&metadata)
@@ -496,6 +833,17 @@ mod tests {
         Wallet::new(id.to_string(), storage, Rc::new(keys))
     }
 
+    fn _journal_len(wallet: &Wallet) -> usize {
+        let mut entries = wallet.search(EXPORT_JOURNAL_TYPE, "{}", None).unwrap();
+        let mut len = 0;
+
+        while let Some(_) = entries.next().unwrap() {
+            len += 1;
+        }
+
+        len
+    }
+
     fn _assert_is_empty(wallet: &Wallet) {
         assert!(wallet.get_all().unwrap().next().unwrap().is_none());
     }
@@ -580,6 +928,10 @@ mod tests {
         _id(2)
     }
 
+    fn _id3() -> String {
+        _id(3)
+    }
+
     fn _value(suffix: usize) -> String {
         format!("id_{}", suffix)
     }
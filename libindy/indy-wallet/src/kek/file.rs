@@ -0,0 +1,115 @@
+use std::fs;
+
+use rust_base58::FromBase58;
+use zeroize::Zeroize;
+
+use indy_api_types::errors::prelude::*;
+use indy_utils::crypto::chacha20poly1305_ietf;
+
+use crate::encryption::{decrypt_merged, encrypt_as_not_searchable};
+
+use super::KeyEncryptionKeyProvider;
+
+// Reference KEK provider that keeps key-encryption key in the local file.
+// key_id is the path to the file that contains base58 encoded key
+// (for example generated with indy_generate_wallet_key call).
+pub struct FileKekProvider {}
+
+impl FileKekProvider {
+    pub fn new() -> FileKekProvider {
+        FileKekProvider {}
+    }
+
+    fn _read_kek(key_id: &str) -> IndyResult<chacha20poly1305_ietf::Key> {
+        let mut content = fs::read_to_string(key_id)
+            .to_indy(IndyErrorKind::IOError, format!("Can't read key-encryption key file: {}", key_id))?;
+
+        let mut bytes = content.trim().from_base58()
+            .map_err(|_| err_msg(IndyErrorKind::InvalidStructure, "Key-encryption key file contains invalid base58 key"))?;
+
+        content.zeroize();
+
+        let res = chacha20poly1305_ietf::Key::from_slice(&bytes)
+            .map_err(|err| err.extend("Invalid key-encryption key"));
+
+        bytes.zeroize();
+        res
+    }
+}
+
+impl KeyEncryptionKeyProvider for FileKekProvider {
+    fn wrap_key(&self, key_id: &str, key: &[u8]) -> IndyResult<Vec<u8>> {
+        let kek = FileKekProvider::_read_kek(key_id)?;
+        Ok(encrypt_as_not_searchable(key, &kek))
+    }
+
+    fn unwrap_key(&self, key_id: &str, wrapped_key: &[u8]) -> IndyResult<Vec<u8>> {
+        let kek = FileKekProvider::_read_kek(key_id)?;
+
+        if wrapped_key.len() < chacha20poly1305_ietf::NONCEBYTES {
+            return Err(err_msg(IndyErrorKind::WalletAccessFailed, "Wrapped master key is too short"));
+        }
+
+        decrypt_merged(wrapped_key, &kek)
+            .map_err(|err| err.map(IndyErrorKind::WalletAccessFailed, "Invalid key-encryption key provided"))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::PathBuf;
+
+    use rust_base58::ToBase58;
+
+    use indy_utils::environment;
+
+    use super::*;
+
+    fn _kek_file(name: &str, kek: &chacha20poly1305_ietf::Key) -> PathBuf {
+        let mut path = environment::tmp_path();
+        fs::create_dir_all(&path).unwrap();
+        path.push(name);
+        fs::write(&path, kek[..].to_base58()).unwrap();
+        path
+    }
+
+    #[test]
+    fn file_kek_provider_wrap_unwrap_works() {
+        let path = _kek_file("file_kek_provider_wrap_unwrap_works", &chacha20poly1305_ietf::gen_key());
+        let key_id = path.to_str().unwrap();
+
+        let provider = FileKekProvider::new();
+        let master_key = chacha20poly1305_ietf::gen_key();
+
+        let wrapped = provider.wrap_key(key_id, &master_key[..]).unwrap();
+        assert_ne!(&master_key[..], &wrapped[..]);
+
+        let unwrapped = provider.unwrap_key(key_id, &wrapped).unwrap();
+        assert_eq!(&master_key[..], &unwrapped[..]);
+
+        fs::remove_file(path).unwrap();
+    }
+
+    #[test]
+    fn file_kek_provider_unwrap_works_for_other_kek() {
+        let path = _kek_file("file_kek_provider_unwrap_works_for_other_kek", &chacha20poly1305_ietf::gen_key());
+        let other_path = _kek_file("file_kek_provider_unwrap_works_for_other_kek_other", &chacha20poly1305_ietf::gen_key());
+
+        let provider = FileKekProvider::new();
+        let wrapped = provider.wrap_key(path.to_str().unwrap(), &chacha20poly1305_ietf::gen_key()[..]).unwrap();
+
+        let res = provider.unwrap_key(other_path.to_str().unwrap(), &wrapped);
+        assert_kind!(IndyErrorKind::WalletAccessFailed, res);
+
+        fs::remove_file(path).unwrap();
+        fs::remove_file(other_path).unwrap();
+    }
+
+    #[test]
+    fn file_kek_provider_wrap_works_for_missed_file() {
+        let provider = FileKekProvider::new();
+
+        let res = provider.wrap_key("/not/existing/kek/file", &chacha20poly1305_ietf::gen_key()[..]);
+        assert_kind!(IndyErrorKind::IOError, res);
+    }
+}
@@ -0,0 +1,93 @@
+pub mod file;
+pub mod plugged;
+
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+
+use indy_api_types::errors::prelude::*;
+
+use self::file::FileKekProvider;
+
+// Key-encryption key (KEK) provider wraps and unwraps wallet master key.
+// It allows to keep the secret that protects wallet outside of application config
+// (for example in PKCS#11 token or OS keystore).
+pub trait KeyEncryptionKeyProvider: Send + Sync {
+    fn wrap_key(&self, key_id: &str, key: &[u8]) -> IndyResult<Vec<u8>>;
+    fn unwrap_key(&self, key_id: &str, wrapped_key: &[u8]) -> IndyResult<Vec<u8>>;
+}
+
+// Master key derivation can be performed outside of the command thread,
+// so registered providers are shared between threads.
+lazy_static! {
+    static ref KEK_PROVIDERS: Mutex<HashMap<String, Arc<dyn KeyEncryptionKeyProvider>>> = {
+        let mut map: HashMap<String, Arc<dyn KeyEncryptionKeyProvider>> = HashMap::new();
+        map.insert("file".to_string(), Arc::new(FileKekProvider::new()));
+        Mutex::new(map)
+    };
+}
+
+pub fn register_kek_provider(type_: &str, provider: Arc<dyn KeyEncryptionKeyProvider>) -> IndyResult<()> {
+    trace!("register_kek_provider >>> type_: {:?}", type_);
+
+    let mut providers = KEK_PROVIDERS.lock().unwrap();
+
+    if providers.contains_key(type_) {
+        return Err(err_msg(IndyErrorKind::WalletStorageTypeAlreadyRegistered, format!("KEK provider is already registered for type: {}", type_)));
+    }
+
+    providers.insert(type_.to_string(), provider);
+
+    trace!("register_kek_provider <<<");
+    Ok(())
+}
+
+pub(crate) fn get_kek_provider(type_: &str) -> IndyResult<Arc<dyn KeyEncryptionKeyProvider>> {
+    KEK_PROVIDERS.lock().unwrap()
+        .get(type_)
+        .cloned()
+        .ok_or_else(|| err_msg(IndyErrorKind::UnknownWalletStorageType, format!("Unknown KEK provider type: {}", type_)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    struct DummyKekProvider;
+
+    impl KeyEncryptionKeyProvider for DummyKekProvider {
+        fn wrap_key(&self, _key_id: &str, key: &[u8]) -> IndyResult<Vec<u8>> {
+            Ok(key.to_vec())
+        }
+
+        fn unwrap_key(&self, _key_id: &str, wrapped_key: &[u8]) -> IndyResult<Vec<u8>> {
+            Ok(wrapped_key.to_vec())
+        }
+    }
+
+    #[test]
+    fn get_kek_provider_works_for_file() {
+        get_kek_provider("file").unwrap();
+    }
+
+    #[test]
+    fn get_kek_provider_works_for_unknown() {
+        let res = get_kek_provider("get_kek_provider_works_for_unknown");
+        assert_kind!(IndyErrorKind::UnknownWalletStorageType, res);
+    }
+
+    #[test]
+    fn register_kek_provider_works() {
+        register_kek_provider("register_kek_provider_works", Arc::new(DummyKekProvider)).unwrap();
+
+        let provider = get_kek_provider("register_kek_provider_works").unwrap();
+        assert_eq!(vec![1, 2, 3], provider.unwrap_key("key", &provider.wrap_key("key", &[1, 2, 3]).unwrap()).unwrap());
+    }
+
+    #[test]
+    fn register_kek_provider_works_for_twice() {
+        register_kek_provider("register_kek_provider_works_for_twice", Arc::new(DummyKekProvider)).unwrap();
+
+        let res = register_kek_provider("register_kek_provider_works_for_twice", Arc::new(DummyKekProvider));
+        assert_kind!(IndyErrorKind::WalletStorageTypeAlreadyRegistered, res);
+    }
+}
@@ -0,0 +1,96 @@
+use std::ffi::CString;
+use std::{ptr, slice};
+
+use indy_api_types::{ErrorCode, IndyHandle};
+use indy_api_types::wallet::*;
+use indy_api_types::errors::prelude::*;
+
+use super::KeyEncryptionKeyProvider;
+
+// This struct is used as a helper to free the key buffer even in case of error.
+struct KeyGuard {
+    key_handle: IndyHandle,
+    free_handler: WalletKekFreeKey,
+}
+
+impl Drop for KeyGuard {
+    fn drop(&mut self) {
+        (self.free_handler)(self.key_handle);
+    }
+}
+
+pub struct PluggedKekProvider {
+    wrap_key_handler: WalletKekWrapKey,
+    unwrap_key_handler: WalletKekUnwrapKey,
+    free_key_handler: WalletKekFreeKey,
+}
+
+impl PluggedKekProvider {
+    pub fn new(wrap_key_handler: WalletKekWrapKey,
+               unwrap_key_handler: WalletKekUnwrapKey,
+               free_key_handler: WalletKekFreeKey) -> PluggedKekProvider {
+        PluggedKekProvider {
+            wrap_key_handler,
+            unwrap_key_handler,
+            free_key_handler,
+        }
+    }
+}
+
+impl KeyEncryptionKeyProvider for PluggedKekProvider {
+    fn wrap_key(&self, key_id: &str, key: &[u8]) -> IndyResult<Vec<u8>> {
+        let key_id = CString::new(key_id)?;
+
+        let mut wrapped_key_ptr: *const u8 = ptr::null();
+        let mut wrapped_key_len: usize = 0;
+        let mut key_handle: IndyHandle = -1;
+
+        let err = (self.wrap_key_handler)(key_id.as_ptr(),
+                                          key.as_ptr(),
+                                          key.len(),
+                                          &mut wrapped_key_ptr,
+                                          &mut wrapped_key_len,
+                                          &mut key_handle);
+
+        if err != ErrorCode::Success {
+            return Err(err.into());
+        }
+
+        let _key_free_helper = KeyGuard { key_handle, free_handler: self.free_key_handler };
+
+        if wrapped_key_ptr.is_null() {
+            return Err(err_msg(IndyErrorKind::InvalidState, "KEK provider returned empty wrapped key"));
+        }
+
+        let wrapped_key = unsafe { slice::from_raw_parts(wrapped_key_ptr, wrapped_key_len) };
+        Ok(wrapped_key.to_vec())
+    }
+
+    fn unwrap_key(&self, key_id: &str, wrapped_key: &[u8]) -> IndyResult<Vec<u8>> {
+        let key_id = CString::new(key_id)?;
+
+        let mut key_ptr: *const u8 = ptr::null();
+        let mut key_len: usize = 0;
+        let mut key_handle: IndyHandle = -1;
+
+        let err = (self.unwrap_key_handler)(key_id.as_ptr(),
+                                            wrapped_key.as_ptr(),
+                                            wrapped_key.len(),
+                                            &mut key_ptr,
+                                            &mut key_len,
+                                            &mut key_handle);
+
+        if err != ErrorCode::Success {
+            return Err(err.into());
+        }
+
+        let _key_free_helper = KeyGuard { key_handle, free_handler: self.free_key_handler };
+
+        if key_ptr.is_null() {
+            return Err(err_msg(IndyErrorKind::InvalidState, "KEK provider returned empty key"));
+        }
+
+        let key = unsafe { slice::from_raw_parts(key_ptr, key_len) };
+        Ok(key.to_vec())
+    }
+}
@@ -45,6 +45,16 @@ impl From<String> for TargetValue {
     }
 }
 
+impl TargetValue {
+    // Numbers are rendered without quotes, so typed comparisons survive passing the query to storage plugins.
+    fn to_number_string(&self) -> String {
+        match *self {
+            TargetValue::Unencrypted(ref s) => s.clone(),
+            TargetValue::Encrypted(_) => self.to_string(),
+        }
+    }
+}
+
 impl string::ToString for TargetValue {
     fn to_string(&self) -> String {
         match *self {
@@ -67,6 +77,11 @@ pub enum Operator {
     Lte(TagName, TargetValue),
     Like(TagName, TargetValue),
     In(TagName, Vec<TargetValue>),
+    NumGt(TagName, TargetValue),
+    NumGte(TagName, TargetValue),
+    NumLt(TagName, TargetValue),
+    NumLte(TagName, TargetValue),
+    Exist(TagName),
 }
 
 impl string::ToString for Operator {
@@ -86,6 +101,11 @@ impl string::ToString for Operator {
                     tag_values.iter().map(|v| v.to_string()).collect::<Vec<String>>().join(",")
                 )
             }
+            Operator::NumGt(ref tag_name, ref tag_value) => format!(r#"{{{}:{{"$gt":{}}}}}"#, tag_name.to_string(), tag_value.to_number_string()),
+            Operator::NumGte(ref tag_name, ref tag_value) => format!(r#"{{{}:{{"$gte":{}}}}}"#, tag_name.to_string(), tag_value.to_number_string()),
+            Operator::NumLt(ref tag_name, ref tag_value) => format!(r#"{{{}:{{"$lt":{}}}}}"#, tag_name.to_string(), tag_value.to_number_string()),
+            Operator::NumLte(ref tag_name, ref tag_value) => format!(r#"{{{}:{{"$lte":{}}}}}"#, tag_name.to_string(), tag_value.to_number_string()),
+            Operator::Exist(ref tag_name) => format!(r#"{{{}:{{"$exist":true}}}}"#, tag_name.to_string()),
             Operator::And(ref operators) => {
                 if !operators.is_empty() {
                     format!(
@@ -11,27 +11,33 @@ extern crate serde;
 #[macro_use]
 extern crate serde_derive;
 
+#[macro_use]
+extern crate lazy_static;
+
 use std::cell::RefCell;
 use std::collections::{HashMap, HashSet};
 use std::fs;
-use std::io::BufReader;
+use std::io::{BufReader, Read, Write};
+use std::ops::Not;
 use std::path::PathBuf;
 use std::rc::Rc;
+use std::sync::Arc;
 
 use serde_json::Value as SValue;
 
 use indy_api_types::wallet::*;
 
-use indy_api_types::domain::wallet::{Config, Credentials, ExportConfig, Tags};
+use indy_api_types::domain::wallet::{Config, Credentials, ExportConfig, ExportFilter, Tags};
 use indy_api_types::errors::prelude::*;
 pub use crate::encryption::KeyDerivationData;
 use indy_utils::crypto::chacha20poly1305_ietf;
 use indy_utils::crypto::chacha20poly1305_ietf::Key as MasterKey;
 
 use self::export_import::{export_continue, finish_import, preparse_file_to_import};
-use self::storage::{WalletStorage, WalletStorageType};
 use self::storage::default::SQLiteStorageType;
+use self::storage::inmem::InmemStorageType;
 use self::storage::plugged::PluggedStorageType;
+use self::kek::plugged::PluggedKekProvider;
 use self::wallet::{Keys, Wallet};
 use indy_api_types::{WalletHandle};
 
@@ -43,13 +49,31 @@ mod iterator;
 pub mod language;
 mod export_import;
 mod wallet;
+mod kek;
+mod audit_log;
+mod check;
+mod stats;
+mod expiry;
+mod stream;
+
+pub use self::kek::{KeyEncryptionKeyProvider, register_kek_provider};
+pub use self::audit_log::{AuditLogEntry, AuditLogOperation};
+pub use self::check::{CorruptedItem, WalletCheckReport};
+pub use self::stats::{TypeStats, WalletStats};
+pub use self::expiry::{EXPIRES_AT_TAG, TTL_TAG};
+pub use self::export_import::{FULL_EXPORT_VERSION, INCREMENTAL_EXPORT_VERSION};
+pub use self::stream::{CallbackReader, CallbackWriter};
+pub use self::storage::{StorageCheckReport, StorageIterator, StorageRecord, StorageStats, StorageTypeStats, Tag, TagName, WalletStorage, WalletStorageType};
+pub use self::wallet::EncryptedValue;
 
 pub struct WalletService {
     storage_types: RefCell<HashMap<String, Box<dyn WalletStorageType>>>,
+    plugged_storage_types: RefCell<HashMap<String, PluggedStorageType>>,
     wallets: RefCell<HashMap<WalletHandle, Box<Wallet>>>,
     wallet_ids: RefCell<HashSet<String>>,
-    pending_for_open: RefCell<HashMap<WalletHandle, (String /* id */, Box<dyn WalletStorage>, Metadata, Option<KeyDerivationData>)>>,
-    pending_for_import: RefCell<HashMap<WalletHandle, (BufReader<::std::fs::File>, chacha20poly1305_ietf::Nonce, usize, Vec<u8>, KeyDerivationData)>>,
+    pending_for_open: RefCell<HashMap<WalletHandle, (String /* id */, Box<dyn WalletStorage>, Metadata, Option<KeyDerivationData>, bool /* audit log */)>>,
+    pending_for_import: RefCell<HashMap<WalletHandle, (BufReader<Box<dyn Read>>, chacha20poly1305_ietf::Nonce, usize, Vec<u8>, KeyDerivationData)>>,
+    pending_for_import_increment: RefCell<HashMap<i32 /* import id */, (WalletHandle, BufReader<Box<dyn Read>>, chacha20poly1305_ietf::Nonce, usize, Vec<u8>)>>,
 }
 
 impl WalletService {
@@ -57,15 +81,18 @@ impl WalletService {
         let storage_types = {
             let mut map: HashMap<String, Box<dyn WalletStorageType>> = HashMap::new();
             map.insert("default".to_string(), Box::new(SQLiteStorageType::new()));
+            map.insert("inmem".to_string(), Box::new(InmemStorageType::new()));
             RefCell::new(map)
         };
 
         WalletService {
             storage_types,
+            plugged_storage_types: RefCell::new(HashMap::new()),
             wallets: RefCell::new(HashMap::new()),
             wallet_ids: RefCell::new(HashSet::new()),
             pending_for_open: RefCell::new(HashMap::new()),
             pending_for_import: RefCell::new(HashMap::new()),
+            pending_for_import_increment: RefCell::new(HashMap::new()),
         }
     }
 
@@ -97,25 +124,71 @@ impl WalletService {
                                    free_search: WalletFreeSearch) -> IndyResult<()> {
         trace!("register_wallet_storage >>> type_: {:?}", type_);
 
+        // C callbacks based storage is just an adapter over native storage type.
+        let storage_type = PluggedStorageType::new(create, open, close, delete,
+                                                   add_record, update_record_value,
+                                                   update_record_tags, add_record_tags, delete_record_tags,
+                                                   delete_record, get_record, get_record_id,
+                                                   get_record_type, get_record_value, get_record_tags, free_record,
+                                                   get_storage_metadata, set_storage_metadata, free_storage_metadata,
+                                                   search_records, search_all_records,
+                                                   get_search_total_count,
+                                                   fetch_search_next_record, free_search);
+
+        self.register_wallet_storage_type(type_, Box::new(storage_type.clone()))?;
+        self.plugged_storage_types.borrow_mut().insert(type_.to_string(), storage_type);
+
+        trace!("register_wallet_storage <<<");
+        Ok(())
+    }
+
+    pub fn register_wallet_storage_type(&self, type_: &str, storage_type: Box<dyn WalletStorageType>) -> IndyResult<()> {
+        trace!("register_wallet_storage_type >>> type_: {:?}", type_);
+
         let mut storage_types = self.storage_types.borrow_mut();
 
         if storage_types.contains_key(type_) {
             return Err(err_msg(IndyErrorKind::WalletStorageTypeAlreadyRegistered, format!("Wallet storage is already registered for type: {}", type_)));
         }
 
-        storage_types.insert(type_.to_string(),
-                             Box::new(
-                                 PluggedStorageType::new(create, open, close, delete,
-                                                         add_record, update_record_value,
-                                                         update_record_tags, add_record_tags, delete_record_tags,
-                                                         delete_record, get_record, get_record_id,
-                                                         get_record_type, get_record_value, get_record_tags, free_record,
-                                                         get_storage_metadata, set_storage_metadata, free_storage_metadata,
-                                                         search_records, search_all_records,
-                                                         get_search_total_count,
-                                                         fetch_search_next_record, free_search)));
+        storage_types.insert(type_.to_string(), storage_type);
 
-        trace!("register_wallet_storage <<<");
+        trace!("register_wallet_storage_type <<<");
+        Ok(())
+    }
+
+    // Stats handlers are optional for plugged storages, so they are registered separately
+    // and used by wallets opened after this call.
+    pub fn register_wallet_storage_stats(&self,
+                                         type_: &str,
+                                         get_storage_stats: WalletGetStorageStats,
+                                         free_storage_stats: WalletFreeStorageStats) -> IndyResult<()> {
+        trace!("register_wallet_storage_stats >>> type_: {:?}", type_);
+
+        let storage_type = self.plugged_storage_types
+            .borrow()
+            .get(type_)
+            .cloned()
+            .ok_or_else(|| err_msg(IndyErrorKind::UnknownWalletStorageType, format!("Plugged wallet storage isn't registered for type: {}", type_)))?
+            .with_stats_handlers(get_storage_stats, free_storage_stats);
+
+        self.storage_types.borrow_mut().insert(type_.to_string(), Box::new(storage_type.clone()));
+        self.plugged_storage_types.borrow_mut().insert(type_.to_string(), storage_type);
+
+        trace!("register_wallet_storage_stats <<<");
+        Ok(())
+    }
+
+    pub fn register_kek_provider(&self,
+                                 type_: &str,
+                                 wrap_key: WalletKekWrapKey,
+                                 unwrap_key: WalletKekUnwrapKey,
+                                 free_key: WalletKekFreeKey) -> IndyResult<()> {
+        trace!("register_kek_provider >>> type_: {:?}", type_);
+
+        kek::register_kek_provider(type_, Arc::new(PluggedKekProvider::new(wrap_key, unwrap_key, free_key)))?;
+
+        trace!("register_kek_provider <<<");
         Ok(())
     }
 
@@ -137,7 +210,7 @@ impl WalletService {
         let (storage_type, storage_config, storage_credentials) = WalletService::_get_config_and_cred_for_storage(config, credentials, &storage_types)?;
 
         let keys = Keys::new();
-        let metadata = self._prepare_metadata(master_key, key_data, &keys)?;
+        let metadata = self._prepare_metadata(master_key, key_data, &keys, config.audit_log.unwrap_or(false))?;
 
         storage_type.create_storage(&config.id,
                                     storage_config
@@ -189,6 +262,34 @@ impl WalletService {
         Ok(())
     }
 
+    pub fn check_wallet_prepare(&self, config: &Config, credentials: &Credentials) -> IndyResult<(Metadata, KeyDerivationData)> {
+        trace!("check_wallet >>> config: {:?}, credentials: {:?}", config, secret!(credentials));
+
+        if self.wallet_ids.borrow_mut().contains(&WalletService::_get_wallet_id(config)) {
+            return Err(err_msg(IndyErrorKind::InvalidState, format!("Wallet has to be closed before checking: {:?}", WalletService::_get_wallet_id(config))));
+        }
+
+        let (_, metadata, key_derivation_data) = self._open_storage_and_fetch_metadata(config, &credentials)?;
+
+        Ok((metadata, key_derivation_data))
+    }
+
+    pub fn check_wallet_continue(&self, config: &Config, credentials: &Credentials, metadata: &Metadata, master_key: &MasterKey, repair: bool) -> IndyResult<WalletCheckReport> {
+        trace!("check_wallet >>> config: {:?}, credentials: {:?}, repair: {:?}", config, secret!(credentials), repair);
+
+        let keys = self._restore_keys(metadata, &master_key)?;
+
+        let mut storage = self._open_storage(config, credentials)?;
+
+        let res = check::check_storage(&*storage, &keys, repair);
+
+        storage.close()?;
+
+        trace!("check_wallet <<< res: {:?}", res);
+
+        res
+    }
+
     pub fn open_wallet_prepare(&self, config: &Config, credentials: &Credentials) -> IndyResult<(WalletHandle, KeyDerivationData, Option<KeyDerivationData>)> {
         trace!("open_wallet >>> config: {:?}, credentials: {:?}", config, secret!(&credentials));
 
@@ -201,25 +302,44 @@ impl WalletService {
         let rekey_data: Option<KeyDerivationData> = credentials.rekey.as_ref().map(|ref rekey|
             KeyDerivationData::from_passphrase_with_new_salt(rekey, &credentials.rekey_derivation_method));
 
-        self.pending_for_open.borrow_mut().insert(wallet_handle, (WalletService::_get_wallet_id(config), storage, metadata, rekey_data.clone()));
+        self.pending_for_open.borrow_mut().insert(wallet_handle, (WalletService::_get_wallet_id(config), storage, metadata, rekey_data.clone(), config.audit_log.unwrap_or(false)));
 
         Ok((wallet_handle, key_derivation_data, rekey_data))
     }
 
     pub fn open_wallet_continue(&self, wallet_handle: WalletHandle, master_key: (&MasterKey, Option<&MasterKey>)) -> IndyResult<WalletHandle> {
-        let (id, storage, metadata, rekey_data) = self.pending_for_open.borrow_mut().remove(&wallet_handle)
+        let (id, storage, metadata, rekey_data, audit_log) = self.pending_for_open.borrow_mut().remove(&wallet_handle)
             .ok_or_else(|| err_msg(IndyErrorKind::InvalidState, "Open data not found"))?;
 
         let (master_key, rekey) = master_key;
         let keys = self._restore_keys(&metadata, &master_key)?;
 
+        // Audit log enabled once stays enabled for the wallet
+        let enable_audit_log = audit_log && !metadata.audit_log();
+        let audit_log = audit_log || metadata.audit_log();
+
         // Rotate master key
         if let (Some(rekey), Some(rekey_data)) = (rekey, rekey_data) {
-            let metadata = self._prepare_metadata(rekey, &rekey_data, &keys)?;
+            let metadata = self._prepare_metadata(rekey, &rekey_data, &keys, audit_log)?;
+            storage.set_storage_metadata(&metadata)?;
+        } else if enable_audit_log {
+            let mut metadata = metadata;
+            metadata.set_audit_log();
+
+            let metadata = serde_json::to_vec(&metadata)
+                .to_indy(IndyErrorKind::InvalidState, "Cannot serialize wallet metadata")?;
             storage.set_storage_metadata(&metadata)?;
         }
 
-        let wallet = Wallet::new(id.clone(), storage, Rc::new(keys));
+        let mut wallet = Wallet::new(id.clone(), storage, Rc::new(keys));
+
+        if audit_log {
+            wallet.enable_audit_log();
+        }
+
+        if export_import::has_export_checkpoint(&wallet)? {
+            wallet.enable_export_journal();
+        }
 
         let mut wallets = self.wallets.borrow_mut();
         wallets.insert(wallet_handle, Box::new(wallet));
@@ -256,6 +376,22 @@ impl WalletService {
         Ok(())
     }
 
+    // Audit log is append-only so its records can't be modified through records API.
+    fn _check_not_audit_log_type(type_: &str) -> IndyResult<()> {
+        if type_ == audit_log::AUDIT_LOG_TYPE {
+            return Err(err_msg(IndyErrorKind::InvalidStructure, format!("Records of type {} can't be modified", type_)));
+        }
+        WalletService::_check_not_internal_type(type_)
+    }
+
+    // Export checkpoints and journal can't be accessed through records API at all.
+    fn _check_not_internal_type(type_: &str) -> IndyResult<()> {
+        if export_import::is_internal_type(type_) {
+            return Err(err_msg(IndyErrorKind::InvalidStructure, format!("Records of type {} can't be accessed", type_)));
+        }
+        Ok(())
+    }
+
     fn _map_wallet_storage_error(err: IndyError, type_: &str, name: &str) -> IndyError {
         match err.kind() {
             IndyErrorKind::WalletItemAlreadyExists => err_msg(IndyErrorKind::WalletItemAlreadyExists, format!("Wallet item already exists with type: {}, id: {}", type_, name)),
@@ -265,7 +401,10 @@ impl WalletService {
     }
 
     pub fn add_record(&self, wallet_handle: WalletHandle, type_: &str, name: &str, value: &str, tags: &Tags) -> IndyResult<()> {
-        match self.wallets.borrow_mut().get_mut(&wallet_handle) {
+        WalletService::_check_not_audit_log_type(type_)?;
+        expiry::check_not_reserved(tags.keys().map(String::as_str))?;
+
+        match self.wallets.borrow().get(&wallet_handle) {
             Some(wallet) => wallet.add(type_, name, value, tags)
                 .map_err(|err| WalletService::_map_wallet_storage_error(err, type_, name)),
             None => Err(err_msg(IndyErrorKind::InvalidWalletHandle, "Unknown wallet handle"))
@@ -287,6 +426,8 @@ impl WalletService {
     }
 
     pub fn update_record_value(&self, wallet_handle: WalletHandle, type_: &str, name: &str, value: &str) -> IndyResult<()> {
+        WalletService::_check_not_audit_log_type(type_)?;
+
         match self.wallets.borrow().get(&wallet_handle) {
             Some(wallet) =>
                 wallet.update(type_, name, value)
@@ -295,6 +436,15 @@ impl WalletService {
         }
     }
 
+    // Applies changes of the wallet records made by `f` atomically, so either all of them are stored or none.
+    // Note change listener is notified about each change as soon as it is applied.
+    pub fn atomic(&self, wallet_handle: WalletHandle, f: &mut dyn FnMut() -> IndyResult<()>) -> IndyResult<()> {
+        match self.wallets.borrow().get(&wallet_handle) {
+            Some(wallet) => wallet.atomic(f),
+            None => Err(err_msg(IndyErrorKind::InvalidWalletHandle, "Unknown wallet handle"))
+        }
+    }
+
     pub fn update_indy_object<T>(&self, wallet_handle: WalletHandle, name: &str, object: &T) -> IndyResult<String> where T: ::serde::Serialize + Sized {
         let type_ = short_type_name::<T>();
         match self.wallets.borrow().get(&wallet_handle) {
@@ -309,7 +459,10 @@ impl WalletService {
     }
 
     pub fn add_record_tags(&self, wallet_handle: WalletHandle, type_: &str, name: &str, tags: &Tags) -> IndyResult<()> {
-        match self.wallets.borrow_mut().get_mut(&wallet_handle) {
+        WalletService::_check_not_audit_log_type(type_)?;
+        expiry::check_not_reserved(tags.keys().map(String::as_str))?;
+
+        match self.wallets.borrow().get(&wallet_handle) {
             Some(wallet) => wallet.add_tags(type_, name, tags)
                 .map_err(|err| WalletService::_map_wallet_storage_error(err, type_, name)),
             None => Err(err_msg(IndyErrorKind::InvalidWalletHandle, "Unknown wallet handle"))
@@ -317,7 +470,10 @@ impl WalletService {
     }
 
     pub fn update_record_tags(&self, wallet_handle: WalletHandle, type_: &str, name: &str, tags: &Tags) -> IndyResult<()> {
-        match self.wallets.borrow_mut().get_mut(&wallet_handle) {
+        WalletService::_check_not_audit_log_type(type_)?;
+        expiry::check_not_reserved(tags.keys().map(String::as_str))?;
+
+        match self.wallets.borrow().get(&wallet_handle) {
             Some(wallet) => wallet.update_tags(type_, name, tags)
                 .map_err(|err| WalletService::_map_wallet_storage_error(err, type_, name)),
             None => Err(err_msg(IndyErrorKind::InvalidWalletHandle, "Unknown wallet handle"))
@@ -325,6 +481,9 @@ impl WalletService {
     }
 
     pub fn delete_record_tags(&self, wallet_handle: WalletHandle, type_: &str, name: &str, tag_names: &[&str]) -> IndyResult<()> {
+        WalletService::_check_not_audit_log_type(type_)?;
+        expiry::check_not_reserved(tag_names.iter().cloned())?;
+
         match self.wallets.borrow().get(&wallet_handle) {
             Some(wallet) => wallet.delete_tags(type_, name, tag_names)
                 .map_err(|err| WalletService::_map_wallet_storage_error(err, type_, name)),
@@ -333,6 +492,8 @@ impl WalletService {
     }
 
     pub fn delete_record(&self, wallet_handle: WalletHandle, type_: &str, name: &str) -> IndyResult<()> {
+        WalletService::_check_not_audit_log_type(type_)?;
+
         match self.wallets.borrow().get(&wallet_handle) {
             Some(wallet) => wallet.delete(type_, name)
                 .map_err(|err| WalletService::_map_wallet_storage_error(err, type_, name)),
@@ -340,11 +501,22 @@ impl WalletService {
         }
     }
 
+    pub fn purge_expired_records(&self, wallet_handle: WalletHandle, type_: &str) -> IndyResult<usize> {
+        WalletService::_check_not_audit_log_type(type_)?;
+
+        match self.wallets.borrow().get(&wallet_handle) {
+            Some(wallet) => wallet.purge_expired(type_),
+            None => Err(err_msg(IndyErrorKind::InvalidWalletHandle, "Unknown wallet handle"))
+        }
+    }
+
     pub fn delete_indy_record<T>(&self, wallet_handle: WalletHandle, name: &str) -> IndyResult<()> where T: Sized {
         self.delete_record(wallet_handle, &self.add_prefix(short_type_name::<T>()), name)
     }
 
     pub fn get_record(&self, wallet_handle: WalletHandle, type_: &str, name: &str, options_json: &str) -> IndyResult<WalletRecord> {
+        WalletService::_check_not_internal_type(type_)?;
+
         match self.wallets.borrow().get(&wallet_handle) {
             Some(wallet) =>
                 wallet.get(type_, name, options_json)
@@ -389,6 +561,8 @@ impl WalletService {
     }
 
     pub fn search_records(&self, wallet_handle: WalletHandle, type_: &str, query_json: &str, options_json: &str) -> IndyResult<WalletSearch> {
+        WalletService::_check_not_internal_type(type_)?;
+
         match self.wallets.borrow().get(&wallet_handle) {
             Some(wallet) => Ok(WalletSearch { iter: wallet.search(type_, query_json, Some(options_json))? }),
             None => Err(err_msg(IndyErrorKind::InvalidWalletHandle, "Unknown wallet handle"))
@@ -429,6 +603,30 @@ impl WalletService {
         }
     }
 
+    pub fn get_audit_log(&self, wallet_handle: WalletHandle, from: Option<u64>, to: Option<u64>) -> IndyResult<Vec<AuditLogEntry>> {
+        match self.wallets.borrow().get(&wallet_handle) {
+            Some(wallet) => wallet.get_audit_log(from, to),
+            None => Err(err_msg(IndyErrorKind::InvalidWalletHandle, "Unknown wallet handle"))
+        }
+    }
+
+    pub fn set_change_listener(&self, wallet_handle: WalletHandle, change_listener: Option<Box<dyn Fn(&AuditLogEntry)>>) -> IndyResult<()> {
+        match self.wallets.borrow_mut().get_mut(&wallet_handle) {
+            Some(wallet) => {
+                wallet.set_change_listener(change_listener);
+                Ok(())
+            }
+            None => Err(err_msg(IndyErrorKind::InvalidWalletHandle, "Unknown wallet handle"))
+        }
+    }
+
+    pub fn get_wallet_stats(&self, wallet_handle: WalletHandle) -> IndyResult<WalletStats> {
+        match self.wallets.borrow().get(&wallet_handle) {
+            Some(wallet) => wallet.get_stats(),
+            None => Err(err_msg(IndyErrorKind::InvalidWalletHandle, "Unknown wallet handle"))
+        }
+    }
+
     pub fn check(&self, handle: WalletHandle) -> IndyResult<()> {
         match self.wallets.borrow().get(&handle) {
             Some(_) => Ok(()),
@@ -439,16 +637,11 @@ impl WalletService {
     pub fn export_wallet(&self, wallet_handle: WalletHandle, export_config: &ExportConfig, version: u32, key: (&KeyDerivationData, &MasterKey)) -> IndyResult<()> {
         trace!("export_wallet >>> wallet_handle: {:?}, export_config: {:?}, version: {:?}", wallet_handle, secret!(export_config), version);
 
-        if version != 0 {
-            return Err(err_msg(IndyErrorKind::InvalidState, "Unsupported version"));
-        }
-
-        let (key_data, key) = key;
+        WalletService::_check_export(version, export_config.filter.as_deref(), key.0)?;
 
-        let wallets = self.wallets.borrow();
-        let wallet = wallets
-            .get(&wallet_handle)
-            .ok_or_else(|| err_msg(IndyErrorKind::InvalidWalletHandle, "Unknown wallet handle"))?;
+        if !self.wallets.borrow().contains_key(&wallet_handle) {
+            return Err(err_msg(IndyErrorKind::InvalidWalletHandle, "Unknown wallet handle"));
+        }
 
         let path = PathBuf::from(&export_config.path);
 
@@ -464,13 +657,49 @@ impl WalletService {
                 .create_new(true)
                 .open(export_config.path.clone())?;
 
-        let res = export_continue(wallet, &mut export_file, version, key.clone(), key_data);
+        let res = self.export_wallet_to_writer(wallet_handle, &mut export_file, version, export_config.filter.as_deref(), key);
 
         trace!("export_wallet <<<");
 
         res
     }
 
+    pub fn export_wallet_to_writer(&self, wallet_handle: WalletHandle, writer: &mut dyn Write, version: u32, filter: Option<&[ExportFilter]>, key: (&KeyDerivationData, &MasterKey)) -> IndyResult<()> {
+        trace!("export_wallet_to_writer >>> wallet_handle: {:?}, version: {:?}, filter: {:?}", wallet_handle, version, filter);
+
+        WalletService::_check_export(version, filter, key.0)?;
+
+        let (key_data, key) = key;
+
+        let wallets = self.wallets.borrow();
+        let wallet = wallets
+            .get(&wallet_handle)
+            .ok_or_else(|| err_msg(IndyErrorKind::InvalidWalletHandle, "Unknown wallet handle"))?;
+
+        let res = export_continue(wallet, writer, version, filter, key.clone(), key_data);
+
+        trace!("export_wallet_to_writer <<<");
+
+        res
+    }
+
+    fn _check_export(version: u32, filter: Option<&[ExportFilter]>, key_data: &KeyDerivationData) -> IndyResult<()> {
+        if version != FULL_EXPORT_VERSION && version != INCREMENTAL_EXPORT_VERSION {
+            return Err(err_msg(IndyErrorKind::InvalidState, "Unsupported version"));
+        }
+
+        // Export journal covers all records of the wallet.
+        if filter.is_some() && version == INCREMENTAL_EXPORT_VERSION {
+            return Err(err_msg(IndyErrorKind::InvalidStructure, "Filter can't be used with incremental export"));
+        }
+
+        if let KeyDerivationData::Kek(..) = key_data {
+            return Err(err_msg(IndyErrorKind::InvalidStructure, "KEK key derivation method is not supported for wallet export"));
+        }
+
+        Ok(())
+    }
+
     pub fn import_wallet_prepare(&self,
                                  config: &Config,
                                  credentials: &Credentials,
@@ -482,7 +711,17 @@ impl WalletService {
                 .read(true)
                 .open(&export_config.path)?;
 
-        let (reader, import_key_derivation_data, nonce, chunk_size, header_bytes) = preparse_file_to_import(exported_file_to_import, &export_config.key)?;
+        self.import_wallet_prepare_from_reader(config, credentials, Box::new(exported_file_to_import), &export_config.key)
+    }
+
+    pub fn import_wallet_prepare_from_reader(&self,
+                                             config: &Config,
+                                             credentials: &Credentials,
+                                             reader: Box<dyn Read>,
+                                             import_key: &str) -> IndyResult<(WalletHandle, KeyDerivationData, KeyDerivationData)> {
+        trace!("import_wallet_prepare_from_reader >>> config: {:?}, credentials: {:?}", config, secret!(credentials));
+
+        let (reader, import_key_derivation_data, nonce, chunk_size, header_bytes) = preparse_file_to_import(reader, import_key)?;
         let key_data = KeyDerivationData::from_passphrase_with_new_salt(&credentials.key, &credentials.key_derivation_method);
 
         let wallet_handle = indy_utils::next_wallet_handle();
@@ -524,6 +763,57 @@ impl WalletService {
         res
     }
 
+    pub fn import_wallet_increment_prepare(&self, wallet_handle: WalletHandle, export_config: &ExportConfig) -> IndyResult<(i32, KeyDerivationData)> {
+        trace!("import_wallet_increment_prepare >>> wallet_handle: {:?}, export_config: {:?}", wallet_handle, secret!(export_config));
+
+        if !self.wallets.borrow().contains_key(&wallet_handle) {
+            return Err(err_msg(IndyErrorKind::InvalidWalletHandle, "Unknown wallet handle"));
+        }
+
+        let exported_file_to_import =
+            fs::OpenOptions::new()
+                .read(true)
+                .open(&export_config.path)?;
+
+        self.import_wallet_increment_prepare_from_reader(wallet_handle, Box::new(exported_file_to_import), &export_config.key)
+    }
+
+    pub fn import_wallet_increment_prepare_from_reader(&self,
+                                                       wallet_handle: WalletHandle,
+                                                       reader: Box<dyn Read>,
+                                                       import_key: &str) -> IndyResult<(i32, KeyDerivationData)> {
+        trace!("import_wallet_increment_prepare_from_reader >>> wallet_handle: {:?}", wallet_handle);
+
+        if !self.wallets.borrow().contains_key(&wallet_handle) {
+            return Err(err_msg(IndyErrorKind::InvalidWalletHandle, "Unknown wallet handle"));
+        }
+
+        let (reader, import_key_derivation_data, nonce, chunk_size, header_bytes) = preparse_file_to_import(reader, import_key)?;
+
+        let import_id = indy_utils::sequence::get_next_id();
+
+        self.pending_for_import_increment.borrow_mut().insert(import_id, (wallet_handle, reader, nonce, chunk_size, header_bytes));
+
+        Ok((import_id, import_key_derivation_data))
+    }
+
+    pub fn import_wallet_increment_continue(&self, import_id: i32, import_key: IndyResult<MasterKey>) -> IndyResult<()> {
+        let (wallet_handle, reader, nonce, chunk_size, header_bytes) = self.pending_for_import_increment.borrow_mut().remove(&import_id)
+            .ok_or_else(|| err_msg(IndyErrorKind::InvalidState, "Import data not found"))?;
+
+        let import_key = import_key?;
+
+        let wallets = self.wallets.borrow();
+        let wallet = wallets
+            .get(&wallet_handle)
+            .ok_or_else(|| err_msg(IndyErrorKind::InvalidWalletHandle, "Unknown wallet handle"))?;
+
+        let res = finish_import(wallet, reader, import_key, nonce, chunk_size, header_bytes);
+
+        trace!("import_wallet_increment <<<");
+        res
+    }
+
     pub fn get_wallets_count(&self) -> usize {
         self.wallets.borrow().len()
     }
@@ -583,12 +873,12 @@ impl WalletService {
         Ok(storage)
     }
 
-    fn _prepare_metadata(&self, master_key: &chacha20poly1305_ietf::Key, key_data: &KeyDerivationData, keys: &Keys) -> IndyResult<Vec<u8>> {
+    fn _prepare_metadata(&self, master_key: &chacha20poly1305_ietf::Key, key_data: &KeyDerivationData, keys: &Keys, audit_log: bool) -> IndyResult<Vec<u8>> {
         let encrypted_keys = keys.serialize_encrypted(master_key)?;
         let metadata = match key_data {
             KeyDerivationData::Raw(_) => {
                 Metadata::MetadataRaw(
-                    MetadataRaw { keys: encrypted_keys }
+                    MetadataRaw { keys: encrypted_keys, audit_log }
                 )
             }
             KeyDerivationData::Argon2iInt(_, salt) | KeyDerivationData::Argon2iMod(_, salt) => {
@@ -596,6 +886,16 @@ impl WalletService {
                     MetadataArgon {
                         keys: encrypted_keys,
                         master_key_salt: salt[..].to_vec(),
+                        audit_log,
+                    }
+                )
+            }
+            KeyDerivationData::Kek(provider, key_id, _) => {
+                Metadata::MetadataKek(
+                    MetadataKek {
+                        keys: encrypted_keys,
+                        wrapped_master_key: kek::get_kek_provider(provider)?.wrap_key(key_id, &master_key[..])?,
+                        audit_log,
                     }
                 )
             }
@@ -627,6 +927,7 @@ impl WalletService {
 #[serde(untagged)]
 pub enum Metadata {
     MetadataArgon(MetadataArgon),
+    MetadataKek(MetadataKek),
     MetadataRaw(MetadataRaw),
 }
 
@@ -634,20 +935,49 @@ impl Metadata {
     pub fn get_keys(&self) -> &Vec<u8> {
         match *self {
             Metadata::MetadataArgon(ref metadata) => &metadata.keys,
+            Metadata::MetadataKek(ref metadata) => &metadata.keys,
             Metadata::MetadataRaw(ref metadata) => &metadata.keys,
         }
     }
+
+    pub fn audit_log(&self) -> bool {
+        match *self {
+            Metadata::MetadataArgon(ref metadata) => metadata.audit_log,
+            Metadata::MetadataKek(ref metadata) => metadata.audit_log,
+            Metadata::MetadataRaw(ref metadata) => metadata.audit_log,
+        }
+    }
+
+    fn set_audit_log(&mut self) {
+        match *self {
+            Metadata::MetadataArgon(ref mut metadata) => metadata.audit_log = true,
+            Metadata::MetadataKek(ref mut metadata) => metadata.audit_log = true,
+            Metadata::MetadataRaw(ref mut metadata) => metadata.audit_log = true,
+        }
+    }
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
 pub struct MetadataArgon {
     pub keys: Vec<u8>,
     pub master_key_salt: Vec<u8>,
+    #[serde(default, skip_serializing_if = "Not::not")]
+    pub audit_log: bool,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct MetadataKek {
+    pub keys: Vec<u8>,
+    pub wrapped_master_key: Vec<u8>,
+    #[serde(default, skip_serializing_if = "Not::not")]
+    pub audit_log: bool,
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
 pub struct MetadataRaw {
-    pub keys: Vec<u8>
+    pub keys: Vec<u8>,
+    #[serde(default, skip_serializing_if = "Not::not")]
+    pub audit_log: bool,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
@@ -807,10 +1137,6 @@ fn short_type_name<T>() -> &'static str {
     type_name.rsplitn(2, "::").next().unwrap_or(type_name)
 }
 
-#[cfg(test)]
-#[macro_use]
-extern crate lazy_static;
-
 #[cfg(test)]
 #[macro_use]
 extern crate serde_json;
@@ -842,7 +1168,7 @@ mod tests {
             let rekey_data: Option<KeyDerivationData> = credentials.rekey.as_ref().map(|ref rekey|
                 KeyDerivationData::from_passphrase_with_new_salt(rekey, &credentials.rekey_derivation_method));
 
-            self.pending_for_open.borrow_mut().insert(wallet_handle, (WalletService::_get_wallet_id(config), storage, metadata, rekey_data.clone()));
+            self.pending_for_open.borrow_mut().insert(wallet_handle, (WalletService::_get_wallet_id(config), storage, metadata, rekey_data.clone(), config.audit_log.unwrap_or(false)));
 
             let key = key_derivation_data.calc_master_key()?;
 
@@ -868,7 +1194,7 @@ mod tests {
                     .read(true)
                     .open(&export_config.path)?;
 
-            let (reader, import_key_derivation_data, nonce, chunk_size, header_bytes) = preparse_file_to_import(exported_file_to_import, &export_config.key)?;
+            let (reader, import_key_derivation_data, nonce, chunk_size, header_bytes) = preparse_file_to_import(Box::new(exported_file_to_import) as Box<dyn Read>, &export_config.key)?;
             let key_data = KeyDerivationData::from_passphrase_with_new_salt(&credentials.key, &credentials.key_derivation_method);
 
             let wallet_handle = next_wallet_handle();
@@ -881,6 +1207,12 @@ mod tests {
             self.import_wallet_continue(wallet_handle, config, credentials, (import_key, master_key))
         }
 
+        pub fn import_wallet_increment(&self, wallet_handle: WalletHandle, export_config: &ExportConfig) -> IndyResult<()> {
+            let (import_id, import_key_derivation_data) = self.import_wallet_increment_prepare(wallet_handle, export_config)?;
+            let import_key = import_key_derivation_data.calc_master_key();
+            self.import_wallet_increment_continue(import_id, import_key)
+        }
+
         pub fn delete_wallet(&self, config: &Config, credentials: &Credentials) -> IndyResult<()> {
             if self.wallets.borrow_mut().values().any(|ref wallet| wallet.get_id() == WalletService::_get_wallet_id(config)) {
                 return Err(err_msg(IndyErrorKind::InvalidState, format!("Wallet has to be closed before deleting: {:?}", WalletService::_get_wallet_id(config))))?;
@@ -909,6 +1241,47 @@ mod tests {
         _cleanup("wallet_service_register_type_works");
     }
 
+    #[test]
+    fn wallet_service_register_storage_type_works_for_native() {
+        test::cleanup_wallet("wallet_service_register_storage_type_works_for_native");
+        {
+            let config = Config { storage_type: Some("native".to_string()), .._config("wallet_service_register_storage_type_works_for_native") };
+
+            let wallet_service = WalletService::new();
+            wallet_service.register_wallet_storage_type("native", Box::new(SQLiteStorageType::new())).unwrap();
+
+            let res = wallet_service.register_wallet_storage_type("native", Box::new(SQLiteStorageType::new()));
+            assert_kind!(IndyErrorKind::WalletStorageTypeAlreadyRegistered, res);
+
+            wallet_service.create_wallet(&config, &RAW_CREDENTIAL, (&RAW_KDD, &RAW_MASTER_KEY)).unwrap();
+            let wallet_handle = wallet_service.open_wallet(&config, &RAW_CREDENTIAL).unwrap();
+
+            wallet_service.add_record(wallet_handle, "type", "key1", "value1", &HashMap::new()).unwrap();
+            wallet_service.get_record(wallet_handle, "type", "key1", "{}").unwrap();
+        }
+        test::cleanup_wallet("wallet_service_register_storage_type_works_for_native");
+    }
+
+    #[test]
+    fn wallet_service_create_wallet_works_for_builtin_inmem() {
+        let config = Config { storage_type: Some("inmem".to_string()), .._config("wallet_service_create_wallet_works_for_builtin_inmem") };
+
+        let wallet_service = WalletService::new();
+
+        // built-in storage type name can't be taken by a custom storage
+        let res = wallet_service.register_wallet_storage_type("inmem", Box::new(InmemStorageType::new()));
+        assert_kind!(IndyErrorKind::WalletStorageTypeAlreadyRegistered, res);
+
+        wallet_service.create_wallet(&config, &RAW_CREDENTIAL, (&RAW_KDD, &RAW_MASTER_KEY)).unwrap();
+        let wallet_handle = wallet_service.open_wallet(&config, &RAW_CREDENTIAL).unwrap();
+
+        wallet_service.add_record(wallet_handle, "type", "key1", "value1", &HashMap::new()).unwrap();
+        wallet_service.get_record(wallet_handle, "type", "key1", "{}").unwrap();
+
+        wallet_service.close_wallet(wallet_handle).unwrap();
+        wallet_service.delete_wallet(&config, &RAW_CREDENTIAL).unwrap();
+    }
+
     #[test]
     fn wallet_service_create_wallet_works() {
         test::cleanup_wallet("wallet_service_create_wallet_works");
@@ -1153,6 +1526,47 @@ mod tests {
         test::cleanup_wallet("wallet_service_open_wallet_works_for_moderate_key_derivation");
     }
 
+    #[test]
+    fn wallet_service_open_wallet_works_for_kek_key_derivation() {
+        test::cleanup_wallet("wallet_service_open_wallet_works_for_kek_key_derivation");
+        {
+            let credentials = _kek_credentials("wallet_service_open_wallet_works_for_kek_key_derivation");
+            let key_data = KeyDerivationData::from_passphrase_with_new_salt(&credentials.key, &credentials.key_derivation_method);
+            let master_key = key_data.calc_master_key().unwrap();
+
+            let wallet_service = WalletService::new();
+            wallet_service.create_wallet(&_config("wallet_service_open_wallet_works_for_kek_key_derivation"), &credentials, (&key_data, &master_key)).unwrap();
+            let handle = wallet_service.open_wallet(&_config("wallet_service_open_wallet_works_for_kek_key_derivation"), &credentials).unwrap();
+
+            // cleanup
+            wallet_service.close_wallet(handle).unwrap();
+            fs::remove_file(&credentials.key).unwrap();
+        }
+        test::cleanup_wallet("wallet_service_open_wallet_works_for_kek_key_derivation");
+    }
+
+    #[test]
+    fn wallet_service_open_wallet_returns_error_for_kek_key_derivation_and_other_kek() {
+        test::cleanup_wallet("wallet_service_open_wallet_returns_error_for_kek_key_derivation_and_other_kek");
+        {
+            let credentials = _kek_credentials("wallet_service_open_wallet_returns_error_for_kek_key_derivation_and_other_kek");
+            let key_data = KeyDerivationData::from_passphrase_with_new_salt(&credentials.key, &credentials.key_derivation_method);
+            let master_key = key_data.calc_master_key().unwrap();
+
+            let wallet_service = WalletService::new();
+            wallet_service.create_wallet(&_config("wallet_service_open_wallet_returns_error_for_kek_key_derivation_and_other_kek"), &credentials, (&key_data, &master_key)).unwrap();
+
+            let other_credentials = _kek_credentials("wallet_service_open_wallet_returns_error_for_kek_key_derivation_and_other_kek_other");
+            let res = wallet_service.open_wallet(&_config("wallet_service_open_wallet_returns_error_for_kek_key_derivation_and_other_kek"), &other_credentials);
+            assert_kind!(IndyErrorKind::WalletAccessFailed, res);
+
+            // cleanup
+            fs::remove_file(&credentials.key).unwrap();
+            fs::remove_file(&other_credentials.key).unwrap();
+        }
+        test::cleanup_wallet("wallet_service_open_wallet_returns_error_for_kek_key_derivation_and_other_kek");
+    }
+
     #[test]
     fn wallet_service_open_wallet_works_for_two_wallets_with_same_ids_but_different_paths() {
         _cleanup("wallet_service_open_wallet_works_for_two_wallets_with_same_ids_but_different_paths");
@@ -1163,6 +1577,7 @@ mod tests {
             id: String::from("same_id"),
             storage_type: None,
             storage_config: None,
+            audit_log: None,
         };
 
         wallet_service.create_wallet(&config_1, &RAW_CREDENTIAL, (&RAW_KDD, &RAW_MASTER_KEY)).unwrap();
@@ -1174,6 +1589,7 @@ mod tests {
             storage_config: Some(json!({
                 "path": _custom_path("wallet_service_open_wallet_works_for_two_wallets_with_same_ids_but_different_paths")
             })),
+            audit_log: None,
         };
 
         wallet_service.create_wallet(&config_2, &RAW_CREDENTIAL, (&RAW_KDD, &RAW_MASTER_KEY)).unwrap();
@@ -1298,35 +1714,226 @@ mod tests {
     }
 
     #[test]
-    fn wallet_service_add_record_works_for_plugged() {
-        _cleanup("wallet_service_add_record_works_for_plugged");
+    fn wallet_service_get_audit_log_works() {
+        test::cleanup_wallet("wallet_service_get_audit_log_works");
+        {
+            let config = Config { audit_log: Some(true), .._config("wallet_service_get_audit_log_works") };
 
-        let wallet_service = WalletService::new();
-        _register_inmem_wallet(&wallet_service);
+            let wallet_service = WalletService::new();
+            wallet_service.create_wallet(&config, &RAW_CREDENTIAL, (&RAW_KDD, &RAW_MASTER_KEY)).unwrap();
+            let wallet_handle = wallet_service.open_wallet(&config, &RAW_CREDENTIAL).unwrap();
 
-        wallet_service.create_wallet(&_config_inmem(), &RAW_CREDENTIAL, (&RAW_KDD, &RAW_MASTER_KEY)).unwrap();
-        let wallet_handle = wallet_service.open_wallet(&_config_inmem(), &RAW_CREDENTIAL).unwrap();
+            wallet_service.add_record(wallet_handle, "type", "key1", "value1", &HashMap::new()).unwrap();
+            wallet_service.delete_record(wallet_handle, "type", "key1").unwrap();
 
-        wallet_service.add_record(wallet_handle, "type", "key1", "value1", &HashMap::new()).unwrap();
-        wallet_service.get_record(wallet_handle, "type", "key1", "{}").unwrap();
+            let entries = wallet_service.get_audit_log(wallet_handle, None, None).unwrap();
+            assert_eq!(2, entries.len());
+            assert!(entries.iter().all(|entry| entry.type_ == "type"));
+
+            wallet_service.close_wallet(wallet_handle).unwrap();
+        }
+        test::cleanup_wallet("wallet_service_get_audit_log_works");
     }
 
     #[test]
-    fn wallet_service_get_record_works_for_id_only() {
-        test::cleanup_wallet("wallet_service_get_record_works_for_id_only");
+    fn wallet_service_get_audit_log_works_for_reopened_wallet() {
+        test::cleanup_wallet("wallet_service_get_audit_log_works_for_reopened_wallet");
         {
             let wallet_service = WalletService::new();
-            wallet_service.create_wallet(&_config("wallet_service_get_record_works_for_id_only"), &RAW_CREDENTIAL, (&RAW_KDD, &RAW_MASTER_KEY)).unwrap();
-            let wallet_handle = wallet_service.open_wallet(&_config("wallet_service_get_record_works_for_id_only"), &RAW_CREDENTIAL).unwrap();
+            wallet_service.create_wallet(&_config("wallet_service_get_audit_log_works_for_reopened_wallet"), &RAW_CREDENTIAL, (&RAW_KDD, &RAW_MASTER_KEY)).unwrap();
 
+            // audit log enabled once is stored in wallet metadata
+            let config = Config { audit_log: Some(true), .._config("wallet_service_get_audit_log_works_for_reopened_wallet") };
+            let wallet_handle = wallet_service.open_wallet(&config, &RAW_CREDENTIAL).unwrap();
             wallet_service.add_record(wallet_handle, "type", "key1", "value1", &HashMap::new()).unwrap();
-            let record = wallet_service.get_record(wallet_handle, "type", "key1", &_fetch_options(false, false, false)).unwrap();
+            wallet_service.close_wallet(wallet_handle).unwrap();
 
-            assert!(record.get_value().is_none());
-            assert!(record.get_type().is_none());
-            assert!(record.get_tags().is_none());
-        }
-        test::cleanup_wallet("wallet_service_get_record_works_for_id_only");
+            let wallet_handle = wallet_service.open_wallet(&_config("wallet_service_get_audit_log_works_for_reopened_wallet"), &RAW_CREDENTIAL).unwrap();
+            wallet_service.delete_record(wallet_handle, "type", "key1").unwrap();
+
+            let entries = wallet_service.get_audit_log(wallet_handle, None, None).unwrap();
+            assert_eq!(vec![AuditLogOperation::Add, AuditLogOperation::Delete],
+                       entries.iter().map(|entry| entry.operation).collect::<Vec<AuditLogOperation>>());
+
+            wallet_service.close_wallet(wallet_handle).unwrap();
+        }
+        test::cleanup_wallet("wallet_service_get_audit_log_works_for_reopened_wallet");
+    }
+
+    #[test]
+    fn wallet_service_get_wallet_stats_works() {
+        test::cleanup_wallet("wallet_service_get_wallet_stats_works");
+        {
+            let wallet_service = WalletService::new();
+            wallet_service.create_wallet(&_config("wallet_service_get_wallet_stats_works"), &RAW_CREDENTIAL, (&RAW_KDD, &RAW_MASTER_KEY)).unwrap();
+            let wallet_handle = wallet_service.open_wallet(&_config("wallet_service_get_wallet_stats_works"), &RAW_CREDENTIAL).unwrap();
+
+            let mut tags = HashMap::new();
+            tags.insert("tag1".to_string(), "value1".to_string());
+            tags.insert("~tag2".to_string(), "value2".to_string());
+
+            wallet_service.add_record(wallet_handle, "type1", "key1", "value1", &tags).unwrap();
+            wallet_service.add_record(wallet_handle, "type1", "key2", "value2", &HashMap::new()).unwrap();
+            wallet_service.add_record(wallet_handle, "type2", "key1", "value1", &HashMap::new()).unwrap();
+
+            let stats = wallet_service.get_wallet_stats(wallet_handle).unwrap();
+            assert_eq!(3, stats.total_records);
+            assert_eq!(2, stats.total_tags);
+            assert_eq!(0, stats.corrupted_records);
+            assert_eq!(TypeStats { records: 2, tags: 2 }, stats.types["type1"]);
+            assert_eq!(TypeStats { records: 1, tags: 0 }, stats.types["type2"]);
+            assert!(stats.storage_size.unwrap() > 0);
+            assert!(stats.last_modified.is_some());
+
+            wallet_service.close_wallet(wallet_handle).unwrap();
+        }
+        test::cleanup_wallet("wallet_service_get_wallet_stats_works");
+    }
+
+    #[test]
+    fn wallet_service_get_wallet_stats_works_for_internal_records() {
+        test::cleanup_wallet("wallet_service_get_wallet_stats_works_for_internal_records");
+        {
+            let config = Config { audit_log: Some(true), .._config("wallet_service_get_wallet_stats_works_for_internal_records") };
+
+            let wallet_service = WalletService::new();
+            wallet_service.create_wallet(&config, &RAW_CREDENTIAL, (&RAW_KDD, &RAW_MASTER_KEY)).unwrap();
+            let wallet_handle = wallet_service.open_wallet(&config, &RAW_CREDENTIAL).unwrap();
+
+            wallet_service.add_record(wallet_handle, "type1", "key1", "value1", &HashMap::new()).unwrap();
+            wallet_service.add_record(wallet_handle, "type1", "key2", "value2", &HashMap::new()).unwrap();
+
+            // audit log entries are reported separately and aren't counted in totals
+            let stats = wallet_service.get_wallet_stats(wallet_handle).unwrap();
+            assert_eq!(2, stats.total_records);
+            assert_eq!(1, stats.types.len());
+            assert_eq!(TypeStats { records: 2, tags: 0 }, stats.types["type1"]);
+            assert_eq!(2, stats.internal[audit_log::AUDIT_LOG_TYPE].records);
+
+            wallet_service.close_wallet(wallet_handle).unwrap();
+        }
+        test::cleanup_wallet("wallet_service_get_wallet_stats_works_for_internal_records");
+    }
+
+    #[test]
+    fn wallet_service_get_wallet_stats_works_for_plugged() {
+        _cleanup("wallet_service_get_wallet_stats_works_for_plugged");
+        {
+            let wallet_service = WalletService::new();
+            _register_inmem_wallet(&wallet_service);
+
+            wallet_service.create_wallet(&_config_inmem(), &RAW_CREDENTIAL, (&RAW_KDD, &RAW_MASTER_KEY)).unwrap();
+            let wallet_handle = wallet_service.open_wallet(&_config_inmem(), &RAW_CREDENTIAL).unwrap();
+
+            let mut tags = HashMap::new();
+            tags.insert("tag1".to_string(), "value1".to_string());
+
+            wallet_service.add_record(wallet_handle, "type1", "key1", "value1", &tags).unwrap();
+            wallet_service.add_record(wallet_handle, "type1", "key2", "value2", &HashMap::new()).unwrap();
+
+            // Records are counted by full scan
+            let stats = wallet_service.get_wallet_stats(wallet_handle).unwrap();
+            assert_eq!(2, stats.total_records);
+            assert_eq!(TypeStats { records: 2, tags: 1 }, stats.types["type1"]);
+            assert!(stats.storage_size.is_none());
+
+            wallet_service.close_wallet(wallet_handle).unwrap();
+        }
+        _cleanup("wallet_service_get_wallet_stats_works_for_plugged");
+    }
+
+    #[test]
+    fn wallet_service_get_wallet_stats_works_for_plugged_stats_handlers() {
+        _cleanup("wallet_service_get_wallet_stats_works_for_plugged_stats_handlers");
+        {
+            let wallet_service = WalletService::new();
+            _register_inmem_wallet(&wallet_service);
+            wallet_service.register_wallet_storage_stats("plugged_inmem", InmemWallet::get_storage_stats, InmemWallet::free_storage_stats).unwrap();
+
+            wallet_service.create_wallet(&_config_inmem(), &RAW_CREDENTIAL, (&RAW_KDD, &RAW_MASTER_KEY)).unwrap();
+            let wallet_handle = wallet_service.open_wallet(&_config_inmem(), &RAW_CREDENTIAL).unwrap();
+
+            let mut tags = HashMap::new();
+            tags.insert("tag1".to_string(), "value1".to_string());
+
+            wallet_service.add_record(wallet_handle, "type1", "key1", "value1", &tags).unwrap();
+            wallet_service.add_record(wallet_handle, "type1", "key2", "value2", &HashMap::new()).unwrap();
+
+            // Storage size is reported by plugin only
+            let stats = wallet_service.get_wallet_stats(wallet_handle).unwrap();
+            assert_eq!(2, stats.total_records);
+            assert_eq!(TypeStats { records: 2, tags: 1 }, stats.types["type1"]);
+            assert!(stats.storage_size.unwrap() > 0);
+
+            wallet_service.close_wallet(wallet_handle).unwrap();
+        }
+        _cleanup("wallet_service_get_wallet_stats_works_for_plugged_stats_handlers");
+    }
+
+    #[test]
+    fn wallet_service_register_wallet_storage_stats_works_for_not_plugged_type() {
+        let wallet_service = WalletService::new();
+
+        let res = wallet_service.register_wallet_storage_stats("unknown", InmemWallet::get_storage_stats, InmemWallet::free_storage_stats);
+        assert_kind!(IndyErrorKind::UnknownWalletStorageType, res);
+
+        let res = wallet_service.register_wallet_storage_stats("default", InmemWallet::get_storage_stats, InmemWallet::free_storage_stats);
+        assert_kind!(IndyErrorKind::UnknownWalletStorageType, res);
+    }
+
+    #[test]
+    fn wallet_service_get_wallet_stats_works_for_invalid_handle() {
+        let wallet_service = WalletService::new();
+        let res = wallet_service.get_wallet_stats(INVALID_WALLET_HANDLE);
+        assert_kind!(IndyErrorKind::InvalidWalletHandle, res);
+    }
+
+    #[test]
+    fn wallet_service_add_record_works_for_audit_log_type() {
+        test::cleanup_wallet("wallet_service_add_record_works_for_audit_log_type");
+        {
+            let wallet_service = WalletService::new();
+            wallet_service.create_wallet(&_config("wallet_service_add_record_works_for_audit_log_type"), &RAW_CREDENTIAL, (&RAW_KDD, &RAW_MASTER_KEY)).unwrap();
+            let wallet_handle = wallet_service.open_wallet(&_config("wallet_service_add_record_works_for_audit_log_type"), &RAW_CREDENTIAL).unwrap();
+
+            let res = wallet_service.add_record(wallet_handle, audit_log::AUDIT_LOG_TYPE, "key1", "value1", &HashMap::new());
+            assert_kind!(IndyErrorKind::InvalidStructure, res);
+
+            wallet_service.close_wallet(wallet_handle).unwrap();
+        }
+        test::cleanup_wallet("wallet_service_add_record_works_for_audit_log_type");
+    }
+
+    #[test]
+    fn wallet_service_add_record_works_for_plugged() {
+        _cleanup("wallet_service_add_record_works_for_plugged");
+
+        let wallet_service = WalletService::new();
+        _register_inmem_wallet(&wallet_service);
+
+        wallet_service.create_wallet(&_config_inmem(), &RAW_CREDENTIAL, (&RAW_KDD, &RAW_MASTER_KEY)).unwrap();
+        let wallet_handle = wallet_service.open_wallet(&_config_inmem(), &RAW_CREDENTIAL).unwrap();
+
+        wallet_service.add_record(wallet_handle, "type", "key1", "value1", &HashMap::new()).unwrap();
+        wallet_service.get_record(wallet_handle, "type", "key1", "{}").unwrap();
+    }
+
+    #[test]
+    fn wallet_service_get_record_works_for_id_only() {
+        test::cleanup_wallet("wallet_service_get_record_works_for_id_only");
+        {
+            let wallet_service = WalletService::new();
+            wallet_service.create_wallet(&_config("wallet_service_get_record_works_for_id_only"), &RAW_CREDENTIAL, (&RAW_KDD, &RAW_MASTER_KEY)).unwrap();
+            let wallet_handle = wallet_service.open_wallet(&_config("wallet_service_get_record_works_for_id_only"), &RAW_CREDENTIAL).unwrap();
+
+            wallet_service.add_record(wallet_handle, "type", "key1", "value1", &HashMap::new()).unwrap();
+            let record = wallet_service.get_record(wallet_handle, "type", "key1", &_fetch_options(false, false, false)).unwrap();
+
+            assert!(record.get_value().is_none());
+            assert!(record.get_type().is_none());
+            assert!(record.get_tags().is_none());
+        }
+        test::cleanup_wallet("wallet_service_get_record_works_for_id_only");
     }
 
     #[test]
@@ -1519,6 +2126,57 @@ mod tests {
         assert_eq!(new_value, record.get_value().unwrap());
     }
 
+    #[test]
+    fn wallet_service_atomic_works() {
+        test::cleanup_wallet("wallet_service_atomic_works");
+        {
+            let wallet_service = WalletService::new();
+            wallet_service.create_wallet(&_config("wallet_service_atomic_works"), &RAW_CREDENTIAL, (&RAW_KDD, &RAW_MASTER_KEY)).unwrap();
+            let wallet_handle = wallet_service.open_wallet(&_config("wallet_service_atomic_works"), &RAW_CREDENTIAL).unwrap();
+
+            wallet_service.add_record(wallet_handle, "type", "key1", "value1", &HashMap::new()).unwrap();
+
+            wallet_service.atomic(wallet_handle, &mut || {
+                wallet_service.update_record_value(wallet_handle, "type", "key1", "value2")?;
+                wallet_service.add_record(wallet_handle, "type", "key2", "value2", &HashMap::new())
+            }).unwrap();
+
+            let record = wallet_service.get_record(wallet_handle, "type", "key1", &_fetch_options(false, true, false)).unwrap();
+            assert_eq!("value2", record.get_value().unwrap());
+            wallet_service.get_record(wallet_handle, "type", "key2", "{}").unwrap();
+        }
+        test::cleanup_wallet("wallet_service_atomic_works");
+    }
+
+    #[test]
+    fn wallet_service_atomic_works_for_rollback() {
+        test::cleanup_wallet("wallet_service_atomic_works_for_rollback");
+        {
+            let wallet_service = WalletService::new();
+            wallet_service.create_wallet(&_config("wallet_service_atomic_works_for_rollback"), &RAW_CREDENTIAL, (&RAW_KDD, &RAW_MASTER_KEY)).unwrap();
+            let wallet_handle = wallet_service.open_wallet(&_config("wallet_service_atomic_works_for_rollback"), &RAW_CREDENTIAL).unwrap();
+
+            wallet_service.add_record(wallet_handle, "type", "key1", "value1", &HashMap::new()).unwrap();
+
+            let res = wallet_service.atomic(wallet_handle, &mut || {
+                wallet_service.update_record_value(wallet_handle, "type", "key1", "value2")?;
+                wallet_service.update_record_value(wallet_handle, "type", "key2", "value2")
+            });
+            assert_kind!(IndyErrorKind::WalletItemNotFound, res);
+
+            let record = wallet_service.get_record(wallet_handle, "type", "key1", &_fetch_options(false, true, false)).unwrap();
+            assert_eq!("value1", record.get_value().unwrap());
+        }
+        test::cleanup_wallet("wallet_service_atomic_works_for_rollback");
+    }
+
+    #[test]
+    fn wallet_service_atomic_works_for_invalid_handle() {
+        let wallet_service = WalletService::new();
+        let res = wallet_service.atomic(INVALID_WALLET_HANDLE, &mut || Ok(()));
+        assert_kind!(IndyErrorKind::InvalidWalletHandle, res);
+    }
+
     /**
      * Delete tests
     */
@@ -1765,6 +2423,167 @@ mod tests {
         test::cleanup_wallet("wallet_service_search_records_works");
     }
 
+    #[test]
+    fn wallet_service_search_records_works_for_exist_and_numeric_operators() {
+        test::cleanup_wallet("wallet_service_search_records_works_for_exist_and_numeric_operators");
+        {
+            let wallet_service = WalletService::new();
+            wallet_service.create_wallet(&_config("wallet_service_search_records_works_for_exist_and_numeric_operators"), &RAW_CREDENTIAL, (&RAW_KDD, &RAW_MASTER_KEY)).unwrap();
+            let wallet_handle = wallet_service.open_wallet(&_config("wallet_service_search_records_works_for_exist_and_numeric_operators"), &RAW_CREDENTIAL).unwrap();
+
+            let mut tags = HashMap::new();
+            tags.insert("~age".to_string(), "9".to_string());
+            tags.insert("email".to_string(), "alice@example.com".to_string());
+            wallet_service.add_record(wallet_handle, "type", "key1", "value1", &tags).unwrap();
+
+            let mut tags = HashMap::new();
+            tags.insert("~age".to_string(), "18".to_string());
+            wallet_service.add_record(wallet_handle, "type", "key2", "value2", &tags).unwrap();
+
+            let mut tags = HashMap::new();
+            tags.insert("~age".to_string(), "unknown".to_string());
+            wallet_service.add_record(wallet_handle, "type", "key3", "value3", &tags).unwrap();
+
+            // Values that SQLite would cast to numbers by prefix
+            let mut tags = HashMap::new();
+            tags.insert("~age".to_string(), "1-2".to_string());
+            wallet_service.add_record(wallet_handle, "type", "key4", "value4", &tags).unwrap();
+
+            let mut tags = HashMap::new();
+            tags.insert("~age".to_string(), "e5".to_string());
+            wallet_service.add_record(wallet_handle, "type", "key5", "value5", &tags).unwrap();
+
+            let search_ids = |query: &str| -> Vec<String> {
+                let mut search = wallet_service.search_records(wallet_handle, "type", query, &_fetch_options(false, true, false)).unwrap();
+                let mut ids = Vec::new();
+                while let Some(record) = search.fetch_next_record().unwrap() {
+                    ids.push(record.get_id().to_string());
+                }
+                ids.sort();
+                ids
+            };
+
+            // Strings comparison puts "9" after "18", numeric one doesn't
+            assert_eq!(vec!["key1", "key2", "key3", "key5"], search_ids(r#"{"~age": {"$gte": "18"}}"#));
+            assert_eq!(vec!["key2"], search_ids(r#"{"~age": {"$gte": 18}}"#));
+            assert_eq!(vec!["key1"], search_ids(r#"{"~age": {"$lt": 10.5}}"#));
+
+            assert_eq!(vec!["key1"], search_ids(r#"{"email": {"$exist": true}}"#));
+            assert_eq!(vec!["key2", "key3", "key4", "key5"], search_ids(r#"{"email": {"$exist": false}}"#));
+            assert_eq!(vec!["key1", "key2", "key3", "key4", "key5"], search_ids(r#"{"~age": {"$exist": true}}"#));
+
+            let res = wallet_service.search_records(wallet_handle, "type", r#"{"email": {"$gt": 1}}"#, &_fetch_options(false, true, false));
+            assert_kind!(IndyErrorKind::WalletQueryError, res);
+        }
+        test::cleanup_wallet("wallet_service_search_records_works_for_exist_and_numeric_operators");
+    }
+
+    #[test]
+    fn wallet_service_records_work_for_ttl() {
+        test::cleanup_wallet("wallet_service_records_work_for_ttl");
+        {
+            let wallet_service = WalletService::new();
+            wallet_service.create_wallet(&_config("wallet_service_records_work_for_ttl"), &RAW_CREDENTIAL, (&RAW_KDD, &RAW_MASTER_KEY)).unwrap();
+            let wallet_handle = wallet_service.open_wallet(&_config("wallet_service_records_work_for_ttl"), &RAW_CREDENTIAL).unwrap();
+
+            let mut tags = HashMap::new();
+            tags.insert(TTL_TAG.to_string(), "3600".to_string());
+            wallet_service.add_record(wallet_handle, "type", "alive", "value1", &tags).unwrap();
+
+            let mut tags = HashMap::new();
+            tags.insert(TTL_TAG.to_string(), "0".to_string());
+            wallet_service.add_record(wallet_handle, "type", "expired", "value2", &tags).unwrap();
+
+            wallet_service.add_record(wallet_handle, "type", "eternal", "value3", &HashMap::new()).unwrap();
+
+            let record = wallet_service.get_record(wallet_handle, "type", "alive", &_fetch_options(false, true, true)).unwrap();
+            assert!(record.get_tags().unwrap().contains_key(EXPIRES_AT_TAG));
+            assert!(!record.get_tags().unwrap().contains_key(TTL_TAG));
+
+            let record = wallet_service.get_record(wallet_handle, "type", "alive", &_fetch_options(false, true, false)).unwrap();
+            assert!(record.get_tags().is_none());
+
+            let res = wallet_service.get_record(wallet_handle, "type", "expired", &_fetch_options(false, true, false));
+            assert_kind!(IndyErrorKind::WalletItemNotFound, res);
+
+            let mut search = wallet_service.search_records(wallet_handle, "type", "{}", &_fetch_options(false, true, false)).unwrap();
+            let mut ids = Vec::new();
+            while let Some(record) = search.fetch_next_record().unwrap() {
+                ids.push(record.get_id().to_string());
+            }
+            ids.sort();
+            assert_eq!(vec!["alive", "eternal"], ids);
+
+            let mut tags = HashMap::new();
+            tags.insert("tag".to_string(), "value".to_string());
+            wallet_service.update_record_tags(wallet_handle, "type", "alive", &tags).unwrap();
+
+            let record = wallet_service.get_record(wallet_handle, "type", "alive", &_fetch_options(false, true, true)).unwrap();
+            assert!(record.get_tags().unwrap().contains_key(EXPIRES_AT_TAG));
+
+            assert_eq!(1, wallet_service.purge_expired_records(wallet_handle, "type").unwrap());
+            assert_eq!(0, wallet_service.purge_expired_records(wallet_handle, "type").unwrap());
+
+            let res = wallet_service.delete_record(wallet_handle, "type", "expired");
+            assert_kind!(IndyErrorKind::WalletItemNotFound, res);
+
+            wallet_service.get_record(wallet_handle, "type", "alive", &_fetch_options(false, true, false)).unwrap();
+            wallet_service.get_record(wallet_handle, "type", "eternal", &_fetch_options(false, true, false)).unwrap();
+
+            wallet_service.close_wallet(wallet_handle).unwrap();
+        }
+        test::cleanup_wallet("wallet_service_records_work_for_ttl");
+    }
+
+    #[test]
+    fn wallet_service_add_record_works_for_invalid_ttl() {
+        test::cleanup_wallet("wallet_service_add_record_works_for_invalid_ttl");
+        {
+            let wallet_service = WalletService::new();
+            wallet_service.create_wallet(&_config("wallet_service_add_record_works_for_invalid_ttl"), &RAW_CREDENTIAL, (&RAW_KDD, &RAW_MASTER_KEY)).unwrap();
+            let wallet_handle = wallet_service.open_wallet(&_config("wallet_service_add_record_works_for_invalid_ttl"), &RAW_CREDENTIAL).unwrap();
+
+            let mut tags = HashMap::new();
+            tags.insert(TTL_TAG.to_string(), "forever".to_string());
+
+            let res = wallet_service.add_record(wallet_handle, "type", "key1", "value1", &tags);
+            assert_kind!(IndyErrorKind::InvalidStructure, res);
+
+            wallet_service.close_wallet(wallet_handle).unwrap();
+        }
+        test::cleanup_wallet("wallet_service_add_record_works_for_invalid_ttl");
+    }
+
+    #[test]
+    fn wallet_service_records_work_for_reserved_expiration_tag() {
+        test::cleanup_wallet("wallet_service_records_work_for_reserved_expiration_tag");
+        {
+            let wallet_service = WalletService::new();
+            wallet_service.create_wallet(&_config("wallet_service_records_work_for_reserved_expiration_tag"), &RAW_CREDENTIAL, (&RAW_KDD, &RAW_MASTER_KEY)).unwrap();
+            let wallet_handle = wallet_service.open_wallet(&_config("wallet_service_records_work_for_reserved_expiration_tag"), &RAW_CREDENTIAL).unwrap();
+
+            let mut tags = HashMap::new();
+            tags.insert(EXPIRES_AT_TAG.to_string(), "99999999999999999999".to_string());
+
+            let res = wallet_service.add_record(wallet_handle, "type", "key1", "value1", &tags);
+            assert_kind!(IndyErrorKind::InvalidStructure, res);
+
+            wallet_service.add_record(wallet_handle, "type", "key1", "value1", &HashMap::new()).unwrap();
+
+            let res = wallet_service.add_record_tags(wallet_handle, "type", "key1", &tags);
+            assert_kind!(IndyErrorKind::InvalidStructure, res);
+
+            let res = wallet_service.update_record_tags(wallet_handle, "type", "key1", &tags);
+            assert_kind!(IndyErrorKind::InvalidStructure, res);
+
+            let res = wallet_service.delete_record_tags(wallet_handle, "type", "key1", &[EXPIRES_AT_TAG]);
+            assert_kind!(IndyErrorKind::InvalidStructure, res);
+
+            wallet_service.close_wallet(wallet_handle).unwrap();
+        }
+        test::cleanup_wallet("wallet_service_records_work_for_reserved_expiration_tag");
+    }
+
     #[test]
     fn wallet_service_search_records_works_for_plugged_wallet() {
         _cleanup("wallet_service_search_records_works_for_plugged_wallet");
@@ -2059,6 +2878,291 @@ mod tests {
         test::cleanup_wallet("wallet_service_export_import_wallet_1_item");
     }
 
+    #[test]
+    fn wallet_service_export_import_wallet_with_buffer() {
+        test::cleanup_wallet("wallet_service_export_import_wallet_with_buffer1");
+        test::cleanup_wallet("wallet_service_export_import_wallet_with_buffer2");
+        {
+            let wallet_service = WalletService::new();
+            wallet_service.create_wallet(&_config("wallet_service_export_import_wallet_with_buffer1"), &RAW_CREDENTIAL, (&RAW_KDD, &RAW_MASTER_KEY)).unwrap();
+            let wallet_handle = wallet_service.open_wallet(&_config("wallet_service_export_import_wallet_with_buffer1"), &RAW_CREDENTIAL).unwrap();
+
+            wallet_service.add_record(wallet_handle, "type", "key1", "value1", &HashMap::new()).unwrap();
+
+            let (kdd, master_key) = _export_key_raw("key_wallet_service_export_import_wallet_with_buffer");
+            let mut buffer: Vec<u8> = Vec::new();
+            wallet_service.export_wallet_to_writer(wallet_handle, &mut buffer, FULL_EXPORT_VERSION, None, (&kdd, &master_key)).unwrap();
+
+            let config = _config("wallet_service_export_import_wallet_with_buffer2");
+            let (import_handle, key_data, import_key_data) = wallet_service
+                .import_wallet_prepare_from_reader(&config, &RAW_CREDENTIAL, Box::new(::std::io::Cursor::new(buffer)), &_export_config_raw("").key)
+                .unwrap();
+            let import_key = import_key_data.calc_master_key().unwrap();
+            let master_key = key_data.calc_master_key().unwrap();
+            wallet_service.import_wallet_continue(import_handle, &config, &RAW_CREDENTIAL, (import_key, master_key)).unwrap();
+
+            let wallet_handle = wallet_service.open_wallet(&config, &RAW_CREDENTIAL).unwrap();
+            wallet_service.get_record(wallet_handle, "type", "key1", "{}").unwrap();
+        }
+        test::cleanup_wallet("wallet_service_export_import_wallet_with_buffer1");
+        test::cleanup_wallet("wallet_service_export_import_wallet_with_buffer2");
+    }
+
+    #[test]
+    fn wallet_service_export_import_wallet_increment() {
+        test::cleanup_wallet("wallet_service_export_import_wallet_increment1");
+        test::cleanup_wallet("wallet_service_export_import_wallet_increment2");
+        let export_config = ExportConfig { incremental: Some(true), .._export_config_raw("wallet_service_export_import_wallet_increment") };
+        {
+            let wallet_service = WalletService::new();
+            wallet_service.create_wallet(&_config("wallet_service_export_import_wallet_increment1"), &RAW_CREDENTIAL, (&RAW_KDD, &RAW_MASTER_KEY)).unwrap();
+            let wallet_handle = wallet_service.open_wallet(&_config("wallet_service_export_import_wallet_increment1"), &RAW_CREDENTIAL).unwrap();
+
+            wallet_service.add_record(wallet_handle, "type", "key1", "value1", &HashMap::new()).unwrap();
+
+            let (kdd, master_key) = _export_key_raw("key_wallet_service_export_import_wallet_increment");
+            remove_exported_wallet(&export_config);
+            wallet_service.export_wallet(wallet_handle, &export_config, INCREMENTAL_EXPORT_VERSION, (&kdd, &master_key)).unwrap();
+
+            wallet_service.import_wallet(&_config("wallet_service_export_import_wallet_increment2"), &RAW_CREDENTIAL, &export_config).unwrap();
+            let imported_wallet_handle = wallet_service.open_wallet(&_config("wallet_service_export_import_wallet_increment2"), &RAW_CREDENTIAL).unwrap();
+            wallet_service.get_record(imported_wallet_handle, "type", "key1", "{}").unwrap();
+
+            wallet_service.add_record(wallet_handle, "type", "key2", "value2", &HashMap::new()).unwrap();
+            wallet_service.delete_record(wallet_handle, "type", "key1").unwrap();
+
+            remove_exported_wallet(&export_config);
+            wallet_service.export_wallet(wallet_handle, &export_config, INCREMENTAL_EXPORT_VERSION, (&kdd, &master_key)).unwrap();
+
+            wallet_service.import_wallet_increment(imported_wallet_handle, &export_config).unwrap();
+            wallet_service.get_record(imported_wallet_handle, "type", "key2", "{}").unwrap();
+            let res = wallet_service.get_record(imported_wallet_handle, "type", "key1", "{}");
+            assert_kind!(IndyErrorKind::WalletItemNotFound, res);
+
+            // The same increment can't be applied twice
+            let res = wallet_service.import_wallet_increment(imported_wallet_handle, &export_config);
+            assert_kind!(IndyErrorKind::InvalidStructure, res);
+        }
+        remove_exported_wallet(&export_config);
+        test::cleanup_wallet("wallet_service_export_import_wallet_increment1");
+        test::cleanup_wallet("wallet_service_export_import_wallet_increment2");
+    }
+
+    #[test]
+    fn wallet_service_export_import_wallet_increment_with_buffer() {
+        test::cleanup_wallet("wallet_service_export_import_wallet_increment_with_buffer1");
+        test::cleanup_wallet("wallet_service_export_import_wallet_increment_with_buffer2");
+        {
+            let wallet_service = WalletService::new();
+            wallet_service.create_wallet(&_config("wallet_service_export_import_wallet_increment_with_buffer1"), &RAW_CREDENTIAL, (&RAW_KDD, &RAW_MASTER_KEY)).unwrap();
+            let wallet_handle = wallet_service.open_wallet(&_config("wallet_service_export_import_wallet_increment_with_buffer1"), &RAW_CREDENTIAL).unwrap();
+
+            wallet_service.add_record(wallet_handle, "type", "key1", "value1", &HashMap::new()).unwrap();
+
+            let (kdd, master_key) = _export_key_raw("key_wallet_service_export_import_wallet_increment_with_buffer");
+            let mut buffer: Vec<u8> = Vec::new();
+            wallet_service.export_wallet_to_writer(wallet_handle, &mut buffer, INCREMENTAL_EXPORT_VERSION, None, (&kdd, &master_key)).unwrap();
+
+            let config = _config("wallet_service_export_import_wallet_increment_with_buffer2");
+            let (import_handle, key_data, import_key_data) = wallet_service
+                .import_wallet_prepare_from_reader(&config, &RAW_CREDENTIAL, Box::new(::std::io::Cursor::new(buffer)), &_export_config_raw("").key)
+                .unwrap();
+            let import_key = import_key_data.calc_master_key().unwrap();
+            let master_key_ = key_data.calc_master_key().unwrap();
+            wallet_service.import_wallet_continue(import_handle, &config, &RAW_CREDENTIAL, (import_key, master_key_)).unwrap();
+
+            let imported_wallet_handle = wallet_service.open_wallet(&config, &RAW_CREDENTIAL).unwrap();
+            wallet_service.get_record(imported_wallet_handle, "type", "key1", "{}").unwrap();
+
+            wallet_service.add_record(wallet_handle, "type", "key2", "value2", &HashMap::new()).unwrap();
+
+            let mut buffer: Vec<u8> = Vec::new();
+            wallet_service.export_wallet_to_writer(wallet_handle, &mut buffer, INCREMENTAL_EXPORT_VERSION, None, (&kdd, &master_key)).unwrap();
+
+            let (import_id, import_key_data) = wallet_service
+                .import_wallet_increment_prepare_from_reader(imported_wallet_handle, Box::new(::std::io::Cursor::new(buffer)), &_export_config_raw("").key)
+                .unwrap();
+            wallet_service.import_wallet_increment_continue(import_id, import_key_data.calc_master_key()).unwrap();
+
+            wallet_service.get_record(imported_wallet_handle, "type", "key2", "{}").unwrap();
+        }
+        test::cleanup_wallet("wallet_service_export_import_wallet_increment_with_buffer1");
+        test::cleanup_wallet("wallet_service_export_import_wallet_increment_with_buffer2");
+    }
+
+    #[test]
+    fn wallet_service_export_import_wallet_increment_works_for_reopened_wallet() {
+        test::cleanup_wallet("wallet_service_export_import_wallet_increment_works_for_reopened_wallet1");
+        test::cleanup_wallet("wallet_service_export_import_wallet_increment_works_for_reopened_wallet2");
+        let export_config = ExportConfig { incremental: Some(true), .._export_config_raw("wallet_service_export_import_wallet_increment_works_for_reopened_wallet") };
+        {
+            let wallet_service = WalletService::new();
+            wallet_service.create_wallet(&_config("wallet_service_export_import_wallet_increment_works_for_reopened_wallet1"), &RAW_CREDENTIAL, (&RAW_KDD, &RAW_MASTER_KEY)).unwrap();
+            let wallet_handle = wallet_service.open_wallet(&_config("wallet_service_export_import_wallet_increment_works_for_reopened_wallet1"), &RAW_CREDENTIAL).unwrap();
+
+            wallet_service.add_record(wallet_handle, "type", "key1", "value1", &HashMap::new()).unwrap();
+
+            let (kdd, master_key) = _export_key_raw("key_wallet_service_export_import_wallet_increment_works_for_reopened_wallet");
+            remove_exported_wallet(&export_config);
+            wallet_service.export_wallet(wallet_handle, &export_config, INCREMENTAL_EXPORT_VERSION, (&kdd, &master_key)).unwrap();
+
+            wallet_service.import_wallet(&_config("wallet_service_export_import_wallet_increment_works_for_reopened_wallet2"), &RAW_CREDENTIAL, &export_config).unwrap();
+            let imported_wallet_handle = wallet_service.open_wallet(&_config("wallet_service_export_import_wallet_increment_works_for_reopened_wallet2"), &RAW_CREDENTIAL).unwrap();
+
+            // Export journal is enabled again for the reopened wallet
+            wallet_service.close_wallet(wallet_handle).unwrap();
+            let wallet_handle = wallet_service.open_wallet(&_config("wallet_service_export_import_wallet_increment_works_for_reopened_wallet1"), &RAW_CREDENTIAL).unwrap();
+
+            wallet_service.update_record_value(wallet_handle, "type", "key1", "value2").unwrap();
+
+            remove_exported_wallet(&export_config);
+            wallet_service.export_wallet(wallet_handle, &export_config, INCREMENTAL_EXPORT_VERSION, (&kdd, &master_key)).unwrap();
+
+            wallet_service.import_wallet_increment(imported_wallet_handle, &export_config).unwrap();
+            let record = wallet_service.get_record(imported_wallet_handle, "type", "key1", "{}").unwrap();
+            assert_eq!("value2", record.get_value().unwrap());
+        }
+        remove_exported_wallet(&export_config);
+        test::cleanup_wallet("wallet_service_export_import_wallet_increment_works_for_reopened_wallet1");
+        test::cleanup_wallet("wallet_service_export_import_wallet_increment_works_for_reopened_wallet2");
+    }
+
+    #[test]
+    fn wallet_service_export_import_wallet_increment_works_for_audit_log() {
+        test::cleanup_wallet("wallet_service_export_import_wallet_increment_works_for_audit_log1");
+        test::cleanup_wallet("wallet_service_export_import_wallet_increment_works_for_audit_log2");
+        let export_config = ExportConfig { incremental: Some(true), .._export_config_raw("wallet_service_export_import_wallet_increment_works_for_audit_log") };
+        {
+            let config = Config { audit_log: Some(true), .._config("wallet_service_export_import_wallet_increment_works_for_audit_log1") };
+
+            let wallet_service = WalletService::new();
+            wallet_service.create_wallet(&config, &RAW_CREDENTIAL, (&RAW_KDD, &RAW_MASTER_KEY)).unwrap();
+            let wallet_handle = wallet_service.open_wallet(&config, &RAW_CREDENTIAL).unwrap();
+
+            wallet_service.add_record(wallet_handle, "type", "key1", "value1", &HashMap::new()).unwrap();
+
+            let (kdd, master_key) = _export_key_raw("key_wallet_service_export_import_wallet_increment_works_for_audit_log");
+            remove_exported_wallet(&export_config);
+            wallet_service.export_wallet(wallet_handle, &export_config, INCREMENTAL_EXPORT_VERSION, (&kdd, &master_key)).unwrap();
+
+            wallet_service.import_wallet(&_config("wallet_service_export_import_wallet_increment_works_for_audit_log2"), &RAW_CREDENTIAL, &export_config).unwrap();
+            let imported_wallet_handle = wallet_service.open_wallet(&_config("wallet_service_export_import_wallet_increment_works_for_audit_log2"), &RAW_CREDENTIAL).unwrap();
+
+            wallet_service.update_record_value(wallet_handle, "type", "key1", "value2").unwrap();
+
+            remove_exported_wallet(&export_config);
+            wallet_service.export_wallet(wallet_handle, &export_config, INCREMENTAL_EXPORT_VERSION, (&kdd, &master_key)).unwrap();
+
+            // audit log record of the update is a part of the increment
+            wallet_service.import_wallet_increment(imported_wallet_handle, &export_config).unwrap();
+
+            let operations: Vec<AuditLogOperation> = wallet_service.get_audit_log(imported_wallet_handle, None, None).unwrap()
+                .iter()
+                .map(|entry| entry.operation)
+                .collect();
+            assert!(operations.contains(&AuditLogOperation::Add));
+            assert!(operations.contains(&AuditLogOperation::Update));
+
+            wallet_service.close_wallet(wallet_handle).unwrap();
+            wallet_service.close_wallet(imported_wallet_handle).unwrap();
+        }
+        remove_exported_wallet(&export_config);
+        test::cleanup_wallet("wallet_service_export_import_wallet_increment_works_for_audit_log1");
+        test::cleanup_wallet("wallet_service_export_import_wallet_increment_works_for_audit_log2");
+    }
+
+    #[test]
+    fn wallet_service_records_work_for_export_internal_types() {
+        test::cleanup_wallet("wallet_service_records_work_for_export_internal_types");
+        {
+            let wallet_service = WalletService::new();
+            wallet_service.create_wallet(&_config("wallet_service_records_work_for_export_internal_types"), &RAW_CREDENTIAL, (&RAW_KDD, &RAW_MASTER_KEY)).unwrap();
+            let wallet_handle = wallet_service.open_wallet(&_config("wallet_service_records_work_for_export_internal_types"), &RAW_CREDENTIAL).unwrap();
+
+            for type_ in &["Indy::ExportCheckpoint", "Indy::ImportCheckpoint", "Indy::ExportJournal"] {
+                let res = wallet_service.add_record(wallet_handle, type_, "checkpoint", "value", &HashMap::new());
+                assert_kind!(IndyErrorKind::InvalidStructure, res);
+
+                let res = wallet_service.get_record(wallet_handle, type_, "checkpoint", "{}");
+                assert_kind!(IndyErrorKind::InvalidStructure, res);
+
+                let res = wallet_service.search_records(wallet_handle, type_, "{}", "{}");
+                assert_kind!(IndyErrorKind::InvalidStructure, res);
+
+                let res = wallet_service.delete_record(wallet_handle, type_, "checkpoint");
+                assert_kind!(IndyErrorKind::InvalidStructure, res);
+            }
+        }
+        test::cleanup_wallet("wallet_service_records_work_for_export_internal_types");
+    }
+
+    #[test]
+    fn wallet_service_export_import_wallet_with_filter() {
+        test::cleanup_wallet("wallet_service_export_import_wallet_with_filter1");
+        test::cleanup_wallet("wallet_service_export_import_wallet_with_filter2");
+        let export_config = ExportConfig {
+            filter: Some(vec![
+                ExportFilter { type_: "type1".to_string(), query: None },
+                ExportFilter { type_: "type2".to_string(), query: Some(json!({"tag": "a"})) },
+            ]),
+            .._export_config_raw("wallet_service_export_import_wallet_with_filter")
+        };
+        {
+            let wallet_service = WalletService::new();
+            wallet_service.create_wallet(&_config("wallet_service_export_import_wallet_with_filter1"), &RAW_CREDENTIAL, (&RAW_KDD, &RAW_MASTER_KEY)).unwrap();
+            let wallet_handle = wallet_service.open_wallet(&_config("wallet_service_export_import_wallet_with_filter1"), &RAW_CREDENTIAL).unwrap();
+
+            let tags_a = serde_json::from_str(r#"{"tag": "a"}"#).unwrap();
+            let tags_b = serde_json::from_str(r#"{"tag": "b"}"#).unwrap();
+
+            wallet_service.add_record(wallet_handle, "type1", "key1", "value1", &HashMap::new()).unwrap();
+            wallet_service.add_record(wallet_handle, "type2", "key2", "value2", &tags_a).unwrap();
+            wallet_service.add_record(wallet_handle, "type2", "key3", "value3", &tags_b).unwrap();
+            wallet_service.add_record(wallet_handle, "type3", "key4", "value4", &HashMap::new()).unwrap();
+
+            let (kdd, master_key) = _export_key_raw("key_wallet_service_export_import_wallet_with_filter");
+            remove_exported_wallet(&export_config);
+            wallet_service.export_wallet(wallet_handle, &export_config, FULL_EXPORT_VERSION, (&kdd, &master_key)).unwrap();
+
+            wallet_service.import_wallet(&_config("wallet_service_export_import_wallet_with_filter2"), &RAW_CREDENTIAL, &export_config).unwrap();
+            let wallet_handle = wallet_service.open_wallet(&_config("wallet_service_export_import_wallet_with_filter2"), &RAW_CREDENTIAL).unwrap();
+
+            wallet_service.get_record(wallet_handle, "type1", "key1", "{}").unwrap();
+            wallet_service.get_record(wallet_handle, "type2", "key2", "{}").unwrap();
+
+            let res = wallet_service.get_record(wallet_handle, "type2", "key3", "{}");
+            assert_kind!(IndyErrorKind::WalletItemNotFound, res);
+
+            let res = wallet_service.get_record(wallet_handle, "type3", "key4", "{}");
+            assert_kind!(IndyErrorKind::WalletItemNotFound, res);
+        }
+        remove_exported_wallet(&export_config);
+        test::cleanup_wallet("wallet_service_export_import_wallet_with_filter1");
+        test::cleanup_wallet("wallet_service_export_import_wallet_with_filter2");
+    }
+
+    #[test]
+    fn wallet_service_export_wallet_with_filter_for_incremental() {
+        test::cleanup_wallet("wallet_service_export_wallet_with_filter_for_incremental");
+        let export_config = ExportConfig {
+            incremental: Some(true),
+            filter: Some(vec![ExportFilter { type_: "type1".to_string(), query: None }]),
+            .._export_config_raw("wallet_service_export_wallet_with_filter_for_incremental")
+        };
+        {
+            let wallet_service = WalletService::new();
+            wallet_service.create_wallet(&_config("wallet_service_export_wallet_with_filter_for_incremental"), &RAW_CREDENTIAL, (&RAW_KDD, &RAW_MASTER_KEY)).unwrap();
+            let wallet_handle = wallet_service.open_wallet(&_config("wallet_service_export_wallet_with_filter_for_incremental"), &RAW_CREDENTIAL).unwrap();
+
+            let (kdd, master_key) = _export_key_raw("key_wallet_service_export_wallet_with_filter_for_incremental");
+            remove_exported_wallet(&export_config);
+            let res = wallet_service.export_wallet(wallet_handle, &export_config, INCREMENTAL_EXPORT_VERSION, (&kdd, &master_key));
+            assert_kind!(IndyErrorKind::InvalidStructure, res);
+        }
+        remove_exported_wallet(&export_config);
+        test::cleanup_wallet("wallet_service_export_wallet_with_filter_for_incremental");
+    }
+
     #[test]
     fn wallet_service_export_import_wallet_1_item_for_interactive_method() {
         test::cleanup_wallet("wallet_service_export_import_wallet_1_item_for_interactive_method");
@@ -2227,6 +3331,7 @@ mod tests {
             id: name.to_string(),
             storage_type: None,
             storage_config: None,
+            audit_log: None,
         }
     }
 
@@ -2235,14 +3340,16 @@ mod tests {
             id: name.to_string(),
             storage_type: Some("default".to_string()),
             storage_config: None,
+            audit_log: None,
         }
     }
 
     fn _config_inmem() -> Config {
         Config {
             id: "w1".to_string(),
-            storage_type: Some("inmem".to_string()),
+            storage_type: Some("plugged_inmem".to_string()),
             storage_config: None,
+            audit_log: None,
         }
     }
 
@@ -2251,6 +3358,7 @@ mod tests {
             id: name.to_string(),
             storage_type: Some("unknown".to_string()),
             storage_config: None,
+            audit_log: None,
         }
     }
 
@@ -2318,6 +3426,23 @@ mod tests {
         static ref RAW_MASTER_KEY: MasterKey =  RAW_KDD.calc_master_key().unwrap();
     }
 
+    fn _kek_credentials(name: &str) -> Credentials {
+        use rust_base58::ToBase58;
+
+        let mut path = environment::tmp_path();
+        fs::create_dir_all(&path).unwrap();
+        path.push(format!("{}.kek", name));
+        fs::write(&path, chacha20poly1305_ietf::gen_key()[..].to_base58()).unwrap();
+
+        Credentials {
+            key: path.to_str().unwrap().to_string(),
+            rekey: None,
+            storage_credentials: None,
+            key_derivation_method: KeyDerivationMethod::KEK("file".to_string()),
+            rekey_derivation_method: KeyDerivationMethod::KEK("file".to_string()),
+        }
+    }
+
     fn _credentials_invalid_raw() -> Credentials {
         Credentials {
             key: "key".to_string(),
@@ -2399,6 +3524,8 @@ mod tests {
             key: "export_key".to_string(),
             path: _export_file_path(name).to_str().unwrap().to_string(),
             key_derivation_method: KeyDerivationMethod::ARGON2I_MOD,
+            incremental: None,
+            filter: None,
         }
     }
 
@@ -2417,6 +3544,8 @@ mod tests {
             key: "export_key".to_string(),
             path: _export_file_path(name).to_str().unwrap().to_string(),
             key_derivation_method: KeyDerivationMethod::ARGON2I_INT,
+            incremental: None,
+            filter: None,
         }
     }
 
@@ -2429,6 +3558,8 @@ mod tests {
             key: "6nxtSiXFvBd593Y2DCed2dYvRY1PGK9WMtxCBjLzKgbw".to_string(),
             path: _export_file_path(name).to_str().unwrap().to_string(),
             key_derivation_method: KeyDerivationMethod::RAW,
+            incremental: None,
+            filter: None,
         }
     }
 
@@ -2444,7 +3575,7 @@ mod tests {
     fn _register_inmem_wallet(wallet_service: &WalletService) {
         wallet_service
             .register_wallet_storage(
-                "inmem",
+                "plugged_inmem",
                 InmemWallet::create,
                 InmemWallet::open,
                 InmemWallet::close,
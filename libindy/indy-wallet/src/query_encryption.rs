@@ -42,17 +42,7 @@ fn transform(query: Query, keys: &Keys) -> IndyResult<Operator> {
             Ok(Operator::Like(encrypted_name, encrypted_value))
         }
         Query::In(name, values) => {
-            let ename = TagName::from(name.clone())?;
-            let ename = match ename {
-                TagName::EncryptedTagName(ref name) => {
-                    let encrypted_name = encrypt_as_searchable(&name[..], &keys.tag_name_key, &keys.tags_hmac_key);
-                    TagName::EncryptedTagName(encrypted_name)
-                }
-                TagName::PlainTagName(ref name) => {
-                    let encrypted_name = encrypt_as_searchable(&name[..], &keys.tag_name_key, &keys.tags_hmac_key);
-                    TagName::PlainTagName(encrypted_name)
-                }
-            };
+            let ename = encrypt_name(name.clone(), keys)?;
             let mut encrypted_values: Vec<TargetValue> = Vec::with_capacity(values.len());
 
             for value in values {
@@ -60,6 +50,23 @@ fn transform(query: Query, keys: &Keys) -> IndyResult<Operator> {
             }
             Ok(Operator::In(ename, encrypted_values))
         }
+        Query::NumGt(name, value) => {
+            let (encrypted_name, value) = encrypt_name_number(name, value, keys)?;
+            Ok(Operator::NumGt(encrypted_name, value))
+        }
+        Query::NumGte(name, value) => {
+            let (encrypted_name, value) = encrypt_name_number(name, value, keys)?;
+            Ok(Operator::NumGte(encrypted_name, value))
+        }
+        Query::NumLt(name, value) => {
+            let (encrypted_name, value) = encrypt_name_number(name, value, keys)?;
+            Ok(Operator::NumLt(encrypted_name, value))
+        }
+        Query::NumLte(name, value) => {
+            let (encrypted_name, value) = encrypt_name_number(name, value, keys)?;
+            Ok(Operator::NumLte(encrypted_name, value))
+        }
+        Query::Exist(name) => Ok(Operator::Exist(encrypt_name(name, keys)?)),
         Query::And(operators) => Ok(Operator::And(transform_list_operators(operators, keys)?)),
         Query::Or(operators) => Ok(Operator::Or(transform_list_operators(operators, keys)?)),
         Query::Not(boxed_operator) => Ok(Operator::Not(Box::new(transform(*boxed_operator, keys)?)))
@@ -95,4 +102,26 @@ fn encrypt_name_value(name: String, value: String, keys: &Keys) -> IndyResult<(T
         }
         _ => Err(err_msg(IndyErrorKind::WalletQueryError, "Reached invalid combination of tag name and value while encrypting query"))
     }
-}
\ No newline at end of file
+}
+
+// Encrypts a single tag name keeping its kind
+fn encrypt_name(name: String, keys: &Keys) -> IndyResult<TagName> {
+    match TagName::from(name)? {
+        TagName::EncryptedTagName(ref name) => {
+            let encrypted_name = encrypt_as_searchable(&name[..], &keys.tag_name_key, &keys.tags_hmac_key);
+            Ok(TagName::EncryptedTagName(encrypted_name))
+        }
+        TagName::PlainTagName(ref name) => {
+            let encrypted_name = encrypt_as_searchable(&name[..], &keys.tag_name_key, &keys.tags_hmac_key);
+            Ok(TagName::PlainTagName(encrypted_name))
+        }
+    }
+}
+
+// Numeric comparison makes sense only for plaintext tag values, so only the tag name is encrypted
+fn encrypt_name_number(name: String, value: String, keys: &Keys) -> IndyResult<(TagName, TargetValue)> {
+    match encrypt_name(name, keys)? {
+        name @ TagName::PlainTagName(_) => Ok((name, TargetValue::Unencrypted(value))),
+        TagName::EncryptedTagName(_) => Err(err_msg(IndyErrorKind::WalletQueryError, "Numeric comparison can be used only for nonencrypted tag"))
+    }
+}
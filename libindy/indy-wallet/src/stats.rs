@@ -0,0 +1,68 @@
+use std::collections::BTreeMap;
+
+use indy_api_types::errors::prelude::*;
+
+use crate::audit_log::AUDIT_LOG_TYPE;
+use crate::encryption::decrypt_merged;
+use crate::export_import::is_internal_type;
+use crate::storage::WalletStorage;
+use crate::wallet::Keys;
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct TypeStats {
+    pub records: usize,
+    pub tags: usize,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct WalletStats {
+    // internal records aren't counted in totals
+    pub total_records: usize,
+    pub total_tags: usize,
+    // keyed by record type, e.g. "Indy::Did", "Indy::Key" or type of non-secret record
+    pub types: BTreeMap<String, TypeStats>,
+    // bookkeeping records maintained by the wallet itself: export journal, export and import checkpoints and audit log
+    pub internal: BTreeMap<String, TypeStats>,
+    // records with type that can't be decrypted, `indy_check_wallet` reports details
+    pub corrupted_records: usize,
+    pub storage_size: Option<u64>,
+    pub last_modified: Option<u64>,
+}
+
+pub(crate) fn get_stats(storage: &dyn WalletStorage, keys: &Keys) -> IndyResult<WalletStats> {
+    trace!("get_stats >>>");
+
+    let storage_stats = storage.get_stats()?;
+
+    let mut stats = WalletStats {
+        storage_size: storage_stats.storage_size,
+        last_modified: storage_stats.last_modified,
+        ..WalletStats::default()
+    };
+
+    for (type_, type_stats) in storage_stats.types {
+        let type_ = decrypt_merged(&type_, &keys.type_key)
+            .ok()
+            .and_then(|type_| String::from_utf8(type_).ok());
+
+        match type_ {
+            Some(type_) => {
+                // journal and audit log grow with every change, so they would skew the numbers of user records
+                if is_internal_type(&type_) || type_ == AUDIT_LOG_TYPE {
+                    stats.internal.insert(type_, TypeStats { records: type_stats.records, tags: type_stats.tags });
+                    continue;
+                }
+
+                stats.types.insert(type_, TypeStats { records: type_stats.records, tags: type_stats.tags });
+            }
+            None => stats.corrupted_records += type_stats.records
+        }
+
+        stats.total_records += type_stats.records;
+        stats.total_tags += type_stats.tags;
+    }
+
+    trace!("get_stats <<< stats: {:?}", stats);
+
+    Ok(stats)
+}
@@ -3,6 +3,9 @@ extern crate owning_ref;
 use std;
 use std::fs;
 use std::rc::Rc;
+use std::thread;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, UNIX_EPOCH};
 
 use rusqlite;
 use serde_json;
@@ -11,7 +14,7 @@ use indy_api_types::errors::prelude::*;
 use crate::language;
 use indy_utils::environment;
 
-use super::{EncryptedValue, StorageIterator, StorageRecord, Tag, TagName, WalletStorage, WalletStorageType};
+use super::{EncryptedValue, StorageCheckReport, StorageIterator, StorageRecord, StorageStats, StorageTypeStats, Tag, TagName, WalletStorage, WalletStorageType};
 use super::super::{RecordOptions, SearchOptions};
 
 use self::owning_ref::OwningHandle;
@@ -20,6 +23,9 @@ mod query;
 mod transaction;
 
 const _SQLITE_DB: &str = "sqlite.db";
+const _DEFAULT_BUSY_TIMEOUT_MS: u64 = 5000;
+const _BUSY_RETRIES: u64 = 5;
+const _BUSY_RETRY_DELAY_MS: u64 = 50;
 const _PLAIN_TAGS_QUERY: &str = "SELECT name, value from tags_plaintext where item_id = ?";
 const _ENCRYPTED_TAGS_QUERY: &str = "SELECT name, value from tags_encrypted where item_id = ?";
 const _CREATE_SCHEMA: &str = "
@@ -218,11 +224,18 @@ impl StorageIterator for SQLiteStorageIterator {
 #[derive(Deserialize, Debug)]
 struct Config {
     path: Option<String>,
+    // Allows other processes to use the same wallet concurrently.
+    #[serde(default)]
+    shared: bool,
+    // How long (in ms) to wait for a lock held by other process in shared mode.
+    busy_timeout: Option<u64>,
 }
 
 #[derive(Debug)]
 struct SQLiteStorage {
     conn: Rc<rusqlite::Connection>,
+    shared: bool,
+    path: PathBuf,
 }
 
 pub struct SQLiteStorageType {}
@@ -235,7 +248,7 @@ impl SQLiteStorageType {
 
     fn _db_path(id: &str, config: Option<&Config>) -> std::path::PathBuf {
         let mut path = match config {
-            Some(Config { path: Some(ref path) }) => std::path::PathBuf::from(path),
+            Some(Config { path: Some(ref path), .. }) => std::path::PathBuf::from(path),
             _ => environment::wallet_home_path()
         };
 
@@ -348,7 +361,7 @@ impl WalletStorage for SQLiteStorage {
     ///  * `IOError("IO error during storage operation:...")` - Failed connection or SQL query
     ///
     fn add(&self, type_: &[u8], id: &[u8], value: &EncryptedValue, tags: &[Tag]) -> IndyResult<()> {
-        let tx: transaction::Transaction = transaction::Transaction::new(&self.conn, rusqlite::TransactionBehavior::Deferred)?;
+        let tx: transaction::Transaction = self._begin_write()?;
         let res = tx.prepare_cached("INSERT INTO items (type, name, value, key) VALUES (?1, ?2, ?3, ?4)")?
             .insert(&[&type_.to_vec(), &id.to_vec(), &value.data, &value.key]);
 
@@ -386,7 +399,7 @@ impl WalletStorage for SQLiteStorage {
     }
 
     fn add_tags(&self, type_: &[u8], id: &[u8], tags: &[Tag]) -> IndyResult<()> {
-        let tx: transaction::Transaction = transaction::Transaction::new(&self.conn, rusqlite::TransactionBehavior::Deferred)?;
+        let tx: transaction::Transaction = self._begin_write()?;
 
         let item_id: i64 = tx.prepare_cached("SELECT id FROM items WHERE type = ?1 AND name = ?2")?
             .query_row(&[&type_.to_vec(), &id.to_vec()], |row| row.get(0))?;
@@ -408,7 +421,7 @@ impl WalletStorage for SQLiteStorage {
     }
 
     fn update_tags(&self, type_: &[u8], id: &[u8], tags: &[Tag]) -> IndyResult<()> {
-        let tx: transaction::Transaction = transaction::Transaction::new(&self.conn, rusqlite::TransactionBehavior::Deferred)?;
+        let tx: transaction::Transaction = self._begin_write()?;
 
         let item_id: i64 = tx.prepare_cached("SELECT id FROM items WHERE type = ?1 AND name = ?2")?
             .query_row(&[&type_.to_vec(), &id.to_vec()], |row| row.get(0))?;
@@ -436,7 +449,7 @@ impl WalletStorage for SQLiteStorage {
         let item_id: i64 = self.conn.prepare_cached("SELECT id FROM items WHERE type =?1 AND name = ?2")?
             .query_row(&[&type_.to_vec(), &id.to_vec()], |row| row.get(0))?;
 
-        let tx: transaction::Transaction = transaction::Transaction::new(&self.conn, rusqlite::TransactionBehavior::Deferred)?;
+        let tx: transaction::Transaction = self._begin_write()?;
         {
             let mut enc_tag_delete_stmt = tx.prepare_cached("DELETE FROM tags_encrypted WHERE item_id = ?1 AND name = ?2")?;
             let mut plain_tag_delete_stmt = tx.prepare_cached("DELETE FROM tags_plaintext WHERE item_id = ?1 AND name = ?2")?;
@@ -570,9 +583,143 @@ impl WalletStorage for SQLiteStorage {
     fn close(&mut self) -> IndyResult<()> {
         Ok(())
     }
+
+    fn check_consistency(&self, repair: bool) -> IndyResult<StorageCheckReport> {
+        let mut stmt = self.conn.prepare("PRAGMA integrity_check")?;
+
+        let integrity_errors = stmt
+            .query_map([], |row| row.get::<_, String>(0))?
+            .collect::<Result<Vec<String>, _>>()?
+            .into_iter()
+            .filter(|res| res != "ok")
+            .collect::<Vec<String>>();
+
+        let mut orphaned_tags = 0;
+
+        for table in &["tags_encrypted", "tags_plaintext"] {
+            let count: i64 = self.conn.query_row(
+                &format!("SELECT COUNT(*) FROM {} WHERE item_id NOT IN (SELECT id FROM items)", table),
+                [],
+                |row| row.get(0),
+            )?;
+
+            if repair && count > 0 {
+                self.conn.execute(&format!("DELETE FROM {} WHERE item_id NOT IN (SELECT id FROM items)", table), [])?;
+            }
+
+            orphaned_tags += count as usize;
+        }
+
+        Ok(StorageCheckReport { integrity_errors, orphaned_tags })
+    }
+
+    // Counts are computed by the database, so records are neither fetched nor decrypted.
+    fn get_stats(&self) -> IndyResult<StorageStats> {
+        let mut stats = StorageStats::default();
+
+        {
+            let mut stmt = self.conn.prepare("SELECT type, COUNT(*) FROM items GROUP BY type")?;
+            let rows = stmt.query_map([], |row| Ok((row.get::<_, Vec<u8>>(0)?, row.get::<_, i64>(1)?)))?;
+
+            for row in rows {
+                let (type_, count) = row?;
+                stats.types.entry(type_).or_insert_with(StorageTypeStats::default).records = count as usize;
+            }
+        }
+
+        for table in &["tags_encrypted", "tags_plaintext"] {
+            let mut stmt = self.conn.prepare(
+                &format!("SELECT i.type, COUNT(*) FROM {} AS t JOIN items AS i ON i.id = t.item_id GROUP BY i.type", table))?;
+            let rows = stmt.query_map([], |row| Ok((row.get::<_, Vec<u8>>(0)?, row.get::<_, i64>(1)?)))?;
+
+            for row in rows {
+                let (type_, count) = row?;
+                stats.types.entry(type_).or_insert_with(StorageTypeStats::default).tags += count as usize;
+            }
+        }
+
+        let files = _db_files_metadata(&self.path);
+
+        stats.storage_size = Some(files.iter().map(fs::Metadata::len).sum());
+        stats.last_modified = files.iter()
+            .filter_map(|metadata| metadata.modified().ok())
+            .filter_map(|modified| modified.duration_since(UNIX_EPOCH).ok())
+            .map(|modified| modified.as_secs())
+            .max();
+
+        Ok(stats)
+    }
+
+    fn get_plain_tag(&self, type_: &[u8], id: &[u8], tag_name: &[u8]) -> IndyResult<Option<String>> {
+        let value: Option<String> = self.conn.query_row(
+            "SELECT tags_plaintext.value FROM items \
+            LEFT JOIN tags_plaintext ON tags_plaintext.item_id = items.id AND tags_plaintext.name = ?3 \
+            WHERE items.type = ?1 AND items.name = ?2",
+            &[&type_.to_vec(), &id.to_vec(), &tag_name.to_vec()],
+            |row| row.get(0),
+        )?;
+
+        Ok(value)
+    }
+
+    fn purge_expired(&self, type_: &[u8], tag_name: &[u8], expired_to: &str) -> IndyResult<Vec<Vec<u8>>> {
+        const EXPIRED_CONDITION: &str = "type = ?1 AND id IN \
+            (SELECT item_id FROM tags_plaintext WHERE name = ?2 AND value <= ?3)";
+
+        let tx: transaction::Transaction = self._begin_write()?;
+
+        let names = tx.prepare(&format!("SELECT name FROM items WHERE {}", EXPIRED_CONDITION))?
+            .query_map(rusqlite::params![&type_.to_vec(), &tag_name.to_vec(), &expired_to], |row| row.get(0))?
+            .collect::<Result<Vec<Vec<u8>>, _>>()?;
+
+        tx.execute(&format!("DELETE FROM items WHERE {}", EXPIRED_CONDITION),
+                   rusqlite::params![&type_.to_vec(), &tag_name.to_vec(), &expired_to])?;
+
+        tx.commit()?;
+        Ok(names)
+    }
+
+    fn atomic(&self, f: &mut dyn FnMut() -> IndyResult<()>) -> IndyResult<()> {
+        let tx: transaction::Transaction = self._begin_write()?;
+        f()?;
+        tx.commit()?;
+        Ok(())
+    }
+}
+
+// In WAL mode recent changes live in the -wal file until they are checkpointed into the database file.
+fn _db_files_metadata(path: &Path) -> Vec<fs::Metadata> {
+    let mut wal_path = path.as_os_str().to_owned();
+    wal_path.push("-wal");
+
+    [path.to_path_buf(), PathBuf::from(wal_path)]
+        .iter()
+        .filter_map(|path| fs::metadata(path).ok())
+        .collect()
 }
 
 impl SQLiteStorage {
+    // In shared mode write transactions take the write lock at once. Upgrading a deferred
+    // transaction from read to write can fail with SQLITE_BUSY without waiting for busy timeout.
+    fn _begin_write(&self) -> IndyResult<transaction::Transaction> {
+        if !self.shared {
+            return Ok(transaction::Transaction::new(&self.conn, rusqlite::TransactionBehavior::Deferred)?);
+        }
+
+        let mut attempt = 0;
+
+        loop {
+            match transaction::Transaction::new(&self.conn, rusqlite::TransactionBehavior::Immediate) {
+                Err(ref err) if _is_busy(err) && attempt < _BUSY_RETRIES => {
+                    attempt += 1;
+                    trace!("_begin_write: database is busy, retry {}", attempt);
+                    thread::sleep(Duration::from_millis(_BUSY_RETRY_DELAY_MS * attempt));
+                }
+                res => return res.map_err(IndyError::from)
+            }
+        }
+    }
+
     fn _prepare_statement(&self, sql: &str) -> IndyResult<OwningHandle<Rc<rusqlite::Connection>, Box<rusqlite::Statement<'static>>>> {
         OwningHandle::try_new(self.conn.clone(), |conn| {
             unsafe { (*conn).prepare(sql) }.map(Box::new).map_err(IndyError::from)
@@ -580,6 +727,14 @@ impl SQLiteStorage {
     }
 }
 
+fn _is_busy(err: &rusqlite::Error) -> bool {
+    match err {
+        rusqlite::Error::SqliteFailure(rusqlite::ffi::Error { code: rusqlite::ffi::ErrorCode::DatabaseBusy, .. }, _) |
+        rusqlite::Error::SqliteFailure(rusqlite::ffi::Error { code: rusqlite::ffi::ErrorCode::DatabaseLocked, .. }, _) => true,
+        _ => false
+    }
+}
+
 
 impl WalletStorageType for SQLiteStorageType {
     ///
@@ -724,6 +879,15 @@ impl WalletStorageType for SQLiteStorageType {
 
         let conn = rusqlite::Connection::open(db_file_path.as_path())?;
 
+        let shared = config.as_ref().map(|config| config.shared).unwrap_or(false);
+
+        if shared {
+            let busy_timeout = config.as_ref().and_then(|config| config.busy_timeout).unwrap_or(_DEFAULT_BUSY_TIMEOUT_MS);
+            conn.busy_timeout(Duration::from_millis(busy_timeout))?;
+
+            let _: String = conn.query_row("PRAGMA locking_mode = NORMAL", [], |row| { row.get(0) })?;
+        }
+
         // set journal mode to WAL, because it provides better performance.
         let journal_mode: String = conn.query_row(
             "PRAGMA journal_mode = WAL",
@@ -737,7 +901,7 @@ impl WalletStorageType for SQLiteStorageType {
             conn.execute("PRAGMA synchronous = FULL", [])?;
         }
 
-        Ok(Box::new(SQLiteStorage { conn: Rc::new(conn) }))
+        Ok(Box::new(SQLiteStorage { conn: Rc::new(conn), shared, path: db_file_path }))
     }
 }
 
@@ -948,6 +1112,42 @@ mod tests {
         _cleanup("sqlite_storage_set_get_works_for_reopen");
     }
 
+    #[test]
+    fn sqlite_storage_works_for_shared_access() {
+        _cleanup("sqlite_storage_works_for_shared_access");
+        {
+            _storage("sqlite_storage_works_for_shared_access");
+
+            let config = r#"{"shared": true, "busy_timeout": 10000}"#;
+
+            let writers: Vec<thread::JoinHandle<()>> = (0..2u8)
+                .map(|writer| thread::spawn(move || {
+                    let storage = SQLiteStorageType::new().open_storage("sqlite_storage_works_for_shared_access", Some(config), None).unwrap();
+
+                    for i in 0..20u8 {
+                        storage.add(&_type1(), &[writer, i], &_value1(), &_tags()).unwrap();
+                        storage.update_tags(&_type1(), &[writer, i], &_new_tags()).unwrap();
+                    }
+                }))
+                .collect();
+
+            for writer in writers {
+                writer.join().unwrap();
+            }
+
+            let storage = SQLiteStorageType::new().open_storage("sqlite_storage_works_for_shared_access", Some(config), None).unwrap();
+            let mut iterator = storage.get_all().unwrap();
+
+            let mut count = 0;
+            while let Some(record) = iterator.next().unwrap() {
+                assert_eq!(_sort(record.tags.unwrap()), _sort(_new_tags()));
+                count += 1;
+            }
+            assert_eq!(40, count);
+        }
+        _cleanup("sqlite_storage_works_for_shared_access");
+    }
+
     #[test]
     fn sqlite_storage_get_works_for_wrong_key() {
         _cleanup("sqlite_storage_get_works_for_wrong_key");
@@ -1031,6 +1231,101 @@ mod tests {
         _cleanup("sqlite_storage_get_all_works");
     }
 
+    #[test]
+    fn sqlite_storage_get_stats_works() {
+        _cleanup("sqlite_storage_get_stats_works");
+        {
+            let storage = _storage("sqlite_storage_get_stats_works");
+            storage.add(&_type1(), &_id1(), &_value1(), &_tags()).unwrap();
+            storage.add(&_type1(), &_id2(), &_value2(), &[]).unwrap();
+            storage.add(&_type2(), &_id1(), &_value1(), &_tags()).unwrap();
+
+            let stats = storage.get_stats().unwrap();
+            assert_eq!(stats.types.len(), 2);
+            assert_eq!(stats.types[&_type1()], StorageTypeStats { records: 2, tags: _tags().len() });
+            assert_eq!(stats.types[&_type2()], StorageTypeStats { records: 1, tags: _tags().len() });
+            assert!(stats.storage_size.unwrap() > 0);
+            assert!(stats.last_modified.is_some());
+        }
+        _cleanup("sqlite_storage_get_stats_works");
+    }
+
+    #[test]
+    fn sqlite_storage_get_plain_tag_works() {
+        _cleanup("sqlite_storage_get_plain_tag_works");
+        {
+            let storage = _storage("sqlite_storage_get_plain_tag_works");
+            storage.add(&_type1(), &_id1(), &_value1(), &[Tag::PlainText(vec![7, 7], "0010".to_string()), Tag::Encrypted(vec![8, 8], vec![9, 9])]).unwrap();
+
+            assert_eq!(Some("0010".to_string()), storage.get_plain_tag(&_type1(), &_id1(), &[7, 7]).unwrap());
+            assert_eq!(None, storage.get_plain_tag(&_type1(), &_id1(), &[8, 8]).unwrap());
+
+            let res = storage.get_plain_tag(&_type1(), &_id2(), &[7, 7]);
+            assert_kind!(IndyErrorKind::WalletItemNotFound, res);
+        }
+        _cleanup("sqlite_storage_get_plain_tag_works");
+    }
+
+    #[test]
+    fn sqlite_storage_purge_expired_works() {
+        _cleanup("sqlite_storage_purge_expired_works");
+        {
+            let storage = _storage("sqlite_storage_purge_expired_works");
+            storage.add(&_type1(), &_id1(), &_value1(), &[Tag::PlainText(vec![7, 7], "0010".to_string())]).unwrap();
+            storage.add(&_type1(), &_id2(), &_value2(), &[Tag::PlainText(vec![7, 7], "0030".to_string())]).unwrap();
+            storage.add(&_type2(), &_id1(), &_value1(), &[Tag::PlainText(vec![7, 7], "0010".to_string())]).unwrap();
+
+            let purged = storage.purge_expired(&_type1(), &[7, 7], "0020").unwrap();
+            assert_eq!(purged, vec![_id1()]);
+
+            let res = storage.get(&_type1(), &_id1(), r##"{"retrieveType": false, "retrieveValue": true, "retrieveTags": true}"##);
+            assert_kind!(IndyErrorKind::WalletItemNotFound, res);
+
+            storage.get(&_type1(), &_id2(), r##"{"retrieveType": false, "retrieveValue": true, "retrieveTags": true}"##).unwrap();
+            storage.get(&_type2(), &_id1(), r##"{"retrieveType": false, "retrieveValue": true, "retrieveTags": true}"##).unwrap();
+        }
+        _cleanup("sqlite_storage_purge_expired_works");
+    }
+
+    #[test]
+    fn sqlite_storage_atomic_works() {
+        _cleanup("sqlite_storage_atomic_works");
+        {
+            let storage = _storage("sqlite_storage_atomic_works");
+
+            storage.atomic(&mut || {
+                storage.add(&_type1(), &_id1(), &_value1(), &_tags())?;
+                storage.update(&_type1(), &_id1(), &_value2())
+            }).unwrap();
+
+            let record = storage.get(&_type1(), &_id1(), r##"{"retrieveType": false, "retrieveValue": true, "retrieveTags": false}"##).unwrap();
+            assert_eq!(record.value.unwrap(), _value2());
+        }
+        _cleanup("sqlite_storage_atomic_works");
+    }
+
+    #[test]
+    fn sqlite_storage_atomic_works_for_rollback() {
+        _cleanup("sqlite_storage_atomic_works_for_rollback");
+        {
+            let storage = _storage("sqlite_storage_atomic_works_for_rollback");
+            storage.add(&_type1(), &_id1(), &_value1(), &_tags()).unwrap();
+
+            let res = storage.atomic(&mut || {
+                storage.add(&_type1(), &_id2(), &_value2(), &_tags())?;
+                storage.add(&_type1(), &_id1(), &_value1(), &_tags())
+            });
+            assert_kind!(IndyErrorKind::WalletItemAlreadyExists, res);
+
+            let res = storage.get(&_type1(), &_id2(), r##"{"retrieveType": false, "retrieveValue": true, "retrieveTags": false}"##);
+            assert_kind!(IndyErrorKind::WalletItemNotFound, res);
+
+            // storage is usable after rollback
+            storage.add(&_type1(), &_id2(), &_value2(), &_tags()).unwrap();
+        }
+        _cleanup("sqlite_storage_atomic_works_for_rollback");
+    }
+
     #[test]
     fn sqlite_storage_get_all_works_for_empty() {
         _cleanup("sqlite_storage_get_all_works_for_empty");
@@ -47,6 +47,11 @@ fn operator_to_sql<'a>(op: &'a Operator, arguments: &mut Vec<&'a dyn ToSql>) ->
         Operator::Lte(ref tag_name, ref target_value) => lte_to_sql(tag_name, target_value, arguments),
         Operator::Like(ref tag_name, ref target_value) => like_to_sql(tag_name, target_value, arguments),
         Operator::In(ref tag_name, ref target_values) => in_to_sql(tag_name, target_values, arguments),
+        Operator::NumGt(ref tag_name, ref target_value) => num_cmp_to_sql(tag_name, target_value, ">", "$gt", arguments),
+        Operator::NumGte(ref tag_name, ref target_value) => num_cmp_to_sql(tag_name, target_value, ">=", "$gte", arguments),
+        Operator::NumLt(ref tag_name, ref target_value) => num_cmp_to_sql(tag_name, target_value, "<", "$lt", arguments),
+        Operator::NumLte(ref tag_name, ref target_value) => num_cmp_to_sql(tag_name, target_value, "<=", "$lte", arguments),
+        Operator::Exist(ref tag_name) => exist_to_sql(tag_name, arguments),
         Operator::And(ref suboperators) => and_to_sql(suboperators, arguments),
         Operator::Or(ref suboperators) => or_to_sql(suboperators, arguments),
         Operator::Not(ref suboperator) => not_to_sql(suboperator, arguments),
@@ -192,6 +197,38 @@ fn in_to_sql<'a>(name: &'a TagName, values: &'a Vec<TargetValue>, arguments: &mu
 }
 
 
+// Only decimal values ([+-]digits[.digits]) take part in numeric comparison,
+// otherwise SQLite would silently cast any other string to a number prefix or 0.
+fn num_cmp_to_sql<'a>(name: &'a TagName, value: &'a TargetValue, sql_op: &str, wql_op: &str, arguments: &mut Vec<&'a dyn ToSql>) -> IndyResult<String> {
+    match (name, value) {
+        (&TagName::PlainTagName(ref queried_name), &TargetValue::Unencrypted(ref queried_value)) => {
+            arguments.push(queried_name);
+            arguments.push(queried_value);
+            Ok(format!("(i.id in (SELECT item_id FROM \
+                        (SELECT item_id, value, CASE WHEN substr(value, 1, 1) IN ('+', '-') THEN substr(value, 2) ELSE value END AS digits \
+                        FROM tags_plaintext WHERE name = ?) \
+                        WHERE digits GLOB '[0-9]*' AND digits NOT GLOB '*[^0-9.]*' AND digits NOT GLOB '*.*.*' AND digits NOT GLOB '*.' \
+                        AND CAST(value AS REAL) {} CAST(? AS REAL)))", sql_op))
+        },
+        _ => Err(err_msg(IndyErrorKind::WalletQueryError, format!("Invalid combination of tag name and value for numeric {} operator", wql_op)))
+    }
+}
+
+
+fn exist_to_sql<'a>(name: &'a TagName, arguments: &mut Vec<&'a dyn ToSql>) -> IndyResult<String> {
+    match *name {
+        TagName::PlainTagName(ref queried_name) => {
+            arguments.push(queried_name);
+            Ok("(i.id in (SELECT item_id FROM tags_plaintext WHERE name = ?))".to_string())
+        },
+        TagName::EncryptedTagName(ref queried_name) => {
+            arguments.push(queried_name);
+            Ok("(i.id in (SELECT item_id FROM tags_encrypted WHERE name = ?))".to_string())
+        },
+    }
+}
+
+
 fn and_to_sql<'a>(suboperators: &'a [Operator], arguments: &mut Vec<&'a dyn ToSql>) -> IndyResult<String> {
     join_operators(suboperators, " AND ", arguments)
 }
@@ -243,4 +280,25 @@ mod tests {
         let class = vec![100,100,100];
         let (_query, _arguments) = wql_to_sql(&class, &query, None).unwrap();
     }
+
+    #[test]
+    fn exist_and_numeric() {
+        let query = Operator::And(vec![
+            Operator::Exist(TagName::EncryptedTagName(vec![1,2,3])),
+            Operator::NumGte(TagName::PlainTagName(vec![4,5,6]), TargetValue::Unencrypted("18".to_string())),
+        ]);
+        let class = vec![100,100,100];
+        let (query, arguments) = wql_to_sql(&class, &query, None).unwrap();
+        assert!(query.contains("SELECT item_id FROM tags_encrypted WHERE name = ?)"));
+        assert!(query.contains("digits NOT GLOB '*[^0-9.]*'"));
+        assert!(query.contains("CAST(value AS REAL) >= CAST(? AS REAL)"));
+        assert_eq!(4, arguments.len());
+    }
+
+    #[test]
+    fn numeric_fails_for_encrypted_tag() {
+        let query = Operator::NumGt(TagName::EncryptedTagName(vec![1,2,3]), TargetValue::Encrypted(vec![4,5,6]));
+        let class = vec![100,100,100];
+        assert!(wql_to_sql(&class, &query, None).is_err());
+    }
 }
\ No newline at end of file
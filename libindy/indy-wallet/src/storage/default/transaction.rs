@@ -1,15 +1,32 @@
 use rusqlite::{TransactionBehavior, Connection, DropBehavior, Result};
 use std::ops::Deref;
 
+// Transaction started inside of another one is a savepoint with this name.
+const NESTED_SAVEPOINT: &str = "indy_nested";
+
 pub struct Transaction<'conn> {
     conn: &'conn Connection,
     drop_behavior: DropBehavior,
     committed: bool,
+    nested: bool,
 }
 
 impl<'conn> Transaction<'conn> {
-    /// Begin a new transaction. Cannot be nested; see `savepoint` for nested transactions.
+    /// Begin a new transaction. Transaction started inside of another one becomes a savepoint,
+    /// so its changes are committed or rolled back together with the outer transaction.
     pub fn new(conn: &Connection, behavior: TransactionBehavior) -> Result<Transaction> {
+        if !conn.is_autocommit() {
+            return conn.execute_batch(&format!("SAVEPOINT {}", NESTED_SAVEPOINT))
+                .map(move |_| {
+                    Transaction {
+                        conn,
+                        drop_behavior: DropBehavior::Rollback,
+                        committed: false,
+                        nested: true,
+                    }
+                });
+        }
+
         let query = match behavior {
             TransactionBehavior::Deferred => "BEGIN DEFERRED",
             TransactionBehavior::Immediate => "BEGIN IMMEDIATE",
@@ -22,6 +39,7 @@ impl<'conn> Transaction<'conn> {
                     conn,
                     drop_behavior: DropBehavior::Rollback,
                     committed: false,
+                    nested: false,
                 }
             })
     }
@@ -44,7 +62,11 @@ impl<'conn> Transaction<'conn> {
 
     fn commit_(&mut self) -> Result<()> {
         self.committed = true;
-        self.conn.execute_batch("COMMIT")
+        if self.nested {
+            self.conn.execute_batch(&format!("RELEASE {}", NESTED_SAVEPOINT))
+        } else {
+            self.conn.execute_batch("COMMIT")
+        }
     }
 
     /// A convenience method which consumes and rolls back a transaction.
@@ -55,7 +77,11 @@ impl<'conn> Transaction<'conn> {
 
     fn rollback_(&mut self) -> Result<()> {
         self.committed = true;
-        self.conn.execute_batch("ROLLBACK")
+        if self.nested {
+            self.conn.execute_batch(&format!("ROLLBACK TO {0}; RELEASE {0}", NESTED_SAVEPOINT))
+        } else {
+            self.conn.execute_batch("ROLLBACK")
+        }
     }
 
     /// Consumes the transaction, committing or rolling back according to the current setting
@@ -0,0 +1,700 @@
+use std::collections::{HashMap, VecDeque};
+use std::sync::{Arc, Mutex, MutexGuard};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use serde_json;
+
+use indy_api_types::errors::prelude::*;
+use crate::language;
+
+use super::{EncryptedValue, StorageCheckReport, StorageIterator, StorageRecord, StorageStats, StorageTypeStats, Tag, TagName, WalletStorage, WalletStorageType};
+use super::super::{RecordOptions, SearchOptions};
+
+mod query;
+
+lazy_static! {
+    // Wallets live for the lifetime of the process, so the data survives close and re-open
+    // but is never written to disk.
+    static ref INMEM_WALLETS: Mutex<HashMap<String, Arc<Mutex<InmemWalletData>>>> = Default::default();
+}
+
+#[derive(Debug, Default, Clone)]
+struct InmemWalletData {
+    metadata: Vec<u8>,
+    records: HashMap<(Vec<u8>, Vec<u8>), InmemRecord>,
+    next_seq_no: u64,
+    last_modified: Option<u64>,
+}
+
+impl InmemWalletData {
+    fn touch(&mut self) {
+        self.last_modified = Some(SystemTime::now().duration_since(UNIX_EPOCH).unwrap().as_secs());
+    }
+}
+
+#[derive(Debug, Clone)]
+struct InmemRecord {
+    seq_no: u64,
+    value: EncryptedValue,
+    tags: Vec<Tag>,
+}
+
+impl InmemRecord {
+    fn set_tag(&mut self, tag: &Tag) {
+        self.tags.retain(|existing| !_same_tag_name(existing, tag));
+        self.tags.push(tag.clone());
+    }
+}
+
+fn _same_tag_name(tag: &Tag, other: &Tag) -> bool {
+    match (tag, other) {
+        (&Tag::Encrypted(ref name, _), &Tag::Encrypted(ref other_name, _)) => name == other_name,
+        (&Tag::PlainText(ref name, _), &Tag::PlainText(ref other_name, _)) => name == other_name,
+        _ => false
+    }
+}
+
+fn _has_tag_name(tag: &Tag, tag_name: &TagName) -> bool {
+    match (tag, tag_name) {
+        (&Tag::Encrypted(ref name, _), &TagName::OfEncrypted(ref other_name)) => name == other_name,
+        (&Tag::PlainText(ref name, _), &TagName::OfPlain(ref other_name)) => name == other_name,
+        _ => false
+    }
+}
+
+struct InmemStorageIterator {
+    records: Option<VecDeque<StorageRecord>>,
+    total_count: Option<usize>,
+}
+
+impl StorageIterator for InmemStorageIterator {
+    fn next(&mut self) -> IndyResult<Option<StorageRecord>> {
+        Ok(self.records.as_mut().and_then(VecDeque::pop_front))
+    }
+
+    fn get_total_count(&self) -> IndyResult<Option<usize>> {
+        Ok(self.total_count)
+    }
+}
+
+struct InmemStorage {
+    data: Arc<Mutex<InmemWalletData>>,
+}
+
+pub struct InmemStorageType {}
+
+impl InmemStorageType {
+    pub fn new() -> InmemStorageType {
+        InmemStorageType {}
+    }
+
+    fn _wallets() -> MutexGuard<'static, HashMap<String, Arc<Mutex<InmemWalletData>>>> {
+        INMEM_WALLETS.lock().unwrap()
+    }
+}
+
+impl InmemStorage {
+    fn _data(&self) -> MutexGuard<InmemWalletData> {
+        self.data.lock().unwrap()
+    }
+
+    // Records are returned in insertion order, the same way SQLite storage returns them.
+    fn _select<F>(&self, type_: Option<&[u8]>, options: &RecordOptions, filter: F) -> Vec<StorageRecord> where F: Fn(&InmemRecord) -> bool {
+        let data = self._data();
+
+        let mut records = data.records
+            .iter()
+            .filter(|&(&(ref record_type, _), record)| type_.map(|type_| type_ == record_type.as_slice()).unwrap_or(true) && filter(record))
+            .collect::<Vec<_>>();
+
+        records.sort_by_key(|&(_, record)| record.seq_no);
+
+        records
+            .into_iter()
+            .map(|(&(ref record_type, ref id), record)| _to_storage_record(record_type, id, record, options))
+            .collect()
+    }
+}
+
+fn _to_storage_record(type_: &[u8], id: &[u8], record: &InmemRecord, options: &RecordOptions) -> StorageRecord {
+    StorageRecord::new(id.to_vec(),
+                       if options.retrieve_value { Some(record.value.clone()) } else { None },
+                       if options.retrieve_type { Some(type_.to_vec()) } else { None },
+                       if options.retrieve_tags { Some(record.tags.clone()) } else { None })
+}
+
+impl WalletStorage for InmemStorage {
+    fn get(&self, type_: &[u8], id: &[u8], options: &str) -> IndyResult<StorageRecord> {
+        let options: RecordOptions = if options == "{}" {
+            RecordOptions::default()
+        } else {
+            serde_json::from_str(options)
+                .to_indy(IndyErrorKind::InvalidStructure, "RecordOptions is malformed json")?
+        };
+
+        let data = self._data();
+
+        let record = data.records.get(&(type_.to_vec(), id.to_vec()))
+            .ok_or_else(|| err_msg(IndyErrorKind::WalletItemNotFound, "Item not found"))?;
+
+        Ok(_to_storage_record(type_, id, record, &options))
+    }
+
+    fn add(&self, type_: &[u8], id: &[u8], value: &EncryptedValue, tags: &[Tag]) -> IndyResult<()> {
+        let mut data = self._data();
+
+        let key = (type_.to_vec(), id.to_vec());
+
+        if data.records.contains_key(&key) {
+            return Err(err_msg(IndyErrorKind::WalletItemAlreadyExists, "Wallet item already exists"));
+        }
+
+        let seq_no = data.next_seq_no;
+        data.next_seq_no += 1;
+
+        let mut record = InmemRecord { seq_no, value: value.clone(), tags: Vec::new() };
+        tags.iter().for_each(|tag| record.set_tag(tag));
+
+        data.records.insert(key, record);
+        data.touch();
+        Ok(())
+    }
+
+    fn update(&self, type_: &[u8], id: &[u8], value: &EncryptedValue) -> IndyResult<()> {
+        let mut data = self._data();
+
+        let record = data.records.get_mut(&(type_.to_vec(), id.to_vec()))
+            .ok_or_else(|| err_msg(IndyErrorKind::WalletItemNotFound, "Item to update not found"))?;
+
+        record.value = value.clone();
+        data.touch();
+        Ok(())
+    }
+
+    fn add_tags(&self, type_: &[u8], id: &[u8], tags: &[Tag]) -> IndyResult<()> {
+        let mut data = self._data();
+
+        let record = data.records.get_mut(&(type_.to_vec(), id.to_vec()))
+            .ok_or_else(|| err_msg(IndyErrorKind::WalletItemNotFound, "Item not found"))?;
+
+        tags.iter().for_each(|tag| record.set_tag(tag));
+        data.touch();
+        Ok(())
+    }
+
+    fn update_tags(&self, type_: &[u8], id: &[u8], tags: &[Tag]) -> IndyResult<()> {
+        let mut data = self._data();
+
+        let record = data.records.get_mut(&(type_.to_vec(), id.to_vec()))
+            .ok_or_else(|| err_msg(IndyErrorKind::WalletItemNotFound, "Item not found"))?;
+
+        record.tags.clear();
+        tags.iter().for_each(|tag| record.set_tag(tag));
+        data.touch();
+        Ok(())
+    }
+
+    fn delete_tags(&self, type_: &[u8], id: &[u8], tag_names: &[TagName]) -> IndyResult<()> {
+        let mut data = self._data();
+
+        let record = data.records.get_mut(&(type_.to_vec(), id.to_vec()))
+            .ok_or_else(|| err_msg(IndyErrorKind::WalletItemNotFound, "Item not found"))?;
+
+        record.tags.retain(|tag| !tag_names.iter().any(|tag_name| _has_tag_name(tag, tag_name)));
+        data.touch();
+        Ok(())
+    }
+
+    fn delete(&self, type_: &[u8], id: &[u8]) -> IndyResult<()> {
+        let mut data = self._data();
+
+        data.records.remove(&(type_.to_vec(), id.to_vec()))
+            .ok_or_else(|| err_msg(IndyErrorKind::WalletItemNotFound, "Item to delete not found"))?;
+
+        data.touch();
+        Ok(())
+    }
+
+    fn get_storage_metadata(&self) -> IndyResult<Vec<u8>> {
+        Ok(self._data().metadata.clone())
+    }
+
+    fn set_storage_metadata(&self, metadata: &[u8]) -> IndyResult<()> {
+        let mut data = self._data();
+        data.metadata = metadata.to_vec();
+        data.touch();
+        Ok(())
+    }
+
+    fn get_all(&self) -> IndyResult<Box<dyn StorageIterator>> {
+        let fetch_options = RecordOptions {
+            retrieve_type: true,
+            retrieve_value: true,
+            retrieve_tags: true,
+        };
+
+        let records = self._select(None, &fetch_options, |_| true);
+
+        Ok(Box::new(InmemStorageIterator { records: Some(records.into()), total_count: None }))
+    }
+
+    fn search(&self, type_: &[u8], query: &language::Operator, options: Option<&str>) -> IndyResult<Box<dyn StorageIterator>> {
+        let search_options = match options {
+            None => SearchOptions::default(),
+            Some(option_str) => serde_json::from_str(option_str)
+                .to_indy(IndyErrorKind::InvalidStructure, "Search options is malformed json")?
+        };
+
+        query::check(query)?;
+
+        let fetch_options = RecordOptions {
+            retrieve_value: search_options.retrieve_value,
+            retrieve_tags: search_options.retrieve_tags,
+            retrieve_type: search_options.retrieve_type,
+        };
+
+        let records = self._select(Some(type_), &fetch_options, |record| query::matches(query, &record.tags));
+
+        let total_count = if search_options.retrieve_total_count { Some(records.len()) } else { None };
+        let records: Option<VecDeque<StorageRecord>> = if search_options.retrieve_records { Some(records.into()) } else { None };
+
+        Ok(Box::new(InmemStorageIterator { records, total_count }))
+    }
+
+    fn close(&mut self) -> IndyResult<()> {
+        Ok(())
+    }
+
+    // Tags are kept inside their records, so in-memory storage can't get inconsistent.
+    fn check_consistency(&self, _repair: bool) -> IndyResult<StorageCheckReport> {
+        Ok(StorageCheckReport::default())
+    }
+
+    // Storage size is the size of stored data, bookkeeping overhead of the process is not counted.
+    fn get_stats(&self) -> IndyResult<StorageStats> {
+        let data = self._data();
+
+        let mut stats = StorageStats { last_modified: data.last_modified, ..StorageStats::default() };
+        let mut storage_size = data.metadata.len();
+
+        for (&(ref type_, ref id), record) in data.records.iter() {
+            let type_stats = stats.types.entry(type_.clone()).or_insert_with(StorageTypeStats::default);
+            type_stats.records += 1;
+            type_stats.tags += record.tags.len();
+
+            let tags_size: usize = record.tags.iter()
+                .map(|tag| match *tag {
+                    Tag::Encrypted(ref name, ref value) => name.len() + value.len(),
+                    Tag::PlainText(ref name, ref value) => name.len() + value.len(),
+                })
+                .sum();
+
+            storage_size += type_.len() + id.len() + record.value.data.len() + record.value.key.len() + tags_size;
+        }
+
+        stats.storage_size = Some(storage_size as u64);
+        Ok(stats)
+    }
+
+    fn get_plain_tag(&self, type_: &[u8], id: &[u8], tag_name: &[u8]) -> IndyResult<Option<String>> {
+        let data = self._data();
+
+        let record = data.records.get(&(type_.to_vec(), id.to_vec()))
+            .ok_or_else(|| err_msg(IndyErrorKind::WalletItemNotFound, "Item not found"))?;
+
+        Ok(record.tags.iter()
+            .filter_map(|tag| match *tag {
+                Tag::PlainText(ref name, ref value) if name.as_slice() == tag_name => Some(value.clone()),
+                _ => None
+            })
+            .next())
+    }
+
+    fn purge_expired(&self, type_: &[u8], tag_name: &[u8], expired_to: &str) -> IndyResult<Vec<Vec<u8>>> {
+        let mut data = self._data();
+
+        let expired: Vec<(Vec<u8>, Vec<u8>)> = data.records.iter()
+            .filter(|&(&(ref record_type, _), record)| record_type.as_slice() == type_ && record.tags.iter().any(|tag| match *tag {
+                Tag::PlainText(ref name, ref value) => name.as_slice() == tag_name && value.as_str() <= expired_to,
+                Tag::Encrypted(_, _) => false
+            }))
+            .map(|(key, _)| key.clone())
+            .collect();
+
+        for key in expired.iter() {
+            data.records.remove(key);
+        }
+
+        if !expired.is_empty() {
+            data.touch();
+        }
+
+        Ok(expired.into_iter().map(|(_, id)| id).collect())
+    }
+
+    // Changes made by `f` are reverted by restoring the snapshot of wallet data.
+    fn atomic(&self, f: &mut dyn FnMut() -> IndyResult<()>) -> IndyResult<()> {
+        let snapshot = self._data().clone();
+
+        f().map_err(|err| {
+            *self._data() = snapshot;
+            err
+        })
+    }
+}
+
+impl WalletStorageType for InmemStorageType {
+    fn create_storage(&self, id: &str, _config: Option<&str>, _credentials: Option<&str>, metadata: &[u8]) -> IndyResult<()> {
+        let mut wallets = InmemStorageType::_wallets();
+
+        if wallets.contains_key(id) {
+            return Err(err_msg(IndyErrorKind::WalletAlreadyExists, format!("In-memory wallet already exists: {}", id)));
+        }
+
+        let data = InmemWalletData { metadata: metadata.to_vec(), ..Default::default() };
+        wallets.insert(id.to_string(), Arc::new(Mutex::new(data)));
+        Ok(())
+    }
+
+    fn open_storage(&self, id: &str, _config: Option<&str>, _credentials: Option<&str>) -> IndyResult<Box<dyn WalletStorage>> {
+        let data = InmemStorageType::_wallets()
+            .get(id)
+            .cloned()
+            .ok_or_else(|| err_msg(IndyErrorKind::WalletNotFound, format!("In-memory wallet not found: {}", id)))?;
+
+        Ok(Box::new(InmemStorage { data }))
+    }
+
+    fn delete_storage(&self, id: &str, _config: Option<&str>, _credentials: Option<&str>) -> IndyResult<()> {
+        InmemStorageType::_wallets()
+            .remove(id)
+            .map(|_| ())
+            .ok_or_else(|| err_msg(IndyErrorKind::WalletNotFound, format!("In-memory wallet not found: {}", id)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::language::{Operator, TargetValue};
+
+    use super::*;
+
+    #[test]
+    fn inmem_storage_type_create_works_for_twice() {
+        _cleanup("inmem_storage_type_create_works_for_twice");
+
+        let storage_type = InmemStorageType::new();
+        storage_type.create_storage("inmem_storage_type_create_works_for_twice", None, None, &_metadata()).unwrap();
+
+        let res = storage_type.create_storage("inmem_storage_type_create_works_for_twice", None, None, &_metadata());
+        assert_kind!(IndyErrorKind::WalletAlreadyExists, res);
+
+        _cleanup("inmem_storage_type_create_works_for_twice");
+    }
+
+    #[test]
+    fn inmem_storage_type_open_works_for_not_created() {
+        let res = InmemStorageType::new().open_storage("inmem_storage_type_open_works_for_not_created", None, None);
+        assert_kind!(IndyErrorKind::WalletNotFound, res);
+    }
+
+    #[test]
+    fn inmem_storage_type_delete_works() {
+        _cleanup("inmem_storage_type_delete_works");
+
+        let storage_type = InmemStorageType::new();
+        storage_type.create_storage("inmem_storage_type_delete_works", None, None, &_metadata()).unwrap();
+        storage_type.delete_storage("inmem_storage_type_delete_works", None, None).unwrap();
+
+        let res = storage_type.delete_storage("inmem_storage_type_delete_works", None, None);
+        assert_kind!(IndyErrorKind::WalletNotFound, res);
+    }
+
+    #[test]
+    fn inmem_storage_set_get_works_for_reopen() {
+        _cleanup("inmem_storage_set_get_works_for_reopen");
+
+        _storage("inmem_storage_set_get_works_for_reopen").add(&_type1(), &_id1(), &_value1(), &_tags()).unwrap();
+
+        let storage = InmemStorageType::new().open_storage("inmem_storage_set_get_works_for_reopen", None, None).unwrap();
+        let record = storage.get(&_type1(), &_id1(), r##"{"retrieveType": false, "retrieveValue": true, "retrieveTags": true}"##).unwrap();
+        assert_eq!(record.value.unwrap(), _value1());
+        assert_eq!(_sort(record.tags.unwrap()), _sort(_tags()));
+        assert_eq!(storage.get_storage_metadata().unwrap(), _metadata());
+
+        _cleanup("inmem_storage_set_get_works_for_reopen");
+    }
+
+    #[test]
+    fn inmem_storage_add_works_for_twice() {
+        _cleanup("inmem_storage_add_works_for_twice");
+
+        let storage = _storage("inmem_storage_add_works_for_twice");
+        storage.add(&_type1(), &_id1(), &_value1(), &_tags()).unwrap();
+
+        let res = storage.add(&_type1(), &_id1(), &_value2(), &_tags());
+        assert_kind!(IndyErrorKind::WalletItemAlreadyExists, res);
+
+        _cleanup("inmem_storage_add_works_for_twice");
+    }
+
+    #[test]
+    fn inmem_storage_update_delete_works() {
+        _cleanup("inmem_storage_update_delete_works");
+
+        let storage = _storage("inmem_storage_update_delete_works");
+        storage.add(&_type1(), &_id1(), &_value1(), &_tags()).unwrap();
+        storage.update(&_type1(), &_id1(), &_value2()).unwrap();
+
+        let record = storage.get(&_type1(), &_id1(), "{}").unwrap();
+        assert_eq!(record.value.unwrap(), _value2());
+
+        storage.delete(&_type1(), &_id1()).unwrap();
+
+        let res = storage.get(&_type1(), &_id1(), "{}");
+        assert_kind!(IndyErrorKind::WalletItemNotFound, res);
+
+        let res = storage.update(&_type1(), &_id1(), &_value1());
+        assert_kind!(IndyErrorKind::WalletItemNotFound, res);
+
+        let res = storage.delete(&_type1(), &_id1());
+        assert_kind!(IndyErrorKind::WalletItemNotFound, res);
+
+        _cleanup("inmem_storage_update_delete_works");
+    }
+
+    #[test]
+    fn inmem_storage_tags_works() {
+        _cleanup("inmem_storage_tags_works");
+
+        let storage = _storage("inmem_storage_tags_works");
+        storage.add(&_type1(), &_id1(), &_value1(), &_tags()).unwrap();
+
+        let tag = Tag::PlainText(vec![1, 5, 8, 1], "New plain value".to_string());
+        storage.add_tags(&_type1(), &_id1(), &[tag.clone()]).unwrap();
+
+        let record = storage.get(&_type1(), &_id1(), r##"{"retrieveTags": true}"##).unwrap();
+        assert_eq!(_sort(record.tags.unwrap()), _sort(vec![Tag::Encrypted(vec![1, 5, 8], vec![3, 5, 6]), tag]));
+
+        storage.update_tags(&_type1(), &_id1(), &_new_tags()).unwrap();
+
+        let record = storage.get(&_type1(), &_id1(), r##"{"retrieveTags": true}"##).unwrap();
+        assert_eq!(_sort(record.tags.unwrap()), _sort(_new_tags()));
+
+        storage.delete_tags(&_type1(), &_id1(), &[TagName::OfEncrypted(vec![1, 1, 1])]).unwrap();
+
+        let record = storage.get(&_type1(), &_id1(), r##"{"retrieveTags": true}"##).unwrap();
+        assert_eq!(record.tags.unwrap(), vec![Tag::PlainText(vec![1, 1, 1], String::from("tag_value_3"))]);
+
+        _cleanup("inmem_storage_tags_works");
+    }
+
+    #[test]
+    fn inmem_storage_get_all_works() {
+        _cleanup("inmem_storage_get_all_works");
+
+        let storage = _storage("inmem_storage_get_all_works");
+        storage.add(&_type2(), &_id2(), &_value2(), &_tags()).unwrap();
+        storage.add(&_type1(), &_id1(), &_value1(), &_tags()).unwrap();
+
+        let mut storage_iterator = storage.get_all().unwrap();
+
+        let record = storage_iterator.next().unwrap().unwrap();
+        assert_eq!(record.type_.unwrap(), _type2());
+        assert_eq!(record.value.unwrap(), _value2());
+
+        let record = storage_iterator.next().unwrap().unwrap();
+        assert_eq!(record.type_.unwrap(), _type1());
+        assert_eq!(record.value.unwrap(), _value1());
+
+        assert!(storage_iterator.next().unwrap().is_none());
+
+        _cleanup("inmem_storage_get_all_works");
+    }
+
+    #[test]
+    fn inmem_storage_get_stats_works() {
+        _cleanup("inmem_storage_get_stats_works");
+
+        let storage = _storage("inmem_storage_get_stats_works");
+        let stats = storage.get_stats().unwrap();
+        assert!(stats.types.is_empty());
+        assert!(stats.last_modified.is_none());
+
+        storage.add(&_type1(), &_id1(), &_value1(), &_tags()).unwrap();
+        storage.add(&_type1(), &_id2(), &_value2(), &[]).unwrap();
+        storage.add(&_type2(), &_id1(), &_value1(), &_tags()).unwrap();
+
+        let stats = storage.get_stats().unwrap();
+        assert_eq!(stats.types[&_type1()], StorageTypeStats { records: 2, tags: _tags().len() });
+        assert_eq!(stats.types[&_type2()], StorageTypeStats { records: 1, tags: _tags().len() });
+        assert!(stats.storage_size.unwrap() > _metadata().len() as u64);
+        assert!(stats.last_modified.is_some());
+
+        _cleanup("inmem_storage_get_stats_works");
+    }
+
+    #[test]
+    fn inmem_storage_get_plain_tag_works() {
+        _cleanup("inmem_storage_get_plain_tag_works");
+
+        let storage = _storage("inmem_storage_get_plain_tag_works");
+        storage.add(&_type1(), &_id1(), &_value1(), &[Tag::PlainText(vec![7, 7], "0010".to_string()), Tag::Encrypted(vec![8, 8], vec![9, 9])]).unwrap();
+
+        assert_eq!(Some("0010".to_string()), storage.get_plain_tag(&_type1(), &_id1(), &[7, 7]).unwrap());
+        assert_eq!(None, storage.get_plain_tag(&_type1(), &_id1(), &[8, 8]).unwrap());
+
+        let res = storage.get_plain_tag(&_type1(), &_id2(), &[7, 7]);
+        assert_kind!(IndyErrorKind::WalletItemNotFound, res);
+
+        _cleanup("inmem_storage_get_plain_tag_works");
+    }
+
+    #[test]
+    fn inmem_storage_purge_expired_works() {
+        _cleanup("inmem_storage_purge_expired_works");
+
+        let storage = _storage("inmem_storage_purge_expired_works");
+        storage.add(&_type1(), &_id1(), &_value1(), &[Tag::PlainText(vec![7, 7], "0010".to_string())]).unwrap();
+        storage.add(&_type1(), &_id2(), &_value2(), &[Tag::PlainText(vec![7, 7], "0030".to_string())]).unwrap();
+        storage.add(&_type2(), &_id1(), &_value1(), &[Tag::PlainText(vec![7, 7], "0010".to_string())]).unwrap();
+
+        let purged = storage.purge_expired(&_type1(), &[7, 7], "0020").unwrap();
+        assert_eq!(purged, vec![_id1()]);
+
+        let res = storage.get(&_type1(), &_id1(), r##"{"retrieveType": false, "retrieveValue": true, "retrieveTags": true}"##);
+        assert_kind!(IndyErrorKind::WalletItemNotFound, res);
+
+        storage.get(&_type1(), &_id2(), r##"{"retrieveType": false, "retrieveValue": true, "retrieveTags": true}"##).unwrap();
+        storage.get(&_type2(), &_id1(), r##"{"retrieveType": false, "retrieveValue": true, "retrieveTags": true}"##).unwrap();
+
+        _cleanup("inmem_storage_purge_expired_works");
+    }
+
+    #[test]
+    fn inmem_storage_atomic_works_for_rollback() {
+        _cleanup("inmem_storage_atomic_works_for_rollback");
+
+        let storage = _storage("inmem_storage_atomic_works_for_rollback");
+        storage.add(&_type1(), &_id1(), &_value1(), &_tags()).unwrap();
+
+        let res = storage.atomic(&mut || {
+            storage.add(&_type1(), &_id2(), &_value2(), &_tags())?;
+            storage.add(&_type1(), &_id1(), &_value1(), &_tags())
+        });
+        assert_kind!(IndyErrorKind::WalletItemAlreadyExists, res);
+
+        let res = storage.get(&_type1(), &_id2(), r##"{"retrieveType": false, "retrieveValue": true, "retrieveTags": false}"##);
+        assert_kind!(IndyErrorKind::WalletItemNotFound, res);
+
+        _cleanup("inmem_storage_atomic_works_for_rollback");
+    }
+
+    #[test]
+    fn inmem_storage_search_works() {
+        _cleanup("inmem_storage_search_works");
+
+        let storage = _storage("inmem_storage_search_works");
+        storage.add(&_type1(), &_id1(), &_value1(), &_tags()).unwrap();
+        storage.add(&_type1(), &_id2(), &_value2(), &_new_tags()).unwrap();
+        storage.add(&_type2(), &_id1(), &_value1(), &_tags()).unwrap();
+
+        let query = Operator::Eq(language::TagName::PlainTagName(vec![1, 5, 8, 1]), TargetValue::Unencrypted("Plain value".to_string()));
+        let mut storage_iterator = storage.search(&_type1(), &query, Some(r##"{"retrieveTotalCount": true}"##)).unwrap();
+
+        assert_eq!(storage_iterator.get_total_count().unwrap(), Some(1));
+
+        let record = storage_iterator.next().unwrap().unwrap();
+        assert_eq!(record.id, _id1());
+        assert_eq!(record.value.unwrap(), _value1());
+        assert!(storage_iterator.next().unwrap().is_none());
+
+        let query = Operator::And(vec![]);
+        let storage_iterator = storage.search(&_type1(), &query, Some(r##"{"retrieveRecords": false, "retrieveTotalCount": true}"##)).unwrap();
+        assert_eq!(storage_iterator.get_total_count().unwrap(), Some(2));
+
+        _cleanup("inmem_storage_search_works");
+    }
+
+    #[test]
+    fn inmem_storage_search_works_for_invalid_query() {
+        _cleanup("inmem_storage_search_works_for_invalid_query");
+
+        let storage = _storage("inmem_storage_search_works_for_invalid_query");
+
+        let query = Operator::NumGt(language::TagName::EncryptedTagName(vec![1, 5, 8]), TargetValue::Encrypted(vec![3, 5, 6]));
+        let res = storage.search(&_type1(), &query, None);
+        assert_kind!(IndyErrorKind::WalletQueryError, res);
+
+        _cleanup("inmem_storage_search_works_for_invalid_query");
+    }
+
+    fn _cleanup(name: &str) {
+        InmemStorageType::_wallets().remove(name);
+    }
+
+    fn _storage(name: &str) -> Box<dyn WalletStorage> {
+        let storage_type = InmemStorageType::new();
+        storage_type.create_storage(name, None, None, &_metadata()).unwrap();
+        storage_type.open_storage(name, None, None).unwrap()
+    }
+
+    fn _metadata() -> Vec<u8> {
+        vec![1, 2, 3, 4, 5, 6, 7, 8]
+    }
+
+    fn _type(i: u8) -> Vec<u8> {
+        vec![i, 1 + i, 2 + i]
+    }
+
+    fn _type1() -> Vec<u8> {
+        _type(1)
+    }
+
+    fn _type2() -> Vec<u8> {
+        _type(2)
+    }
+
+    fn _id(i: u8) -> Vec<u8> {
+        vec![3 + i, 4 + i, 5 + i]
+    }
+
+    fn _id1() -> Vec<u8> {
+        _id(1)
+    }
+
+    fn _id2() -> Vec<u8> {
+        _id(2)
+    }
+
+    fn _value(i: u8) -> EncryptedValue {
+        EncryptedValue { data: vec![6 + i, 7 + i, 8 + i], key: vec![9 + i, 10 + i, 11 + i] }
+    }
+
+    fn _value1() -> EncryptedValue {
+        _value(1)
+    }
+
+    fn _value2() -> EncryptedValue {
+        _value(2)
+    }
+
+    fn _tags() -> Vec<Tag> {
+        vec![
+            Tag::Encrypted(vec![1, 5, 8], vec![3, 5, 6]),
+            Tag::PlainText(vec![1, 5, 8, 1], "Plain value".to_string())
+        ]
+    }
+
+    fn _new_tags() -> Vec<Tag> {
+        vec![
+            Tag::Encrypted(vec![1, 1, 1], vec![2, 2, 2]),
+            Tag::PlainText(vec![1, 1, 1], String::from("tag_value_3"))
+        ]
+    }
+
+    fn _sort(mut v: Vec<Tag>) -> Vec<Tag> {
+        v.sort();
+        v
+    }
+}
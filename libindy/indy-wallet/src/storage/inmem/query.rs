@@ -0,0 +1,230 @@
+use indy_api_types::errors::prelude::*;
+use crate::language::{Operator, TagName, TargetValue};
+
+use super::super::Tag;
+
+
+// Evaluates Wallet Query Language against the tags of a single record.
+// Query must be validated with `check` first, so evaluation itself can't fail.
+pub fn matches(op: &Operator, tags: &[Tag]) -> bool {
+    match *op {
+        Operator::Eq(ref name, ref value) => _tag_value_matches(name, tags, |tag_value| _eq(tag_value, value)),
+        Operator::Neq(ref name, ref value) => _tag_value_matches(name, tags, |tag_value| !_eq(tag_value, value)),
+        Operator::Gt(ref name, ref value) => _plain_tag_matches(name, tags, |tag_value| tag_value > _plain(value)),
+        Operator::Gte(ref name, ref value) => _plain_tag_matches(name, tags, |tag_value| tag_value >= _plain(value)),
+        Operator::Lt(ref name, ref value) => _plain_tag_matches(name, tags, |tag_value| tag_value < _plain(value)),
+        Operator::Lte(ref name, ref value) => _plain_tag_matches(name, tags, |tag_value| tag_value <= _plain(value)),
+        Operator::Like(ref name, ref value) => _plain_tag_matches(name, tags, |tag_value| _like(tag_value.as_bytes(), _plain(value).as_bytes())),
+        Operator::In(ref name, ref values) => _tag_value_matches(name, tags, |tag_value| values.iter().any(|value| _eq(tag_value, value))),
+        Operator::NumGt(ref name, ref value) => _num_tag_matches(name, value, tags, |tag_value, value| tag_value > value),
+        Operator::NumGte(ref name, ref value) => _num_tag_matches(name, value, tags, |tag_value, value| tag_value >= value),
+        Operator::NumLt(ref name, ref value) => _num_tag_matches(name, value, tags, |tag_value, value| tag_value < value),
+        Operator::NumLte(ref name, ref value) => _num_tag_matches(name, value, tags, |tag_value, value| tag_value <= value),
+        Operator::Exist(ref name) => _tag_value_matches(name, tags, |_| true),
+        Operator::And(ref suboperators) => suboperators.iter().all(|op| matches(op, tags)),
+        Operator::Or(ref suboperators) => suboperators.is_empty() || suboperators.iter().any(|op| matches(op, tags)),
+        Operator::Not(ref suboperator) => !matches(suboperator, tags),
+    }
+}
+
+
+// Rejects the same combinations of tag names and values as SQLite storage does.
+pub fn check(op: &Operator) -> IndyResult<()> {
+    match *op {
+        Operator::Eq(ref name, ref value) => _check_same_kind(name, value, "equality operator"),
+        Operator::Neq(ref name, ref value) => _check_same_kind(name, value, "inequality operator"),
+        Operator::Gt(ref name, ref value) => _check_plain(name, value, "$gt operator"),
+        Operator::Gte(ref name, ref value) => _check_plain(name, value, "$gte operator"),
+        Operator::Lt(ref name, ref value) => _check_plain(name, value, "$lt operator"),
+        Operator::Lte(ref name, ref value) => _check_plain(name, value, "$lte operator"),
+        Operator::Like(ref name, ref value) => _check_plain(name, value, "$like operator"),
+        Operator::In(ref name, ref values) => {
+            values.iter().map(|value| _check_same_kind(name, value, "$in operator")).collect()
+        }
+        Operator::NumGt(ref name, ref value) => _check_plain(name, value, "numeric $gt operator"),
+        Operator::NumGte(ref name, ref value) => _check_plain(name, value, "numeric $gte operator"),
+        Operator::NumLt(ref name, ref value) => _check_plain(name, value, "numeric $lt operator"),
+        Operator::NumLte(ref name, ref value) => _check_plain(name, value, "numeric $lte operator"),
+        Operator::Exist(_) => Ok(()),
+        Operator::And(ref suboperators) | Operator::Or(ref suboperators) => suboperators.iter().map(check).collect(),
+        Operator::Not(ref suboperator) => check(suboperator),
+    }
+}
+
+
+fn _check_same_kind(name: &TagName, value: &TargetValue, op: &str) -> IndyResult<()> {
+    match (name, value) {
+        (&TagName::PlainTagName(_), &TargetValue::Unencrypted(_)) |
+        (&TagName::EncryptedTagName(_), &TargetValue::Encrypted(_)) => Ok(()),
+        _ => Err(err_msg(IndyErrorKind::WalletQueryError, format!("Invalid combination of tag name and value for {}", op)))
+    }
+}
+
+
+fn _check_plain(name: &TagName, value: &TargetValue, op: &str) -> IndyResult<()> {
+    match (name, value) {
+        (&TagName::PlainTagName(_), &TargetValue::Unencrypted(_)) => Ok(()),
+        _ => Err(err_msg(IndyErrorKind::WalletQueryError, format!("Invalid combination of tag name and value for {}", op)))
+    }
+}
+
+
+enum TagValue<'a> {
+    Plain(&'a str),
+    Encrypted(&'a [u8]),
+}
+
+
+fn _find_tag<'a>(name: &TagName, tags: &'a [Tag]) -> Option<TagValue<'a>> {
+    tags.iter().filter_map(|tag| match (name, tag) {
+        (&TagName::PlainTagName(ref name), &Tag::PlainText(ref tag_name, ref tag_value)) if name == tag_name =>
+            Some(TagValue::Plain(tag_value.as_str())),
+        (&TagName::EncryptedTagName(ref name), &Tag::Encrypted(ref tag_name, ref tag_value)) if name == tag_name =>
+            Some(TagValue::Encrypted(tag_value.as_slice())),
+        _ => None
+    }).next()
+}
+
+
+fn _tag_value_matches<F>(name: &TagName, tags: &[Tag], f: F) -> bool where F: Fn(&TagValue) -> bool {
+    _find_tag(name, tags).map(|tag_value| f(&tag_value)).unwrap_or(false)
+}
+
+
+fn _plain_tag_matches<F>(name: &TagName, tags: &[Tag], f: F) -> bool where F: Fn(&str) -> bool {
+    match _find_tag(name, tags) {
+        Some(TagValue::Plain(tag_value)) => f(tag_value),
+        _ => false
+    }
+}
+
+
+// Only decimal values take part in numeric comparison, the same as for SQLite storage.
+fn _num_tag_matches<F>(name: &TagName, value: &TargetValue, tags: &[Tag], f: F) -> bool where F: Fn(f64, f64) -> bool {
+    let value = match _plain(value).parse::<f64>() {
+        Ok(value) => value,
+        Err(_) => return false
+    };
+
+    _plain_tag_matches(name, tags, |tag_value| {
+        _is_decimal(tag_value) && tag_value.parse::<f64>().map(|tag_value| f(tag_value, value)).unwrap_or(false)
+    })
+}
+
+// [+-]digits[.digits]
+fn _is_decimal(value: &str) -> bool {
+    let digits = value.trim_start_matches(|c| c == '+' || c == '-');
+    if value.len() - digits.len() > 1 {
+        return false;
+    }
+
+    let mut parts = digits.splitn(2, '.');
+    let is_digits = |part: &str| !part.is_empty() && part.chars().all(|c| c.is_ascii_digit());
+
+    parts.next().map(is_digits).unwrap_or(false) && parts.next().map(is_digits).unwrap_or(true)
+}
+
+
+fn _eq(tag_value: &TagValue, value: &TargetValue) -> bool {
+    match (tag_value, value) {
+        (&TagValue::Plain(tag_value), &TargetValue::Unencrypted(ref value)) => tag_value == value,
+        (&TagValue::Encrypted(tag_value), &TargetValue::Encrypted(ref value)) => tag_value == value.as_slice(),
+        _ => false
+    }
+}
+
+
+fn _plain(value: &TargetValue) -> &str {
+    match *value {
+        TargetValue::Unencrypted(ref value) => value.as_str(),
+        TargetValue::Encrypted(_) => ""
+    }
+}
+
+
+// SQL LIKE semantic: `%` matches any sequence, `_` matches a single character,
+// ASCII letters are compared case insensitive.
+fn _like(value: &[u8], pattern: &[u8]) -> bool {
+    match pattern.split_first() {
+        None => value.is_empty(),
+        Some((b'%', rest)) => (0..=value.len()).any(|i| _like(&value[i..], rest)),
+        Some((b'_', rest)) => !value.is_empty() && _like(&value[_char_len(value[0])..], rest),
+        Some((c, rest)) => match value.split_first() {
+            Some((v, value_rest)) if v.eq_ignore_ascii_case(c) => _like(value_rest, rest),
+            _ => false
+        }
+    }
+}
+
+
+fn _char_len(first_byte: u8) -> usize {
+    match first_byte {
+        0xF0..=0xFF => 4,
+        0xE0..=0xEF => 3,
+        0xC0..=0xDF => 2,
+        _ => 1
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn _tags() -> Vec<Tag> {
+        vec![
+            Tag::Encrypted(vec![1, 2, 3], vec![4, 5, 6]),
+            Tag::PlainText(vec![7, 8, 9], "Spam and eggs".to_string()),
+            Tag::PlainText(vec![10, 11, 12], "18".to_string()),
+        ]
+    }
+
+    #[test]
+    fn matches_works_for_eq() {
+        assert!(matches(&Operator::Eq(TagName::EncryptedTagName(vec![1, 2, 3]), TargetValue::Encrypted(vec![4, 5, 6])), &_tags()));
+        assert!(!matches(&Operator::Eq(TagName::EncryptedTagName(vec![1, 2, 3]), TargetValue::Encrypted(vec![4, 5, 7])), &_tags()));
+        assert!(!matches(&Operator::Eq(TagName::PlainTagName(vec![1, 2, 3]), TargetValue::Unencrypted("eggs".to_string())), &_tags()));
+    }
+
+    #[test]
+    fn matches_works_for_like() {
+        assert!(matches(&Operator::Like(TagName::PlainTagName(vec![7, 8, 9]), TargetValue::Unencrypted("spam%".to_string())), &_tags()));
+        assert!(matches(&Operator::Like(TagName::PlainTagName(vec![7, 8, 9]), TargetValue::Unencrypted("%and_eggs".to_string())), &_tags()));
+        assert!(!matches(&Operator::Like(TagName::PlainTagName(vec![7, 8, 9]), TargetValue::Unencrypted("eggs%".to_string())), &_tags()));
+    }
+
+    #[test]
+    fn matches_works_for_numeric_and_exist() {
+        let query = Operator::And(vec![
+            Operator::Exist(TagName::EncryptedTagName(vec![1, 2, 3])),
+            Operator::NumGte(TagName::PlainTagName(vec![10, 11, 12]), TargetValue::Unencrypted("9".to_string())),
+        ]);
+        assert!(matches(&query, &_tags()));
+
+        // string comparison would give the opposite result
+        assert!(!matches(&Operator::NumLt(TagName::PlainTagName(vec![10, 11, 12]), TargetValue::Unencrypted("9".to_string())), &_tags()));
+        assert!(!matches(&Operator::NumLt(TagName::PlainTagName(vec![7, 8, 9]), TargetValue::Unencrypted("9".to_string())), &_tags()));
+        assert!(matches(&Operator::Not(Box::new(Operator::Exist(TagName::PlainTagName(vec![1, 2, 3])))), &_tags()));
+    }
+
+    #[test]
+    fn is_decimal_works() {
+        for value in &["0", "18", "-5", "+5", "1.5", "-0.25"] {
+            assert!(_is_decimal(value), "{}", value);
+        }
+
+        for value in &["", "-", "e5", "1e5", "1-2", "--1", "1.", ".5", "1.2.3", "0x10", " 1"] {
+            assert!(!_is_decimal(value), "{}", value);
+        }
+    }
+
+    #[test]
+    fn check_fails_for_invalid_combination() {
+        assert!(check(&Operator::NumGt(TagName::EncryptedTagName(vec![1, 2, 3]), TargetValue::Encrypted(vec![4, 5, 6]))).is_err());
+        assert!(check(&Operator::Gt(TagName::EncryptedTagName(vec![1, 2, 3]), TargetValue::Encrypted(vec![4, 5, 6]))).is_err());
+        assert!(check(&Operator::Or(vec![
+            Operator::Eq(TagName::PlainTagName(vec![1, 2, 3]), TargetValue::Encrypted(vec![4, 5, 6]))
+        ])).is_err());
+        assert!(check(&Operator::Eq(TagName::PlainTagName(vec![1, 2, 3]), TargetValue::Unencrypted("a".to_string()))).is_ok());
+    }
+}
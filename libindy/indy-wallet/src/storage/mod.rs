@@ -1,6 +1,9 @@
 pub mod default;
+pub mod inmem;
 pub mod plugged;
 
+use std::collections::HashMap;
+
 use indy_api_types::errors::prelude::*;
 use crate::language;
 use crate::wallet::EncryptedValue;
@@ -26,7 +29,7 @@ pub struct StorageRecord {
 }
 
 impl StorageRecord {
-    fn new(id: Vec<u8>, value: Option<EncryptedValue>, type_: Option<Vec<u8>>, tags: Option<Vec<Tag>>) -> Self {
+    pub fn new(id: Vec<u8>, value: Option<EncryptedValue>, type_: Option<Vec<u8>>, tags: Option<Vec<Tag>>) -> Self {
         Self {
             id,
             value,
@@ -36,6 +39,30 @@ impl StorageRecord {
     }
 }
 
+#[derive(Debug, Default)]
+pub struct StorageCheckReport {
+    pub integrity_errors: Vec<String>,
+    pub orphaned_tags: usize,
+}
+
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct StorageTypeStats {
+    pub records: usize,
+    pub tags: usize,
+}
+
+#[derive(Debug, Default)]
+pub struct StorageStats {
+    // keyed by encrypted record type
+    pub types: HashMap<Vec<u8>, StorageTypeStats>,
+    // bytes occupied by the storage, if backend can tell
+    pub storage_size: Option<u64>,
+    // seconds since unix epoch, if backend can tell
+    pub last_modified: Option<u64>,
+}
+
+// Storage backends operate on already encrypted records, so implementations
+// never see plain text of values, types, ids and encrypted tags.
 pub trait StorageIterator {
     fn next(&mut self) -> Result<Option<StorageRecord>, IndyError>;
     fn get_total_count(&self) -> Result<Option<usize>, IndyError>;
@@ -54,9 +81,68 @@ pub trait WalletStorage {
     fn get_all(&self) -> Result<Box<dyn StorageIterator>, IndyError>;
     fn search(&self, type_: &[u8], query: &language::Operator, options: Option<&str>) -> Result<Box<dyn StorageIterator>, IndyError>;
     fn close(&mut self) -> Result<(), IndyError>;
+    fn check_consistency(&self, repair: bool) -> Result<StorageCheckReport, IndyError>;
+    // Default implementation counts records by walking all of them, so it is O(number of records).
+    // Storage size and modification time are unknown.
+    fn get_stats(&self) -> Result<StorageStats, IndyError> {
+        scan_stats(self)
+    }
+    // Returns value of plaintext tag `tag_name` of the record or None if the record has no such tag.
+    // Default implementation fetches all tags of the record.
+    fn get_plain_tag(&self, type_: &[u8], id: &[u8], tag_name: &[u8]) -> Result<Option<String>, IndyError> {
+        let record = self.get(type_, id, r#"{"retrieveValue": false, "retrieveTags": true}"#)?;
+
+        Ok(record.tags.unwrap_or_default().into_iter()
+            .filter_map(|tag| match tag {
+                Tag::PlainText(name, value) if name.as_slice() == tag_name => Some(value),
+                _ => None
+            })
+            .next())
+    }
+    // Deletes records of the type with plaintext tag `tag_name` less than or equal to `expired_to` (as strings).
+    // Returns names of deleted records.
+    // Default implementation finds expired records by search and deletes them one by one.
+    fn purge_expired(&self, type_: &[u8], tag_name: &[u8], expired_to: &str) -> Result<Vec<Vec<u8>>, IndyError> {
+        let query = language::Operator::Lte(language::TagName::PlainTagName(tag_name.to_vec()),
+                                            language::TargetValue::Unencrypted(expired_to.to_string()));
+
+        let mut ids = Vec::new();
+        let mut iterator = self.search(type_, &query, Some(r#"{"retrieveValue": false}"#))?;
+
+        while let Some(record) = iterator.next()? {
+            ids.push(record.id);
+        }
+
+        for id in ids.iter() {
+            self.delete(type_, id)?;
+        }
+
+        Ok(ids)
+    }
+    // Applies all changes made by `f` atomically, so either all of them are stored or none.
+    // Storages that can't group changes apply them one by one.
+    fn atomic(&self, f: &mut dyn FnMut() -> Result<(), IndyError>) -> Result<(), IndyError> {
+        f()
+    }
+}
+
+// Counts records and tags of the storage by walking all records.
+pub(crate) fn scan_stats<S: WalletStorage + ?Sized>(storage: &S) -> Result<StorageStats, IndyError> {
+    let mut stats = StorageStats::default();
+    let mut iterator = storage.get_all()?;
+
+    while let Some(record) = iterator.next()? {
+        let type_stats = stats.types.entry(record.type_.unwrap_or_default()).or_insert_with(StorageTypeStats::default);
+        type_stats.records += 1;
+        type_stats.tags += record.tags.map(|tags| tags.len()).unwrap_or(0);
+    }
+
+    Ok(stats)
 }
 
-pub trait WalletStorageType {
+// Storage types are registered once and used from the command thread,
+// so they can be passed there from any other thread.
+pub trait WalletStorageType: Send {
     fn create_storage(&self, id: &str, config: Option<&str>, credentials: Option<&str>, metadata: &[u8]) -> Result<(), IndyError>;
     fn open_storage(&self, id: &str, config: Option<&str>, credentials: Option<&str>) -> Result<Box<dyn WalletStorage>, IndyError>;
     fn delete_storage(&self, id: &str, config: Option<&str>, credentials: Option<&str>) -> Result<(), IndyError>;
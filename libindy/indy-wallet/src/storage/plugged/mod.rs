@@ -12,7 +12,7 @@ use indy_api_types::errors::prelude::*;
 use crate::language;
 use indy_utils::crypto::base64;
 
-use super::{EncryptedValue, StorageIterator, StorageRecord, Tag, TagName, WalletStorage, WalletStorageType};
+use super::{EncryptedValue, StorageCheckReport, StorageIterator, StorageRecord, StorageStats, StorageTypeStats, Tag, TagName, WalletStorage, WalletStorageType};
 use super::super::{RecordOptions, SearchOptions};
 
 #[derive(Debug, Deserialize)]
@@ -26,6 +26,19 @@ pub struct PluggedWalletJSONValues {
     pub values: Vec<PluggedWalletJSONValue>
 }
 
+#[derive(Debug, Deserialize)]
+struct PluggedStorageTypeStats {
+    records: usize,
+    tags: usize,
+}
+
+#[derive(Debug, Deserialize)]
+struct PluggedStorageStats {
+    types: HashMap<String, PluggedStorageTypeStats>,
+    storage_size: Option<u64>,
+    last_modified: Option<u64>,
+}
+
 // This struct is used as a helper to free the resource even in case of error.
 // It is workaround for Rust's lack of try/catch.
 struct ResourceGuard {
@@ -235,6 +248,7 @@ struct PluggedStorage {
     fetch_search_next_record_handler: WalletFetchSearchNextRecord,
     free_search_handler: WalletFreeSearch,
     close_handler: WalletClose,
+    stats_handlers: Option<(WalletGetStorageStats, WalletFreeStorageStats)>,
 }
 
 impl PluggedStorage {
@@ -259,7 +273,8 @@ impl PluggedStorage {
            get_search_total_count_handler: WalletGetSearchTotalCount,
            fetch_search_next_record_handler: WalletFetchSearchNextRecord,
            free_search_handler: WalletFreeSearch,
-           close_handler: WalletClose) -> PluggedStorage {
+           close_handler: WalletClose,
+           stats_handlers: Option<(WalletGetStorageStats, WalletFreeStorageStats)>) -> PluggedStorage {
         PluggedStorage {
             handle,
             add_record_handler,
@@ -283,6 +298,7 @@ impl PluggedStorage {
             fetch_search_next_record_handler,
             free_search_handler,
             close_handler,
+            stats_handlers,
         }
     }
 }
@@ -618,6 +634,54 @@ impl WalletStorage for PluggedStorage {
 
         Ok(())
     }
+
+    // Plugged storages don't expose their internal structure, so there is nothing to check here.
+    fn check_consistency(&self, _repair: bool) -> IndyResult<StorageCheckReport> {
+        Ok(StorageCheckReport::default())
+    }
+
+    // Without get_storage_stats handler records are counted by full scan of the storage.
+    fn get_stats(&self) -> IndyResult<StorageStats> {
+        let (get_stats_handler, free_stats_handler) = match self.stats_handlers {
+            Some(handlers) => handlers,
+            None => return super::scan_stats(self),
+        };
+
+        let mut stats_ptr: *const c_char = ptr::null_mut();
+        let mut stats_handle = -1;
+
+        let err = (get_stats_handler)(self.handle, &mut stats_ptr, &mut stats_handle);
+
+        if err != ErrorCode::Success {
+            return Err(err.into());
+        }
+
+        let _stats_free_helper = ResourceGuard::new(self.handle, stats_handle, free_stats_handler);
+
+        let stats_json = unsafe {
+            CStr::from_ptr(stats_ptr)
+                .to_str()
+                .to_indy(IndyErrorKind::InvalidState, "Storage stats contains non-utf8 symbol")?
+        };
+
+        let stats: PluggedStorageStats = serde_json::from_str(stats_json)
+            .to_indy(IndyErrorKind::InvalidState, "Unable to deserialize storage stats from json")?;
+
+        let mut types = HashMap::with_capacity(stats.types.len());
+
+        for (type_, type_stats) in stats.types {
+            types.insert(
+                base64::decode(&type_).to_indy(IndyErrorKind::InvalidState, "Unable to decode record type from base64")?,
+                StorageTypeStats { records: type_stats.records, tags: type_stats.tags },
+            );
+        }
+
+        Ok(StorageStats {
+            types,
+            storage_size: stats.storage_size,
+            last_modified: stats.last_modified,
+        })
+    }
 }
 
 impl Drop for PluggedStorage {
@@ -629,7 +693,7 @@ impl Drop for PluggedStorage {
     }
 }
 
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct PluggedStorageType {
     create_handler: WalletCreate,
     open_handler: WalletOpen,
@@ -655,6 +719,7 @@ pub struct PluggedStorageType {
     get_search_total_count_handler: WalletGetSearchTotalCount,
     fetch_search_next_record_handler: WalletFetchSearchNextRecord,
     free_search_handler: WalletFreeSearch,
+    stats_handlers: Option<(WalletGetStorageStats, WalletFreeStorageStats)>,
 }
 
 
@@ -708,6 +773,16 @@ impl PluggedStorageType {
             get_search_total_count_handler,
             fetch_search_next_record_handler,
             free_search_handler,
+            stats_handlers: None,
+        }
+    }
+
+    pub fn with_stats_handlers(self,
+                               get_storage_stats_handler: WalletGetStorageStats,
+                               free_storage_stats_handler: WalletFreeStorageStats) -> PluggedStorageType {
+        PluggedStorageType {
+            stats_handlers: Some((get_storage_stats_handler, free_storage_stats_handler)),
+            ..self
         }
     }
 }
@@ -781,7 +856,8 @@ impl WalletStorageType for PluggedStorageType {
                 self.get_search_total_count_handler,
                 self.fetch_search_next_record_handler,
                 self.free_search_handler,
-                self.close_handler)))
+                self.close_handler,
+                self.stats_handlers)))
     }
 
     fn delete_storage(&self, id: &str, config: Option<&str>, credentials: Option<&str>) -> IndyResult<()> {
@@ -867,6 +943,8 @@ mod tests {
         GetSearchTotalCountHandler(i32, i32),
         FetchSearchNextRecordHandler(i32, i32),
         FreeSearchHandler(i32, i32),
+        GetStorageStatsHandler(i32),
+        FreeStorageStatsHandler(i32, i32),
     }
 
     fn _random_vector(len: usize) -> Vec<u8> {
@@ -906,6 +984,17 @@ mod tests {
             let tags_json = CString::new(_tags_to_json(&tags).unwrap()).unwrap();
             (tags_json, tags)
         });
+        static ref RETURN_STATS: RwLock<(CString, HashMap<Vec<u8>, StorageTypeStats>)> = RwLock::new({
+            let type_ = _random_vector(32);
+            let stats_json = json!({
+                "types": {base64::encode(&type_): {"records": 3, "tags": 5}},
+                "storage_size": RETURN_STORAGE_SIZE,
+            }).to_string();
+
+            let mut types = HashMap::new();
+            types.insert(type_, StorageTypeStats { records: 3, tags: 5 });
+            (CString::new(stats_json).unwrap(), types)
+        });
         static ref RETURN_METADATA: RwLock<(CString, Vec<u8>)> = RwLock::new({
             let data = _random_vector(512);
             let str = CString::new(
@@ -920,6 +1009,8 @@ mod tests {
     static RETURN_SEARCH_HANDLE: i32 = 3i32;
     static RETURN_METADATA_HANDLE: i32 = 4i32;
     static RETURN_SEARCH_TOTAL_COUNT: usize = 1024;
+    static RETURN_STATS_HANDLE: i32 = 5i32;
+    static RETURN_STORAGE_SIZE: u64 = 4096;
 
     fn _convert_c_string(str: *const c_char) -> Option<String> {
         if str != ptr::null() {
@@ -1340,6 +1431,35 @@ mod tests {
         ErrorCode::Success
     }
 
+    extern "C" fn _mock_get_storage_stats_handler(storage_handle: i32,
+                                                  stats_json_p: *mut *const c_char,
+                                                  stats_handle_p: *mut i32) -> ErrorCode {
+        DEBUG_VEC.write().unwrap().push(
+            Call::GetStorageStatsHandler(
+                storage_handle,
+            )
+        );
+
+        unsafe {
+            *stats_json_p = RETURN_STATS.read().unwrap().0.as_ptr();
+            *stats_handle_p = RETURN_STATS_HANDLE;
+        }
+
+        ErrorCode::Success
+    }
+
+    extern "C" fn _mock_free_storage_stats_handler(storage_handle: i32,
+                                                   stats_handle: i32) -> ErrorCode {
+        DEBUG_VEC.write().unwrap().push(
+            Call::FreeStorageStatsHandler(
+                storage_handle,
+                stats_handle,
+            )
+        );
+
+        ErrorCode::Success
+    }
+
     fn _create_storage_type() -> PluggedStorageType {
         PluggedStorageType::new(
             _mock_create_handler,
@@ -2185,4 +2305,37 @@ mod tests {
         assert_eq!(&expected_free_record_call, debug.get(6).unwrap());
         assert_eq!(&expected_free_search_call, debug.get(7).unwrap());
     }
+
+    #[test]
+    fn plugged_storage_get_stats_works() {
+        DEBUG_VEC.write().unwrap().clear();
+
+        let storage = _create_storage_type()
+            .with_stats_handlers(_mock_get_storage_stats_handler, _mock_free_storage_stats_handler)
+            .open_storage("wallet1", None, Some("credentials"))
+            .unwrap();
+
+        DEBUG_VEC.write().unwrap().clear();
+
+        let stats = storage.get_stats().unwrap();
+
+        assert_eq!(RETURN_STATS.read().unwrap().1, stats.types);
+        assert_eq!(Some(RETURN_STORAGE_SIZE), stats.storage_size);
+        assert_eq!(None, stats.last_modified);
+
+        let expected_get_call = Call::GetStorageStatsHandler(
+            RETURN_STORAGE_HANDLE,
+        );
+
+        let expected_free_call = Call::FreeStorageStatsHandler(
+            RETURN_STORAGE_HANDLE,
+            RETURN_STATS_HANDLE,
+        );
+
+        let debug = DEBUG_VEC.read().unwrap();
+
+        assert_eq!(debug.len(), 2);
+        assert_eq!(&expected_get_call, debug.get(0).unwrap());
+        assert_eq!(&expected_free_call, debug.get(1).unwrap());
+    }
 }
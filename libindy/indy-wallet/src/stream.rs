@@ -0,0 +1,69 @@
+use std::io;
+use std::io::{Read, Write};
+
+use indy_api_types::{ErrorCode, IndyHandle};
+use indy_api_types::wallet::{WalletExportWrite, WalletImportRead};
+
+// Adapts export sink callback to Write to reuse export_import chunk encryption.
+pub struct CallbackWriter {
+    sink_handle: IndyHandle,
+    write_handler: WalletExportWrite,
+}
+
+impl CallbackWriter {
+    pub fn new(sink_handle: IndyHandle, write_handler: WalletExportWrite) -> CallbackWriter {
+        CallbackWriter {
+            sink_handle,
+            write_handler,
+        }
+    }
+}
+
+impl Write for CallbackWriter {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        let err = (self.write_handler)(self.sink_handle, buf.as_ptr(), buf.len());
+
+        if err != ErrorCode::Success {
+            return Err(io::Error::new(io::ErrorKind::Other, format!("Export sink returned error: {:?}", err)));
+        }
+
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+// Adapts import source callback to Read.
+pub struct CallbackReader {
+    source_handle: IndyHandle,
+    read_handler: WalletImportRead,
+}
+
+impl CallbackReader {
+    pub fn new(source_handle: IndyHandle, read_handler: WalletImportRead) -> CallbackReader {
+        CallbackReader {
+            source_handle,
+            read_handler,
+        }
+    }
+}
+
+impl Read for CallbackReader {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        let mut read_len: usize = 0;
+
+        let err = (self.read_handler)(self.source_handle, buf.as_mut_ptr(), buf.len(), &mut read_len);
+
+        if err != ErrorCode::Success {
+            return Err(io::Error::new(io::ErrorKind::Other, format!("Import source returned error: {:?}", err)));
+        }
+
+        if read_len > buf.len() {
+            return Err(io::Error::new(io::ErrorKind::InvalidData, "Import source returned more data than requested"));
+        }
+
+        Ok(read_len)
+    }
+}
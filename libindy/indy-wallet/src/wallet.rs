@@ -1,3 +1,4 @@
+use std::cell::Cell;
 use std::collections::HashMap;
 use std::rc::Rc;
 
@@ -12,7 +13,11 @@ use super::storage;
 use super::iterator::WalletIterator;
 use super::encryption::*;
 use super::query_encryption::encrypt_query;
-use super::WalletRecord;
+use super::{RecordOptions, WalletRecord};
+use super::audit_log::{self, AuditLogEntry, AuditLogOperation, AUDIT_LOG_TYPE};
+use super::stats::{self, WalletStats};
+use super::expiry::{self, EXPIRES_AT_TAG};
+use super::export_import::{self, EXPORT_JOURNAL_TYPE};
 
 #[derive(Serialize, Deserialize)]
 pub(super) struct Keys {
@@ -116,14 +121,35 @@ pub(super) struct Wallet {
     id: String,
     storage: Box<dyn storage::WalletStorage>,
     keys: Rc<Keys>,
+    audit_log: bool,
+    export_journal: Cell<bool>,
+    change_listener: Option<Box<dyn Fn(&AuditLogEntry)>>,
 }
 
 impl Wallet {
     pub fn new(id: String, storage: Box<dyn storage::WalletStorage>, keys: Rc<Keys>) -> Wallet {
-        Wallet { id, storage, keys }
+        Wallet { id, storage, keys, audit_log: false, export_journal: Cell::new(false), change_listener: None }
+    }
+
+    pub fn enable_audit_log(&mut self) {
+        self.audit_log = true;
+    }
+
+    // Changes of records are journaled for the next incremental export once the first one is done.
+    pub fn enable_export_journal(&self) {
+        self.export_journal.set(true);
+    }
+
+    pub fn set_change_listener(&mut self, change_listener: Option<Box<dyn Fn(&AuditLogEntry)>>) {
+        self.change_listener = change_listener;
     }
 
     pub fn add(&self, type_: &str, name: &str, value: &str, tags: &HashMap<String, String>) -> IndyResult<()> {
+        let tags = expiry::apply_ttl(type_, tags)?;
+        self._change(type_, name, AuditLogOperation::Add, || self._add(type_, name, value, &tags))
+    }
+
+    fn _add(&self, type_: &str, name: &str, value: &str, tags: &HashMap<String, String>) -> IndyResult<()> {
         let etype = encrypt_as_searchable(type_.as_bytes(), &self.keys.type_key, &self.keys.item_hmac_key);
         let ename = encrypt_as_searchable(name.as_bytes(), &self.keys.name_key, &self.keys.item_hmac_key);
         let evalue = EncryptedValue::encrypt(value, &self.keys.value_key);
@@ -135,57 +161,125 @@ impl Wallet {
     pub fn add_tags(&self, type_: &str, name: &str, tags: &HashMap<String, String>) -> IndyResult<()> {
         let encrypted_type = encrypt_as_searchable(type_.as_bytes(), &self.keys.type_key, &self.keys.item_hmac_key);
         let encrypted_name = encrypt_as_searchable(name.as_bytes(), &self.keys.name_key, &self.keys.item_hmac_key);
-        let encrypted_tags = encrypt_tags(tags, &self.keys.tag_name_key, &self.keys.tag_value_key, &self.keys.tags_hmac_key);
-        self.storage.add_tags(&encrypted_type, &encrypted_name, &encrypted_tags)?;
-        Ok(())
+        let tags = expiry::apply_ttl(type_, tags)?;
+        let encrypted_tags = encrypt_tags(&tags, &self.keys.tag_name_key, &self.keys.tag_value_key, &self.keys.tags_hmac_key);
+        self._change(type_, name, AuditLogOperation::UpdateTags, || self.storage.add_tags(&encrypted_type, &encrypted_name, &encrypted_tags))
     }
 
     pub fn update_tags(&self, type_: &str, name: &str, tags: &HashMap<String, String>) -> IndyResult<()> {
         let encrypted_type = encrypt_as_searchable(type_.as_bytes(), &self.keys.type_key, &self.keys.item_hmac_key);
         let encrypted_name = encrypt_as_searchable(name.as_bytes(), &self.keys.name_key, &self.keys.item_hmac_key);
-        let encrypted_tags = encrypt_tags(tags, &self.keys.tag_name_key, &self.keys.tag_value_key, &self.keys.tags_hmac_key);
-        self.storage.update_tags(&encrypted_type, &encrypted_name, &encrypted_tags)?;
-        Ok(())
+
+        // Replacing of tags keeps expiration time of the record unless new TTL is set.
+        let mut tags = expiry::apply_ttl(type_, tags)?;
+        if expiry::is_expirable(type_) && !tags.contains_key(EXPIRES_AT_TAG) {
+            if let Some(expires_at) = self._get_expires_at(&encrypted_type, &encrypted_name)? {
+                tags.insert(EXPIRES_AT_TAG.to_string(), expires_at);
+            }
+        }
+
+        let encrypted_tags = encrypt_tags(&tags, &self.keys.tag_name_key, &self.keys.tag_value_key, &self.keys.tags_hmac_key);
+        self._change(type_, name, AuditLogOperation::UpdateTags, || self.storage.update_tags(&encrypted_type, &encrypted_name, &encrypted_tags))
     }
 
     pub fn delete_tags(&self, type_: &str, name: &str, tag_names: &[&str]) -> IndyResult<()> {
         let encrypted_type = encrypt_as_searchable(type_.as_bytes(), &self.keys.type_key, &self.keys.item_hmac_key);
         let encrypted_name = encrypt_as_searchable(name.as_bytes(), &self.keys.name_key, &self.keys.item_hmac_key);
         let encrypted_tag_names = encrypt_tag_names(tag_names, &self.keys.tag_name_key, &self.keys.tags_hmac_key);
-        self.storage.delete_tags(&encrypted_type, &encrypted_name, &encrypted_tag_names[..])?;
-        Ok(())
+        self._change(type_, name, AuditLogOperation::UpdateTags, || self.storage.delete_tags(&encrypted_type, &encrypted_name, &encrypted_tag_names[..]))
     }
 
     pub fn update(&self, type_: &str, name: &str, new_value: &str) -> IndyResult<()> {
         let encrypted_type = encrypt_as_searchable(type_.as_bytes(), &self.keys.type_key, &self.keys.item_hmac_key);
         let encrypted_name = encrypt_as_searchable(name.as_bytes(), &self.keys.name_key, &self.keys.item_hmac_key);
         let encrypted_value = EncryptedValue::encrypt(new_value, &self.keys.value_key);
-        self.storage.update(&encrypted_type, &encrypted_name, &encrypted_value)?;
-        Ok(())
+        self._change(type_, name, AuditLogOperation::Update, || self.storage.update(&encrypted_type, &encrypted_name, &encrypted_value))
     }
 
     pub fn get(&self, type_: &str, name: &str, options: &str) -> IndyResult<WalletRecord> {
         let etype = encrypt_as_searchable(type_.as_bytes(), &self.keys.type_key, &self.keys.item_hmac_key);
         let ename = encrypt_as_searchable(name.as_bytes(), &self.keys.name_key, &self.keys.item_hmac_key);
 
-        let result = self.storage.get(&etype, &ename, options)?;
+        let options: RecordOptions = ::serde_json::from_str(options)
+            .to_indy(IndyErrorKind::InvalidStructure, "RecordOptions is malformed json")?;
+
+        let result = self.storage.get(&etype, &ename, &::serde_json::to_string(&options)
+            .to_indy(IndyErrorKind::InvalidState, "Cannot serialize RecordOptions")?)?;
+
+        let tags = decrypt_tags(&result.tags, &self.keys.tag_name_key, &self.keys.tag_value_key)?;
+
+        // Expiration time is taken from fetched tags or looked up separately if tags weren't requested.
+        if expiry::is_expirable(type_) {
+            let expires_at = if options.retrieve_tags {
+                tags.as_ref().and_then(|tags| tags.get(EXPIRES_AT_TAG)).cloned()
+            } else {
+                self._get_expires_at(&etype, &ename)?
+            };
+
+            if expiry::is_expired(expires_at.as_ref().map(String::as_str), &expiry::now()) {
+                return Err(err_msg(IndyErrorKind::WalletItemNotFound, "Item is expired"));
+            }
+        }
 
         let value = match result.value {
             None => None,
             Some(encrypted_value) => Some(encrypted_value.decrypt(&self.keys.value_key)?)
         };
 
-        let tags = decrypt_tags(&result.tags, &self.keys.tag_name_key, &self.keys.tag_value_key)?;
-
         Ok(WalletRecord::new(String::from(name), result.type_.map(|_| type_.to_string()), value, tags))
     }
 
+    // Deletes records of the type with expired TTL and returns number of deleted records.
+    pub fn purge_expired(&self, type_: &str) -> IndyResult<usize> {
+        let etype = encrypt_as_searchable(type_.as_bytes(), &self.keys.type_key, &self.keys.item_hmac_key);
+        let etag_name = encrypt_as_searchable(&EXPIRES_AT_TAG.as_bytes()[1..], &self.keys.tag_name_key, &self.keys.tags_hmac_key);
+
+        let mut entries = Vec::new();
+        let mut purged = 0;
+
+        let logged = self._is_logged(type_);
+        let journaled = self._is_journaled(type_);
+
+        // Deletion of records and audit log and journal entries about it are stored atomically.
+        self.storage.atomic(&mut || {
+            let enames = self.storage.purge_expired(&etype, &etag_name, &expiry::now())?;
+            purged = enames.len();
+            entries.clear();
+
+            if !logged && !journaled {
+                return Ok(());
+            }
+
+            for ename in enames.iter() {
+                let name = String::from_utf8(decrypt_merged(ename, &self.keys.name_key)?)
+                    .to_indy(IndyErrorKind::WalletEncryptionError, "Record name is invalid utf8")?;
+
+                if journaled {
+                    self._journal_change(type_, &name)?;
+                }
+
+                if logged {
+                    let entry = AuditLogEntry::new(type_, &name, AuditLogOperation::Delete)?;
+                    self._store_audit_log_entry(&entry)?;
+                    entries.push(entry);
+                }
+            }
+
+            Ok(())
+        })?;
+
+        for entry in entries.iter() {
+            self._notify_change(entry);
+        }
+
+        Ok(purged)
+    }
+
     pub fn delete(&self, type_: &str, name: &str) -> IndyResult<()> {
         let etype = encrypt_as_searchable(type_.as_bytes(), &self.keys.type_key, &self.keys.item_hmac_key);
         let ename = encrypt_as_searchable(name.as_bytes(), &self.keys.name_key, &self.keys.item_hmac_key);
 
-        self.storage.delete(&etype, &ename)?;
-        Ok(())
+        self._change(type_, name, AuditLogOperation::Delete, || self.storage.delete(&etype, &ename))
     }
 
     pub fn search<'a>(&'a self, type_: &str, query: &str, options: Option<&str>) -> IndyResult<WalletIterator> {
@@ -194,6 +288,8 @@ impl Wallet {
             .optimise()
             .unwrap_or_default();
 
+        let parsed_query = if expiry::is_expirable(type_) { expiry::exclude_expired(parsed_query, &expiry::now()) } else { parsed_query };
+
         let encrypted_query = encrypt_query(parsed_query, &self.keys)?;
         let encrypted_type_ = encrypt_as_searchable(type_.as_bytes(), &self.keys.type_key, &self.keys.item_hmac_key);
         let storage_iterator = self.storage.search(&encrypted_type_, &encrypted_query, options)?;
@@ -201,6 +297,11 @@ impl Wallet {
         Ok(wallet_iterator)
     }
 
+    // Stores all changes made by `f` or none of them.
+    pub fn atomic(&self, f: &mut dyn FnMut() -> IndyResult<()>) -> IndyResult<()> {
+        self.storage.atomic(f)
+    }
+
     pub fn close(&mut self) -> IndyResult<()> {
         self.storage.close()
             .map_err(IndyError::from)
@@ -211,9 +312,113 @@ impl Wallet {
         Ok(WalletIterator::new(all_items, Rc::clone(&self.keys)))
     }
 
+    pub fn get_stats(&self) -> IndyResult<WalletStats> {
+        stats::get_stats(&*self.storage, &self.keys)
+    }
+
     pub fn get_id<'a>(&'a self) -> &'a str {
         &self.id
     }
+
+    pub fn get_audit_log(&self, from: Option<u64>, to: Option<u64>) -> IndyResult<Vec<AuditLogEntry>> {
+        let mut iter = self.search(AUDIT_LOG_TYPE, &audit_log::build_query(from, to), None)?;
+
+        let mut entries = Vec::new();
+
+        while let Some(record) = iter.next()? {
+            let value = record.value
+                .ok_or_else(|| err_msg(IndyErrorKind::InvalidState, "Audit log entry without value"))?;
+
+            let entry: AuditLogEntry = ::serde_json::from_str(&value)
+                .to_indy(IndyErrorKind::InvalidState, "Cannot deserialize audit log entry")?;
+
+            entries.push(entry);
+        }
+
+        entries.sort_by_key(|entry| entry.timestamp);
+        Ok(entries)
+    }
+
+    fn _is_logged(&self, type_: &str) -> bool {
+        type_ != AUDIT_LOG_TYPE && (self.audit_log || self.change_listener.is_some())
+    }
+
+    fn _is_journaled(&self, type_: &str) -> bool {
+        self.export_journal.get() && !export_import::is_internal_type(type_)
+    }
+
+    // Looks up only the expiration time tag of the record instead of fetching all its tags.
+    fn _get_expires_at(&self, etype: &[u8], ename: &[u8]) -> IndyResult<Option<String>> {
+        let etag_name = encrypt_as_searchable(&EXPIRES_AT_TAG.as_bytes()[1..], &self.keys.tag_name_key, &self.keys.tags_hmac_key);
+        self.storage.get_plain_tag(etype, ename, &etag_name)
+    }
+
+    // Applies the change of the record. Audit log and export journal entries about the change are stored
+    // atomically with it, change listener is notified once the change is stored.
+    fn _change<F>(&self, type_: &str, name: &str, operation: AuditLogOperation, mut f: F) -> IndyResult<()> where F: FnMut() -> IndyResult<()> {
+        let logged = self._is_logged(type_);
+        let journaled = self._is_journaled(type_);
+
+        if !logged && !journaled {
+            return f();
+        }
+
+        let entry = if logged { Some(AuditLogEntry::new(type_, name, operation)?) } else { None };
+
+        self.storage.atomic(&mut || {
+            f()?;
+
+            if journaled {
+                self._journal_change(type_, name)?;
+            }
+
+            match entry {
+                Some(ref entry) => self._store_audit_log_entry(entry),
+                None => Ok(())
+            }
+        })?;
+
+        if let Some(ref entry) = entry {
+            self._notify_change(entry);
+        }
+
+        Ok(())
+    }
+
+    fn _store_audit_log_entry(&self, entry: &AuditLogEntry) -> IndyResult<()> {
+        if !self.audit_log {
+            return Ok(());
+        }
+
+        let value = ::serde_json::to_string(entry)
+            .to_indy(IndyErrorKind::InvalidState, "Cannot serialize audit log entry")?;
+
+        // Name has a random part, so it's built once for both the record and the journal.
+        let name = entry.record_name();
+
+        self._add(AUDIT_LOG_TYPE, &name, &value, &entry.record_tags())?;
+
+        // Audit log records are exported as well.
+        if self.export_journal.get() {
+            self._journal_change(AUDIT_LOG_TYPE, &name)?;
+        }
+
+        Ok(())
+    }
+
+    // The journal keeps only names of changed records, current state is read by the next incremental export.
+    fn _journal_change(&self, type_: &str, name: &str) -> IndyResult<()> {
+        match self._add(EXPORT_JOURNAL_TYPE, &export_import::journal_entry_name(type_, name)?, "", &HashMap::new()) {
+            Err(ref err) if err.kind() == IndyErrorKind::WalletItemAlreadyExists => Ok(()),
+            res => res
+        }
+    }
+
+    fn _notify_change(&self, entry: &AuditLogEntry) {
+        if let Some(ref change_listener) = self.change_listener {
+            change_listener(entry);
+        }
+    }
 }
 
 #[cfg(test)]
@@ -221,6 +426,7 @@ mod tests {
     use super::*;
 
     use serde_json;
+    use std::cell::RefCell;
     use std::rc::Rc;
     use std::collections::HashMap;
 
@@ -1948,6 +2154,92 @@ mod tests {
         test::cleanup_wallet("wallet_search_works_for_nested_empty");
     }
 
+    #[test]
+    fn wallet_get_audit_log_works() {
+        test::cleanup_wallet("wallet_get_audit_log_works");
+        {
+            let mut wallet = _wallet("wallet_get_audit_log_works");
+            wallet.enable_audit_log();
+
+            wallet.add(_type1(), _id1(), _value1(), &_tags()).unwrap();
+            wallet.update(_type1(), _id1(), _value2()).unwrap();
+            wallet.delete(_type1(), _id1()).unwrap();
+
+            let operations = wallet.get_audit_log(None, None).unwrap()
+                .into_iter()
+                .map(|entry| {
+                    assert_eq!(entry.type_, _type1());
+                    entry.operation
+                })
+                .collect::<Vec<AuditLogOperation>>();
+
+            assert_eq!(3, operations.len());
+            assert!(operations.contains(&AuditLogOperation::Add));
+            assert!(operations.contains(&AuditLogOperation::Update));
+            assert!(operations.contains(&AuditLogOperation::Delete));
+
+            wallet.close().unwrap();
+        }
+        test::cleanup_wallet("wallet_get_audit_log_works");
+    }
+
+    #[test]
+    fn wallet_get_audit_log_works_for_time_range() {
+        test::cleanup_wallet("wallet_get_audit_log_works_for_time_range");
+        {
+            let mut wallet = _wallet("wallet_get_audit_log_works_for_time_range");
+            wallet.enable_audit_log();
+
+            wallet.add(_type1(), _id1(), _value1(), &_tags()).unwrap();
+
+            let entries = wallet.get_audit_log(Some(0), None).unwrap();
+            assert_eq!(1, entries.len());
+
+            let entries = wallet.get_audit_log(Some(entries[0].timestamp + 1), None).unwrap();
+            assert!(entries.is_empty());
+
+            wallet.close().unwrap();
+        }
+        test::cleanup_wallet("wallet_get_audit_log_works_for_time_range");
+    }
+
+    #[test]
+    fn wallet_get_audit_log_works_for_disabled() {
+        test::cleanup_wallet("wallet_get_audit_log_works_for_disabled");
+        {
+            let mut wallet = _wallet("wallet_get_audit_log_works_for_disabled");
+            wallet.add(_type1(), _id1(), _value1(), &_tags()).unwrap();
+
+            assert!(wallet.get_audit_log(None, None).unwrap().is_empty());
+
+            wallet.close().unwrap();
+        }
+        test::cleanup_wallet("wallet_get_audit_log_works_for_disabled");
+    }
+
+    #[test]
+    fn wallet_change_listener_works() {
+        test::cleanup_wallet("wallet_change_listener_works");
+        {
+            let changes: Rc<RefCell<Vec<AuditLogEntry>>> = Rc::new(RefCell::new(Vec::new()));
+
+            let mut wallet = _wallet("wallet_change_listener_works");
+            let listener_changes = changes.clone();
+            wallet.set_change_listener(Some(Box::new(move |entry: &AuditLogEntry| listener_changes.borrow_mut().push(entry.clone()))));
+
+            wallet.add(_type1(), _id1(), _value1(), &_tags()).unwrap();
+            wallet.add_tags(_type1(), _id1(), &_tags()).unwrap();
+
+            let changes = changes.borrow();
+            assert_eq!(2, changes.len());
+            assert_eq!(AuditLogOperation::Add, changes[0].operation);
+            assert_eq!(AuditLogOperation::UpdateTags, changes[1].operation);
+
+            wallet.close().unwrap();
+        }
+        test::cleanup_wallet("wallet_change_listener_works");
+    }
+
     fn _type1() -> &'static str {
         "type1"
     }
@@ -2000,6 +2292,7 @@ mod tests {
             let metadata = Metadata::MetadataArgon(MetadataArgon {
                 master_key_salt: master_key_salt[..].to_vec(),
                 keys: keys.serialize_encrypted(&master_key).unwrap(),
+                audit_log: false,
             });
 
             serde_json::to_vec(&metadata).unwrap()
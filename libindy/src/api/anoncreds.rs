@@ -1,4 +1,4 @@
-use indy_api_types::{ErrorCode, IndyHandle, CommandHandle, WalletHandle, SearchHandle, INVALID_SEARCH_HANDLE};
+use indy_api_types::{ErrorCode, IndyHandle, CommandHandle, PoolHandle, WalletHandle, SearchHandle, INVALID_SEARCH_HANDLE};
 use indy_api_types::errors::prelude::*;
 use crate::commands::{Command, CommandExecutor};
 use crate::commands::anoncreds::AnoncredsCommand;
@@ -7,13 +7,17 @@ use crate::commands::anoncreds::prover::ProverCommand;
 use crate::commands::anoncreds::verifier::VerifierCommand;
 use crate::domain::anoncreds::schema::{Schema, AttributeNames, Schemas};
 use crate::domain::crypto::did::DidValue;
+use crate::domain::cache::GetCacheOptions;
 use crate::domain::anoncreds::credential_definition::{CredentialDefinition, CredentialDefinitionConfig, CredentialDefinitionId, CredentialDefinitions};
 use crate::domain::anoncreds::credential_offer::CredentialOffer;
+use crate::domain::anoncreds::credential_selection::CredentialSelectionPolicy;
 use crate::domain::anoncreds::credential_request::{CredentialRequest, CredentialRequestMetadata};
 use crate::domain::anoncreds::credential_attr_tag_policy::CredentialAttrTagPolicy;
-use crate::domain::anoncreds::credential::{Credential, CredentialValues};
+use crate::domain::anoncreds::credential_batch::{CredentialIssuanceRequests, CredentialRevocationIds};
+use crate::domain::anoncreds::credential::{Credential, RawCredentialValues, ShortCredentialValues};
 use crate::domain::anoncreds::revocation_registry_definition::{RevocationRegistryConfig, RevocationRegistryDefinition, RevocationRegistryId, RevocationRegistryDefinitions};
 use crate::domain::anoncreds::revocation_registry_delta::RevocationRegistryDelta;
+use crate::domain::anoncreds::revocation_registry_pool::RevocationRegistryPoolConfig;
 use crate::domain::anoncreds::proof::Proof;
 use crate::domain::anoncreds::proof_request::{ProofRequest, ProofRequestExtraQuery};
 use crate::domain::anoncreds::requested_credential::RequestedCredentials;
@@ -478,6 +482,192 @@ pub extern fn indy_issuer_create_and_store_revoc_reg(command_handle: CommandHand
     res
 }
 
+/// Callback type for publishing revocation registries created by a revocation registry pool
+/// (see `indy_issuer_create_revoc_reg_pool`).
+///
+/// The hook is called on the libindy command thread, so it must not wait for the results of other libindy calls.
+/// It is expected to schedule sending of REVOC_REG_DEF and REVOC_REG_ENTRY transactions and return immediately.
+///
+/// # params
+/// revoc_reg_id: identifier of created revocation registry definition
+/// revoc_reg_def_json: public part of revocation registry definition
+/// revoc_reg_entry_json: revocation registry entry that defines initial state of revocation registry
+///
+/// # return
+/// Success if the registry is going to be published. Any other code leaves the registry unpublished
+/// and the hook will be called for it again before the registry is used for issuance.
+pub type RevocRegCreatedHook = extern fn(revoc_reg_id: *const c_char,
+                                         revoc_reg_def_json: *const c_char,
+                                         revoc_reg_entry_json: *const c_char) -> ErrorCode;
+
+/// Register the hook to be called each time a revocation registry pool creates a new revocation registry
+/// (see type description for `RevocRegCreatedHook`). Only one hook can be registered, the latest one wins.
+///
+/// If no hook is registered all pool registries are considered published and the issuer
+/// is responsible for sending them to the ledger (see `indy_issuer_get_revoc_reg_pool`).
+///
+/// #Params
+/// command_handle: command handle to map callback to user context.
+/// hook: hook to call for created revocation registries (pass NULL to unregister).
+/// cb: Callback that takes command result as parameter.
+///
+/// #Errors
+/// Common*
+#[no_mangle]
+pub extern fn indy_issuer_register_revoc_reg_created_hook(command_handle: CommandHandle,
+                                                          hook: Option<RevocRegCreatedHook>,
+                                                          cb: Option<extern fn(command_handle_: CommandHandle, err: ErrorCode)>) -> ErrorCode {
+    trace!("indy_issuer_register_revoc_reg_created_hook: >>> hook: {:?}", hook);
+
+    check_useful_c_callback!(cb, ErrorCode::CommonInvalidParam3);
+
+    trace!("indy_issuer_register_revoc_reg_created_hook: entities >>> hook: {:?}", hook);
+
+    let result = CommandExecutor::instance()
+        .send(Command::Anoncreds(
+            AnoncredsCommand::Issuer(
+                IssuerCommand::RegisterRevocationRegistryCreatedHook(
+                    hook,
+                    Box::new(move |result| {
+                        let err = prepare_result!(result);
+                        trace!("indy_issuer_register_revoc_reg_created_hook: err: {:?}", err);
+                        cb(command_handle, err)
+                    })
+                ))));
+
+    let res = prepare_result!(result);
+
+    trace!("indy_issuer_register_revoc_reg_created_hook: <<< res: {:?}", res);
+
+    res
+}
+
+/// Create a pool of revocation registries for the credential definition.
+///
+/// The pool consists of the active revocation registry used for issuance and the pre-provisioned next one.
+/// Passing the id of any pool registry to `indy_issuer_create_credential` issues the credential in the active registry.
+/// When the active registry is full the next registry becomes active and a new next registry is created,
+/// so the issuer never has to switch registries manually. The credential json returned by `indy_issuer_create_credential`
+/// contains the id of the registry actually used.
+///
+/// Each created registry is passed to the hook registered with `indy_issuer_register_revoc_reg_created_hook`.
+/// A registry the hook failed for is never activated until the hook succeeds for it.
+///
+/// Tails of all pool registries are written with the configured writer. The tails reader passed to
+/// `indy_issuer_create_credential` must be able to read all of them.
+///
+/// #Params
+/// command_handle: command handle to map callback to user context.
+/// wallet_handle: wallet handle (created by open_wallet).
+/// issuer_did: a DID of the issuer
+/// cred_def_id: id of stored in ledger credential definition
+/// config_json: pool configuration as json:
+/// {
+///     "issuance_type": (optional) type of issuance of pool registries (see `indy_issuer_create_and_store_revoc_reg`),
+///     "max_cred_num": (optional) maximum number of credentials each pool registry can process (default 100000),
+///     "tag_prefix": (optional) prefix of pool registry tags, registries are tagged as <tag_prefix>_<n> (default "pool"),
+///     "tails_writer_type": (optional) type of blob storage writer for tails (default "default"),
+///     "tails_writer_config": string - config of blob storage writer for tails (see `indy_open_blob_storage_writer`),
+/// }
+/// cb: Callback that takes command result as parameter.
+///
+/// #Returns
+/// revoc_reg_pool_json: state of the pool
+/// {
+///     "cred_def_id": string - id of the credential definition,
+///     "issuer_did": string - a DID of the issuer,
+///     "config": <config_json>,
+///     "active": string - id of the revocation registry used for issuance,
+///     "next": string - id of the revocation registry that becomes active when the active one is full,
+///     "registries": [string] - ids of all pool registries in creation order,
+///     "published": [string] - ids of pool registries the hook succeeded for,
+/// }
+///
+/// #Errors
+/// Common*
+/// Wallet*
+/// Anoncreds*
+#[no_mangle]
+pub extern fn indy_issuer_create_revoc_reg_pool(command_handle: CommandHandle,
+                                                wallet_handle: WalletHandle,
+                                                issuer_did: *const c_char,
+                                                cred_def_id: *const c_char,
+                                                config_json: *const c_char,
+                                                cb: Option<extern fn(command_handle_: CommandHandle, err: ErrorCode,
+                                                                     revoc_reg_pool_json: *const c_char)>) -> ErrorCode {
+    trace!("indy_issuer_create_revoc_reg_pool: >>> wallet_handle: {:?}, issuer_did: {:?}, cred_def_id: {:?}, config_json: {:?}",
+           wallet_handle, issuer_did, cred_def_id, config_json);
+
+    check_useful_validatable_string!(issuer_did, ErrorCode::CommonInvalidParam3, DidValue);
+    check_useful_validatable_string!(cred_def_id, ErrorCode::CommonInvalidParam4, CredentialDefinitionId);
+    check_useful_validatable_json!(config_json, ErrorCode::CommonInvalidParam5, RevocationRegistryPoolConfig);
+    check_useful_c_callback!(cb, ErrorCode::CommonInvalidParam6);
+
+    trace!("indy_issuer_create_revoc_reg_pool: entities >>> wallet_handle: {:?}, issuer_did: {:?}, cred_def_id: {:?}, config_json: {:?}",
+           wallet_handle, issuer_did, cred_def_id, config_json);
+
+    let result = CommandExecutor::instance()
+        .send(Command::Anoncreds(
+            AnoncredsCommand::Issuer(
+                IssuerCommand::CreateRevocationRegistryPool(
+                    wallet_handle,
+                    issuer_did,
+                    cred_def_id,
+                    config_json,
+                    boxed_callback_string!("indy_issuer_create_revoc_reg_pool", cb, command_handle)
+                ))));
+
+    let res = prepare_result!(result);
+
+    trace!("indy_issuer_create_revoc_reg_pool: <<< res: {:?}", res);
+
+    res
+}
+
+/// Get the state of the revocation registry pool created for the credential definition
+/// by `indy_issuer_create_revoc_reg_pool`.
+///
+/// #Params
+/// command_handle: command handle to map callback to user context.
+/// wallet_handle: wallet handle (created by open_wallet).
+/// cred_def_id: id of the credential definition the pool is created for
+/// cb: Callback that takes command result as parameter.
+///
+/// #Returns
+/// revoc_reg_pool_json: state of the pool (see `indy_issuer_create_revoc_reg_pool`)
+///
+/// #Errors
+/// Common*
+/// Wallet*
+#[no_mangle]
+pub extern fn indy_issuer_get_revoc_reg_pool(command_handle: CommandHandle,
+                                             wallet_handle: WalletHandle,
+                                             cred_def_id: *const c_char,
+                                             cb: Option<extern fn(command_handle_: CommandHandle, err: ErrorCode,
+                                                                  revoc_reg_pool_json: *const c_char)>) -> ErrorCode {
+    trace!("indy_issuer_get_revoc_reg_pool: >>> wallet_handle: {:?}, cred_def_id: {:?}", wallet_handle, cred_def_id);
+
+    check_useful_validatable_string!(cred_def_id, ErrorCode::CommonInvalidParam3, CredentialDefinitionId);
+    check_useful_c_callback!(cb, ErrorCode::CommonInvalidParam4);
+
+    trace!("indy_issuer_get_revoc_reg_pool: entities >>> wallet_handle: {:?}, cred_def_id: {:?}", wallet_handle, cred_def_id);
+
+    let result = CommandExecutor::instance()
+        .send(Command::Anoncreds(
+            AnoncredsCommand::Issuer(
+                IssuerCommand::GetRevocationRegistryPool(
+                    wallet_handle,
+                    cred_def_id,
+                    boxed_callback_string!("indy_issuer_get_revoc_reg_pool", cb, command_handle)
+                ))));
+
+    let res = prepare_result!(result);
+
+    trace!("indy_issuer_get_revoc_reg_pool: <<< res: {:?}", res);
+
+    res
+}
+
 /// Create credential offer that will be used by Prover for
 /// credential request creation. Offer includes nonce and key correctness proof
 /// for authentication between protocol steps and integrity checking.
@@ -556,6 +746,11 @@ pub extern fn indy_issuer_create_credential_offer(command_handle: CommandHandle,
 ///      "attr2" : {"raw": "value1", "encoded": "value1_as_int" }
 ///     }
 ///   If you want to use empty value for some credential field, you should set "raw" to "" and "encoded" should not be empty
+///   "encoded" can be omitted: in this case it will be calculated with the canonical encoding (see `indy_encode_credential_values`)
+///     {
+///      "attr1" : {"raw": "value1"},
+///      "attr2" : {"raw": "value1", "encoded": "value1_as_int" }
+///     }
 /// rev_reg_id: id of revocation registry stored in the wallet
 /// blob_storage_reader_handle: configuration of blob storage reader handle that will allow to read revocation tails (returned by `indy_open_blob_storage_reader`)
 /// cb: Callback that takes command result as parameter.
@@ -605,7 +800,7 @@ pub extern fn indy_issuer_create_credential(command_handle: CommandHandle,
 
     check_useful_validatable_json!(cred_offer_json, ErrorCode::CommonInvalidParam3, CredentialOffer);
     check_useful_validatable_json!(cred_req_json, ErrorCode::CommonInvalidParam4, CredentialRequest);
-    check_useful_validatable_json!(cred_values_json, ErrorCode::CommonInvalidParam5, CredentialValues);
+    check_useful_validatable_json!(cred_values_json, ErrorCode::CommonInvalidParam5, RawCredentialValues);
     check_useful_validatable_opt_string!(rev_reg_id, ErrorCode::CommonInvalidParam6, RevocationRegistryId);
     check_useful_c_callback!(cb, ErrorCode::CommonInvalidParam8);
 
@@ -644,6 +839,86 @@ pub extern fn indy_issuer_create_credential(command_handle: CommandHandle,
     res
 }
 
+/// Create credentials for a batch of credential requests in the same revocation registry.
+///
+/// Works as `indy_issuer_create_credential` called for each request, but the revocation registry state
+/// is stored into the wallet only once all credentials are issued: if any credential can't be issued
+/// (for example the registry can't hold the whole batch) the wallet is left untouched.
+/// Instead of a delta per credential this call returns a single merged revoc registry delta
+/// intended to be shared as one REVOC_REG_ENTRY transaction.
+///
+/// #Params
+/// command_handle: command handle to map callback to user context.
+/// wallet_handle: wallet handle (created by open_wallet).
+/// cred_requests_json: list of credentials to issue for the same credential definition
+///     [{
+///         "cred_offer": a cred offer created by indy_issuer_create_credential_offer,
+///         "cred_req": a credential request created by indy_prover_create_credential_req,
+///         "cred_values": credential values (see `cred_values_json` of `indy_issuer_create_credential`)
+///     }]
+/// rev_reg_id: id of revocation registry stored in the wallet
+/// blob_storage_reader_handle: configuration of blob storage reader handle that will allow to read revocation tails (returned by `indy_open_blob_storage_reader`)
+/// cb: Callback that takes command result as parameter.
+///
+/// #Returns
+/// creds_json: issued credentials in the order of requests
+///     [{
+///         "cred": <credential json> (see `cred_json` of `indy_issuer_create_credential`),
+///         "cred_revoc_id": Optional<string> - local id for revocation info
+///     }]
+/// revoc_reg_delta_json: Revocation registry delta json with all newly issued credentials
+///
+/// #Errors
+/// Anoncreds*
+/// Common*
+/// Wallet*
+#[no_mangle]
+pub extern fn indy_issuer_create_credentials(command_handle: CommandHandle,
+                                             wallet_handle: WalletHandle,
+                                             cred_requests_json: *const c_char,
+                                             rev_reg_id: *const c_char,
+                                             blob_storage_reader_handle: IndyHandle,
+                                             cb: Option<extern fn(command_handle_: CommandHandle, err: ErrorCode,
+                                                                  creds_json: *const c_char,
+                                                                  revoc_reg_delta_json: *const c_char)>) -> ErrorCode {
+    trace!("indy_issuer_create_credentials: >>> wallet_handle: {:?}, cred_requests_json: {:?}, rev_reg_id: {:?}, blob_storage_reader_handle: {:?}",
+           wallet_handle, cred_requests_json, rev_reg_id, blob_storage_reader_handle);
+
+    check_useful_validatable_json!(cred_requests_json, ErrorCode::CommonInvalidParam3, CredentialIssuanceRequests);
+    check_useful_validatable_opt_string!(rev_reg_id, ErrorCode::CommonInvalidParam4, RevocationRegistryId);
+    check_useful_c_callback!(cb, ErrorCode::CommonInvalidParam6);
+
+    let blob_storage_reader_handle = if blob_storage_reader_handle != -1 { Some(blob_storage_reader_handle) } else { None };
+
+    trace!("indy_issuer_create_credentials: entities >>> wallet_handle: {:?}, cred_requests_json: {:?}, rev_reg_id: {:?}, blob_storage_reader_handle: {:?}",
+           wallet_handle, secret!(&cred_requests_json), rev_reg_id, blob_storage_reader_handle);
+
+    let result = CommandExecutor::instance()
+        .send(Command::Anoncreds(
+            AnoncredsCommand::Issuer(
+                IssuerCommand::CreateCredentials(
+                    wallet_handle,
+                    cred_requests_json,
+                    rev_reg_id,
+                    blob_storage_reader_handle,
+                    Box::new(move |result| {
+                        let (err, creds_json, revoc_reg_delta_json) = prepare_result_2!(result, String::new(), None);
+                        trace!("indy_issuer_create_credentials: creds_json: {:?}, revoc_reg_delta_json: {:?}",
+                               secret!(creds_json.as_str()), revoc_reg_delta_json);
+                        let creds_json = ctypes::string_to_cstring(creds_json);
+                        let revoc_reg_delta_json = revoc_reg_delta_json.map(ctypes::string_to_cstring);
+                        cb(command_handle, err, creds_json.as_ptr(),
+                           revoc_reg_delta_json.as_ref().map(|delta| delta.as_ptr()).unwrap_or(ptr::null()))
+                    })
+                ))));
+
+    let res = prepare_result!(result);
+
+    trace!("indy_issuer_create_credentials: <<< res: {:?}", res);
+
+    res
+}
+
 /// Revoke a credential identified by a cred_revoc_id (returned by indy_issuer_create_credential).
 ///
 /// The corresponding credential definition and revocation registry must be already
@@ -711,6 +986,66 @@ pub extern fn indy_issuer_revoke_credential(command_handle: CommandHandle,
     res
 }
 
+/// Revoke a list of credentials identified by cred_revoc_ids (returned by indy_issuer_create_credential)
+/// in the same revocation registry.
+///
+/// Works as `indy_issuer_revoke_credential` called for each id, but the revocation registry state
+/// is stored into the wallet only once all credentials are revoked: if any id is invalid the wallet is left untouched.
+/// Instead of a delta per credential this call returns a single merged revoc registry delta
+/// intended to be shared as one REVOC_REG_ENTRY transaction.
+///
+/// #Params
+/// command_handle: command handle to map callback to user context.
+/// wallet_handle: wallet handle (created by open_wallet).
+/// blob_storage_reader_cfg_handle: configuration of blob storage reader handle that will allow to read revocation tails (returned by `indy_open_blob_storage_reader`).
+/// rev_reg_id: id of revocation registry stored in wallet
+/// cred_revoc_ids_json: list of local ids for revocation info related to issued credentials: [string]
+/// cb: Callback that takes command result as parameter.
+///
+/// #Returns
+/// revoc_reg_delta_json: Revocation registry delta json with all revoked credentials
+/// (see `revoc_reg_delta_json` of `indy_issuer_revoke_credential`)
+///
+/// #Errors
+/// Anoncreds*
+/// Common*
+/// Wallet*
+#[no_mangle]
+pub extern fn indy_issuer_revoke_credentials(command_handle: CommandHandle,
+                                             wallet_handle: WalletHandle,
+                                             blob_storage_reader_cfg_handle: IndyHandle,
+                                             rev_reg_id: *const c_char,
+                                             cred_revoc_ids_json: *const c_char,
+                                             cb: Option<extern fn(command_handle_: CommandHandle, err: ErrorCode,
+                                                                  revoc_reg_delta_json: *const c_char)>) -> ErrorCode {
+    trace!("indy_issuer_revoke_credentials: >>> wallet_handle: {:?}, blob_storage_reader_cfg_handle: {:?}, rev_reg_id: {:?}, cred_revoc_ids_json: {:?}",
+           wallet_handle, blob_storage_reader_cfg_handle, rev_reg_id, cred_revoc_ids_json);
+
+    check_useful_validatable_string!(rev_reg_id, ErrorCode::CommonInvalidParam4, RevocationRegistryId);
+    check_useful_validatable_json!(cred_revoc_ids_json, ErrorCode::CommonInvalidParam5, CredentialRevocationIds);
+    check_useful_c_callback!(cb, ErrorCode::CommonInvalidParam6);
+
+    trace!("indy_issuer_revoke_credentials: entities >>> wallet_handle: {:?}, blob_storage_reader_cfg_handle: {:?}, rev_reg_id: {:?}, cred_revoc_ids_json: {:?}",
+           wallet_handle, blob_storage_reader_cfg_handle, rev_reg_id, secret!(&cred_revoc_ids_json));
+
+    let result = CommandExecutor::instance()
+        .send(Command::Anoncreds(
+            AnoncredsCommand::Issuer(
+                IssuerCommand::RevokeCredentials(
+                    wallet_handle,
+                    blob_storage_reader_cfg_handle,
+                    rev_reg_id,
+                    cred_revoc_ids_json,
+                    boxed_callback_string!("indy_issuer_revoke_credentials", cb, command_handle)
+                ))));
+
+    let res = prepare_result!(result);
+
+    trace!("indy_issuer_revoke_credentials: <<< res: {:?}", res);
+
+    res
+}
+
 /*/// Recover a credential identified by a cred_revoc_id (returned by indy_issuer_create_credential).
 ///
 /// The corresponding credential definition and revocation registry must be already
@@ -773,6 +1108,121 @@ pub extern fn indy_issuer_recover_credential(command_handle: CommandHandle,
     prepare_result!(result)
 }*/
 
+/// Get the state of the revocation registry stored in the issuer's wallet.
+///
+/// #Params
+/// command_handle: command handle to map callback to user context.
+/// wallet_handle: wallet handle (created by open_wallet).
+/// rev_reg_id: id of revocation registry stored in the wallet
+/// cb: Callback that takes command result as parameter.
+///
+/// #Returns
+/// revoc_reg_status_json: state of the revocation registry
+/// {
+///     "rev_reg_id": string - id of the revocation registry,
+///     "issuance_type": string - ISSUANCE_BY_DEFAULT or ISSUANCE_ON_DEMAND,
+///     "max_cred_num": number - capacity of the registry,
+///     "next_index": Optional<number> - index the next credential will be issued with (null if the registry is full),
+///     "issued": number - count of issued indices,
+///     "revoked": number - count of revoked indices,
+///     "revoked_ids": [number] - revoked indices in ascending order
+/// }
+///
+/// #Errors
+/// Anoncreds*
+/// Common*
+/// Wallet*
+#[no_mangle]
+pub extern fn indy_issuer_get_revoc_reg_status(command_handle: CommandHandle,
+                                               wallet_handle: WalletHandle,
+                                               rev_reg_id: *const c_char,
+                                               cb: Option<extern fn(command_handle_: CommandHandle, err: ErrorCode,
+                                                                    revoc_reg_status_json: *const c_char)>) -> ErrorCode {
+    trace!("indy_issuer_get_revoc_reg_status: >>> wallet_handle: {:?}, rev_reg_id: {:?}", wallet_handle, rev_reg_id);
+
+    check_useful_validatable_string!(rev_reg_id, ErrorCode::CommonInvalidParam3, RevocationRegistryId);
+    check_useful_c_callback!(cb, ErrorCode::CommonInvalidParam4);
+
+    trace!("indy_issuer_get_revoc_reg_status: entities >>> wallet_handle: {:?}, rev_reg_id: {:?}", wallet_handle, rev_reg_id);
+
+    let result = CommandExecutor::instance()
+        .send(Command::Anoncreds(
+            AnoncredsCommand::Issuer(
+                IssuerCommand::GetRevocationRegistryStatus(
+                    wallet_handle,
+                    rev_reg_id,
+                    boxed_callback_string!("indy_issuer_get_revoc_reg_status", cb, command_handle)
+                ))));
+
+    let res = prepare_result!(result);
+
+    trace!("indy_issuer_get_revoc_reg_status: <<< res: {:?}", res);
+
+    res
+}
+
+/// Compare the accumulator of the revocation registry stored in the issuer's wallet
+/// with the current accumulator on the ledger (GET_REVOC_REG) to detect unpublished or lost registry entries.
+///
+/// #Params
+/// command_handle: command handle to map callback to user context.
+/// pool_handle: pool handle (created by open_pool_ledger).
+/// wallet_handle: wallet handle (created by open_wallet).
+/// submitter_did: (Optional) DID of the read request sender (if not provided then default Libindy DID will be used).
+/// rev_reg_id: id of revocation registry stored in the wallet
+/// cb: Callback that takes command result as parameter.
+///
+/// #Returns
+/// revoc_reg_drift_json: result of the comparison
+/// {
+///     "rev_reg_id": string - id of the revocation registry,
+///     "in_sync": bool - whether the local accumulator matches the ledger one,
+///     "local_accum": string - accumulator stored in the wallet,
+///     "ledger_accum": string - accumulator stored on the ledger,
+///     "ledger_timestamp": number - time of the ledger revocation registry entry
+/// }
+///
+/// #Errors
+/// Anoncreds*
+/// Common*
+/// Ledger*
+/// Wallet*
+#[no_mangle]
+pub extern fn indy_issuer_check_revoc_reg_drift(command_handle: CommandHandle,
+                                                pool_handle: PoolHandle,
+                                                wallet_handle: WalletHandle,
+                                                submitter_did: *const c_char,
+                                                rev_reg_id: *const c_char,
+                                                cb: Option<extern fn(command_handle_: CommandHandle, err: ErrorCode,
+                                                                     revoc_reg_drift_json: *const c_char)>) -> ErrorCode {
+    trace!("indy_issuer_check_revoc_reg_drift: >>> pool_handle: {:?}, wallet_handle: {:?}, submitter_did: {:?}, rev_reg_id: {:?}",
+           pool_handle, wallet_handle, submitter_did, rev_reg_id);
+
+    check_useful_validatable_opt_string!(submitter_did, ErrorCode::CommonInvalidParam4, DidValue);
+    check_useful_validatable_string!(rev_reg_id, ErrorCode::CommonInvalidParam5, RevocationRegistryId);
+    check_useful_c_callback!(cb, ErrorCode::CommonInvalidParam6);
+
+    trace!("indy_issuer_check_revoc_reg_drift: entities >>> pool_handle: {:?}, wallet_handle: {:?}, submitter_did: {:?}, rev_reg_id: {:?}",
+           pool_handle, wallet_handle, submitter_did, rev_reg_id);
+
+    let result = CommandExecutor::instance()
+        .send(Command::Anoncreds(
+            AnoncredsCommand::Issuer(
+                IssuerCommand::CheckRevocationRegistryDrift(
+                    pool_handle,
+                    wallet_handle,
+                    submitter_did,
+                    rev_reg_id,
+                    boxed_callback_string!("indy_issuer_check_revoc_reg_drift", cb, command_handle)
+                ))));
+
+    let res = prepare_result!(result);
+
+    trace!("indy_issuer_check_revoc_reg_drift: <<< res: {:?}", res);
+
+    res
+}
+
 /// Merge two revocation registry deltas (returned by indy_issuer_create_credential or indy_issuer_revoke_credential) to accumulate common delta.
 /// Send common delta to ledger to reduce the load.
 ///
@@ -1540,8 +1990,9 @@ pub  extern fn indy_prover_close_credentials_search(command_handle: CommandHandl
 /// predicate_info: Describes requested attribute predicate
 ///     {
 ///         "name": attribute name, (case insensitive and ignore spaces)
-///         "p_type": predicate type (">=", ">", "<=", "<")
+///         "p_type": predicate type (">=", ">", "<=", "<", "between")
 ///         "p_value": int predicate value
+///         "p_value_to": Optional<int>, // upper bound of "between" predicate, "p_value" is the lower one (both inclusive)
 ///         "restrictions": Optional<filter_json>, // see below
 ///         "non_revoked": Optional<<non_revoc_interval>>, // see below,
 ///                        // If specified prover must proof non-revocation
@@ -1668,8 +2119,9 @@ pub extern fn indy_prover_get_credentials_for_proof_req(command_handle: CommandH
 /// predicate_info: Describes requested attribute predicate
 ///     {
 ///         "name": attribute name, (case insensitive and ignore spaces)
-///         "p_type": predicate type (">=", ">", "<=", "<")
+///         "p_type": predicate type (">=", ">", "<=", "<", "between")
 ///         "p_value": predicate value
+///         "p_value_to": Optional<int>, // upper bound of "between" predicate, "p_value" is the lower one (both inclusive)
 ///         "restrictions": Optional<wql query>, // see below
 ///         "non_revoked": Optional<<non_revoc_interval>>, // see below,
 ///                        // If specified prover must proof non-revocation
@@ -1852,6 +2304,74 @@ pub  extern fn indy_prover_close_credentials_search_for_proof_req(command_handle
     res
 }
 
+/// Selects credentials from the wallet to satisfy the proof request and builds
+/// requested credentials json ready to be passed to `indy_prover_create_proof`.
+///
+/// Candidates for every referent are the same as returned by `indy_prover_get_credentials_for_proof_req`.
+/// Selected attributes are always revealed. For revocable credentials `timestamp` is set to the end (`to`)
+/// of the non-revocation interval (current time if `to` is omitted), so revocation states must be created for this time.
+///
+/// #Params
+/// command_handle: command handle to map callback to user context.
+/// wallet_handle: wallet handle (created by open_wallet).
+/// proof_request_json: proof request json (see `indy_prover_create_proof`)
+/// policy_json: selection policy
+///     {
+///         "strategy": Optional<string> - how to choose among several suitable credentials:
+///             - "newest" (default) - the most recently stored credential for every referent
+///             - "fewest_credentials" - cover all referents with as few credentials as possible
+///             - "prefer_non_revocable" - non-revocable credentials first, the most recently stored otherwise
+///         "self_attested_attributes": Optional<object> - values for requested attributes which must be self attested
+///             {"<attr_referent>": string, ...}
+///     }
+/// cb: Callback that takes command result as parameter.
+///
+/// #Returns
+/// selection_json:
+///     {
+///         "requested_credentials": <requested_credentials_json>, // see `indy_prover_create_proof`
+///         "unmet_referents": {
+///             "requested_attributes": [<attr_referent>, ...], // attributes no credential found for
+///             "requested_predicates": [<predicate_referent>, ...], // predicates no credential found for
+///         }
+///     }
+///
+/// #Errors
+/// Anoncreds*
+/// Common*
+/// Wallet*
+#[no_mangle]
+pub extern fn indy_prover_auto_select_credentials(command_handle: CommandHandle,
+                                                  wallet_handle: WalletHandle,
+                                                  proof_request_json: *const c_char,
+                                                  policy_json: *const c_char,
+                                                  cb: Option<extern fn(command_handle_: CommandHandle, err: ErrorCode,
+                                                                       selection_json: *const c_char)>) -> ErrorCode {
+    trace!("indy_prover_auto_select_credentials: >>> wallet_handle: {:?}, proof_request_json: {:?}, policy_json: {:?}",
+           wallet_handle, proof_request_json, policy_json);
+
+    check_useful_validatable_json!(proof_request_json, ErrorCode::CommonInvalidParam3, ProofRequest);
+    check_useful_validatable_json!(policy_json, ErrorCode::CommonInvalidParam4, CredentialSelectionPolicy);
+    check_useful_c_callback!(cb, ErrorCode::CommonInvalidParam5);
+
+    trace!("indy_prover_auto_select_credentials: entities >>> wallet_handle: {:?}, proof_request_json: {:?}, policy_json: {:?}",
+           wallet_handle, proof_request_json, policy_json);
+
+    let result = CommandExecutor::instance()
+        .send(Command::Anoncreds(AnoncredsCommand::Prover(ProverCommand::AutoSelectCredentials(
+            wallet_handle,
+            proof_request_json,
+            policy_json,
+            boxed_callback_string!("indy_prover_auto_select_credentials", cb, command_handle)
+        ))));
+
+    let res = prepare_result!(result);
+
+    trace!("indy_prover_auto_select_credentials: <<< res: {:?}", res);
+
+    res
+}
+
 /// Creates a proof according to the given proof request
 /// Either a corresponding credential with optionally revealed attributes or self-attested attribute must be provided
 /// for each requested attribute (see indy_prover_get_credentials_for_pool_req).
@@ -1945,8 +2465,9 @@ pub  extern fn indy_prover_close_credentials_search_for_proof_req(command_handle
 /// predicate_info: Describes requested attribute predicate
 ///     {
 ///         "name": attribute name, (case insensitive and ignore spaces)
-///         "p_type": predicate type (">=", ">", "<=", "<")
+///         "p_type": predicate type (">=", ">", "<=", "<", "between")
 ///         "p_value": predicate value
+///         "p_value_to": Optional<int>, // upper bound of "between" predicate, "p_value" is the lower one (both inclusive)
 ///         "restrictions": Optional<wql query>, // see below
 ///         "non_revoked": Optional<<non_revoc_interval>>, // see below,
 ///                        // If specified prover must proof non-revocation
@@ -2177,8 +2698,9 @@ pub extern fn indy_prover_create_proof(command_handle: CommandHandle,
 /// predicate_info: Describes requested attribute predicate
 ///     {
 ///         "name": attribute name, (case insensitive and ignore spaces)
-///         "p_type": predicate type (">=", ">", "<=", "<")
+///         "p_type": predicate type (">=", ">", "<=", "<", "between")
 ///         "p_value": predicate value
+///         "p_value_to": Optional<int>, // upper bound of "between" predicate, "p_value" is the lower one (both inclusive)
 ///         "restrictions": Optional<wql query>, // see below
 ///         "non_revoked": Optional<<non_revoc_interval>>, // see below,
 ///                        // If specified prover must proof non-revocation
@@ -2205,6 +2727,10 @@ pub extern fn indy_prover_create_proof(command_handle: CommandHandle,
 /// 
 /// cb: Callback that takes command result as parameter.
 ///
+/// If `check_canonical_encoding` runtime config is set (see `indy_set_runtime_config`),
+/// raw values of revealed attributes must match the canonical encoding (see `indy_encode_credential_values`),
+/// otherwise the proof is rejected.
+///
 /// #Returns
 /// valid: true - if signature is valid, false - otherwise
 ///
@@ -2259,6 +2785,194 @@ pub extern fn indy_verifier_verify_proof(command_handle: CommandHandle,
     res
 }
 
+/// Verifies a proof (of multiple credential) and returns a detailed report
+/// instead of a bare boolean.
+///
+/// Unlike `indy_verifier_verify_proof` most problems of the proof don't end up with an error:
+/// every requested attribute and predicate is checked separately and the results of all checks are returned.
+///
+/// #Params
+/// command_handle: command handle to map callback to user context.
+/// proof_request_json: proof request json. The same as for `indy_verifier_verify_proof`.
+/// proof_json: created for request proof json. The same as for `indy_verifier_verify_proof`.
+/// schemas_json: all schemas participating in the proof. The same as for `indy_verifier_verify_proof`.
+/// credential_defs_json: all credential definitions participating in the proof. The same as for `indy_verifier_verify_proof`.
+/// rev_reg_defs_json: all revocation registry definitions participating in the proof. The same as for `indy_verifier_verify_proof`.
+/// rev_regs_json: all revocation registries participating in the proof. The same as for `indy_verifier_verify_proof`.
+/// cb: Callback that takes command result as parameter.
+///
+/// #Returns
+/// report_json: proof verification report
+///     {
+///         "valid": bool, - true if status is "valid"
+///         "status": string, - one of:
+///             "valid" - proof is cryptographically valid and satisfies proof request
+///             "proof_invalid" - proof is malformed or its cryptographic verification failed
+///             "request_not_satisfied" - proof is valid but doesn't satisfy proof request
+///             "missing_ledger_data" - some of schemas, credential definitions or revocation registries are not provided
+///         "requested_attributes": {
+///             "<attr_referent>": <referent_report>, // see below
+///             ...,
+///         },
+///         "requested_predicates": {
+///             "<predicate_referent>": <referent_report>, // see below
+///             ...,
+///         },
+///         "sub_proofs": [<sub_proof_report>, ...], // see below, in order of `proof[identifiers]`
+///         "crypto": Optional<<check>>, // result of cryptographic verification,
+///                                      // null if it was skipped because of missing ledger data
+///     }
+/// where
+/// referent_report:
+///     {
+///         "sub_proof_index": Optional<int>,
+///         "self_attested": bool,
+///         "received": <check>, // referent is present in both proof request and proof
+///         "values": Optional<<check>>, // encoded values of revealed attributes match to CryptoProof
+///         "encoding": Optional<<check>>, // raw values of revealed attributes match to the canonical encoding (see `indy_encode_credential_values`)
+///                                        // makes proof invalid only if `check_canonical_encoding` runtime config is set (see `indy_set_runtime_config`),
+///                                        // otherwise informational only as credentials could be issued with a custom encoding
+///         "restrictions": Optional<<check>>, // requested restrictions are satisfied
+///         "timestamp": Optional<<check>>, // timestamp is provided for requested non-revocation interval
+///     }
+/// sub_proof_report:
+///     {
+///         "schema_id": string,
+///         "cred_def_id": string,
+///         "rev_reg_id": Optional<string>,
+///         "timestamp": Optional<int>,
+///         "ledger_data": <check>, // schema and credential definition are provided
+///         "non_revoked": Optional<<check>>, // revocation registry is provided and non-revocation is confirmed
+///                                           // (non-revocation proof is verified as a part of cryptographic verification)
+///     }
+/// check:
+///     {
+///         "passed": bool,
+///         "error": Optional<string>, // description of failure
+///     }
+///
+/// #Errors
+/// Anoncreds*
+/// Common*
+#[no_mangle]
+pub extern fn indy_verifier_verify_proof_with_report(command_handle: CommandHandle,
+                                                     proof_request_json: *const c_char,
+                                                     proof_json: *const c_char,
+                                                     schemas_json: *const c_char,
+                                                     credential_defs_json: *const c_char,
+                                                     rev_reg_defs_json: *const c_char,
+                                                     rev_regs_json: *const c_char,
+                                                     cb: Option<extern fn(command_handle_: CommandHandle, err: ErrorCode,
+                                                                          report_json: *const c_char)>) -> ErrorCode {
+    trace!("indy_verifier_verify_proof_with_report: >>> proof_request_json: {:?}, proof_json: {:?}, schemas_json: {:?}, credential_defs_json: {:?}, \
+    rev_reg_defs_json: {:?}, rev_regs_json: {:?}", proof_request_json, proof_json, schemas_json, credential_defs_json, rev_reg_defs_json, rev_regs_json);
+
+    check_useful_validatable_json!(proof_request_json, ErrorCode::CommonInvalidParam2, ProofRequest);
+    check_useful_validatable_json!(proof_json, ErrorCode::CommonInvalidParam3, Proof);
+    check_useful_json!(schemas_json, ErrorCode::CommonInvalidParam4, Schemas);
+    check_useful_json!(credential_defs_json, ErrorCode::CommonInvalidParam5, CredentialDefinitions);
+    check_useful_json!(rev_reg_defs_json, ErrorCode::CommonInvalidParam6, RevocationRegistryDefinitions);
+    check_useful_json!(rev_regs_json, ErrorCode::CommonInvalidParam7, RevocationRegistries);
+    check_useful_c_callback!(cb, ErrorCode::CommonInvalidParam8);
+
+    trace!("indy_verifier_verify_proof_with_report: entities >>> proof_request_json: {:?}, proof_json: {:?}, schemas_json: {:?}, credential_defs_json: {:?}, \
+    rev_reg_defs_json: {:?}, rev_regs_json: {:?}", proof_request_json, proof_json, schemas_json, credential_defs_json, rev_reg_defs_json, rev_regs_json);
+
+    let result = CommandExecutor::instance()
+        .send(Command::Anoncreds(AnoncredsCommand::Verifier(VerifierCommand::VerifyProofWithReport(
+            proof_request_json,
+            proof_json,
+            schemas_json,
+            credential_defs_json,
+            rev_reg_defs_json,
+            rev_regs_json,
+            boxed_callback_string!("indy_verifier_verify_proof_with_report", cb, command_handle)
+        ))));
+
+    let res = prepare_result!(result);
+
+    trace!("indy_verifier_verify_proof_with_report: <<< res: {:?}", res);
+
+    res
+}
+
+/// Verifies a proof (of multiple credential) resolving all the ledger objects it refers to.
+///
+/// Works the same way as `indy_verifier_verify_proof_with_report` but instead of taking schemas, credential definitions,
+/// revocation registry definitions and revocation registries from the caller
+/// fetches them for every entry of `proof.identifiers`:
+///  - schemas, credential definitions and revocation registry definitions are taken through the cache
+///    (see `indy_get_schema` and `indy_get_cred_def`),
+///  - revocation registries are requested from the ledger (GET_REVOC_REG) at the timestamp stated in the identifier.
+/// Objects missing on the ledger don't fail the call, they are reported with "missing_ledger_data" status.
+///
+/// EXPERIMENTAL
+///
+/// #Params
+/// command_handle: command handle to map callback to user context.
+/// pool_handle: pool handle (created by open_pool_ledger).
+/// wallet_handle: wallet handle (created by open_wallet) used for the ledger objects cache.
+/// submitter_did: DID of the submitter stored in secured Wallet.
+/// proof_request_json: proof request json (see `indy_verifier_verify_proof`)
+/// proof_json: created for request proof json (see `indy_verifier_verify_proof`)
+/// options_json: cache options used for schemas, credential definitions and revocation registry definitions:
+///  {
+///    noCache: (bool, optional, false by default) Skip usage of cache,
+///    noUpdate: (bool, optional, false by default) Use only cached data, do not try to update.
+///    noStore: (bool, optional, false by default) Skip storing fresh data if updated,
+///    ttl: (int, optional) Time to live of stored data in seconds. Expired data is not used and can be removed by indy_purge_expired_records.
+///    minFresh: (int, optional, -1 by default) Return cached data if not older than this many seconds. -1 means do not check age.
+///  }
+/// cb: Callback that takes command result as parameter.
+///
+/// #Returns
+/// report_json: proof verification report (see `indy_verifier_verify_proof_with_report`)
+///
+/// #Errors
+/// Anoncreds*
+/// Common*
+/// Ledger*
+/// Wallet*
+#[no_mangle]
+pub extern fn indy_verifier_verify_proof_with_ledger(command_handle: CommandHandle,
+                                                     pool_handle: PoolHandle,
+                                                     wallet_handle: WalletHandle,
+                                                     submitter_did: *const c_char,
+                                                     proof_request_json: *const c_char,
+                                                     proof_json: *const c_char,
+                                                     options_json: *const c_char,
+                                                     cb: Option<extern fn(command_handle_: CommandHandle, err: ErrorCode,
+                                                                          report_json: *const c_char)>) -> ErrorCode {
+    trace!("indy_verifier_verify_proof_with_ledger: >>> pool_handle: {:?}, wallet_handle: {:?}, submitter_did: {:?}, proof_request_json: {:?}, proof_json: {:?}, \
+    options_json: {:?}", pool_handle, wallet_handle, submitter_did, proof_request_json, proof_json, options_json);
+
+    check_useful_validatable_string!(submitter_did, ErrorCode::CommonInvalidParam4, DidValue);
+    check_useful_validatable_json!(proof_request_json, ErrorCode::CommonInvalidParam5, ProofRequest);
+    check_useful_validatable_json!(proof_json, ErrorCode::CommonInvalidParam6, Proof);
+    check_useful_json!(options_json, ErrorCode::CommonInvalidParam7, GetCacheOptions);
+    check_useful_c_callback!(cb, ErrorCode::CommonInvalidParam8);
+
+    trace!("indy_verifier_verify_proof_with_ledger: entities >>> pool_handle: {:?}, wallet_handle: {:?}, submitter_did: {:?}, proof_request_json: {:?}, proof_json: {:?}, \
+    options_json: {:?}", pool_handle, wallet_handle, submitter_did, proof_request_json, proof_json, options_json);
+
+    let result = CommandExecutor::instance()
+        .send(Command::Anoncreds(AnoncredsCommand::Verifier(VerifierCommand::VerifyProofWithLedger(
+            pool_handle,
+            wallet_handle,
+            submitter_did,
+            proof_request_json,
+            proof_json,
+            options_json,
+            boxed_callback_string!("indy_verifier_verify_proof_with_ledger", cb, command_handle)
+        ))));
+
+    let res = prepare_result!(result);
+
+    trace!("indy_verifier_verify_proof_with_ledger: <<< res: {:?}", res);
+
+    res
+}
+
 /// Create revocation state for a credential that corresponds to a particular time.
 ///
 /// Note that revocation delta must cover the whole registry existence time.
@@ -2486,3 +3200,181 @@ pub  extern fn indy_to_unqualified(command_handle: CommandHandle,
     res
 }
 
+
+/// Encode raw credential attribute values with the canonical encoding.
+///
+/// The canonical encoding keeps 32-bit integers as is and encodes any other value
+/// as SHA-256 hash of its UTF-8 representation converted to a big integer.
+/// The result can be passed as `cred_values_json` to `indy_issuer_create_credential`.
+///
+/// #Params
+/// command_handle: Command handle to map callback to caller context.
+/// raw_values_json: raw values of credential attributes
+///     {
+///         "attr1": "value1",
+///         "attr2": "value2"
+///     }
+/// cb: Callback that takes command result as parameter.
+///
+/// #Returns
+/// cred_values_json: credential values with both raw and encoded forms
+///     {
+///         "attr1": {"raw": "value1", "encoded": "value1_as_int" },
+///         "attr2": {"raw": "value2", "encoded": "value2_as_int" }
+///     }
+///
+/// #Errors
+/// Common*
+#[no_mangle]
+pub extern fn indy_encode_credential_values(command_handle: CommandHandle,
+                                            raw_values_json: *const c_char,
+                                            cb: Option<extern fn(command_handle_: CommandHandle,
+                                                                 err: ErrorCode,
+                                                                 cred_values_json: *const c_char)>) -> ErrorCode {
+    trace!("indy_encode_credential_values: >>> raw_values_json: {:?}", raw_values_json);
+
+    check_useful_json!(raw_values_json, ErrorCode::CommonInvalidParam2, ShortCredentialValues);
+    check_useful_c_callback!(cb, ErrorCode::CommonInvalidParam3);
+
+    trace!("indy_encode_credential_values: entities >>> raw_values_json: {:?}", secret!(&raw_values_json));
+
+    let result = CommandExecutor::instance()
+        .send(Command::Anoncreds(AnoncredsCommand::EncodeCredentialValues(
+            raw_values_json,
+            Box::new(move |result| {
+                let (err, cred_values_json) = prepare_result_1!(result, String::new());
+                trace!("indy_encode_credential_values: cred_values_json: {:?}", secret!(&cred_values_json));
+                let cred_values_json = ctypes::string_to_cstring(cred_values_json);
+                cb(command_handle, err, cred_values_json.as_ptr())
+            }),
+        )));
+
+    let res = prepare_result!(result);
+
+    trace!("indy_encode_credential_values: <<< res: {:?}", res);
+
+    res
+}
+
+/// Validate anoncreds object received from a remote party before passing it to other anoncreds functions.
+///
+/// Besides parsing, the object is checked for:
+///  - identifiers format and consistency of fully qualified and unqualified identifiers
+///  - attribute names (empty, duplicated after case and whitespace normalization, reserved `master_secret`)
+///  - consistency of identifiers with other object fields (schema name and version, tags, revocation data)
+///  - non-revocation intervals of proof requests
+///  - references of proof referents to proof identifiers
+///
+/// If trusted schema and/or credential definition are passed, the object is also checked against them:
+///  - identifiers of the object must match schema and credential definition ids
+///  - credential and credential definition attributes must be the same as schema attributes:
+///    both missing and extra attributes are reported
+///  - attributes requested by proof request and revealed in proof sub proofs of this schema must be present in schema
+/// If only credential definition is passed, its attributes are used instead of schema attributes.
+/// Note that proof request can combine attributes of several schemas, so it should be checked with context
+/// only if all its attributes are expected to come from one schema.
+///
+/// #Params
+/// command_handle: Command handle to map callback to caller context.
+/// object_type: type of the object to validate, one of:
+///     "schema", "cred_def", "rev_reg_def", "cred_offer", "cred_req", "cred", "proof_req", "proof"
+/// object_json: object json received from a remote party
+/// schema_json: (optional) trusted schema json the object is related to (for example, received from the ledger)
+/// cred_def_json: (optional) trusted credential definition json the object is related to
+/// cb: Callback that takes command result as parameter.
+///
+/// #Returns
+/// problems_json: list of human-readable problems found in the object, empty list if the object is valid
+///     [<str>, ...]
+///
+/// #Errors
+/// Common*
+#[no_mangle]
+pub extern fn indy_anoncreds_validate(command_handle: CommandHandle,
+                                      object_type: *const c_char,
+                                      object_json: *const c_char,
+                                      schema_json: *const c_char,
+                                      cred_def_json: *const c_char,
+                                      cb: Option<extern fn(command_handle_: CommandHandle,
+                                                           err: ErrorCode,
+                                                           problems_json: *const c_char)>) -> ErrorCode {
+    trace!("indy_anoncreds_validate: >>> object_type: {:?}, object_json: {:?}, schema_json: {:?}, cred_def_json: {:?}",
+           object_type, object_json, schema_json, cred_def_json);
+
+    check_useful_c_str!(object_type, ErrorCode::CommonInvalidParam2);
+    check_useful_c_str!(object_json, ErrorCode::CommonInvalidParam3);
+    check_useful_opt_validatable_json!(schema_json, ErrorCode::CommonInvalidParam4, Schema);
+    check_useful_opt_validatable_json!(cred_def_json, ErrorCode::CommonInvalidParam5, CredentialDefinition);
+    check_useful_c_callback!(cb, ErrorCode::CommonInvalidParam6);
+
+    trace!("indy_anoncreds_validate: entities >>> object_type: {:?}, object_json: {:?}, schema_json: {:?}, cred_def_json: {:?}",
+           object_type, secret!(&object_json), schema_json, cred_def_json);
+
+    let result = CommandExecutor::instance()
+        .send(Command::Anoncreds(AnoncredsCommand::Validate(
+            object_type,
+            object_json,
+            schema_json,
+            cred_def_json,
+            Box::new(move |result| {
+                let (err, problems_json) = prepare_result_1!(result, String::new());
+                trace!("indy_anoncreds_validate: problems_json: {:?}", problems_json);
+                let problems_json = ctypes::string_to_cstring(problems_json);
+                cb(command_handle, err, problems_json.as_ptr())
+            }),
+        )));
+
+    let res = prepare_result!(result);
+
+    trace!("indy_anoncreds_validate: <<< res: {:?}", res);
+
+    res
+}
+
+/// Encode a calendar date with the standard date encoding for predicates.
+///
+/// `YYYY-MM-DD` date is encoded as `YYYYMMDD` integer, so encoded dates keep their order
+/// and can be compared with predicates (for example "between" predicate for date of birth).
+/// The result should be used as raw value of credential attribute holding the date
+/// and as `p_value` / `p_value_to` of predicates over this attribute.
+///
+/// #Params
+/// command_handle: Command handle to map callback to caller context.
+/// date: calendar date in `YYYY-MM-DD` format
+/// cb: Callback that takes command result as parameter.
+///
+/// #Returns
+/// encoded_date: date encoded as decimal integer string, for example "20000115" for "2000-01-15"
+///
+/// #Errors
+/// Common*
+#[no_mangle]
+pub extern fn indy_encode_date(command_handle: CommandHandle,
+                               date: *const c_char,
+                               cb: Option<extern fn(command_handle_: CommandHandle,
+                                                    err: ErrorCode,
+                                                    encoded_date: *const c_char)>) -> ErrorCode {
+    trace!("indy_encode_date: >>> date: {:?}", date);
+
+    check_useful_c_str!(date, ErrorCode::CommonInvalidParam2);
+    check_useful_c_callback!(cb, ErrorCode::CommonInvalidParam3);
+
+    trace!("indy_encode_date: entities >>> date: {:?}", secret!(&date));
+
+    let result = CommandExecutor::instance()
+        .send(Command::Anoncreds(AnoncredsCommand::EncodeDate(
+            date,
+            Box::new(move |result| {
+                let (err, encoded_date) = prepare_result_1!(result, String::new());
+                trace!("indy_encode_date: encoded_date: {:?}", secret!(&encoded_date));
+                let encoded_date = ctypes::string_to_cstring(encoded_date);
+                cb(command_handle, err, encoded_date.as_ptr())
+            }),
+        )));
+
+    let res = prepare_result!(result);
+
+    trace!("indy_encode_date: <<< res: {:?}", res);
+
+    res
+}
@@ -2,10 +2,21 @@
 use indy_api_types::{ErrorCode, IndyHandle, CommandHandle};
 use crate::commands::{Command, CommandExecutor};
 use crate::commands::blob_storage::BlobStorageCommand;
+use crate::domain::anoncreds::revocation_registry_definition::RevocationRegistryDefinition;
+use crate::domain::anoncreds::tails::TailsReaderConfig;
+use crate::services::blob_storage::{PluggedReaderType, PluggedWriterType};
+use indy_api_types::blob_storage::*;
 use indy_api_types::errors::prelude::*;
 use indy_utils::ctypes;
+use indy_api_types::validation::Validatable;
 
 use libc::c_char;
+use std::sync::mpsc::channel;
+
+// Rust-native blob storage plugins implement these traits and register them
+// with register_blob_storage_reader_type and register_blob_storage_writer_type calls without any C handlers.
+pub use crate::services::blob_storage::{ReaderType as BlobStorageReaderType, Reader as BlobStorageReader, ReadableBlob as BlobStorageReadableBlob};
+pub use crate::services::blob_storage::{WriterType as BlobStorageWriterType, Writer as BlobStorageWriter, WritableBlob as BlobStorageWritableBlob};
 
 #[no_mangle]
 pub extern fn indy_open_blob_storage_reader(command_handle: CommandHandle,
@@ -72,3 +83,259 @@ pub extern fn indy_open_blob_storage_writer(command_handle: CommandHandle,
 
     res
 }
+
+/// Open blob storage reader for the tails file of the revocation registry.
+///
+/// Tails file is looked up in the local content-addressed tails directory first.
+/// If it is missing it's fetched from `tailsLocation` of the revocation registry definition,
+/// checked against `tailsHash` and stored in the tails directory, so it's downloaded only once.
+/// Files are stored only after the check, so already stored files are trusted and not hashed again.
+///
+/// #Params
+/// command_handle: Command handle to map callback to caller context.
+/// rev_reg_def_json: revocation registry definition json
+/// config_json: tails reader config json
+///     {
+///         "tails_dir": Optional<string> - directory to store tails files in (<indy home>/tails by default),
+///         "fetcher_type": Optional<string> - blob storage reader type used to fetch missing tails file.
+///                         By default "http" type is used for http:// locations and local file is read otherwise.
+///                         Built-in "http" type supports plain http:// only: no HTTPS and redirects aren't followed.
+///         "fetcher_config": Optional<object> - config of the fetcher reader type (empty object by default)
+///     }
+/// cb: Callback that takes command result as parameter.
+///
+/// #Returns
+/// handle: blob storage reader handle to use in revocation related anoncreds calls
+///
+/// #Errors
+/// Common*
+#[no_mangle]
+pub extern fn indy_open_tails_reader(command_handle: CommandHandle,
+                                     rev_reg_def_json: *const c_char,
+                                     config_json: *const c_char,
+                                     cb: Option<extern fn(command_handle_: CommandHandle,
+                                                          err: ErrorCode,
+                                                          handle: IndyHandle)>) -> ErrorCode {
+    trace!("indy_open_tails_reader: >>> rev_reg_def_json: {:?}, config_json: {:?}", rev_reg_def_json, config_json);
+
+    check_useful_validatable_json!(rev_reg_def_json, ErrorCode::CommonInvalidParam2, RevocationRegistryDefinition);
+    check_useful_validatable_json!(config_json, ErrorCode::CommonInvalidParam3, TailsReaderConfig);
+    check_useful_c_callback!(cb, ErrorCode::CommonInvalidParam4);
+
+    trace!("indy_open_tails_reader: entities >>> rev_reg_def_json: {:?}, config_json: {:?}", rev_reg_def_json, config_json);
+
+    let result = CommandExecutor::instance()
+        .send(Command::BlobStorage(BlobStorageCommand::OpenTailsReader(
+            rev_reg_def_json,
+            config_json,
+            Box::new(move |result| {
+                let (err, handle) = prepare_result_1!(result, 0);
+                trace!("indy_open_tails_reader: handle: {:?}", handle);
+                cb(command_handle, err, handle)
+            }),
+        )));
+
+    let res = prepare_result!(result);
+
+    trace!("indy_open_tails_reader: <<< res: {:?}", res);
+
+    res
+}
+
+/// Register custom blob storage reader implementation.
+///
+/// Registered reader type can be used in indy_open_blob_storage_reader call
+/// the same way as built-in "default" (local files) and "http" (read-through HTTP download with local cache) types.
+///
+/// #Params
+/// command_handle: Command handle to map callback to caller context.
+/// type_: Blob storage reader type name.
+/// open: Reader open operation handler
+/// open_blob: Reader open blob operation handler
+/// read: Reader read operation handler
+/// verify: Reader verify operation handler
+/// close_blob: Reader close blob operation handler
+///
+/// #Returns
+/// Error code
+///
+/// #Errors
+/// Common*
+#[no_mangle]
+pub extern fn indy_register_blob_storage_reader(command_handle: CommandHandle,
+                                                type_: *const c_char,
+                                                open: Option<BlobStorageReaderOpen>,
+                                                open_blob: Option<BlobStorageReaderOpenBlob>,
+                                                read: Option<BlobStorageReaderRead>,
+                                                verify: Option<BlobStorageReaderVerify>,
+                                                close_blob: Option<BlobStorageReaderCloseBlob>,
+                                                cb: Option<extern fn(command_handle_: CommandHandle,
+                                                                     err: ErrorCode)>) -> ErrorCode {
+    trace!("indy_register_blob_storage_reader: >>> command_handle: {:?}, type_: {:?}", command_handle, type_);
+
+    check_useful_c_str!(type_, ErrorCode::CommonInvalidParam2);
+    check_useful_c_callback!(open, ErrorCode::CommonInvalidParam3);
+    check_useful_c_callback!(open_blob, ErrorCode::CommonInvalidParam4);
+    check_useful_c_callback!(read, ErrorCode::CommonInvalidParam5);
+    check_useful_c_callback!(verify, ErrorCode::CommonInvalidParam6);
+    check_useful_c_callback!(close_blob, ErrorCode::CommonInvalidParam7);
+    check_useful_c_callback!(cb, ErrorCode::CommonInvalidParam8);
+
+    trace!("indy_register_blob_storage_reader: entities >>> type_: {:?}", type_);
+
+    let result = CommandExecutor::instance()
+        .send(Command::BlobStorage(BlobStorageCommand::RegisterReaderType(
+            type_,
+            Box::new(PluggedReaderType::new(open, open_blob, read, verify, close_blob)),
+            Box::new(move |result| {
+                let err = prepare_result!(result);
+                trace!("indy_register_blob_storage_reader: cb command_handle: {:?}, err: {:?}", command_handle, err);
+                cb(command_handle, err)
+            }),
+        )));
+
+    let res = prepare_result!(result);
+
+    trace!("indy_register_blob_storage_reader: <<< res: {:?}", res);
+
+    res
+}
+
+/// Register custom blob storage writer implementation.
+///
+/// Registered writer type can be used in indy_open_blob_storage_writer call
+/// the same way as built-in "default" (local files) type.
+///
+/// #Params
+/// command_handle: Command handle to map callback to caller context.
+/// type_: Blob storage writer type name.
+/// open: Writer open operation handler
+/// create_blob: Writer create blob operation handler
+/// append: Writer append operation handler
+/// finalize: Writer finalize operation handler
+/// free_blob: Writer free blob operation handler
+///
+/// #Returns
+/// Error code
+///
+/// #Errors
+/// Common*
+#[no_mangle]
+pub extern fn indy_register_blob_storage_writer(command_handle: CommandHandle,
+                                                type_: *const c_char,
+                                                open: Option<BlobStorageWriterOpen>,
+                                                create_blob: Option<BlobStorageWriterCreateBlob>,
+                                                append: Option<BlobStorageWriterAppend>,
+                                                finalize: Option<BlobStorageWriterFinalize>,
+                                                free_blob: Option<BlobStorageWriterFreeBlob>,
+                                                cb: Option<extern fn(command_handle_: CommandHandle,
+                                                                     err: ErrorCode)>) -> ErrorCode {
+    trace!("indy_register_blob_storage_writer: >>> command_handle: {:?}, type_: {:?}", command_handle, type_);
+
+    check_useful_c_str!(type_, ErrorCode::CommonInvalidParam2);
+    check_useful_c_callback!(open, ErrorCode::CommonInvalidParam3);
+    check_useful_c_callback!(create_blob, ErrorCode::CommonInvalidParam4);
+    check_useful_c_callback!(append, ErrorCode::CommonInvalidParam5);
+    check_useful_c_callback!(finalize, ErrorCode::CommonInvalidParam6);
+    check_useful_c_callback!(free_blob, ErrorCode::CommonInvalidParam7);
+    check_useful_c_callback!(cb, ErrorCode::CommonInvalidParam8);
+
+    trace!("indy_register_blob_storage_writer: entities >>> type_: {:?}", type_);
+
+    let result = CommandExecutor::instance()
+        .send(Command::BlobStorage(BlobStorageCommand::RegisterWriterType(
+            type_,
+            Box::new(PluggedWriterType::new(open, create_blob, append, finalize, free_blob)),
+            Box::new(move |result| {
+                let err = prepare_result!(result);
+                trace!("indy_register_blob_storage_writer: cb command_handle: {:?}, err: {:?}", command_handle, err);
+                cb(command_handle, err)
+            }),
+        )));
+
+    let res = prepare_result!(result);
+
+    trace!("indy_register_blob_storage_writer: <<< res: {:?}", res);
+
+    res
+}
+
+/// Register custom blob storage reader implemented in Rust.
+///
+/// Unlike indy_register_blob_storage_reader it doesn't require C handlers, so Rust applications
+/// that link libindy as a crate can plug storage directly. Both calls share reader type names.
+/// The call blocks until the reader type is registered.
+///
+/// #Params
+/// type_: Blob storage reader type name.
+/// reader_type: Blob storage reader type implementation.
+///
+/// #Returns
+/// Error code
+///
+/// #Errors
+/// Common*
+pub fn register_blob_storage_reader_type(type_: &str, reader_type: Box<dyn BlobStorageReaderType>) -> ErrorCode {
+    trace!("register_blob_storage_reader_type: >>> type_: {:?}", type_);
+
+    let (sender, receiver) = channel();
+
+    let result = CommandExecutor::instance()
+        .send(Command::BlobStorage(BlobStorageCommand::RegisterReaderType(
+            type_.to_string(),
+            reader_type,
+            Box::new(move |result| {
+                let err = prepare_result!(result);
+                trace!("register_blob_storage_reader_type: cb err: {:?}", err);
+                sender.send(err).unwrap_or(())
+            }),
+        )));
+
+    let res = match prepare_result!(result) {
+        ErrorCode::Success => receiver.recv().unwrap_or(ErrorCode::CommonInvalidState),
+        err => err
+    };
+
+    trace!("register_blob_storage_reader_type: <<< res: {:?}", res);
+    res
+}
+
+/// Register custom blob storage writer implemented in Rust.
+///
+/// Unlike indy_register_blob_storage_writer it doesn't require C handlers, so Rust applications
+/// that link libindy as a crate can plug storage directly. Both calls share writer type names.
+/// The call blocks until the writer type is registered.
+///
+/// #Params
+/// type_: Blob storage writer type name.
+/// writer_type: Blob storage writer type implementation.
+///
+/// #Returns
+/// Error code
+///
+/// #Errors
+/// Common*
+pub fn register_blob_storage_writer_type(type_: &str, writer_type: Box<dyn BlobStorageWriterType>) -> ErrorCode {
+    trace!("register_blob_storage_writer_type: >>> type_: {:?}", type_);
+
+    let (sender, receiver) = channel();
+
+    let result = CommandExecutor::instance()
+        .send(Command::BlobStorage(BlobStorageCommand::RegisterWriterType(
+            type_.to_string(),
+            writer_type,
+            Box::new(move |result| {
+                let err = prepare_result!(result);
+                trace!("register_blob_storage_writer_type: cb err: {:?}", err);
+                sender.send(err).unwrap_or(())
+            }),
+        )));
+
+    let res = match prepare_result!(result) {
+        ErrorCode::Success => receiver.recv().unwrap_or(ErrorCode::CommonInvalidState),
+        err => err
+    };
+
+    trace!("register_blob_storage_writer_type: <<< res: {:?}", res);
+    res
+}
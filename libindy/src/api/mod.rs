@@ -30,6 +30,9 @@ use indy_utils::ctypes;
 ///     "collect_backtrace": Optional<bool> - whether errors backtrace should be collected.
 ///         Capturing of backtrace can affect library performance.
 ///         NOTE: must be set before invocation of any other API functions.
+///     "check_canonical_encoding": Optional<bool> - whether proof verification should reject revealed attributes
+///         whose encoded values don't match the canonical encoding of raw values (see `indy_encode_credential_values`).
+///         Disabled by default as credentials could be issued with a custom encoding.
 /// }
 ///
 /// #Errors
@@ -28,6 +28,10 @@ use libc::c_char;
 ///   If tag name starts with "~" the tag will be stored un-encrypted that will allow
 ///   usage of this tag in complex search queries (comparison, predicates)
 ///   Encrypted tags can be searched only for exact matching
+///  Reserved "~ttl": <str> tag sets time to live of the record in seconds. It is stored as
+///  "~Indy::ExpiresAt" tag with expiration time (seconds since epoch, zero padded to 20 digits).
+///  "~Indy::ExpiresAt" tag itself can't be set or removed directly.
+///  Expired records are not returned by get and search and can be removed by indy_purge_expired_records
 #[no_mangle]
 pub extern fn indy_add_wallet_record(command_handle: CommandHandle,
                                      wallet_handle: WalletHandle,
@@ -310,6 +314,49 @@ pub extern fn indy_delete_wallet_record(command_handle: CommandHandle,
     res
 }
 
+/// Delete expired wallet records of the type
+///
+/// Records expire when time to live set by reserved "~ttl" tag passes (see indy_add_wallet_record).
+///
+/// #Params
+/// command_handle: command handle to map callback to caller context
+/// wallet_handle: wallet handle (created by open_wallet)
+/// type_: record type
+/// cb: Callback that takes command result as parameter.
+///
+/// #Returns
+/// purged_count: number of deleted records
+#[no_mangle]
+pub extern fn indy_purge_expired_records(command_handle: CommandHandle,
+                                         wallet_handle: WalletHandle,
+                                         type_: *const c_char,
+                                         cb: Option<extern fn(command_handle_: CommandHandle, err: ErrorCode, purged_count: usize)>) -> ErrorCode {
+    trace!("indy_purge_expired_records: >>> wallet_handle: {:?}, type_: {:?}", wallet_handle, type_);
+
+    check_useful_c_str!(type_, ErrorCode::CommonInvalidParam3);
+    check_useful_c_callback!(cb, ErrorCode::CommonInvalidParam4);
+
+    trace!("indy_purge_expired_records: entities >>> wallet_handle: {:?}, type_: {:?}", wallet_handle, type_);
+
+    let result = CommandExecutor::instance()
+        .send(Command::NonSecrets(
+            NonSecretsCommand::PurgeExpiredRecords(
+                wallet_handle,
+                type_,
+                Box::new(move |result| {
+                    let (err, purged_count) = prepare_result_1!(result, 0);
+                    trace!("indy_purge_expired_records: purged_count: {:?}", purged_count);
+                    cb(command_handle, err, purged_count)
+                })
+            )));
+
+    let res = prepare_result!(result);
+
+    trace!("indy_purge_expired_records: <<< res: {:?}", res);
+
+    res
+}
+
 /// Get an wallet record by id
 ///
 /// #Params
@@ -1,8 +1,8 @@
 
-use indy_api_types::{ErrorCode, CommandHandle, WalletHandle, INVALID_WALLET_HANDLE};
+use indy_api_types::{ErrorCode, CommandHandle, IndyHandle, WalletHandle, INVALID_WALLET_HANDLE};
 use crate::commands::{Command, CommandExecutor};
-use crate::commands::wallet::WalletCommand;
-use indy_api_types::domain::wallet::{Config, Credentials, ExportConfig, KeyConfig};
+use crate::commands::wallet::{ExportTarget, ImportSource, WalletCommand};
+use indy_api_types::domain::wallet::{AuditLogQuery, CheckWalletOptions, Config, Credentials, ExportConfig, KeyConfig, StreamExportConfig};
 use indy_api_types::wallet::*;
 use indy_api_types::errors::prelude::*;
 use indy_utils::ctypes;
@@ -10,6 +10,13 @@ use indy_api_types::validation::Validatable;
 
 use serde_json;
 use libc::c_char;
+use std::sync::mpsc::channel;
+
+// Rust-native storage plugins implement these traits and register them
+// with register_wallet_storage_type call without any C handlers.
+pub use indy_api_types::errors::{err_msg, IndyError, IndyErrorKind, IndyResult};
+pub use indy_wallet::{EncryptedValue, StorageCheckReport, StorageIterator, StorageRecord, StorageStats, StorageTypeStats, Tag, TagName, WalletStorage, WalletStorageType};
+pub use indy_wallet::language;
 
 
 /// Register custom wallet storage implementation.
@@ -143,6 +150,170 @@ pub extern fn indy_register_wallet_storage(command_handle: CommandHandle,
     res
 }
 
+/// Register custom wallet storage implemented in Rust.
+///
+/// Unlike indy_register_wallet_storage it doesn't require C handlers, so Rust applications
+/// that link libindy as a crate can plug storage directly. indy_register_wallet_storage
+/// is an adapter over the same mechanism, so both calls share storage type names.
+/// The call waits until the storage type is registered, so it can't be called from libindy callbacks.
+///
+/// #Params
+/// type_: Storage type name.
+/// storage_type: Storage type implementation.
+///
+/// #Returns
+/// Ok if storage type is registered
+///
+/// #Errors
+/// InvalidState if called from libindy callback
+/// WalletStorageTypeAlreadyRegistered
+pub fn register_wallet_storage_type(type_: &str, storage_type: Box<dyn WalletStorageType>) -> IndyResult<()> {
+    trace!("register_wallet_storage_type: >>> type_: {:?}", type_);
+
+    let (sender, receiver) = channel();
+
+    {
+        let command_executor = CommandExecutor::instance();
+
+        if command_executor.is_worker_thread() {
+            return Err(err_msg(IndyErrorKind::InvalidState, "Wallet storage type can't be registered from libindy callback"));
+        }
+
+        command_executor
+            .send(Command::Wallet(
+                WalletCommand::RegisterWalletStorageType(
+                    type_.to_string(),
+                    storage_type,
+                    Box::new(move |result| {
+                        trace!("register_wallet_storage_type: cb result: {:?}", result);
+                        sender.send(result).unwrap_or(())
+                    })
+                )))?;
+    }
+
+    let res = receiver.recv()
+        .to_indy(IndyErrorKind::InvalidState, "Wallet storage type registration result wasn't received")?;
+
+    trace!("register_wallet_storage_type: <<< res: {:?}", res);
+    res
+}
+
+/// Register statistics handlers for custom wallet storage implementation.
+///
+/// Handlers are optional. Without them indy_get_wallet_stats counts records and tags
+/// by iterating over all records of the storage, so the call cost grows with wallet size
+/// and storage size is unknown. Handlers are used by wallets opened after this call.
+///
+/// #Params
+/// command_handle: Command handle to map callback to caller context.
+/// type_: Storage type name registered with indy_register_wallet_storage call.
+/// get_storage_stats: WalletType get storage statistics operation handler
+/// free_storage_stats: WalletType free storage statistics operation handler
+///
+/// #Returns
+/// Error code
+///
+/// #Errors
+/// Common*
+/// WalletUnknownTypeError
+#[no_mangle]
+pub extern fn indy_register_wallet_storage_stats(command_handle: CommandHandle,
+                                                 type_: *const c_char,
+                                                 get_storage_stats: Option<WalletGetStorageStats>,
+                                                 free_storage_stats: Option<WalletFreeStorageStats>,
+                                                 cb: Option<extern fn(command_handle_: CommandHandle,
+                                                                      err: ErrorCode)>) -> ErrorCode {
+    trace!("indy_register_wallet_storage_stats: >>> command_handle: {:?}, type_: {:?}, cb: {:?}",
+           command_handle, type_, cb);
+
+    check_useful_c_str!(type_, ErrorCode::CommonInvalidParam2);
+    check_useful_c_callback!(get_storage_stats, ErrorCode::CommonInvalidParam3);
+    check_useful_c_callback!(free_storage_stats, ErrorCode::CommonInvalidParam4);
+    check_useful_c_callback!(cb, ErrorCode::CommonInvalidParam5);
+
+    trace!("indy_register_wallet_storage_stats: params type_: {:?}", type_);
+
+    let result = CommandExecutor::instance()
+        .send(Command::Wallet(
+            WalletCommand::RegisterStorageStats(
+                type_,
+                get_storage_stats,
+                free_storage_stats,
+                Box::new(move |result| {
+                    let err = prepare_result!(result);
+                    trace!("indy_register_wallet_storage_stats: cb command_handle: {:?}, err: {:?}", command_handle, err);
+                    cb(command_handle, err)
+                })
+            )));
+
+    let res = prepare_result!(result);
+    trace!("indy_register_wallet_storage_stats: <<< res: {:?}", res);
+    res
+}
+
+/// Register custom key-encryption key (KEK) provider implementation.
+///
+/// KEK provider wraps random wallet master key on wallet creation (and rekey)
+/// and unwraps it on wallet opening. It allows to keep the secret that protects wallet
+/// in external key store (HSM, OS keystore and etc.) instead of application config.
+/// Wallets use registered provider if "key_derivation_method" of wallet credentials
+/// is set to {"KEK": "<provider type>"}. In this case "key" field contains the identifier
+/// of the key-encryption key.
+///
+/// Note "file" provider type is built-in. It reads base58 encoded key-encryption key
+/// (for example generated with indy_generate_wallet_key call) from the file with "key" path.
+///
+/// #Params
+/// command_handle: Command handle to map callback to caller context.
+/// type_: KEK provider type name.
+/// wrap_key: KEK provider wrap key operation handler
+/// unwrap_key: KEK provider unwrap key operation handler
+/// free_key: KEK provider free key buffer operation handler
+///
+/// #Returns
+/// Error code
+///
+/// #Errors
+/// Common*
+/// WalletTypeAlreadyRegisteredError
+#[no_mangle]
+pub extern fn indy_register_wallet_kek_provider(command_handle: CommandHandle,
+                                                type_: *const c_char,
+                                                wrap_key: Option<WalletKekWrapKey>,
+                                                unwrap_key: Option<WalletKekUnwrapKey>,
+                                                free_key: Option<WalletKekFreeKey>,
+                                                cb: Option<extern fn(command_handle_: CommandHandle,
+                                                                     err: ErrorCode)>) -> ErrorCode {
+    trace!("indy_register_wallet_kek_provider: >>> command_handle: {:?}, type_: {:?}, cb: {:?}",
+           command_handle, type_, cb);
+
+    check_useful_c_str!(type_, ErrorCode::CommonInvalidParam2);
+    check_useful_c_callback!(wrap_key, ErrorCode::CommonInvalidParam3);
+    check_useful_c_callback!(unwrap_key, ErrorCode::CommonInvalidParam4);
+    check_useful_c_callback!(free_key, ErrorCode::CommonInvalidParam5);
+    check_useful_c_callback!(cb, ErrorCode::CommonInvalidParam6);
+
+    trace!("indy_register_wallet_kek_provider: params type_: {:?}", type_);
+
+    let result = CommandExecutor::instance()
+        .send(Command::Wallet(
+            WalletCommand::RegisterKekProvider(
+                type_,
+                wrap_key,
+                unwrap_key,
+                free_key,
+                Box::new(move |result| {
+                    let err = prepare_result!(result);
+                    trace!("indy_register_wallet_kek_provider: cb command_handle: {:?}, err: {:?}", command_handle, err);
+                    cb(command_handle, err)
+                })
+            )));
+
+    let res = prepare_result!(result);
+    trace!("indy_register_wallet_kek_provider: <<< res: {:?}", res);
+    res
+}
+
 /// Create a new secure wallet.
 ///
 /// #Params
@@ -152,6 +323,7 @@ pub extern fn indy_register_wallet_storage(command_handle: CommandHandle,
 ///         Configured storage uses this identifier to lookup exact wallet data placement.
 ///   "storage_type": optional<string>, Type of the wallet storage. Defaults to 'default'.
 ///                  'Default' storage type allows to store wallet data in the local file.
+///                  'inmem' storage type keeps wallet data in the process memory only, it is lost when the process exits.
 ///                  Custom storage types can be registered with indy_register_wallet_storage call.
 ///   "storage_config": optional<object>, Storage configuration json. Storage type defines set of supported keys.
 ///                     Can be optional if storage supports default configuration.
@@ -174,6 +346,10 @@ pub extern fn indy_register_wallet_storage(command_handle: CommandHandle,
 ///                          ARGON2I_INT - derive secured wallet master key (less secured but faster)
 ///                          RAW - raw wallet key master provided (skip derivation).
 ///                                RAW keys can be generated with indy_generate_wallet_key call
+///                          {"KEK": "<provider type>"} - random wallet master key wrapped by key-encryption key provider.
+///                                "key" is the identifier of the key-encryption key for the provider.
+///                                Built-in "file" provider reads base58 encoded key from the file with "key" path.
+///                                Custom providers can be registered with indy_register_wallet_kek_provider call
 /// }
 ///
 /// #Returns
@@ -225,6 +401,7 @@ pub extern fn indy_create_wallet(command_handle: CommandHandle,
 ///             Configured storage uses this identifier to lookup exact wallet data placement.
 ///       "storage_type": optional<string>, Type of the wallet storage. Defaults to 'default'.
 ///                       'Default' storage type allows to store wallet data in the local file.
+///                       'inmem' storage type is described in indy_create_wallet.
 ///                       Custom storage types can be registered with indy_register_wallet_storage call.
 ///       "storage_config": optional<object>, Storage configuration json. Storage type defines set of supported keys.
 ///                         Can be optional if storage supports default configuration.
@@ -233,7 +410,17 @@ pub extern fn indy_create_wallet(command_handle: CommandHandle,
 ///              "path": optional<string>, Path to the directory with wallet files.
 ///                      Defaults to $HOME/.indy_client/wallet.
 ///                      Wallet will be stored in the file {path}/{id}/sqlite.db
+///              "shared": optional<bool>, If true the wallet can be opened by several processes at the same time.
+///                        Writes are serialized by SQLite locks, every operation is atomic
+///                        and sees all changes committed by other processes before it started.
+///                        Note that libindy still allows to open the wallet only once within a process.
+///                        Defaults to false (the wallet is expected to be used by the single process).
+///              "busy_timeout": optional<number>, Time in milliseconds to wait for a lock held by
+///                              other process in shared mode. Defaults to 5000.
 ///           }
+///       "audit_log": optional<bool>, If true changes of wallet records will be stored in encrypted
+///                    append-only audit log (See indy_get_wallet_audit_log). Defaults to false.
+///                    Once enabled it can't be disabled for the wallet.
 ///
 ///   }
 /// credentials: Wallet credentials json
@@ -249,11 +436,13 @@ pub extern fn indy_create_wallet(command_handle: CommandHandle,
 ///                          ARGON2I_INT - derive secured wallet master key (less secured but faster)
 ///                          RAW - raw wallet key master provided (skip derivation).
 ///                                RAW keys can be generated with indy_generate_wallet_key call
+///                          {"KEK": "<provider type>"} - random wallet master key wrapped by key-encryption key provider.
 ///       "rekey_derivation_method": optional<string> Algorithm to use for wallet rekey derivation:
 ///                          ARGON2I_MOD - derive secured wallet master rekey (used by default)
 ///                          ARGON2I_INT - derive secured wallet master rekey (less secured but faster)
 ///                          RAW - raw wallet rekey master provided (skip derivation).
 ///                                RAW keys can be generated with indy_generate_wallet_key call
+///                          {"KEK": "<provider type>"} - new random wallet master key wrapped by key-encryption key provider.
 ///   }
 ///
 /// #Returns
@@ -311,6 +500,16 @@ pub extern fn indy_open_wallet(command_handle: CommandHandle,
 ///                              ARGON2I_INT - derive secured export key (less secured but faster)
 ///                              RAW - raw export key provided (skip derivation).
 ///                                RAW keys can be generated with indy_generate_wallet_key call
+///     "incremental": optional<bool> If true only changes since the previous incremental export are exported
+///                    (all records for the first one). Such exports have to be imported in the same order:
+///                    the first one with indy_import_wallet and the next ones with indy_import_wallet_increment.
+///                    Defaults to false.
+///     "filter": optional<array> Export only records matching any of the filters (all records by default).
+///               Can't be used with incremental export.
+///               [{
+///                   "type": string, Record type, e.g. "Indy::Credential", "Indy::MasterSecret" or type of non-secret records
+///                   "query": optional<object> WQL query for record tags (all records of the type by default)
+///               }]
 ///   }
 ///
 /// #Returns
@@ -360,6 +559,7 @@ pub extern fn indy_export_wallet(command_handle: CommandHandle,
 ///         Configured storage uses this identifier to lookup exact wallet data placement.
 ///   "storage_type": optional<string>, Type of the wallet storage. Defaults to 'default'.
 ///                  'Default' storage type allows to store wallet data in the local file.
+///                  'inmem' storage type is described in indy_create_wallet.
 ///                  Custom storage types can be registered with indy_register_wallet_storage call.
 ///   "storage_config": optional<object>, Storage configuration json. Storage type defines set of supported keys.
 ///                     Can be optional if storage supports default configuration.
@@ -430,6 +630,404 @@ pub extern fn indy_import_wallet(command_handle: CommandHandle,
     res
 }
 
+/// Imports content of the incremental export into opened wallet.
+///
+/// Increments have to be imported in the same order as they were exported.
+/// The first incremental export of the wallet contains all records and has to be imported with indy_import_wallet.
+/// Increments exported to a buffer or sink can be imported with indy_import_wallet_increment_from_buffer
+/// and indy_import_wallet_increment_from_source.
+///
+/// #Params
+/// wallet_handle: wallet handle returned by indy_open_wallet
+/// import_config: Import settings json.
+/// {
+///   "path": <string>, path of the file that contains exported wallet increment
+///   "key": <string>, key used for export of the wallet
+/// }
+///
+/// #Returns
+/// Error code
+///
+/// #Errors
+/// Common*
+/// Wallet*
+#[no_mangle]
+pub extern fn indy_import_wallet_increment(command_handle: CommandHandle,
+                                           wallet_handle: WalletHandle,
+                                           import_config: *const c_char,
+                                           cb: Option<extern fn(command_handle_: CommandHandle,
+                                                                err: ErrorCode)>) -> ErrorCode {
+    trace!("indy_import_wallet_increment: >>> command_handle: {:?}, wallet_handle: {:?}, import_config: {:?}, cb: {:?}",
+           command_handle, wallet_handle, import_config, cb);
+
+    check_useful_json!(import_config, ErrorCode::CommonInvalidParam3, ExportConfig);
+    check_useful_c_callback!(cb, ErrorCode::CommonInvalidParam4);
+
+    trace!("indy_import_wallet_increment: params wallet_handle: {:?}, import_config: {:?}", wallet_handle, secret!(&import_config));
+
+    let result = CommandExecutor::instance()
+        .send(Command::Wallet(WalletCommand::ImportIncrement(
+            wallet_handle,
+            import_config,
+            Box::new(move |result| {
+                let err = prepare_result!(result);
+                trace!("indy_import_wallet_increment: cb command_handle: {:?}, err: {:?}", command_handle, err);
+                cb(command_handle, err)
+            })
+        )));
+
+    let res = prepare_result!(result);
+    trace!("indy_import_wallet_increment: <<< res: {:?}", res);
+    res
+}
+
+/// Exports opened wallet into a memory buffer.
+///
+/// #Params:
+/// wallet_handle: wallet handle returned by indy_open_wallet
+/// export_config: JSON containing settings for input operation.
+///   {
+///     "key": <string>, Key or passphrase used for wallet export key derivation.
+///                     Look to key_derivation_method param for information about supported key derivation methods.
+///     "key_derivation_method": optional<string> Algorithm to use for wallet export key derivation:
+///                              ARGON2I_MOD - derive secured export key (used by default)
+///                              ARGON2I_INT - derive secured export key (less secured but faster)
+///                              RAW - raw export key provided (skip derivation).
+///                                RAW keys can be generated with indy_generate_wallet_key call
+///     "incremental": optional<bool> The same as for indy_export_wallet. Defaults to false.
+///     "filter": optional<array> The same as for indy_export_wallet.
+///   }
+/// cb: Callback that takes command result as parameter.
+///
+/// #Returns
+/// Exported wallet content (the same format as indy_export_wallet writes to the file)
+///
+/// #Errors
+/// Common*
+/// Wallet*
+#[no_mangle]
+pub extern fn indy_export_wallet_to_buffer(command_handle: CommandHandle,
+                                           wallet_handle: WalletHandle,
+                                           export_config: *const c_char,
+                                           cb: Option<extern fn(command_handle_: CommandHandle,
+                                                                err: ErrorCode,
+                                                                data_raw: *const u8,
+                                                                data_len: u32)>) -> ErrorCode {
+    trace!("indy_export_wallet_to_buffer: >>> wallet_handle: {:?}, export_config: {:?}", wallet_handle, export_config);
+
+    check_useful_json!(export_config, ErrorCode::CommonInvalidParam3, StreamExportConfig);
+    check_useful_c_callback!(cb, ErrorCode::CommonInvalidParam4);
+
+    trace!("indy_export_wallet_to_buffer: params wallet_handle: {:?}, export_config: {:?}", wallet_handle, secret!(&export_config));
+
+    let result = CommandExecutor::instance()
+        .send(Command::Wallet(WalletCommand::ExportToStream(
+            wallet_handle,
+            export_config,
+            ExportTarget::Buffer,
+            Box::new(move |result| {
+                let (err, data) = prepare_result_1!(result, Vec::new());
+                trace!("indy_export_wallet_to_buffer: cb command_handle: {:?} err: {:?}, data_len: {:?}", command_handle, err, data.len());
+                let (data_raw, data_len) = ctypes::vec_to_pointer(&data);
+                cb(command_handle, err, data_raw, data_len)
+            })
+        )));
+
+    let res = prepare_result!(result);
+    trace!("indy_export_wallet_to_buffer: <<< res: {:?}", res);
+    res
+}
+
+/// Exports opened wallet into an application provided sink.
+///
+/// Exported content is passed to write_cb chunk by chunk in the same format as indy_export_wallet writes to the file.
+/// Note that write_cb is called from libindy thread, so it must not block on other libindy calls.
+///
+/// #Params:
+/// wallet_handle: wallet handle returned by indy_open_wallet
+/// export_config: JSON containing settings for input operation (See indy_export_wallet_to_buffer).
+/// sink_handle: application defined handle passed to write_cb
+/// write_cb: callback that writes the next chunk of the exported wallet.
+///           Any error code except Success aborts export.
+/// cb: Callback that takes command result as parameter.
+///
+/// #Returns
+/// Error code
+///
+/// #Errors
+/// Common*
+/// Wallet*
+#[no_mangle]
+pub extern fn indy_export_wallet_to_sink(command_handle: CommandHandle,
+                                         wallet_handle: WalletHandle,
+                                         export_config: *const c_char,
+                                         sink_handle: IndyHandle,
+                                         write_cb: Option<WalletExportWrite>,
+                                         cb: Option<extern fn(command_handle_: CommandHandle,
+                                                              err: ErrorCode)>) -> ErrorCode {
+    trace!("indy_export_wallet_to_sink: >>> wallet_handle: {:?}, export_config: {:?}, sink_handle: {:?}",
+           wallet_handle, export_config, sink_handle);
+
+    check_useful_json!(export_config, ErrorCode::CommonInvalidParam3, StreamExportConfig);
+    check_useful_c_callback!(write_cb, ErrorCode::CommonInvalidParam5);
+    check_useful_c_callback!(cb, ErrorCode::CommonInvalidParam6);
+
+    trace!("indy_export_wallet_to_sink: params wallet_handle: {:?}, export_config: {:?}, sink_handle: {:?}",
+           wallet_handle, secret!(&export_config), sink_handle);
+
+    let result = CommandExecutor::instance()
+        .send(Command::Wallet(WalletCommand::ExportToStream(
+            wallet_handle,
+            export_config,
+            ExportTarget::Sink(sink_handle, write_cb),
+            Box::new(move |result| {
+                let err = prepare_result!(result);
+                trace!("indy_export_wallet_to_sink: cb command_handle: {:?} err: {:?}", command_handle, err);
+                cb(command_handle, err)
+            })
+        )));
+
+    let res = prepare_result!(result);
+    trace!("indy_export_wallet_to_sink: <<< res: {:?}", res);
+    res
+}
+
+/// Creates a new secure wallet and then imports its content from a memory buffer
+/// produced by indy_export_wallet_to_buffer (or read from the file written by indy_export_wallet).
+///
+/// #Params
+/// config: Wallet configuration json (See indy_import_wallet).
+/// credentials: Wallet credentials json (See indy_import_wallet).
+/// import_config: Import settings json.
+/// {
+///   "key": <string>, key used for export of the wallet
+/// }
+/// data_raw: a pointer to first byte of exported wallet content
+/// data_len: exported wallet content length
+/// cb: Callback that takes command result as parameter.
+///
+/// #Returns
+/// Error code
+///
+/// #Errors
+/// Common*
+/// Wallet*
+#[no_mangle]
+pub extern fn indy_import_wallet_from_buffer(command_handle: CommandHandle,
+                                             config: *const c_char,
+                                             credentials: *const c_char,
+                                             import_config: *const c_char,
+                                             data_raw: *const u8,
+                                             data_len: u32,
+                                             cb: Option<extern fn(command_handle_: CommandHandle,
+                                                                  err: ErrorCode)>) -> ErrorCode {
+    trace!("indy_import_wallet_from_buffer: >>> command_handle: {:?}, config: {:?}, credentials: {:?}, import_config: {:?}, data_len: {:?}",
+           command_handle, config, credentials, import_config, data_len);
+
+    check_useful_validatable_json!(config, ErrorCode::CommonInvalidParam2, Config);
+    check_useful_json!(credentials, ErrorCode::CommonInvalidParam3, Credentials);
+    check_useful_json!(import_config, ErrorCode::CommonInvalidParam4, StreamExportConfig);
+    check_useful_c_byte_array!(data_raw, data_len, ErrorCode::CommonInvalidParam5, ErrorCode::CommonInvalidParam6);
+    check_useful_c_callback!(cb, ErrorCode::CommonInvalidParam7);
+
+    trace!("indy_import_wallet_from_buffer: params config: {:?}, credentials: {:?}, import_config: {:?}, data_len: {:?}",
+           config, secret!(&credentials), secret!(&import_config), data_raw.len());
+
+    let result = CommandExecutor::instance()
+        .send(Command::Wallet(WalletCommand::ImportFromStream(
+            config,
+            credentials,
+            import_config,
+            ImportSource::Buffer(data_raw),
+            Box::new(move |result| {
+                let err = prepare_result!(result);
+                trace!("indy_import_wallet_from_buffer: cb command_handle: {:?}, err: {:?}", command_handle, err);
+                cb(command_handle, err)
+            })
+        )));
+
+    let res = prepare_result!(result);
+    trace!("indy_import_wallet_from_buffer: <<< res: {:?}", res);
+    res
+}
+
+/// Creates a new secure wallet and then imports its content from an application provided source.
+///
+/// Exported content is requested from read_cb chunk by chunk until it reports 0 read bytes.
+/// Note that read_cb is called from libindy thread, so it must not block on other libindy calls.
+///
+/// #Params
+/// config: Wallet configuration json (See indy_import_wallet).
+/// credentials: Wallet credentials json (See indy_import_wallet).
+/// import_config: Import settings json.
+/// {
+///   "key": <string>, key used for export of the wallet
+/// }
+/// source_handle: application defined handle passed to read_cb
+/// read_cb: callback that reads the next chunk of the exported wallet.
+///          Any error code except Success aborts import.
+/// cb: Callback that takes command result as parameter.
+///
+/// #Returns
+/// Error code
+///
+/// #Errors
+/// Common*
+/// Wallet*
+#[no_mangle]
+pub extern fn indy_import_wallet_from_source(command_handle: CommandHandle,
+                                             config: *const c_char,
+                                             credentials: *const c_char,
+                                             import_config: *const c_char,
+                                             source_handle: IndyHandle,
+                                             read_cb: Option<WalletImportRead>,
+                                             cb: Option<extern fn(command_handle_: CommandHandle,
+                                                                  err: ErrorCode)>) -> ErrorCode {
+    trace!("indy_import_wallet_from_source: >>> command_handle: {:?}, config: {:?}, credentials: {:?}, import_config: {:?}, source_handle: {:?}",
+           command_handle, config, credentials, import_config, source_handle);
+
+    check_useful_validatable_json!(config, ErrorCode::CommonInvalidParam2, Config);
+    check_useful_json!(credentials, ErrorCode::CommonInvalidParam3, Credentials);
+    check_useful_json!(import_config, ErrorCode::CommonInvalidParam4, StreamExportConfig);
+    check_useful_c_callback!(read_cb, ErrorCode::CommonInvalidParam6);
+    check_useful_c_callback!(cb, ErrorCode::CommonInvalidParam7);
+
+    trace!("indy_import_wallet_from_source: params config: {:?}, credentials: {:?}, import_config: {:?}, source_handle: {:?}",
+           config, secret!(&credentials), secret!(&import_config), source_handle);
+
+    let result = CommandExecutor::instance()
+        .send(Command::Wallet(WalletCommand::ImportFromStream(
+            config,
+            credentials,
+            import_config,
+            ImportSource::Source(source_handle, read_cb),
+            Box::new(move |result| {
+                let err = prepare_result!(result);
+                trace!("indy_import_wallet_from_source: cb command_handle: {:?}, err: {:?}", command_handle, err);
+                cb(command_handle, err)
+            })
+        )));
+
+    let res = prepare_result!(result);
+    trace!("indy_import_wallet_from_source: <<< res: {:?}", res);
+    res
+}
+
+/// Imports content of the incremental export from a memory buffer into opened wallet
+/// (See indy_import_wallet_increment).
+///
+/// The first increment can be imported with indy_import_wallet_from_buffer.
+///
+/// #Params
+/// wallet_handle: wallet handle returned by indy_open_wallet
+/// import_config: Import settings json.
+/// {
+///   "key": <string>, key used for export of the wallet
+/// }
+/// data_raw: a pointer to first byte of exported wallet increment
+/// data_len: exported wallet increment length
+/// cb: Callback that takes command result as parameter.
+///
+/// #Returns
+/// Error code
+///
+/// #Errors
+/// Common*
+/// Wallet*
+#[no_mangle]
+pub extern fn indy_import_wallet_increment_from_buffer(command_handle: CommandHandle,
+                                                       wallet_handle: WalletHandle,
+                                                       import_config: *const c_char,
+                                                       data_raw: *const u8,
+                                                       data_len: u32,
+                                                       cb: Option<extern fn(command_handle_: CommandHandle,
+                                                                            err: ErrorCode)>) -> ErrorCode {
+    trace!("indy_import_wallet_increment_from_buffer: >>> command_handle: {:?}, wallet_handle: {:?}, import_config: {:?}, data_len: {:?}",
+           command_handle, wallet_handle, import_config, data_len);
+
+    check_useful_json!(import_config, ErrorCode::CommonInvalidParam3, StreamExportConfig);
+    check_useful_c_byte_array!(data_raw, data_len, ErrorCode::CommonInvalidParam4, ErrorCode::CommonInvalidParam5);
+    check_useful_c_callback!(cb, ErrorCode::CommonInvalidParam6);
+
+    trace!("indy_import_wallet_increment_from_buffer: params wallet_handle: {:?}, import_config: {:?}, data_len: {:?}",
+           wallet_handle, secret!(&import_config), data_raw.len());
+
+    let result = CommandExecutor::instance()
+        .send(Command::Wallet(WalletCommand::ImportIncrementFromStream(
+            wallet_handle,
+            import_config,
+            ImportSource::Buffer(data_raw),
+            Box::new(move |result| {
+                let err = prepare_result!(result);
+                trace!("indy_import_wallet_increment_from_buffer: cb command_handle: {:?}, err: {:?}", command_handle, err);
+                cb(command_handle, err)
+            })
+        )));
+
+    let res = prepare_result!(result);
+    trace!("indy_import_wallet_increment_from_buffer: <<< res: {:?}", res);
+    res
+}
+
+/// Imports content of the incremental export from an application provided source into opened wallet
+/// (See indy_import_wallet_increment).
+///
+/// The first increment can be imported with indy_import_wallet_from_source.
+/// Exported content is requested from read_cb chunk by chunk until it reports 0 read bytes.
+/// Note that read_cb is called from libindy thread, so it must not block on other libindy calls.
+///
+/// #Params
+/// wallet_handle: wallet handle returned by indy_open_wallet
+/// import_config: Import settings json.
+/// {
+///   "key": <string>, key used for export of the wallet
+/// }
+/// source_handle: application defined handle passed to read_cb
+/// read_cb: callback that reads the next chunk of the exported wallet increment.
+///          Any error code except Success aborts import.
+/// cb: Callback that takes command result as parameter.
+///
+/// #Returns
+/// Error code
+///
+/// #Errors
+/// Common*
+/// Wallet*
+#[no_mangle]
+pub extern fn indy_import_wallet_increment_from_source(command_handle: CommandHandle,
+                                                       wallet_handle: WalletHandle,
+                                                       import_config: *const c_char,
+                                                       source_handle: IndyHandle,
+                                                       read_cb: Option<WalletImportRead>,
+                                                       cb: Option<extern fn(command_handle_: CommandHandle,
+                                                                            err: ErrorCode)>) -> ErrorCode {
+    trace!("indy_import_wallet_increment_from_source: >>> command_handle: {:?}, wallet_handle: {:?}, import_config: {:?}, source_handle: {:?}",
+           command_handle, wallet_handle, import_config, source_handle);
+
+    check_useful_json!(import_config, ErrorCode::CommonInvalidParam3, StreamExportConfig);
+    check_useful_c_callback!(read_cb, ErrorCode::CommonInvalidParam5);
+    check_useful_c_callback!(cb, ErrorCode::CommonInvalidParam6);
+
+    trace!("indy_import_wallet_increment_from_source: params wallet_handle: {:?}, import_config: {:?}, source_handle: {:?}",
+           wallet_handle, secret!(&import_config), source_handle);
+
+    let result = CommandExecutor::instance()
+        .send(Command::Wallet(WalletCommand::ImportIncrementFromStream(
+            wallet_handle,
+            import_config,
+            ImportSource::Source(source_handle, read_cb),
+            Box::new(move |result| {
+                let err = prepare_result!(result);
+                trace!("indy_import_wallet_increment_from_source: cb command_handle: {:?}, err: {:?}", command_handle, err);
+                cb(command_handle, err)
+            })
+        )));
+
+    let res = prepare_result!(result);
+    trace!("indy_import_wallet_increment_from_source: <<< res: {:?}", res);
+    res
+}
+
 
 /// Closes opened wallet and frees allocated resources.
 ///
@@ -469,6 +1067,168 @@ pub extern fn indy_close_wallet(command_handle: CommandHandle,
     res
 }
 
+/// Get entries of the wallet audit log.
+///
+/// Audit log is maintained only for wallets opened with "audit_log": true in wallet config.
+/// Once enabled the option is stored in wallet metadata and stays on for all subsequent opens.
+/// Entries are written in the same storage transaction as the change itself
+/// (plugged storages without transaction support write them sequentially).
+/// Each entry describes one change of wallet record: additions, modifications and deletions.
+/// Note record names are not stored in the log, only SHA-256 hashes of them.
+///
+/// #Params
+/// command_handle: Command handle to map callback to caller context.
+/// wallet_handle: wallet handle returned by indy_open_wallet.
+/// query_json: (optional) time range of the requested entries
+///   {
+///       "from": optional<int>, Return entries created after or at this time (seconds since epoch)
+///       "to": optional<int>, Return entries created before or at this time (seconds since epoch)
+///   }
+/// cb: Callback that takes command result as parameter.
+///
+/// #Returns
+/// entries_json: list of audit log entries ordered by time
+///   [{
+///       "type": string, Type of the changed record
+///       "name_hash": string, Base58 encoded SHA-256 hash of the changed record name
+///       "operation": string, One of "add", "update", "update_tags", "delete"
+///       "timestamp": int, Time of the change (seconds since epoch)
+///   }]
+///
+/// #Errors
+/// Common*
+/// Wallet*
+#[no_mangle]
+pub extern fn indy_get_wallet_audit_log(command_handle: CommandHandle,
+                                        wallet_handle: WalletHandle,
+                                        query_json: *const c_char,
+                                        cb: Option<extern fn(command_handle_: CommandHandle,
+                                                             err: ErrorCode,
+                                                             entries_json: *const c_char)>) -> ErrorCode {
+    trace!("indy_get_wallet_audit_log: >>> command_handle: {:?}, wallet_handle: {:?}, query_json: {:?}, cb: {:?}",
+           command_handle, wallet_handle, query_json, cb);
+
+    check_useful_opt_json!(query_json, ErrorCode::CommonInvalidParam3, AuditLogQuery);
+    check_useful_c_callback!(cb, ErrorCode::CommonInvalidParam4);
+
+    trace!("indy_get_wallet_audit_log: params wallet_handle: {:?}, query_json: {:?}", wallet_handle, query_json);
+
+    let result = CommandExecutor::instance()
+        .send(Command::Wallet(WalletCommand::GetAuditLog(
+            wallet_handle,
+            query_json.unwrap_or_default(),
+            boxed_callback_string!("indy_get_wallet_audit_log", cb, command_handle)
+        )));
+
+    let res = prepare_result!(result);
+    trace!("indy_get_wallet_audit_log: <<< res: {:?}", res);
+    res
+}
+
+/// Get usage statistics of the opened wallet.
+///
+/// Statistics are computed by wallet storage without decryption of record values and tags.
+///
+/// Built-in storages keep counters in indexes, so the call doesn't read records.
+/// Custom storages without statistics handlers (See indy_register_wallet_storage_stats)
+/// are scanned through all records, so the call cost grows with wallet size.
+///
+/// #Params
+/// command_handle: Command handle to map callback to caller context.
+/// wallet_handle: wallet handle returned by indy_open_wallet.
+/// cb: Callback that takes command result as parameter.
+///
+/// #Returns
+/// stats_json: wallet statistics
+///   {
+///       "total_records": int, Number of records in the wallet (internal records are not counted)
+///       "total_tags": int, Number of tags of all records (internal records are not counted)
+///       "types": { Number of records and tags per record type ("Indy::Did", "Indy::Key", non-secrets types etc.)
+///           <type>: {"records": int, "tags": int},
+///       },
+///       "internal": { Number of records and tags per type of records maintained by the wallet itself
+///           (export journal, export and import checkpoints, audit log)
+///           <type>: {"records": int, "tags": int},
+///       },
+///       "corrupted_records": int, Number of records with type that can't be decrypted (See indy_check_wallet)
+///       "storage_size": optional<int>, Size of wallet storage in bytes if storage type can provide it
+///       "last_modified": optional<int>, Time of last storage change (seconds since epoch) if storage type can provide it
+///   }
+///
+/// #Errors
+/// Common*
+/// Wallet*
+#[no_mangle]
+pub extern fn indy_get_wallet_stats(command_handle: CommandHandle,
+                                    wallet_handle: WalletHandle,
+                                    cb: Option<extern fn(command_handle_: CommandHandle,
+                                                         err: ErrorCode,
+                                                         stats_json: *const c_char)>) -> ErrorCode {
+    trace!("indy_get_wallet_stats: >>> command_handle: {:?}, wallet_handle: {:?}, cb: {:?}",
+           command_handle, wallet_handle, cb);
+
+    check_useful_c_callback!(cb, ErrorCode::CommonInvalidParam3);
+
+    trace!("indy_get_wallet_stats: params wallet_handle: {:?}", wallet_handle);
+
+    let result = CommandExecutor::instance()
+        .send(Command::Wallet(WalletCommand::GetWalletStats(
+            wallet_handle,
+            boxed_callback_string!("indy_get_wallet_stats", cb, command_handle)
+        )));
+
+    let res = prepare_result!(result);
+    trace!("indy_get_wallet_stats: <<< res: {:?}", res);
+    res
+}
+
+/// Set callback to be notified about changes of wallet records.
+///
+/// Callback is called after each successful addition, modification or deletion of wallet record
+/// with change description json (the same as entry of indy_get_wallet_audit_log).
+/// It works regardless of "audit_log" wallet config option.
+///
+/// #Params
+/// command_handle: Command handle to map callback to caller context.
+/// wallet_handle: wallet handle returned by indy_open_wallet.
+/// change_cb: (optional) Change notification callback. Pass NULL to unsubscribe.
+/// cb: Callback that takes command result as parameter.
+///
+/// #Returns
+/// Error code
+///
+/// #Errors
+/// Common*
+/// Wallet*
+#[no_mangle]
+pub extern fn indy_set_wallet_change_callback(command_handle: CommandHandle,
+                                              wallet_handle: WalletHandle,
+                                              change_cb: Option<WalletChangeCB>,
+                                              cb: Option<extern fn(command_handle_: CommandHandle,
+                                                                   err: ErrorCode)>) -> ErrorCode {
+    trace!("indy_set_wallet_change_callback: >>> command_handle: {:?}, wallet_handle: {:?}, change_cb: {:?}, cb: {:?}",
+           command_handle, wallet_handle, change_cb, cb);
+
+    check_useful_c_callback!(cb, ErrorCode::CommonInvalidParam4);
+
+    trace!("indy_set_wallet_change_callback: params wallet_handle: {:?}", wallet_handle);
+
+    let result = CommandExecutor::instance()
+        .send(Command::Wallet(WalletCommand::SetChangeCallback(
+            wallet_handle,
+            change_cb,
+            Box::new(move |result| {
+                let err = prepare_result!(result);
+                trace!("indy_set_wallet_change_callback: cb command_handle: {:?}, err: {:?}", command_handle, err);
+                cb(command_handle, err)
+            })
+        )));
+
+    let res = prepare_result!(result);
+    trace!("indy_set_wallet_change_callback: <<< res: {:?}", res);
+    res
+}
+
 /// Deletes created wallet.
 ///
 /// #Params
@@ -478,6 +1238,7 @@ pub extern fn indy_close_wallet(command_handle: CommandHandle,
 ///         Configured storage uses this identifier to lookup exact wallet data placement.
 ///   "storage_type": optional<string>, Type of the wallet storage. Defaults to 'default'.
 ///                  'Default' storage type allows to store wallet data in the local file.
+///                  'inmem' storage type is described in indy_create_wallet.
 ///                  Custom storage types can be registered with indy_register_wallet_storage call.
 ///   "storage_config": optional<object>, Storage configuration json. Storage type defines set of supported keys.
 ///                     Can be optional if storage supports default configuration.
@@ -539,6 +1300,74 @@ pub extern fn indy_delete_wallet(command_handle: CommandHandle,
     res
 }
 
+/// Checks integrity of created wallet and optionally repairs it.
+///
+/// Walks every wallet record and verifies that its name, type, value and tags can be decrypted
+/// with wallet keys. For 'default' storage type also checks database integrity and consistency of tags.
+/// In repair mode corrupted records are moved to "Indy::Quarantine" record type
+/// and tags not linked to any record are removed.
+///
+/// Wallet must be closed.
+///
+/// #Params
+/// command_handle: Command handle to map callback to caller context.
+/// config: Wallet configuration json (the same as for indy_delete_wallet).
+/// credentials: Wallet credentials json (the same as for indy_delete_wallet).
+/// options_json: (optional) check options
+///   {
+///       "repair": optional<bool>, Quarantine corrupted records. Defaults to false.
+///   }
+/// cb: Callback that takes command result as parameter.
+///
+/// #Returns
+/// report_json: wallet check report
+///   {
+///       "total_items": int, Count of checked records
+///       "corrupted_items": [{
+///           "type": optional<string>, Type of the corrupted record if it can be decrypted
+///           "id": optional<string>, Id of the corrupted record if it can be decrypted
+///           "reason": string, Description of the problem
+///       }],
+///       "quarantined_items": int, Count of quarantined records
+///       "orphaned_tags": int, Count of tags not linked to any record
+///       "integrity_errors": [string], Problems reported by the storage
+///       "storage_error": optional<string>, Error that interrupted walking through the storage
+///   }
+///
+/// #Errors
+/// Common*
+/// Wallet*
+#[no_mangle]
+pub extern fn indy_check_wallet(command_handle: CommandHandle,
+                                config: *const c_char,
+                                credentials: *const c_char,
+                                options_json: *const c_char,
+                                cb: Option<extern fn(command_handle_: CommandHandle,
+                                                     err: ErrorCode,
+                                                     report_json: *const c_char)>) -> ErrorCode {
+    trace!("indy_check_wallet: >>> command_handle: {:?}, config: {:?}, credentials: {:?}, options_json: {:?}, cb: {:?}",
+           command_handle, config, credentials, options_json, cb);
+
+    check_useful_validatable_json!(config, ErrorCode::CommonInvalidParam2, Config);
+    check_useful_json!(credentials, ErrorCode::CommonInvalidParam3, Credentials);
+    check_useful_opt_json!(options_json, ErrorCode::CommonInvalidParam4, CheckWalletOptions);
+    check_useful_c_callback!(cb, ErrorCode::CommonInvalidParam5);
+
+    trace!("indy_check_wallet: params config: {:?}, credentials: {:?}, options_json: {:?}", config, secret!(&credentials), options_json);
+
+    let result = CommandExecutor::instance()
+        .send(Command::Wallet(WalletCommand::Check(
+            config,
+            credentials,
+            options_json.unwrap_or_default(),
+            boxed_callback_string!("indy_check_wallet", cb, command_handle)
+        )));
+
+    let res = prepare_result!(result);
+    trace!("indy_check_wallet: <<< res: {:?}", res);
+    res
+}
+
 /// Generate wallet master key.
 /// Returned key is compatible with "RAW" key derivation method.
 /// It allows to avoid expensive key derivation for use cases when wallet keys can be stored in a secure enclave.
@@ -580,3 +1409,91 @@ pub extern fn indy_generate_wallet_key(command_handle: CommandHandle,
     trace!("indy_generate_wallet_key: <<< res: {:?}", res);
     res
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use std::sync::atomic::{AtomicBool, Ordering};
+    use std::sync::Arc;
+
+    struct TestStorageType {
+        created: Arc<AtomicBool>,
+    }
+
+    impl WalletStorageType for TestStorageType {
+        fn create_storage(&self, _id: &str, _config: Option<&str>, _credentials: Option<&str>, _metadata: &[u8]) -> IndyResult<()> {
+            self.created.store(true, Ordering::SeqCst);
+            Ok(())
+        }
+
+        fn open_storage(&self, _id: &str, _config: Option<&str>, _credentials: Option<&str>) -> IndyResult<Box<dyn WalletStorage>> {
+            Err(err_msg(IndyErrorKind::WalletNotFound, "Test storage can't be opened"))
+        }
+
+        fn delete_storage(&self, _id: &str, _config: Option<&str>, _credentials: Option<&str>) -> IndyResult<()> {
+            Ok(())
+        }
+    }
+
+    fn _storage_type() -> (Box<dyn WalletStorageType>, Arc<AtomicBool>) {
+        let created = Arc::new(AtomicBool::new(false));
+        (Box::new(TestStorageType { created: created.clone() }), created)
+    }
+
+    fn _create_wallet(type_: &str) -> IndyResult<()> {
+        let config: Config = serde_json::from_value(json!({"id": type_, "storage_type": type_})).unwrap();
+        let credentials: Credentials = serde_json::from_value(json!({
+            "key": "6nxtSiXFvBd593Y2DCed2dYvRY1PGK9WMtxCBjLzKgbw",
+            "key_derivation_method": "RAW"
+        })).unwrap();
+
+        let (sender, receiver) = channel();
+
+        CommandExecutor::instance()
+            .send(Command::Wallet(WalletCommand::Create(config, credentials, Box::new(move |result| sender.send(result).unwrap()))))
+            .unwrap();
+
+        receiver.recv().unwrap()
+    }
+
+    #[test]
+    fn register_wallet_storage_type_works() {
+        let (storage_type, created) = _storage_type();
+
+        register_wallet_storage_type("register_wallet_storage_type_works", storage_type).unwrap();
+        _create_wallet("register_wallet_storage_type_works").unwrap();
+
+        assert!(created.load(Ordering::SeqCst));
+    }
+
+    #[test]
+    fn register_wallet_storage_type_works_for_twice() {
+        let (storage_type, _) = _storage_type();
+        register_wallet_storage_type("register_wallet_storage_type_works_for_twice", storage_type).unwrap();
+
+        let (storage_type, _) = _storage_type();
+        let res = register_wallet_storage_type("register_wallet_storage_type_works_for_twice", storage_type);
+        assert_kind!(IndyErrorKind::WalletStorageTypeAlreadyRegistered, res);
+    }
+
+    #[test]
+    fn register_wallet_storage_type_works_for_callback() {
+        let (sender, receiver) = channel();
+
+        let (storage_type, _) = _storage_type();
+
+        CommandExecutor::instance()
+            .send(Command::Wallet(WalletCommand::RegisterWalletStorageType(
+                "register_wallet_storage_type_works_for_callback".to_string(),
+                storage_type,
+                Box::new(move |_| {
+                    let (storage_type, _) = _storage_type();
+                    sender.send(register_wallet_storage_type("register_wallet_storage_type_works_for_callback_nested", storage_type)).unwrap()
+                }))))
+            .unwrap();
+
+        let res = receiver.recv().unwrap();
+        assert_kind!(IndyErrorKind::InvalidState, res);
+    }
+}
@@ -9,9 +9,12 @@ use ursa::cl::{
 };
 use ursa::cl::{CredentialKeyCorrectnessProof, CredentialPrivateKey};
 
+use crate::api::anoncreds::RevocRegCreatedHook;
 use crate::commands::{Command, CommandExecutor, BoxedCallbackStringStringSend};
 use crate::commands::anoncreds::AnoncredsCommand;
-use crate::domain::anoncreds::credential::{CredentialValues, Credential};
+use crate::commands::ledger::LedgerCommand;
+use crate::domain::anoncreds::credential::{CredentialValues, Credential, RawCredentialValues};
+use crate::domain::anoncreds::credential_batch::{CredentialIssuanceRequests, CredentialRevocationIds, IssuedCredential};
 use crate::domain::anoncreds::credential_definition::{
     CredentialDefinition,
     CredentialDefinitionConfig,
@@ -27,9 +30,12 @@ use crate::domain::anoncreds::credential_offer::CredentialOffer;
 use crate::domain::anoncreds::credential_request::CredentialRequest;
 use crate::domain::anoncreds::revocation_registry::{
     RevocationRegistry,
+    RevocationRegistryDrift,
+    RevocationRegistryStatus,
     RevocationRegistryV1,
 };
 use crate::domain::anoncreds::revocation_registry_definition::{
+    CL_ACCUM,
     IssuanceType,
     RegistryType,
     RevocationRegistryConfig,
@@ -40,6 +46,7 @@ use crate::domain::anoncreds::revocation_registry_definition::{
     RevocationRegistryInfo,
     RevocationRegistryId
 };
+use crate::domain::anoncreds::revocation_registry_pool::{RevocationRegistryPool, RevocationRegistryPoolConfig};
 use crate::domain::anoncreds::revocation_registry_delta::{
     RevocationRegistryDelta,
     RevocationRegistryDeltaV1,
@@ -49,14 +56,15 @@ use crate::domain::crypto::did::DidValue;
 use indy_api_types::domain::wallet::Tags;
 use indy_api_types::errors::prelude::*;
 use crate::services::anoncreds::AnoncredsService;
-use crate::services::anoncreds::helpers::parse_cred_rev_id;
+use crate::services::anoncreds::helpers::{complete_credential_values, parse_cred_rev_id};
 use crate::services::blob_storage::BlobStorageService;
 use crate::services::crypto::CryptoService;
 use crate::services::pool::PoolService;
 use indy_wallet::{RecordOptions, WalletService};
 
 use super::tails::{SDKTailsAccessor, store_tails_from_generator};
-use indy_api_types::{WalletHandle, CommandHandle};
+use indy_api_types::{ErrorCode, WalletHandle, CommandHandle, PoolHandle};
+use indy_utils::ctypes;
 use indy_utils::next_command_handle;
 
 pub enum IssuerCommand {
@@ -113,6 +121,19 @@ pub enum IssuerCommand {
         RevocationRegistryConfig, // config
         i32, // tails writer handle
         Box<dyn Fn(IndyResult<(String, String, String)>) + Send>),
+    CreateRevocationRegistryPool(
+        WalletHandle,
+        DidValue, // issuer did
+        CredentialDefinitionId, // credential definition id
+        RevocationRegistryPoolConfig, // config
+        Box<dyn Fn(IndyResult<String>) + Send>),
+    GetRevocationRegistryPool(
+        WalletHandle,
+        CredentialDefinitionId, // credential definition id
+        Box<dyn Fn(IndyResult<String>) + Send>),
+    RegisterRevocationRegistryCreatedHook(
+        Option<RevocRegCreatedHook>, // hook
+        Box<dyn Fn(IndyResult<()>) + Send>),
     CreateCredentialOffer(
         WalletHandle,
         CredentialDefinitionId, // credential definition id
@@ -121,28 +142,64 @@ pub enum IssuerCommand {
         WalletHandle,
         CredentialOffer, // credential offer
         CredentialRequest, // credential request
-        CredentialValues, // credential values
+        RawCredentialValues, // credential values
         Option<RevocationRegistryId>, // revocation registry id
         Option<i32>, // blob storage reader config handle
         Box<dyn Fn(IndyResult<(String, Option<String>, Option<String>)>) + Send>),
+    CreateCredentials(
+        WalletHandle,
+        CredentialIssuanceRequests, // credential issuance requests
+        Option<RevocationRegistryId>, // revocation registry id
+        Option<i32>, // blob storage reader config handle
+        Box<dyn Fn(IndyResult<(String, Option<String>)>) + Send>),
     RevokeCredential(
         WalletHandle,
         i32, // blob storage reader config handle
         RevocationRegistryId, //revocation registry id
         String, //credential revoc id
         Box<dyn Fn(IndyResult<String>) + Send>),
+    RevokeCredentials(
+        WalletHandle,
+        i32, // blob storage reader config handle
+        RevocationRegistryId, //revocation registry id
+        CredentialRevocationIds, //credential revoc ids
+        Box<dyn Fn(IndyResult<String>) + Send>),
     /*    RecoverCredential(
             WalletHandle,
             i32, // blob storage reader config handle
             String, //revocation revoc id
             String, //credential revoc id
             Box<dyn Fn(Result<String, IndyError>) + Send>),*/
+    GetRevocationRegistryStatus(
+        WalletHandle,
+        RevocationRegistryId, // revocation registry id
+        Box<dyn Fn(IndyResult<String>) + Send>),
+    CheckRevocationRegistryDrift(
+        PoolHandle,
+        WalletHandle,
+        Option<DidValue>, // submitter did
+        RevocationRegistryId, // revocation registry id
+        Box<dyn Fn(IndyResult<String>) + Send>),
+    CheckRevocationRegistryDriftContinue(
+        WalletHandle,
+        RevocationRegistryId, // revocation registry id
+        IndyResult<(String, String, u64)>, // ledger revocation registry
+        CommandHandle),
     MergeRevocationRegistryDeltas(
         RevocationRegistryDelta, //revocation registry delta
         RevocationRegistryDelta, //other revocation registry delta
         Box<dyn Fn(IndyResult<String>) + Send>),
 }
 
+struct IssuanceRevocationRegistry {
+    id: RevocationRegistryId,
+    definition: RevocationRegistryDefinitionV1,
+    registry: RevocationRegistryV1,
+    private: RevocationRegistryDefinitionPrivate,
+    info: RevocationRegistryInfo,
+    tails_accessor: SDKTailsAccessor,
+}
+
 pub struct IssuerCommandExecutor {
     pub anoncreds_service: Rc<AnoncredsService>,
     pub blob_storage_service: Rc<BlobStorageService>,
@@ -151,6 +208,7 @@ pub struct IssuerCommandExecutor {
     pub crypto_service: Rc<CryptoService>,
     pending_str_str_callbacks: RefCell<HashMap<CommandHandle, BoxedCallbackStringStringSend>>,
     pending_str_callbacks: RefCell<HashMap<CommandHandle, Box<dyn Fn(IndyResult<String>) + Send>>>,
+    revoc_reg_created_hook: RefCell<Option<RevocRegCreatedHook>>,
 }
 
 impl IssuerCommandExecutor {
@@ -167,6 +225,7 @@ impl IssuerCommandExecutor {
             crypto_service,
             pending_str_str_callbacks: RefCell::new(HashMap::new()),
             pending_str_callbacks: RefCell::new(HashMap::new()),
+            revoc_reg_created_hook: RefCell::new(None),
         }
     }
 
@@ -208,22 +267,55 @@ impl IssuerCommandExecutor {
                                                              &config,
                                                              tails_writer_handle));
             }
+            IssuerCommand::CreateRevocationRegistryPool(wallet_handle, issuer_did, cred_def_id, config, cb) => {
+                debug!(target: "issuer_command_executor", "CreateRevocationRegistryPool command received");
+                cb(self.create_revocation_registry_pool(wallet_handle, &issuer_did, &cred_def_id, config));
+            }
+            IssuerCommand::GetRevocationRegistryPool(wallet_handle, cred_def_id, cb) => {
+                debug!(target: "issuer_command_executor", "GetRevocationRegistryPool command received");
+                cb(self.get_revocation_registry_pool(wallet_handle, &cred_def_id));
+            }
+            IssuerCommand::RegisterRevocationRegistryCreatedHook(hook, cb) => {
+                debug!(target: "issuer_command_executor", "RegisterRevocationRegistryCreatedHook command received");
+                cb(self.register_revocation_registry_created_hook(hook));
+            }
             IssuerCommand::CreateCredentialOffer(wallet_handle, cred_def_id, cb) => {
                 debug!(target: "issuer_command_executor", "CreateCredentialOffer command received");
                 cb(self.create_credential_offer(wallet_handle, &cred_def_id));
             }
             IssuerCommand::CreateCredential(wallet_handle, cred_offer, cred_req, cred_values, rev_reg_id, blob_storage_reader_handle, cb) => {
                 debug!(target: "issuer_command_executor", "CreateCredential command received");
-                cb(self.new_credential(wallet_handle, &cred_offer, &cred_req, &cred_values, rev_reg_id.as_ref(), blob_storage_reader_handle));
+                cb(complete_credential_values(cred_values)
+                    .and_then(|cred_values| self.new_credential(wallet_handle, &cred_offer, &cred_req, &CredentialValues(cred_values), rev_reg_id.as_ref(), blob_storage_reader_handle)));
+            }
+            IssuerCommand::CreateCredentials(wallet_handle, requests, rev_reg_id, blob_storage_reader_handle, cb) => {
+                debug!(target: "issuer_command_executor", "CreateCredentials command received");
+                cb(self.new_credentials(wallet_handle, requests, rev_reg_id.as_ref(), blob_storage_reader_handle));
             }
             IssuerCommand::RevokeCredential(wallet_handle, blob_storage_reader_handle, rev_reg_id, cred_revoc_id, cb) => {
                 debug!(target: "issuer_command_executor", "RevokeCredential command received");
                 cb(self.revoke_credential(wallet_handle, blob_storage_reader_handle, &rev_reg_id, &cred_revoc_id));
             }
+            IssuerCommand::RevokeCredentials(wallet_handle, blob_storage_reader_handle, rev_reg_id, cred_revoc_ids, cb) => {
+                debug!(target: "issuer_command_executor", "RevokeCredentials command received");
+                cb(self.revoke_credentials(wallet_handle, blob_storage_reader_handle, &rev_reg_id, &cred_revoc_ids));
+            }
             /*            IssuerCommand::RecoverCredential(wallet_handle, blob_storage_reader_handle, rev_reg_id, cred_revoc_id, cb) => {
                             debug!(target: "issuer_command_executor", "RecoverCredential command received");
                             cb(self.recovery_credential(wallet_handle, blob_storage_reader_handle, &rev_reg_id, &cred_revoc_id));
                         }*/
+            IssuerCommand::GetRevocationRegistryStatus(wallet_handle, rev_reg_id, cb) => {
+                debug!(target: "issuer_command_executor", "GetRevocationRegistryStatus command received");
+                cb(self.get_revocation_registry_status(wallet_handle, &rev_reg_id));
+            }
+            IssuerCommand::CheckRevocationRegistryDrift(pool_handle, wallet_handle, submitter_did, rev_reg_id, cb) => {
+                debug!(target: "issuer_command_executor", "CheckRevocationRegistryDrift command received");
+                self.check_revocation_registry_drift(pool_handle, wallet_handle, submitter_did, rev_reg_id, cb);
+            }
+            IssuerCommand::CheckRevocationRegistryDriftContinue(wallet_handle, rev_reg_id, ledger_rev_reg, cb_id) => {
+                debug!(target: "issuer_command_executor", "CheckRevocationRegistryDriftContinue command received");
+                self._check_revocation_registry_drift_continue(wallet_handle, &rev_reg_id, ledger_rev_reg, cb_id);
+            }
             IssuerCommand::MergeRevocationRegistryDeltas(rev_reg_delta, other_rev_reg_delta, cb) => {
                 debug!(target: "issuer_command_executor", "MergeRevocationRegistryDeltas command received");
                 cb(self.merge_revocation_registry_deltas(&mut RevocationRegistryDeltaV1::from(rev_reg_delta),
@@ -639,6 +731,205 @@ impl IssuerCommandExecutor {
         Ok((rev_reg_id.0, revoc_reg_def_json, revoc_reg_json))
     }
 
+    fn create_revocation_registry_pool(&self,
+                                       wallet_handle: WalletHandle,
+                                       issuer_did: &DidValue,
+                                       cred_def_id: &CredentialDefinitionId,
+                                       config: RevocationRegistryPoolConfig) -> IndyResult<String> {
+        debug!("create_revocation_registry_pool >>> wallet_handle: {:?}, issuer_did: {:?}, cred_def_id: {:?}, config: {:?}",
+               wallet_handle, issuer_did, cred_def_id, config);
+
+        if self.wallet_service.get_indy_opt_object::<RevocationRegistryPool>(wallet_handle, &cred_def_id.0, &RecordOptions::id_value())?.is_some() {
+            return Err(err_msg(IndyErrorKind::WalletItemAlreadyExists,
+                               format!("Revocation registry pool already exists for credential definition: {}", cred_def_id.0)));
+        }
+
+        let mut pool = RevocationRegistryPool {
+            cred_def_id: cred_def_id.clone(),
+            issuer_did: issuer_did.clone(),
+            config,
+            active: RevocationRegistryId(String::new()),
+            next: RevocationRegistryId(String::new()),
+            registries: Vec::new(),
+            published: HashSet::new(),
+        };
+
+        pool.active = self._provision_pool_revocation_registry(wallet_handle, &mut pool)?;
+        pool.next = self._provision_pool_revocation_registry(wallet_handle, &mut pool)?;
+
+        let pool_json = self.wallet_service.add_indy_object(wallet_handle, &cred_def_id.0, &pool, &HashMap::new())?;
+
+        debug!("create_revocation_registry_pool <<< pool_json: {:?}", pool_json);
+
+        Ok(pool_json)
+    }
+
+    fn get_revocation_registry_pool(&self,
+                                    wallet_handle: WalletHandle,
+                                    cred_def_id: &CredentialDefinitionId) -> IndyResult<String> {
+        debug!("get_revocation_registry_pool >>> wallet_handle: {:?}, cred_def_id: {:?}", wallet_handle, cred_def_id);
+
+        let pool_json = self.wallet_service.get_indy_record_value::<RevocationRegistryPool>(wallet_handle, &cred_def_id.0, &RecordOptions::id_value())?;
+
+        debug!("get_revocation_registry_pool <<< pool_json: {:?}", pool_json);
+
+        Ok(pool_json)
+    }
+
+    fn register_revocation_registry_created_hook(&self, hook: Option<RevocRegCreatedHook>) -> IndyResult<()> {
+        debug!("register_revocation_registry_created_hook >>> hook: {:?}", hook);
+
+        *self.revoc_reg_created_hook.try_borrow_mut()? = hook;
+
+        debug!("register_revocation_registry_created_hook <<<");
+
+        Ok(())
+    }
+
+    fn _provision_pool_revocation_registry(&self,
+                                           wallet_handle: WalletHandle,
+                                           pool: &mut RevocationRegistryPool) -> IndyResult<RevocationRegistryId> {
+        // registries left behind by an interrupted provisioning keep their tags occupied
+        let mut seq_no = pool.registries.len() + 1;
+        let tag = loop {
+            let tag = format!("{}_{}", pool.config.tag_prefix(), seq_no);
+            let rev_reg_id = RevocationRegistryId::new(&pool.issuer_did, &pool.cred_def_id, CL_ACCUM, &tag);
+
+            if self.wallet_service.get_indy_opt_object::<RevocationRegistryInfo>(wallet_handle, &rev_reg_id.0, &RecordOptions::id_value())?.is_none() {
+                break tag;
+            }
+            seq_no += 1;
+        };
+
+        let tails_writer_handle = self.blob_storage_service.open_writer(pool.config.tails_writer_type(), &pool.config.tails_writer_config)?;
+
+        let (rev_reg_id, rev_reg_def_json, rev_reg_json) =
+            self.create_and_store_revocation_registry(wallet_handle,
+                                                      &pool.issuer_did,
+                                                      None,
+                                                      &tag,
+                                                      &pool.cred_def_id,
+                                                      &pool.config.registry_config(),
+                                                      tails_writer_handle)?;
+
+        let rev_reg_id = RevocationRegistryId(rev_reg_id);
+        pool.registries.push(rev_reg_id.clone());
+
+        if self._notify_revocation_registry_created(&rev_reg_id, &rev_reg_def_json, &rev_reg_json) {
+            pool.published.insert(rev_reg_id.clone());
+        }
+
+        Ok(rev_reg_id)
+    }
+
+    fn _publish_pool_revocation_registry(&self,
+                                         wallet_handle: WalletHandle,
+                                         pool: &mut RevocationRegistryPool,
+                                         rev_reg_id: &RevocationRegistryId) -> IndyResult<bool> {
+        if pool.is_published(rev_reg_id) {
+            return Ok(true);
+        }
+
+        let rev_reg_def_json = self.wallet_service.get_indy_record_value::<RevocationRegistryDefinition>(wallet_handle, &rev_reg_id.0, &RecordOptions::id_value())?;
+        let rev_reg_json = self.wallet_service.get_indy_record_value::<RevocationRegistry>(wallet_handle, &rev_reg_id.0, &RecordOptions::id_value())?;
+
+        let published = self._notify_revocation_registry_created(rev_reg_id, &rev_reg_def_json, &rev_reg_json);
+
+        if published {
+            pool.published.insert(rev_reg_id.clone());
+        }
+
+        Ok(published)
+    }
+
+    fn _notify_revocation_registry_created(&self, rev_reg_id: &RevocationRegistryId, rev_reg_def_json: &str, rev_reg_json: &str) -> bool {
+        let hook = match *self.revoc_reg_created_hook.borrow() {
+            Some(hook) => hook,
+            None => return true
+        };
+
+        let rev_reg_id_ = ctypes::string_to_cstring(rev_reg_id.0.clone());
+        let rev_reg_def_json = ctypes::string_to_cstring(rev_reg_def_json.to_string());
+        let rev_reg_json = ctypes::string_to_cstring(rev_reg_json.to_string());
+
+        let err = hook(rev_reg_id_.as_ptr(), rev_reg_def_json.as_ptr(), rev_reg_json.as_ptr());
+
+        if err != ErrorCode::Success {
+            warn!("Revocation registry created hook failed for {:?} with error {:?}", rev_reg_id, err);
+            return false;
+        }
+
+        true
+    }
+
+    fn _resolve_pool_revocation_registry(&self,
+                                         wallet_handle: WalletHandle,
+                                         cred_def_id: &CredentialDefinitionId,
+                                         rev_reg_id: &RevocationRegistryId) -> IndyResult<RevocationRegistryId> {
+        let mut pool = match self.wallet_service.get_indy_opt_object::<RevocationRegistryPool>(wallet_handle, &cred_def_id.0, &RecordOptions::id_value())? {
+            Some(ref pool) if !pool.contains(rev_reg_id) => return Ok(rev_reg_id.clone()),
+            Some(pool) => pool,
+            None => return Ok(rev_reg_id.clone())
+        };
+
+        let active = pool.active.clone();
+
+        let active_published = if self._is_revocation_registry_full(wallet_handle, &active)? {
+            self._rollover_revocation_registry_pool(wallet_handle, &mut pool)?;
+            true
+        } else {
+            self._publish_pool_revocation_registry(wallet_handle, &mut pool, &active)?
+        };
+
+        self.wallet_service.update_indy_object(wallet_handle, &cred_def_id.0, &pool)?;
+
+        if !active_published {
+            return Err(err_msg(IndyErrorKind::InvalidState,
+                               format!("Active revocation registry {} of the pool is not published", active.0)));
+        }
+
+        debug!("_resolve_pool_revocation_registry <<< requested: {:?}, active: {:?}", rev_reg_id, pool.active);
+
+        Ok(pool.active)
+    }
+
+    fn _rollover_revocation_registry_pool(&self,
+                                          wallet_handle: WalletHandle,
+                                          pool: &mut RevocationRegistryPool) -> IndyResult<()> {
+        if pool.next == pool.active {
+            pool.next = self._provision_pool_revocation_registry(wallet_handle, pool)?;
+        }
+
+        let next = pool.next.clone();
+
+        if !self._publish_pool_revocation_registry(wallet_handle, pool, &next)? {
+            return Err(err_msg(IndyErrorKind::RevocationRegistryFull,
+                               format!("Revocation registry {} is full and the next registry {} is not published", pool.active.0, next.0)));
+        }
+
+        debug!("_rollover_revocation_registry_pool: {:?} -> {:?}", pool.active, next);
+
+        pool.active = next;
+
+        // the pool keeps working without a pre-provisioned registry; it is provisioned on the next rollover then
+        match self._provision_pool_revocation_registry(wallet_handle, pool) {
+            Ok(rev_reg_id) => pool.next = rev_reg_id,
+            Err(err) => {
+                warn!("Cannot provision next revocation registry for pool {:?}: {:?}", pool.cred_def_id, err);
+                pool.next = pool.active.clone();
+            }
+        }
+
+        Ok(())
+    }
+
+    fn _is_revocation_registry_full(&self, wallet_handle: WalletHandle, rev_reg_id: &RevocationRegistryId) -> IndyResult<bool> {
+        let rev_reg_def = RevocationRegistryDefinitionV1::from(self._wallet_get_rev_reg_def(wallet_handle, rev_reg_id)?);
+        let rev_reg_info = self._wallet_get_rev_reg_info(wallet_handle, rev_reg_id)?;
+
+        Ok(rev_reg_info.curr_id >= rev_reg_def.value.max_cred_num)
+    }
+
     fn create_credential_offer(&self,
                                wallet_handle: WalletHandle,
                                cred_def_id: &CredentialDefinitionId) -> IndyResult<String> {
@@ -677,90 +968,151 @@ impl IssuerCommandExecutor {
         debug!("new_credential >>> wallet_handle: {:?}, cred_offer: {:?}, cred_req: {:?}, cred_values_json: {:?}, rev_reg_id: {:?}, blob_storage_reader_handle: {:?}",
                wallet_handle, secret!(&cred_offer), secret!(&cred_request), secret!(&cred_values), rev_reg_id, blob_storage_reader_handle);
 
-        let cred_def_id = match cred_offer.method_name {
-            Some(ref method_name) => cred_offer.cred_def_id.qualify(method_name),
-            None => cred_offer.cred_def_id.clone()
-        };
+        let (cred_def_id, cred_def, cred_def_priv_key) = self._wallet_get_issuance_cred_def(wallet_handle, cred_offer)?;
 
-        let cred_def: CredentialDefinitionV1 =
-            CredentialDefinitionV1::from(
-                self.wallet_service.get_indy_object::<CredentialDefinition>(wallet_handle, &cred_def_id.0, &RecordOptions::id_value())?);
+        let mut rev_reg = self._wallet_get_issuance_rev_reg(wallet_handle, &cred_def_id, rev_reg_id, blob_storage_reader_handle)?;
 
-        let cred_def_priv_key: CredentialDefinitionPrivateKey =
-            self.wallet_service.get_indy_object(wallet_handle, &cred_def_id.0, &RecordOptions::id_value())?;
+        let (credential, cred_rev_id, rev_reg_delta) =
+            self._issue_credential(&cred_def, &cred_def_priv_key, cred_offer, cred_request, cred_values, rev_reg.as_mut())?;
 
-        let (rev_reg_def, mut rev_reg,
-            rev_reg_def_priv, sdk_tails_accessor, rev_reg_info) = match rev_reg_id {
-            Some(ref r_reg_id) => {
-                let rev_reg_def: RevocationRegistryDefinitionV1 =
-                    RevocationRegistryDefinitionV1::from(
-                        self._wallet_get_rev_reg_def(wallet_handle, &r_reg_id)?);
+        let cred_json = serde_json::to_string(&credential)
+            .to_indy(IndyErrorKind::InvalidState, "Cannot serialize Credential")?;
+
+        let rev_reg_delta_json = rev_reg_delta
+            .map(|r_reg_delta| RevocationRegistryDelta::RevocationRegistryDeltaV1(RevocationRegistryDeltaV1 { value: r_reg_delta }))
+            .as_ref()
+            .map(serde_json::to_string)
+            .map_or(Ok(None), |v| v.map(Some))
+            .to_indy(IndyErrorKind::InvalidState, "Cannot serialize RevocationRegistryDelta")?;
 
-                let rev_reg: RevocationRegistryV1 =
-                    RevocationRegistryV1::from(
-                        self._wallet_get_rev_reg(wallet_handle, &r_reg_id)?);
+        if let Some(rev_reg) = rev_reg {
+            self._wallet_update_issuance_rev_reg(wallet_handle, rev_reg)?;
+        }
+
+        let cred_rev_id = cred_rev_id.map(|cred_rev_id| cred_rev_id.to_string());
+
+        debug!("new_credential <<< cred_json: {:?}, cred_rev_id: {:?}, rev_reg_delta_json: {:?}", secret!(&cred_json), secret!(&cred_rev_id), rev_reg_delta_json);
 
-                let rev_key_priv: RevocationRegistryDefinitionPrivate =
-                    self.wallet_service.get_indy_object(wallet_handle, &r_reg_id.0, &RecordOptions::id_value())?;
+        Ok((cred_json, cred_rev_id, rev_reg_delta_json))
+    }
 
-                let mut rev_reg_info = self._wallet_get_rev_reg_info(wallet_handle, &r_reg_id)?;
+    fn new_credentials(&self,
+                       wallet_handle: WalletHandle,
+                       requests: CredentialIssuanceRequests,
+                       rev_reg_id: Option<&RevocationRegistryId>,
+                       blob_storage_reader_handle: Option<i32>) -> IndyResult<(String, Option<String>)> {
+        debug!("new_credentials >>> wallet_handle: {:?}, requests: {:?}, rev_reg_id: {:?}, blob_storage_reader_handle: {:?}",
+               wallet_handle, secret!(&requests), rev_reg_id, blob_storage_reader_handle);
 
-                rev_reg_info.curr_id += 1;
+        // all requests reference the same credential definition, it is checked by validation
+        let (cred_def_id, cred_def, cred_def_priv_key) = self._wallet_get_issuance_cred_def(wallet_handle, &requests.0[0].cred_offer)?;
 
-                if rev_reg_info.curr_id > rev_reg_def.value.max_cred_num {
-                    return Err(err_msg(IndyErrorKind::RevocationRegistryFull, "RevocationRegistryAccumulator is full"));
-                }
+        let mut rev_reg = self._wallet_get_issuance_rev_reg(wallet_handle, &cred_def_id, rev_reg_id, blob_storage_reader_handle)?;
+
+        let mut issued_credentials = Vec::with_capacity(requests.0.len());
+        let mut merged_rev_reg_delta: Option<CryptoRevocationRegistryDelta> = None;
 
-                if rev_reg_def.value.issuance_type == IssuanceType::ISSUANCE_ON_DEMAND {
-                    rev_reg_info.used_ids.insert(rev_reg_info.curr_id);
+        for request in requests.0 {
+            let cred_values = CredentialValues(complete_credential_values(request.cred_values)?);
+
+            let (credential, cred_rev_id, rev_reg_delta) =
+                self._issue_credential(&cred_def, &cred_def_priv_key, &request.cred_offer, &request.cred_req, &cred_values, rev_reg.as_mut())?;
+
+            merged_rev_reg_delta = match (merged_rev_reg_delta, rev_reg_delta) {
+                (Some(mut merged), Some(rev_reg_delta)) => {
+                    merged.merge(&rev_reg_delta)?;
+                    Some(merged)
                 }
+                (merged, rev_reg_delta) => merged.or(rev_reg_delta)
+            };
 
-                // TODO: FIXME: Review error kind!
-                let blob_storage_reader_handle = blob_storage_reader_handle
-                    .ok_or_else(|| err_msg(IndyErrorKind::InvalidStructure, "TailsReaderHandle not found"))?;
+            issued_credentials.push(IssuedCredential {
+                cred: credential,
+                cred_revoc_id: cred_rev_id.map(|cred_rev_id| cred_rev_id.to_string()),
+            });
+        }
 
-                let sdk_tails_accessor = SDKTailsAccessor::new(self.blob_storage_service.clone(),
-                                                               blob_storage_reader_handle,
-                                                               &rev_reg_def)?;
+        let issued_credentials_json = serde_json::to_string(&issued_credentials)
+            .to_indy(IndyErrorKind::InvalidState, "Cannot serialize Credentials")?;
 
-                (Some(rev_reg_def), Some(rev_reg), Some(rev_key_priv), Some(sdk_tails_accessor), Some(rev_reg_info))
+        let rev_reg_delta_json = merged_rev_reg_delta
+            .map(|r_reg_delta| RevocationRegistryDelta::RevocationRegistryDeltaV1(RevocationRegistryDeltaV1 { value: r_reg_delta }))
+            .as_ref()
+            .map(serde_json::to_string)
+            .map_or(Ok(None), |v| v.map(Some))
+            .to_indy(IndyErrorKind::InvalidState, "Cannot serialize RevocationRegistryDelta")?;
+
+        // the registry is stored only once all credentials are issued, so a failed batch leaves no trace
+        if let Some(rev_reg) = rev_reg {
+            self._wallet_update_issuance_rev_reg(wallet_handle, rev_reg)?;
+        }
+
+        debug!("new_credentials <<< issued_credentials_json: {:?}, rev_reg_delta_json: {:?}", secret!(&issued_credentials_json), rev_reg_delta_json);
+
+        Ok((issued_credentials_json, rev_reg_delta_json))
+    }
+
+    fn _issue_credential(&self,
+                         cred_def: &CredentialDefinitionV1,
+                         cred_def_priv_key: &CredentialDefinitionPrivateKey,
+                         cred_offer: &CredentialOffer,
+                         cred_request: &CredentialRequest,
+                         cred_values: &CredentialValues,
+                         mut rev_reg: Option<&mut IssuanceRevocationRegistry>) -> IndyResult<(Credential, Option<u32>, Option<CryptoRevocationRegistryDelta>)> {
+        if let Some(ref mut rev_reg) = rev_reg {
+            rev_reg.info.curr_id += 1;
+
+            if rev_reg.info.curr_id > rev_reg.definition.value.max_cred_num {
+                return Err(err_msg(IndyErrorKind::RevocationRegistryFull, "RevocationRegistryAccumulator is full"));
             }
-            None => (None, None, None, None, None)
-        };
 
-        let (credential_signature, signature_correctness_proof, rev_reg_delta) =
-            self.anoncreds_service.issuer.new_credential(&cred_def,
+            if rev_reg.definition.value.issuance_type == IssuanceType::ISSUANCE_ON_DEMAND {
+                let curr_id = rev_reg.info.curr_id;
+                rev_reg.info.used_ids.insert(curr_id);
+            }
+        }
+
+        let (credential_signature, signature_correctness_proof, rev_reg_delta) = {
+            let (rev_idx, rev_reg_def, rev_reg_value, rev_key_priv, tails_accessor) = match rev_reg {
+                Some(ref mut rev_reg) => {
+                    let IssuanceRevocationRegistry { ref definition, ref mut registry, ref private, ref info, ref tails_accessor, .. } = **rev_reg;
+                    (Some(info.curr_id), Some(definition), Some(&mut registry.value), Some(&private.value), Some(tails_accessor))
+                }
+                None => (None, None, None, None, None)
+            };
+
+            self.anoncreds_service.issuer.new_credential(cred_def,
                                                          &cred_def_priv_key.value,
                                                          &cred_offer.nonce,
-                                                         &cred_request,
-                                                         &cred_values,
-                                                         rev_reg_info.as_ref().map(|r_reg_info| r_reg_info.curr_id),
-                                                         rev_reg_def.as_ref(),
-                                                         rev_reg.as_mut().map(|r_reg| &mut r_reg.value),
-                                                         rev_reg_def_priv.as_ref().map(|r_reg_def_priv| &r_reg_def_priv.value),
-                                                         sdk_tails_accessor.as_ref())?;
-
-        let witness =
-            if let (&Some(ref r_reg_def), &Some(ref r_reg), &Some(ref rev_tails_accessor), &Some(ref rev_reg_info)) =
-            (&rev_reg_def, &rev_reg, &sdk_tails_accessor, &rev_reg_info) {
-                let (issued, revoked) = match r_reg_def.value.issuance_type {
-                    IssuanceType::ISSUANCE_ON_DEMAND => (rev_reg_info.used_ids.clone(), HashSet::new()),
-                    IssuanceType::ISSUANCE_BY_DEFAULT => (HashSet::new(), rev_reg_info.used_ids.clone())
-                };
+                                                         cred_request,
+                                                         cred_values,
+                                                         rev_idx,
+                                                         rev_reg_def,
+                                                         rev_reg_value,
+                                                         rev_key_priv,
+                                                         tails_accessor)?
+        };
 
-                let rev_reg_delta = CryptoRevocationRegistryDelta::from_parts(None, &r_reg.value, &issued, &revoked);
+        let witness = match rev_reg {
+            Some(ref rev_reg) => {
+                let (issued, revoked) = match rev_reg.definition.value.issuance_type {
+                    IssuanceType::ISSUANCE_ON_DEMAND => (rev_reg.info.used_ids.clone(), HashSet::new()),
+                    IssuanceType::ISSUANCE_BY_DEFAULT => (HashSet::new(), rev_reg.info.used_ids.clone())
+                };
 
-                Some(Witness::new(rev_reg_info.curr_id, r_reg_def.value.max_cred_num,
-                                  r_reg_def.value.issuance_type.to_bool(), &rev_reg_delta, rev_tails_accessor)?)
-            } else {
-                None
-            };
+                let rev_reg_delta = CryptoRevocationRegistryDelta::from_parts(None, &rev_reg.registry.value, &issued, &revoked);
 
-        let cred_rev_reg_id = match (rev_reg_id, cred_offer.method_name.as_ref()) {
-            (Some(rev_reg_id), Some(ref _method_name)) => Some(rev_reg_id.to_unqualified()),
-            (rev_reg_id, _) => rev_reg_id.cloned()
+                Some(Witness::new(rev_reg.info.curr_id, rev_reg.definition.value.max_cred_num,
+                                  rev_reg.definition.value.issuance_type.to_bool(), &rev_reg_delta, &rev_reg.tails_accessor)?)
+            }
+            None => None
         };
 
+        let cred_rev_reg_id = rev_reg.as_ref().map(|rev_reg| match cred_offer.method_name {
+            Some(ref _method_name) => rev_reg.id.to_unqualified(),
+            None => rev_reg.id.clone()
+        });
+
         let credential = Credential {
             schema_id: cred_offer.schema_id.clone(),
             cred_def_id: cred_offer.cred_def_id.clone(),
@@ -768,32 +1120,84 @@ impl IssuerCommandExecutor {
             values: cred_values.clone(),
             signature: credential_signature,
             signature_correctness_proof,
-            rev_reg: rev_reg.map(|r_reg| r_reg.value),
+            rev_reg: rev_reg.as_ref().map(|rev_reg| rev_reg.registry.value.clone()),
             witness,
         };
 
-        let cred_json = serde_json::to_string(&credential)
-            .to_indy(IndyErrorKind::InvalidState, "Cannot serialize Credential")?;
+        let cred_rev_id = rev_reg.as_ref().map(|rev_reg| rev_reg.info.curr_id);
 
-        let rev_reg_delta_json = rev_reg_delta
-            .map(|r_reg_delta| RevocationRegistryDelta::RevocationRegistryDeltaV1(RevocationRegistryDeltaV1 { value: r_reg_delta }))
-            .as_ref()
-            .map(serde_json::to_string)
-            .map_or(Ok(None), |v| v.map(Some))
-            .to_indy(IndyErrorKind::InvalidState, "Cannot serialize RevocationRegistryDelta")?;
+        Ok((credential, cred_rev_id, rev_reg_delta))
+    }
 
-        if let (Some(r_reg), Some(r_reg_id), Some(r_reg_info)) = (credential.rev_reg, rev_reg_id, rev_reg_info.clone()) {
-            let revoc_reg = RevocationRegistry::RevocationRegistryV1(RevocationRegistryV1 { value: r_reg });
+    fn _wallet_get_issuance_cred_def(&self,
+                                     wallet_handle: WalletHandle,
+                                     cred_offer: &CredentialOffer) -> IndyResult<(CredentialDefinitionId, CredentialDefinitionV1, CredentialDefinitionPrivateKey)> {
+        let cred_def_id = match cred_offer.method_name {
+            Some(ref method_name) => cred_offer.cred_def_id.qualify(method_name),
+            None => cred_offer.cred_def_id.clone()
+        };
 
-            self.wallet_service.update_indy_object(wallet_handle, &r_reg_id.0, &revoc_reg)?;
-            self.wallet_service.update_indy_object(wallet_handle, &r_reg_id.0, &r_reg_info)?;
+        let cred_def: CredentialDefinitionV1 =
+            CredentialDefinitionV1::from(
+                self.wallet_service.get_indy_object::<CredentialDefinition>(wallet_handle, &cred_def_id.0, &RecordOptions::id_value())?);
+
+        let cred_def_priv_key: CredentialDefinitionPrivateKey =
+            self.wallet_service.get_indy_object(wallet_handle, &cred_def_id.0, &RecordOptions::id_value())?;
+
+        Ok((cred_def_id, cred_def, cred_def_priv_key))
+    }
+
+    fn _wallet_get_issuance_rev_reg(&self,
+                                    wallet_handle: WalletHandle,
+                                    cred_def_id: &CredentialDefinitionId,
+                                    rev_reg_id: Option<&RevocationRegistryId>,
+                                    blob_storage_reader_handle: Option<i32>) -> IndyResult<Option<IssuanceRevocationRegistry>> {
+        let rev_reg_id = match rev_reg_id {
+            Some(rev_reg_id) => self._resolve_pool_revocation_registry(wallet_handle, cred_def_id, rev_reg_id)?,
+            None => return Ok(None)
         };
 
-        let cred_rev_id = rev_reg_info.map(|r_reg_info| r_reg_info.curr_id.to_string());
+        let definition: RevocationRegistryDefinitionV1 =
+            RevocationRegistryDefinitionV1::from(
+                self._wallet_get_rev_reg_def(wallet_handle, &rev_reg_id)?);
 
-        debug!("new_credential <<< cred_json: {:?}, cred_rev_id: {:?}, rev_reg_delta_json: {:?}", secret!(&cred_json), secret!(&cred_rev_id), rev_reg_delta_json);
+        let registry: RevocationRegistryV1 =
+            RevocationRegistryV1::from(
+                self._wallet_get_rev_reg(wallet_handle, &rev_reg_id)?);
 
-        Ok((cred_json, cred_rev_id, rev_reg_delta_json))
+        let private: RevocationRegistryDefinitionPrivate =
+            self.wallet_service.get_indy_object(wallet_handle, &rev_reg_id.0, &RecordOptions::id_value())?;
+
+        let info = self._wallet_get_rev_reg_info(wallet_handle, &rev_reg_id)?;
+
+        // TODO: FIXME: Review error kind!
+        let blob_storage_reader_handle = blob_storage_reader_handle
+            .ok_or_else(|| err_msg(IndyErrorKind::InvalidStructure, "TailsReaderHandle not found"))?;
+
+        let tails_accessor = SDKTailsAccessor::new(self.blob_storage_service.clone(),
+                                                   blob_storage_reader_handle,
+                                                   &definition)?;
+
+        Ok(Some(IssuanceRevocationRegistry { id: rev_reg_id, definition, registry, private, info, tails_accessor }))
+    }
+
+    fn _wallet_update_issuance_rev_reg(&self, wallet_handle: WalletHandle, rev_reg: IssuanceRevocationRegistry) -> IndyResult<()> {
+        let IssuanceRevocationRegistry { id, registry, info, .. } = rev_reg;
+
+        self._wallet_update_rev_reg(wallet_handle, &id, &RevocationRegistry::RevocationRegistryV1(registry), &info)
+    }
+
+    // Registry and its info are stored in one wallet transaction, so used ids always match the registry.
+    fn _wallet_update_rev_reg(&self,
+                              wallet_handle: WalletHandle,
+                              rev_reg_id: &RevocationRegistryId,
+                              rev_reg: &RevocationRegistry,
+                              rev_reg_info: &RevocationRegistryInfo) -> IndyResult<()> {
+        self.wallet_service.atomic(wallet_handle, &mut || {
+            self.wallet_service.update_indy_object(wallet_handle, &rev_reg_id.0, rev_reg)?;
+            self.wallet_service.update_indy_object(wallet_handle, &rev_reg_id.0, rev_reg_info)?;
+            Ok(())
+        })
     }
 
     fn revoke_credential(&self,
@@ -804,8 +1208,33 @@ impl IssuerCommandExecutor {
         debug!("revoke_credential >>> wallet_handle: {:?}, blob_storage_reader_handle:  {:?}, rev_reg_id: {:?}, cred_revoc_id: {:?}",
                wallet_handle, blob_storage_reader_handle, rev_reg_id, secret!(cred_revoc_id));
 
-        let cred_revoc_id = parse_cred_rev_id(cred_revoc_id)?;
+        let rev_reg_delta_json = self._revoke_credentials(wallet_handle, blob_storage_reader_handle, rev_reg_id, &[cred_revoc_id.to_string()])?;
+
+        debug!("revoke_credential <<< rev_reg_delta_json: {:?}", rev_reg_delta_json);
+
+        Ok(rev_reg_delta_json)
+    }
+
+    fn revoke_credentials(&self,
+                          wallet_handle: WalletHandle,
+                          blob_storage_reader_handle: i32,
+                          rev_reg_id: &RevocationRegistryId,
+                          cred_revoc_ids: &CredentialRevocationIds) -> IndyResult<String> {
+        debug!("revoke_credentials >>> wallet_handle: {:?}, blob_storage_reader_handle:  {:?}, rev_reg_id: {:?}, cred_revoc_ids: {:?}",
+               wallet_handle, blob_storage_reader_handle, rev_reg_id, secret!(cred_revoc_ids));
+
+        let rev_reg_delta_json = self._revoke_credentials(wallet_handle, blob_storage_reader_handle, rev_reg_id, &cred_revoc_ids.0)?;
+
+        debug!("revoke_credentials <<< rev_reg_delta_json: {:?}", rev_reg_delta_json);
 
+        Ok(rev_reg_delta_json)
+    }
+
+    fn _revoke_credentials(&self,
+                           wallet_handle: WalletHandle,
+                           blob_storage_reader_handle: i32,
+                           rev_reg_id: &RevocationRegistryId,
+                           cred_revoc_ids: &[String]) -> IndyResult<String> {
         let revocation_registry_definition: RevocationRegistryDefinitionV1 =
             RevocationRegistryDefinitionV1::from(
                 self._wallet_get_rev_reg_def(wallet_handle, &rev_reg_id)?);
@@ -818,27 +1247,44 @@ impl IssuerCommandExecutor {
                                                        blob_storage_reader_handle,
                                                        &revocation_registry_definition)?;
 
-        if cred_revoc_id > revocation_registry_definition.value.max_cred_num + 1 {
-            return Err(err_msg(IndyErrorKind::InvalidUserRevocId, format!("Revocation id: {:?} not found in RevocationRegistry", cred_revoc_id)));
-        }
-
         let mut rev_reg_info = self._wallet_get_rev_reg_info(wallet_handle, &rev_reg_id)?;
 
-        match revocation_registry_definition.value.issuance_type {
-            IssuanceType::ISSUANCE_ON_DEMAND => {
-                if !rev_reg_info.used_ids.remove(&cred_revoc_id) {
-                    return Err(err_msg(IndyErrorKind::InvalidUserRevocId, format!("Revocation id: {:?} not found in RevocationRegistry", cred_revoc_id)));
-                };
+        let mut merged_rev_reg_delta: Option<CryptoRevocationRegistryDelta> = None;
+
+        for cred_revoc_id in cred_revoc_ids {
+            let cred_revoc_id = parse_cred_rev_id(cred_revoc_id)?;
+
+            if cred_revoc_id > revocation_registry_definition.value.max_cred_num + 1 {
+                return Err(err_msg(IndyErrorKind::InvalidUserRevocId, format!("Revocation id: {:?} not found in RevocationRegistry", cred_revoc_id)));
             }
-            IssuanceType::ISSUANCE_BY_DEFAULT => {
-                if !rev_reg_info.used_ids.insert(cred_revoc_id) {
-                    return Err(err_msg(IndyErrorKind::InvalidUserRevocId, format!("Revocation id: {:?} not found in RevocationRegistry", cred_revoc_id)));
+
+            match revocation_registry_definition.value.issuance_type {
+                IssuanceType::ISSUANCE_ON_DEMAND => {
+                    if !rev_reg_info.used_ids.remove(&cred_revoc_id) {
+                        return Err(err_msg(IndyErrorKind::InvalidUserRevocId, format!("Revocation id: {:?} not found in RevocationRegistry", cred_revoc_id)));
+                    };
                 }
-            }
-        };
+                IssuanceType::ISSUANCE_BY_DEFAULT => {
+                    if !rev_reg_info.used_ids.insert(cred_revoc_id) {
+                        return Err(err_msg(IndyErrorKind::InvalidUserRevocId, format!("Revocation id: {:?} not found in RevocationRegistry", cred_revoc_id)));
+                    }
+                }
+            };
+
+            let rev_reg_delta =
+                self.anoncreds_service.issuer.revoke(&mut rev_reg.value, revocation_registry_definition.value.max_cred_num, cred_revoc_id, &sdk_tails_accessor)?;
+
+            merged_rev_reg_delta = Some(match merged_rev_reg_delta {
+                Some(mut merged) => {
+                    merged.merge(&rev_reg_delta)?;
+                    merged
+                }
+                None => rev_reg_delta
+            });
+        }
 
-        let rev_reg_delta =
-            self.anoncreds_service.issuer.revoke(&mut rev_reg.value, revocation_registry_definition.value.max_cred_num, cred_revoc_id, &sdk_tails_accessor)?;
+        let rev_reg_delta = merged_rev_reg_delta
+            .ok_or_else(|| err_msg(IndyErrorKind::InvalidStructure, "No revocation ids have been passed"))?;
 
         let rev_reg_delta = RevocationRegistryDelta::RevocationRegistryDeltaV1(RevocationRegistryDeltaV1 { value: rev_reg_delta });
 
@@ -847,10 +1293,8 @@ impl IssuerCommandExecutor {
 
         let rev_reg = RevocationRegistry::RevocationRegistryV1(rev_reg);
 
-        self.wallet_service.update_indy_object(wallet_handle, &rev_reg_id.0, &rev_reg)?;
-        self.wallet_service.update_indy_object(wallet_handle, &rev_reg_id.0, &rev_reg_info)?;
-
-        debug!("revoke_credential <<< rev_reg_delta_json: {:?}", rev_reg_delta_json);
+        // the registry is stored only once all credentials are revoked, so a failed batch leaves no trace
+        self._wallet_update_rev_reg(wallet_handle, rev_reg_id, &rev_reg, &rev_reg_info)?;
 
         Ok(rev_reg_delta_json)
     }
@@ -906,14 +1350,131 @@ impl IssuerCommandExecutor {
 
         let rev_reg = RevocationRegistry::RevocationRegistryV1(rev_reg);
 
-        self.wallet_service.update_indy_object(wallet_handle, &rev_reg_id.0, &rev_reg)?;
-        self.wallet_service.update_indy_object(wallet_handle, &rev_reg_id.0, &rev_reg_info)?;
+        self._wallet_update_rev_reg(wallet_handle, rev_reg_id, &rev_reg, &rev_reg_info)?;
 
         debug!("recovery_credential <<< rev_reg_delta_json: {:?}", rev_reg_delta_json);
 
         Ok(rev_reg_delta_json)
     }
 
+    fn get_revocation_registry_status(&self,
+                                      wallet_handle: WalletHandle,
+                                      rev_reg_id: &RevocationRegistryId) -> IndyResult<String> {
+        debug!("get_revocation_registry_status >>> wallet_handle: {:?}, rev_reg_id: {:?}", wallet_handle, rev_reg_id);
+
+        let rev_reg_def = RevocationRegistryDefinitionV1::from(self._wallet_get_rev_reg_def(wallet_handle, rev_reg_id)?);
+        let rev_reg_info = self._wallet_get_rev_reg_info(wallet_handle, rev_reg_id)?;
+
+        let max_cred_num = rev_reg_def.value.max_cred_num;
+
+        // `used_ids` holds issued indices for ISSUANCE_ON_DEMAND and revoked indices for ISSUANCE_BY_DEFAULT
+        let mut revoked_ids: Vec<u32> = match rev_reg_def.value.issuance_type {
+            IssuanceType::ISSUANCE_ON_DEMAND => (1..=rev_reg_info.curr_id).filter(|id| !rev_reg_info.used_ids.contains(id)).collect(),
+            IssuanceType::ISSUANCE_BY_DEFAULT => rev_reg_info.used_ids.iter().cloned().collect()
+        };
+        revoked_ids.sort();
+
+        let status = RevocationRegistryStatus {
+            rev_reg_id: rev_reg_id.clone(),
+            issuance_type: rev_reg_def.value.issuance_type,
+            max_cred_num,
+            next_index: if rev_reg_info.curr_id < max_cred_num { Some(rev_reg_info.curr_id + 1) } else { None },
+            issued: rev_reg_info.curr_id,
+            revoked: revoked_ids.len() as u32,
+            revoked_ids,
+        };
+
+        let status_json = serde_json::to_string(&status)
+            .to_indy(IndyErrorKind::InvalidState, "Cannot serialize RevocationRegistryStatus")?;
+
+        debug!("get_revocation_registry_status <<< status_json: {:?}", status_json);
+
+        Ok(status_json)
+    }
+
+    fn check_revocation_registry_drift(&self,
+                                       pool_handle: PoolHandle,
+                                       wallet_handle: WalletHandle,
+                                       submitter_did: Option<DidValue>,
+                                       rev_reg_id: RevocationRegistryId,
+                                       cb: Box<dyn Fn(IndyResult<String>) + Send>) {
+        debug!("check_revocation_registry_drift >>> pool_handle: {:?}, wallet_handle: {:?}, submitter_did: {:?}, rev_reg_id: {:?}",
+               pool_handle, wallet_handle, submitter_did, rev_reg_id);
+
+        // fail fast for registries the issuer doesn't own
+        try_cb!(self._wallet_get_rev_reg(wallet_handle, &rev_reg_id), cb);
+
+        let cb_id = next_command_handle();
+        self.pending_str_callbacks.borrow_mut().insert(cb_id, cb);
+
+        CommandExecutor::instance().send(
+            Command::Ledger(
+                LedgerCommand::GetRevocReg(
+                    pool_handle,
+                    submitter_did,
+                    rev_reg_id.clone(),
+                    time::get_time().sec,
+                    Box::new(move |ledger_rev_reg| {
+                        CommandExecutor::instance().send(
+                            Command::Anoncreds(
+                                AnoncredsCommand::Issuer(
+                                    IssuerCommand::CheckRevocationRegistryDriftContinue(
+                                        wallet_handle,
+                                        rev_reg_id.clone(),
+                                        ledger_rev_reg,
+                                        cb_id,
+                                    ))
+                            )).unwrap();
+                    }),
+                )
+            )
+        ).unwrap();
+    }
+
+    fn _check_revocation_registry_drift_continue(&self,
+                                                 wallet_handle: WalletHandle,
+                                                 rev_reg_id: &RevocationRegistryId,
+                                                 ledger_rev_reg: IndyResult<(String, String, u64)>,
+                                                 cb_id: CommandHandle) {
+        let cb = self.pending_str_callbacks.borrow_mut().remove(&cb_id).expect("FIXME INVALID STATE");
+
+        cb(ledger_rev_reg.and_then(|(_, ledger_rev_reg_json, ledger_timestamp)|
+            self._compare_revocation_registry_accums(wallet_handle, rev_reg_id, &ledger_rev_reg_json, ledger_timestamp)));
+    }
+
+    fn _compare_revocation_registry_accums(&self,
+                                           wallet_handle: WalletHandle,
+                                           rev_reg_id: &RevocationRegistryId,
+                                           ledger_rev_reg_json: &str,
+                                           ledger_timestamp: u64) -> IndyResult<String> {
+        let local_rev_reg = RevocationRegistryV1::from(self._wallet_get_rev_reg(wallet_handle, rev_reg_id)?);
+
+        let ledger_rev_reg: RevocationRegistryV1 = serde_json::from_str::<RevocationRegistry>(ledger_rev_reg_json)
+            .map(RevocationRegistryV1::from)
+            .to_indy(IndyErrorKind::InvalidStructure, "Cannot deserialize ledger RevocationRegistry")?;
+
+        let local_accum = serde_json::to_value(&local_rev_reg.value)
+            .to_indy(IndyErrorKind::InvalidState, "Cannot serialize RevocationRegistry")?["accum"].clone();
+
+        let ledger_accum = serde_json::to_value(&ledger_rev_reg.value)
+            .to_indy(IndyErrorKind::InvalidState, "Cannot serialize RevocationRegistry")?["accum"].clone();
+
+        let drift = RevocationRegistryDrift {
+            rev_reg_id: rev_reg_id.clone(),
+            in_sync: local_accum == ledger_accum,
+            local_accum,
+            ledger_accum,
+            ledger_timestamp,
+        };
+
+        let drift_json = serde_json::to_string(&drift)
+            .to_indy(IndyErrorKind::InvalidState, "Cannot serialize RevocationRegistryDrift")?;
+
+        debug!("check_revocation_registry_drift <<< drift_json: {:?}", drift_json);
+
+        Ok(drift_json)
+    }
+
     fn merge_revocation_registry_deltas(&self,
                                         rev_reg_delta: &mut RevocationRegistryDeltaV1,
                                         other_rev_reg_delta: &RevocationRegistryDeltaV1) -> IndyResult<String> {
@@ -12,7 +12,11 @@ use crate::services::blob_storage::BlobStorageService;
 use crate::services::pool::PoolService;
 use indy_wallet::WalletService;
 use crate::services::crypto::CryptoService;
-use crate::services::anoncreds::helpers::to_unqualified;
+use crate::services::anoncreds::helpers::{encode_credential_values, encode_date, to_unqualified};
+use crate::services::anoncreds::validation::{validate_object, ValidationContext};
+use crate::domain::anoncreds::credential::{CredentialValues, ShortCredentialValues};
+use crate::domain::anoncreds::credential_definition::CredentialDefinition;
+use crate::domain::anoncreds::schema::Schema;
 
 use indy_api_types::errors::prelude::*;
 
@@ -24,6 +28,18 @@ pub enum AnoncredsCommand {
     Verifier(VerifierCommand),
     ToUnqualified(
         String, // entity
+        Box<dyn Fn(IndyResult<String>) + Send>),
+    EncodeCredentialValues(
+        ShortCredentialValues, // raw values
+        Box<dyn Fn(IndyResult<String>) + Send>),
+    Validate(
+        String, // object type
+        String, // object json
+        Option<Schema>, // schema
+        Option<CredentialDefinition>, // credential definition
+        Box<dyn Fn(IndyResult<String>) + Send>),
+    EncodeDate(
+        String, // date
         Box<dyn Fn(IndyResult<String>) + Send>)
 }
 
@@ -68,6 +84,46 @@ impl AnoncredsCommandExecutor {
                 debug!("ToUnqualified command received");
                 cb(to_unqualified(&entity));
             }
+            AnoncredsCommand::EncodeCredentialValues(raw_values, cb) => {
+                debug!("EncodeCredentialValues command received");
+                cb(self.encode_credential_values(&raw_values));
+            }
+            AnoncredsCommand::Validate(object_type, object_json, schema, cred_def, cb) => {
+                debug!("Validate command received");
+                cb(self.validate(&object_type, &object_json, schema.as_ref(), cred_def.as_ref()));
+            }
+            AnoncredsCommand::EncodeDate(date, cb) => {
+                debug!("EncodeDate command received");
+                cb(encode_date(&date).map(|encoded_date| encoded_date.to_string()));
+            }
         };
     }
+
+    fn encode_credential_values(&self, raw_values: &ShortCredentialValues) -> IndyResult<String> {
+        debug!("encode_credential_values >>> raw_values: {:?}", secret!(raw_values));
+
+        let cred_values = CredentialValues(encode_credential_values(raw_values)?);
+
+        let res = serde_json::to_string(&cred_values)
+            .to_indy(IndyErrorKind::InvalidState, "Cannot serialize CredentialValues")?;
+
+        debug!("encode_credential_values <<< res: {:?}", secret!(&res));
+
+        Ok(res)
+    }
+
+    fn validate(&self, object_type: &str, object_json: &str, schema: Option<&Schema>, cred_def: Option<&CredentialDefinition>) -> IndyResult<String> {
+        debug!("validate >>> object_type: {:?}, object_json: {:?}, schema: {:?}, cred_def: {:?}", object_type, secret!(object_json), schema, cred_def);
+
+        let context = ValidationContext::new(schema, cred_def);
+
+        let problems = validate_object(object_type, object_json, &context)?;
+
+        let res = serde_json::to_string(&problems)
+            .to_indy(IndyErrorKind::InvalidState, "Cannot serialize validation problems")?;
+
+        debug!("validate <<< res: {:?}", res);
+
+        Ok(res)
+    }
 }
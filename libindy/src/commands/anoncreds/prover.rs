@@ -11,6 +11,7 @@ use crate::domain::anoncreds::credential::{Credential, CredentialInfo};
 use crate::domain::anoncreds::credential_definition::{cred_defs_map_to_cred_defs_v1_map, CredentialDefinition, CredentialDefinitionV1, CredentialDefinitionId, CredentialDefinitions};
 use crate::domain::anoncreds::credential_for_proof_request::{CredentialsForProofRequest, RequestedCredential};
 use crate::domain::anoncreds::credential_offer::CredentialOffer;
+use crate::domain::anoncreds::credential_selection::{CandidateCredential, CredentialSelectionPolicy};
 use crate::domain::anoncreds::credential_request::{CredentialRequest, CredentialRequestMetadata};
 use crate::domain::anoncreds::master_secret::MasterSecret;
 use crate::domain::anoncreds::proof_request::{NonRevocedInterval, PredicateInfo, ProofRequest, ProofRequestExtraQuery};
@@ -23,6 +24,7 @@ use crate::domain::crypto::did::DidValue;
 use indy_api_types::errors::prelude::*;
 use crate::services::anoncreds::AnoncredsService;
 use crate::services::anoncreds::helpers::{parse_cred_rev_id, get_non_revoc_interval};
+use crate::services::anoncreds::prover::CREDENTIAL_STORED_AT_TAG;
 use crate::services::blob_storage::BlobStorageService;
 use crate::services::crypto::CryptoService;
 use indy_wallet::{RecordOptions, SearchOptions, WalletRecord, WalletSearch, WalletService};
@@ -103,6 +105,11 @@ pub enum ProverCommand {
     CloseCredentialsSearchForProofReq(
         SearchHandle,
         Box<dyn Fn(IndyResult<()>) + Send>),
+    AutoSelectCredentials(
+        WalletHandle,
+        ProofRequest, // proof request
+        CredentialSelectionPolicy, // policy
+        Box<dyn Fn(IndyResult<String>) + Send>),
     CreateProof(
         WalletHandle,
         ProofRequest, // proof request
@@ -238,6 +245,10 @@ impl ProverCommandExecutor {
                 debug!(target: "prover_command_executor", "CloseCredentialsSearchForProofReq command received");
                 cb(self.close_credentials_search_for_proof_req(search_handle));
             }
+            ProverCommand::AutoSelectCredentials(wallet_handle, proof_req, policy, cb) => {
+                debug!(target: "prover_command_executor", "AutoSelectCredentials command received");
+                cb(self.auto_select_credentials(wallet_handle, &proof_req, policy));
+            }
             ProverCommand::CreateProof(wallet_handle, proof_req, requested_credentials, master_secret_name,
                                        schemas, cred_defs, rev_states, cb) => {
                 debug!(target: "prover_command_executor", "CreateProof command received");
@@ -409,7 +420,8 @@ impl ProverCommandExecutor {
             None
         };
 
-        let cred_tags = self.anoncreds_service.prover.build_credential_tags(&credential, catpol.as_ref())?;
+        let mut cred_tags = self.anoncreds_service.prover.build_credential_tags(&credential, catpol.as_ref())?;
+        cred_tags.insert(CREDENTIAL_STORED_AT_TAG.to_string(), time::get_time().sec.to_string());
         self.wallet_service.add_indy_object(wallet_handle, &out_cred_id, credential, &cred_tags)?;
 
         debug!("store_credential <<< out_cred_id: {:?}", out_cred_id);
@@ -657,6 +669,71 @@ impl ProverCommandExecutor {
         Ok(())
     }
 
+    fn auto_select_credentials(&self,
+                               wallet_handle: WalletHandle,
+                               proof_request: &ProofRequest,
+                               policy: CredentialSelectionPolicy) -> IndyResult<String> {
+        debug!("auto_select_credentials >>> wallet_handle: {:?}, proof_request: {:?}, policy: {:?}", wallet_handle, proof_request, policy);
+
+        let proof_req = proof_request.value();
+        let proof_req_version = proof_request.version();
+
+        if let Some(referent) = policy.self_attested_attributes.keys().find(|referent| !proof_req.requested_attributes.contains_key(*referent)) {
+            return Err(err_msg(IndyErrorKind::InvalidStructure, format!("Self attested attribute {} not found in proof request", referent)));
+        }
+
+        let mut stored_at_cache: HashMap<String, u64> = HashMap::new();
+
+        let mut attrs: HashMap<String, Vec<CandidateCredential>> = HashMap::new();
+
+        for (attr_id, requested_attr) in proof_req.requested_attributes.iter() {
+            if policy.self_attested_attributes.contains_key(attr_id) {
+                continue;
+            }
+
+            let query = self.anoncreds_service.prover.process_proof_request_restrictions(&proof_req_version,
+                                                                                         &requested_attr.name,
+                                                                                         &requested_attr.names,
+                                                                                         &attr_id,
+                                                                                         &requested_attr.restrictions,
+                                                                                         &None)?;
+            let interval = get_non_revoc_interval(&proof_req.non_revoked, &requested_attr.non_revoked);
+
+            let credentials = self._query_requested_credentials(wallet_handle, &query, None, &interval)?;
+
+            attrs.insert(attr_id.to_string(), self._build_candidate_credentials(wallet_handle, credentials, &mut stored_at_cache)?);
+        }
+
+        let mut predicates: HashMap<String, Vec<CandidateCredential>> = HashMap::new();
+
+        for (predicate_id, requested_predicate) in proof_req.requested_predicates.iter() {
+            let query = self.anoncreds_service.prover.process_proof_request_restrictions(&proof_req_version,
+                                                                                         &Some(requested_predicate.name.clone()),
+                                                                                         &None,
+                                                                                         &predicate_id,
+                                                                                         &requested_predicate.restrictions,
+                                                                                         &None)?;
+
+            let interval = get_non_revoc_interval(&proof_req.non_revoked, &requested_predicate.non_revoked);
+
+            let credentials = self._query_requested_credentials(wallet_handle, &query, Some(&requested_predicate), &interval)?;
+
+            predicates.insert(predicate_id.to_string(), self._build_candidate_credentials(wallet_handle, credentials, &mut stored_at_cache)?);
+        }
+
+        let selection = self.anoncreds_service.prover.select_credentials(&attrs,
+                                                                         &predicates,
+                                                                         policy.self_attested_attributes,
+                                                                         policy.strategy);
+
+        let selection_json = serde_json::to_string(&selection)
+            .to_indy(IndyErrorKind::InvalidState, "Cannot serialize CredentialSelection")?;
+
+        debug!("auto_select_credentials <<< selection_json: {:?}", selection_json);
+
+        Ok(selection_json)
+    }
+
     fn delete_credential(&self,
                          wallet_handle: WalletHandle,
                          cred_id: &str) -> IndyResult<()> {
@@ -878,6 +955,52 @@ impl ProverCommandExecutor {
     }
 
 
+    fn _build_candidate_credentials(&self,
+                                    wallet_handle: WalletHandle,
+                                    credentials: Vec<RequestedCredential>,
+                                    stored_at_cache: &mut HashMap<String, u64>) -> IndyResult<Vec<CandidateCredential>> {
+        credentials
+            .into_iter()
+            .map(|credential| {
+                let cred_id = credential.cred_info.referent;
+
+                let stored_at = match stored_at_cache.get(&cred_id) {
+                    Some(stored_at) => *stored_at,
+                    None => {
+                        let stored_at = self._get_credential_stored_at(wallet_handle, &cred_id)?;
+                        stored_at_cache.insert(cred_id.clone(), stored_at);
+                        stored_at
+                    }
+                };
+
+                let revocable = credential.cred_info.rev_reg_id.is_some();
+
+                // Revocation state is expected to be built for the end of requested interval
+                let timestamp = match credential.interval {
+                    Some(ref interval) if revocable => Some(interval.to.unwrap_or_else(|| time::get_time().sec as u64)),
+                    _ => None
+                };
+
+                Ok(CandidateCredential { cred_id, revocable, stored_at, timestamp })
+            })
+            .collect()
+    }
+
+    fn _get_credential_stored_at(&self, wallet_handle: WalletHandle, cred_id: &str) -> IndyResult<u64> {
+        let options_json = json!({
+            "retrieveValue": false,
+            "retrieveTags": true,
+        }).to_string();
+
+        let record = self.wallet_service.get_indy_record::<Credential>(wallet_handle, cred_id, &options_json)?;
+
+        // Credentials stored before the tag was introduced are considered the oldest
+        Ok(record.get_tags()
+            .and_then(|tags| tags.get(CREDENTIAL_STORED_AT_TAG))
+            .and_then(|stored_at| stored_at.parse().ok())
+            .unwrap_or(0))
+    }
+
     fn _wallet_get_master_secret(&self, wallet_handle: WalletHandle, key: &str) -> IndyResult<MasterSecret> {
         self.wallet_service.get_indy_object(wallet_handle, &key, &RecordOptions::id_value())
     }
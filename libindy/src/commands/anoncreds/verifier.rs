@@ -1,13 +1,22 @@
-use std::collections::HashMap;
+use std::cell::RefCell;
+use std::collections::{HashMap, HashSet};
 use std::rc::Rc;
 
-use crate::domain::anoncreds::credential_definition::{cred_defs_map_to_cred_defs_v1_map, CredentialDefinitionV1, CredentialDefinitionId, CredentialDefinitions};
+use crate::commands::{Command, CommandExecutor};
+use crate::commands::anoncreds::AnoncredsCommand;
+use crate::commands::cache::CacheCommand;
+use crate::commands::ledger::LedgerCommand;
+use crate::domain::anoncreds::credential_definition::{cred_defs_map_to_cred_defs_v1_map, CredentialDefinition, CredentialDefinitionV1, CredentialDefinitionId, CredentialDefinitions};
 use crate::domain::anoncreds::proof::Proof;
 use crate::domain::anoncreds::proof_request::{ProofRequest, ProofRequestPayload};
-use crate::domain::anoncreds::revocation_registry::{rev_regs_map_to_rev_regs_local_map, RevocationRegistryV1, RevocationRegistries};
-use crate::domain::anoncreds::revocation_registry_definition::{rev_reg_defs_map_to_rev_reg_defs_v1_map, RevocationRegistryDefinitionV1, RevocationRegistryId, RevocationRegistryDefinitions};
-use crate::domain::anoncreds::schema::{schemas_map_to_schemas_v1_map, SchemaV1, SchemaId, Schemas};
+use crate::domain::anoncreds::revocation_registry::{rev_regs_map_to_rev_regs_local_map, RevocationRegistry, RevocationRegistryV1, RevocationRegistries};
+use crate::domain::anoncreds::revocation_registry_definition::{rev_reg_defs_map_to_rev_reg_defs_v1_map, RevocationRegistryDefinition, RevocationRegistryDefinitionV1, RevocationRegistryId, RevocationRegistryDefinitions};
+use crate::domain::anoncreds::schema::{schemas_map_to_schemas_v1_map, Schema, SchemaV1, SchemaId, Schemas};
+use crate::domain::cache::GetCacheOptions;
+use crate::domain::crypto::did::DidValue;
+use indy_api_types::{CommandHandle, PoolHandle, WalletHandle};
 use indy_api_types::errors::prelude::*;
+use indy_utils::next_command_handle;
 use crate::services::anoncreds::AnoncredsService;
 
 pub enum VerifierCommand {
@@ -19,18 +28,86 @@ pub enum VerifierCommand {
         RevocationRegistryDefinitions, // rev reg defs
         RevocationRegistries, // rev reg entries
         Box<dyn Fn(IndyResult<bool>) + Send>),
+    VerifyProofWithReport(
+        ProofRequest, // proof request
+        Proof, // proof
+        Schemas, // credential schemas
+        CredentialDefinitions, // credential defs
+        RevocationRegistryDefinitions, // rev reg defs
+        RevocationRegistries, // rev reg entries
+        Box<dyn Fn(IndyResult<String>) + Send>),
+    VerifyProofWithLedger(
+        PoolHandle,
+        WalletHandle,
+        DidValue, // submitter did
+        ProofRequest, // proof request
+        Proof, // proof
+        GetCacheOptions, // options
+        Box<dyn Fn(IndyResult<String>) + Send>),
+    VerifyProofWithLedgerContinue(
+        CommandHandle, // cb_id
+        IndyResult<LedgerObject>),
     GenerateNonce(
         Box<dyn Fn(IndyResult<String>) + Send>)
 }
 
+pub enum LedgerObject {
+    Schema(SchemaId, String),
+    CredentialDefinition(CredentialDefinitionId, String),
+    RevocationRegistryDefinition(RevocationRegistryId, String),
+    RevocationRegistry(RevocationRegistryId, u64, String),
+}
+
+struct PendingLedgerVerification {
+    proof_request: ProofRequest,
+    proof: Proof,
+    schemas: Schemas,
+    cred_defs: CredentialDefinitions,
+    rev_reg_defs: RevocationRegistryDefinitions,
+    rev_regs: RevocationRegistries,
+    remaining: usize,
+    cb: Box<dyn Fn(IndyResult<String>) + Send>,
+}
+
+impl PendingLedgerVerification {
+    fn add(&mut self, object: LedgerObject) -> IndyResult<()> {
+        match object {
+            LedgerObject::Schema(id, json) => {
+                let schema: Schema = serde_json::from_str(&json)
+                    .to_indy(IndyErrorKind::InvalidStructure, "Cannot deserialize Schema received from ledger")?;
+                self.schemas.insert(id, schema);
+            }
+            LedgerObject::CredentialDefinition(id, json) => {
+                let cred_def: CredentialDefinition = serde_json::from_str(&json)
+                    .to_indy(IndyErrorKind::InvalidStructure, "Cannot deserialize CredentialDefinition received from ledger")?;
+                self.cred_defs.insert(id, cred_def);
+            }
+            LedgerObject::RevocationRegistryDefinition(id, json) => {
+                let rev_reg_def: RevocationRegistryDefinition = serde_json::from_str(&json)
+                    .to_indy(IndyErrorKind::InvalidStructure, "Cannot deserialize RevocationRegistryDefinition received from ledger")?;
+                self.rev_reg_defs.insert(id, rev_reg_def);
+            }
+            LedgerObject::RevocationRegistry(id, timestamp, json) => {
+                let rev_reg: RevocationRegistry = serde_json::from_str(&json)
+                    .to_indy(IndyErrorKind::InvalidStructure, "Cannot deserialize RevocationRegistry received from ledger")?;
+                self.rev_regs.entry(id).or_insert_with(HashMap::new).insert(timestamp, rev_reg);
+            }
+        }
+
+        Ok(())
+    }
+}
+
 pub struct VerifierCommandExecutor {
     anoncreds_service: Rc<AnoncredsService>,
+    pending_ledger_verifications: RefCell<HashMap<CommandHandle, PendingLedgerVerification>>,
 }
 
 impl VerifierCommandExecutor {
     pub fn new(anoncreds_service: Rc<AnoncredsService>) -> VerifierCommandExecutor {
         VerifierCommandExecutor {
             anoncreds_service,
+            pending_ledger_verifications: RefCell::new(HashMap::new()),
         }
     }
 
@@ -44,6 +121,22 @@ impl VerifierCommandExecutor {
                                      &rev_reg_defs_map_to_rev_reg_defs_v1_map(rev_reg_defs),
                                      &rev_regs_map_to_rev_regs_local_map(rev_regs)));
             }
+            VerifierCommand::VerifyProofWithReport(proof_request, proof, schemas, credential_defs, rev_reg_defs, rev_regs, cb) => {
+                debug!(target: "verifier_command_executor", "VerifyProofWithReport command received");
+                cb(self.verify_proof_with_report(&proof_request.value(), proof,
+                                                 &schemas_map_to_schemas_v1_map(schemas),
+                                                 &cred_defs_map_to_cred_defs_v1_map(credential_defs),
+                                                 &rev_reg_defs_map_to_rev_reg_defs_v1_map(rev_reg_defs),
+                                                 &rev_regs_map_to_rev_regs_local_map(rev_regs)));
+            }
+            VerifierCommand::VerifyProofWithLedger(pool_handle, wallet_handle, submitter_did, proof_request, proof, options, cb) => {
+                debug!(target: "verifier_command_executor", "VerifyProofWithLedger command received");
+                self.verify_proof_with_ledger(pool_handle, wallet_handle, submitter_did, proof_request, proof, options, cb);
+            }
+            VerifierCommand::VerifyProofWithLedgerContinue(cb_id, ledger_object) => {
+                debug!(target: "verifier_command_executor", "VerifyProofWithLedgerContinue command received");
+                self._verify_proof_with_ledger_continue(cb_id, ledger_object);
+            }
             VerifierCommand::GenerateNonce(cb) => {
                 debug!(target: "verifier_command_executor", "GenerateNonce command received");
                 cb(self.generate_nonce());
@@ -74,6 +167,184 @@ impl VerifierCommandExecutor {
         Ok(result)
     }
 
+    fn verify_proof_with_report(&self,
+                                proof_req: &ProofRequestPayload,
+                                proof: Proof,
+                                schemas: &HashMap<SchemaId, SchemaV1>,
+                                cred_defs: &HashMap<CredentialDefinitionId, CredentialDefinitionV1>,
+                                rev_reg_defs: &HashMap<RevocationRegistryId, RevocationRegistryDefinitionV1>,
+                                rev_regs: &HashMap<RevocationRegistryId, HashMap<u64, RevocationRegistryV1>>) -> IndyResult<String> {
+        debug!("verify_proof_with_report >>> proof_req: {:?}, proof: {:?}, schemas: {:?}, cred_defs: {:?},  \
+               rev_reg_defs: {:?}, rev_regs: {:?}",
+               proof_req, proof, schemas, cred_defs, rev_reg_defs, rev_regs);
+
+        let report = self.anoncreds_service.verifier.verify_with_report(&proof,
+                                                                        &proof_req,
+                                                                        schemas,
+                                                                        cred_defs,
+                                                                        rev_reg_defs,
+                                                                        rev_regs)?;
+
+        let res = serde_json::to_string(&report)
+            .to_indy(IndyErrorKind::InvalidState, "Cannot serialize ProofVerificationReport")?;
+
+        debug!("verify_proof_with_report <<< res: {:?}", res);
+
+        Ok(res)
+    }
+
+    fn verify_proof_with_ledger(&self,
+                                pool_handle: PoolHandle,
+                                wallet_handle: WalletHandle,
+                                submitter_did: DidValue,
+                                proof_request: ProofRequest,
+                                proof: Proof,
+                                options: GetCacheOptions,
+                                cb: Box<dyn Fn(IndyResult<String>) + Send>) {
+        debug!("verify_proof_with_ledger >>> pool_handle: {:?}, wallet_handle: {:?}, submitter_did: {:?}, proof_request: {:?}, proof: {:?}, options: {:?}",
+               pool_handle, wallet_handle, submitter_did, proof_request, proof, options);
+
+        let schema_ids: HashSet<SchemaId> = proof.identifiers.iter().map(|identifier| identifier.schema_id.clone()).collect();
+        let cred_def_ids: HashSet<CredentialDefinitionId> = proof.identifiers.iter().map(|identifier| identifier.cred_def_id.clone()).collect();
+        let rev_reg_def_ids: HashSet<RevocationRegistryId> = proof.identifiers.iter().filter_map(|identifier| identifier.rev_reg_id.clone()).collect();
+        let rev_reg_ids: HashSet<(RevocationRegistryId, u64)> = proof.identifiers.iter()
+            .filter_map(|identifier| match (identifier.rev_reg_id.as_ref(), identifier.timestamp) {
+                (Some(rev_reg_id), Some(timestamp)) => Some((rev_reg_id.clone(), timestamp)),
+                _ => None
+            })
+            .collect();
+
+        let remaining = schema_ids.len() + cred_def_ids.len() + rev_reg_def_ids.len() + rev_reg_ids.len();
+
+        let cb_id = next_command_handle();
+        self.pending_ledger_verifications.borrow_mut().insert(cb_id, PendingLedgerVerification {
+            proof_request,
+            proof,
+            schemas: HashMap::new(),
+            cred_defs: HashMap::new(),
+            rev_reg_defs: HashMap::new(),
+            rev_regs: HashMap::new(),
+            remaining,
+            cb,
+        });
+
+        if remaining == 0 {
+            return self._verify_proof_with_ledger_complete(cb_id, Ok(()));
+        }
+
+        for id in schema_ids {
+            CommandExecutor::instance().send(
+                Command::Cache(
+                    CacheCommand::GetSchema(
+                        pool_handle,
+                        wallet_handle,
+                        submitter_did.clone(),
+                        id.clone(),
+                        options.clone(),
+                        Box::new(move |schema| {
+                            _send_ledger_object(cb_id, schema.map(|schema| LedgerObject::Schema(id.clone(), schema)))
+                        }),
+                    )
+                )
+            ).unwrap();
+        }
+
+        for id in cred_def_ids {
+            CommandExecutor::instance().send(
+                Command::Cache(
+                    CacheCommand::GetCredDef(
+                        pool_handle,
+                        wallet_handle,
+                        submitter_did.clone(),
+                        id.clone(),
+                        options.clone(),
+                        Box::new(move |cred_def| {
+                            _send_ledger_object(cb_id, cred_def.map(|cred_def| LedgerObject::CredentialDefinition(id.clone(), cred_def)))
+                        }),
+                    )
+                )
+            ).unwrap();
+        }
+
+        for id in rev_reg_def_ids {
+            CommandExecutor::instance().send(
+                Command::Cache(
+                    CacheCommand::GetRevocRegDef(
+                        pool_handle,
+                        wallet_handle,
+                        submitter_did.clone(),
+                        id.clone(),
+                        options.clone(),
+                        Box::new(move |rev_reg_def| {
+                            _send_ledger_object(cb_id, rev_reg_def.map(|rev_reg_def| LedgerObject::RevocationRegistryDefinition(id.clone(), rev_reg_def)))
+                        }),
+                    )
+                )
+            ).unwrap();
+        }
+
+        for (id, timestamp) in rev_reg_ids {
+            CommandExecutor::instance().send(
+                Command::Ledger(
+                    LedgerCommand::GetRevocReg(
+                        pool_handle,
+                        Some(submitter_did.clone()),
+                        id.clone(),
+                        timestamp as i64,
+                        Box::new(move |rev_reg| {
+                            // Proof refers to the registry state by the timestamp requested, not by the ledger txn time
+                            _send_ledger_object(cb_id, rev_reg.map(|(_, rev_reg, _)| LedgerObject::RevocationRegistry(id.clone(), timestamp, rev_reg)))
+                        }),
+                    )
+                )
+            ).unwrap();
+        }
+    }
+
+    fn _verify_proof_with_ledger_continue(&self, cb_id: CommandHandle, ledger_object: IndyResult<LedgerObject>) {
+        let res = {
+            let mut pending_ledger_verifications = self.pending_ledger_verifications.borrow_mut();
+
+            // Verification is already completed with an error of another ledger request
+            let pending = match pending_ledger_verifications.get_mut(&cb_id) {
+                Some(pending) => pending,
+                None => return
+            };
+
+            pending.remaining -= 1;
+
+            let res = match ledger_object {
+                Ok(ledger_object) => pending.add(ledger_object),
+                // Object missing on the ledger is reported as missing ledger data instead of failing the whole verification
+                Err(ref err) if err.kind() == IndyErrorKind::LedgerItemNotFound => Ok(()),
+                Err(err) => Err(err)
+            };
+
+            match res {
+                Ok(()) if pending.remaining > 0 => return,
+                res => res
+            }
+        };
+
+        self._verify_proof_with_ledger_complete(cb_id, res);
+    }
+
+    fn _verify_proof_with_ledger_complete(&self, cb_id: CommandHandle, res: IndyResult<()>) {
+        let pending = self.pending_ledger_verifications.borrow_mut().remove(&cb_id).expect("FIXME INVALID STATE");
+
+        let PendingLedgerVerification { proof_request, proof, schemas, cred_defs, rev_reg_defs, rev_regs, cb, .. } = pending;
+
+        let res = res.and_then(|_| self.verify_proof_with_report(&proof_request.value(), proof,
+                                                                 &schemas_map_to_schemas_v1_map(schemas),
+                                                                 &cred_defs_map_to_cred_defs_v1_map(cred_defs),
+                                                                 &rev_reg_defs_map_to_rev_reg_defs_v1_map(rev_reg_defs),
+                                                                 &rev_regs_map_to_rev_regs_local_map(rev_regs)));
+
+        debug!("verify_proof_with_ledger <<< res: {:?}", res);
+
+        cb(res)
+    }
+
     fn generate_nonce(&self) -> IndyResult<String> {
         debug!("generate_nonce >>> ");
 
@@ -87,3 +358,16 @@ impl VerifierCommandExecutor {
         Ok(result)
     }
 }
+
+fn _send_ledger_object(cb_id: CommandHandle, ledger_object: IndyResult<LedgerObject>) {
+    CommandExecutor::instance().send(
+        Command::Anoncreds(
+            AnoncredsCommand::Verifier(
+                VerifierCommand::VerifyProofWithLedgerContinue(
+                    cb_id,
+                    ledger_object,
+                )
+            )
+        )
+    ).unwrap();
+}
@@ -1,7 +1,13 @@
-use crate::services::blob_storage::BlobStorageService;
+use crate::domain::anoncreds::revocation_registry_definition::{RevocationRegistryDefinition, RevocationRegistryDefinitionV1};
+use crate::domain::anoncreds::tails::TailsReaderConfig;
+use crate::services::blob_storage::{BlobStorageService, ReaderType, WriterType};
+use std::path::PathBuf;
 use std::rc::Rc;
 
 use indy_api_types::errors::prelude::*;
+use crate::utils::environment;
+
+use rust_base58::{FromBase58, ToBase58};
 
 pub enum BlobStorageCommand {
     OpenReader(
@@ -12,6 +18,18 @@ pub enum BlobStorageCommand {
         String, // writer type
         String, // writer config JSON
         Box<dyn Fn(IndyResult<i32 /* handle */>) + Send>),
+    OpenTailsReader(
+        RevocationRegistryDefinition,
+        TailsReaderConfig,
+        Box<dyn Fn(IndyResult<i32 /* handle */>) + Send>),
+    RegisterReaderType(
+        String, // reader type
+        Box<dyn ReaderType>,
+        Box<dyn Fn(IndyResult<()>) + Send>),
+    RegisterWriterType(
+        String, // writer type
+        Box<dyn WriterType>,
+        Box<dyn Fn(IndyResult<()>) + Send>),
 }
 
 pub struct BlobStorageCommandExecutor {
@@ -35,6 +53,18 @@ impl BlobStorageCommandExecutor {
                 debug!("OpenWriter command received");
                 cb(self.open_writer(&writer_type, &writer_config));
             }
+            BlobStorageCommand::OpenTailsReader(rev_reg_def, config, cb) => {
+                debug!("OpenTailsReader command received");
+                cb(self.open_tails_reader(&RevocationRegistryDefinitionV1::from(rev_reg_def), &config));
+            }
+            BlobStorageCommand::RegisterReaderType(type_, reader_type, cb) => {
+                debug!("RegisterReaderType command received");
+                cb(self.register_reader_type(&type_, reader_type));
+            }
+            BlobStorageCommand::RegisterWriterType(type_, writer_type, cb) => {
+                debug!("RegisterWriterType command received");
+                cb(self.register_writer_type(&type_, writer_type));
+            }
         }
     }
 
@@ -57,4 +87,66 @@ impl BlobStorageCommandExecutor {
 
         res
     }
+
+    fn open_tails_reader(&self, rev_reg_def: &RevocationRegistryDefinitionV1, config: &TailsReaderConfig) -> IndyResult<i32> {
+        debug!("open_tails_reader >>> rev_reg_def: {:?}, config: {:?}", rev_reg_def, config);
+
+        let tails_hash = rev_reg_def.value.tails_hash.from_base58()
+            .map_err(|_| err_msg(IndyErrorKind::InvalidStructure, "Invalid base58 for Tails hash"))?;
+
+        let tails_location = &rev_reg_def.value.tails_location;
+
+        let tails_dir = config.tails_dir.as_ref()
+            .map(PathBuf::from)
+            .unwrap_or_else(|| environment::indy_home_path().join("tails"));
+
+        // tails files are content-addressed, so already stored file is reused for any registry with the same tails
+        let tails_path = tails_dir.join(tails_hash.to_base58());
+
+        if !self.blob_storage_service.is_blob_stored(&tails_path) {
+            let stored = match config.fetcher_type {
+                Some(ref fetcher_type) =>
+                    self.blob_storage_service.fetch_blob(Some(fetcher_type.as_str()), &config.fetcher_config(), tails_location, &tails_hash, &tails_path)?,
+                None if tails_location.starts_with("http://") => {
+                    // http reader caches the file in tails dir itself, so it isn't copied there once again
+                    let http_config = json!({ "cache_dir": tails_dir.to_string_lossy() }).to_string();
+                    self.blob_storage_service.fetch_blob(Some("http"), &http_config, tails_location, &tails_hash, &tails_path)?
+                }
+                None =>
+                    self.blob_storage_service.fetch_blob(None, "", tails_location, &tails_hash, &tails_path)?
+            };
+
+            if !stored {
+                return Err(err_msg(IndyErrorKind::InvalidStructure, format!("Tails file fetched from {} doesn't match Tails hash", tails_location)));
+            }
+        }
+
+        let reader_config = json!({ "base_dir": tails_dir.to_string_lossy() }).to_string();
+
+        let res = self.blob_storage_service.open_reader("default", &reader_config);
+
+        debug!("open_tails_reader << res: {:?}", res);
+
+        res
+    }
+
+    fn register_reader_type(&self, type_: &str, reader_type: Box<dyn ReaderType>) -> IndyResult<()> {
+        debug!("register_reader_type >>> type_: {:?}", type_);
+
+        let res = self.blob_storage_service.register_reader_type(type_, reader_type);
+
+        debug!("register_reader_type << res: {:?}", res);
+
+        res
+    }
+
+    fn register_writer_type(&self, type_: &str, writer_type: Box<dyn WriterType>) -> IndyResult<()> {
+        debug!("register_writer_type >>> type_: {:?}", type_);
+
+        let res = self.blob_storage_service.register_writer_type(type_, writer_type);
+
+        debug!("register_writer_type << res: {:?}", res);
+
+        res
+    }
 }
@@ -6,8 +6,9 @@ use std::time::{SystemTime, UNIX_EPOCH};
 use indy_api_types::domain::wallet::Tags;
 use crate::domain::anoncreds::schema::SchemaId;
 use crate::domain::anoncreds::credential_definition::CredentialDefinitionId;
+use crate::domain::anoncreds::revocation_registry_definition::RevocationRegistryId;
 use indy_api_types::errors::prelude::*;
-use indy_wallet::{WalletService, WalletRecord};
+use indy_wallet::{WalletService, WalletRecord, TTL_TAG};
 use indy_api_types::{WalletHandle, PoolHandle, CommandHandle};
 use crate::commands::{Command, CommandExecutor};
 use crate::commands::ledger::LedgerCommand;
@@ -18,6 +19,7 @@ use indy_utils::next_command_handle;
 
 const CRED_DEF_CACHE: &str = "cred_def_cache";
 const SCHEMA_CACHE: &str = "schema_cache";
+const REVOC_REG_DEF_CACHE: &str = "revoc_reg_def_cache";
 
 pub enum CacheCommand {
     GetSchema(PoolHandle,
@@ -44,6 +46,18 @@ pub enum CacheCommand {
         GetCacheOptions,              // options
         CommandHandle,                          // cb_id
     ),
+    GetRevocRegDef(PoolHandle,
+                   WalletHandle,
+                   DidValue, // submitter_did
+                   RevocationRegistryId, // id
+                   GetCacheOptions, // options
+                   Box<dyn Fn(IndyResult<String>) + Send>),
+    GetRevocRegDefContinue(
+        WalletHandle,
+        IndyResult<(String, String)>, // ledger_response
+        GetCacheOptions,              // options
+        CommandHandle,                          // cb_id
+    ),
     PurgeSchemaCache(WalletHandle,
                      PurgeOptions, // options
                      Box<dyn Fn(IndyResult<()>) + Send>),
@@ -105,6 +119,14 @@ impl CacheCommandExecutor {
                 debug!(target: "non_secrets_command_executor", "GetCredDefContinue command received");
                 self._get_cred_def_continue(wallet_handle, ledger_response, options, cb_id);
             }
+            CacheCommand::GetRevocRegDef(pool_handle, wallet_handle, submitter_did, id, options, cb) => {
+                debug!(target: "non_secrets_command_executor", "GetRevocRegDef command received");
+                self.get_revoc_reg_def(pool_handle, wallet_handle, &submitter_did, &id, options, cb);
+            }
+            CacheCommand::GetRevocRegDefContinue(wallet_handle, ledger_response, options, cb_id) => {
+                debug!(target: "non_secrets_command_executor", "GetRevocRegDefContinue command received");
+                self._get_revoc_reg_def_continue(wallet_handle, ledger_response, options, cb_id);
+            }
             CacheCommand::PurgeSchemaCache(wallet_handle, options, cb) => {
                 debug!(target: "non_secrets_command_executor", "PurgeSchemaCache command received");
                 cb(self.purge_schema_cache(wallet_handle, options));
@@ -178,6 +200,9 @@ impl CacheCommandExecutor {
                 }
             };
             tags.insert("timestamp".to_string(), ts.to_string());
+            if let Some(ttl) = options.ttl {
+                tags.insert(TTL_TAG.to_string(), ttl.to_string());
+            }
             let _ignore = self.wallet_service.delete_record(wallet_handle, which_cache, &schema_id);
             self.wallet_service.add_record(wallet_handle, which_cache, &schema_id, &schema_json, &tags)?
         }
@@ -268,6 +293,62 @@ impl CacheCommandExecutor {
         }
     }
 
+    fn get_revoc_reg_def(&self,
+                         pool_handle: PoolHandle,
+                         wallet_handle: WalletHandle,
+                         submitter_did: &DidValue,
+                         id: &RevocationRegistryId,
+                         options: GetCacheOptions,
+                         cb: Box<dyn Fn(IndyResult<String>) + Send>) {
+        trace!("get_revoc_reg_def >>> pool_handle: {:?}, wallet_handle: {:?}, submitter_did: {:?}, id: {:?}, options: {:?}",
+               pool_handle, wallet_handle, submitter_did, id, options);
+
+        let cache = self.get_record_from_cache(wallet_handle, &id.0, &options, REVOC_REG_DEF_CACHE);
+        let cache = try_cb!(cache, cb);
+
+        check_cache!(cache, options, cb);
+
+        if options.no_update.unwrap_or(false) {
+            return cb(Err(IndyError::from(IndyErrorKind::LedgerItemNotFound)));
+        }
+
+        let cb_id = next_command_handle();
+        self.pending_callbacks.borrow_mut().insert(cb_id, cb);
+
+        CommandExecutor::instance().send(
+            Command::Ledger(
+                LedgerCommand::GetRevocRegDef(
+                    pool_handle,
+                    Some(submitter_did.clone()),
+                    id.clone(),
+                    Box::new(move |ledger_response| {
+                        CommandExecutor::instance().send(
+                            Command::Cache(
+                                CacheCommand::GetRevocRegDefContinue(
+                                    wallet_handle,
+                                    ledger_response,
+                                    options.clone(),
+                                    cb_id,
+                                )
+                            )
+                        ).unwrap();
+                    })
+                )
+            )
+        ).unwrap();
+    }
+
+    fn _get_revoc_reg_def_continue(&self, wallet_handle: WalletHandle, ledger_response: IndyResult<(String, String)>, options: GetCacheOptions, cb_id: CommandHandle) {
+        let cb = self.pending_callbacks.borrow_mut().remove(&cb_id).expect("FIXME INVALID STATE");
+
+        let (revoc_reg_def_id, revoc_reg_def_json) = try_cb!(ledger_response, cb);
+
+        match self._delete_and_add_record(wallet_handle, options, &revoc_reg_def_id, &revoc_reg_def_json, REVOC_REG_DEF_CACHE) {
+            Ok(_) => cb(Ok(revoc_reg_def_json)),
+            Err(err) => cb(Err(IndyError::from_msg(IndyErrorKind::InvalidState, format!("get_revoc_reg_def_continue failed: {:?}", err))))
+        }
+    }
+
     fn get_seconds_since_epoch() -> Result<i32, IndyError> {
         match SystemTime::now().duration_since(UNIX_EPOCH) {
             Ok(ts) => Ok(ts.as_secs() as i32),
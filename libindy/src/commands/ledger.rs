@@ -239,6 +239,27 @@ pub enum LedgerCommand {
         IndyResult<String>,
         CommandHandle,
     ),
+    GetRevocRegDef(
+        PoolHandle,
+        Option<DidValue>,
+        RevocationRegistryId,
+        BoxedCallbackStringStringSend,
+    ),
+    GetRevocRegDefContinue(
+        IndyResult<String>,
+        CommandHandle,
+    ),
+    GetRevocReg(
+        PoolHandle,
+        Option<DidValue>,
+        RevocationRegistryId,
+        i64, // timestamp
+        Box<dyn Fn(IndyResult<(String, String, u64)>) + Send>,
+    ),
+    GetRevocRegContinue(
+        IndyResult<String>,
+        CommandHandle,
+    ),
     BuildTxnAuthorAgreementRequest(
         DidValue, // submitter did
         Option<String>, // text
@@ -293,6 +314,7 @@ pub struct LedgerCommandExecutor {
 
     send_callbacks: RefCell<HashMap<CommandHandle, Box<dyn Fn(IndyResult<String>)>>>,
     pending_callbacks: RefCell<HashMap<CommandHandle, Box<dyn Fn(IndyResult<(String, String)>)>>>,
+    pending_revoc_reg_callbacks: RefCell<HashMap<CommandHandle, Box<dyn Fn(IndyResult<(String, String, u64)>)>>>,
 }
 
 impl LedgerCommandExecutor {
@@ -307,6 +329,7 @@ impl LedgerCommandExecutor {
             ledger_service,
             send_callbacks: RefCell::new(HashMap::new()),
             pending_callbacks: RefCell::new(HashMap::new()),
+            pending_revoc_reg_callbacks: RefCell::new(HashMap::new()),
         }
     }
 
@@ -499,6 +522,22 @@ impl LedgerCommandExecutor {
                 debug!(target: "ledger_command_executor", "GetCredDefContinue command received");
                 self._get_cred_def_continue(id, pool_response, cb_id);
             }
+            LedgerCommand::GetRevocRegDef(pool_handle, submitter_did, id, cb) => {
+                debug!(target: "ledger_command_executor", "GetRevocRegDef command received");
+                self.get_revoc_reg_def(pool_handle, submitter_did.as_ref(), &id, cb);
+            }
+            LedgerCommand::GetRevocRegDefContinue(pool_response, cb_id) => {
+                debug!(target: "ledger_command_executor", "GetRevocRegDefContinue command received");
+                self._get_revoc_reg_def_continue(pool_response, cb_id);
+            }
+            LedgerCommand::GetRevocReg(pool_handle, submitter_did, id, timestamp, cb) => {
+                debug!(target: "ledger_command_executor", "GetRevocReg command received");
+                self.get_revoc_reg(pool_handle, submitter_did.as_ref(), &id, timestamp, cb);
+            }
+            LedgerCommand::GetRevocRegContinue(pool_response, cb_id) => {
+                debug!(target: "ledger_command_executor", "GetRevocRegContinue command received");
+                self._get_revoc_reg_continue(pool_response, cb_id);
+            }
             LedgerCommand::BuildTxnAuthorAgreementRequest(submitter_did, text, version, ratification_ts, retirement_ts, cb) => {
                 debug!(target: "ledger_command_executor", "BuildTxnAuthorAgreementRequest command received");
                 cb(self.build_txn_author_agreement_request(&submitter_did, text.as_ref().map(String::as_str), &version, ratification_ts, retirement_ts));
@@ -1324,6 +1363,55 @@ impl LedgerCommandExecutor {
         cb(self.ledger_service.parse_get_cred_def_response(&pool_response, id.get_method().as_ref().map(String::as_str)))
     }
 
+    fn get_revoc_reg_def(&self, pool_handle: i32, submitter_did: Option<&DidValue>, id: &RevocationRegistryId, cb: BoxedCallbackStringStringSend) {
+        let request_json = try_cb!(self.build_get_revoc_reg_def_request(submitter_did, id), cb);
+
+        let cb_id = next_command_handle();
+        self.pending_callbacks.borrow_mut().insert(cb_id, cb);
+
+        self.submit_request(pool_handle, &request_json, Box::new(move |response| {
+            CommandExecutor::instance().send(
+                Command::Ledger(
+                    LedgerCommand::GetRevocRegDefContinue(
+                        response,
+                        cb_id
+                    )
+                )
+            ).unwrap();
+        }));
+    }
+
+    fn _get_revoc_reg_def_continue(&self, pool_response: IndyResult<String>, cb_id: CommandHandle) {
+        let cb = self.pending_callbacks.borrow_mut().remove(&cb_id).expect("FIXME INVALID STATE");
+        let pool_response = try_cb!(pool_response, cb);
+        cb(self.ledger_service.parse_get_revoc_reg_def_response(&pool_response))
+    }
+
+    fn get_revoc_reg(&self, pool_handle: i32, submitter_did: Option<&DidValue>, id: &RevocationRegistryId, timestamp: i64,
+                     cb: Box<dyn Fn(IndyResult<(String, String, u64)>) + Send>) {
+        let request_json = try_cb!(self.build_get_revoc_reg_request(submitter_did, id, timestamp), cb);
+
+        let cb_id = next_command_handle();
+        self.pending_revoc_reg_callbacks.borrow_mut().insert(cb_id, cb);
+
+        self.submit_request(pool_handle, &request_json, Box::new(move |response| {
+            CommandExecutor::instance().send(
+                Command::Ledger(
+                    LedgerCommand::GetRevocRegContinue(
+                        response,
+                        cb_id
+                    )
+                )
+            ).unwrap();
+        }));
+    }
+
+    fn _get_revoc_reg_continue(&self, pool_response: IndyResult<String>, cb_id: CommandHandle) {
+        let cb = self.pending_revoc_reg_callbacks.borrow_mut().remove(&cb_id).expect("FIXME INVALID STATE");
+        let pool_response = try_cb!(pool_response, cb);
+        cb(self.ledger_service.parse_get_revoc_reg_response(&pool_response))
+    }
+
     fn build_ledgers_freeze_request(&self, submitter_did: &DidValue, ledgers_ids: Vec<u64>) -> IndyResult<String>{
         debug!("build_ledgers_freeze_request >>> submitter_did: {:?}, ledgers_ids: {:?}", submitter_did, ledgers_ids);
 
@@ -22,6 +22,7 @@ use crate::commands::metrics::{MetricsCommand, MetricsCommandExecutor};
 use crate::domain::IndyConfig;
 use indy_api_types::errors::prelude::*;
 use crate::services::anoncreds::AnoncredsService;
+use crate::services::anoncreds::verifier::set_canonical_encoding_check;
 use crate::services::blob_storage::BlobStorageService;
 use crate::services::crypto::CryptoService;
 use crate::services::ledger::LedgerService;
@@ -96,6 +97,9 @@ pub fn indy_set_runtime_config(config: IndyConfig) {
     if let Some(threshold) = config.freshness_threshold {
         set_freshness_threshold(threshold);
     }
+    if let Some(check_canonical_encoding) = config.check_canonical_encoding {
+        set_canonical_encoding_check(check_canonical_encoding);
+    }
 }
 
 fn get_cur_time() -> u128 {
@@ -229,6 +233,14 @@ impl CommandExecutor {
             .send(InstrumentedCommand::new(cmd))
             .map_err(|err| err_msg(IndyErrorKind::InvalidState, format!("Can't send msg to CommandExecutor: {}", err)))
     }
+
+    // Callbacks are called from the worker thread, so waiting there for command result would deadlock.
+    pub fn is_worker_thread(&self) -> bool {
+        self.worker
+            .as_ref()
+            .map(|worker| worker.thread().id() == thread::current().id())
+            .unwrap_or(false)
+    }
 }
 
 impl Drop for CommandExecutor {
@@ -40,6 +40,9 @@ pub enum NonSecretsCommand {
                  String, // type
                  String, // id
                  Box<dyn Fn(IndyResult<()>) + Send>),
+    PurgeExpiredRecords(WalletHandle,
+                        String, // type
+                        Box<dyn Fn(IndyResult<usize>) + Send>),
     GetRecord(WalletHandle,
               String, // type
               String, // id
@@ -97,6 +100,10 @@ impl NonSecretsCommandExecutor {
                 debug!(target: "non_secrets_command_executor", "DeleteRecord command received");
                 cb(self.delete_record(handle, &type_, &id));
             }
+            NonSecretsCommand::PurgeExpiredRecords(handle, type_, cb) => {
+                debug!(target: "non_secrets_command_executor", "PurgeExpiredRecords command received");
+                cb(self.purge_expired_records(handle, &type_));
+            }
             NonSecretsCommand::GetRecord(handle, type_, id, options_json, cb) => {
                 debug!(target: "non_secrets_command_executor", "GetRecord command received");
                 cb(self.get_record(handle, &type_, &id, &options_json));
@@ -215,6 +222,20 @@ impl NonSecretsCommandExecutor {
         Ok(())
     }
 
+    fn purge_expired_records(&self,
+                             wallet_handle: WalletHandle,
+                             type_: &str) -> IndyResult<usize> {
+        trace!("purge_expired_records >>> wallet_handle: {:?}, type_: {:?}", wallet_handle, type_);
+
+        self._check_type(type_)?;
+
+        let res = self.wallet_service.purge_expired_records(wallet_handle, type_)?;
+
+        trace!("purge_expired_records <<< res: {:?}", res);
+
+        Ok(res)
+    }
+
     fn get_record(&self,
                   wallet_handle: WalletHandle,
                   type_: &str,
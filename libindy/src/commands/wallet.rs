@@ -4,17 +4,29 @@ use std::rc::Rc;
 
 use indy_api_types::wallet::*;
 use crate::commands::{Command, CommandExecutor};
-use indy_api_types::domain::wallet::{Config, Credentials, ExportConfig, KeyConfig};
+use indy_api_types::domain::wallet::{AuditLogQuery, CheckWalletOptions, Config, Credentials, ExportConfig, KeyConfig, StreamExportConfig};
 use indy_api_types::errors::prelude::*;
 use crate::services::crypto::CryptoService;
-use indy_wallet::{KeyDerivationData, WalletService, Metadata};
+use indy_wallet::{AuditLogEntry, CallbackReader, CallbackWriter, KeyDerivationData, WalletService, WalletStorageType, Metadata, FULL_EXPORT_VERSION, INCREMENTAL_EXPORT_VERSION};
 use indy_utils::crypto::{chacha20poly1305_ietf, randombytes};
+use indy_utils::ctypes;
 use indy_utils::crypto::chacha20poly1305_ietf::Key as MasterKey;
-use indy_api_types::{WalletHandle, CallbackHandle};
+use indy_api_types::{WalletHandle, CallbackHandle, IndyHandle};
 use rust_base58::ToBase58;
 
 type DeriveKeyResult<T> = IndyResult<T>;
 
+#[derive(Clone, Copy)]
+pub enum ExportTarget {
+    Buffer,
+    Sink(IndyHandle, WalletExportWrite),
+}
+
+pub enum ImportSource {
+    Buffer(Vec<u8>),
+    Source(IndyHandle, WalletImportRead),
+}
+
 pub enum WalletCommand {
     RegisterWalletType(String, // type_
                        WalletCreate, // create
@@ -42,6 +54,18 @@ pub enum WalletCommand {
                        WalletFetchSearchNextRecord, // fetch search next record
                        WalletFreeSearch, // free search
                        Box<dyn Fn(IndyResult<()>) + Send>),
+    RegisterWalletStorageType(String, // type_
+                              Box<dyn WalletStorageType>, // storage type
+                              Box<dyn Fn(IndyResult<()>) + Send>),
+    RegisterStorageStats(String, // type_
+                         WalletGetStorageStats, // get storage stats
+                         WalletFreeStorageStats, // free storage stats
+                         Box<dyn Fn(IndyResult<()>) + Send>),
+    RegisterKekProvider(String, // type_
+                        WalletKekWrapKey, // wrap key
+                        WalletKekUnwrapKey, // unwrap key
+                        WalletKekFreeKey, // free key
+                        Box<dyn Fn(IndyResult<()>) + Send>),
     Create(Config, // config
            Credentials, // credentials
            Box<dyn Fn(IndyResult<()>) + Send>),
@@ -66,6 +90,16 @@ pub enum WalletCommand {
                    Metadata, // credentials
                    DeriveKeyResult<MasterKey>,
                    CallbackHandle),
+    Check(Config, // config
+          Credentials, // credentials
+          CheckWalletOptions, // options
+          Box<dyn Fn(IndyResult<String>) + Send>),
+    CheckContinue(Config, // config
+                  Credentials, // credentials
+                  CheckWalletOptions, // options
+                  Metadata, // metadata
+                  DeriveKeyResult<MasterKey>,
+                  CallbackHandle),
     Export(WalletHandle,
            ExportConfig, // export config
            Box<dyn Fn(IndyResult<()>) + Send>),
@@ -84,6 +118,39 @@ pub enum WalletCommand {
                    WalletHandle,
                    CallbackHandle
     ),
+    ExportToStream(WalletHandle,
+                   StreamExportConfig, // export config
+                   ExportTarget,
+                   Box<dyn Fn(IndyResult<Vec<u8>>) + Send>),
+    ExportToStreamContinue(WalletHandle,
+                           StreamExportConfig, // export config
+                           ExportTarget,
+                           KeyDerivationData,
+                           DeriveKeyResult<MasterKey>,
+                           CallbackHandle),
+    ImportFromStream(Config, // config
+                     Credentials, // credentials
+                     StreamExportConfig, // import config
+                     ImportSource,
+                     Box<dyn Fn(IndyResult<()>) + Send>),
+    ImportIncrement(WalletHandle,
+                    ExportConfig, // import config
+                    Box<dyn Fn(IndyResult<()>) + Send>),
+    ImportIncrementFromStream(WalletHandle,
+                              StreamExportConfig, // import config
+                              ImportSource,
+                              Box<dyn Fn(IndyResult<()>) + Send>),
+    ImportIncrementContinue(i32, // import id
+                            DeriveKeyResult<MasterKey>,
+                            CallbackHandle),
+    GetAuditLog(WalletHandle,
+                AuditLogQuery, // query
+                Box<dyn Fn(IndyResult<String>) + Send>),
+    GetWalletStats(WalletHandle,
+                   Box<dyn Fn(IndyResult<String>) + Send>),
+    SetChangeCallback(WalletHandle,
+                      Option<WalletChangeCB>, // change callback
+                      Box<dyn Fn(IndyResult<()>) + Send>),
     GenerateKey(Option<KeyConfig>, // config
                 Box<dyn Fn(IndyResult<String>) + Send>),
     DeriveKey(KeyDerivationData,
@@ -101,7 +168,9 @@ pub struct WalletCommandExecutor {
     wallet_service: Rc<WalletService>,
     crypto_service: Rc<CryptoService>,
     open_callbacks: RefCell<HashMap<WalletHandle, Box<dyn Fn(IndyResult<WalletHandle>) + Send>>>,
-    pending_callbacks: RefCell<HashMap<CallbackHandle, Box<dyn Fn(IndyResult<()>) + Send>>>
+    pending_callbacks: RefCell<HashMap<CallbackHandle, Box<dyn Fn(IndyResult<()>) + Send>>>,
+    check_callbacks: RefCell<HashMap<CallbackHandle, Box<dyn Fn(IndyResult<String>) + Send>>>,
+    export_stream_callbacks: RefCell<HashMap<CallbackHandle, Box<dyn Fn(IndyResult<Vec<u8>>) + Send>>>
 }
 
 impl WalletCommandExecutor {
@@ -110,7 +179,9 @@ impl WalletCommandExecutor {
             wallet_service,
             crypto_service,
             open_callbacks: RefCell::new(HashMap::new()),
-            pending_callbacks: RefCell::new(HashMap::new())
+            pending_callbacks: RefCell::new(HashMap::new()),
+            check_callbacks: RefCell::new(HashMap::new()),
+            export_stream_callbacks: RefCell::new(HashMap::new())
         }
     }
 
@@ -130,6 +201,18 @@ impl WalletCommandExecutor {
                                        free_storage_metadata, search_records, search_all_records, get_search_total_count,
                                        fetch_search_next_record, free_search));
             }
+            WalletCommand::RegisterWalletStorageType(type_, storage_type, cb) => {
+                debug!(target: "wallet_command_executor", "RegisterWalletStorageType command received");
+                cb(self._register_storage_type(&type_, storage_type));
+            }
+            WalletCommand::RegisterStorageStats(type_, get_storage_stats, free_storage_stats, cb) => {
+                debug!(target: "wallet_command_executor", "RegisterStorageStats command received");
+                cb(self._register_storage_stats(&type_, get_storage_stats, free_storage_stats));
+            }
+            WalletCommand::RegisterKekProvider(type_, wrap_key, unwrap_key, free_key, cb) => {
+                debug!(target: "wallet_command_executor", "RegisterKekProvider command received");
+                cb(self._register_kek_provider(&type_, wrap_key, unwrap_key, free_key));
+            }
             WalletCommand::Create(config, credentials, cb) => {
                 debug!(target: "wallet_command_executor", "Create command received");
                 self._create(&config, &credentials, cb)
@@ -158,6 +241,14 @@ impl WalletCommandExecutor {
                 debug!(target: "wallet_command_executor", "DeleteContinue command received");
                 self._delete_continue(cb_id, &config, &credentials, &metadata, key_result)
             }
+            WalletCommand::Check(config, credentials, options, cb) => {
+                debug!(target: "wallet_command_executor", "Check command received");
+                self._check(&config, &credentials, &options, cb)
+            }
+            WalletCommand::CheckContinue(config, credentials, options, metadata, key_result, cb_id) => {
+                debug!(target: "wallet_command_executor", "CheckContinue command received");
+                self._check_continue(cb_id, &config, &credentials, &options, &metadata, key_result)
+            }
             WalletCommand::Export(wallet_handle, export_config, cb) => {
                 debug!(target: "wallet_command_executor", "Export command received");
                 self._export(wallet_handle, &export_config, cb)
@@ -174,6 +265,42 @@ impl WalletCommandExecutor {
                 debug!(target: "wallet_command_executor", "ImportContinue command received");
                 self._import_continue(cb_id, wallet_handle, &config, &credential, key_result);
             }
+            WalletCommand::ExportToStream(wallet_handle, export_config, target, cb) => {
+                debug!(target: "wallet_command_executor", "ExportToStream command received");
+                self._export_to_stream(wallet_handle, &export_config, target, cb)
+            }
+            WalletCommand::ExportToStreamContinue(wallet_handle, export_config, target, key_data, key_result, cb_id) => {
+                debug!(target: "wallet_command_executor", "ExportToStreamContinue command received");
+                self._export_to_stream_continue(cb_id, wallet_handle, &export_config, target, key_data, key_result)
+            }
+            WalletCommand::ImportFromStream(config, credentials, import_config, source, cb) => {
+                debug!(target: "wallet_command_executor", "ImportFromStream command received");
+                self._import_from_stream(&config, &credentials, &import_config, source, cb);
+            }
+            WalletCommand::ImportIncrement(wallet_handle, import_config, cb) => {
+                debug!(target: "wallet_command_executor", "ImportIncrement command received");
+                self._import_increment(wallet_handle, &import_config, cb);
+            }
+            WalletCommand::ImportIncrementFromStream(wallet_handle, import_config, source, cb) => {
+                debug!(target: "wallet_command_executor", "ImportIncrementFromStream command received");
+                self._import_increment_from_stream(wallet_handle, &import_config, source, cb);
+            }
+            WalletCommand::ImportIncrementContinue(import_id, key_result, cb_id) => {
+                debug!(target: "wallet_command_executor", "ImportIncrementContinue command received");
+                self._import_increment_continue(cb_id, import_id, key_result);
+            }
+            WalletCommand::GetAuditLog(wallet_handle, query, cb) => {
+                debug!(target: "wallet_command_executor", "GetAuditLog command received");
+                cb(self._get_audit_log(wallet_handle, &query));
+            }
+            WalletCommand::GetWalletStats(wallet_handle, cb) => {
+                debug!(target: "wallet_command_executor", "GetWalletStats command received");
+                cb(self._get_wallet_stats(wallet_handle));
+            }
+            WalletCommand::SetChangeCallback(wallet_handle, change_cb, cb) => {
+                debug!(target: "wallet_command_executor", "SetChangeCallback command received");
+                cb(self._set_change_callback(wallet_handle, change_cb));
+            }
             WalletCommand::GenerateKey(config, cb) => {
                 debug!(target: "wallet_command_executor", "DeriveKey command received");
                 cb(self._generate_key(config.as_ref()));
@@ -226,6 +353,46 @@ impl WalletCommandExecutor {
         Ok(())
     }
 
+    fn _register_storage_type(&self, type_: &str, storage_type: Box<dyn WalletStorageType>) -> IndyResult<()> {
+        trace!("_register_storage_type >>> type_: {:?}", type_);
+
+        self
+            .wallet_service
+            .register_wallet_storage_type(type_, storage_type)?;
+
+        trace!("_register_storage_type <<< res: ()");
+        Ok(())
+    }
+
+    fn _register_storage_stats(&self,
+                               type_: &str,
+                               get_storage_stats: WalletGetStorageStats,
+                               free_storage_stats: WalletFreeStorageStats) -> IndyResult<()> {
+        trace!("_register_storage_stats >>> type_: {:?}", type_);
+
+        self
+            .wallet_service
+            .register_wallet_storage_stats(type_, get_storage_stats, free_storage_stats)?;
+
+        trace!("_register_storage_stats <<< res: ()");
+        Ok(())
+    }
+
+    fn _register_kek_provider(&self,
+                              type_: &str,
+                              wrap_key: WalletKekWrapKey,
+                              unwrap_key: WalletKekUnwrapKey,
+                              free_key: WalletKekFreeKey) -> IndyResult<()> {
+        trace!("_register_kek_provider >>> type_: {:?}", type_);
+
+        self
+            .wallet_service
+            .register_kek_provider(type_, wrap_key, unwrap_key, free_key)?;
+
+        trace!("_register_kek_provider <<< res: ()");
+        Ok(())
+    }
+
     fn _create(&self,
                config: &Config,
                credentials: &Credentials,
@@ -387,6 +554,62 @@ impl WalletCommandExecutor {
             .and_then(|key| self.wallet_service.delete_wallet_continue(config, credentials, metadata, &key)))
     }
 
+    fn _check(&self,
+              config: &Config,
+              credentials: &Credentials,
+              options: &CheckWalletOptions,
+              cb: Box<dyn Fn(IndyResult<String>) + Send>) {
+        trace!("_check >>> config: {:?}, credentials: {:?}, options: {:?}", config, secret!(credentials), options);
+
+        let (metadata, key_derivation_data) = try_cb!(self.wallet_service.check_wallet_prepare(&config, &credentials), cb);
+
+        let cb_id: CallbackHandle = indy_utils::sequence::get_next_id();
+        self.check_callbacks.borrow_mut().insert(cb_id, cb);
+
+        let config = config.clone();
+        let credentials = credentials.clone();
+        let options = options.clone();
+
+        CommandExecutor::instance().send(
+            Command::Wallet(WalletCommand::DeriveKey(
+                key_derivation_data,
+                Box::new(move |key_result| {
+                    CommandExecutor::instance().send(
+                        Command::Wallet(WalletCommand::CheckContinue(
+                            config.clone(),
+                            credentials.clone(),
+                            options.clone(),
+                            metadata.clone(),
+                            key_result,
+                            cb_id)
+                        )).unwrap()
+                }),
+            ))
+        ).unwrap();
+
+        trace!("_check <<<");
+    }
+
+    fn _check_continue(&self,
+                       cb_id: CallbackHandle,
+                       config: &Config,
+                       credentials: &Credentials,
+                       options: &CheckWalletOptions,
+                       metadata: &Metadata,
+                       key_result: DeriveKeyResult<MasterKey>) {
+        let cb = match self.check_callbacks.borrow_mut().remove(&cb_id) {
+            Some(cb) => cb,
+            None => return error!("No pending command for id: {}", cb_id)
+        };
+
+        let repair = options.repair.unwrap_or(false);
+
+        cb(key_result
+            .and_then(|key| self.wallet_service.check_wallet_continue(config, credentials, metadata, &key, repair))
+            .and_then(|report| serde_json::to_string(&report)
+                .to_indy(IndyErrorKind::InvalidState, "Cannot serialize wallet check report")))
+    }
+
     fn _export(&self,
                wallet_handle: WalletHandle,
                export_config: &ExportConfig,
@@ -425,8 +648,74 @@ impl WalletCommandExecutor {
                         key_data: KeyDerivationData,
                         key_result: DeriveKeyResult<MasterKey>) {
         let cb = get_cb!(self, cb_id);
+
+        let version = if export_config.incremental.unwrap_or(false) { INCREMENTAL_EXPORT_VERSION } else { FULL_EXPORT_VERSION };
+
         cb(key_result
-            .and_then(|key| self.wallet_service.export_wallet(wallet_handle, export_config, 0, (&key_data,& key)))) // TODO - later add proper versioning
+            .and_then(|key| self.wallet_service.export_wallet(wallet_handle, export_config, version, (&key_data,& key))))
+    }
+
+    fn _export_to_stream(&self,
+                         wallet_handle: WalletHandle,
+                         export_config: &StreamExportConfig,
+                         target: ExportTarget,
+                         cb: Box<dyn Fn(IndyResult<Vec<u8>>) + Send>) {
+        trace!("_export_to_stream >>> handle: {:?}, export_config: {:?}", wallet_handle, secret!(export_config));
+
+        let key_data = KeyDerivationData::from_passphrase_with_new_salt(&export_config.key, &export_config.key_derivation_method);
+
+        let cb_id = indy_utils::sequence::get_next_id();
+        self.export_stream_callbacks.borrow_mut().insert(cb_id, cb);
+
+        let export_config = export_config.clone();
+
+        CommandExecutor::instance().send(
+            Command::Wallet(WalletCommand::DeriveKey(
+                key_data.clone(),
+                Box::new(move |master_key_res| {
+                    CommandExecutor::instance().send(Command::Wallet(WalletCommand::ExportToStreamContinue(
+                        wallet_handle,
+                        export_config.clone(),
+                        target,
+                        key_data.clone(),
+                        master_key_res,
+                        cb_id,
+                    ))).unwrap();
+                })
+            ))
+        ).unwrap();
+
+        trace!("_export_to_stream <<<");
+    }
+
+    fn _export_to_stream_continue(&self,
+                                  cb_id: CallbackHandle,
+                                  wallet_handle: WalletHandle,
+                                  export_config: &StreamExportConfig,
+                                  target: ExportTarget,
+                                  key_data: KeyDerivationData,
+                                  key_result: DeriveKeyResult<MasterKey>) {
+        let cb = match self.export_stream_callbacks.borrow_mut().remove(&cb_id) {
+            Some(cb) => cb,
+            None => return error!("No pending command for id: {}", cb_id)
+        };
+
+        let version = if export_config.incremental.unwrap_or(false) { INCREMENTAL_EXPORT_VERSION } else { FULL_EXPORT_VERSION };
+
+        cb(key_result.and_then(|key| {
+            match target {
+                ExportTarget::Buffer => {
+                    let mut buffer: Vec<u8> = Vec::new();
+                    self.wallet_service.export_wallet_to_writer(wallet_handle, &mut buffer, version, export_config.filter.as_deref(), (&key_data, &key))?;
+                    Ok(buffer)
+                }
+                ExportTarget::Sink(sink_handle, write_cb) => {
+                    let mut writer = CallbackWriter::new(sink_handle, write_cb);
+                    self.wallet_service.export_wallet_to_writer(wallet_handle, &mut writer, version, export_config.filter.as_deref(), (&key_data, &key))?;
+                    Ok(Vec::new())
+                }
+            }
+        }))
     }
 
     fn _import(&self,
@@ -439,6 +728,39 @@ impl WalletCommandExecutor {
 
         let (wallet_handle, key_data, import_key_data) = try_cb!(self.wallet_service.import_wallet_prepare(&config, &credentials, &import_config), cb);
 
+        self._import_prepared(config, credentials, wallet_handle, key_data, import_key_data, cb);
+
+        trace!("_import <<<");
+    }
+
+    fn _import_from_stream(&self,
+                           config: &Config,
+                           credentials: &Credentials,
+                           import_config: &StreamExportConfig,
+                           source: ImportSource,
+                           cb: Box<dyn Fn(IndyResult<()>) + Send>) {
+        trace!("_import_from_stream >>> config: {:?}, credentials: {:?}, import_config: {:?}",
+               config, secret!(credentials), secret!(import_config));
+
+        let reader: Box<dyn std::io::Read> = match source {
+            ImportSource::Buffer(data) => Box::new(std::io::Cursor::new(data)),
+            ImportSource::Source(source_handle, read_cb) => Box::new(CallbackReader::new(source_handle, read_cb)),
+        };
+
+        let (wallet_handle, key_data, import_key_data) = try_cb!(self.wallet_service.import_wallet_prepare_from_reader(&config, &credentials, reader, &import_config.key), cb);
+
+        self._import_prepared(config, credentials, wallet_handle, key_data, import_key_data, cb);
+
+        trace!("_import_from_stream <<<");
+    }
+
+    fn _import_prepared(&self,
+                        config: &Config,
+                        credentials: &Credentials,
+                        wallet_handle: WalletHandle,
+                        key_data: KeyDerivationData,
+                        import_key_data: KeyDerivationData,
+                        cb: Box<dyn Fn(IndyResult<()>) + Send>) {
         let cb_id : CallbackHandle = indy_utils::sequence::get_next_id();
         self.pending_callbacks.borrow_mut().insert(cb_id, cb);
 
@@ -470,8 +792,6 @@ impl WalletCommandExecutor {
                 }),
             ))
         ).unwrap();
-
-        trace!("_import <<<");
     }
 
     fn _import_continue(&self,
@@ -485,6 +805,114 @@ impl WalletCommandExecutor {
             .and_then(|key| self.wallet_service.import_wallet_continue(wallet_handle, &config, &credential, key)))
     }
 
+    fn _import_increment(&self,
+                         wallet_handle: WalletHandle,
+                         import_config: &ExportConfig,
+                         cb: Box<dyn Fn(IndyResult<()>) + Send>) {
+        trace!("_import_increment >>> wallet_handle: {:?}, import_config: {:?}", wallet_handle, secret!(import_config));
+
+        let (import_id, import_key_data) = try_cb!(self.wallet_service.import_wallet_increment_prepare(wallet_handle, import_config), cb);
+
+        self._import_increment_prepared(import_id, import_key_data, cb);
+
+        trace!("_import_increment <<<");
+    }
+
+    fn _import_increment_from_stream(&self,
+                                     wallet_handle: WalletHandle,
+                                     import_config: &StreamExportConfig,
+                                     source: ImportSource,
+                                     cb: Box<dyn Fn(IndyResult<()>) + Send>) {
+        trace!("_import_increment_from_stream >>> wallet_handle: {:?}, import_config: {:?}", wallet_handle, secret!(import_config));
+
+        let reader: Box<dyn std::io::Read> = match source {
+            ImportSource::Buffer(data) => Box::new(std::io::Cursor::new(data)),
+            ImportSource::Source(source_handle, read_cb) => Box::new(CallbackReader::new(source_handle, read_cb)),
+        };
+
+        let (import_id, import_key_data) = try_cb!(self.wallet_service.import_wallet_increment_prepare_from_reader(wallet_handle, reader, &import_config.key), cb);
+
+        self._import_increment_prepared(import_id, import_key_data, cb);
+
+        trace!("_import_increment_from_stream <<<");
+    }
+
+    fn _import_increment_prepared(&self,
+                                  import_id: i32,
+                                  import_key_data: KeyDerivationData,
+                                  cb: Box<dyn Fn(IndyResult<()>) + Send>) {
+        let cb_id: CallbackHandle = indy_utils::sequence::get_next_id();
+        self.pending_callbacks.borrow_mut().insert(cb_id, cb);
+
+        CommandExecutor::instance().send(
+            Command::Wallet(WalletCommand::DeriveKey(
+                import_key_data,
+                Box::new(move |key_result| {
+                    CommandExecutor::instance().send(Command::Wallet(WalletCommand::ImportIncrementContinue(
+                        import_id,
+                        key_result,
+                        cb_id
+                    ))).unwrap();
+                }),
+            ))
+        ).unwrap();
+    }
+
+    fn _import_increment_continue(&self,
+                                  cb_id: CallbackHandle,
+                                  import_id: i32,
+                                  key_result: DeriveKeyResult<MasterKey>) {
+        let cb = get_cb!(self, cb_id);
+        cb(self.wallet_service.import_wallet_increment_continue(import_id, key_result))
+    }
+
+    fn _get_audit_log(&self,
+                      wallet_handle: WalletHandle,
+                      query: &AuditLogQuery) -> IndyResult<String> {
+        trace!("_get_audit_log >>> wallet_handle: {:?}, query: {:?}", wallet_handle, query);
+
+        let entries = self.wallet_service.get_audit_log(wallet_handle, query.from, query.to)?;
+
+        let res = serde_json::to_string(&entries)
+            .to_indy(IndyErrorKind::InvalidState, "Cannot serialize audit log entries")?;
+
+        trace!("_get_audit_log <<< res: {:?}", res);
+        Ok(res)
+    }
+
+    fn _get_wallet_stats(&self,
+                         wallet_handle: WalletHandle) -> IndyResult<String> {
+        trace!("_get_wallet_stats >>> wallet_handle: {:?}", wallet_handle);
+
+        let stats = self.wallet_service.get_wallet_stats(wallet_handle)?;
+
+        let res = serde_json::to_string(&stats)
+            .to_indy(IndyErrorKind::InvalidState, "Cannot serialize wallet stats")?;
+
+        trace!("_get_wallet_stats <<< res: {:?}", res);
+        Ok(res)
+    }
+
+    fn _set_change_callback(&self,
+                            wallet_handle: WalletHandle,
+                            change_cb: Option<WalletChangeCB>) -> IndyResult<()> {
+        trace!("_set_change_callback >>> wallet_handle: {:?}", wallet_handle);
+
+        let change_listener = change_cb.map(|change_cb| -> Box<dyn Fn(&AuditLogEntry)> {
+            Box::new(move |entry: &AuditLogEntry| {
+                match serde_json::to_string(entry) {
+                    Ok(change_json) => change_cb(wallet_handle, ctypes::string_to_cstring(change_json).as_ptr()),
+                    Err(err) => warn!("Cannot serialize wallet change: {:?}", err)
+                }
+            })
+        });
+
+        self.wallet_service.set_change_listener(wallet_handle, change_listener)?;
+
+        trace!("_set_change_callback <<< res: ()");
+        Ok(())
+    }
+
     fn _generate_key(&self,
                      config: Option<&KeyConfig>) -> IndyResult<String> {
         trace!("_generate_key >>>config: {:?}", secret!(config));
@@ -55,6 +55,28 @@ pub struct AttributeValues {
     pub encoded: String
 }
 
+/// Credential values as passed by Issuer: `encoded` can be omitted,
+/// in this case it is calculated with the canonical encoding.
+#[derive(Debug, Clone, Deserialize, Serialize, Eq, PartialEq)]
+pub struct RawCredentialValues(pub HashMap<String, RawAttributeValues>);
+
+#[derive(Debug, Clone, Deserialize, Serialize, Eq, PartialEq)]
+pub struct RawAttributeValues {
+    pub raw: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub encoded: Option<String>
+}
+
+impl Validatable for RawCredentialValues {
+    fn validate(&self) -> Result<(), String> {
+        if self.0.is_empty() {
+            return Err(String::from("CredentialValues validation failed: empty list has been passed"));
+        }
+
+        Ok(())
+    }
+}
+
 impl Validatable for CredentialValues {
     fn validate(&self) -> Result<(), String> {
         if self.0.is_empty() {
@@ -0,0 +1,59 @@
+use std::collections::HashSet;
+
+use super::credential::{Credential, RawCredentialValues};
+use super::credential_offer::CredentialOffer;
+use super::credential_request::CredentialRequest;
+
+use indy_api_types::validation::Validatable;
+
+#[derive(Debug, Deserialize, Serialize)]
+pub struct CredentialIssuanceRequest {
+    pub cred_offer: CredentialOffer,
+    pub cred_req: CredentialRequest,
+    pub cred_values: RawCredentialValues,
+}
+
+#[derive(Debug, Deserialize, Serialize)]
+pub struct CredentialIssuanceRequests(pub Vec<CredentialIssuanceRequest>);
+
+impl Validatable for CredentialIssuanceRequests {
+    fn validate(&self) -> Result<(), String> {
+        let first = self.0.first()
+            .ok_or_else(|| String::from("CredentialIssuanceRequests validation failed: empty list has been passed"))?;
+
+        for request in self.0.iter() {
+            request.cred_offer.validate()?;
+            request.cred_req.validate()?;
+            request.cred_values.validate()?;
+
+            if request.cred_offer.cred_def_id != first.cred_offer.cred_def_id {
+                return Err(String::from("CredentialIssuanceRequests validation failed: all offers must reference the same Credential Definition"));
+            }
+        }
+
+        Ok(())
+    }
+}
+
+#[derive(Debug, Deserialize, Serialize)]
+pub struct IssuedCredential {
+    pub cred: Credential,
+    pub cred_revoc_id: Option<String>,
+}
+
+#[derive(Debug, Deserialize, Serialize)]
+pub struct CredentialRevocationIds(pub Vec<String>);
+
+impl Validatable for CredentialRevocationIds {
+    fn validate(&self) -> Result<(), String> {
+        if self.0.is_empty() {
+            return Err(String::from("CredentialRevocationIds validation failed: empty list has been passed"));
+        }
+
+        if self.0.iter().collect::<HashSet<&String>>().len() != self.0.len() {
+            return Err(String::from("CredentialRevocationIds validation failed: duplicated ids have been passed"));
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,52 @@
+use std::collections::HashMap;
+
+use super::requested_credential::RequestedCredentials;
+
+use indy_api_types::validation::Validatable;
+
+#[derive(Debug, Clone, Copy, Deserialize, Serialize, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum CredentialSelectionStrategy {
+    /// The most recently stored credential is taken for every referent
+    Newest,
+    /// Referents are grouped to be covered by as few credentials as possible
+    FewestCredentials,
+    /// Non-revocable credentials are taken if any, the newest ones otherwise
+    PreferNonRevocable,
+}
+
+impl Default for CredentialSelectionStrategy {
+    fn default() -> Self {
+        CredentialSelectionStrategy::Newest
+    }
+}
+
+#[derive(Debug, Default, Deserialize, Serialize)]
+pub struct CredentialSelectionPolicy {
+    #[serde(default)]
+    pub strategy: CredentialSelectionStrategy,
+    #[serde(default)]
+    pub self_attested_attributes: HashMap<String, String>,
+}
+
+impl Validatable for CredentialSelectionPolicy {}
+
+#[derive(Debug, Clone)]
+pub struct CandidateCredential {
+    pub cred_id: String,
+    pub revocable: bool,
+    pub stored_at: u64,
+    pub timestamp: Option<u64>,
+}
+
+#[derive(Debug, Default, Deserialize, Serialize, PartialEq)]
+pub struct UnmetReferents {
+    pub requested_attributes: Vec<String>,
+    pub requested_predicates: Vec<String>,
+}
+
+#[derive(Debug, Deserialize, Serialize)]
+pub struct CredentialSelection {
+    pub requested_credentials: RequestedCredentials,
+    pub unmet_referents: UnmetReferents,
+}
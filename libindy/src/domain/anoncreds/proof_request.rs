@@ -144,6 +144,9 @@ pub struct PredicateInfo {
     pub name: String,
     pub p_type: PredicateTypes,
     pub p_value: i32,
+    // upper bound of `between` predicate, `p_value` is the lower one
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub p_value_to: Option<i32>,
     pub restrictions: Option<Query>,
     pub non_revoked: Option<NonRevocedInterval>
 }
@@ -157,7 +160,9 @@ pub enum PredicateTypes {
     #[serde(rename = ">")]
     GT,
     #[serde(rename = "<")]
-    LT
+    LT,
+    #[serde(rename = "between")]
+    Between
 }
 
 impl fmt::Display for PredicateTypes {
@@ -166,7 +171,8 @@ impl fmt::Display for PredicateTypes {
             PredicateTypes::GE => write!(f, "GE"),
             PredicateTypes::GT => write!(f, "GT"),
             PredicateTypes::LE => write!(f, "LE"),
-            PredicateTypes::LT => write!(f, "LT")
+            PredicateTypes::LT => write!(f, "LT"),
+            PredicateTypes::Between => write!(f, "BETWEEN")
         }
     }
 }
@@ -213,6 +219,19 @@ impl Validatable for ProofRequest {
             if requested_predicate.name.is_empty() {
                 return Err(format!("Proof Request validation failed: there is empty requested attribute: {:?}", requested_predicate));
             }
+            match (&requested_predicate.p_type, requested_predicate.p_value_to) {
+                (PredicateTypes::Between, None) => {
+                    return Err(format!("Proof Request validation failed: `between` predicate requires `p_value_to`: {:?}", requested_predicate));
+                }
+                (PredicateTypes::Between, Some(p_value_to)) if p_value_to < requested_predicate.p_value => {
+                    return Err(format!("Proof Request validation failed: `p_value_to` of `between` predicate is less than `p_value`: {:?}", requested_predicate));
+                }
+                (PredicateTypes::Between, Some(_)) => {}
+                (_, Some(_)) => {
+                    return Err(format!("Proof Request validation failed: `p_value_to` can be set only for `between` predicate: {:?}", requested_predicate));
+                }
+                (_, None) => {}
+            }
             if let Some(ref restrictions) = requested_predicate.restrictions {
                 _process_operator(&restrictions, &version)?;
             }
@@ -300,6 +319,14 @@ fn _process_operator(restriction_op: &Query, version: &ProofRequestsVersion) ->
         Query::Like(ref tag_name, ref tag_value) => {
             _check_restriction(tag_name, tag_value, version)
         }
+        // Credential tags checked by verifier don't support these wallet query operators.
+        Query::Exist(_) |
+        Query::NumGt(..) |
+        Query::NumGte(..) |
+        Query::NumLt(..) |
+        Query::NumLte(..) => {
+            Err(format!("Restriction operator is not supported: {:?}", restriction_op))
+        }
         Query::In(ref tag_name, ref tag_values) => {
             tag_values
                 .iter()
@@ -401,6 +428,7 @@ mod tests {
                 name: "age".to_string(),
                 p_type: PredicateTypes::GE,
                 p_value: 0,
+                p_value_to: None,
                 restrictions: Some(Query::And(vec![
                     Query::Eq("schema_issuer_did".to_string(), DID_QUALIFIED.to_string()),
                     Query::Eq("rev_reg_id".to_string(), REV_REG_ID_QUALIFIED.to_string()),
@@ -435,6 +463,7 @@ mod tests {
                 name: "age".to_string(),
                 p_type: PredicateTypes::GE,
                 p_value: 0,
+                p_value_to: None,
                 restrictions: Some(Query::And(vec![
                     Query::Eq("schema_issuer_did".to_string(), DID_UNQUALIFIED.to_string()),
                     Query::Eq("rev_reg_id".to_string(), REV_REG_ID_UNQUALIFIED.to_string()),
@@ -448,4 +477,32 @@ mod tests {
             assert_eq!(ProofRequestsVersion::V2, proof_request.version());
         }
     }
+
+    mod restrictions {
+        use super::*;
+
+        #[test]
+        fn process_operator_works() {
+            let query = Query::And(vec![
+                Query::Eq("schema_name".to_string(), "gvt".to_string()),
+                Query::In("issuer_did".to_string(), vec!["NcYxiDXkpYi6ov5FcYDi1e".to_string()]),
+            ]);
+            _process_operator(&query, &ProofRequestsVersion::V2).unwrap();
+        }
+
+        #[test]
+        fn process_operator_works_for_unsupported_operators() {
+            let queries = vec![
+                Query::Exist("schema_name".to_string()),
+                Query::NumGt("schema_version".to_string(), "1".to_string()),
+                Query::NumGte("schema_version".to_string(), "1".to_string()),
+                Query::NumLt("schema_version".to_string(), "1".to_string()),
+                Query::NumLte("schema_version".to_string(), "1".to_string()),
+            ];
+
+            for query in queries {
+                _process_operator(&Query::Not(Box::new(query)), &ProofRequestsVersion::V2).unwrap_err();
+            }
+        }
+    }
 }
\ No newline at end of file
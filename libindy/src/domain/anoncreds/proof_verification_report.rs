@@ -0,0 +1,77 @@
+use std::collections::HashMap;
+
+use super::schema::SchemaId;
+use super::credential_definition::CredentialDefinitionId;
+use super::revocation_registry_definition::RevocationRegistryId;
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum VerificationStatus {
+    /// Proof is cryptographically valid and satisfies proof request
+    Valid,
+    /// Proof is malformed or its cryptographic verification failed
+    ProofInvalid,
+    /// Proof is valid but doesn't satisfy proof request
+    RequestNotSatisfied,
+    /// Some of schemas, credential definitions or revocation registries are not provided
+    MissingLedgerData,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct CheckResult {
+    pub passed: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+impl CheckResult {
+    pub fn passed() -> CheckResult {
+        CheckResult { passed: true, error: None }
+    }
+
+    pub fn failed(error: String) -> CheckResult {
+        CheckResult { passed: false, error: Some(error) }
+    }
+
+    pub fn is_failed(check: &Option<CheckResult>) -> bool {
+        check.as_ref().map(|check| !check.passed).unwrap_or(false)
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ReferentReport {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub sub_proof_index: Option<u32>,
+    pub self_attested: bool,
+    pub received: CheckResult,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub values: Option<CheckResult>,
+    /// Affects the status only if `check_canonical_encoding` runtime config is set as credentials could be issued with a custom encoding
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub encoding: Option<CheckResult>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub restrictions: Option<CheckResult>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub timestamp: Option<CheckResult>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct SubProofReport {
+    pub schema_id: SchemaId,
+    pub cred_def_id: CredentialDefinitionId,
+    pub rev_reg_id: Option<RevocationRegistryId>,
+    pub timestamp: Option<u64>,
+    pub ledger_data: CheckResult,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub non_revoked: Option<CheckResult>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ProofVerificationReport {
+    pub valid: bool,
+    pub status: VerificationStatus,
+    pub requested_attributes: HashMap<String, ReferentReport>,
+    pub requested_predicates: HashMap<String, ReferentReport>,
+    pub sub_proofs: Vec<SubProofReport>,
+    pub crypto: Option<CheckResult>,
+}
@@ -4,7 +4,7 @@ use std::collections::HashMap;
 
 use indy_api_types::validation::Validatable;
 
-use super::revocation_registry_definition::RevocationRegistryId;
+use super::revocation_registry_definition::{IssuanceType, RevocationRegistryId};
 
 #[derive(Debug, Serialize, Deserialize)]
 pub struct RevocationRegistryV1 {
@@ -43,3 +43,23 @@ pub fn rev_regs_map_to_rev_regs_local_map(rev_regs: RevocationRegistries) -> Has
 }
 
 impl Validatable for RevocationRegistry {}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct RevocationRegistryStatus {
+    pub rev_reg_id: RevocationRegistryId,
+    pub issuance_type: IssuanceType,
+    pub max_cred_num: u32,
+    pub next_index: Option<u32>,
+    pub issued: u32,
+    pub revoked: u32,
+    pub revoked_ids: Vec<u32>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct RevocationRegistryDrift {
+    pub rev_reg_id: RevocationRegistryId,
+    pub in_sync: bool,
+    pub local_accum: serde_json::Value,
+    pub ledger_accum: serde_json::Value,
+    pub ledger_timestamp: u64,
+}
@@ -0,0 +1,71 @@
+use std::collections::HashSet;
+
+use super::credential_definition::CredentialDefinitionId;
+use super::revocation_registry_definition::{IssuanceType, RevocationRegistryConfig, RevocationRegistryId};
+use super::super::crypto::did::DidValue;
+
+use indy_api_types::validation::Validatable;
+
+pub const DEFAULT_POOL_TAG_PREFIX: &str = "pool";
+pub const DEFAULT_POOL_TAILS_WRITER_TYPE: &str = "default";
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct RevocationRegistryPoolConfig {
+    pub issuance_type: Option<IssuanceType>,
+    pub max_cred_num: Option<u32>,
+    pub tag_prefix: Option<String>,
+    pub tails_writer_type: Option<String>,
+    pub tails_writer_config: String,
+}
+
+impl RevocationRegistryPoolConfig {
+    pub fn tag_prefix(&self) -> &str {
+        self.tag_prefix.as_ref().map(String::as_str).unwrap_or(DEFAULT_POOL_TAG_PREFIX)
+    }
+
+    pub fn tails_writer_type(&self) -> &str {
+        self.tails_writer_type.as_ref().map(String::as_str).unwrap_or(DEFAULT_POOL_TAILS_WRITER_TYPE)
+    }
+
+    pub fn registry_config(&self) -> RevocationRegistryConfig {
+        RevocationRegistryConfig {
+            issuance_type: self.issuance_type.clone(),
+            max_cred_num: self.max_cred_num,
+        }
+    }
+}
+
+impl Validatable for RevocationRegistryPoolConfig {
+    fn validate(&self) -> Result<(), String> {
+        if self.max_cred_num == Some(0) {
+            return Err(String::from("RevocationRegistryPoolConfig validation failed: `max_cred_num` must be greater than 0"));
+        }
+        if self.tag_prefix().is_empty() {
+            return Err(String::from("RevocationRegistryPoolConfig validation failed: `tag_prefix` must not be empty"));
+        }
+        Ok(())
+    }
+}
+
+/// Set of revocation registries the issuer rotates through for one credential definition.
+/// `active` is used for issuance, `next` is pre-provisioned and takes over once `active` is full.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct RevocationRegistryPool {
+    pub cred_def_id: CredentialDefinitionId,
+    pub issuer_did: DidValue,
+    pub config: RevocationRegistryPoolConfig,
+    pub active: RevocationRegistryId,
+    pub next: RevocationRegistryId,
+    pub registries: Vec<RevocationRegistryId>,
+    pub published: HashSet<RevocationRegistryId>,
+}
+
+impl RevocationRegistryPool {
+    pub fn contains(&self, rev_reg_id: &RevocationRegistryId) -> bool {
+        self.registries.contains(rev_reg_id)
+    }
+
+    pub fn is_published(&self, rev_reg_id: &RevocationRegistryId) -> bool {
+        self.published.contains(rev_reg_id)
+    }
+}
@@ -0,0 +1,28 @@
+use indy_api_types::validation::Validatable;
+
+#[derive(Debug, Default, Deserialize, Serialize)]
+pub struct TailsReaderConfig {
+    pub tails_dir: Option<String>,
+    pub fetcher_type: Option<String>,
+    pub fetcher_config: Option<serde_json::Value>,
+}
+
+impl TailsReaderConfig {
+    pub fn fetcher_config(&self) -> String {
+        self.fetcher_config.as_ref()
+            .map(|config| config.to_string())
+            .unwrap_or_else(|| String::from("{}"))
+    }
+}
+
+impl Validatable for TailsReaderConfig {
+    fn validate(&self) -> Result<(), String> {
+        if self.fetcher_config.is_some() && self.fetcher_type.is_none() {
+            return Err(String::from("TailsReaderConfig validation failed: `fetcher_config` requires `fetcher_type` to be set"));
+        }
+        if self.tails_dir.as_ref().map(String::is_empty).unwrap_or(false) {
+            return Err(String::from("TailsReaderConfig validation failed: `tails_dir` must not be empty"));
+        }
+        Ok(())
+    }
+}
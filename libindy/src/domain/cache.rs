@@ -11,4 +11,5 @@ pub struct GetCacheOptions {
     pub no_update: Option<bool>,    // Use only cached data, do not try to update.
     pub no_store: Option<bool>,     // Skip storing fresh data if updated
     pub min_fresh: Option<i32>,     // Return cached data if not older than this many seconds. -1 means do not check age.
+    pub ttl: Option<u64>,           // Time to live of stored data in seconds.
 }
\ No newline at end of file
@@ -11,7 +11,8 @@ use indy_api_types::validation::Validatable;
 pub struct IndyConfig {
     pub crypto_thread_pool_size: Option<usize>,
     pub collect_backtrace: Option<bool>,
-    pub freshness_threshold: Option<u64>
+    pub freshness_threshold: Option<u64>,
+    pub check_canonical_encoding: Option<bool>
 }
 
 impl Validatable for IndyConfig {}
\ No newline at end of file
@@ -1,7 +1,7 @@
 use indy_api_types::errors::prelude::*;
 
-use crate::domain::anoncreds::credential::AttributeValues;
-use crate::domain::anoncreds::proof_request::{AttributeInfo, PredicateInfo, NonRevocedInterval};
+use crate::domain::anoncreds::credential::{AttributeValues, RawCredentialValues, ShortCredentialValues};
+use crate::domain::anoncreds::proof_request::{AttributeInfo, PredicateInfo, PredicateTypes, NonRevocedInterval};
 use ursa::cl::{issuer, verifier, CredentialSchema, NonCredentialSchema, MasterSecret, CredentialValues, SubProofRequest};
 
 use crate::domain::crypto::did::DidValue;
@@ -17,6 +17,9 @@ use crate::domain::anoncreds::proof_request::ProofRequest;
 
 use std::collections::{HashSet, HashMap};
 
+use sha2::{Digest, Sha256};
+use ursa::bn::BigNumber;
+
 pub fn attr_common_view(attr: &str) -> String {
     attr.replace(" ", "").to_lowercase()
 }
@@ -65,6 +68,50 @@ pub fn build_credential_values(credential_values: &HashMap<String, AttributeValu
     Ok(res)
 }
 
+/// Canonical encoding of a raw credential attribute value:
+/// 32-bit integers are kept as is, any other value is encoded as SHA-256 hash converted to a big integer.
+pub fn encode_credential_value(raw: &str) -> IndyResult<String> {
+    if let Ok(value) = raw.parse::<i32>() {
+        return Ok(value.to_string());
+    }
+
+    let hash = Sha256::digest(raw.as_bytes());
+
+    let encoded = BigNumber::from_bytes(&hash)?.to_dec()?;
+
+    Ok(encoded)
+}
+
+pub fn encode_credential_values(raw_values: &ShortCredentialValues) -> IndyResult<HashMap<String, AttributeValues>> {
+    trace!("encode_credential_values >>> raw_values: {:?}", secret!(raw_values));
+
+    let mut res = HashMap::new();
+    for (attr, raw) in raw_values {
+        res.insert(attr.clone(), AttributeValues { raw: raw.clone(), encoded: encode_credential_value(raw)? });
+    }
+
+    trace!("encode_credential_values <<< res: {:?}", secret!(&res));
+
+    Ok(res)
+}
+
+pub fn complete_credential_values(cred_values: RawCredentialValues) -> IndyResult<HashMap<String, AttributeValues>> {
+    trace!("complete_credential_values >>> cred_values: {:?}", secret!(&cred_values));
+
+    let mut res = HashMap::new();
+    for (attr, values) in cred_values.0 {
+        let encoded = match values.encoded {
+            Some(encoded) => encoded,
+            None => encode_credential_value(&values.raw)?
+        };
+        res.insert(attr, AttributeValues { raw: values.raw, encoded });
+    }
+
+    trace!("complete_credential_values <<< res: {:?}", secret!(&res));
+
+    Ok(res)
+}
+
 pub fn build_sub_proof_request(attrs_for_credential: &[AttributeInfo],
                                predicates_for_credential: &[PredicateInfo]) -> IndyResult<SubProofRequest> {
     trace!("build_sub_proof_request >>> attrs_for_credential: {:?}, predicates_for_credential: {:?}", attrs_for_credential, predicates_for_credential);
@@ -87,9 +134,9 @@ pub fn build_sub_proof_request(attrs_for_credential: &[AttributeInfo],
     }
 
     for predicate in predicates_for_credential {
-        let p_type = format!("{}", predicate.p_type);
-
-        sub_proof_request_builder.add_predicate(&attr_common_view(&predicate.name), &p_type, predicate.p_value)?;
+        for (p_type, p_value) in expand_predicate(predicate)? {
+            sub_proof_request_builder.add_predicate(&attr_common_view(&predicate.name), &p_type, p_value)?;
+        }
     }
 
     let res = sub_proof_request_builder.finalize()?;
@@ -99,6 +146,61 @@ pub fn build_sub_proof_request(attrs_for_credential: &[AttributeInfo],
     Ok(res)
 }
 
+/// Predicates supported by CL signatures: `between` is proved as a pair of `GE` and `LE` predicates on the same attribute.
+pub fn expand_predicate(predicate: &PredicateInfo) -> IndyResult<Vec<(String, i32)>> {
+    match (&predicate.p_type, predicate.p_value_to) {
+        (PredicateTypes::Between, Some(p_value_to)) => {
+            Ok(vec![
+                (format!("{}", PredicateTypes::GE), predicate.p_value),
+                (format!("{}", PredicateTypes::LE), p_value_to),
+            ])
+        }
+        (PredicateTypes::Between, None) => {
+            Err(err_msg(IndyErrorKind::InvalidStructure, format!("`between` predicate for \"{}\" attribute doesn't contain `p_value_to`", predicate.name)))
+        }
+        (p_type, _) => Ok(vec![(format!("{}", p_type), predicate.p_value)])
+    }
+}
+
+/// Standard encoding of a calendar date for predicates: `YYYY-MM-DD` is encoded as `YYYYMMDD` integer,
+/// so dates are compared as numbers. Credential attribute holding a date should have this value as raw.
+pub fn encode_date(date: &str) -> IndyResult<i32> {
+    trace!("encode_date >>> date: {:?}", date);
+
+    let invalid_date = || err_msg(IndyErrorKind::InvalidStructure, format!("Invalid date: {:?}, expected format is YYYY-MM-DD", date));
+
+    let parts = date.split('-').collect::<Vec<&str>>();
+
+    if parts.len() != 3 || parts[0].len() != 4 || parts[1].len() != 2 || parts[2].len() != 2
+        || !parts.iter().all(|part| part.chars().all(|c| c.is_ascii_digit())) {
+        return Err(invalid_date());
+    }
+
+    let year = parts[0].parse::<i32>().map_err(|_| invalid_date())?;
+    let month = parts[1].parse::<i32>().map_err(|_| invalid_date())?;
+    let day = parts[2].parse::<i32>().map_err(|_| invalid_date())?;
+
+    let is_leap_year = (year % 4 == 0 && year % 100 != 0) || year % 400 == 0;
+
+    let days_in_month = match month {
+        1 | 3 | 5 | 7 | 8 | 10 | 12 => 31,
+        4 | 6 | 9 | 11 => 30,
+        2 if is_leap_year => 29,
+        2 => 28,
+        _ => return Err(invalid_date())
+    };
+
+    if year == 0 || day == 0 || day > days_in_month {
+        return Err(invalid_date());
+    }
+
+    let res = year * 10000 + month * 100 + day;
+
+    trace!("encode_date <<< res: {:?}", res);
+
+    Ok(res)
+}
+
 pub fn parse_cred_rev_id(cred_rev_id: &str) -> IndyResult<u32> {
     trace!("parse_cred_rev_id >>> cred_rev_id: {:?}", cred_rev_id);
 
@@ -187,6 +289,106 @@ mod tests {
         assert_eq!(None, res);
     }
 
+    mod encode_credential_value {
+        use super::*;
+
+        #[test]
+        fn encode_credential_value_works_for_int32() {
+            assert_eq!("1234", encode_credential_value("1234").unwrap());
+            assert_eq!("-1234", encode_credential_value("-1234").unwrap());
+            assert_eq!("1234", encode_credential_value("01234").unwrap());
+            assert_eq!("2147483647", encode_credential_value("2147483647").unwrap());
+            assert_eq!("-2147483648", encode_credential_value("-2147483648").unwrap());
+        }
+
+        #[test]
+        fn encode_credential_value_works_for_out_of_int32() {
+            assert_eq!("26221484005389514539852548961319751347124425277437769688639924217837557266135",
+                       encode_credential_value("2147483648").unwrap());
+            assert_eq!("68956915425095939579909400566452872085353864667122112803508671228696852865689",
+                       encode_credential_value("-2147483649").unwrap());
+        }
+
+        #[test]
+        fn encode_credential_value_works_for_string() {
+            assert_eq!("32770349619296211525721019403974704547883091481854305319049714074652726739013",
+                       encode_credential_value("Cat").unwrap());
+            assert_eq!("62838607218564353630028473473939957328943626306458686867332534889076311281879",
+                       encode_credential_value("0.0").unwrap());
+            assert_eq!("102987336249554097029535212322581322789799900648198034993379397001115665086549",
+                       encode_credential_value("").unwrap());
+        }
+
+        #[test]
+        fn complete_credential_values_keeps_provided_encoding() {
+            let cred_values: RawCredentialValues = serde_json::from_str(r#"{"name":{"raw":"Alex","encoded":"1139481716457488690172217916278103335"},"age":{"raw":"28"}}"#).unwrap();
+
+            let res = complete_credential_values(cred_values).unwrap();
+            assert_eq!("1139481716457488690172217916278103335", res["name"].encoded);
+            assert_eq!("28", res["age"].encoded);
+        }
+    }
+
+    mod encode_date {
+        use super::*;
+
+        #[test]
+        fn encode_date_works() {
+            assert_eq!(20000115, encode_date("2000-01-15").unwrap());
+            assert_eq!(19991231, encode_date("1999-12-31").unwrap());
+            assert_eq!(20000229, encode_date("2000-02-29").unwrap());
+        }
+
+        #[test]
+        fn encode_date_keeps_order() {
+            assert!(encode_date("1999-12-31").unwrap() < encode_date("2000-01-01").unwrap());
+            assert!(encode_date("2000-01-31").unwrap() < encode_date("2000-02-01").unwrap());
+        }
+
+        #[test]
+        fn encode_date_works_for_invalid_date() {
+            assert_kind!(IndyErrorKind::InvalidStructure, encode_date("2000-1-15"));
+            assert_kind!(IndyErrorKind::InvalidStructure, encode_date("2000/01/15"));
+            assert_kind!(IndyErrorKind::InvalidStructure, encode_date("2000-13-01"));
+            assert_kind!(IndyErrorKind::InvalidStructure, encode_date("1900-02-29"));
+            assert_kind!(IndyErrorKind::InvalidStructure, encode_date("+200-01-15"));
+            assert_kind!(IndyErrorKind::InvalidStructure, encode_date(""));
+        }
+    }
+
+    mod expand_predicate {
+        use super::*;
+
+        fn _predicate_info(p_type: PredicateTypes, p_value_to: Option<i32>) -> PredicateInfo {
+            PredicateInfo {
+                name: "dob".to_string(),
+                p_type,
+                p_value: 19900101,
+                p_value_to,
+                restrictions: None,
+                non_revoked: None,
+            }
+        }
+
+        #[test]
+        fn expand_predicate_works_for_between() {
+            let res = expand_predicate(&_predicate_info(PredicateTypes::Between, Some(20001231))).unwrap();
+            assert_eq!(vec![("GE".to_string(), 19900101), ("LE".to_string(), 20001231)], res);
+        }
+
+        #[test]
+        fn expand_predicate_works_for_simple_predicate() {
+            let res = expand_predicate(&_predicate_info(PredicateTypes::GT, None)).unwrap();
+            assert_eq!(vec![("GT".to_string(), 19900101)], res);
+        }
+
+        #[test]
+        fn expand_predicate_works_for_between_without_upper_bound() {
+            let res = expand_predicate(&_predicate_info(PredicateTypes::Between, None));
+            assert_kind!(IndyErrorKind::InvalidStructure, res);
+        }
+    }
+
     mod to_unqualified {
         use super::*;
 
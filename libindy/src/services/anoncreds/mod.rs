@@ -2,6 +2,7 @@ pub mod helpers;
 pub mod issuer;
 pub mod prover;
 pub mod verifier;
+pub mod validation;
 
 use crate::services::anoncreds::issuer::Issuer;
 use crate::services::anoncreds::prover::Prover;
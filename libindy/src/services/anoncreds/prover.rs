@@ -1,5 +1,5 @@
 use std::collections::hash_map::Entry;
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 
 use ursa::cl::{
     BlindedCredentialSecrets,
@@ -20,7 +20,8 @@ use crate::domain::anoncreds::credential_offer::CredentialOffer;
 use crate::domain::anoncreds::credential_request::CredentialRequestMetadata;
 use crate::domain::anoncreds::proof::{Identifier, Proof, RequestedProof, RevealedAttributeInfo, SubProofReferent, RevealedAttributeGroupInfo, AttributeValue};
 use crate::domain::anoncreds::proof_request::{PredicateInfo, PredicateTypes, ProofRequest, ProofRequestPayload, ProofRequestsVersion, RequestedAttributeInfo, RequestedPredicateInfo, ProofRequestExtraQuery};
-use crate::domain::anoncreds::requested_credential::ProvingCredentialKey;
+use crate::domain::anoncreds::credential_selection::{CandidateCredential, CredentialSelection, CredentialSelectionStrategy, UnmetReferents};
+use crate::domain::anoncreds::requested_credential::{ProvingCredentialKey, RequestedAttribute};
 use crate::domain::anoncreds::requested_credential::RequestedCredentials;
 use crate::domain::anoncreds::revocation_registry_definition::RevocationRegistryDefinitionV1;
 use crate::domain::anoncreds::revocation_state::RevocationState;
@@ -31,6 +32,7 @@ use crate::utils::wql::Query;
 use crate::services::anoncreds::verifier::Verifier;
 
 const ATTRIBUTE_EXISTENCE_MARKER: &str = "1";
+pub const CREDENTIAL_STORED_AT_TAG: &str = "stored_at";
 
 pub struct Prover {}
 
@@ -351,6 +353,13 @@ impl Prover {
                     .to_indy(IndyErrorKind::InvalidStructure, format!("Credential attribute value \"{:?}\" is invalid", attribute_value))?;
                 Ok(attribute_value < predicate.p_value)
             }
+            PredicateTypes::Between => {
+                let attribute_value = attribute_value.parse::<i32>()
+                    .to_indy(IndyErrorKind::InvalidStructure, format!("Credential attribute value \"{:?}\" is invalid", attribute_value))?;
+                let p_value_to = predicate.p_value_to
+                    .ok_or_else(|| err_msg(IndyErrorKind::InvalidStructure, "`between` predicate doesn't contain `p_value_to`"))?;
+                Ok(attribute_value >= predicate.p_value && attribute_value <= p_value_to)
+            }
         };
 
         trace!("attribute_satisfy_predicate <<< res: {:?}", res);
@@ -431,9 +440,9 @@ impl Prover {
         }
 
         for predicate in req_predicates_for_credential {
-            let p_type = format!("{}", predicate.predicate_info.p_type);
-
-            sub_proof_request_builder.add_predicate(&attr_common_view(&predicate.predicate_info.name), &p_type, predicate.predicate_info.p_value)?;
+            for (p_type, p_value) in expand_predicate(&predicate.predicate_info)? {
+                sub_proof_request_builder.add_predicate(&attr_common_view(&predicate.predicate_info.name), &p_type, p_value)?;
+            }
         }
 
         let sub_proof_request = sub_proof_request_builder.finalize()?;
@@ -587,6 +596,116 @@ impl Prover {
             _ => return Err(IndyError::from_msg(IndyErrorKind::InvalidStructure, "unsupported operator"))
         })
     }
+
+    pub fn select_credentials(&self,
+                              attrs: &HashMap<String, Vec<CandidateCredential>>,
+                              predicates: &HashMap<String, Vec<CandidateCredential>>,
+                              self_attested_attributes: HashMap<String, String>,
+                              strategy: CredentialSelectionStrategy) -> CredentialSelection {
+        trace!("select_credentials >>> attrs: {:?}, predicates: {:?}, self_attested_attributes: {:?}, strategy: {:?}",
+               attrs, predicates, self_attested_attributes, strategy);
+
+        let (selected_attrs, unmet_attrs, selected_predicates, unmet_predicates) = match strategy {
+            CredentialSelectionStrategy::FewestCredentials => {
+                let mut referents: Vec<(bool, &String, &Vec<CandidateCredential>)> =
+                    attrs.iter().map(|(referent, candidates)| (false, referent, candidates))
+                        .chain(predicates.iter().map(|(referent, candidates)| (true, referent, candidates)))
+                        .collect();
+
+                let mut selected_attrs: HashMap<String, CandidateCredential> = HashMap::new();
+                let mut selected_predicates: HashMap<String, CandidateCredential> = HashMap::new();
+
+                // Greedy set cover: take the credential satisfying the most of remaining referents
+                loop {
+                    let mut coverage: HashMap<&str, (usize, u64)> = HashMap::new();
+
+                    for (_, _, candidates) in referents.iter() {
+                        let cred_ids: HashSet<&str> = candidates.iter().map(|candidate| candidate.cred_id.as_str()).collect();
+
+                        for cred_id in cred_ids {
+                            let stored_at = candidates.iter()
+                                .find(|candidate| candidate.cred_id == cred_id)
+                                .map(|candidate| candidate.stored_at)
+                                .unwrap_or(0);
+
+                            coverage.entry(cred_id).or_insert((0, stored_at)).0 += 1;
+                        }
+                    }
+
+                    let cred_id = match coverage.into_iter().max_by_key(|(cred_id, (count, stored_at))| (*count, *stored_at, *cred_id)) {
+                        Some((cred_id, _)) => cred_id.to_string(),
+                        None => break
+                    };
+
+                    referents.retain(|(is_predicate, referent, candidates)| {
+                        match candidates.iter().find(|candidate| candidate.cred_id == cred_id) {
+                            Some(candidate) => {
+                                let selected = if *is_predicate { &mut selected_predicates } else { &mut selected_attrs };
+                                selected.insert(referent.to_string(), candidate.clone());
+                                false
+                            }
+                            None => true
+                        }
+                    });
+                }
+
+                let unmet_attrs: Vec<String> = attrs.keys().filter(|referent| !selected_attrs.contains_key(*referent)).cloned().collect();
+                let unmet_predicates: Vec<String> = predicates.keys().filter(|referent| !selected_predicates.contains_key(*referent)).cloned().collect();
+
+                (selected_attrs, unmet_attrs, selected_predicates, unmet_predicates)
+            }
+            _ => {
+                let (selected_attrs, unmet_attrs) = Self::_select_credential_per_referent(attrs, strategy);
+                let (selected_predicates, unmet_predicates) = Self::_select_credential_per_referent(predicates, strategy);
+
+                (selected_attrs, unmet_attrs, selected_predicates, unmet_predicates)
+            }
+        };
+
+        let mut unmet_referents = UnmetReferents {
+            requested_attributes: unmet_attrs,
+            requested_predicates: unmet_predicates,
+        };
+        unmet_referents.requested_attributes.sort();
+        unmet_referents.requested_predicates.sort();
+
+        let requested_credentials = RequestedCredentials {
+            self_attested_attributes,
+            requested_attributes: selected_attrs.into_iter()
+                .map(|(referent, candidate)| (referent, RequestedAttribute { cred_id: candidate.cred_id, timestamp: candidate.timestamp, revealed: true }))
+                .collect(),
+            requested_predicates: selected_predicates.into_iter()
+                .map(|(referent, candidate)| (referent, ProvingCredentialKey { cred_id: candidate.cred_id, timestamp: candidate.timestamp }))
+                .collect(),
+        };
+
+        let res = CredentialSelection { requested_credentials, unmet_referents };
+
+        trace!("select_credentials <<< res: {:?}", res);
+
+        res
+    }
+
+    fn _select_credential_per_referent(referents: &HashMap<String, Vec<CandidateCredential>>,
+                                       strategy: CredentialSelectionStrategy) -> (HashMap<String, CandidateCredential>, Vec<String>) {
+        let mut selected: HashMap<String, CandidateCredential> = HashMap::new();
+        let mut unmet: Vec<String> = Vec::new();
+
+        for (referent, candidates) in referents {
+            // `max_by_key` returns the last of equal elements so credentials stored later win on ties
+            let candidate = candidates.iter().max_by_key(|candidate| match strategy {
+                CredentialSelectionStrategy::PreferNonRevocable => (!candidate.revocable, candidate.stored_at),
+                _ => (true, candidate.stored_at)
+            });
+
+            match candidate {
+                Some(candidate) => { selected.insert(referent.to_string(), candidate.clone()); }
+                None => unmet.push(referent.to_string())
+            }
+        }
+
+        (selected, unmet)
+    }
 }
 
 #[cfg(test)]
@@ -752,6 +871,7 @@ mod tests {
                 name: "age".to_string(),
                 p_type: PredicateTypes::GE,
                 p_value: 8,
+                p_value_to: None,
                 restrictions: None,
                 non_revoked: None,
             }
@@ -777,6 +897,16 @@ mod tests {
             let res = ps.attribute_satisfy_predicate(&predicate_info(), "string");
             assert_kind!(IndyErrorKind::InvalidStructure, res);
         }
+
+        #[test]
+        fn attribute_satisfy_predicate_works_for_between() {
+            let ps = Prover::new();
+            let predicate = PredicateInfo { p_type: PredicateTypes::Between, p_value_to: Some(10), ..predicate_info() };
+            assert!(ps.attribute_satisfy_predicate(&predicate, "8").unwrap());
+            assert!(ps.attribute_satisfy_predicate(&predicate, "10").unwrap());
+            assert!(!ps.attribute_satisfy_predicate(&predicate, "7").unwrap());
+            assert!(!ps.attribute_satisfy_predicate(&predicate, "11").unwrap());
+        }
     }
 
     mod prepare_credentials_for_proving {
@@ -803,6 +933,7 @@ mod tests {
                 name: "age".to_string(),
                 p_type: PredicateTypes::GE,
                 p_value: 8,
+                p_value_to: None,
                 restrictions: None,
                 non_revoked: None,
             }
@@ -1252,4 +1383,93 @@ mod tests {
             assert_eq!(expected_query, query);
         }
     }
+
+    mod select_credentials {
+        use super::*;
+
+        fn _candidate(cred_id: &str, revocable: bool, stored_at: u64) -> CandidateCredential {
+            CandidateCredential {
+                cred_id: cred_id.to_string(),
+                revocable,
+                stored_at,
+                timestamp: if revocable { Some(100) } else { None },
+            }
+        }
+
+        #[test]
+        fn select_credentials_works_for_newest() {
+            let ps = Prover::new();
+
+            let attrs = hashmap!(
+                "attr1_referent".to_string() => vec![_candidate("old", false, 1), _candidate("new", true, 2)]
+            );
+
+            let selection = ps.select_credentials(&attrs, &HashMap::new(), HashMap::new(), CredentialSelectionStrategy::Newest);
+
+            let requested_attribute = &selection.requested_credentials.requested_attributes["attr1_referent"];
+            assert_eq!("new", requested_attribute.cred_id);
+            assert_eq!(Some(100), requested_attribute.timestamp);
+            assert!(requested_attribute.revealed);
+            assert_eq!(UnmetReferents::default(), selection.unmet_referents);
+        }
+
+        #[test]
+        fn select_credentials_works_for_prefer_non_revocable() {
+            let ps = Prover::new();
+
+            let predicates = hashmap!(
+                "predicate1_referent".to_string() => vec![_candidate("old", false, 1), _candidate("new", true, 2)]
+            );
+
+            let selection = ps.select_credentials(&HashMap::new(), &predicates, HashMap::new(), CredentialSelectionStrategy::PreferNonRevocable);
+
+            let requested_predicate = &selection.requested_credentials.requested_predicates["predicate1_referent"];
+            assert_eq!("old", requested_predicate.cred_id);
+            assert_eq!(None, requested_predicate.timestamp);
+        }
+
+        #[test]
+        fn select_credentials_works_for_fewest_credentials() {
+            let ps = Prover::new();
+
+            let attrs = hashmap!(
+                "attr1_referent".to_string() => vec![_candidate("cred1", false, 1), _candidate("cred2", false, 2)],
+                "attr2_referent".to_string() => vec![_candidate("cred1", false, 1), _candidate("cred3", false, 3)]
+            );
+            let predicates = hashmap!(
+                "predicate1_referent".to_string() => vec![_candidate("cred1", false, 1)]
+            );
+
+            let selection = ps.select_credentials(&attrs, &predicates, HashMap::new(), CredentialSelectionStrategy::FewestCredentials);
+
+            assert_eq!("cred1", selection.requested_credentials.requested_attributes["attr1_referent"].cred_id);
+            assert_eq!("cred1", selection.requested_credentials.requested_attributes["attr2_referent"].cred_id);
+            assert_eq!("cred1", selection.requested_credentials.requested_predicates["predicate1_referent"].cred_id);
+        }
+
+        #[test]
+        fn select_credentials_works_for_unmet_referents() {
+            let ps = Prover::new();
+
+            let attrs = hashmap!(
+                "attr1_referent".to_string() => vec![_candidate("cred1", false, 1)],
+                "attr2_referent".to_string() => vec![]
+            );
+            let predicates = hashmap!(
+                "predicate1_referent".to_string() => vec![]
+            );
+            let self_attested = hashmap!(
+                "attr3_referent".to_string() => "value".to_string()
+            );
+
+            let selection = ps.select_credentials(&attrs, &predicates, self_attested, CredentialSelectionStrategy::FewestCredentials);
+
+            assert_eq!(1, selection.requested_credentials.requested_attributes.len());
+            assert_eq!("value", selection.requested_credentials.self_attested_attributes["attr3_referent"]);
+            assert_eq!(UnmetReferents {
+                requested_attributes: vec!["attr2_referent".to_string()],
+                requested_predicates: vec!["predicate1_referent".to_string()],
+            }, selection.unmet_referents);
+        }
+    }
 }
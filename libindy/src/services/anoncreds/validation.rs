@@ -0,0 +1,563 @@
+use std::collections::{HashMap, HashSet};
+
+use serde::de::DeserializeOwned;
+use serde_json::Value;
+
+use indy_api_types::errors::prelude::*;
+use indy_api_types::validation::Validatable;
+
+use crate::domain::anoncreds::credential::Credential;
+use crate::domain::anoncreds::credential_definition::{CredentialDefinition, CredentialDefinitionId};
+use crate::domain::anoncreds::credential_offer::CredentialOffer;
+use crate::domain::anoncreds::credential_request::CredentialRequest;
+use crate::domain::anoncreds::proof::Proof;
+use crate::domain::anoncreds::proof_request::{NonRevocedInterval, ProofRequest};
+use crate::domain::anoncreds::revocation_registry_definition::{RevocationRegistryDefinition, RevocationRegistryId};
+use crate::domain::anoncreds::schema::{Schema, SchemaId, MAX_ATTRIBUTES_COUNT};
+use crate::services::anoncreds::helpers::attr_common_view;
+use crate::utils::qualifier;
+
+const MASTER_SECRET_ATTR: &str = "master_secret";
+
+/// Trusted objects the validated object is compared with.
+/// Attribute names are taken from the schema or, if only credential definition is known, from its public key.
+pub struct ValidationContext {
+    schema_id: Option<String>,
+    schema_seq_no: Option<u32>,
+    cred_def_id: Option<String>,
+    attr_names: Option<(&'static str, Vec<String>)>,
+}
+
+impl ValidationContext {
+    pub fn new(schema: Option<&Schema>, cred_def: Option<&CredentialDefinition>) -> ValidationContext {
+        let schema = schema.map(|schema| match schema {
+            Schema::SchemaV1(schema) => schema
+        });
+
+        let cred_def = cred_def.map(|cred_def| match cred_def {
+            CredentialDefinition::CredentialDefinitionV1(cred_def) => cred_def
+        });
+
+        let attr_names = match (schema, cred_def) {
+            (Some(schema), _) => {
+                let mut names: Vec<String> = schema.attr_names.0.iter().cloned().collect();
+                names.sort();
+                Some(("Schema", names))
+            }
+            (None, Some(cred_def)) => {
+                let primary = serde_json::to_value(&cred_def.value.primary).unwrap_or_default();
+                let mut names: Vec<String> = primary["r"].as_object()
+                    .map(|r| r.keys().filter(|name| name.as_str() != MASTER_SECRET_ATTR).cloned().collect())
+                    .unwrap_or_default();
+                names.sort();
+                Some(("Credential Definition", names))
+            }
+            (None, None) => None
+        };
+
+        ValidationContext {
+            schema_id: schema.map(|schema| schema.id.0.clone()),
+            schema_seq_no: schema.and_then(|schema| schema.seq_no),
+            cred_def_id: cred_def.map(|cred_def| cred_def.id.0.clone()),
+            attr_names,
+        }
+    }
+
+    fn check_schema_id(&self, problems: &mut Vec<String>, name: &str, schema_id: &str) {
+        if let Some(ref expected) = self.schema_id {
+            // credential definitions written to the ledger refer to the schema by its sequence number
+            if schema_id != expected && self.schema_seq_no.map(|seq_no| seq_no.to_string()) != Some(schema_id.to_string()) {
+                problems.push(format!("`{}` {:?} doesn't match Schema Id {:?}", name, schema_id, expected));
+            }
+        }
+    }
+
+    fn check_cred_def_id(&self, problems: &mut Vec<String>, name: &str, cred_def_id: &str) {
+        if let Some(ref expected) = self.cred_def_id {
+            if cred_def_id != expected {
+                problems.push(format!("`{}` {:?} doesn't match Credential Definition Id {:?}", name, cred_def_id, expected));
+            }
+        }
+    }
+
+    // reports names which are unknown to the context and, if `complete` is set, context names which are missing
+    fn check_attr_names(&self, problems: &mut Vec<String>, owner: &str, names: &[&String], complete: bool) {
+        let (source, expected) = match self.attr_names {
+            Some((source, ref expected)) => (source, expected),
+            None => return
+        };
+
+        let expected_common: HashSet<String> = expected.iter().map(|name| attr_common_view(name)).collect();
+        let names_common: HashSet<String> = names.iter().map(|name| attr_common_view(name)).collect();
+
+        for name in names {
+            if !expected_common.contains(&attr_common_view(name)) {
+                problems.push(format!("{} contains attribute {:?} which is not present in {}", owner, name, source));
+            }
+        }
+
+        if complete {
+            for name in expected {
+                if !names_common.contains(&attr_common_view(name)) {
+                    problems.push(format!("{} attribute {:?} is missing in {}", source, name, owner));
+                }
+            }
+        }
+    }
+}
+
+/// Checks anoncreds object received from an untrusted party and returns the list of found problems.
+/// Empty list means that the object passed all checks.
+///
+/// Identifiers and attribute names of the object are also compared with the context objects if they are set.
+/// Unparsable object is reported as a problem, only unknown `object_type` is an error.
+pub fn validate_object(object_type: &str, object_json: &str, context: &ValidationContext) -> IndyResult<Vec<String>> {
+    trace!("validate_object >>> object_type: {:?}, object_json: {:?}", object_type, secret!(object_json));
+
+    let problems = match object_type {
+        "schema" => _validate::<Schema>("Schema", object_json, context, _check_schema),
+        "cred_def" => _validate::<CredentialDefinition>("Credential Definition", object_json, context, _check_cred_def),
+        "rev_reg_def" => _validate::<RevocationRegistryDefinition>("Revocation Registry Definition", object_json, context, _check_rev_reg_def),
+        "cred_offer" => _validate::<CredentialOffer>("Credential Offer", object_json, context, _check_cred_offer),
+        "cred_req" => _validate::<CredentialRequest>("Credential Request", object_json, context, _check_cred_req),
+        "cred" => _validate::<Credential>("Credential", object_json, context, _check_credential),
+        "proof_req" => _validate::<ProofRequest>("Proof Request", object_json, context, _check_proof_req),
+        "proof" => _validate::<Proof>("Proof", object_json, context, _check_proof),
+        _ => return Err(err_msg(IndyErrorKind::InvalidStructure,
+                                format!("Unknown anoncreds object type: {}, expected one of: schema, cred_def, rev_reg_def, cred_offer, cred_req, cred, proof_req, proof", object_type)))
+    };
+
+    trace!("validate_object <<< problems: {:?}", problems);
+
+    Ok(problems)
+}
+
+fn _validate<T>(name: &str, object_json: &str, context: &ValidationContext, check: fn(&T, &Value, &ValidationContext) -> Vec<String>) -> Vec<String>
+    where T: DeserializeOwned + Validatable {
+    let value: Value = match serde_json::from_str(object_json) {
+        Ok(value) => value,
+        Err(err) => return vec![format!("{} is not a valid JSON: {}", name, err)]
+    };
+
+    let object: T = match serde_json::from_value(value.clone()) {
+        Ok(object) => object,
+        Err(err) => return vec![format!("{} has invalid structure: {}", name, err)]
+    };
+
+    let mut problems = Vec::new();
+
+    if let Err(err) = object.validate() {
+        problems.push(err);
+    }
+
+    for problem in check(&object, &value, context) {
+        if !problems.contains(&problem) {
+            problems.push(problem);
+        }
+    }
+
+    problems
+}
+
+fn _check_schema(schema: &Schema, value: &Value, _context: &ValidationContext) -> Vec<String> {
+    let mut problems = Vec::new();
+
+    let schema = match schema {
+        Schema::SchemaV1(schema) => schema
+    };
+
+    _check_id_prefix(&mut problems, "id", &schema.id.0, SchemaId::PREFIX);
+
+    // attribute names are deserialized into a set, so duplicates can be found only in the raw object
+    let raw_names = value["attrNames"].as_array().cloned().unwrap_or_default();
+
+    let mut seen: HashMap<String, String> = HashMap::new();
+    for name in raw_names.iter().filter_map(Value::as_str) {
+        if name.trim().is_empty() {
+            problems.push(String::from("Schema contains empty attribute name"));
+            continue;
+        }
+
+        if attr_common_view(name) == MASTER_SECRET_ATTR {
+            problems.push(format!("Schema attribute name {:?} is reserved", name));
+        }
+
+        match seen.get(&attr_common_view(name)) {
+            Some(prev) if prev == name => problems.push(format!("Schema attribute {:?} is duplicated", name)),
+            Some(prev) => problems.push(format!("Schema attributes {:?} and {:?} are the same attribute after case and whitespace normalization", prev, name)),
+            None => { seen.insert(attr_common_view(name), name.to_string()); }
+        }
+    }
+
+    if raw_names.len() > MAX_ATTRIBUTES_COUNT {
+        problems.push(format!("The number of Schema attributes {} cannot be greater than {}", raw_names.len(), MAX_ATTRIBUTES_COUNT));
+    }
+
+    problems
+}
+
+fn _check_cred_def(cred_def: &CredentialDefinition, value: &Value, context: &ValidationContext) -> Vec<String> {
+    let mut problems = Vec::new();
+
+    let cred_def = match cred_def {
+        CredentialDefinition::CredentialDefinitionV1(cred_def) => cred_def
+    };
+
+    _check_id_prefix(&mut problems, "id", &cred_def.id.0, CredentialDefinitionId::PREFIX);
+    _check_id_prefix(&mut problems, "schemaId", &cred_def.schema_id.0, SchemaId::PREFIX);
+    _check_ids_qualification(&mut problems, &[
+        ("id", Some(&cred_def.id.0)),
+        ("schemaId", Some(&cred_def.schema_id.0)),
+    ]);
+
+    if let Some((_, signature_type, _, tag)) = cred_def.id.parts() {
+        if signature_type != "CL" {
+            problems.push(format!("Credential Definition Id {:?} contains unsupported signature type: {}", cred_def.id.0, signature_type));
+        }
+        if !tag.is_empty() && tag != cred_def.tag {
+            problems.push(format!("Inconsistent Credential Definition Id and tag: {:?} and {:?}", cred_def.id.0, cred_def.tag));
+        }
+    }
+
+    match value["value"]["primary"]["r"].as_object() {
+        Some(r) => {
+            if !r.contains_key(MASTER_SECRET_ATTR) {
+                problems.push(String::from("Credential Definition public key doesn't contain `master_secret` attribute"));
+            }
+            if r.len() <= 1 {
+                problems.push(String::from("Credential Definition public key doesn't contain any schema attribute"));
+            }
+
+            let names: Vec<&String> = r.keys().filter(|name| name.as_str() != MASTER_SECRET_ATTR).collect();
+            context.check_attr_names(&mut problems, "Credential Definition", &names, true);
+        }
+        None => problems.push(String::from("Credential Definition public key doesn't contain attributes"))
+    }
+
+    context.check_schema_id(&mut problems, "schemaId", &cred_def.schema_id.0);
+    context.check_cred_def_id(&mut problems, "id", &cred_def.id.0);
+
+    problems
+}
+
+fn _check_rev_reg_def(rev_reg_def: &RevocationRegistryDefinition, _value: &Value, context: &ValidationContext) -> Vec<String> {
+    let mut problems = Vec::new();
+
+    let rev_reg_def = match rev_reg_def {
+        RevocationRegistryDefinition::RevocationRegistryDefinitionV1(rev_reg_def) => rev_reg_def
+    };
+
+    if let Err(err) = rev_reg_def.cred_def_id.validate() {
+        problems.push(err);
+    }
+
+    _check_id_prefix(&mut problems, "id", &rev_reg_def.id.0, RevocationRegistryId::PREFIX);
+    _check_id_prefix(&mut problems, "credDefId", &rev_reg_def.cred_def_id.0, CredentialDefinitionId::PREFIX);
+    _check_ids_qualification(&mut problems, &[
+        ("id", Some(&rev_reg_def.id.0)),
+        ("credDefId", Some(&rev_reg_def.cred_def_id.0)),
+    ]);
+
+    if let Some((_, cred_def_id, _, tag)) = rev_reg_def.id.parts() {
+        if cred_def_id != rev_reg_def.cred_def_id {
+            problems.push(format!("Inconsistent Revocation Registry Id and Credential Definition Id: {:?} and {:?}", rev_reg_def.id.0, rev_reg_def.cred_def_id.0));
+        }
+        if tag != rev_reg_def.tag {
+            problems.push(format!("Inconsistent Revocation Registry Id and tag: {:?} and {:?}", rev_reg_def.id.0, rev_reg_def.tag));
+        }
+    }
+
+    if rev_reg_def.value.max_cred_num == 0 {
+        problems.push(String::from("Revocation Registry Definition `maxCredNum` must be greater than 0"));
+    }
+
+    if rev_reg_def.value.tails_hash.is_empty() {
+        problems.push(String::from("Revocation Registry Definition `tailsHash` is empty"));
+    }
+
+    if rev_reg_def.value.tails_location.is_empty() {
+        problems.push(String::from("Revocation Registry Definition `tailsLocation` is empty"));
+    }
+
+    context.check_cred_def_id(&mut problems, "credDefId", &rev_reg_def.cred_def_id.0);
+
+    problems
+}
+
+fn _check_cred_offer(cred_offer: &CredentialOffer, _value: &Value, context: &ValidationContext) -> Vec<String> {
+    let mut problems = Vec::new();
+
+    _check_id_prefix(&mut problems, "schema_id", &cred_offer.schema_id.0, SchemaId::PREFIX);
+    _check_id_prefix(&mut problems, "cred_def_id", &cred_offer.cred_def_id.0, CredentialDefinitionId::PREFIX);
+    _check_ids_qualification(&mut problems, &[
+        ("schema_id", Some(&cred_offer.schema_id.0)),
+        ("cred_def_id", Some(&cred_offer.cred_def_id.0)),
+    ]);
+
+    if let Some(ref method_name) = cred_offer.method_name {
+        if cred_offer.cred_def_id.is_fully_qualified() {
+            problems.push(format!("Credential Offer `method_name` {:?} can be set only for unqualified identifiers", method_name));
+        }
+    }
+
+    context.check_schema_id(&mut problems, "schema_id", &cred_offer.schema_id.0);
+    context.check_cred_def_id(&mut problems, "cred_def_id", &cred_offer.cred_def_id.0);
+
+    problems
+}
+
+fn _check_cred_req(cred_req: &CredentialRequest, _value: &Value, context: &ValidationContext) -> Vec<String> {
+    let mut problems = Vec::new();
+
+    // prover DID isn't checked against the credential definition: it belongs to another party and may use another method
+    _check_id_prefix(&mut problems, "cred_def_id", &cred_req.cred_def_id.0, CredentialDefinitionId::PREFIX);
+
+    if let Some((_, signature_type, _, _)) = cred_req.cred_def_id.parts() {
+        if signature_type != "CL" {
+            problems.push(format!("Credential Definition Id {:?} contains unsupported signature type: {}", cred_req.cred_def_id.0, signature_type));
+        }
+    }
+
+    context.check_cred_def_id(&mut problems, "cred_def_id", &cred_req.cred_def_id.0);
+
+    problems
+}
+
+fn _check_credential(credential: &Credential, _value: &Value, context: &ValidationContext) -> Vec<String> {
+    let mut problems = Vec::new();
+
+    if let Some(ref rev_reg_id) = credential.rev_reg_id {
+        if let Err(err) = rev_reg_id.validate() {
+            problems.push(err);
+        }
+    }
+
+    _check_id_prefix(&mut problems, "schema_id", &credential.schema_id.0, SchemaId::PREFIX);
+    _check_id_prefix(&mut problems, "cred_def_id", &credential.cred_def_id.0, CredentialDefinitionId::PREFIX);
+    if let Some(ref rev_reg_id) = credential.rev_reg_id {
+        _check_id_prefix(&mut problems, "rev_reg_id", &rev_reg_id.0, RevocationRegistryId::PREFIX);
+    }
+    _check_ids_qualification(&mut problems, &[
+        ("schema_id", Some(&credential.schema_id.0)),
+        ("cred_def_id", Some(&credential.cred_def_id.0)),
+        ("rev_reg_id", credential.rev_reg_id.as_ref().map(|id| &id.0)),
+    ]);
+
+    if credential.rev_reg_id.is_none() && (credential.witness.is_some() || credential.rev_reg.is_some()) {
+        problems.push(String::from("Credential contains `witness` or `rev_reg` but `rev_reg_id` isn't set"));
+    }
+
+    let mut names: Vec<&String> = credential.values.0.keys().collect();
+    names.sort();
+
+    context.check_schema_id(&mut problems, "schema_id", &credential.schema_id.0);
+    context.check_cred_def_id(&mut problems, "cred_def_id", &credential.cred_def_id.0);
+    context.check_attr_names(&mut problems, "Credential", &names, true);
+
+    let mut seen: HashSet<String> = HashSet::new();
+    for name in names {
+        if !seen.insert(attr_common_view(name)) {
+            problems.push(format!("Credential attribute {:?} is duplicated after case and whitespace normalization", name));
+        }
+
+        if attr_common_view(name) == MASTER_SECRET_ATTR {
+            problems.push(format!("Credential attribute name {:?} is reserved", name));
+        }
+
+        let attr_value = &credential.values.0[name];
+        if !_is_decimal(&attr_value.encoded) {
+            problems.push(format!("Credential attribute {:?} has encoded value which is not a decimal number: {:?}", name, attr_value.encoded));
+        } else if let Ok(raw) = attr_value.raw.parse::<i32>() {
+            // custom encodings are allowed for strings, but predicates rely on integers being encoded as is
+            if attr_value.encoded.parse::<i64>().ok() != Some(i64::from(raw)) {
+                problems.push(format!("Credential attribute {:?} has integer raw value {:?} which is encoded as a different number", name, attr_value.raw));
+            }
+        }
+    }
+
+    problems
+}
+
+fn _check_proof_req(proof_req: &ProofRequest, _value: &Value, context: &ValidationContext) -> Vec<String> {
+    let mut problems = Vec::new();
+
+    let payload = proof_req.value();
+
+    if let Some(ref interval) = payload.non_revoked {
+        _check_interval(&mut problems, "Proof Request", interval);
+    }
+
+    let mut referents: Vec<&String> = payload.requested_attributes.keys().collect();
+    referents.sort();
+
+    for referent in referents {
+        let attr_info = &payload.requested_attributes[referent];
+
+        let names = attr_info.names.clone()
+            .unwrap_or_else(|| attr_info.name.clone().into_iter().collect());
+
+        let mut seen: HashSet<String> = HashSet::new();
+        for name in names.iter() {
+            if name.trim().is_empty() {
+                problems.push(format!("Requested attribute {:?} contains empty attribute name", referent));
+            } else if attr_common_view(name) == MASTER_SECRET_ATTR {
+                problems.push(format!("Requested attribute {:?} requests reserved attribute {:?}", referent, name));
+            }
+
+            if !seen.insert(attr_common_view(name)) {
+                problems.push(format!("Requested attribute {:?} contains attribute {:?} more than once", referent, name));
+            }
+        }
+
+        context.check_attr_names(&mut problems, &format!("Requested attribute {:?}", referent), &names.iter().collect::<Vec<&String>>(), false);
+
+        if let Some(ref interval) = attr_info.non_revoked {
+            _check_interval(&mut problems, &format!("Requested attribute {:?}", referent), interval);
+        }
+    }
+
+    let mut referents: Vec<&String> = payload.requested_predicates.keys().collect();
+    referents.sort();
+
+    for referent in referents {
+        let predicate_info = &payload.requested_predicates[referent];
+
+        if attr_common_view(&predicate_info.name) == MASTER_SECRET_ATTR {
+            problems.push(format!("Requested predicate {:?} requests reserved attribute {:?}", referent, predicate_info.name));
+        }
+
+        context.check_attr_names(&mut problems, &format!("Requested predicate {:?}", referent), &[&predicate_info.name], false);
+
+        if let Some(ref interval) = predicate_info.non_revoked {
+            _check_interval(&mut problems, &format!("Requested predicate {:?}", referent), interval);
+        }
+    }
+
+    problems
+}
+
+fn _check_proof(proof: &Proof, value: &Value, context: &ValidationContext) -> Vec<String> {
+    let mut problems = Vec::new();
+
+    // proof of self attested attributes only has no identifiers
+    if proof.identifiers.is_empty() && proof.requested_proof.self_attested_attrs.is_empty() {
+        problems.push(String::from("Proof doesn't contain any identifiers"));
+    }
+
+    for (index, identifier) in proof.identifiers.iter().enumerate() {
+        if let Err(err) = identifier.schema_id.validate() {
+            problems.push(format!("Identifier {}: {}", index, err));
+        }
+        if let Err(err) = identifier.cred_def_id.validate() {
+            problems.push(format!("Identifier {}: {}", index, err));
+        }
+        if let Some(ref rev_reg_id) = identifier.rev_reg_id {
+            if let Err(err) = rev_reg_id.validate() {
+                problems.push(format!("Identifier {}: {}", index, err));
+            }
+        }
+
+        let mut identifier_problems = Vec::new();
+        _check_id_prefix(&mut identifier_problems, "schema_id", &identifier.schema_id.0, SchemaId::PREFIX);
+        _check_id_prefix(&mut identifier_problems, "cred_def_id", &identifier.cred_def_id.0, CredentialDefinitionId::PREFIX);
+        if let Some(ref rev_reg_id) = identifier.rev_reg_id {
+            _check_id_prefix(&mut identifier_problems, "rev_reg_id", &rev_reg_id.0, RevocationRegistryId::PREFIX);
+        }
+        _check_ids_qualification(&mut identifier_problems, &[
+            ("schema_id", Some(&identifier.schema_id.0)),
+            ("cred_def_id", Some(&identifier.cred_def_id.0)),
+            ("rev_reg_id", identifier.rev_reg_id.as_ref().map(|id| &id.0)),
+        ]);
+        problems.extend(identifier_problems.into_iter().map(|problem| format!("Identifier {}: {}", index, problem)));
+
+        if identifier.timestamp.is_some() && identifier.rev_reg_id.is_none() {
+            problems.push(format!("Identifier {}: `timestamp` is set but `rev_reg_id` isn't", index));
+        }
+
+        // a proof may combine credentials of several schemas, so only sub proofs of the context schema are compared
+        let is_context_sub_proof = context.schema_id.as_ref() == Some(&identifier.schema_id.0) ||
+            context.cred_def_id.as_ref() == Some(&identifier.cred_def_id.0);
+
+        if is_context_sub_proof {
+            let revealed_attrs = value["proof"]["proofs"][index]["primary_proof"]["eq_proof"]["revealed_attrs"].as_object();
+            let mut names: Vec<&String> = revealed_attrs.map(|attrs| attrs.keys().collect()).unwrap_or_default();
+            names.sort();
+            context.check_attr_names(&mut problems, &format!("Sub proof {}", index), &names, false);
+        }
+    }
+
+    let requested_proof = &proof.requested_proof;
+    let identifiers_count = proof.identifiers.len() as u32;
+
+    let mut sub_proof_indexes: Vec<(String, u32)> = Vec::new();
+    sub_proof_indexes.extend(requested_proof.revealed_attrs.iter().map(|(referent, info)| (referent.clone(), info.sub_proof_index)));
+    sub_proof_indexes.extend(requested_proof.revealed_attr_groups.iter().map(|(referent, info)| (referent.clone(), info.sub_proof_index)));
+    sub_proof_indexes.extend(requested_proof.unrevealed_attrs.iter().map(|(referent, info)| (referent.clone(), info.sub_proof_index)));
+    sub_proof_indexes.extend(requested_proof.predicates.iter().map(|(referent, info)| (referent.clone(), info.sub_proof_index)));
+    sub_proof_indexes.sort();
+
+    for (referent, sub_proof_index) in sub_proof_indexes {
+        if sub_proof_index >= identifiers_count {
+            problems.push(format!("Referent {:?} points to sub proof {} but proof contains only {} identifiers", referent, sub_proof_index, identifiers_count));
+        }
+    }
+
+    let mut encoded_values: Vec<(&String, &String)> = requested_proof.revealed_attrs.iter()
+        .map(|(referent, info)| (referent, &info.encoded))
+        .collect();
+    encoded_values.extend(requested_proof.revealed_attr_groups.iter()
+        .flat_map(|(referent, info)| info.values.values().map(move |value| (referent, &value.encoded))));
+    encoded_values.sort();
+
+    for (referent, encoded) in encoded_values {
+        if !_is_decimal(encoded) {
+            problems.push(format!("Referent {:?} has encoded value which is not a decimal number: {:?}", referent, encoded));
+        }
+    }
+
+    problems
+}
+
+fn _check_interval(problems: &mut Vec<String>, owner: &str, interval: &NonRevocedInterval) {
+    match (interval.from, interval.to) {
+        (Some(from), Some(to)) if from > to => {
+            problems.push(format!("{} non-revoked interval is invalid: `from` {} is greater than `to` {}", owner, from, to));
+        }
+        (None, None) => {
+            problems.push(format!("{} non-revoked interval is empty: neither `from` nor `to` is set", owner));
+        }
+        _ => {}
+    }
+}
+
+fn _check_id_prefix(problems: &mut Vec<String>, name: &str, id: &str, prefix: &str) {
+    if qualifier::is_fully_qualified(id) && !id.starts_with(&format!("{}:", prefix)) {
+        problems.push(format!("Fully qualified `{}` {:?} must start with {:?} prefix", name, id, prefix));
+    }
+}
+
+// identifiers of one object must be either all fully qualified or all unqualified
+fn _check_ids_qualification(problems: &mut Vec<String>, ids: &[(&str, Option<&String>)]) {
+    let ids: Vec<(&str, &String)> = ids.iter()
+        .filter_map(|&(name, id)| id.map(|id| (name, id)))
+        .collect();
+
+    let qualified: Vec<&str> = ids.iter()
+        .filter(|(_, id)| qualifier::is_fully_qualified(id))
+        .map(|&(name, _)| name)
+        .collect();
+
+    if !qualified.is_empty() && qualified.len() != ids.len() {
+        let unqualified: Vec<&str> = ids.iter()
+            .filter(|(_, id)| !qualifier::is_fully_qualified(id))
+            .map(|&(name, _)| name)
+            .collect();
+
+        problems.push(format!("Mix of fully qualified ({}) and unqualified ({}) identifiers", qualified.join(", "), unqualified.join(", ")));
+    }
+}
+
+fn _is_decimal(value: &str) -> bool {
+    let digits = if value.starts_with('-') { &value[1..] } else { value };
+    !digits.is_empty() && digits.chars().all(|c| c.is_ascii_digit())
+}
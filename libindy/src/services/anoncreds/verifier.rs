@@ -1,8 +1,10 @@
 use std::collections::{HashMap, HashSet};
+use std::sync::Mutex;
 
 use crate::domain::anoncreds::credential_definition::{CredentialDefinitionV1, CredentialDefinitionId};
-use crate::domain::anoncreds::proof::{Proof, RequestedProof, Identifier, RevealedAttributeInfo};
+use crate::domain::anoncreds::proof::{Proof, RequestedProof, Identifier, RevealedAttributeInfo, RevealedAttributeGroupInfo};
 use crate::domain::anoncreds::proof_request::{AttributeInfo, PredicateInfo, ProofRequestPayload, NonRevocedInterval};
+use crate::domain::anoncreds::proof_verification_report::{CheckResult, ProofVerificationReport, ReferentReport, SubProofReport, VerificationStatus};
 use crate::domain::anoncreds::revocation_registry::RevocationRegistryV1;
 use crate::domain::anoncreds::revocation_registry_definition::{RevocationRegistryDefinitionV1, RevocationRegistryId};
 use crate::domain::anoncreds::schema::{SchemaV1, SchemaId};
@@ -11,9 +13,10 @@ use crate::services::anoncreds::helpers::*;
 
 use ursa::bn::BigNumber;
 use ursa::cl::{CredentialPublicKey, new_nonce, Nonce};
-use ursa::cl::verifier::Verifier as CryptoVerifier;
+use ursa::cl::verifier::{ProofVerifier, Verifier as CryptoVerifier};
 use crate::utils::wql::Query;
 use regex::Regex;
+use failure::Fail;
 
 #[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
 pub struct Filter {
@@ -28,6 +31,15 @@ pub struct Filter {
 lazy_static! {
     pub static ref VALUE_TAG_MATCHER: Regex = Regex::new("^attr::([^:]+)::value$").unwrap();
     pub static ref MARKER_TAG_MATCHER: Regex = Regex::new("^attr::([^:]+)::marker$").unwrap();
+    static ref CANONICAL_ENCODING_CHECK: Mutex<bool> = Mutex::new(false);
+}
+
+pub fn set_canonical_encoding_check(enabled: bool) {
+    *CANONICAL_ENCODING_CHECK.lock().unwrap() = enabled;
+}
+
+fn is_canonical_encoding_check_enabled() -> bool {
+    *CANONICAL_ENCODING_CHECK.lock().unwrap()
 }
 
 pub struct Verifier {}
@@ -60,6 +72,10 @@ impl Verifier {
 
         Verifier::_verify_revealed_attribute_values(&proof_req, &full_proof)?;
 
+        if is_canonical_encoding_check_enabled() {
+            Verifier::_verify_revealed_attribute_encodings(&full_proof.requested_proof)?;
+        }
+
         Verifier::_verify_requested_restrictions(&proof_req,
                                                  &full_proof.requested_proof,
                                                  &received_revealed_attrs,
@@ -73,38 +89,265 @@ impl Verifier {
                                                              &received_self_attested_attrs,
                                                              &received_predicates)?;
 
-        let mut proof_verifier = CryptoVerifier::new_proof_verifier()?;
-        let non_credential_schema = build_non_credential_schema()?;
+        let mut proof_verifier = Verifier::_build_proof_verifier(full_proof, proof_req, schemas, cred_defs, rev_reg_defs, rev_regs)?;
 
-        for sub_proof_index in 0..full_proof.identifiers.len() {
-            let identifier = full_proof.identifiers[sub_proof_index].clone();
+        let valid = proof_verifier.verify(&full_proof.proof, &proof_req.nonce)?;
+
+        trace!("verify <<< valid: {:?}", valid);
+
+        Ok(valid)
+    }
+
+    pub fn verify_with_report(&self,
+                              full_proof: &Proof,
+                              proof_req: &ProofRequestPayload,
+                              schemas: &HashMap<SchemaId, SchemaV1>,
+                              cred_defs: &HashMap<CredentialDefinitionId, CredentialDefinitionV1>,
+                              rev_reg_defs: &HashMap<RevocationRegistryId, RevocationRegistryDefinitionV1>,
+                              rev_regs: &HashMap<RevocationRegistryId, HashMap<u64, RevocationRegistryV1>>) -> IndyResult<ProofVerificationReport> {
+        trace!("verify_with_report >>> full_proof: {:?}, proof_req: {:?}, schemas: {:?}, cred_defs: {:?}, rev_reg_defs: {:?} rev_regs: {:?}",
+               full_proof, proof_req, schemas, cred_defs, rev_reg_defs, rev_regs);
+
+        let requested_proof = &full_proof.requested_proof;
+
+        // Unlike `verify` referents pointing to unknown sub proofs are not fatal here, they are reported per referent
+        let identifier = |sub_proof_index: u32| Verifier::_get_proof_identifier(full_proof, sub_proof_index).ok();
+
+        let received_revealed_attrs: HashMap<String, Identifier> = requested_proof.revealed_attrs
+            .iter()
+            .map(|(referent, info)| (referent, info.sub_proof_index))
+            .chain(requested_proof.revealed_attr_groups.iter().map(|(referent, info)| (referent, info.sub_proof_index)))
+            .filter_map(|(referent, sub_proof_index)| identifier(sub_proof_index).map(|id| (referent.to_string(), id)))
+            .collect();
+
+        let received_unrevealed_attrs: HashMap<String, Identifier> = requested_proof.unrevealed_attrs
+            .iter()
+            .filter_map(|(referent, info)| identifier(info.sub_proof_index).map(|id| (referent.to_string(), id)))
+            .collect();
+
+        let received_predicates: HashMap<String, Identifier> = requested_proof.predicates
+            .iter()
+            .filter_map(|(referent, info)| identifier(info.sub_proof_index).map(|id| (referent.to_string(), id)))
+            .collect();
+
+        let received_self_attested_attrs: HashSet<String> = Verifier::_received_self_attested_attrs(&full_proof);
+
+        let proof_attr_identifiers: HashMap<String, Identifier> = received_revealed_attrs
+            .iter()
+            .chain(&received_unrevealed_attrs)
+            .map(|(referent, id)| (referent.to_string(), id.clone()))
+            .collect();
+
+        let requested_attrs: HashMap<String, AttributeInfo> = proof_req.requested_attributes
+            .iter()
+            .filter(|&(referent, info)| !Verifier::_is_self_attested(&referent, &info, &received_self_attested_attrs))
+            .map(|(referent, info)| (referent.to_string(), info.clone()))
+            .collect();
+
+        let mut proof_invalid = false;
+        let mut request_not_satisfied = false;
+
+        // credentials could be issued with a custom encoding, so the encoding check is informational unless enabled
+        let encoding_check_enabled = is_canonical_encoding_check_enabled();
+
+        let mut requested_attributes: HashMap<String, ReferentReport> = HashMap::new();
+
+        for (referent, info) in proof_req.requested_attributes.iter() {
+            let sub_proof_index = requested_proof.revealed_attrs.get(referent).map(|info| info.sub_proof_index)
+                .or_else(|| requested_proof.revealed_attr_groups.get(referent).map(|info| info.sub_proof_index))
+                .or_else(|| requested_proof.unrevealed_attrs.get(referent).map(|info| info.sub_proof_index));
+            let self_attested = received_self_attested_attrs.contains(referent);
+
+            let received = match sub_proof_index {
+                Some(sub_proof_index) => Verifier::_check(Verifier::_get_proof_identifier(full_proof, sub_proof_index).map(|_| ())),
+                None if self_attested => CheckResult::passed(),
+                None => CheckResult::failed(format!("Attribute with referent \"{}\" not found in Proof", referent)),
+            };
+
+            if !received.passed {
+                if sub_proof_index.is_some() { proof_invalid = true } else { request_not_satisfied = true }
+            }
+
+            let (values, encoding) = if let Some(attr_info) = requested_proof.revealed_attrs.get(referent) {
+                (Some(Verifier::_check(Verifier::_verify_revealed_attribute(proof_req, full_proof, referent, attr_info))),
+                 Some(Verifier::_check(Verifier::_verify_canonical_encoding(&attr_info.raw, &attr_info.encoded))))
+            } else if let Some(attr_infos) = requested_proof.revealed_attr_groups.get(referent) {
+                (Some(Verifier::_check(Verifier::_verify_revealed_attribute_group(proof_req, full_proof, referent, attr_infos))),
+                 Some(Verifier::_check(attr_infos.values
+                     .values()
+                     .map(|value| Verifier::_verify_canonical_encoding(&value.raw, &value.encoded))
+                     .collect::<IndyResult<Vec<()>>>()
+                     .map(|_| ()))))
+            } else { (None, None) };
+
+            proof_invalid |= CheckResult::is_failed(&values) || (encoding_check_enabled && CheckResult::is_failed(&encoding));
+
+            let restrictions = match info.restrictions {
+                Some(ref query) if requested_attrs.contains_key(referent) && received.passed =>
+                    Some(Verifier::_check(Verifier::_verify_attribute_restrictions(proof_req, requested_proof, referent, info, query, &proof_attr_identifiers))),
+                _ => None
+            };
+
+            let timestamp = if received.passed && get_non_revoc_interval(&proof_req.non_revoked, &info.non_revoked).is_some() {
+                Some(Verifier::_check(
+                    Verifier::_validate_timestamp(&received_revealed_attrs, referent, &proof_req.non_revoked, &info.non_revoked)
+                        .or_else(|_| Verifier::_validate_timestamp(&received_unrevealed_attrs, referent, &proof_req.non_revoked, &info.non_revoked))
+                        .or_else(|_| received_self_attested_attrs.get(referent).map(|_| ()).ok_or_else(|| IndyError::from(IndyErrorKind::InvalidStructure)))
+                        .map_err(|_| err_msg(IndyErrorKind::InvalidStructure, "Timestamp is required by non-revocation interval but not found in Proof"))))
+            } else { None };
+
+            request_not_satisfied |= CheckResult::is_failed(&restrictions) || CheckResult::is_failed(&timestamp);
+
+            requested_attributes.insert(referent.to_string(), ReferentReport { sub_proof_index, self_attested, received, values, encoding, restrictions, timestamp });
+        }
+
+        let unrequested_attrs = requested_proof.revealed_attrs.keys()
+            .chain(requested_proof.revealed_attr_groups.keys())
+            .chain(requested_proof.unrevealed_attrs.keys())
+            .chain(requested_proof.self_attested_attrs.keys())
+            .filter(|referent| !proof_req.requested_attributes.contains_key(*referent))
+            .cloned()
+            .collect::<HashSet<String>>();
+
+        for referent in unrequested_attrs {
+            request_not_satisfied = true;
+            requested_attributes.insert(referent.clone(), ReferentReport {
+                sub_proof_index: None,
+                self_attested: received_self_attested_attrs.contains(&referent),
+                received: CheckResult::failed(format!("Attribute with referent \"{}\" not found in ProofRequest", referent)),
+                values: None,
+                encoding: None,
+                restrictions: None,
+                timestamp: None,
+            });
+        }
+
+        let mut requested_predicates: HashMap<String, ReferentReport> = HashMap::new();
+
+        for (referent, info) in proof_req.requested_predicates.iter() {
+            let sub_proof_index = requested_proof.predicates.get(referent).map(|info| info.sub_proof_index);
+
+            let received = match sub_proof_index {
+                Some(sub_proof_index) => Verifier::_check(Verifier::_get_proof_identifier(full_proof, sub_proof_index).map(|_| ())),
+                None => CheckResult::failed(format!("Predicate with referent \"{}\" not found in Proof", referent)),
+            };
+
+            if !received.passed {
+                if sub_proof_index.is_some() { proof_invalid = true } else { request_not_satisfied = true }
+            }
 
-            let schema: &SchemaV1 = schemas.get(&identifier.schema_id)
-                .ok_or_else(|| err_msg(IndyErrorKind::InvalidStructure, format!("Schema not found for id: {:?}", identifier.schema_id)))?;
+            let restrictions = match info.restrictions {
+                Some(ref query) if received.passed =>
+                    Some(Verifier::_check(Verifier::_verify_predicate_restrictions(requested_proof, referent, info, query, &received_predicates, &requested_attrs))),
+                _ => None
+            };
 
-            let cred_def: &CredentialDefinitionV1 = cred_defs.get(&identifier.cred_def_id)
-                .ok_or_else(|| err_msg(IndyErrorKind::InvalidStructure, format!("CredentialDefinition not found for id: {:?}", identifier.cred_def_id)))?;
+            let timestamp = if received.passed && get_non_revoc_interval(&proof_req.non_revoked, &info.non_revoked).is_some() {
+                Some(Verifier::_check(
+                    Verifier::_validate_timestamp(&received_predicates, referent, &proof_req.non_revoked, &info.non_revoked)
+                        .map_err(|_| err_msg(IndyErrorKind::InvalidStructure, "Timestamp is required by non-revocation interval but not found in Proof"))))
+            } else { None };
 
-            let (rev_reg_def, rev_reg) =
-                if let Some(timestamp) = identifier.timestamp {
-                    let rev_reg_id = identifier.rev_reg_id
-                        .clone()
-                        .ok_or_else(|| err_msg(IndyErrorKind::InvalidStructure, "Revocation Registry Id not found"))?;
+            request_not_satisfied |= CheckResult::is_failed(&restrictions) || CheckResult::is_failed(&timestamp);
 
-                    let rev_reg_def = Some(rev_reg_defs
-                        .get(&rev_reg_id)
-                        .ok_or_else(|| err_msg(IndyErrorKind::InvalidStructure, format!("RevocationRegistryDefinition not found for id: {:?}", identifier.rev_reg_id)))?);
+            requested_predicates.insert(referent.to_string(), ReferentReport { sub_proof_index, self_attested: false, received, values: None, encoding: None, restrictions, timestamp });
+        }
+
+        for referent in requested_proof.predicates.keys().filter(|referent| !proof_req.requested_predicates.contains_key(*referent)) {
+            request_not_satisfied = true;
+            requested_predicates.insert(referent.to_string(), ReferentReport {
+                sub_proof_index: None,
+                self_attested: false,
+                received: CheckResult::failed(format!("Predicate with referent \"{}\" not found in ProofRequest", referent)),
+                values: None,
+                encoding: None,
+                restrictions: None,
+                timestamp: None,
+            });
+        }
+
+        let mut sub_proofs: Vec<SubProofReport> = full_proof.identifiers
+            .iter()
+            .map(|identifier| SubProofReport {
+                schema_id: identifier.schema_id.clone(),
+                cred_def_id: identifier.cred_def_id.clone(),
+                rev_reg_id: identifier.rev_reg_id.clone(),
+                timestamp: identifier.timestamp,
+                ledger_data: Verifier::_check(Verifier::_get_ledger_data(identifier, schemas, cred_defs).map(|_| ())),
+                non_revoked: identifier.timestamp
+                    .map(|_| Verifier::_check(Verifier::_get_revocation_data(identifier, rev_reg_defs, rev_regs).map(|_| ()))),
+            })
+            .collect();
 
-                    let rev_regs_for_cred = rev_regs
-                        .get(&rev_reg_id)
-                        .ok_or_else(|| err_msg(IndyErrorKind::InvalidStructure, format!("RevocationRegistry not found for id: {:?}", rev_reg_id)))?;
+        let missing_ledger_data = sub_proofs
+            .iter()
+            .any(|sub_proof| !sub_proof.ledger_data.passed || CheckResult::is_failed(&sub_proof.non_revoked));
+
+        // Crypto verification can't be done without all ledger data
+        let crypto = if missing_ledger_data { None } else {
+            let valid = Verifier::_build_proof_verifier(full_proof, proof_req, schemas, cred_defs, rev_reg_defs, rev_regs)
+                .and_then(|mut proof_verifier| Ok(proof_verifier.verify(&full_proof.proof, &proof_req.nonce)?))
+                .and_then(|valid| if valid { Ok(()) } else { Err(err_msg(IndyErrorKind::ProofRejected, "Cryptographic verification of Proof failed")) });
+            Some(Verifier::_check(valid))
+        };
+
+        proof_invalid |= CheckResult::is_failed(&crypto);
+
+        // Non-revocation proofs are part of the aggregated proof, so they can be confirmed only with the whole proof
+        if CheckResult::is_failed(&crypto) {
+            for non_revoked in sub_proofs.iter_mut().filter_map(|sub_proof| sub_proof.non_revoked.as_mut()) {
+                *non_revoked = CheckResult::failed("Non-revocation can't be confirmed as cryptographic verification of Proof failed".to_string());
+            }
+        }
 
-                    let rev_reg = Some(rev_regs_for_cred
-                        .get(&timestamp)
-                        .ok_or_else(|| err_msg(IndyErrorKind::InvalidStructure, format!("RevocationRegistry not found for timestamp: {:?}", timestamp)))?);
+        let status = if proof_invalid {
+            VerificationStatus::ProofInvalid
+        } else if missing_ledger_data {
+            VerificationStatus::MissingLedgerData
+        } else if request_not_satisfied {
+            VerificationStatus::RequestNotSatisfied
+        } else {
+            VerificationStatus::Valid
+        };
 
-                    (rev_reg_def, rev_reg)
-                } else { (None, None) };
+        let report = ProofVerificationReport {
+            valid: status == VerificationStatus::Valid,
+            status,
+            requested_attributes,
+            requested_predicates,
+            sub_proofs,
+            crypto,
+        };
+
+        trace!("verify_with_report <<< report: {:?}", report);
+
+        Ok(report)
+    }
+
+    pub fn generate_nonce(&self) -> IndyResult<Nonce> {
+        trace!("generate_nonce >>> ");
+
+        let nonce = new_nonce()?;
+
+        trace!("generate_nonce <<< nonce: {:?} ", nonce);
+
+        Ok(nonce)
+    }
+
+    fn _build_proof_verifier(full_proof: &Proof,
+                             proof_req: &ProofRequestPayload,
+                             schemas: &HashMap<SchemaId, SchemaV1>,
+                             cred_defs: &HashMap<CredentialDefinitionId, CredentialDefinitionV1>,
+                             rev_reg_defs: &HashMap<RevocationRegistryId, RevocationRegistryDefinitionV1>,
+                             rev_regs: &HashMap<RevocationRegistryId, HashMap<u64, RevocationRegistryV1>>) -> IndyResult<ProofVerifier> {
+        let mut proof_verifier = CryptoVerifier::new_proof_verifier()?;
+        let non_credential_schema = build_non_credential_schema()?;
+
+        for sub_proof_index in 0..full_proof.identifiers.len() {
+            let identifier = &full_proof.identifiers[sub_proof_index];
+
+            let (schema, cred_def) = Verifier::_get_ledger_data(identifier, schemas, cred_defs)?;
+            let (rev_reg_def, rev_reg) = Verifier::_get_revocation_data(identifier, rev_reg_defs, rev_regs)?;
 
             let attrs_for_credential = Verifier::_get_revealed_attributes_for_credential(sub_proof_index, &full_proof.requested_proof, proof_req)?;
             let predicates_for_credential = Verifier::_get_predicates_for_credential(sub_proof_index, &full_proof.requested_proof, proof_req)?;
@@ -122,21 +365,60 @@ impl Verifier {
                                                  rev_reg.as_ref().map(|r_reg| &r_reg.value))?;
         }
 
-        let valid = proof_verifier.verify(&full_proof.proof, &proof_req.nonce)?;
+        Ok(proof_verifier)
+    }
 
-        trace!("verify <<< valid: {:?}", valid);
+    fn _get_ledger_data<'a>(identifier: &Identifier,
+                            schemas: &'a HashMap<SchemaId, SchemaV1>,
+                            cred_defs: &'a HashMap<CredentialDefinitionId, CredentialDefinitionV1>) -> IndyResult<(&'a SchemaV1, &'a CredentialDefinitionV1)> {
+        let schema: &SchemaV1 = schemas.get(&identifier.schema_id)
+            .ok_or_else(|| err_msg(IndyErrorKind::InvalidStructure, format!("Schema not found for id: {:?}", identifier.schema_id)))?;
 
-        Ok(valid)
+        let cred_def: &CredentialDefinitionV1 = cred_defs.get(&identifier.cred_def_id)
+            .ok_or_else(|| err_msg(IndyErrorKind::InvalidStructure, format!("CredentialDefinition not found for id: {:?}", identifier.cred_def_id)))?;
+
+        Ok((schema, cred_def))
     }
 
-    pub fn generate_nonce(&self) -> IndyResult<Nonce> {
-        trace!("generate_nonce >>> ");
+    fn _get_revocation_data<'a>(identifier: &Identifier,
+                                rev_reg_defs: &'a HashMap<RevocationRegistryId, RevocationRegistryDefinitionV1>,
+                                rev_regs: &'a HashMap<RevocationRegistryId, HashMap<u64, RevocationRegistryV1>>)
+                                -> IndyResult<(Option<&'a RevocationRegistryDefinitionV1>, Option<&'a RevocationRegistryV1>)> {
+        let timestamp = match identifier.timestamp {
+            Some(timestamp) => timestamp,
+            None => return Ok((None, None))
+        };
 
-        let nonce = new_nonce()?;
+        let rev_reg_id = identifier.rev_reg_id
+            .clone()
+            .ok_or_else(|| err_msg(IndyErrorKind::InvalidStructure, "Revocation Registry Id not found"))?;
 
-        trace!("generate_nonce <<< nonce: {:?} ", nonce);
+        let rev_reg_def = rev_reg_defs
+            .get(&rev_reg_id)
+            .ok_or_else(|| err_msg(IndyErrorKind::InvalidStructure, format!("RevocationRegistryDefinition not found for id: {:?}", identifier.rev_reg_id)))?;
 
-        Ok(nonce)
+        let rev_regs_for_cred = rev_regs
+            .get(&rev_reg_id)
+            .ok_or_else(|| err_msg(IndyErrorKind::InvalidStructure, format!("RevocationRegistry not found for id: {:?}", rev_reg_id)))?;
+
+        let rev_reg = rev_regs_for_cred
+            .get(&timestamp)
+            .ok_or_else(|| err_msg(IndyErrorKind::InvalidStructure, format!("RevocationRegistry not found for timestamp: {:?}", timestamp)))?;
+
+        Ok((Some(rev_reg_def), Some(rev_reg)))
+    }
+
+    fn _check(res: IndyResult<()>) -> CheckResult {
+        match res {
+            Ok(()) => CheckResult::passed(),
+            Err(err) => {
+                let message = err.iter_causes()
+                    .map(|cause| cause.to_string())
+                    .collect::<Vec<String>>();
+
+                CheckResult::failed(if message.is_empty() { err.kind().to_string() } else { message.join(": ") })
+            }
+        }
     }
 
     fn _get_revealed_attributes_for_credential(sub_proof_index: usize,
@@ -325,34 +607,48 @@ impl Verifier {
     fn _verify_revealed_attribute_values(proof_req: &ProofRequestPayload,
                                          proof: &Proof) -> IndyResult<()> {
         for (attr_referent, attr_info) in proof.requested_proof.revealed_attrs.iter() {
-
-            let attr_name = proof_req.requested_attributes.get(attr_referent)
-                .as_ref()
-                .ok_or(IndyError::from_msg(IndyErrorKind::ProofRejected, format!("Attribute with referent \"{}\" not found in ProofRequests", attr_referent)))?
-                .name.as_ref()
-                .ok_or(IndyError::from_msg(IndyErrorKind::ProofRejected, format!("Attribute with referent \"{}\" not found in ProofRequests", attr_referent)))?;
-            Verifier::_verify_revealed_attribute_value(attr_name.as_str(), proof, &attr_info)?;
+            Verifier::_verify_revealed_attribute(proof_req, proof, attr_referent, attr_info)?;
         }
 
         for (attr_referent, attr_infos) in proof.requested_proof.revealed_attr_groups.iter() {
-            let attr_names = proof_req.requested_attributes.get(attr_referent)
-                .as_ref()
-                .ok_or(IndyError::from_msg(IndyErrorKind::ProofRejected, format!("Attribute with referent \"{}\" not found in ProofRequests", attr_referent)))?
-                .names.as_ref()
-                .ok_or(IndyError::from_msg(IndyErrorKind::ProofRejected, format!("Attribute with referent \"{}\" not found in ProofRequests", attr_referent)))?;
-            if attr_infos.values.len() != attr_names.len() {
-                error!("Proof Revealed Attr Group does not match Proof Request Attribute Group, proof request attrs: {:?}, referent: {:?}, attr_infos: {:?}", proof_req.requested_attributes, attr_referent, attr_infos);
-                return Err(IndyError::from_msg(IndyErrorKind::InvalidStructure, "Proof Revealed Attr Group does not match Proof Request Attribute Group"))
-            }
-            for attr_name in attr_names {
-                let attr_info = &attr_infos.values.get(attr_name)
-                    .ok_or(IndyError::from_msg(IndyErrorKind::InvalidStructure, "Proof Revealed Attr Group does not match Proof Request Attribute Group"))?;
-                Verifier::_verify_revealed_attribute_value(attr_name, proof, &RevealedAttributeInfo {
-                    sub_proof_index: attr_infos.sub_proof_index,
-                    raw: attr_info.raw.clone(),
-                    encoded: attr_info.encoded.clone()
-                })?;
-            }
+            Verifier::_verify_revealed_attribute_group(proof_req, proof, attr_referent, attr_infos)?;
+        }
+        Ok(())
+    }
+
+    fn _verify_revealed_attribute(proof_req: &ProofRequestPayload,
+                                  proof: &Proof,
+                                  attr_referent: &str,
+                                  attr_info: &RevealedAttributeInfo) -> IndyResult<()> {
+        let attr_name = proof_req.requested_attributes.get(attr_referent)
+            .as_ref()
+            .ok_or(IndyError::from_msg(IndyErrorKind::ProofRejected, format!("Attribute with referent \"{}\" not found in ProofRequests", attr_referent)))?
+            .name.as_ref()
+            .ok_or(IndyError::from_msg(IndyErrorKind::ProofRejected, format!("Attribute with referent \"{}\" not found in ProofRequests", attr_referent)))?;
+        Verifier::_verify_revealed_attribute_value(attr_name.as_str(), proof, &attr_info)
+    }
+
+    fn _verify_revealed_attribute_group(proof_req: &ProofRequestPayload,
+                                        proof: &Proof,
+                                        attr_referent: &str,
+                                        attr_infos: &RevealedAttributeGroupInfo) -> IndyResult<()> {
+        let attr_names = proof_req.requested_attributes.get(attr_referent)
+            .as_ref()
+            .ok_or(IndyError::from_msg(IndyErrorKind::ProofRejected, format!("Attribute with referent \"{}\" not found in ProofRequests", attr_referent)))?
+            .names.as_ref()
+            .ok_or(IndyError::from_msg(IndyErrorKind::ProofRejected, format!("Attribute with referent \"{}\" not found in ProofRequests", attr_referent)))?;
+        if attr_infos.values.len() != attr_names.len() {
+            error!("Proof Revealed Attr Group does not match Proof Request Attribute Group, proof request attrs: {:?}, referent: {:?}, attr_infos: {:?}", proof_req.requested_attributes, attr_referent, attr_infos);
+            return Err(IndyError::from_msg(IndyErrorKind::InvalidStructure, "Proof Revealed Attr Group does not match Proof Request Attribute Group"))
+        }
+        for attr_name in attr_names {
+            let attr_info = &attr_infos.values.get(attr_name)
+                .ok_or(IndyError::from_msg(IndyErrorKind::InvalidStructure, "Proof Revealed Attr Group does not match Proof Request Attribute Group"))?;
+            Verifier::_verify_revealed_attribute_value(attr_name, proof, &RevealedAttributeInfo {
+                sub_proof_index: attr_infos.sub_proof_index,
+                raw: attr_info.raw.clone(),
+                encoded: attr_info.encoded.clone()
+            })?;
         }
         Ok(())
     }
@@ -380,6 +676,31 @@ impl Verifier {
         Ok(())
     }
 
+    fn _verify_revealed_attribute_encodings(requested_proof: &RequestedProof) -> IndyResult<()> {
+        for attr_info in requested_proof.revealed_attrs.values() {
+            Verifier::_verify_canonical_encoding(&attr_info.raw, &attr_info.encoded)?;
+        }
+
+        for attr_infos in requested_proof.revealed_attr_groups.values() {
+            for value in attr_infos.values.values() {
+                Verifier::_verify_canonical_encoding(&value.raw, &value.encoded)?;
+            }
+        }
+
+        Ok(())
+    }
+
+    fn _verify_canonical_encoding(raw: &str, encoded: &str) -> IndyResult<()> {
+        let canonical = encode_credential_value(raw)?;
+
+        if BigNumber::from_dec(encoded)? != BigNumber::from_dec(&canonical)? {
+            return Err(IndyError::from_msg(IndyErrorKind::ProofRejected,
+                                           format!("Encoded value \"{}\" doesn't match to the canonical encoding \"{}\" of raw value \"{}\"", encoded, canonical, raw)));
+        }
+
+        Ok(())
+    }
+
     fn _verify_requested_restrictions(proof_req: &ProofRequestPayload,
                                       requested_proof: &RequestedProof,
                                       received_revealed_attrs: &HashMap<String, Identifier>,
@@ -398,74 +719,90 @@ impl Verifier {
             .map(|(referent, info)| (referent.to_string(), info.clone()))
             .collect();
 
-        for (referent, info) in requested_attrs.clone() {
+        for (referent, info) in requested_attrs.iter() {
             if let Some(ref query) = info.restrictions {
-                let filter = Verifier::_gather_filter_info(&referent, &proof_attr_identifiers)?;
-
-                let name_value_map: HashMap<String, Option<&str>> = if let Some(name) = info.name {
-                    let mut map = HashMap::new();
-                    map.insert(name.clone(), requested_proof.revealed_attrs.get(&referent).map(|attr| attr.raw.as_str()));
-                    map
-                } else if let Some(names) = info.names {
-                    let mut map = HashMap::new();
-                    let attrs = requested_proof.revealed_attr_groups.get(&referent)
-                        .ok_or(IndyError::from_msg(IndyErrorKind::InvalidStructure, "Proof does not have referent from proof request"))?;
-                    for name in names {
-                        let val = attrs.values.get(&name).map(|attr| attr.raw.as_str());
-                        map.insert(name, val);
-                    }
-                    map
-                } else {
-                    error!(r#"Proof Request attribute restriction should contain "name" or "names" param. Current proof request: {:?}"#, proof_req);
-                    return Err(IndyError::from_msg(IndyErrorKind::InvalidStructure, r#"Proof Request attribute restriction should contain "name" or "names" param"#));
-                };
-
-                Verifier::_do_process_operator(&name_value_map, &query, &filter)
-                    .map_err(|err| err.extend(format!("Requested restriction validation failed for \"{:?}\" attributes", &name_value_map)))?;
+                Verifier::_verify_attribute_restrictions(proof_req, requested_proof, referent, info, query, &proof_attr_identifiers)?;
             }
         }
 
         for (referent, info) in proof_req.requested_predicates.iter() {
             if let Some(ref query) = info.restrictions {
-                let filter = Verifier::_gather_filter_info(&referent, received_predicates)?;
-
-                // start with the predicate requested attribute, which is un-revealed
-                let mut attr_value_map = HashMap::new();
-                attr_value_map.insert(info.name.to_string(), None);
-
-                // include any revealed attributes for the same credential (based on sub_proof_index)
-                let pred_sub_proof_index = requested_proof.predicates.get(referent).unwrap().sub_proof_index;
-                for attr_referent in requested_proof.revealed_attrs.keys() {
-                    let attr_info = requested_proof.revealed_attrs.get(attr_referent).unwrap();
-                    let attr_sub_proof_index = attr_info.sub_proof_index;
-                    if pred_sub_proof_index == attr_sub_proof_index {
-                        let attr_name = requested_attrs.get(attr_referent).unwrap().name.clone();
-                        if let Some(name) = attr_name {
-                            attr_value_map.insert(name, Some(attr_info.raw.as_str()));
-                        }
-                    }
-                }
-                for attr_referent in requested_proof.revealed_attr_groups.keys() {
-                    let attr_info = requested_proof.revealed_attr_groups.get(attr_referent).unwrap();
-                    let attr_sub_proof_index = attr_info.sub_proof_index;
-                    if pred_sub_proof_index == attr_sub_proof_index {
-                        for name in attr_info.values.keys() {
-                            let raw_val = attr_info.values.get(name).unwrap().raw.as_str();
-                            attr_value_map.insert(name.clone(), Some(raw_val.clone()));
-                        }
-                    }
-                }
+                Verifier::_verify_predicate_restrictions(requested_proof, referent, info, query, received_predicates, &requested_attrs)?;
+            }
+        }
+
+        Ok(())
+    }
+
+    fn _verify_attribute_restrictions(proof_req: &ProofRequestPayload,
+                                      requested_proof: &RequestedProof,
+                                      referent: &str,
+                                      info: &AttributeInfo,
+                                      query: &Query,
+                                      proof_attr_identifiers: &HashMap<String, Identifier>) -> IndyResult<()> {
+        let filter = Verifier::_gather_filter_info(&referent, proof_attr_identifiers)?;
+
+        let name_value_map: HashMap<String, Option<&str>> = if let Some(ref name) = info.name {
+            let mut map = HashMap::new();
+            map.insert(name.clone(), requested_proof.revealed_attrs.get(referent).map(|attr| attr.raw.as_str()));
+            map
+        } else if let Some(ref names) = info.names {
+            let mut map = HashMap::new();
+            let attrs = requested_proof.revealed_attr_groups.get(referent)
+                .ok_or(IndyError::from_msg(IndyErrorKind::InvalidStructure, "Proof does not have referent from proof request"))?;
+            for name in names {
+                let val = attrs.values.get(name).map(|attr| attr.raw.as_str());
+                map.insert(name.clone(), val);
+            }
+            map
+        } else {
+            error!(r#"Proof Request attribute restriction should contain "name" or "names" param. Current proof request: {:?}"#, proof_req);
+            return Err(IndyError::from_msg(IndyErrorKind::InvalidStructure, r#"Proof Request attribute restriction should contain "name" or "names" param"#));
+        };
 
-                Verifier::_do_process_operator(&attr_value_map, &query, &filter)
-                    .map_err(|err| err.extend(format!("Requested restriction validation failed for \"{}\" predicate", &info.name)))?;
+        Verifier::_do_process_operator(&name_value_map, &query, &filter)
+            .map_err(|err| err.extend(format!("Requested restriction validation failed for \"{:?}\" attributes", &name_value_map)))
+    }
+
+    fn _verify_predicate_restrictions(requested_proof: &RequestedProof,
+                                      referent: &str,
+                                      info: &PredicateInfo,
+                                      query: &Query,
+                                      received_predicates: &HashMap<String, Identifier>,
+                                      requested_attrs: &HashMap<String, AttributeInfo>) -> IndyResult<()> {
+        let filter = Verifier::_gather_filter_info(&referent, received_predicates)?;
 
-                // old style :-/ which fails for attribute restrictions on predicates
-                //Verifier::_process_operator(&info.name, &query, &filter, None)
-                //    .map_err(|err| err.extend(format!("Requested restriction validation failed for \"{}\" predicate", &info.name)))?;
+        // start with the predicate requested attribute, which is un-revealed
+        let mut attr_value_map = HashMap::new();
+        attr_value_map.insert(info.name.to_string(), None);
+
+        // include any revealed attributes for the same credential (based on sub_proof_index)
+        let pred_sub_proof_index = requested_proof.predicates.get(referent)
+            .ok_or_else(|| err_msg(IndyErrorKind::InvalidStructure, format!("Predicate with referent \"{}\" not found in Proof", referent)))?
+            .sub_proof_index;
+        for attr_referent in requested_proof.revealed_attrs.keys() {
+            let attr_info = requested_proof.revealed_attrs.get(attr_referent).unwrap();
+            let attr_sub_proof_index = attr_info.sub_proof_index;
+            if pred_sub_proof_index == attr_sub_proof_index {
+                let attr_name = requested_attrs.get(attr_referent).and_then(|attr| attr.name.clone());
+                if let Some(name) = attr_name {
+                    attr_value_map.insert(name, Some(attr_info.raw.as_str()));
+                }
+            }
+        }
+        for attr_referent in requested_proof.revealed_attr_groups.keys() {
+            let attr_info = requested_proof.revealed_attr_groups.get(attr_referent).unwrap();
+            let attr_sub_proof_index = attr_info.sub_proof_index;
+            if pred_sub_proof_index == attr_sub_proof_index {
+                for name in attr_info.values.keys() {
+                    let raw_val = attr_info.values.get(name).unwrap().raw.as_str();
+                    attr_value_map.insert(name.clone(), Some(raw_val.clone()));
+                }
             }
         }
 
-        Ok(())
+        Verifier::_do_process_operator(&attr_value_map, &query, &filter)
+            .map_err(|err| err.extend(format!("Requested restriction validation failed for \"{}\" predicate", &info.name)))
     }
 
     fn _is_self_attested(referent: &str, info: &AttributeInfo, self_attested_attrs: &HashSet<String>) -> bool {
@@ -0,0 +1,166 @@
+use std::fs::File;
+use std::io;
+use std::io::{BufRead, BufReader, Read, Write};
+use std::net::TcpStream;
+use std::path::PathBuf;
+use std::time::Duration;
+
+use rust_base58::ToBase58;
+use serde_json;
+
+use indy_api_types::errors::prelude::*;
+
+use super::{store_blob, ReadableBlob, Reader, ReaderType};
+use super::default_reader::DefaultReader;
+
+const DEFAULT_HTTP_TIMEOUT: u64 = 30;
+
+/// Read-through reader: blobs are downloaded over plain HTTP on first access,
+/// checked against the expected hash and kept in `cache_dir` for subsequent reads.
+/// Only http:// URLs are supported: no HTTPS and redirects aren't followed.
+///
+/// Blob URL is `base_url` joined with base58 encoded hash if `base_url` is set,
+/// otherwise the blob location (for example `tailsLocation` of revocation registry definition) is used.
+#[derive(Serialize, Deserialize)]
+struct HttpReaderConfig {
+    base_url: Option<String>,
+    cache_dir: String,
+    timeout: Option<u64>,
+}
+
+impl ReaderType for HttpReaderType {
+    fn open(&self, config: &str) -> IndyResult<Box<dyn Reader>> {
+        let config: HttpReaderConfig = serde_json::from_str(config)
+            .to_indy(IndyErrorKind::InvalidStructure, "Can't deserialize HttpReaderConfig")?;
+
+        Ok(Box::new(config))
+    }
+}
+
+impl Reader for HttpReaderConfig {
+    fn open(&self, hash: &[u8], location: &str) -> IndyResult<Box<dyn ReadableBlob>> {
+        let mut path = PathBuf::from(&self.cache_dir);
+        path.push(hash.to_base58());
+
+        // cached blobs are verified before they are stored, so they aren't hashed again
+        if !path.is_file() {
+            let url = match self.base_url {
+                Some(ref base_url) => format!("{}/{}", base_url.trim_end_matches('/'), hash.to_base58()),
+                None => location.to_string()
+            };
+
+            let mut body = _http_get(&url, Duration::from_secs(self.timeout.unwrap_or(DEFAULT_HTTP_TIMEOUT)))?;
+
+            if !store_blob(&path, hash, &mut body)? {
+                return Err(err_msg(IndyErrorKind::InvalidStructure, format!("Blob downloaded from {} doesn't match the expected hash", url)));
+            }
+        }
+
+        let file = File::open(path)?;
+        Ok(Box::new(DefaultReader::new(file, hash)))
+    }
+}
+
+// Response body is returned as a stream, so the blob is written to disk without keeping it in memory.
+fn _http_get(url: &str, timeout: Duration) -> IndyResult<HttpBody> {
+    trace!("_http_get >>> url: {:?}", url);
+
+    let rest = if url.starts_with("http://") {
+        &url["http://".len()..]
+    } else {
+        return Err(err_msg(IndyErrorKind::InvalidStructure, format!("Unsupported blob URL: {}, only http:// scheme is supported", url)));
+    };
+
+    let (host, path) = match rest.find('/') {
+        Some(pos) => (&rest[..pos], &rest[pos..]),
+        None => (rest, "/")
+    };
+
+    let addr = if host.contains(':') { host.to_string() } else { format!("{}:80", host) };
+
+    let mut stream = TcpStream::connect(&addr)?;
+    stream.set_read_timeout(Some(timeout))?;
+    stream.set_write_timeout(Some(timeout))?;
+
+    // HTTP/1.0 keeps the response body plain: no chunked encoding and connection is closed at the end
+    let request = format!("GET {} HTTP/1.0\r\nHost: {}\r\nAccept: application/octet-stream\r\n\r\n", path, host);
+    stream.write_all(request.as_bytes())?;
+
+    let mut stream = BufReader::new(stream);
+
+    let status_line = _read_header_line(&mut stream, url)?;
+    let status = status_line.split_whitespace().nth(1).unwrap_or("");
+
+    // redirects aren't followed, so 3xx responses are errors as well
+    if status != "200" {
+        return Err(err_msg(IndyErrorKind::IOError, format!("Can't download blob from {}: {}", url, status_line)));
+    }
+
+    let mut content_length = None;
+
+    loop {
+        let line = _read_header_line(&mut stream, url)?;
+
+        if line.is_empty() {
+            break;
+        }
+
+        let mut parts = line.splitn(2, ':');
+
+        if let (Some(name), Some(value)) = (parts.next(), parts.next()) {
+            if name.trim().eq_ignore_ascii_case("Content-Length") {
+                let len = value.trim().parse::<u64>()
+                    .to_indy(IndyErrorKind::IOError, format!("Invalid Content-Length in HTTP response from {}", url))?;
+                content_length = Some(len);
+            }
+        }
+    }
+
+    trace!("_http_get <<< content_length: {:?}", content_length);
+    Ok(HttpBody { stream, remaining: content_length })
+}
+
+fn _read_header_line(stream: &mut BufReader<TcpStream>, url: &str) -> IndyResult<String> {
+    let mut line = String::new();
+
+    if stream.read_line(&mut line)? == 0 {
+        return Err(err_msg(IndyErrorKind::IOError, format!("Invalid HTTP response from {}", url)));
+    }
+
+    Ok(line.trim_end_matches(|c| c == '\r' || c == '\n').to_string())
+}
+
+// Body stream limited by Content-Length if it's set.
+// Connection closed before Content-Length bytes are received is an error, so truncated blob isn't taken for the whole one.
+struct HttpBody {
+    stream: BufReader<TcpStream>,
+    remaining: Option<u64>,
+}
+
+impl Read for HttpBody {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        let remaining = match self.remaining {
+            Some(0) => return Ok(0),
+            Some(remaining) => remaining,
+            None => return self.stream.read(buf)
+        };
+
+        let max = ::std::cmp::min(buf.len() as u64, remaining) as usize;
+        let size = self.stream.read(&mut buf[..max])?;
+
+        if size == 0 {
+            return Err(io::Error::new(io::ErrorKind::UnexpectedEof, "HTTP response body is shorter than Content-Length"));
+        }
+
+        self.remaining = Some(remaining - size as u64);
+        Ok(size)
+    }
+}
+
+pub struct HttpReaderType {}
+
+impl HttpReaderType {
+    pub fn new() -> Self {
+        HttpReaderType {}
+    }
+}
@@ -1,42 +1,56 @@
 use std::cell::RefCell;
 use std::collections::HashMap;
+use std::fs;
+use std::fs::File;
+use std::io::{Read, Write};
+use std::path::Path;
 
 use indy_api_types::errors::prelude::*;
+use indy_utils::crypto::randombytes;
 use indy_utils::sequence;
+use rust_base58::ToBase58;
 
 use sha2::Sha256;
 use sha2::digest::{FixedOutput, Update};
 
 mod default_writer;
 mod default_reader;
+mod http_reader;
+mod plugged_writer;
+mod plugged_reader;
 
-trait WriterType {
+pub use self::plugged_writer::PluggedWriterType;
+pub use self::plugged_reader::PluggedReaderType;
+
+pub trait WriterType: Send {
     fn open(&self, config: &str) -> IndyResult<Box<dyn Writer>>;
 }
 
-trait Writer {
+pub trait Writer {
     fn create(&self, id: i32) -> IndyResult<Box<dyn WritableBlob>>;
 }
 
-trait WritableBlob {
+pub trait WritableBlob {
     fn append(&mut self, bytes: &[u8]) -> IndyResult<usize>;
     fn finalize(&mut self, hash: &[u8]) -> IndyResult<String>;
 }
 
-trait ReaderType {
+pub trait ReaderType: Send {
     fn open(&self, config: &str) -> IndyResult<Box<dyn Reader>>;
 }
 
-trait Reader {
+pub trait Reader {
     fn open(&self, hash: &[u8], location: &str) -> IndyResult<Box<dyn ReadableBlob>>;
 }
 
-trait ReadableBlob {
+pub trait ReadableBlob {
     fn read(&mut self, size: usize, offset: usize) -> IndyResult<Vec<u8>>;
     fn verify(&mut self) -> IndyResult<bool>;
     fn close(&self) -> IndyResult<()>;
 }
 
+const FETCH_CHUNK_SIZE: usize = 64 * 1024;
+
 pub struct BlobStorageService {
     writer_types: RefCell<HashMap<String, Box<dyn WriterType>>>,
     writer_configs: RefCell<HashMap<i32, Box<dyn Writer>>>,
@@ -53,6 +67,7 @@ impl BlobStorageService {
         writer_types.insert("default".to_owned(), Box::new(default_writer::DefaultWriterType::new()));
         let mut reader_types: HashMap<String, Box<dyn ReaderType>> = HashMap::new();
         reader_types.insert("default".to_owned(), Box::new(default_reader::DefaultReaderType::new()));
+        reader_types.insert("http".to_owned(), Box::new(http_reader::HttpReaderType::new()));
 
         BlobStorageService {
             writer_types: RefCell::new(writer_types),
@@ -68,6 +83,17 @@ impl BlobStorageService {
 
 /* Writer */
 impl BlobStorageService {
+    pub fn register_writer_type(&self, type_: &str, writer_type: Box<dyn WriterType>) -> IndyResult<()> {
+        let mut writer_types = self.writer_types.try_borrow_mut()?;
+
+        if writer_types.contains_key(type_) {
+            return Err(err_msg(IndyErrorKind::InvalidStructure, format!("BlobStorage Writer type {} is already registered", type_)));
+        }
+
+        writer_types.insert(type_.to_string(), writer_type);
+        Ok(())
+    }
+
     pub fn open_writer(&self, type_: &str, config: &str) -> IndyResult<i32> {
         let writer_config = self.writer_types.try_borrow()?
             .get(type_).ok_or_else(|| err_msg(IndyErrorKind::InvalidStructure, "Unknown BlobStorage Writer type"))?
@@ -113,6 +139,17 @@ impl BlobStorageService {
 
 /* Reader */
 impl BlobStorageService {
+    pub fn register_reader_type(&self, type_: &str, reader_type: Box<dyn ReaderType>) -> IndyResult<()> {
+        let mut reader_types = self.reader_types.try_borrow_mut()?;
+
+        if reader_types.contains_key(type_) {
+            return Err(err_msg(IndyErrorKind::InvalidStructure, format!("BlobStorage Reader type {} is already registered", type_)));
+        }
+
+        reader_types.insert(type_.to_string(), reader_type);
+        Ok(())
+    }
+
     pub fn open_reader(&self, type_: &str, config: &str) -> IndyResult<i32> {
         let reader_config = self.reader_types.try_borrow()?
             .get(type_).ok_or_else(|| err_msg(IndyErrorKind::InvalidStructure, "Invalid BlobStorage Reader type"))? // FIXME: Review error kind
@@ -153,3 +190,112 @@ impl BlobStorageService {
             .close()
     }
 }
+
+/* Fetching */
+impl BlobStorageService {
+    // Blob is streamed to `path` and hashed on the fly, so it is never kept in memory as a whole.
+    // Returns false if the fetched blob doesn't match `hash`, nothing is stored in this case.
+    pub fn fetch_blob(&self, type_: Option<&str>, config: &str, location: &str, hash: &[u8], path: &Path) -> IndyResult<bool> {
+        let type_ = match type_ {
+            Some(type_) => type_,
+            None => {
+                let mut file = File::open(location)
+                    .to_indy(IndyErrorKind::IOError, format!("Can't read blob from {}", location))?;
+
+                return store_blob(path, hash, &mut file);
+            }
+        };
+
+        let reader = self.reader_types.try_borrow()?
+            .get(type_).ok_or_else(|| err_msg(IndyErrorKind::InvalidStructure, "Invalid BlobStorage Reader type"))?
+            .open(config)?;
+
+        let mut blob = reader.open(hash, location)?;
+
+        // caching readers (like http one) may already keep verified blob at the same content-addressed path
+        let res = if self.is_blob_stored(path) {
+            Ok(true)
+        } else {
+            store_blob(path, hash, &mut BlobRead { blob: blob.as_mut(), offset: 0 })
+        };
+
+        blob.close()?;
+
+        res
+    }
+
+    // Blobs are stored under their hash only after verification (See store_blob),
+    // so the presence of the file is enough and its content isn't hashed again.
+    pub fn is_blob_stored(&self, path: &Path) -> bool {
+        path.is_file()
+    }
+}
+
+// Adapts readable blob to io::Read, so blobs of any reader type can be streamed.
+struct BlobRead<'a> {
+    blob: &'a mut dyn ReadableBlob,
+    offset: usize,
+}
+
+impl<'a> Read for BlobRead<'a> {
+    fn read(&mut self, buf: &mut [u8]) -> ::std::io::Result<usize> {
+        let chunk = self.blob.read(buf.len(), self.offset)
+            .map_err(|err| ::std::io::Error::new(::std::io::ErrorKind::Other, err.to_string()))?;
+
+        buf[..chunk.len()].copy_from_slice(&chunk);
+        self.offset += chunk.len();
+
+        Ok(chunk.len())
+    }
+}
+
+// Blob is written to temporary file and moved to `path` only if its hash matches,
+// so concurrent readers never see partial data and stored blob is always verified.
+// Every writer uses its own temporary file, so concurrent fetches of the same blob don't mix their data.
+// Returns false if the hash doesn't match.
+pub fn store_blob(path: &Path, hash: &[u8], source: &mut dyn Read) -> IndyResult<bool> {
+    let (dir, file_name) = match (path.parent(), path.file_name()) {
+        (Some(dir), Some(file_name)) => (dir, file_name),
+        _ => return Err(err_msg(IndyErrorKind::InvalidStructure, format!("Invalid blob path: {:?}", path)))
+    };
+
+    fs::DirBuilder::new()
+        .recursive(true)
+        .create(dir)?;
+
+    let tmp_path = dir.join(format!("{}.{}.tmp", file_name.to_string_lossy(), randombytes::randombytes(16).to_base58()));
+
+    let res = _write_blob(&tmp_path, source);
+
+    match res {
+        Ok(ref blob_hash) if blob_hash.as_slice() == hash => {
+            fs::rename(&tmp_path, path)?;
+            Ok(true)
+        }
+        res => {
+            fs::remove_file(&tmp_path).ok();
+            res.map(|_| false)
+        }
+    }
+}
+
+fn _write_blob(path: &Path, source: &mut dyn Read) -> IndyResult<Vec<u8>> {
+    let mut file = File::create(path)?;
+    let mut hasher = Sha256::default();
+    let mut buf = vec![0u8; FETCH_CHUNK_SIZE];
+
+    loop {
+        let size = source.read(&mut buf)?;
+
+        if size == 0 {
+            break;
+        }
+
+        hasher.update(&buf[..size]);
+        file.write_all(&buf[..size])?;
+    }
+
+    file.sync_all()?;
+
+    Ok(hasher.finalize_fixed().to_vec())
+}
@@ -0,0 +1,127 @@
+use std::ffi::CString;
+
+use indy_api_types::{ErrorCode, IndyHandle};
+use indy_api_types::blob_storage::*;
+use indy_api_types::errors::prelude::*;
+
+use super::{ReadableBlob, Reader, ReaderType};
+
+#[derive(Clone, Copy)]
+struct PluggedReaderHandlers {
+    open: BlobStorageReaderOpen,
+    open_blob: BlobStorageReaderOpenBlob,
+    read: BlobStorageReaderRead,
+    verify: BlobStorageReaderVerify,
+    close_blob: BlobStorageReaderCloseBlob,
+}
+
+pub struct PluggedReaderType {
+    handlers: PluggedReaderHandlers,
+}
+
+impl PluggedReaderType {
+    pub fn new(open: BlobStorageReaderOpen,
+               open_blob: BlobStorageReaderOpenBlob,
+               read: BlobStorageReaderRead,
+               verify: BlobStorageReaderVerify,
+               close_blob: BlobStorageReaderCloseBlob) -> PluggedReaderType {
+        PluggedReaderType {
+            handlers: PluggedReaderHandlers {
+                open,
+                open_blob,
+                read,
+                verify,
+                close_blob,
+            }
+        }
+    }
+}
+
+impl ReaderType for PluggedReaderType {
+    fn open(&self, config: &str) -> IndyResult<Box<dyn Reader>> {
+        let config = CString::new(config)?;
+        let mut reader_handle: IndyHandle = -1;
+
+        let err = (self.handlers.open)(config.as_ptr(), &mut reader_handle);
+
+        if err != ErrorCode::Success {
+            return Err(err.into());
+        }
+
+        Ok(Box::new(PluggedReader {
+            handlers: self.handlers,
+            reader_handle,
+        }))
+    }
+}
+
+struct PluggedReader {
+    handlers: PluggedReaderHandlers,
+    reader_handle: IndyHandle,
+}
+
+impl Reader for PluggedReader {
+    fn open(&self, hash: &[u8], location: &str) -> IndyResult<Box<dyn ReadableBlob>> {
+        let location = CString::new(location)?;
+        let mut blob_handle: IndyHandle = -1;
+
+        let err = (self.handlers.open_blob)(self.reader_handle, hash.as_ptr(), hash.len(), location.as_ptr(), &mut blob_handle);
+
+        if err != ErrorCode::Success {
+            return Err(err.into());
+        }
+
+        Ok(Box::new(PluggedReadableBlob {
+            handlers: self.handlers,
+            blob_handle,
+        }))
+    }
+}
+
+struct PluggedReadableBlob {
+    handlers: PluggedReaderHandlers,
+    blob_handle: IndyHandle,
+}
+
+impl ReadableBlob for PluggedReadableBlob {
+    fn read(&mut self, size: usize, offset: usize) -> IndyResult<Vec<u8>> {
+        let mut buf = vec![0u8; size];
+        let mut read_len: usize = 0;
+
+        let err = (self.handlers.read)(self.blob_handle, offset, buf.as_mut_ptr(), buf.len(), &mut read_len);
+
+        if err != ErrorCode::Success {
+            return Err(err.into());
+        }
+
+        if read_len > size {
+            return Err(err_msg(IndyErrorKind::InvalidState, "BlobStorage Reader returned more bytes than requested"));
+        }
+
+        buf.truncate(read_len);
+
+        Ok(buf)
+    }
+
+    fn verify(&mut self) -> IndyResult<bool> {
+        let mut valid = false;
+
+        let err = (self.handlers.verify)(self.blob_handle, &mut valid);
+
+        if err != ErrorCode::Success {
+            return Err(err.into());
+        }
+
+        Ok(valid)
+    }
+
+    fn close(&self) -> IndyResult<()> {
+        let err = (self.handlers.close_blob)(self.blob_handle);
+
+        if err != ErrorCode::Success {
+            return Err(err.into());
+        }
+
+        Ok(())
+    }
+}
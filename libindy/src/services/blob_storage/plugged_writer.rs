@@ -0,0 +1,125 @@
+use std::ffi::{CStr, CString};
+use std::ptr;
+
+use indy_api_types::{ErrorCode, IndyHandle};
+use indy_api_types::blob_storage::*;
+use indy_api_types::errors::prelude::*;
+
+use super::{WritableBlob, Writer, WriterType};
+
+#[derive(Clone, Copy)]
+struct PluggedWriterHandlers {
+    open: BlobStorageWriterOpen,
+    create_blob: BlobStorageWriterCreateBlob,
+    append: BlobStorageWriterAppend,
+    finalize: BlobStorageWriterFinalize,
+    free_blob: BlobStorageWriterFreeBlob,
+}
+
+pub struct PluggedWriterType {
+    handlers: PluggedWriterHandlers,
+}
+
+impl PluggedWriterType {
+    pub fn new(open: BlobStorageWriterOpen,
+               create_blob: BlobStorageWriterCreateBlob,
+               append: BlobStorageWriterAppend,
+               finalize: BlobStorageWriterFinalize,
+               free_blob: BlobStorageWriterFreeBlob) -> PluggedWriterType {
+        PluggedWriterType {
+            handlers: PluggedWriterHandlers {
+                open,
+                create_blob,
+                append,
+                finalize,
+                free_blob,
+            }
+        }
+    }
+}
+
+impl WriterType for PluggedWriterType {
+    fn open(&self, config: &str) -> IndyResult<Box<dyn Writer>> {
+        let config = CString::new(config)?;
+        let mut writer_handle: IndyHandle = -1;
+
+        let err = (self.handlers.open)(config.as_ptr(), &mut writer_handle);
+
+        if err != ErrorCode::Success {
+            return Err(err.into());
+        }
+
+        Ok(Box::new(PluggedWriter {
+            handlers: self.handlers,
+            writer_handle,
+        }))
+    }
+}
+
+struct PluggedWriter {
+    handlers: PluggedWriterHandlers,
+    writer_handle: IndyHandle,
+}
+
+impl Writer for PluggedWriter {
+    fn create(&self, _id: i32) -> IndyResult<Box<dyn WritableBlob>> {
+        let mut blob_handle: IndyHandle = -1;
+
+        let err = (self.handlers.create_blob)(self.writer_handle, &mut blob_handle);
+
+        if err != ErrorCode::Success {
+            return Err(err.into());
+        }
+
+        Ok(Box::new(PluggedWritableBlob {
+            handlers: self.handlers,
+            blob_handle,
+        }))
+    }
+}
+
+// Plugin blob is freed on drop, so it is released even if writing fails before finalize.
+struct PluggedWritableBlob {
+    handlers: PluggedWriterHandlers,
+    blob_handle: IndyHandle,
+}
+
+impl WritableBlob for PluggedWritableBlob {
+    fn append(&mut self, bytes: &[u8]) -> IndyResult<usize> {
+        let mut written_len: usize = 0;
+
+        let err = (self.handlers.append)(self.blob_handle, bytes.as_ptr(), bytes.len(), &mut written_len);
+
+        if err != ErrorCode::Success {
+            return Err(err.into());
+        }
+
+        Ok(written_len)
+    }
+
+    fn finalize(&mut self, hash: &[u8]) -> IndyResult<String> {
+        let mut location_ptr: *const libc::c_char = ptr::null();
+
+        let err = (self.handlers.finalize)(self.blob_handle, hash.as_ptr(), hash.len(), &mut location_ptr);
+
+        if err != ErrorCode::Success {
+            return Err(err.into());
+        }
+
+        if location_ptr.is_null() {
+            return Err(err_msg(IndyErrorKind::InvalidState, "BlobStorage Writer returned empty location"));
+        }
+
+        let location = unsafe { CStr::from_ptr(location_ptr) }.to_str()
+            .to_indy(IndyErrorKind::InvalidState, "BlobStorage Writer returned invalid location")?
+            .to_string();
+
+        Ok(location)
+    }
+}
+
+impl Drop for PluggedWritableBlob {
+    fn drop(&mut self) {
+        (self.handlers.free_blob)(self.blob_handle);
+    }
+}
@@ -58,15 +58,39 @@ impl From<&IssuerCommand> for CommandMetric {
             IssuerCommand::CreateAndStoreRevocationRegistry(_, _, _, _, _, _, _, _) => {
                 CommandMetric::IssuerCommandCreateAndStoreRevocationRegistry
             }
+            IssuerCommand::CreateRevocationRegistryPool(_, _, _, _, _) => {
+                CommandMetric::IssuerCommandCreateRevocationRegistryPool
+            }
+            IssuerCommand::GetRevocationRegistryPool(_, _, _) => {
+                CommandMetric::IssuerCommandGetRevocationRegistryPool
+            }
+            IssuerCommand::RegisterRevocationRegistryCreatedHook(_, _) => {
+                CommandMetric::IssuerCommandRegisterRevocationRegistryCreatedHook
+            }
             IssuerCommand::CreateCredentialOffer(_, _, _) => {
                 CommandMetric::IssuerCommandCreateCredentialOffer
             }
             IssuerCommand::CreateCredential(_, _, _, _, _, _, _) => {
                 CommandMetric::IssuerCommandCreateCredential
             }
+            IssuerCommand::CreateCredentials(_, _, _, _, _) => {
+                CommandMetric::IssuerCommandCreateCredentials
+            }
             IssuerCommand::RevokeCredential(_, _, _, _, _) => {
                 CommandMetric::IssuerCommandRevokeCredential
             }
+            IssuerCommand::RevokeCredentials(_, _, _, _, _) => {
+                CommandMetric::IssuerCommandRevokeCredentials
+            }
+            IssuerCommand::GetRevocationRegistryStatus(_, _, _) => {
+                CommandMetric::IssuerCommandGetRevocationRegistryStatus
+            }
+            IssuerCommand::CheckRevocationRegistryDrift(_, _, _, _, _) => {
+                CommandMetric::IssuerCommandCheckRevocationRegistryDrift
+            }
+            IssuerCommand::CheckRevocationRegistryDriftContinue(_, _, _, _) => {
+                CommandMetric::IssuerCommandCheckRevocationRegistryDriftContinue
+            }
             IssuerCommand::MergeRevocationRegistryDeltas(_, _, _) => {
                 CommandMetric::IssuerCommandMergeRevocationRegistryDeltas
             }
@@ -92,6 +116,7 @@ impl From<&ProverCommand> for CommandMetric {
             ProverCommand::SearchCredentialsForProofReq(_, _, _, _) => { CommandMetric::ProverCommandSearchCredentialsForProofReq }
             ProverCommand::FetchCredentialForProofReq(_, _, _, _) => { CommandMetric::ProverCommandFetchCredentialForProofReq }
             ProverCommand::CloseCredentialsSearchForProofReq(_, _) => { CommandMetric::ProverCommandCloseCredentialsSearchForProofReq }
+            ProverCommand::AutoSelectCredentials(_, _, _, _) => { CommandMetric::ProverCommandAutoSelectCredentials }
             ProverCommand::CreateProof(_, _, _, _, _, _, _, _) => { CommandMetric::ProverCommandCreateProof }
             ProverCommand::CreateRevocationState(_, _, _, _, _, _) => { CommandMetric::ProverCommandCreateRevocationState }
             ProverCommand::UpdateRevocationState(_, _, _, _, _, _, _) => { CommandMetric::ProverCommandUpdateRevocationState }
@@ -103,6 +128,9 @@ impl From<&VerifierCommand> for CommandMetric {
     fn from(cmd: &VerifierCommand) -> Self {
         match cmd {
             VerifierCommand::VerifyProof(_, _, _, _, _, _, _) => { CommandMetric::VerifierCommandVerifyProof }
+            VerifierCommand::VerifyProofWithReport(_, _, _, _, _, _, _) => { CommandMetric::VerifierCommandVerifyProofWithReport }
+            VerifierCommand::VerifyProofWithLedger(_, _, _, _, _, _, _) => { CommandMetric::VerifierCommandVerifyProofWithLedger }
+            VerifierCommand::VerifyProofWithLedgerContinue(_, _) => { CommandMetric::VerifierCommandVerifyProofWithLedgerContinue }
             VerifierCommand::GenerateNonce(_) => { CommandMetric::VerifierCommandGenerateNonce }
         }
     }
@@ -118,12 +146,18 @@ impl From<&Command> for CommandMetric {
                     AnoncredsCommand::Prover(cmd) => { cmd.into() }
                     AnoncredsCommand::Verifier(cmd) => { cmd.into() }
                     AnoncredsCommand::ToUnqualified(_, _) => { CommandMetric::AnoncredsCommandToUnqualified }
+                    AnoncredsCommand::EncodeCredentialValues(_, _) => { CommandMetric::AnoncredsCommandEncodeCredentialValues }
+                    AnoncredsCommand::Validate(_, _, _, _, _) => { CommandMetric::AnoncredsCommandValidate }
+                    AnoncredsCommand::EncodeDate(_, _) => { CommandMetric::AnoncredsCommandEncodeDate }
                 }
             }
             Command::BlobStorage(cmd) => {
                 match cmd {
                     BlobStorageCommand::OpenReader(_, _, _) => { CommandMetric::BlobStorageCommandOpenReader }
                     BlobStorageCommand::OpenWriter(_, _, _) => { CommandMetric::BlobStorageCommandOpenWriter }
+                    BlobStorageCommand::OpenTailsReader(_, _, _) => { CommandMetric::BlobStorageCommandOpenTailsReader }
+                    BlobStorageCommand::RegisterReaderType(_, _, _) => { CommandMetric::BlobStorageCommandRegisterReaderType }
+                    BlobStorageCommand::RegisterWriterType(_, _, _) => { CommandMetric::BlobStorageCommandRegisterWriterType }
                 }
             }
             Command::Crypto(cmd) => {
@@ -184,6 +218,10 @@ impl From<&Command> for CommandMetric {
                     LedgerCommand::GetSchemaContinue(_, _, _) => { CommandMetric::LedgerCommandGetSchemaContinue }
                     LedgerCommand::GetCredDef(_, _, _, _) => { CommandMetric::LedgerCommandGetCredDef }
                     LedgerCommand::GetCredDefContinue(_, _, _) => { CommandMetric::LedgerCommandGetCredDefContinue }
+                    LedgerCommand::GetRevocRegDef(_, _, _, _) => { CommandMetric::LedgerCommandGetRevocRegDef }
+                    LedgerCommand::GetRevocRegDefContinue(_, _) => { CommandMetric::LedgerCommandGetRevocRegDefContinue }
+                    LedgerCommand::GetRevocReg(_, _, _, _, _) => { CommandMetric::LedgerCommandGetRevocReg }
+                    LedgerCommand::GetRevocRegContinue(_, _) => { CommandMetric::LedgerCommandGetRevocRegContinue }
                     LedgerCommand::BuildTxnAuthorAgreementRequest(_, _, _, _, _, _) => { CommandMetric::LedgerCommandBuildTxnAuthorAgreementRequest }
                     LedgerCommand::BuildDisableAllTxnAuthorAgreementsRequest(_, _) => { CommandMetric::LedgerCommandBuildDisableAllTxnAuthorAgreementsRequest }
                     LedgerCommand::BuildGetTxnAuthorAgreementRequest(_, _, _) => { CommandMetric::LedgerCommandBuildGetTxnAuthorAgreementRequest }
@@ -232,6 +270,9 @@ impl From<&Command> for CommandMetric {
             Command::Wallet(cmd) => {
                 match cmd {
                     WalletCommand::RegisterWalletType(_, _, _, _, _, _, _, _, _, _, _, _, _, _, _, _, _, _, _, _, _, _, _, _, _, _) => { CommandMetric::WalletCommandRegisterWalletType }
+                    WalletCommand::RegisterWalletStorageType(_, _, _) => { CommandMetric::WalletCommandRegisterWalletStorageType }
+                    WalletCommand::RegisterStorageStats(_, _, _, _) => { CommandMetric::WalletCommandRegisterStorageStats }
+                    WalletCommand::RegisterKekProvider(_, _, _, _, _) => { CommandMetric::WalletCommandRegisterKekProvider }
                     WalletCommand::Create(_, _, _) => { CommandMetric::WalletCommandCreate }
                     WalletCommand::CreateContinue(_, _, _, _, _) => { CommandMetric::WalletCommandCreateContinue }
                     WalletCommand::Open(_, _, _) => { CommandMetric::WalletCommandOpen }
@@ -239,10 +280,21 @@ impl From<&Command> for CommandMetric {
                     WalletCommand::Close(_, _) => { CommandMetric::WalletCommandClose }
                     WalletCommand::Delete(_, _, _) => { CommandMetric::WalletCommandDelete }
                     WalletCommand::DeleteContinue(_, _, _, _, _) => { CommandMetric::WalletCommandDeleteContinue }
+                    WalletCommand::Check(_, _, _, _) => { CommandMetric::WalletCommandCheck }
+                    WalletCommand::CheckContinue(_, _, _, _, _, _) => { CommandMetric::WalletCommandCheckContinue }
                     WalletCommand::Export(_, _, _) => { CommandMetric::WalletCommandExport }
                     WalletCommand::ExportContinue(_, _, _, _, _) => { CommandMetric::WalletCommandExportContinue }
                     WalletCommand::Import(_, _, _, _) => { CommandMetric::WalletCommandImport }
                     WalletCommand::ImportContinue(_, _, _, _, _) => { CommandMetric::WalletCommandImportContinue }
+                    WalletCommand::ExportToStream(_, _, _, _) => { CommandMetric::WalletCommandExportToStream }
+                    WalletCommand::ExportToStreamContinue(_, _, _, _, _, _) => { CommandMetric::WalletCommandExportToStreamContinue }
+                    WalletCommand::ImportFromStream(_, _, _, _, _) => { CommandMetric::WalletCommandImportFromStream }
+                    WalletCommand::ImportIncrement(_, _, _) => { CommandMetric::WalletCommandImportIncrement }
+                    WalletCommand::ImportIncrementFromStream(_, _, _, _) => { CommandMetric::WalletCommandImportIncrementFromStream }
+                    WalletCommand::ImportIncrementContinue(_, _, _) => { CommandMetric::WalletCommandImportIncrementContinue }
+                    WalletCommand::GetAuditLog(_, _, _) => { CommandMetric::WalletCommandGetAuditLog }
+                    WalletCommand::GetWalletStats(_, _) => { CommandMetric::WalletCommandGetWalletStats }
+                    WalletCommand::SetChangeCallback(_, _, _) => { CommandMetric::WalletCommandSetChangeCallback }
                     WalletCommand::GenerateKey(_, _) => { CommandMetric::WalletCommandGenerateKey }
                     WalletCommand::DeriveKey(_, _) => { CommandMetric::WalletCommandDeriveKey }
                 }
@@ -264,6 +316,7 @@ impl From<&Command> for CommandMetric {
                     NonSecretsCommand::AddRecordTags(_, _, _, _, _) => { CommandMetric::NonSecretsCommandAddRecordTags }
                     NonSecretsCommand::DeleteRecordTags(_, _, _, _, _) => { CommandMetric::NonSecretsCommandDeleteRecordTags }
                     NonSecretsCommand::DeleteRecord(_, _, _, _) => { CommandMetric::NonSecretsCommandDeleteRecord }
+                    NonSecretsCommand::PurgeExpiredRecords(_, _, _) => { CommandMetric::NonSecretsCommandPurgeExpiredRecords }
                     NonSecretsCommand::GetRecord(_, _, _, _, _) => { CommandMetric::NonSecretsCommandGetRecord }
                     NonSecretsCommand::OpenSearch(_, _, _, _, _) => { CommandMetric::NonSecretsCommandOpenSearch }
                     NonSecretsCommand::FetchSearchNextRecords(_, _, _, _) => { CommandMetric::NonSecretsCommandFetchSearchNextRecords }
@@ -314,6 +367,8 @@ impl From<&Command> for CommandMetric {
                     CacheCommand::GetSchemaContinue(_, _, _, _) => { CommandMetric::CacheCommandGetSchemaContinue }
                     CacheCommand::GetCredDef(_, _, _, _, _, _) => { CommandMetric::CacheCommandGetCredDef }
                     CacheCommand::GetCredDefContinue(_, _, _, _) => { CommandMetric::CacheCommandGetCredDefContinue }
+                    CacheCommand::GetRevocRegDef(_, _, _, _, _, _) => { CommandMetric::CacheCommandGetRevocRegDef }
+                    CacheCommand::GetRevocRegDefContinue(_, _, _, _) => { CommandMetric::CacheCommandGetRevocRegDefContinue }
                     CacheCommand::PurgeSchemaCache(_, _, _) => { CommandMetric::CacheCommandPurgeSchemaCache }
                     CacheCommand::PurgeCredDefCache(_, _, _) => { CommandMetric::CacheCommandPurgeCredDefCache }
                 }
@@ -337,9 +392,17 @@ pub enum CommandMetric {
     IssuerCommandRotateCredentialDefinitionStartComplete,
     IssuerCommandRotateCredentialDefinitionApply,
     IssuerCommandCreateAndStoreRevocationRegistry,
+    IssuerCommandCreateRevocationRegistryPool,
+    IssuerCommandGetRevocationRegistryPool,
+    IssuerCommandRegisterRevocationRegistryCreatedHook,
     IssuerCommandCreateCredentialOffer,
     IssuerCommandCreateCredential,
+    IssuerCommandCreateCredentials,
     IssuerCommandRevokeCredential,
+    IssuerCommandRevokeCredentials,
+    IssuerCommandGetRevocationRegistryStatus,
+    IssuerCommandCheckRevocationRegistryDrift,
+    IssuerCommandCheckRevocationRegistryDriftContinue,
     IssuerCommandMergeRevocationRegistryDeltas,
     // ProverCommand
     ProverCommandCreateMasterSecret,
@@ -357,17 +420,27 @@ pub enum CommandMetric {
     ProverCommandSearchCredentialsForProofReq,
     ProverCommandFetchCredentialForProofReq,
     ProverCommandCloseCredentialsSearchForProofReq,
+    ProverCommandAutoSelectCredentials,
     ProverCommandCreateProof,
     ProverCommandCreateRevocationState,
     ProverCommandUpdateRevocationState,
     // VerifierCommand
     VerifierCommandVerifyProof,
+    VerifierCommandVerifyProofWithReport,
+    VerifierCommandVerifyProofWithLedger,
+    VerifierCommandVerifyProofWithLedgerContinue,
     VerifierCommandGenerateNonce,
     // AnoncredsCommand
     AnoncredsCommandToUnqualified,
+    AnoncredsCommandEncodeCredentialValues,
+    AnoncredsCommandValidate,
+    AnoncredsCommandEncodeDate,
     // BlobStorage
     BlobStorageCommandOpenReader,
     BlobStorageCommandOpenWriter,
+    BlobStorageCommandOpenTailsReader,
+    BlobStorageCommandRegisterReaderType,
+    BlobStorageCommandRegisterWriterType,
     // CryptoCommand
     CryptoCommandCreateKey,
     CryptoCommandSetKeyMetadata,
@@ -422,6 +495,10 @@ pub enum CommandMetric {
     LedgerCommandGetSchemaContinue,
     LedgerCommandGetCredDef,
     LedgerCommandGetCredDefContinue,
+    LedgerCommandGetRevocRegDef,
+    LedgerCommandGetRevocRegDefContinue,
+    LedgerCommandGetRevocReg,
+    LedgerCommandGetRevocRegContinue,
     LedgerCommandBuildTxnAuthorAgreementRequest,
     LedgerCommandBuildDisableAllTxnAuthorAgreementsRequest,
     LedgerCommandBuildGetTxnAuthorAgreementRequest,
@@ -461,6 +538,9 @@ pub enum CommandMetric {
     DidCommandQualifyDid,
     // WalletCommand
     WalletCommandRegisterWalletType,
+    WalletCommandRegisterWalletStorageType,
+    WalletCommandRegisterStorageStats,
+    WalletCommandRegisterKekProvider,
     WalletCommandCreate,
     WalletCommandCreateContinue,
     WalletCommandOpen,
@@ -468,10 +548,21 @@ pub enum CommandMetric {
     WalletCommandClose,
     WalletCommandDelete,
     WalletCommandDeleteContinue,
+    WalletCommandCheck,
+    WalletCommandCheckContinue,
     WalletCommandExport,
     WalletCommandExportContinue,
     WalletCommandImport,
     WalletCommandImportContinue,
+    WalletCommandExportToStream,
+    WalletCommandExportToStreamContinue,
+    WalletCommandImportFromStream,
+    WalletCommandImportIncrement,
+    WalletCommandImportIncrementFromStream,
+    WalletCommandImportIncrementContinue,
+    WalletCommandGetAuditLog,
+    WalletCommandGetWalletStats,
+    WalletCommandSetChangeCallback,
     WalletCommandGenerateKey,
     WalletCommandDeriveKey,
     // PairwiseCommand
@@ -487,6 +578,7 @@ pub enum CommandMetric {
     NonSecretsCommandAddRecordTags,
     NonSecretsCommandDeleteRecordTags,
     NonSecretsCommandDeleteRecord,
+    NonSecretsCommandPurgeExpiredRecords,
     NonSecretsCommandGetRecord,
     NonSecretsCommandOpenSearch,
     NonSecretsCommandFetchSearchNextRecords,
@@ -531,6 +623,8 @@ pub enum CommandMetric {
     CacheCommandGetSchemaContinue,
     CacheCommandGetCredDef,
     CacheCommandGetCredDefContinue,
+    CacheCommandGetRevocRegDef,
+    CacheCommandGetRevocRegDefContinue,
     CacheCommandPurgeSchemaCache,
     CacheCommandPurgeCredDefCache,
     // MetricsCommand
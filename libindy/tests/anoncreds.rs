@@ -85,11 +85,75 @@ mod high_cases {
 
     mod issuer_create_credential {
         use super::*;
+        use crate::utils::domain::anoncreds::credential::Credential;
 
         #[test]
         fn issuer_create_credential_works() {
             anoncreds::init_common_wallet();
         }
+
+        #[test]
+        fn issuer_create_credential_works_for_raw_only_values() {
+            let (_, credential_offer, credential_req, _) = anoncreds::init_common_wallet();
+
+            let wallet_handle = wallet::open_wallet(ANONCREDS_WALLET_CONFIG, WALLET_CREDENTIALS).unwrap();
+
+            let cred_values_json = json!({
+                "sex": {"raw": "male"},
+                "name": {"raw": "Alex", "encoded": "1139481716457488690172217916278103335"},
+                "height": {"raw": "175"},
+                "age": {"raw": "28"}
+            }).to_string();
+
+            let (cred_json, _, _) = anoncreds::issuer_create_credential(wallet_handle,
+                                                                        &credential_offer,
+                                                                        &credential_req,
+                                                                        &cred_values_json,
+                                                                        None,
+                                                                        None).unwrap();
+            let credential: Credential = serde_json::from_str(&cred_json).unwrap();
+
+            assert_eq!(anoncreds::gvt_credential_values(), credential.values.0);
+
+            wallet::close_wallet(wallet_handle).unwrap();
+        }
+    }
+
+    mod issuer_create_credentials {
+        use super::*;
+        use crate::utils::domain::anoncreds::credential::Credential;
+
+        #[test]
+        fn issuer_create_credentials_works() {
+            let (_, credential_offer, credential_req, _) = anoncreds::init_common_wallet();
+
+            let wallet_handle = wallet::open_wallet(ANONCREDS_WALLET_CONFIG, WALLET_CREDENTIALS).unwrap();
+
+            let cred_requests_json = json!([
+                {
+                    "cred_offer": serde_json::from_str::<serde_json::Value>(&credential_offer).unwrap(),
+                    "cred_req": serde_json::from_str::<serde_json::Value>(&credential_req).unwrap(),
+                    "cred_values": serde_json::from_str::<serde_json::Value>(&anoncreds::gvt_credential_values_json()).unwrap(),
+                },
+                {
+                    "cred_offer": serde_json::from_str::<serde_json::Value>(&credential_offer).unwrap(),
+                    "cred_req": serde_json::from_str::<serde_json::Value>(&credential_req).unwrap(),
+                    "cred_values": serde_json::from_str::<serde_json::Value>(&anoncreds::gvt2_credential_values_json()).unwrap(),
+                }
+            ]).to_string();
+
+            let (creds_json, revoc_reg_delta_json) = anoncreds::issuer_create_credentials(wallet_handle, &cred_requests_json, None, None).unwrap();
+            assert!(revoc_reg_delta_json.is_none());
+
+            let creds: Vec<serde_json::Value> = serde_json::from_str(&creds_json).unwrap();
+            assert_eq!(2, creds.len());
+
+            let credential: Credential = serde_json::from_value(creds[0]["cred"].clone()).unwrap();
+            assert_eq!(anoncreds::gvt_credential_values(), credential.values.0);
+            assert_eq!(serde_json::Value::Null, creds[0]["cred_revoc_id"]);
+
+            wallet::close_wallet(wallet_handle).unwrap();
+        }
     }
 
     mod prover_store_credential {
@@ -3086,6 +3150,91 @@ mod high_cases {
         }
     }
 
+    mod prover_auto_select_credentials {
+        use super::*;
+
+        #[test]
+        fn prover_auto_select_credentials_works() {
+            anoncreds::init_common_wallet();
+
+            let wallet_handle = wallet::open_wallet(ANONCREDS_WALLET_CONFIG, WALLET_CREDENTIALS).unwrap();
+
+            let proof_req = json!({
+               "nonce":"123432421212",
+               "name":"proof_req_1",
+               "version":"0.1",
+               "requested_attributes": json!({
+                   "attr1_referent": json!({
+                       "name":"name",
+                       "restrictions": json!({ "cred_def_id": anoncreds::issuer_1_gvt_cred_def_id() })
+                   })
+               }),
+               "requested_predicates": json!({
+                   "predicate1_referent": json!({
+                       "name":"age", "p_type":">=", "p_value":18,
+                       "restrictions": json!({ "cred_def_id": anoncreds::issuer_1_gvt_cred_def_id() })
+                   })
+               }),
+            }).to_string();
+
+            let selection_json = anoncreds::prover_auto_select_credentials(wallet_handle,
+                                                                           &proof_req,
+                                                                           r#"{"strategy": "fewest_credentials"}"#).unwrap();
+            let selection: serde_json::Value = serde_json::from_str(&selection_json).unwrap();
+
+            let requested_credentials = &selection["requested_credentials"];
+            assert_eq!(json!(CREDENTIAL1_ID), requested_credentials["requested_attributes"]["attr1_referent"]["cred_id"]);
+            assert_eq!(json!(true), requested_credentials["requested_attributes"]["attr1_referent"]["revealed"]);
+            assert_eq!(json!(CREDENTIAL1_ID), requested_credentials["requested_predicates"]["predicate1_referent"]["cred_id"]);
+            assert_eq!(json!({"requested_attributes": [], "requested_predicates": []}), selection["unmet_referents"]);
+
+            anoncreds::prover_create_proof(wallet_handle,
+                                           &proof_req,
+                                           &requested_credentials.to_string(),
+                                           COMMON_MASTER_SECRET,
+                                           &anoncreds::schemas_for_proof(),
+                                           &anoncreds::cred_defs_for_proof(),
+                                           "{}").unwrap();
+
+            wallet::close_wallet(wallet_handle).unwrap();
+        }
+
+        #[test]
+        fn prover_auto_select_credentials_works_for_self_attested_and_unmet_referents() {
+            anoncreds::init_common_wallet();
+
+            let wallet_handle = wallet::open_wallet(ANONCREDS_WALLET_CONFIG, WALLET_CREDENTIALS).unwrap();
+
+            let proof_req = json!({
+               "nonce":"123432421212",
+               "name":"proof_req_1",
+               "version":"0.1",
+               "requested_attributes": json!({
+                   "attr1_referent": json!({ "name":"name" }),
+                   "attr2_referent": json!({ "name":"unknown_attribute" })
+               }),
+               "requested_predicates": json!({
+                   "predicate1_referent": json!({ "name":"age", "p_type":">=", "p_value":100 })
+               }),
+            }).to_string();
+
+            let policy = json!({
+                "self_attested_attributes": json!({ "attr1_referent": "Alex" })
+            }).to_string();
+
+            let selection_json = anoncreds::prover_auto_select_credentials(wallet_handle, &proof_req, &policy).unwrap();
+            let selection: serde_json::Value = serde_json::from_str(&selection_json).unwrap();
+
+            assert_eq!(json!({"attr1_referent": "Alex"}), selection["requested_credentials"]["self_attested_attributes"]);
+            assert_eq!(json!({}), selection["requested_credentials"]["requested_attributes"]);
+            assert_eq!(json!({}), selection["requested_credentials"]["requested_predicates"]);
+            assert_eq!(json!({"requested_attributes": ["attr2_referent"], "requested_predicates": ["predicate1_referent"]}),
+                       selection["unmet_referents"]);
+
+            wallet::close_wallet(wallet_handle).unwrap();
+        }
+    }
+
     mod prover_create_proof_works {
         use super::*;
 
@@ -3344,6 +3493,179 @@ mod high_cases {
         assert!(!valid);
     }
 
+    mod verifier_verify_proof_with_report {
+        use super::*;
+        use crate::utils::domain::anoncreds::proof_verification_report::{ProofVerificationReport, VerificationStatus};
+
+        fn _verify_with_report(proof_req_json: &str, proof_json: &str, cred_defs_json: &str) -> ProofVerificationReport {
+            let report_json = anoncreds::verifier_verify_proof_with_report(proof_req_json,
+                                                                           proof_json,
+                                                                           &anoncreds::schemas_for_proof(),
+                                                                           cred_defs_json,
+                                                                           "{}",
+                                                                           "{}").unwrap();
+            serde_json::from_str(&report_json).unwrap()
+        }
+
+        #[test]
+        fn verifier_verify_proof_with_report_works_for_correct_proof() {
+            let report = _verify_with_report(&anoncreds::proof_request_attr(), &anoncreds::proof_json(), &anoncreds::cred_defs_for_proof());
+
+            assert!(report.valid);
+            assert_eq!(VerificationStatus::Valid, report.status);
+            assert!(report.crypto.unwrap().passed);
+
+            let attr_report = &report.requested_attributes["attr1_referent"];
+            assert!(attr_report.received.passed);
+            assert!(attr_report.values.as_ref().unwrap().passed);
+            assert_eq!(Some(0), attr_report.sub_proof_index);
+
+            assert_eq!(1, report.sub_proofs.len());
+            assert!(report.sub_proofs[0].ledger_data.passed);
+            assert!(report.sub_proofs[0].non_revoked.is_none());
+        }
+
+        #[test]
+        fn verifier_verify_proof_with_report_works_for_non_canonical_encoding() {
+            let report = _verify_with_report(&anoncreds::proof_request_attr(), &anoncreds::proof_json(), &anoncreds::cred_defs_for_proof());
+
+            // "Alex" is encoded with a custom encoding in the proof, that is reported but doesn't make the proof invalid
+            assert!(report.valid);
+            assert!(!report.requested_attributes["attr1_referent"].encoding.as_ref().unwrap().passed);
+        }
+
+        #[test]
+        fn verifier_verify_proof_with_report_works_for_missed_predicate() {
+            let proof_req_json = json!({
+               "nonce":"123432421212",
+               "name":"proof_req_1",
+               "version":"0.1",
+               "requested_attributes": {
+                   "attr1_referent": { "name":"name" }
+               },
+               "requested_predicates": {
+                   "predicate1_referent": { "name":"age", "p_type":">=", "p_value":18 }
+               },
+            }).to_string();
+
+            let report = _verify_with_report(&proof_req_json, &anoncreds::proof_json(), &anoncreds::cred_defs_for_proof());
+
+            assert!(!report.valid);
+            assert_eq!(VerificationStatus::RequestNotSatisfied, report.status);
+            assert!(report.requested_attributes["attr1_referent"].received.passed);
+            assert!(!report.requested_predicates["predicate1_referent"].received.passed);
+        }
+
+        #[test]
+        fn verifier_verify_proof_with_report_works_for_failed_restriction() {
+            let proof_req_json = json!({
+               "nonce":"123432421212",
+               "name":"proof_req_1",
+               "version":"0.1",
+               "requested_attributes": {
+                   "attr1_referent": {
+                       "name":"name",
+                       "restrictions": { "issuer_did": { "$in": ["NO DID"] } }
+                   }
+               },
+               "requested_predicates": {},
+            }).to_string();
+
+            let report = _verify_with_report(&proof_req_json, &anoncreds::proof_json(), &anoncreds::cred_defs_for_proof());
+
+            assert_eq!(VerificationStatus::RequestNotSatisfied, report.status);
+
+            let restrictions = report.requested_attributes["attr1_referent"].restrictions.as_ref().unwrap();
+            assert!(!restrictions.passed);
+            assert!(restrictions.error.is_some());
+        }
+
+        #[test]
+        fn verifier_verify_proof_with_report_works_for_wrong_revealed_attr_value() {
+            let proof_json = anoncreds::proof_json().replace(r#"name":"1139481716457488690172217916278103335"#, r#"name":"1111111111111111111111111111111111111"#);
+
+            let report = _verify_with_report(&anoncreds::proof_request_attr(), &proof_json, &anoncreds::cred_defs_for_proof());
+
+            assert_eq!(VerificationStatus::ProofInvalid, report.status);
+            assert!(!report.requested_attributes["attr1_referent"].values.as_ref().unwrap().passed);
+        }
+
+        #[test]
+        fn verifier_verify_proof_with_report_works_for_missed_cred_def() {
+            let report = _verify_with_report(&anoncreds::proof_request_attr(), &anoncreds::proof_json(), "{}");
+
+            assert!(!report.valid);
+            assert_eq!(VerificationStatus::MissingLedgerData, report.status);
+            assert!(report.crypto.is_none());
+            assert!(!report.sub_proofs[0].ledger_data.passed);
+        }
+    }
+
+    mod verifier_verify_proof_with_ledger {
+        use super::*;
+        use indy::{IndyError, WalletHandle};
+        use crate::utils::non_secrets;
+        use crate::utils::domain::anoncreds::proof_verification_report::{ProofVerificationReport, VerificationStatus};
+
+        // Ledger objects are put to the cache directly, so verification doesn't need a pool
+        fn _cache_ledger_objects(wallet_handle: WalletHandle, objects_json: &str, cache_type: &str) {
+            let objects: serde_json::Map<String, serde_json::Value> = serde_json::from_str(objects_json).unwrap();
+
+            for (id, object) in objects {
+                non_secrets::add_wallet_record(wallet_handle, cache_type, &id, &object.to_string(), None).unwrap();
+            }
+        }
+
+        fn _verify_with_ledger(setup: &Setup, options_json: &str) -> Result<ProofVerificationReport, IndyError> {
+            anoncreds::verifier_verify_proof_with_ledger(setup.pool_handle,
+                                                         setup.wallet_handle,
+                                                         DID_MY1,
+                                                         &anoncreds::proof_request_attr(),
+                                                         &anoncreds::proof_json(),
+                                                         options_json)
+                .map(|report_json| serde_json::from_str(&report_json).unwrap())
+        }
+
+        #[test]
+        fn verifier_verify_proof_with_ledger_works_for_cached_ledger_objects() {
+            let setup = Setup::wallet();
+
+            _cache_ledger_objects(setup.wallet_handle, &anoncreds::schemas_for_proof(), "schema_cache");
+            _cache_ledger_objects(setup.wallet_handle, &anoncreds::cred_defs_for_proof(), "cred_def_cache");
+
+            let report = _verify_with_ledger(&setup, r#"{"noUpdate": true}"#).unwrap();
+
+            assert!(report.valid);
+            assert_eq!(VerificationStatus::Valid, report.status);
+            assert!(report.sub_proofs[0].ledger_data.passed);
+        }
+
+        #[test]
+        fn verifier_verify_proof_with_ledger_works_for_missed_ledger_object() {
+            let setup = Setup::wallet();
+
+            _cache_ledger_objects(setup.wallet_handle, &anoncreds::schemas_for_proof(), "schema_cache");
+
+            let report = _verify_with_ledger(&setup, r#"{"noUpdate": true}"#).unwrap();
+
+            assert!(!report.valid);
+            assert_eq!(VerificationStatus::MissingLedgerData, report.status);
+            assert!(report.crypto.is_none());
+            assert!(!report.sub_proofs[0].ledger_data.passed);
+        }
+
+        #[test]
+        fn verifier_verify_proof_with_ledger_works_for_ledger_error() {
+            let setup = Setup::wallet();
+
+            _cache_ledger_objects(setup.wallet_handle, &anoncreds::schemas_for_proof(), "schema_cache");
+
+            // credential definition is requested from the pool that isn't opened
+            let res = _verify_with_ledger(&setup, "{}");
+            assert_code!(ErrorCode::PoolLedgerInvalidPoolHandle, res);
+        }
+    }
+
     mod verifier_verify_proof_with_proof_req_restrictions {
         use super::*;
 
@@ -3742,6 +4064,240 @@ mod high_cases {
         }
     }
 
+    mod encode_credential_values {
+        use super::*;
+        use crate::utils::domain::anoncreds::credential::CredentialValues;
+
+        #[test]
+        fn encode_credential_values_works() {
+            let raw_values_json = json!({"sex": "male", "height": "175"}).to_string();
+
+            let cred_values_json = anoncreds::encode_credential_values(&raw_values_json).unwrap();
+            let cred_values: CredentialValues = serde_json::from_str(&cred_values_json).unwrap();
+
+            let expected = anoncreds::gvt_credential_values();
+            assert_eq!(2, cred_values.0.len());
+            assert_eq!(expected["sex"], cred_values.0["sex"]);
+            assert_eq!(expected["height"], cred_values.0["height"]);
+        }
+    }
+
+    mod anoncreds_validate {
+        use super::*;
+
+        fn _problems(object_type: &str, object_json: &str) -> Vec<String> {
+            let problems_json = anoncreds::validate(object_type, object_json, None, None).unwrap();
+            serde_json::from_str(&problems_json).unwrap()
+        }
+
+        fn _problems_with_context(object_type: &str, object_json: &str, schema_json: Option<&str>, cred_def_json: Option<&str>) -> Vec<String> {
+            let problems_json = anoncreds::validate(object_type, object_json, schema_json, cred_def_json).unwrap();
+            serde_json::from_str(&problems_json).unwrap()
+        }
+
+        #[test]
+        fn anoncreds_validate_works_for_valid_objects() {
+            let (cred_def_json, cred_offer_json, cred_req_json, cred_json) = anoncreds::init_common_wallet();
+
+            assert!(_problems("schema", &anoncreds::gvt_schema_json()).is_empty());
+            assert!(_problems("cred_def", cred_def_json).is_empty());
+            assert!(_problems("cred_offer", cred_offer_json).is_empty());
+            assert!(_problems("cred_req", cred_req_json).is_empty());
+            assert!(_problems("cred", cred_json).is_empty());
+            assert!(_problems("proof_req", &anoncreds::proof_request_attr_and_predicate()).is_empty());
+            assert!(_problems("proof", &anoncreds::proof_json()).is_empty());
+        }
+
+        #[test]
+        fn anoncreds_validate_works_for_objects_matching_context() {
+            let (cred_def_json, cred_offer_json, cred_req_json, cred_json) = anoncreds::init_common_wallet();
+            let schema_json = anoncreds::gvt_schema_json();
+
+            assert!(_problems_with_context("cred_def", cred_def_json, Some(&schema_json), None).is_empty());
+            assert!(_problems_with_context("cred_offer", cred_offer_json, Some(&schema_json), Some(cred_def_json)).is_empty());
+            assert!(_problems_with_context("cred_req", cred_req_json, None, Some(cred_def_json)).is_empty());
+            assert!(_problems_with_context("cred", cred_json, Some(&schema_json), Some(cred_def_json)).is_empty());
+            assert!(_problems_with_context("cred", cred_json, None, Some(cred_def_json)).is_empty());
+            assert!(_problems_with_context("proof_req", &anoncreds::proof_request_attr_and_predicate(), Some(&schema_json), None).is_empty());
+            assert!(_problems_with_context("proof", &anoncreds::proof_json(), Some(&schema_json), None).is_empty());
+        }
+
+        #[test]
+        fn anoncreds_validate_works_for_mixed_qualified_and_unqualified_ids() {
+            let (_, cred_offer_json, _, _) = anoncreds::init_common_wallet();
+
+            let mut cred_offer: serde_json::Value = serde_json::from_str(cred_offer_json).unwrap();
+            cred_offer["schema_id"] = json!(anoncreds::gvt_schema_id_fully_qualified());
+
+            let problems = _problems("cred_offer", &cred_offer.to_string());
+            assert_eq!(1, problems.len());
+            assert!(problems[0].contains("Mix of fully qualified"));
+        }
+
+        #[test]
+        fn anoncreds_validate_works_for_invalid_non_revoked_interval() {
+            let proof_req_json = json!({
+                "nonce": "123432421212",
+                "name": "proof_req_1",
+                "version": "0.1",
+                "requested_attributes": {
+                    "attr1_referent": {"name": "name", "non_revoked": {"from": 200, "to": 100}}
+                },
+                "requested_predicates": {},
+            }).to_string();
+
+            let problems = _problems("proof_req", &proof_req_json);
+            assert_eq!(1, problems.len());
+            assert!(problems[0].contains("attr1_referent"));
+        }
+    }
+
+    mod between_predicate {
+        use super::*;
+        use indy::WalletHandle;
+
+        const DOB_SCHEMA_NAME: &str = "dob";
+        const DOB_SCHEMA_ATTRIBUTES: &str = r#"["name", "dob"]"#;
+
+        // issues credential for Alex born on 2000-01-15, returns schemas and cred defs for proving
+        fn _issue_dob_credential(wallet_handle: WalletHandle) -> (String, String) {
+            let (schema_id, schema_json, cred_def_id, cred_def_json) =
+                anoncreds::multi_steps_issuer_preparation(wallet_handle, ISSUER_DID, DOB_SCHEMA_NAME, DOB_SCHEMA_ATTRIBUTES);
+
+            anoncreds::prover_create_master_secret(wallet_handle, COMMON_MASTER_SECRET).unwrap();
+
+            let dob = anoncreds::encode_date("2000-01-15").unwrap();
+            let cred_values_json = json!({
+                "name": {"raw": "Alex"},
+                "dob": {"raw": dob}
+            }).to_string();
+
+            anoncreds::multi_steps_create_credential(COMMON_MASTER_SECRET,
+                                                     wallet_handle,
+                                                     wallet_handle,
+                                                     CREDENTIAL1_ID,
+                                                     &cred_values_json,
+                                                     &cred_def_id,
+                                                     &cred_def_json);
+
+            let schemas_json = json!({schema_id: serde_json::from_str::<serde_json::Value>(&schema_json).unwrap()}).to_string();
+            let cred_defs_json = json!({cred_def_id: serde_json::from_str::<serde_json::Value>(&cred_def_json).unwrap()}).to_string();
+
+            (schemas_json, cred_defs_json)
+        }
+
+        fn _proof_req_dob_between(from: &str, to: &str) -> String {
+            json!({
+                "nonce": anoncreds::generate_nonce().unwrap(),
+                "name": "proof_req_dob",
+                "version": "0.1",
+                "requested_attributes": {},
+                "requested_predicates": {
+                    "predicate1_referent": {
+                        "name": "dob",
+                        "p_type": "between",
+                        "p_value": anoncreds::encode_date(from).unwrap().parse::<i32>().unwrap(),
+                        "p_value_to": anoncreds::encode_date(to).unwrap().parse::<i32>().unwrap()
+                    }
+                }
+            }).to_string()
+        }
+
+        #[test]
+        fn encode_date_works() {
+            assert_eq!("20000115", anoncreds::encode_date("2000-01-15").unwrap());
+        }
+
+        #[test]
+        fn anoncreds_works_for_between_date_predicate() {
+            let setup = Setup::wallet();
+
+            let (schemas_json, cred_defs_json) = _issue_dob_credential(setup.wallet_handle);
+
+            let proof_req_json = _proof_req_dob_between("1990-01-01", "2005-12-31");
+
+            let credentials_json = anoncreds::prover_get_credentials_for_proof_req(setup.wallet_handle, &proof_req_json).unwrap();
+            let credentials: CredentialsForProofRequest = serde_json::from_str(&credentials_json).unwrap();
+            assert_eq!(1, credentials.predicates["predicate1_referent"].len());
+
+            let requested_credentials_json = json!({
+                "self_attested_attributes": {},
+                "requested_attributes": {},
+                "requested_predicates": {
+                    "predicate1_referent": {"cred_id": CREDENTIAL1_ID}
+                }
+            }).to_string();
+
+            let proof_json = anoncreds::prover_create_proof(setup.wallet_handle,
+                                                            &proof_req_json,
+                                                            &requested_credentials_json,
+                                                            COMMON_MASTER_SECRET,
+                                                            &schemas_json,
+                                                            &cred_defs_json,
+                                                            "{}").unwrap();
+
+            let proof: Proof = serde_json::from_str(&proof_json).unwrap();
+            assert_eq!(0, proof.requested_proof.predicates["predicate1_referent"].sub_proof_index);
+
+            let valid = anoncreds::verifier_verify_proof(&proof_req_json,
+                                                         &proof_json,
+                                                         &schemas_json,
+                                                         &cred_defs_json,
+                                                         "{}",
+                                                         "{}").unwrap();
+            assert!(valid);
+        }
+
+        #[test]
+        fn anoncreds_works_for_between_date_predicate_bounds() {
+            let setup = Setup::wallet();
+
+            _issue_dob_credential(setup.wallet_handle);
+
+            // bounds are inclusive
+            let proof_req_json = _proof_req_dob_between("2000-01-15", "2000-01-15");
+            let credentials_json = anoncreds::prover_get_credentials_for_proof_req(setup.wallet_handle, &proof_req_json).unwrap();
+            let credentials: CredentialsForProofRequest = serde_json::from_str(&credentials_json).unwrap();
+            assert_eq!(1, credentials.predicates["predicate1_referent"].len());
+
+            let proof_req_json = _proof_req_dob_between("2000-01-16", "2010-12-31");
+            let credentials_json = anoncreds::prover_get_credentials_for_proof_req(setup.wallet_handle, &proof_req_json).unwrap();
+            let credentials: CredentialsForProofRequest = serde_json::from_str(&credentials_json).unwrap();
+            assert_eq!(0, credentials.predicates["predicate1_referent"].len());
+
+            let proof_req_json = _proof_req_dob_between("1990-01-01", "2000-01-14");
+            let credentials_json = anoncreds::prover_get_credentials_for_proof_req(setup.wallet_handle, &proof_req_json).unwrap();
+            let credentials: CredentialsForProofRequest = serde_json::from_str(&credentials_json).unwrap();
+            assert_eq!(0, credentials.predicates["predicate1_referent"].len());
+        }
+
+        #[test]
+        fn prover_create_proof_works_for_unsatisfied_between_predicate() {
+            let setup = Setup::wallet();
+
+            let (schemas_json, cred_defs_json) = _issue_dob_credential(setup.wallet_handle);
+
+            let proof_req_json = _proof_req_dob_between("2001-01-01", "2010-12-31");
+
+            let requested_credentials_json = json!({
+                "self_attested_attributes": {},
+                "requested_attributes": {},
+                "requested_predicates": {
+                    "predicate1_referent": {"cred_id": CREDENTIAL1_ID}
+                }
+            }).to_string();
+
+            let res = anoncreds::prover_create_proof(setup.wallet_handle,
+                                                     &proof_req_json,
+                                                     &requested_credentials_json,
+                                                     COMMON_MASTER_SECRET,
+                                                     &schemas_json,
+                                                     &cred_defs_json,
+                                                     "{}");
+            assert_code!(ErrorCode::CommonInvalidStructure, res);
+        }
+    }
+
     mod to_unqualified {
         use super::*;
         use utils::domain::anoncreds::schema::SchemaV1;
@@ -4152,6 +4708,185 @@ mod medium_cases {
         }
     }
 
+    mod encode_credential_values {
+        use super::*;
+
+        #[test]
+        fn encode_credential_values_works_for_invalid_raw_values() {
+            let res = anoncreds::encode_credential_values(r#"{"sex": {"raw": "male"}}"#);
+            assert_code!(ErrorCode::CommonInvalidStructure, res);
+        }
+    }
+
+    mod anoncreds_validate {
+        use super::*;
+
+        fn _problems(object_type: &str, object_json: &str) -> Vec<String> {
+            let problems_json = anoncreds::validate(object_type, object_json, None, None).unwrap();
+            serde_json::from_str(&problems_json).unwrap()
+        }
+
+        fn _problems_with_context(object_type: &str, object_json: &str, schema_json: Option<&str>, cred_def_json: Option<&str>) -> Vec<String> {
+            let problems_json = anoncreds::validate(object_type, object_json, schema_json, cred_def_json).unwrap();
+            serde_json::from_str(&problems_json).unwrap()
+        }
+
+        #[test]
+        fn anoncreds_validate_works_for_unknown_object_type() {
+            let res = anoncreds::validate("revocation_state", "{}", None, None);
+            assert_code!(ErrorCode::CommonInvalidStructure, res);
+        }
+
+        #[test]
+        fn anoncreds_validate_works_for_unparsable_object() {
+            let problems = _problems("cred_offer", r#"{"schema_id": 1}"#);
+            assert_eq!(1, problems.len());
+
+            let problems = _problems("proof", "not a json");
+            assert_eq!(1, problems.len());
+        }
+
+        #[test]
+        fn anoncreds_validate_works_for_schema_attribute_names() {
+            let schema_json = json!({
+                "ver": "1.0",
+                "id": anoncreds::gvt_schema_id(),
+                "name": GVT_SCHEMA_NAME,
+                "version": SCHEMA_VERSION,
+                "attrNames": ["name", "Name", "master_secret", " "],
+                "seqNo": null
+            }).to_string();
+
+            let problems = _problems("schema", &schema_json);
+            assert_eq!(3, problems.len());
+        }
+
+        #[test]
+        fn anoncreds_validate_works_for_credential_attributes_not_matching_schema() {
+            let (_, _, _, cred_json) = anoncreds::init_common_wallet();
+
+            let mut credential: serde_json::Value = serde_json::from_str(cred_json).unwrap();
+            credential["values"].as_object_mut().unwrap().remove("height");
+            credential["values"]["weight"] = json!({"raw": "80", "encoded": "80"});
+
+            let problems = _problems_with_context("cred", &credential.to_string(), Some(&anoncreds::gvt_schema_json()), None);
+            assert_eq!(2, problems.len());
+            assert!(problems.iter().any(|problem| problem.contains("\"weight\" which is not present in Schema")));
+            assert!(problems.iter().any(|problem| problem.contains("\"height\" is missing in Credential")));
+        }
+
+        #[test]
+        fn anoncreds_validate_works_for_proof_request_attribute_not_in_schema() {
+            let problems = _problems_with_context("proof_req", &anoncreds::proof_request_attr_and_predicate(), Some(&anoncreds::xyz_schema_json()), None);
+            assert_eq!(2, problems.len());
+            assert!(problems[0].contains("attr1_referent"));
+            assert!(problems[1].contains("predicate1_referent"));
+        }
+
+        #[test]
+        fn anoncreds_validate_works_for_cred_offer_for_other_schema() {
+            let (_, cred_offer_json, _, _) = anoncreds::init_common_wallet();
+
+            let problems = _problems_with_context("cred_offer", cred_offer_json, Some(&anoncreds::xyz_schema_json()), None);
+            assert_eq!(1, problems.len());
+            assert!(problems[0].contains("doesn't match Schema Id"));
+        }
+
+        #[test]
+        fn anoncreds_validate_works_for_invalid_schema_context() {
+            let (_, cred_offer_json, _, _) = anoncreds::init_common_wallet();
+
+            let res = anoncreds::validate("cred_offer", cred_offer_json, Some("not a schema"), None);
+            assert_code!(ErrorCode::CommonInvalidStructure, res);
+        }
+
+        #[test]
+        fn anoncreds_validate_works_for_proof_sub_proof_index_out_of_range() {
+            let mut proof: serde_json::Value = serde_json::from_str(&anoncreds::proof_json()).unwrap();
+            proof["requested_proof"]["revealed_attrs"]["attr1_referent"]["sub_proof_index"] = json!(1);
+
+            let problems = _problems("proof", &proof.to_string());
+            assert_eq!(1, problems.len());
+            assert!(problems[0].contains("attr1_referent"));
+        }
+
+        #[test]
+        fn anoncreds_validate_works_for_proof_timestamp_without_rev_reg_id() {
+            let mut proof: serde_json::Value = serde_json::from_str(&anoncreds::proof_json()).unwrap();
+            proof["identifiers"][0]["timestamp"] = json!(100);
+
+            let problems = _problems("proof", &proof.to_string());
+            assert_eq!(1, problems.len());
+        }
+    }
+
+    mod between_predicate {
+        use super::*;
+
+        #[test]
+        fn encode_date_works_for_invalid_date() {
+            let res = anoncreds::encode_date("2000-02-30");
+            assert_code!(ErrorCode::CommonInvalidStructure, res);
+
+            let res = anoncreds::encode_date("15.01.2000");
+            assert_code!(ErrorCode::CommonInvalidStructure, res);
+        }
+
+        #[test]
+        fn prover_get_credentials_for_proof_req_works_for_between_without_upper_bound() {
+            let setup = Setup::wallet();
+
+            let proof_req_json = json!({
+                "nonce": "123432421212",
+                "name": "proof_req_1",
+                "version": "0.1",
+                "requested_attributes": {},
+                "requested_predicates": {
+                    "predicate1_referent": {"name": "dob", "p_type": "between", "p_value": 19900101}
+                }
+            }).to_string();
+
+            let res = anoncreds::prover_get_credentials_for_proof_req(setup.wallet_handle, &proof_req_json);
+            assert_code!(ErrorCode::CommonInvalidStructure, res);
+        }
+
+        #[test]
+        fn prover_get_credentials_for_proof_req_works_for_between_with_inverted_bounds() {
+            let setup = Setup::wallet();
+
+            let proof_req_json = json!({
+                "nonce": "123432421212",
+                "name": "proof_req_1",
+                "version": "0.1",
+                "requested_attributes": {},
+                "requested_predicates": {
+                    "predicate1_referent": {"name": "dob", "p_type": "between", "p_value": 20051231, "p_value_to": 19900101}
+                }
+            }).to_string();
+
+            let res = anoncreds::prover_get_credentials_for_proof_req(setup.wallet_handle, &proof_req_json);
+            assert_code!(ErrorCode::CommonInvalidStructure, res);
+        }
+
+        #[test]
+        fn prover_get_credentials_for_proof_req_works_for_upper_bound_of_not_between_predicate() {
+            let setup = Setup::wallet();
+
+            let proof_req_json = json!({
+                "nonce": "123432421212",
+                "name": "proof_req_1",
+                "version": "0.1",
+                "requested_attributes": {},
+                "requested_predicates": {
+                    "predicate1_referent": {"name": "age", "p_type": ">=", "p_value": 18, "p_value_to": 65}
+                }
+            }).to_string();
+
+            let res = anoncreds::prover_get_credentials_for_proof_req(setup.wallet_handle, &proof_req_json);
+            assert_code!(ErrorCode::CommonInvalidStructure, res);
+        }
+    }
+
     mod issuer_create_credential {
         use super::*;
 
@@ -4237,6 +4972,22 @@ mod medium_cases {
         }
     }
 
+    mod issuer_create_credentials {
+        use super::*;
+
+        #[test]
+        fn issuer_create_credentials_works_for_empty_list() {
+            anoncreds::init_common_wallet();
+
+            let wallet_handle = wallet::open_wallet(ANONCREDS_WALLET_CONFIG, WALLET_CREDENTIALS).unwrap();
+
+            let res = anoncreds::issuer_create_credentials(wallet_handle, "[]", None, None);
+            assert_code!(ErrorCode::CommonInvalidStructure, res);
+
+            wallet::close_wallet(wallet_handle).unwrap();
+        }
+    }
+
     mod prover_store_credential {
         use super::*;
 
@@ -4405,6 +5156,42 @@ mod medium_cases {
         }
     }
 
+    mod prover_auto_select_credentials {
+        use super::*;
+
+        #[test]
+        fn prover_auto_select_credentials_works_for_unknown_strategy() {
+            anoncreds::init_common_wallet();
+
+            let wallet_handle = wallet::open_wallet(ANONCREDS_WALLET_CONFIG, WALLET_CREDENTIALS).unwrap();
+
+            let res = anoncreds::prover_auto_select_credentials(wallet_handle,
+                                                                &anoncreds::proof_request_attr_and_predicate(),
+                                                                r#"{"strategy": "oldest"}"#);
+            assert_code!(ErrorCode::CommonInvalidStructure, res);
+
+            wallet::close_wallet(wallet_handle).unwrap();
+        }
+
+        #[test]
+        fn prover_auto_select_credentials_works_for_unknown_self_attested_referent() {
+            anoncreds::init_common_wallet();
+
+            let wallet_handle = wallet::open_wallet(ANONCREDS_WALLET_CONFIG, WALLET_CREDENTIALS).unwrap();
+
+            let policy = json!({
+                "self_attested_attributes": json!({ "attr2_referent": "value" })
+            }).to_string();
+
+            let res = anoncreds::prover_auto_select_credentials(wallet_handle,
+                                                                &anoncreds::proof_request_attr_and_predicate(),
+                                                                &policy);
+            assert_code!(ErrorCode::CommonInvalidStructure, res);
+
+            wallet::close_wallet(wallet_handle).unwrap();
+        }
+    }
+
     mod prover_create_proof_works {
         use super::*;
 
@@ -10,7 +10,7 @@ extern crate indyrs as api;
 mod demos {
     use super::*;
 
-    use crate::utils::{wallet, anoncreds};
+    use crate::utils::{wallet, anoncreds, blob_storage};
     use crate::utils::anoncreds::{COMMON_MASTER_SECRET, CREDENTIAL1_ID, CREDENTIAL2_ID, CREDENTIAL3_ID};
 
     use indy::ErrorCode;
@@ -25,8 +25,15 @@ mod demos {
     use crate::utils::domain::anoncreds::credential_for_proof_request::CredentialsForProofRequest;
     use crate::utils::domain::anoncreds::proof::Proof;
     use crate::utils::domain::anoncreds::revocation_state::RevocationState;
-    use crate::utils::domain::anoncreds::revocation_registry::RevocationRegistry;
+    use crate::utils::domain::anoncreds::revocation_registry::{RevocationRegistry, RevocationRegistryStatus};
     use crate::utils::domain::anoncreds::credential_offer::CredentialOffer;
+    use crate::utils::domain::anoncreds::credential::Credential;
+    use crate::utils::domain::anoncreds::revocation_registry_pool::RevocationRegistryPool;
+
+    use std::ffi::CStr;
+    use std::os::raw::c_char;
+    use std::sync::Mutex;
+    use std::collections::HashSet;
 
     #[test]
     fn anoncreds_works_for_single_issuer_single_prover() {
@@ -2171,6 +2178,105 @@ mod demos {
     }
 
 
+    #[cfg(feature = "revocation_tests")]
+    #[test]
+    fn anoncreds_works_for_revocation_registry_pool_rollover() {
+        Setup::empty();
+
+        lazy_static! {
+            static ref CREATED_REV_REG_IDS: Mutex<Vec<String>> = Default::default();
+        }
+
+        extern fn revoc_reg_created_hook(revoc_reg_id: *const c_char, _revoc_reg_def_json: *const c_char, _revoc_reg_entry_json: *const c_char) -> i32 {
+            let revoc_reg_id = unsafe { CStr::from_ptr(revoc_reg_id) }.to_str().unwrap().to_string();
+            CREATED_REV_REG_IDS.lock().unwrap().push(revoc_reg_id);
+            ErrorCode::Success as i32
+        }
+
+        //1. Issuer creates wallet, gets wallet handle
+        let (issuer_wallet_handle, issuer_wallet_config) = wallet::create_and_open_default_wallet("anoncreds_works_for_revocation_registry_pool_rollover").unwrap();
+
+        //2. Prover creates wallet, gets wallet handle
+        let (prover_wallet_handle, prover_wallet_config) = wallet::create_and_open_default_wallet("anoncreds_works_for_revocation_registry_pool_rollover").unwrap();
+
+        //3. Issuer creates Schema and Credential Definition
+        let (_, schema_json) = anoncreds::issuer_create_schema(ISSUER_DID,
+                                                               GVT_SCHEMA_NAME,
+                                                               SCHEMA_VERSION,
+                                                               GVT_SCHEMA_ATTRIBUTES).unwrap();
+
+        let (cred_def_id, cred_def_json) = anoncreds::issuer_create_credential_definition(issuer_wallet_handle,
+                                                                                          ISSUER_DID,
+                                                                                          &schema_json,
+                                                                                          TAG_1,
+                                                                                          None,
+                                                                                          Some(&anoncreds::revocation_cred_def_config())).unwrap();
+
+        //4. Issuer registers hook and creates Revocation Registry Pool holding one credential per registry
+        anoncreds::issuer_register_revoc_reg_created_hook(Some(revoc_reg_created_hook)).unwrap();
+
+        let tails_writer_config = anoncreds::tails_writer_config();
+        let pool_config = json!({
+            "max_cred_num": 1,
+            "tails_writer_config": tails_writer_config,
+        }).to_string();
+
+        let pool_json = anoncreds::issuer_create_revoc_reg_pool(issuer_wallet_handle, ISSUER_DID, &cred_def_id, &pool_config).unwrap();
+        let pool: RevocationRegistryPool = serde_json::from_str(&pool_json).unwrap();
+
+        assert_ne!(pool.active, pool.next);
+        assert_eq!(vec![pool.active.clone(), pool.next.clone()], pool.registries);
+        assert!(pool.is_published(&pool.active) && pool.is_published(&pool.next));
+
+        let blob_storage_reader_handle = blob_storage::open_reader(TYPE, &tails_writer_config).unwrap();
+
+        //5. Prover creates Master Secret
+        anoncreds::prover_create_master_secret(prover_wallet_handle, COMMON_MASTER_SECRET).unwrap();
+
+        //6. Issuer issues credentials passing the id of the first pool registry
+        let mut issued_rev_reg_ids = Vec::new();
+        for _ in 0..2 {
+            let cred_offer_json = anoncreds::issuer_create_credential_offer(issuer_wallet_handle, &cred_def_id).unwrap();
+
+            let (cred_req_json, _) = anoncreds::prover_create_credential_req(prover_wallet_handle,
+                                                                             DID_MY1,
+                                                                             &cred_offer_json,
+                                                                             &cred_def_json,
+                                                                             COMMON_MASTER_SECRET).unwrap();
+
+            let (cred_json, cred_rev_id, _) = anoncreds::issuer_create_credential(issuer_wallet_handle,
+                                                                                  &cred_offer_json,
+                                                                                  &cred_req_json,
+                                                                                  &anoncreds::gvt_credential_values_json(),
+                                                                                  Some(&pool.active.0),
+                                                                                  Some(blob_storage_reader_handle)).unwrap();
+            assert_eq!(Some("1".to_string()), cred_rev_id);
+
+            let credential: Credential = serde_json::from_str(&cred_json).unwrap();
+            issued_rev_reg_ids.push(credential.rev_reg_id.unwrap());
+        }
+
+        //7. Check the second credential is issued in the pre-provisioned registry and a new one is provisioned
+        assert_eq!(vec![pool.active.clone(), pool.next.clone()], issued_rev_reg_ids);
+
+        let rolled_pool_json = anoncreds::issuer_get_revoc_reg_pool(issuer_wallet_handle, &cred_def_id).unwrap();
+        let rolled_pool: RevocationRegistryPool = serde_json::from_str(&rolled_pool_json).unwrap();
+
+        assert_eq!(pool.next, rolled_pool.active);
+        assert_eq!(3, rolled_pool.registries.len());
+        assert_eq!(rolled_pool.registries[2], rolled_pool.next);
+
+        let created_rev_reg_ids = CREATED_REV_REG_IDS.lock().unwrap().clone();
+        for rev_reg_id in rolled_pool.registries.iter() {
+            assert!(created_rev_reg_ids.contains(&rev_reg_id.0));
+        }
+
+        anoncreds::issuer_register_revoc_reg_created_hook(None).unwrap();
+
+        wallet::close_and_delete_wallet(issuer_wallet_handle, &issuer_wallet_config).unwrap();
+        wallet::close_and_delete_wallet(prover_wallet_handle, &prover_wallet_config).unwrap();
+    }
+
     #[cfg(feature = "revocation_tests")]
     #[test]
     fn anoncreds_works_for_issuance_by_default_revocation_strategy_for_full_revocation_registry() {
@@ -2327,6 +2433,205 @@ mod demos {
     }
 
 
+    #[cfg(feature = "revocation_tests")]
+    #[test]
+    fn anoncreds_works_for_batch_issuance_and_batch_revocation() {
+        Setup::empty();
+
+        //1. Issuer creates wallet, gets wallet handle
+        let (issuer_wallet_handle, issuer_wallet_config) = wallet::create_and_open_default_wallet("anoncreds_works_for_batch_issuance_and_batch_revocation").unwrap();
+
+        //2. Prover creates wallet, gets wallet handle
+        let (prover_wallet_handle, prover_wallet_config) = wallet::create_and_open_default_wallet("anoncreds_works_for_batch_issuance_and_batch_revocation").unwrap();
+
+        //3. Issuer creates Schema, Credential Definition and Revocation Registry
+        let (_, _,
+            cred_def_id, cred_def_json,
+            rev_reg_id, revoc_reg_def_json, _,
+            blob_storage_reader_handle) = anoncreds::multi_steps_issuer_revocation_preparation(issuer_wallet_handle,
+                                                                                               ISSUER_DID,
+                                                                                               GVT_SCHEMA_NAME,
+                                                                                               GVT_SCHEMA_ATTRIBUTES,
+                                                                                               r#"{"max_cred_num":5, "issuance_type":"ISSUANCE_ON_DEMAND"}"#);
+
+        //4. Prover creates Master Secret and Credential Requests
+        anoncreds::prover_create_master_secret(prover_wallet_handle, COMMON_MASTER_SECRET).unwrap();
+
+        let cred_values = vec![anoncreds::gvt_credential_values_json(), anoncreds::gvt2_credential_values_json(), anoncreds::gvt3_credential_values_json()];
+
+        let mut cred_requests = Vec::new();
+        let mut cred_req_metadatas = Vec::new();
+        for cred_values_json in cred_values.iter() {
+            let cred_offer_json = anoncreds::issuer_create_credential_offer(issuer_wallet_handle, &cred_def_id).unwrap();
+
+            let (cred_req_json, cred_req_metadata_json) = anoncreds::prover_create_credential_req(prover_wallet_handle,
+                                                                                                 DID_MY1,
+                                                                                                 &cred_offer_json,
+                                                                                                 &cred_def_json,
+                                                                                                 COMMON_MASTER_SECRET).unwrap();
+
+            cred_requests.push(json!({
+                "cred_offer": serde_json::from_str::<serde_json::Value>(&cred_offer_json).unwrap(),
+                "cred_req": serde_json::from_str::<serde_json::Value>(&cred_req_json).unwrap(),
+                "cred_values": serde_json::from_str::<serde_json::Value>(cred_values_json).unwrap(),
+            }));
+            cred_req_metadatas.push(cred_req_metadata_json);
+        }
+
+        //5. Issuer issues all Credentials at once
+        let (creds_json, revoc_reg_delta_json) = anoncreds::issuer_create_credentials(issuer_wallet_handle,
+                                                                                      &serde_json::to_string(&cred_requests).unwrap(),
+                                                                                      Some(&rev_reg_id),
+                                                                                      Some(blob_storage_reader_handle)).unwrap();
+
+        let revoc_reg_delta: serde_json::Value = serde_json::from_str(&revoc_reg_delta_json.unwrap()).unwrap();
+        let issued: HashSet<u32> = serde_json::from_value(revoc_reg_delta["value"]["issued"].clone()).unwrap();
+        assert_eq!(vec![1, 2, 3].into_iter().collect::<HashSet<u32>>(), issued);
+
+        let creds: Vec<serde_json::Value> = serde_json::from_str(&creds_json).unwrap();
+        assert_eq!(3, creds.len());
+
+        //6. Prover stores Credentials
+        for (i, cred) in creds.iter().enumerate() {
+            assert_eq!(json!((i + 1).to_string()), cred["cred_revoc_id"]);
+
+            anoncreds::prover_store_credential(prover_wallet_handle,
+                                               &format!("credential_{}", i),
+                                               &cred_req_metadatas[i],
+                                               &cred["cred"].to_string(),
+                                               &cred_def_json,
+                                               Some(&revoc_reg_def_json)).unwrap();
+        }
+
+        //7. Issuer revokes the first and the last Credentials at once
+        let revoc_reg_delta_json = anoncreds::issuer_revoke_credentials(issuer_wallet_handle, blob_storage_reader_handle, &rev_reg_id, r#"["1", "3"]"#).unwrap();
+
+        let revoc_reg_delta: serde_json::Value = serde_json::from_str(&revoc_reg_delta_json).unwrap();
+        let revoked: HashSet<u32> = serde_json::from_value(revoc_reg_delta["value"]["revoked"].clone()).unwrap();
+        assert_eq!(vec![1, 3].into_iter().collect::<HashSet<u32>>(), revoked);
+
+        wallet::close_and_delete_wallet(issuer_wallet_handle, &issuer_wallet_config).unwrap();
+        wallet::close_and_delete_wallet(prover_wallet_handle, &prover_wallet_config).unwrap();
+    }
+
+    #[cfg(feature = "revocation_tests")]
+    #[test]
+    fn anoncreds_works_for_batch_revocation_with_not_issued_credential_id() {
+        Setup::empty();
+
+        //1. Issuer creates wallet, gets wallet handle
+        let (issuer_wallet_handle, issuer_wallet_config) = wallet::create_and_open_default_wallet("anoncreds_works_for_batch_revocation_with_not_issued_credential_id").unwrap();
+
+        //2. Prover creates wallet, gets wallet handle
+        let (prover_wallet_handle, prover_wallet_config) = wallet::create_and_open_default_wallet("anoncreds_works_for_batch_revocation_with_not_issued_credential_id").unwrap();
+
+        //3. Issuer creates Schema, Credential Definition and Revocation Registry
+        let (_, _,
+            cred_def_id, cred_def_json,
+            rev_reg_id, revoc_reg_def_json, _,
+            blob_storage_reader_handle) = anoncreds::multi_steps_issuer_revocation_preparation(issuer_wallet_handle,
+                                                                                               ISSUER_DID,
+                                                                                               GVT_SCHEMA_NAME,
+                                                                                               GVT_SCHEMA_ATTRIBUTES,
+                                                                                               r#"{"max_cred_num":5, "issuance_type":"ISSUANCE_ON_DEMAND"}"#);
+
+        //4. Issuer issues Credential for Prover
+        anoncreds::prover_create_master_secret(prover_wallet_handle, COMMON_MASTER_SECRET).unwrap();
+
+        let (cred_rev_id, _) = anoncreds::multi_steps_create_revocation_credential(COMMON_MASTER_SECRET,
+                                                                                  prover_wallet_handle,
+                                                                                  issuer_wallet_handle,
+                                                                                  CREDENTIAL1_ID,
+                                                                                  &anoncreds::gvt_credential_values_json(),
+                                                                                  &cred_def_id,
+                                                                                  &cred_def_json,
+                                                                                  &rev_reg_id,
+                                                                                  &revoc_reg_def_json,
+                                                                                  blob_storage_reader_handle);
+
+        //5. Issuer revokes issued and not issued Credentials at once
+        let cred_revoc_ids_json = json!([cred_rev_id, "10"]).to_string();
+        let res = anoncreds::issuer_revoke_credentials(issuer_wallet_handle, blob_storage_reader_handle, &rev_reg_id, &cred_revoc_ids_json);
+        assert_code!(ErrorCode::AnoncredsInvalidUserRevocId, res);
+
+        //6. Check the issued Credential is not revoked by the failed batch
+        anoncreds::issuer_revoke_credential(issuer_wallet_handle, blob_storage_reader_handle, &rev_reg_id, &cred_rev_id).unwrap();
+
+        wallet::close_and_delete_wallet(issuer_wallet_handle, &issuer_wallet_config).unwrap();
+        wallet::close_and_delete_wallet(prover_wallet_handle, &prover_wallet_config).unwrap();
+    }
+
+    #[cfg(feature = "revocation_tests")]
+    #[test]
+    fn anoncreds_works_for_revocation_registry_status() {
+        Setup::empty();
+
+        //1. Issuer creates wallet, gets wallet handle
+        let (issuer_wallet_handle, issuer_wallet_config) = wallet::create_and_open_default_wallet("anoncreds_works_for_revocation_registry_status").unwrap();
+
+        //2. Prover creates wallet, gets wallet handle
+        let (prover_wallet_handle, prover_wallet_config) = wallet::create_and_open_default_wallet("anoncreds_works_for_revocation_registry_status").unwrap();
+
+        //3. Issuer creates Schema, Credential Definition and Revocation Registry
+        let (_, _,
+            cred_def_id, cred_def_json,
+            rev_reg_id, revoc_reg_def_json, _,
+            blob_storage_reader_handle) = anoncreds::multi_steps_issuer_revocation_preparation(issuer_wallet_handle,
+                                                                                               ISSUER_DID,
+                                                                                               GVT_SCHEMA_NAME,
+                                                                                               GVT_SCHEMA_ATTRIBUTES,
+                                                                                               r#"{"max_cred_num":5, "issuance_type":"ISSUANCE_ON_DEMAND"}"#);
+
+        //4. Check status of the empty Revocation Registry
+        let status_json = anoncreds::issuer_get_revoc_reg_status(issuer_wallet_handle, &rev_reg_id).unwrap();
+        let status: RevocationRegistryStatus = serde_json::from_str(&status_json).unwrap();
+        assert_eq!(5, status.max_cred_num);
+        assert_eq!(Some(1), status.next_index);
+        assert_eq!(0, status.issued);
+        assert_eq!(0, status.revoked);
+
+        //5. Issuer issues two Credentials for Prover
+        anoncreds::prover_create_master_secret(prover_wallet_handle, COMMON_MASTER_SECRET).unwrap();
+
+        let (cred_rev_id, _) = anoncreds::multi_steps_create_revocation_credential(COMMON_MASTER_SECRET,
+                                                                                  prover_wallet_handle,
+                                                                                  issuer_wallet_handle,
+                                                                                  CREDENTIAL1_ID,
+                                                                                  &anoncreds::gvt_credential_values_json(),
+                                                                                  &cred_def_id,
+                                                                                  &cred_def_json,
+                                                                                  &rev_reg_id,
+                                                                                  &revoc_reg_def_json,
+                                                                                  blob_storage_reader_handle);
+
+        anoncreds::multi_steps_create_revocation_credential(COMMON_MASTER_SECRET,
+                                                            prover_wallet_handle,
+                                                            issuer_wallet_handle,
+                                                            CREDENTIAL2_ID,
+                                                            &anoncreds::gvt2_credential_values_json(),
+                                                            &cred_def_id,
+                                                            &cred_def_json,
+                                                            &rev_reg_id,
+                                                            &revoc_reg_def_json,
+                                                            blob_storage_reader_handle);
+
+        //6. Issuer revokes the first Credential
+        anoncreds::issuer_revoke_credential(issuer_wallet_handle, blob_storage_reader_handle, &rev_reg_id, &cred_rev_id).unwrap();
+
+        //7. Check status reflects issuance and revocation
+        let status_json = anoncreds::issuer_get_revoc_reg_status(issuer_wallet_handle, &rev_reg_id).unwrap();
+        let status: RevocationRegistryStatus = serde_json::from_str(&status_json).unwrap();
+        assert_eq!(rev_reg_id, status.rev_reg_id.0);
+        assert_eq!(5, status.max_cred_num);
+        assert_eq!(Some(3), status.next_index);
+        assert_eq!(2, status.issued);
+        assert_eq!(1, status.revoked);
+        assert_eq!(vec![cred_rev_id.parse::<u32>().unwrap()], status.revoked_ids);
+
+        wallet::close_and_delete_wallet(issuer_wallet_handle, &issuer_wallet_config).unwrap();
+        wallet::close_and_delete_wallet(prover_wallet_handle, &prover_wallet_config).unwrap();
+    }
+
     #[test]
     fn anoncreds_works_for_multiple_requested_predicates_from_one_credential() {
         Setup::empty();
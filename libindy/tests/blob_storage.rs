@@ -0,0 +1,568 @@
+#[macro_use]
+mod utils;
+
+inject_indy_dependencies!();
+
+extern crate indyrs as indy;
+extern crate indyrs as api;
+
+use crate::utils::{anoncreds, blob_storage, environment, wallet};
+use crate::utils::anoncreds::{COMMON_MASTER_SECRET, CREDENTIAL1_ID};
+use crate::utils::blob_storage::{InmemBlobStorage, INMEM_BLOB_STORAGE_TYPE};
+use crate::utils::constants::*;
+use crate::utils::Setup;
+use crate::utils::domain::anoncreds::revocation_registry_definition::RevocationRegistryDefinition;
+
+use self::indy::{ErrorCode, WalletHandle};
+
+use std::fs;
+use std::path::PathBuf;
+use std::sync::atomic::Ordering;
+
+fn issuer_revocation_preparation(wallet_handle: WalletHandle, tails_writer_handle: i32) -> (String, String, String, String) {
+    let (_, schema_json) = anoncreds::issuer_create_schema(ISSUER_DID,
+                                                           GVT_SCHEMA_NAME,
+                                                           SCHEMA_VERSION,
+                                                           GVT_SCHEMA_ATTRIBUTES).unwrap();
+
+    let (cred_def_id, cred_def_json) = anoncreds::issuer_create_credential_definition(wallet_handle,
+                                                                                      ISSUER_DID,
+                                                                                      &schema_json,
+                                                                                      TAG_1,
+                                                                                      None,
+                                                                                      Some(&anoncreds::revocation_cred_def_config())).unwrap();
+
+    let (rev_reg_id, revoc_reg_def_json, _) = anoncreds::issuer_create_and_store_revoc_reg(wallet_handle,
+                                                                                           ISSUER_DID,
+                                                                                           None,
+                                                                                           TAG_1,
+                                                                                           &cred_def_id,
+                                                                                           r#"{"max_cred_num":5, "issuance_type":"ISSUANCE_ON_DEMAND"}"#,
+                                                                                           tails_writer_handle).unwrap();
+
+    (cred_def_id, cred_def_json, rev_reg_id, revoc_reg_def_json)
+}
+
+fn tails_hash(revoc_reg_def_json: &str) -> String {
+    let revoc_reg_def: RevocationRegistryDefinition = serde_json::from_str(revoc_reg_def_json).unwrap();
+    match revoc_reg_def {
+        RevocationRegistryDefinition::RevocationRegistryDefinitionV1(revoc_reg_def) => revoc_reg_def.value.tails_hash
+    }
+}
+
+fn tails_base_dir() -> PathBuf {
+    let mut base_dir = environment::tmp_path();
+    base_dir.push("tails");
+    base_dir
+}
+
+fn http_reader_config(base_url: &str, name: &str) -> (String, PathBuf) {
+    let cache_dir = environment::tmp_file_path(&format!("tails_cache_{}", name));
+
+    let config = json!({
+        "base_url": base_url,
+        "cache_dir": cache_dir.to_str().unwrap(),
+    }).to_string();
+
+    (config, cache_dir)
+}
+
+fn with_tails_location(revoc_reg_def_json: &str, tails_location: &str) -> String {
+    let mut revoc_reg_def: serde_json::Value = serde_json::from_str(revoc_reg_def_json).unwrap();
+    revoc_reg_def["value"]["tailsLocation"] = json!(tails_location);
+    revoc_reg_def.to_string()
+}
+
+fn tails_reader_config(name: &str) -> (String, PathBuf) {
+    let tails_dir = environment::tmp_file_path(&format!("tails_store_{}", name));
+
+    let config = json!({
+        "tails_dir": tails_dir.to_str().unwrap(),
+    }).to_string();
+
+    (config, tails_dir)
+}
+
+fn issue_credential(prover_wallet_handle: WalletHandle,
+                    issuer_wallet_handle: WalletHandle,
+                    cred_def_id: &str,
+                    cred_def_json: &str,
+                    rev_reg_id: &str,
+                    blob_storage_reader_handle: i32) -> Result<(), indy::IndyError> {
+    let cred_offer_json = anoncreds::issuer_create_credential_offer(issuer_wallet_handle, cred_def_id).unwrap();
+
+    let (cred_req_json, _) = anoncreds::prover_create_credential_req(prover_wallet_handle,
+                                                                     DID_MY1,
+                                                                     &cred_offer_json,
+                                                                     cred_def_json,
+                                                                     COMMON_MASTER_SECRET).unwrap();
+
+    anoncreds::issuer_create_credential(issuer_wallet_handle,
+                                        &cred_offer_json,
+                                        &cred_req_json,
+                                        &anoncreds::gvt_credential_values_json(),
+                                        Some(rev_reg_id),
+                                        Some(blob_storage_reader_handle))?;
+
+    Ok(())
+}
+
+mod high_cases {
+    use super::*;
+
+    mod register_blob_storage {
+        use super::*;
+
+        #[test]
+        fn register_blob_storage_works_for_plugged_writer_and_reader() {
+            let setup = Setup::empty();
+
+            blob_storage::register_inmem_blob_storage().unwrap();
+
+            let (issuer_wallet_handle, issuer_wallet_config) = wallet::create_and_open_default_wallet(&format!("{}_issuer", setup.name)).unwrap();
+            let (prover_wallet_handle, prover_wallet_config) = wallet::create_and_open_default_wallet(&format!("{}_prover", setup.name)).unwrap();
+
+            let tails_writer_handle = blob_storage::open_writer(INMEM_BLOB_STORAGE_TYPE, "{}").unwrap();
+
+            let (cred_def_id, cred_def_json, rev_reg_id, revoc_reg_def_json) =
+                issuer_revocation_preparation(issuer_wallet_handle, tails_writer_handle);
+
+            let revoc_reg_def: serde_json::Value = serde_json::from_str(&revoc_reg_def_json).unwrap();
+            assert!(revoc_reg_def["value"]["tailsLocation"].as_str().unwrap().starts_with("inmem://"));
+            assert!(InmemBlobStorage::freed_blobs_count() >= 1);
+
+            let blob_storage_reader_handle = blob_storage::open_reader(INMEM_BLOB_STORAGE_TYPE, "{}").unwrap();
+
+            anoncreds::prover_create_master_secret(prover_wallet_handle, COMMON_MASTER_SECRET).unwrap();
+
+            let (cred_rev_id, _) = anoncreds::multi_steps_create_revocation_credential(COMMON_MASTER_SECRET,
+                                                                                      prover_wallet_handle,
+                                                                                      issuer_wallet_handle,
+                                                                                      CREDENTIAL1_ID,
+                                                                                      &anoncreds::gvt_credential_values_json(),
+                                                                                      &cred_def_id,
+                                                                                      &cred_def_json,
+                                                                                      &rev_reg_id,
+                                                                                      &revoc_reg_def_json,
+                                                                                      blob_storage_reader_handle);
+
+            anoncreds::issuer_revoke_credential(issuer_wallet_handle, blob_storage_reader_handle, &rev_reg_id, &cred_rev_id).unwrap();
+
+            wallet::close_and_delete_wallet(issuer_wallet_handle, &issuer_wallet_config).unwrap();
+            wallet::close_and_delete_wallet(prover_wallet_handle, &prover_wallet_config).unwrap();
+        }
+    }
+
+    mod http_reader {
+        use super::*;
+
+        #[test]
+        fn http_reader_works_for_download_and_cache() {
+            let setup = Setup::empty();
+
+            let (issuer_wallet_handle, issuer_wallet_config) = wallet::create_and_open_default_wallet(&format!("{}_issuer", setup.name)).unwrap();
+            let (prover_wallet_handle, prover_wallet_config) = wallet::create_and_open_default_wallet(&format!("{}_prover", setup.name)).unwrap();
+
+            let tails_writer_handle = blob_storage::open_writer("default", &anoncreds::tails_writer_config()).unwrap();
+
+            let (cred_def_id, cred_def_json, rev_reg_id, revoc_reg_def_json) =
+                issuer_revocation_preparation(issuer_wallet_handle, tails_writer_handle);
+
+            let (base_url, requests) = blob_storage::start_tails_server(tails_base_dir());
+            let (reader_config, cache_dir) = http_reader_config(&base_url, &setup.name);
+
+            let blob_storage_reader_handle = blob_storage::open_reader("http", &reader_config).unwrap();
+
+            anoncreds::prover_create_master_secret(prover_wallet_handle, COMMON_MASTER_SECRET).unwrap();
+
+            issue_credential(prover_wallet_handle, issuer_wallet_handle, &cred_def_id, &cred_def_json,
+                             &rev_reg_id, blob_storage_reader_handle).unwrap();
+
+            let mut cached_tails = cache_dir.clone();
+            cached_tails.push(tails_hash(&revoc_reg_def_json));
+            assert!(cached_tails.exists());
+            assert_eq!(1, requests.load(Ordering::SeqCst));
+
+            issue_credential(prover_wallet_handle, issuer_wallet_handle, &cred_def_id, &cred_def_json,
+                             &rev_reg_id, blob_storage_reader_handle).unwrap();
+
+            assert_eq!(1, requests.load(Ordering::SeqCst));
+
+            fs::remove_dir_all(cache_dir).unwrap();
+            wallet::close_and_delete_wallet(issuer_wallet_handle, &issuer_wallet_config).unwrap();
+            wallet::close_and_delete_wallet(prover_wallet_handle, &prover_wallet_config).unwrap();
+        }
+    }
+
+    mod open_tails_reader {
+        use super::*;
+
+        #[test]
+        fn open_tails_reader_works_for_local_location() {
+            let setup = Setup::empty();
+
+            let (issuer_wallet_handle, issuer_wallet_config) = wallet::create_and_open_default_wallet(&format!("{}_issuer", setup.name)).unwrap();
+            let (prover_wallet_handle, prover_wallet_config) = wallet::create_and_open_default_wallet(&format!("{}_prover", setup.name)).unwrap();
+
+            let tails_writer_handle = blob_storage::open_writer("default", &anoncreds::tails_writer_config()).unwrap();
+
+            let (cred_def_id, cred_def_json, rev_reg_id, revoc_reg_def_json) =
+                issuer_revocation_preparation(issuer_wallet_handle, tails_writer_handle);
+
+            let (config, tails_dir) = tails_reader_config(&setup.name);
+
+            let blob_storage_reader_handle = blob_storage::open_tails_reader(&revoc_reg_def_json, &config).unwrap();
+
+            let mut stored_tails = tails_dir.clone();
+            stored_tails.push(tails_hash(&revoc_reg_def_json));
+            assert!(stored_tails.exists());
+
+            anoncreds::prover_create_master_secret(prover_wallet_handle, COMMON_MASTER_SECRET).unwrap();
+
+            issue_credential(prover_wallet_handle, issuer_wallet_handle, &cred_def_id, &cred_def_json,
+                             &rev_reg_id, blob_storage_reader_handle).unwrap();
+
+            fs::remove_dir_all(tails_dir).unwrap();
+            wallet::close_and_delete_wallet(issuer_wallet_handle, &issuer_wallet_config).unwrap();
+            wallet::close_and_delete_wallet(prover_wallet_handle, &prover_wallet_config).unwrap();
+        }
+
+        #[test]
+        fn open_tails_reader_works_for_stored_tails() {
+            let setup = Setup::empty();
+
+            let (issuer_wallet_handle, issuer_wallet_config) = wallet::create_and_open_default_wallet(&format!("{}_issuer", setup.name)).unwrap();
+            let (prover_wallet_handle, prover_wallet_config) = wallet::create_and_open_default_wallet(&format!("{}_prover", setup.name)).unwrap();
+
+            let tails_writer_handle = blob_storage::open_writer("default", &anoncreds::tails_writer_config()).unwrap();
+
+            let (cred_def_id, cred_def_json, rev_reg_id, revoc_reg_def_json) =
+                issuer_revocation_preparation(issuer_wallet_handle, tails_writer_handle);
+
+            let (config, tails_dir) = tails_reader_config(&setup.name);
+
+            blob_storage::open_tails_reader(&revoc_reg_def_json, &config).unwrap();
+
+            // stored tails file is used, so location isn't accessed anymore
+            let revoc_reg_def_json = with_tails_location(&revoc_reg_def_json, "/not/existing/tails/file");
+            let blob_storage_reader_handle = blob_storage::open_tails_reader(&revoc_reg_def_json, &config).unwrap();
+
+            anoncreds::prover_create_master_secret(prover_wallet_handle, COMMON_MASTER_SECRET).unwrap();
+
+            issue_credential(prover_wallet_handle, issuer_wallet_handle, &cred_def_id, &cred_def_json,
+                             &rev_reg_id, blob_storage_reader_handle).unwrap();
+
+            fs::remove_dir_all(tails_dir).unwrap();
+            wallet::close_and_delete_wallet(issuer_wallet_handle, &issuer_wallet_config).unwrap();
+            wallet::close_and_delete_wallet(prover_wallet_handle, &prover_wallet_config).unwrap();
+        }
+
+        #[test]
+        fn open_tails_reader_works_for_http_location() {
+            let setup = Setup::empty();
+
+            let (issuer_wallet_handle, issuer_wallet_config) = wallet::create_and_open_default_wallet(&format!("{}_issuer", setup.name)).unwrap();
+            let (prover_wallet_handle, prover_wallet_config) = wallet::create_and_open_default_wallet(&format!("{}_prover", setup.name)).unwrap();
+
+            let tails_writer_handle = blob_storage::open_writer("default", &anoncreds::tails_writer_config()).unwrap();
+
+            let (cred_def_id, cred_def_json, rev_reg_id, revoc_reg_def_json) =
+                issuer_revocation_preparation(issuer_wallet_handle, tails_writer_handle);
+
+            let (base_url, requests) = blob_storage::start_tails_server(tails_base_dir());
+            let revoc_reg_def_json = with_tails_location(&revoc_reg_def_json,
+                                                         &format!("{}/{}", base_url, tails_hash(&revoc_reg_def_json)));
+
+            let (config, tails_dir) = tails_reader_config(&setup.name);
+
+            blob_storage::open_tails_reader(&revoc_reg_def_json, &config).unwrap();
+            let blob_storage_reader_handle = blob_storage::open_tails_reader(&revoc_reg_def_json, &config).unwrap();
+
+            assert_eq!(1, requests.load(Ordering::SeqCst));
+
+            anoncreds::prover_create_master_secret(prover_wallet_handle, COMMON_MASTER_SECRET).unwrap();
+
+            issue_credential(prover_wallet_handle, issuer_wallet_handle, &cred_def_id, &cred_def_json,
+                             &rev_reg_id, blob_storage_reader_handle).unwrap();
+
+            fs::remove_dir_all(tails_dir).unwrap();
+            wallet::close_and_delete_wallet(issuer_wallet_handle, &issuer_wallet_config).unwrap();
+            wallet::close_and_delete_wallet(prover_wallet_handle, &prover_wallet_config).unwrap();
+        }
+
+        #[test]
+        fn open_tails_reader_works_for_plugged_fetcher() {
+            let setup = Setup::empty();
+
+            blob_storage::register_inmem_blob_storage().unwrap();
+
+            let (issuer_wallet_handle, issuer_wallet_config) = wallet::create_and_open_default_wallet(&format!("{}_issuer", setup.name)).unwrap();
+            let (prover_wallet_handle, prover_wallet_config) = wallet::create_and_open_default_wallet(&format!("{}_prover", setup.name)).unwrap();
+
+            let tails_writer_handle = blob_storage::open_writer(INMEM_BLOB_STORAGE_TYPE, "{}").unwrap();
+
+            let (cred_def_id, cred_def_json, rev_reg_id, revoc_reg_def_json) =
+                issuer_revocation_preparation(issuer_wallet_handle, tails_writer_handle);
+
+            let tails_dir = environment::tmp_file_path(&format!("tails_store_{}", setup.name));
+            let config = json!({
+                "tails_dir": tails_dir.to_str().unwrap(),
+                "fetcher_type": INMEM_BLOB_STORAGE_TYPE,
+                "fetcher_config": {},
+            }).to_string();
+
+            let blob_storage_reader_handle = blob_storage::open_tails_reader(&revoc_reg_def_json, &config).unwrap();
+
+            let mut stored_tails = tails_dir.clone();
+            stored_tails.push(tails_hash(&revoc_reg_def_json));
+            assert!(stored_tails.exists());
+
+            anoncreds::prover_create_master_secret(prover_wallet_handle, COMMON_MASTER_SECRET).unwrap();
+
+            issue_credential(prover_wallet_handle, issuer_wallet_handle, &cred_def_id, &cred_def_json,
+                             &rev_reg_id, blob_storage_reader_handle).unwrap();
+
+            fs::remove_dir_all(tails_dir).unwrap();
+            wallet::close_and_delete_wallet(issuer_wallet_handle, &issuer_wallet_config).unwrap();
+            wallet::close_and_delete_wallet(prover_wallet_handle, &prover_wallet_config).unwrap();
+        }
+    }
+}
+
+#[cfg(not(feature = "only_high_cases"))]
+mod medium_cases {
+    use super::*;
+
+    mod register_blob_storage {
+        use super::*;
+
+        #[test]
+        fn register_blob_storage_works_for_twice() {
+            Setup::empty();
+
+            blob_storage::register_reader_for_type("register_blob_storage_works_for_twice").unwrap();
+
+            let res = blob_storage::register_reader_for_type("register_blob_storage_works_for_twice");
+            assert_code!(ErrorCode::CommonInvalidStructure, res);
+        }
+
+        #[test]
+        fn register_blob_storage_works_for_builtin_type() {
+            Setup::empty();
+
+            let res = blob_storage::register_reader_for_type("default");
+            assert_code!(ErrorCode::CommonInvalidStructure, res);
+        }
+
+        #[test]
+        fn open_reader_works_for_unknown_type() {
+            Setup::empty();
+
+            let res = blob_storage::open_reader("unknown_blob_storage_type", "{}");
+            assert_code!(ErrorCode::CommonInvalidStructure, res);
+        }
+    }
+
+    mod http_reader {
+        use super::*;
+
+        #[test]
+        fn http_reader_works_for_hash_mismatch() {
+            let setup = Setup::empty();
+
+            let (issuer_wallet_handle, issuer_wallet_config) = wallet::create_and_open_default_wallet(&format!("{}_issuer", setup.name)).unwrap();
+            let (prover_wallet_handle, prover_wallet_config) = wallet::create_and_open_default_wallet(&format!("{}_prover", setup.name)).unwrap();
+
+            let tails_writer_handle = blob_storage::open_writer("default", &anoncreds::tails_writer_config()).unwrap();
+
+            let (cred_def_id, cred_def_json, rev_reg_id, revoc_reg_def_json) =
+                issuer_revocation_preparation(issuer_wallet_handle, tails_writer_handle);
+
+            // server returns corrupted tails file
+            let server_dir = environment::tmp_file_path(&format!("tails_server_{}", setup.name));
+            fs::create_dir_all(&server_dir).unwrap();
+            let mut corrupted_tails = server_dir.clone();
+            corrupted_tails.push(tails_hash(&revoc_reg_def_json));
+            fs::write(&corrupted_tails, b"corrupted").unwrap();
+
+            let (base_url, _) = blob_storage::start_tails_server(server_dir.clone());
+            let (reader_config, cache_dir) = http_reader_config(&base_url, &setup.name);
+
+            let blob_storage_reader_handle = blob_storage::open_reader("http", &reader_config).unwrap();
+
+            anoncreds::prover_create_master_secret(prover_wallet_handle, COMMON_MASTER_SECRET).unwrap();
+
+            let res = issue_credential(prover_wallet_handle, issuer_wallet_handle, &cred_def_id, &cred_def_json,
+                                       &rev_reg_id, blob_storage_reader_handle);
+            assert_code!(ErrorCode::CommonInvalidStructure, res);
+
+            let mut cached_tails = cache_dir.clone();
+            cached_tails.push(tails_hash(&revoc_reg_def_json));
+            assert!(!cached_tails.exists());
+
+            fs::remove_dir_all(server_dir).unwrap();
+            wallet::close_and_delete_wallet(issuer_wallet_handle, &issuer_wallet_config).unwrap();
+            wallet::close_and_delete_wallet(prover_wallet_handle, &prover_wallet_config).unwrap();
+        }
+
+        #[test]
+        fn http_reader_works_for_not_found_blob() {
+            let setup = Setup::empty();
+
+            let (issuer_wallet_handle, issuer_wallet_config) = wallet::create_and_open_default_wallet(&format!("{}_issuer", setup.name)).unwrap();
+            let (prover_wallet_handle, prover_wallet_config) = wallet::create_and_open_default_wallet(&format!("{}_prover", setup.name)).unwrap();
+
+            let tails_writer_handle = blob_storage::open_writer("default", &anoncreds::tails_writer_config()).unwrap();
+
+            let (cred_def_id, cred_def_json, rev_reg_id, revoc_reg_def_json) =
+                issuer_revocation_preparation(issuer_wallet_handle, tails_writer_handle);
+
+            let server_dir = environment::tmp_file_path(&format!("tails_server_{}", setup.name));
+            fs::create_dir_all(&server_dir).unwrap();
+
+            let (base_url, _) = blob_storage::start_tails_server(server_dir.clone());
+            let (reader_config, _) = http_reader_config(&base_url, &setup.name);
+
+            let blob_storage_reader_handle = blob_storage::open_reader("http", &reader_config).unwrap();
+
+            anoncreds::prover_create_master_secret(prover_wallet_handle, COMMON_MASTER_SECRET).unwrap();
+
+            let res = issue_credential(prover_wallet_handle, issuer_wallet_handle, &cred_def_id, &cred_def_json,
+                                       &rev_reg_id, blob_storage_reader_handle);
+            assert_code!(ErrorCode::CommonIOError, res);
+
+            fs::remove_dir_all(server_dir).unwrap();
+            wallet::close_and_delete_wallet(issuer_wallet_handle, &issuer_wallet_config).unwrap();
+            wallet::close_and_delete_wallet(prover_wallet_handle, &prover_wallet_config).unwrap();
+        }
+    }
+
+    mod open_tails_reader {
+        use super::*;
+
+        #[test]
+        fn open_tails_reader_works_for_hash_mismatch() {
+            let setup = Setup::empty();
+
+            let (issuer_wallet_handle, issuer_wallet_config) = wallet::create_and_open_default_wallet(&format!("{}_issuer", setup.name)).unwrap();
+
+            let tails_writer_handle = blob_storage::open_writer("default", &anoncreds::tails_writer_config()).unwrap();
+
+            let (_, _, _, revoc_reg_def_json) = issuer_revocation_preparation(issuer_wallet_handle, tails_writer_handle);
+
+            // server returns corrupted tails file
+            let server_dir = environment::tmp_file_path(&format!("tails_server_{}", setup.name));
+            fs::create_dir_all(&server_dir).unwrap();
+            let mut corrupted_tails = server_dir.clone();
+            corrupted_tails.push(tails_hash(&revoc_reg_def_json));
+            fs::write(&corrupted_tails, b"corrupted").unwrap();
+
+            let (base_url, _) = blob_storage::start_tails_server(server_dir.clone());
+            let revoc_reg_def_json = with_tails_location(&revoc_reg_def_json,
+                                                         &format!("{}/{}", base_url, tails_hash(&revoc_reg_def_json)));
+
+            let (config, tails_dir) = tails_reader_config(&setup.name);
+
+            let res = blob_storage::open_tails_reader(&revoc_reg_def_json, &config);
+            assert_code!(ErrorCode::CommonInvalidStructure, res);
+
+            let mut stored_tails = tails_dir.clone();
+            stored_tails.push(tails_hash(&revoc_reg_def_json));
+            assert!(!stored_tails.exists());
+
+            fs::remove_dir_all(server_dir).unwrap();
+            wallet::close_and_delete_wallet(issuer_wallet_handle, &issuer_wallet_config).unwrap();
+        }
+
+        #[test]
+        fn open_tails_reader_works_for_truncated_http_response() {
+            let setup = Setup::empty();
+
+            let (issuer_wallet_handle, issuer_wallet_config) = wallet::create_and_open_default_wallet(&format!("{}_issuer", setup.name)).unwrap();
+
+            let tails_writer_handle = blob_storage::open_writer("default", &anoncreds::tails_writer_config()).unwrap();
+
+            let (_, _, _, revoc_reg_def_json) = issuer_revocation_preparation(issuer_wallet_handle, tails_writer_handle);
+
+            // connection is closed before Content-Length bytes are sent
+            let base_url = blob_storage::start_raw_http_server(b"HTTP/1.0 200 OK\r\nContent-Length: 1000\r\n\r\ntruncated".to_vec());
+            let revoc_reg_def_json = with_tails_location(&revoc_reg_def_json,
+                                                         &format!("{}/{}", base_url, tails_hash(&revoc_reg_def_json)));
+
+            let (config, tails_dir) = tails_reader_config(&setup.name);
+
+            let res = blob_storage::open_tails_reader(&revoc_reg_def_json, &config);
+            assert_code!(ErrorCode::CommonIOError, res);
+
+            let mut stored_tails = tails_dir.clone();
+            stored_tails.push(tails_hash(&revoc_reg_def_json));
+            assert!(!stored_tails.exists());
+
+            wallet::close_and_delete_wallet(issuer_wallet_handle, &issuer_wallet_config).unwrap();
+        }
+
+        #[test]
+        fn open_tails_reader_works_for_http_redirect() {
+            let setup = Setup::empty();
+
+            let (issuer_wallet_handle, issuer_wallet_config) = wallet::create_and_open_default_wallet(&format!("{}_issuer", setup.name)).unwrap();
+
+            let tails_writer_handle = blob_storage::open_writer("default", &anoncreds::tails_writer_config()).unwrap();
+
+            let (_, _, _, revoc_reg_def_json) = issuer_revocation_preparation(issuer_wallet_handle, tails_writer_handle);
+
+            // redirects aren't followed
+            let (base_url, _) = blob_storage::start_tails_server(tails_base_dir());
+            let redirect = format!("HTTP/1.0 302 Found\r\nLocation: {}/{}\r\nContent-Length: 0\r\n\r\n", base_url, tails_hash(&revoc_reg_def_json));
+            let redirect_url = blob_storage::start_raw_http_server(redirect.into_bytes());
+            let revoc_reg_def_json = with_tails_location(&revoc_reg_def_json,
+                                                         &format!("{}/{}", redirect_url, tails_hash(&revoc_reg_def_json)));
+
+            let (config, _) = tails_reader_config(&setup.name);
+
+            let res = blob_storage::open_tails_reader(&revoc_reg_def_json, &config);
+            assert_code!(ErrorCode::CommonIOError, res);
+
+            wallet::close_and_delete_wallet(issuer_wallet_handle, &issuer_wallet_config).unwrap();
+        }
+
+        #[test]
+        fn open_tails_reader_works_for_missed_local_file() {
+            let setup = Setup::empty();
+
+            let (issuer_wallet_handle, issuer_wallet_config) = wallet::create_and_open_default_wallet(&format!("{}_issuer", setup.name)).unwrap();
+
+            let tails_writer_handle = blob_storage::open_writer("default", &anoncreds::tails_writer_config()).unwrap();
+
+            let (_, _, _, revoc_reg_def_json) = issuer_revocation_preparation(issuer_wallet_handle, tails_writer_handle);
+            let revoc_reg_def_json = with_tails_location(&revoc_reg_def_json, "/not/existing/tails/file");
+
+            let (config, _) = tails_reader_config(&setup.name);
+
+            let res = blob_storage::open_tails_reader(&revoc_reg_def_json, &config);
+            assert_code!(ErrorCode::CommonIOError, res);
+
+            wallet::close_and_delete_wallet(issuer_wallet_handle, &issuer_wallet_config).unwrap();
+        }
+
+        #[test]
+        fn open_tails_reader_works_for_fetcher_config_without_type() {
+            let setup = Setup::empty();
+
+            let (issuer_wallet_handle, issuer_wallet_config) = wallet::create_and_open_default_wallet(&format!("{}_issuer", setup.name)).unwrap();
+
+            let tails_writer_handle = blob_storage::open_writer("default", &anoncreds::tails_writer_config()).unwrap();
+
+            let (_, _, _, revoc_reg_def_json) = issuer_revocation_preparation(issuer_wallet_handle, tails_writer_handle);
+
+            let config = json!({
+                "fetcher_config": {"base_url": "http://127.0.0.1:1"},
+            }).to_string();
+
+            let res = blob_storage::open_tails_reader(&revoc_reg_def_json, &config);
+            assert_code!(ErrorCode::CommonInvalidStructure, res);
+
+            wallet::close_and_delete_wallet(issuer_wallet_handle, &issuer_wallet_config).unwrap();
+        }
+    }
+}
@@ -1,6 +1,53 @@
+#[macro_use]
+mod utils;
+
+inject_indy_dependencies!();
+
 extern crate indyrs as indy;
+extern crate indyrs as api;
+
+use indy::ErrorCode;
+use crate::utils::anoncreds;
+use crate::utils::domain::anoncreds::proof_verification_report::{ProofVerificationReport, VerificationStatus};
 
 #[test]
 fn set_runtime_config_works() {
     indy::set_runtime_config(r#"{"crypto_thread_pool_size": 2}"#);
-}
\ No newline at end of file
+}
+
+#[test]
+fn set_runtime_config_works_for_check_canonical_encoding() {
+    // "Alex" is encoded with a custom encoding in the proof
+    indy::set_runtime_config(r#"{"check_canonical_encoding": true}"#);
+
+    let res = anoncreds::verifier_verify_proof(&anoncreds::proof_request_attr(),
+                                               &anoncreds::proof_json(),
+                                               &anoncreds::schemas_for_proof(),
+                                               &anoncreds::cred_defs_for_proof(),
+                                               "{}",
+                                               "{}");
+
+    let report_json = anoncreds::verifier_verify_proof_with_report(&anoncreds::proof_request_attr(),
+                                                                   &anoncreds::proof_json(),
+                                                                   &anoncreds::schemas_for_proof(),
+                                                                   &anoncreds::cred_defs_for_proof(),
+                                                                   "{}",
+                                                                   "{}").unwrap();
+
+    indy::set_runtime_config(r#"{"check_canonical_encoding": false}"#);
+
+    assert_code!(ErrorCode::AnoncredsProofRejected, res);
+
+    let report: ProofVerificationReport = serde_json::from_str(&report_json).unwrap();
+    assert!(!report.valid);
+    assert_eq!(VerificationStatus::ProofInvalid, report.status);
+    assert!(!report.requested_attributes["attr1_referent"].encoding.as_ref().unwrap().passed);
+
+    let valid = anoncreds::verifier_verify_proof(&anoncreds::proof_request_attr(),
+                                                 &anoncreds::proof_json(),
+                                                 &anoncreds::schemas_for_proof(),
+                                                 &anoncreds::cred_defs_for_proof(),
+                                                 "{}",
+                                                 "{}").unwrap();
+    assert!(valid);
+}
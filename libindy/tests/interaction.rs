@@ -24,6 +24,7 @@ use crate::utils::domain::anoncreds::credential::Credential;
 use crate::utils::domain::anoncreds::credential::CredentialInfo;
 use crate::utils::domain::anoncreds::revocation_registry_definition::RevocationRegistryDefinition;
 use crate::utils::domain::anoncreds::proof::Proof;
+use crate::utils::domain::anoncreds::proof_verification_report::ProofVerificationReport;
 use crate::utils::domain::anoncreds::revocation_state::RevocationState;
 use crate::utils::domain::anoncreds::revocation_registry::RevocationRegistry;
 
@@ -509,6 +510,19 @@ impl Verifier {
 
         valid
     }
+
+    pub fn verify_with_ledger(&self, pool: &Pool, wallet_handle: WalletHandle, proof_json: &str) -> bool
+    {
+        let report_json = anoncreds::verifier_verify_proof_with_ledger(pool.pool_handle,
+                                                                       wallet_handle,
+                                                                       DID_MY1,
+                                                                       &self.proof_request,
+                                                                       proof_json,
+                                                                       "{}").unwrap();
+
+        let report: ProofVerificationReport = serde_json::from_str(&report_json).unwrap();
+        report.valid
+    }
 }
 
 #[cfg(feature = "revocation_tests")]
@@ -599,6 +613,10 @@ fn anoncreds_revocation_interaction_test_one_prover(revocation_registry_config:
     let valid = verifier.verify(&pool, &proof_json);
     assert!(valid);
 
+    // Verifier resolves all the ledger data by itself
+    let valid = verifier.verify_with_ledger(&pool, issuer.issuer_wallet_handle, &proof_json);
+    assert!(valid);
+
     /////////////////////////////////////////////////////////////////////////////////////////
     // Issuer revokes cred_rev_id
     let _rev_reg_delta_json = issuer.revoke_credential(&pool, &cred_rev_id);
@@ -614,6 +632,9 @@ fn anoncreds_revocation_interaction_test_one_prover(revocation_registry_config:
     let valid = verifier.verify(&pool, &proof_json);
     assert!(!valid);
 
+    let valid = verifier.verify_with_ledger(&pool, issuer.issuer_wallet_handle, &proof_json);
+    assert!(!valid);
+
 
     issuer.close();
     prover.close();
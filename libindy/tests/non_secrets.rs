@@ -232,6 +232,44 @@ mod high_cases {
         }
     }
 
+    mod purge_expired_records {
+        use super::*;
+
+        #[test]
+        fn indy_purge_expired_records_works() {
+            let setup = Setup::wallet();
+
+            add_wallet_record(setup.wallet_handle, TYPE, ID, VALUE, Some(r#"{"~ttl": "0"}"#)).unwrap();
+            add_wallet_record(setup.wallet_handle, TYPE, ID_2, VALUE_2, Some(r#"{"~ttl": "3600"}"#)).unwrap();
+
+            let res = get_wallet_record(setup.wallet_handle, TYPE, ID, OPTIONS_EMPTY);
+            assert_code!(ErrorCode::WalletItemNotFound, res);
+
+            assert_eq!(1, purge_expired_records(setup.wallet_handle, TYPE).unwrap());
+            assert_eq!(0, purge_expired_records(setup.wallet_handle, TYPE).unwrap());
+
+            get_wallet_record(setup.wallet_handle, TYPE, ID_2, OPTIONS_EMPTY).unwrap();
+        }
+
+        #[test]
+        fn indy_purge_expired_records_works_for_search() {
+            let setup = Setup::wallet();
+
+            add_wallet_record(setup.wallet_handle, TYPE, ID, VALUE, Some(r#"{"~ttl": "0"}"#)).unwrap();
+            add_wallet_record(setup.wallet_handle, TYPE, ID_2, VALUE_2, None).unwrap();
+
+            let search_handle = open_wallet_search(setup.wallet_handle, TYPE, QUERY_EMPTY, OPTIONS_EMPTY).unwrap();
+            let search_records = fetch_wallet_search_next_records(setup.wallet_handle, search_handle, 5).unwrap();
+            let search_records: SearchRecords = serde_json::from_str(&search_records).unwrap();
+
+            let records = search_records.records.unwrap();
+            assert_eq!(1, records.len());
+            assert_eq!(ID_2, records[0].id);
+
+            close_wallet_search(search_handle).unwrap();
+        }
+    }
+
     mod get_record {
         use super::*;
 
@@ -1121,6 +1159,44 @@ mod medium_cases {
         }
     }
 
+    mod purge_expired_records {
+        use super::*;
+
+        #[test]
+        fn indy_purge_expired_records_works_for_invalid_type() {
+            let setup = Setup::wallet();
+
+            let res = purge_expired_records(setup.wallet_handle, FORBIDDEN_TYPE);
+            assert_code!(ErrorCode::WalletAccessFailed, res);
+        }
+
+        #[test]
+        fn indy_add_wallet_record_works_for_invalid_ttl() {
+            let setup = Setup::wallet();
+
+            let res = add_wallet_record(setup.wallet_handle, TYPE, ID, VALUE, Some(r#"{"~ttl": "forever"}"#));
+            assert_code!(ErrorCode::CommonInvalidStructure, res);
+        }
+
+        #[test]
+        fn indy_wallet_record_works_for_reserved_expiration_tag() {
+            let setup = Setup::wallet();
+
+            let tags = r#"{"~Indy::ExpiresAt": "99999999999999999999"}"#;
+
+            let res = add_wallet_record(setup.wallet_handle, TYPE, ID, VALUE, Some(tags));
+            assert_code!(ErrorCode::CommonInvalidStructure, res);
+
+            add_wallet_record(setup.wallet_handle, TYPE, ID, VALUE, None).unwrap();
+
+            let res = add_wallet_record_tags(setup.wallet_handle, TYPE, ID, tags);
+            assert_code!(ErrorCode::CommonInvalidStructure, res);
+
+            let res = update_wallet_record_tags(setup.wallet_handle, TYPE, ID, tags);
+            assert_code!(ErrorCode::CommonInvalidStructure, res);
+        }
+    }
+
     mod get_record {
         use super::*;
 
@@ -21,7 +21,7 @@ use crate::utils::domain::anoncreds::credential::{AttributeValues, CredentialInf
 use crate::utils::domain::anoncreds::credential_for_proof_request::CredentialsForProofRequest;
 use crate::utils::domain::crypto::did::DidValue;
 
-use indy::WalletHandle;
+use indy::{PoolHandle, WalletHandle};
 
 pub static mut CREDENTIAL_DEF_JSON: &'static str = "";
 pub static mut CREDENTIAL_OFFER_JSON: &'static str = "";
@@ -71,6 +71,18 @@ pub fn issuer_create_and_store_revoc_reg(wallet_handle: WalletHandle, issuer_did
     anoncreds::issuer_create_and_store_revoc_reg(wallet_handle, issuer_did, type_, tag, cred_def_id, config_json, tails_writer_handle).wait()
 }
 
+pub fn issuer_register_revoc_reg_created_hook(hook: Option<anoncreds::RevocRegCreatedHook>) -> Result<(), IndyError> {
+    anoncreds::issuer_register_revoc_reg_created_hook(hook).wait()
+}
+
+pub fn issuer_create_revoc_reg_pool(wallet_handle: WalletHandle, issuer_did: &str, cred_def_id: &str, config_json: &str) -> Result<String, IndyError> {
+    anoncreds::issuer_create_revoc_reg_pool(wallet_handle, issuer_did, cred_def_id, config_json).wait()
+}
+
+pub fn issuer_get_revoc_reg_pool(wallet_handle: WalletHandle, cred_def_id: &str) -> Result<String, IndyError> {
+    anoncreds::issuer_get_revoc_reg_pool(wallet_handle, cred_def_id).wait()
+}
+
 pub fn issuer_create_credential_offer(wallet_handle: WalletHandle, cred_def_id: &str) -> Result<String, IndyError> {
     anoncreds::issuer_create_credential_offer(wallet_handle, cred_def_id).wait()
 }
@@ -80,10 +92,27 @@ pub fn issuer_create_credential(wallet_handle: WalletHandle, cred_offer_json: &s
     anoncreds::issuer_create_credential(wallet_handle, cred_offer_json, cred_req_json, cred_values_json, rev_reg_id, blob_storage_reader_handle.unwrap_or(-1)).wait() // TODO OPTIONAL blob_storage_reader_handle
 }
 
+pub fn issuer_create_credentials(wallet_handle: WalletHandle, cred_requests_json: &str, rev_reg_id: Option<&str>,
+                                 blob_storage_reader_handle: Option<i32>) -> Result<(String, Option<String>), IndyError> {
+    anoncreds::issuer_create_credentials(wallet_handle, cred_requests_json, rev_reg_id, blob_storage_reader_handle.unwrap_or(-1)).wait()
+}
+
 pub fn issuer_revoke_credential(wallet_handle: WalletHandle, blob_storage_reader_handle: i32, rev_reg_id: &str, cred_revoc_id: &str) -> Result<String, IndyError> {
     anoncreds::issuer_revoke_credential(wallet_handle, blob_storage_reader_handle, rev_reg_id, cred_revoc_id).wait()
 }
 
+pub fn issuer_revoke_credentials(wallet_handle: WalletHandle, blob_storage_reader_handle: i32, rev_reg_id: &str, cred_revoc_ids_json: &str) -> Result<String, IndyError> {
+    anoncreds::issuer_revoke_credentials(wallet_handle, blob_storage_reader_handle, rev_reg_id, cred_revoc_ids_json).wait()
+}
+
+pub fn issuer_get_revoc_reg_status(wallet_handle: WalletHandle, rev_reg_id: &str) -> Result<String, IndyError> {
+    anoncreds::issuer_get_revoc_reg_status(wallet_handle, rev_reg_id).wait()
+}
+
+pub fn issuer_check_revoc_reg_drift(pool_handle: PoolHandle, wallet_handle: WalletHandle, submitter_did: Option<&str>, rev_reg_id: &str) -> Result<String, IndyError> {
+    anoncreds::issuer_check_revoc_reg_drift(pool_handle, wallet_handle, submitter_did, rev_reg_id).wait()
+}
+
 pub fn issuer_merge_revocation_registry_deltas(rev_reg_delta: &str, other_rev_reg_delta: &str) -> Result<String, IndyError> {
     anoncreds::issuer_merge_revocation_registry_deltas(rev_reg_delta, other_rev_reg_delta).wait()
 }
@@ -145,6 +174,10 @@ pub fn prover_search_credentials_for_proof_req(wallet_handle: WalletHandle, proo
     anoncreds::prover_search_credentials_for_proof_req(wallet_handle, proof_request_json, extra_query_json).wait()
 }
 
+pub fn prover_auto_select_credentials(wallet_handle: WalletHandle, proof_request_json: &str, policy_json: &str) -> Result<String, IndyError> {
+    anoncreds::prover_auto_select_credentials(wallet_handle, proof_request_json, policy_json).wait()
+}
+
 pub fn prover_fetch_next_credentials_for_proof_req(search_handle: i32, item_ref: &str, count: usize) -> Result<String, IndyError> {
     anoncreds::prover_fetch_credentials_for_proof_req(search_handle, item_ref, count).wait()
 }
@@ -165,6 +198,16 @@ pub fn verifier_verify_proof(proof_request_json: &str, proof_json: &str, schemas
     anoncreds::verifier_verify_proof(proof_request_json, proof_json, schemas_json, cred_defs_json, rev_reg_defs_json, rev_regs_json).wait()
 }
 
+pub fn verifier_verify_proof_with_report(proof_request_json: &str, proof_json: &str, schemas_json: &str,
+                                         cred_defs_json: &str, rev_reg_defs_json: &str, rev_regs_json: &str) -> Result<String, IndyError> {
+    anoncreds::verifier_verify_proof_with_report(proof_request_json, proof_json, schemas_json, cred_defs_json, rev_reg_defs_json, rev_regs_json).wait()
+}
+
+pub fn verifier_verify_proof_with_ledger(pool_handle: PoolHandle, wallet_handle: WalletHandle, submitter_did: &str,
+                                         proof_request_json: &str, proof_json: &str, options_json: &str) -> Result<String, IndyError> {
+    anoncreds::verifier_verify_proof_with_ledger(pool_handle, wallet_handle, submitter_did, proof_request_json, proof_json, options_json).wait()
+}
+
 pub fn create_revocation_state(blob_storage_reader_handle: i32, rev_reg_def_json: &str,
                                rev_reg_delta_json: &str, timestamp: u64, cred_rev_id: &str) -> Result<String, IndyError> {
     anoncreds::create_revocation_state(blob_storage_reader_handle, rev_reg_def_json, rev_reg_delta_json, timestamp, cred_rev_id).wait()
@@ -183,6 +226,18 @@ pub fn to_unqualified(entity: &str) -> Result<String, IndyError> {
     anoncreds::to_unqualified(entity).wait()
 }
 
+pub fn encode_credential_values(raw_values_json: &str) -> Result<String, IndyError> {
+    anoncreds::encode_credential_values(raw_values_json).wait()
+}
+
+pub fn validate(object_type: &str, object_json: &str, schema_json: Option<&str>, cred_def_json: Option<&str>) -> Result<String, IndyError> {
+    anoncreds::validate(object_type, object_json, schema_json, cred_def_json).wait()
+}
+
+pub fn encode_date(date: &str) -> Result<String, IndyError> {
+    anoncreds::encode_date(date).wait()
+}
+
 pub fn default_cred_def_config() -> String {
     serde_json::to_string(&CredentialDefinitionConfig { support_revocation: false }).unwrap()
 }
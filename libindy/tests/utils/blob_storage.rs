@@ -1,14 +1,289 @@
 extern crate futures;
 
-use indy::IndyError;
+use indy::{ErrorCode, IndyError};
 use indy::blob_storage;
 
 use self::futures::Future;
 
+use std::collections::{HashMap, HashSet};
+use std::ffi::{CStr, CString};
+use std::fs;
+use std::io::{Read, Write};
+use std::net::TcpListener;
+use std::path::PathBuf;
+use std::sync::Arc;
+use std::sync::Mutex;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::{ptr, slice, thread};
+
+use super::libc::c_char;
+use super::sequence;
+
+pub const INMEM_BLOB_STORAGE_TYPE: &'static str = "inmem";
+
 pub fn open_reader(type_: &str, config_json: &str) -> Result<i32, IndyError> {
     blob_storage::open_reader(type_, config_json).wait()
 }
 
 pub fn open_writer(type_: &str, config_json: &str) -> Result<i32, IndyError> {
     blob_storage::open_writer(type_, config_json).wait()
-}
\ No newline at end of file
+}
+
+pub fn open_tails_reader(rev_reg_def_json: &str, config_json: &str) -> Result<i32, IndyError> {
+    blob_storage::open_tails_reader(rev_reg_def_json, config_json).wait()
+}
+
+pub fn register_inmem_blob_storage() -> Result<(), IndyError> {
+    lazy_static! {
+        static ref REGISTERED: Mutex<bool> = Mutex::new(false);
+    }
+
+    let mut registered = REGISTERED.lock().unwrap();
+
+    if *registered {
+        return Ok(());
+    }
+
+    blob_storage::register_writer(INMEM_BLOB_STORAGE_TYPE,
+                                  Some(InmemBlobStorage::open_writer),
+                                  Some(InmemBlobStorage::create_blob),
+                                  Some(InmemBlobStorage::append),
+                                  Some(InmemBlobStorage::finalize),
+                                  Some(InmemBlobStorage::free_blob)).wait()?;
+
+    blob_storage::register_reader(INMEM_BLOB_STORAGE_TYPE,
+                                  Some(InmemBlobStorage::open_reader),
+                                  Some(InmemBlobStorage::open_blob),
+                                  Some(InmemBlobStorage::read),
+                                  Some(InmemBlobStorage::verify),
+                                  Some(InmemBlobStorage::close_blob)).wait()?;
+
+    *registered = true;
+
+    Ok(())
+}
+
+pub fn register_reader_for_type(type_: &str) -> Result<(), IndyError> {
+    blob_storage::register_reader(type_,
+                                  Some(InmemBlobStorage::open_reader),
+                                  Some(InmemBlobStorage::open_blob),
+                                  Some(InmemBlobStorage::read),
+                                  Some(InmemBlobStorage::verify),
+                                  Some(InmemBlobStorage::close_blob)).wait()
+}
+
+/// Blob storage plugin keeping blobs in process memory, registered through C handlers.
+pub struct InmemBlobStorage {}
+
+#[derive(Default)]
+struct InmemBlobStorageState {
+    // location -> (data, hash)
+    blobs: HashMap<String, (Vec<u8>, Vec<u8>)>,
+    // blob handle -> data being written
+    writing: HashMap<i32, Vec<u8>>,
+    // blob handle -> location returned by finalize
+    locations: HashMap<i32, CString>,
+    // blob handle -> (location, hash the blob was opened with)
+    reading: HashMap<i32, (String, Vec<u8>)>,
+    freed: HashSet<i32>,
+}
+
+lazy_static! {
+    static ref INMEM_BLOB_STORAGE: Mutex<InmemBlobStorageState> = Default::default();
+}
+
+impl InmemBlobStorage {
+    pub fn freed_blobs_count() -> usize {
+        INMEM_BLOB_STORAGE.lock().unwrap().freed.len()
+    }
+
+    pub extern fn open_writer(_config: *const c_char, writer_handle_p: *mut i32) -> i32 {
+        unsafe { *writer_handle_p = sequence::get_next_id(); }
+        ErrorCode::Success as i32
+    }
+
+    pub extern fn create_blob(_writer_handle: i32, blob_handle_p: *mut i32) -> i32 {
+        let blob_handle = sequence::get_next_id();
+        INMEM_BLOB_STORAGE.lock().unwrap().writing.insert(blob_handle, Vec::new());
+        unsafe { *blob_handle_p = blob_handle; }
+        ErrorCode::Success as i32
+    }
+
+    pub extern fn append(blob_handle: i32, data: *const u8, data_len: usize, written_len_p: *mut usize) -> i32 {
+        let mut storage = INMEM_BLOB_STORAGE.lock().unwrap();
+
+        match storage.writing.get_mut(&blob_handle) {
+            Some(blob) => {
+                blob.extend_from_slice(unsafe { slice::from_raw_parts(data, data_len) });
+                unsafe { *written_len_p = data_len; }
+                ErrorCode::Success as i32
+            }
+            None => ErrorCode::CommonInvalidState as i32
+        }
+    }
+
+    pub extern fn finalize(blob_handle: i32, hash: *const u8, hash_len: usize, location_p: *mut *const c_char) -> i32 {
+        let mut storage = INMEM_BLOB_STORAGE.lock().unwrap();
+
+        let data = match storage.writing.remove(&blob_handle) {
+            Some(data) => data,
+            None => return ErrorCode::CommonInvalidState as i32
+        };
+
+        let location = format!("inmem://{}", blob_handle);
+        let hash = unsafe { slice::from_raw_parts(hash, hash_len) }.to_vec();
+        storage.blobs.insert(location.clone(), (data, hash));
+
+        let location = CString::new(location).unwrap();
+        unsafe { *location_p = location.as_ptr(); }
+        storage.locations.insert(blob_handle, location);
+
+        ErrorCode::Success as i32
+    }
+
+    pub extern fn free_blob(blob_handle: i32) -> i32 {
+        let mut storage = INMEM_BLOB_STORAGE.lock().unwrap();
+        storage.writing.remove(&blob_handle);
+        storage.locations.remove(&blob_handle);
+        storage.freed.insert(blob_handle);
+        ErrorCode::Success as i32
+    }
+
+    pub extern fn open_reader(_config: *const c_char, reader_handle_p: *mut i32) -> i32 {
+        unsafe { *reader_handle_p = sequence::get_next_id(); }
+        ErrorCode::Success as i32
+    }
+
+    pub extern fn open_blob(_reader_handle: i32, hash: *const u8, hash_len: usize, location: *const c_char, blob_handle_p: *mut i32) -> i32 {
+        let mut storage = INMEM_BLOB_STORAGE.lock().unwrap();
+
+        let location = unsafe { CStr::from_ptr(location) }.to_str().unwrap().to_string();
+
+        if !storage.blobs.contains_key(&location) {
+            return ErrorCode::CommonIOError as i32;
+        }
+
+        let blob_handle = sequence::get_next_id();
+        let hash = unsafe { slice::from_raw_parts(hash, hash_len) }.to_vec();
+        storage.reading.insert(blob_handle, (location, hash));
+        unsafe { *blob_handle_p = blob_handle; }
+
+        ErrorCode::Success as i32
+    }
+
+    pub extern fn read(blob_handle: i32, offset: usize, buf: *mut u8, buf_len: usize, read_len_p: *mut usize) -> i32 {
+        let storage = INMEM_BLOB_STORAGE.lock().unwrap();
+
+        let data = match storage.reading.get(&blob_handle).and_then(|(location, _)| storage.blobs.get(location)) {
+            Some((data, _)) => data,
+            None => return ErrorCode::CommonInvalidState as i32
+        };
+
+        let start = ::std::cmp::min(offset, data.len());
+        let end = ::std::cmp::min(offset + buf_len, data.len());
+
+        unsafe {
+            ptr::copy_nonoverlapping(data[start..end].as_ptr(), buf, end - start);
+            *read_len_p = end - start;
+        }
+
+        ErrorCode::Success as i32
+    }
+
+    pub extern fn verify(blob_handle: i32, valid_p: *mut bool) -> i32 {
+        let storage = INMEM_BLOB_STORAGE.lock().unwrap();
+
+        let valid = match storage.reading.get(&blob_handle) {
+            Some((location, hash)) => storage.blobs.get(location).map(|(_, stored_hash)| stored_hash == hash).unwrap_or(false),
+            None => return ErrorCode::CommonInvalidState as i32
+        };
+
+        unsafe { *valid_p = valid; }
+        ErrorCode::Success as i32
+    }
+
+    pub extern fn close_blob(blob_handle: i32) -> i32 {
+        INMEM_BLOB_STORAGE.lock().unwrap().reading.remove(&blob_handle);
+        ErrorCode::Success as i32
+    }
+}
+
+/// Stand-in of the tails server: serves files of `base_dir` by name over HTTP.
+/// Returns server base url and the counter of handled requests.
+pub fn start_tails_server(base_dir: PathBuf) -> (String, Arc<AtomicUsize>) {
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let base_url = format!("http://{}", listener.local_addr().unwrap());
+
+    let requests = Arc::new(AtomicUsize::new(0));
+    let requests_counter = requests.clone();
+
+    thread::spawn(move || {
+        for stream in listener.incoming() {
+            let mut stream = match stream {
+                Ok(stream) => stream,
+                Err(_) => continue
+            };
+
+            let mut request = Vec::new();
+            let mut buf = [0u8; 1024];
+
+            while !request.windows(4).any(|window| window == b"\r\n\r\n") {
+                match stream.read(&mut buf) {
+                    Ok(0) | Err(_) => break,
+                    Ok(size) => request.extend_from_slice(&buf[..size])
+                }
+            }
+
+            requests_counter.fetch_add(1, Ordering::SeqCst);
+
+            let request = String::from_utf8_lossy(&request).to_string();
+            let name = request.split_whitespace().nth(1).unwrap_or("/").trim_start_matches('/').to_string();
+
+            let mut path = base_dir.clone();
+            path.push(&name);
+
+            let response = match fs::read(&path) {
+                Ok(data) => {
+                    let mut response = format!("HTTP/1.0 200 OK\r\nContent-Length: {}\r\n\r\n", data.len()).into_bytes();
+                    response.extend_from_slice(&data);
+                    response
+                }
+                Err(_) => b"HTTP/1.0 404 Not Found\r\nContent-Length: 0\r\n\r\n".to_vec()
+            };
+
+            stream.write_all(&response).ok();
+        }
+    });
+
+    (base_url, requests)
+}
+
+/// Stand-in of the misbehaving tails server: answers any request with the same raw `response`.
+/// Returns server base url.
+pub fn start_raw_http_server(response: Vec<u8>) -> String {
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let base_url = format!("http://{}", listener.local_addr().unwrap());
+
+    thread::spawn(move || {
+        for stream in listener.incoming() {
+            let mut stream = match stream {
+                Ok(stream) => stream,
+                Err(_) => continue
+            };
+
+            let mut request = Vec::new();
+            let mut buf = [0u8; 1024];
+
+            while !request.windows(4).any(|window| window == b"\r\n\r\n") {
+                match stream.read(&mut buf) {
+                    Ok(0) | Err(_) => break,
+                    Ok(size) => request.extend_from_slice(&buf[..size])
+                }
+            }
+
+            stream.write_all(&response).ok();
+        }
+    });
+
+    base_url
+}
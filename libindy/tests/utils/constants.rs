@@ -1,7 +1,7 @@
 pub const SEQ_NO: i32 = 1;
 pub const PROTOCOL_VERSION: usize = 2;
 pub const TYPE: &'static str = "default";
-pub const INMEM_TYPE: &'static str = "inmem";
+pub const INMEM_TYPE: &'static str = "plugged_inmem";
 pub const SIGNATURE_TYPE: &'static str = "CL";
 pub const TRUSTEE_SEED: &'static str = "000000000000000000000000Trustee1";
 pub const STEWARD_SEED: &'static str = "000000000000000000000000Steward1";
@@ -57,7 +57,7 @@ pub const WALLET_CREDENTIALS_ARGON2I_MOD: &'static str = r#"{"key":"key", "key_d
 pub const WALLET_CREDENTIALS_ARGON2I_INT: &'static str = r#"{"key":"key", "key_derivation_method":"ARGON2I_INT"}"#;
 pub const WALLET_CREDENTIALS_RAW: &'static str = r#"{"key":"8dvfYSt5d1taSd6yJdpjq4emkwsPDDLYxkNFysFD2cZY", "key_derivation_method":"RAW"}"#;
 pub const DEFAULT_WALLET_CONFIG: &'static str = r#"{"id":"default_wallet_1","storage_type":"default"}"#;  // FIXME never use global names
-pub const INMEM_WALLET_CONFIG: &'static str = r#"{"id":"inmem_wallet_1","storage_type":"inmem"}"#;  // FIXME never use global names
+pub const INMEM_WALLET_CONFIG: &'static str = r#"{"id":"inmem_wallet_1","storage_type":"plugged_inmem"}"#;  // FIXME never use global names
 pub const UNKNOWN_WALLET_CONFIG: &'static str = r#"{"id":"unknown_wallet_1","storage_type":"unknown"}"#;  // FIXME never use global names
 pub const AGENT_MESSAGE: &'static str = r#"{ "@id": "123456780","@type":"did:sov:BzCbsNYhMrjHiqZDTUASHg;spec/basicmessage/1.0/message","sent_time": "2019-01-15 18:42:01Z","content": "Your hovercraft is full of eels."}"#;
 pub const DEFAULT_METHOD_NAME: &'static str = "sov";
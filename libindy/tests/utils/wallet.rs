@@ -10,9 +10,9 @@ use self::futures::Future;
 use crate::utils::{callback, sequence, environment};
 use crate::utils::inmem_wallet::InmemWallet;
 
-use std::collections::HashSet;
+use std::collections::{HashMap, HashSet};
 use std::sync::Mutex;
-use std::ffi::CString;
+use std::ffi::{CStr, CString};
 use super::libc::c_char;
 
 use crate::utils::constants::{TYPE, INMEM_TYPE, WALLET_CREDENTIALS};
@@ -73,6 +73,24 @@ pub fn register_wallet_storage(xtype: &str, force_create: bool) -> Result<(), Er
     super::results::result_to_empty(err as i32, receiver)
 }
 
+pub fn register_wallet_storage_stats(xtype: &str) -> Result<(), ErrorCode> {
+    let (receiver, command_handle, cb) = callback::_closure_to_cb_ec();
+
+    let xxtype = CString::new(xtype).unwrap();
+
+    let err = unsafe {
+        indy_register_wallet_storage_stats(
+            command_handle,
+            xxtype.as_ptr(),
+            Some(InmemWallet::get_storage_stats),
+            Some(InmemWallet::free_storage_stats),
+            cb
+        )
+    };
+
+    super::results::result_to_empty(err as i32, receiver)
+}
+
 pub fn create_wallet(config: &str, credentials: &str) -> Result<(), IndyError> {
     wallet::create_wallet(config, credentials).wait()
 }
@@ -98,7 +116,7 @@ pub fn create_and_open_plugged_wallet() -> Result<(WalletHandle, String), IndyEr
             "storage_type": INMEM_TYPE
         }).to_string();
 
-    register_wallet_storage("inmem", false).unwrap();
+    register_wallet_storage(INMEM_TYPE, false).unwrap();
     create_wallet(&config, WALLET_CREDENTIALS)?;
     let wallet_handle = open_wallet(&config, WALLET_CREDENTIALS).unwrap();
     Ok((wallet_handle, config))
@@ -108,6 +126,10 @@ pub fn delete_wallet(config: &str, credentials: &str) -> Result<(), IndyError> {
     wallet::delete_wallet(config, credentials).wait()
 }
 
+pub fn check_wallet(config: &str, credentials: &str, options_json: Option<&str>) -> Result<String, IndyError> {
+    wallet::check_wallet(config, credentials, options_json).wait()
+}
+
 pub fn close_wallet(wallet_handle: WalletHandle) -> Result<(), IndyError> {
     wallet::close_wallet(wallet_handle).wait()
 }
@@ -125,6 +147,22 @@ pub fn import_wallet(config: &str, credentials: &str, import_config: &str) -> Re
     wallet::import_wallet(config, credentials, import_config).wait()
 }
 
+pub fn import_wallet_increment(wallet_handle: WalletHandle, import_config: &str) -> Result<(), IndyError> {
+    wallet::import_wallet_increment(wallet_handle, import_config).wait()
+}
+
+pub fn export_wallet_to_buffer(wallet_handle: WalletHandle, export_config: &str) -> Result<Vec<u8>, IndyError> {
+    wallet::export_wallet_to_buffer(wallet_handle, export_config).wait()
+}
+
+pub fn import_wallet_from_buffer(config: &str, credentials: &str, import_config: &str, data: &[u8]) -> Result<(), IndyError> {
+    wallet::import_wallet_from_buffer(config, credentials, import_config, data).wait()
+}
+
+pub fn import_wallet_increment_from_buffer(wallet_handle: WalletHandle, import_config: &str, data: &[u8]) -> Result<(), IndyError> {
+    wallet::import_wallet_increment_from_buffer(wallet_handle, import_config, data).wait()
+}
+
 pub fn export_wallet_path(name: &str) -> PathBuf {
     environment::tmp_file_path(name)
 }
@@ -141,6 +179,31 @@ pub fn generate_wallet_key(config: Option<&str>) -> Result<String, IndyError> {
     wallet::generate_wallet_key(config).wait()
 }
 
+pub fn get_wallet_audit_log(wallet_handle: WalletHandle, query_json: Option<&str>) -> Result<String, IndyError> {
+    wallet::get_wallet_audit_log(wallet_handle, query_json).wait()
+}
+
+pub fn get_wallet_stats(wallet_handle: WalletHandle) -> Result<String, IndyError> {
+    wallet::get_wallet_stats(wallet_handle).wait()
+}
+
+pub fn set_wallet_change_callback(wallet_handle: WalletHandle, change_cb: Option<extern fn(WalletHandle, *const c_char)>) -> Result<(), IndyError> {
+    wallet::set_wallet_change_callback(wallet_handle, change_cb).wait()
+}
+
+lazy_static! {
+    static ref WALLET_CHANGES: Mutex<HashMap<i32, Vec<String>>> = Default::default();
+}
+
+pub extern fn collect_wallet_change(wallet_handle: WalletHandle, change_json: *const c_char) {
+    let change_json = unsafe { CStr::from_ptr(change_json).to_str().unwrap().to_string() };
+    WALLET_CHANGES.lock().unwrap().entry(wallet_handle.0).or_insert_with(Vec::new).push(change_json);
+}
+
+pub fn collected_wallet_changes(wallet_handle: WalletHandle) -> Vec<String> {
+    WALLET_CHANGES.lock().unwrap().remove(&wallet_handle.0).unwrap_or_default()
+}
+
 extern {
     #[no_mangle]
     pub fn indy_register_wallet_storage(command_handle: CommandHandle,
@@ -170,6 +233,13 @@ extern {
                                         fetch_search_next_record: Option<WalletFetchSearchNextRecord>,
                                         free_search: Option<WalletFreeSearch>,
                                         cb: Option<ResponseEmptyCB>) -> ErrorCode;
+
+    #[no_mangle]
+    pub fn indy_register_wallet_storage_stats(command_handle: CommandHandle,
+                                              type_: *const c_char,
+                                              get_storage_stats: Option<WalletGetStorageStats>,
+                                              free_storage_stats: Option<WalletFreeStorageStats>,
+                                              cb: Option<ResponseEmptyCB>) -> ErrorCode;
 }
 
 pub type WalletCreate = extern fn(name: *const c_char,
@@ -252,5 +322,10 @@ pub type WalletFetchSearchNextRecord = extern fn(storage_handle: i32,
                                                  record_handle_p: *mut i32) -> ErrorCode;
 pub type WalletFreeSearch = extern fn(storage_handle: i32,
                                       search_handle: i32) -> ErrorCode;
+pub type WalletGetStorageStats = extern fn(storage_handle: i32,
+                                           stats_json_p: *mut *const c_char,
+                                           stats_handle_p: *mut i32) -> ErrorCode;
+pub type WalletFreeStorageStats = extern fn(storage_handle: i32,
+                                            stats_handle: i32) -> ErrorCode;
 
 pub type ResponseEmptyCB = extern fn(xcommand_handle: i32, err: i32);
@@ -7,11 +7,11 @@ extern crate indyrs as indy;
 extern crate indyrs as api;
 
 use crate::utils::inmem_wallet::InmemWallet;
-use crate::utils::{environment, wallet, test, did};
+use crate::utils::{environment, wallet, test, did, non_secrets};
 use crate::utils::constants::*;
 use crate::utils::Setup;
 
-use self::indy::ErrorCode;
+use self::indy::{ErrorCode, WalletHandle};
 use std::path::PathBuf;
 use std::fs;
 
@@ -25,6 +25,19 @@ fn config(name: &str) -> String {
     json!({"id": name}).to_string()
 }
 
+fn kek_credentials(name: &str) -> (String, PathBuf) {
+    let path = environment::tmp_file_path(&format!("{}.kek", name));
+    fs::create_dir_all(path.parent().unwrap()).unwrap();
+    fs::write(&path, wallet::generate_wallet_key(None).unwrap()).unwrap();
+
+    let credentials = json!({
+        "key": path.to_str().unwrap(),
+        "key_derivation_method": {"KEK": "file"}
+    }).to_string();
+
+    (credentials, path)
+}
+
 mod high_cases {
     use super::*;
 
@@ -186,6 +199,20 @@ mod high_cases {
 
             InmemWallet::cleanup();
         }
+
+        #[test]
+        fn indy_open_wallet_works_for_kek_file_provider() {
+            let setup = Setup::empty();
+            let config = config(&setup.name);
+            let (credentials, path) = kek_credentials(&setup.name);
+
+            wallet::create_wallet(&config, &credentials).unwrap();
+            let wallet_handle = wallet::open_wallet(&config, &credentials).unwrap();
+
+            wallet::close_wallet(wallet_handle).unwrap();
+            wallet::delete_wallet(&config, &credentials).unwrap();
+            cleanup_file(&path);
+        }
     }
 
     mod close_wallet {
@@ -281,6 +308,421 @@ mod high_cases {
             wallet::close_and_delete_wallet(wallet_handle, &config).unwrap();
             cleanup_file(&path);
         }
+
+        #[test]
+        fn indy_import_wallet_works_for_filtered_export() {
+            let setup = Setup::empty();
+            let config = config(&setup.name);
+
+            let path = wallet::export_wallet_path(&setup.name);
+            let config_json = json!({
+                "path": path.to_str().unwrap(),
+                "key": "export_key",
+                "filter": [{"type": non_secrets::TYPE, "query": {"tagName1": "str1"}}]
+            }).to_string();
+
+            let (wallet_handle, wallet_config) = wallet::create_and_open_default_wallet(&setup.name).unwrap();
+
+            non_secrets::add_wallet_record(wallet_handle, non_secrets::TYPE, non_secrets::ID, non_secrets::VALUE, Some(non_secrets::TAGS)).unwrap();
+            non_secrets::add_wallet_record(wallet_handle, non_secrets::TYPE, non_secrets::ID_2, non_secrets::VALUE_2, Some(non_secrets::TAGS_2)).unwrap();
+            non_secrets::add_wallet_record(wallet_handle, non_secrets::TYPE_2, non_secrets::ID_3, non_secrets::VALUE_3, Some(non_secrets::TAGS_3)).unwrap();
+
+            cleanup_file(&path);
+            wallet::export_wallet(wallet_handle, &config_json).unwrap();
+
+            wallet::close_wallet(wallet_handle).unwrap();
+            wallet::delete_wallet(&wallet_config, WALLET_CREDENTIALS).unwrap();
+
+            wallet::import_wallet(&config, WALLET_CREDENTIALS, &config_json).unwrap();
+
+            let wallet_handle = wallet::open_wallet(&config, WALLET_CREDENTIALS).unwrap();
+
+            non_secrets::get_wallet_record(wallet_handle, non_secrets::TYPE, non_secrets::ID, non_secrets::OPTIONS_EMPTY).unwrap();
+
+            let res = non_secrets::get_wallet_record(wallet_handle, non_secrets::TYPE, non_secrets::ID_2, non_secrets::OPTIONS_EMPTY);
+            assert_code!(ErrorCode::WalletItemNotFound, res);
+
+            let res = non_secrets::get_wallet_record(wallet_handle, non_secrets::TYPE_2, non_secrets::ID_3, non_secrets::OPTIONS_EMPTY);
+            assert_code!(ErrorCode::WalletItemNotFound, res);
+
+            wallet::close_and_delete_wallet(wallet_handle, &config).unwrap();
+            cleanup_file(&path);
+        }
+    }
+
+    mod import_wallet_increment {
+        use super::*;
+
+        #[test]
+        fn indy_import_wallet_increment_works() {
+            let setup = Setup::empty();
+            let config = config(&setup.name);
+
+            let path = wallet::export_wallet_path(&setup.name);
+            let config_json = json!({
+                "path": path.to_str().unwrap(),
+                "key": "export_key",
+                "incremental": true,
+            }).to_string();
+
+            let (wallet_handle, wallet_config) = wallet::create_and_open_default_wallet(&setup.name).unwrap();
+
+            let (did, _) = did::create_my_did(wallet_handle, "{}").unwrap();
+
+            cleanup_file(&path);
+            wallet::export_wallet(wallet_handle, &config_json).unwrap();
+
+            wallet::import_wallet(&config, WALLET_CREDENTIALS, &config_json).unwrap();
+            let imported_wallet_handle = wallet::open_wallet(&config, WALLET_CREDENTIALS).unwrap();
+            did::get_my_did_with_metadata(imported_wallet_handle, &did).unwrap();
+
+            did::set_did_metadata(wallet_handle, &did, METADATA).unwrap();
+
+            cleanup_file(&path);
+            wallet::export_wallet(wallet_handle, &config_json).unwrap();
+
+            wallet::import_wallet_increment(imported_wallet_handle, &config_json).unwrap();
+            assert_eq!(did::get_my_did_with_metadata(wallet_handle, &did).unwrap(),
+                       did::get_my_did_with_metadata(imported_wallet_handle, &did).unwrap());
+
+            let res = wallet::import_wallet_increment(imported_wallet_handle, &config_json);
+            assert_code!(ErrorCode::CommonInvalidStructure, res);
+
+            wallet::close_and_delete_wallet(wallet_handle, &wallet_config).unwrap();
+            wallet::close_and_delete_wallet(imported_wallet_handle, &config).unwrap();
+            cleanup_file(&path);
+        }
+    }
+
+    mod export_import_wallet_buffer {
+        use super::*;
+
+        #[test]
+        fn indy_export_import_wallet_buffer_works() {
+            let setup = Setup::empty();
+            let config = config(&setup.name);
+
+            let (wallet_handle, wallet_config) = wallet::create_and_open_default_wallet(&setup.name).unwrap();
+
+            let (did, _) = did::create_my_did(wallet_handle, "{}").unwrap();
+            did::set_did_metadata(wallet_handle, &did, METADATA).unwrap();
+
+            let export_config = json!({"key": "export_key"}).to_string();
+            let data = wallet::export_wallet_to_buffer(wallet_handle, &export_config).unwrap();
+            assert!(!data.is_empty());
+
+            wallet::import_wallet_from_buffer(&config, WALLET_CREDENTIALS, &export_config, &data).unwrap();
+
+            let imported_wallet_handle = wallet::open_wallet(&config, WALLET_CREDENTIALS).unwrap();
+            assert_eq!(did::get_my_did_with_metadata(wallet_handle, &did).unwrap(),
+                       did::get_my_did_with_metadata(imported_wallet_handle, &did).unwrap());
+
+            wallet::close_and_delete_wallet(wallet_handle, &wallet_config).unwrap();
+            wallet::close_and_delete_wallet(imported_wallet_handle, &config).unwrap();
+        }
+
+        #[test]
+        fn indy_import_wallet_from_buffer_works_for_wrong_key() {
+            let setup = Setup::empty();
+            let config = config(&setup.name);
+
+            let (wallet_handle, wallet_config) = wallet::create_and_open_default_wallet(&setup.name).unwrap();
+
+            let data = wallet::export_wallet_to_buffer(wallet_handle, &json!({"key": "export_key"}).to_string()).unwrap();
+
+            let res = wallet::import_wallet_from_buffer(&config, WALLET_CREDENTIALS, &json!({"key": "other_key"}).to_string(), &data);
+            assert_code!(ErrorCode::CommonInvalidStructure, res);
+
+            wallet::close_and_delete_wallet(wallet_handle, &wallet_config).unwrap();
+        }
+
+        #[test]
+        fn indy_import_wallet_increment_from_buffer_works() {
+            let setup = Setup::empty();
+            let config = config(&setup.name);
+
+            let (wallet_handle, wallet_config) = wallet::create_and_open_default_wallet(&setup.name).unwrap();
+
+            let (did, _) = did::create_my_did(wallet_handle, "{}").unwrap();
+
+            let export_config = json!({"key": "export_key", "incremental": true}).to_string();
+            let data = wallet::export_wallet_to_buffer(wallet_handle, &export_config).unwrap();
+
+            wallet::import_wallet_from_buffer(&config, WALLET_CREDENTIALS, &export_config, &data).unwrap();
+            let imported_wallet_handle = wallet::open_wallet(&config, WALLET_CREDENTIALS).unwrap();
+
+            did::set_did_metadata(wallet_handle, &did, METADATA).unwrap();
+
+            let data = wallet::export_wallet_to_buffer(wallet_handle, &export_config).unwrap();
+            wallet::import_wallet_increment_from_buffer(imported_wallet_handle, &export_config, &data).unwrap();
+
+            assert_eq!(METADATA, did::get_did_metadata(imported_wallet_handle, &did).unwrap());
+
+            // The same increment can't be applied twice
+            let res = wallet::import_wallet_increment_from_buffer(imported_wallet_handle, &export_config, &data);
+            assert_code!(ErrorCode::CommonInvalidStructure, res);
+
+            wallet::close_and_delete_wallet(wallet_handle, &wallet_config).unwrap();
+            wallet::close_and_delete_wallet(imported_wallet_handle, &config).unwrap();
+        }
+    }
+
+    mod shared_wallet {
+        use super::*;
+        use crate::utils::non_secrets;
+        use std::env;
+        use std::process::{Child, Command};
+
+        const SHARED_WALLET_CONFIG_ENV: &str = "INDY_TEST_SHARED_WALLET_CONFIG";
+        const SHARED_WALLET_WRITER_ENV: &str = "INDY_TEST_SHARED_WALLET_WRITER";
+        const RECORDS_PER_WRITER: u64 = 20;
+
+        #[test]
+        fn indy_shared_wallet_works_for_concurrent_processes() {
+            let setup = Setup::empty();
+            let config = json!({"id": &setup.name, "storage_config": {"shared": true, "busy_timeout": 10000}}).to_string();
+
+            wallet::create_wallet(&config, WALLET_CREDENTIALS).unwrap();
+
+            let writers: Vec<Child> = (0..2)
+                .map(|writer| {
+                    Command::new(env::current_exe().unwrap())
+                        .args(&["high_cases::shared_wallet::shared_wallet_writer_process", "--exact", "--ignored"])
+                        .env(SHARED_WALLET_CONFIG_ENV, &config)
+                        .env(SHARED_WALLET_WRITER_ENV, writer.to_string())
+                        .spawn()
+                        .unwrap()
+                })
+                .collect();
+
+            // This process writes into the wallet concurrently with the child ones.
+            let wallet_handle = wallet::open_wallet(&config, WALLET_CREDENTIALS).unwrap();
+            _write_records(wallet_handle, "parent");
+
+            for mut writer in writers {
+                assert!(writer.wait().unwrap().success());
+            }
+
+            let search_handle = non_secrets::open_wallet_search(wallet_handle, non_secrets::TYPE, "{}",
+                                                                r#"{"retrieveRecords": false, "retrieveTotalCount": true}"#).unwrap();
+            let search: serde_json::Value = serde_json::from_str(
+                &non_secrets::fetch_wallet_search_next_records(wallet_handle, search_handle, 1).unwrap()).unwrap();
+            assert_eq!(3 * RECORDS_PER_WRITER, search["totalCount"].as_u64().unwrap());
+            non_secrets::close_wallet_search(search_handle).unwrap();
+
+            wallet::close_and_delete_wallet(wallet_handle, &config).unwrap();
+        }
+
+        // Is started as a separate process by indy_shared_wallet_works_for_concurrent_processes.
+        #[test]
+        #[ignore]
+        fn shared_wallet_writer_process() {
+            let (config, writer) = match (env::var(SHARED_WALLET_CONFIG_ENV), env::var(SHARED_WALLET_WRITER_ENV)) {
+                (Ok(config), Ok(writer)) => (config, writer),
+                _ => return
+            };
+
+            let wallet_handle = wallet::open_wallet(&config, WALLET_CREDENTIALS).unwrap();
+            _write_records(wallet_handle, &writer);
+            wallet::close_wallet(wallet_handle).unwrap();
+        }
+
+        fn _write_records(wallet_handle: WalletHandle, writer: &str) {
+            for i in 0..RECORDS_PER_WRITER {
+                let id = format!("{}-{}", writer, i);
+                non_secrets::add_wallet_record(wallet_handle, non_secrets::TYPE, &id, non_secrets::VALUE, Some(non_secrets::TAGS)).unwrap();
+                non_secrets::update_wallet_record_value(wallet_handle, non_secrets::TYPE, &id, non_secrets::VALUE_2).unwrap();
+            }
+        }
+    }
+
+    mod inmem_wallet {
+        use super::*;
+        use crate::utils::non_secrets;
+
+        #[test]
+        fn indy_inmem_wallet_works() {
+            let setup = Setup::empty();
+            let config = json!({"id": &setup.name, "storage_type": "inmem"}).to_string();
+
+            wallet::create_wallet(&config, WALLET_CREDENTIALS).unwrap();
+
+            let wallet_handle = wallet::open_wallet(&config, WALLET_CREDENTIALS).unwrap();
+            non_secrets::add_wallet_record(wallet_handle, non_secrets::TYPE, non_secrets::ID, non_secrets::VALUE, Some(non_secrets::TAGS)).unwrap();
+            non_secrets::add_wallet_record(wallet_handle, non_secrets::TYPE, non_secrets::ID_2, non_secrets::VALUE_2, Some(non_secrets::TAGS_2)).unwrap();
+            wallet::close_wallet(wallet_handle).unwrap();
+
+            assert!(!environment::wallet_home_path().join(&setup.name).exists());
+
+            // data survives re-open within the process
+            let wallet_handle = wallet::open_wallet(&config, WALLET_CREDENTIALS).unwrap();
+
+            let search_handle = non_secrets::open_wallet_search(wallet_handle, non_secrets::TYPE,
+                                                                r#"{"~tagName3": {"$gt": 4}}"#,
+                                                                r#"{"retrieveTotalCount": true}"#).unwrap();
+            let search: serde_json::Value = serde_json::from_str(
+                &non_secrets::fetch_wallet_search_next_records(wallet_handle, search_handle, 5).unwrap()).unwrap();
+            assert_eq!(1, search["totalCount"].as_u64().unwrap());
+            assert_eq!(non_secrets::ID, search["records"][0]["id"].as_str().unwrap());
+            non_secrets::close_wallet_search(search_handle).unwrap();
+
+            wallet::close_and_delete_wallet(wallet_handle, &config).unwrap();
+
+            let res = wallet::open_wallet(&config, WALLET_CREDENTIALS);
+            assert_code!(ErrorCode::WalletNotFoundError, res);
+        }
+    }
+
+    mod get_wallet_audit_log {
+        use super::*;
+        use crate::utils::non_secrets;
+
+        #[test]
+        fn indy_get_wallet_audit_log_works() {
+            let setup = Setup::empty();
+            let config = json!({"id": &setup.name, "audit_log": true}).to_string();
+
+            wallet::create_wallet(&config, WALLET_CREDENTIALS).unwrap();
+            let wallet_handle = wallet::open_wallet(&config, WALLET_CREDENTIALS).unwrap();
+
+            non_secrets::add_wallet_record(wallet_handle, "type", "id", "value", None).unwrap();
+            non_secrets::delete_wallet_record(wallet_handle, "type", "id").unwrap();
+
+            let entries_json = wallet::get_wallet_audit_log(wallet_handle, None).unwrap();
+            let entries: Vec<serde_json::Value> = serde_json::from_str(&entries_json).unwrap();
+
+            assert_eq!(2, entries.len());
+            assert_eq!("type", entries[0]["type"].as_str().unwrap());
+            assert!(entries[0]["name_hash"].as_str().is_some());
+
+            wallet::close_wallet(wallet_handle).unwrap();
+        }
+
+        #[test]
+        fn indy_get_wallet_audit_log_works_for_disabled() {
+            let setup = Setup::wallet();
+
+            non_secrets::add_wallet_record(setup.wallet_handle, "type", "id", "value", None).unwrap();
+
+            let entries_json = wallet::get_wallet_audit_log(setup.wallet_handle, None).unwrap();
+            assert_eq!("[]", entries_json);
+        }
+    }
+
+    mod set_wallet_change_callback {
+        use super::*;
+        use crate::utils::non_secrets;
+
+        #[test]
+        fn indy_set_wallet_change_callback_works() {
+            let setup = Setup::wallet();
+
+            wallet::set_wallet_change_callback(setup.wallet_handle, Some(wallet::collect_wallet_change)).unwrap();
+
+            non_secrets::add_wallet_record(setup.wallet_handle, "type", "id", "value", None).unwrap();
+            non_secrets::update_wallet_record_value(setup.wallet_handle, "type", "id", "value2").unwrap();
+            non_secrets::delete_wallet_record(setup.wallet_handle, "type", "id").unwrap();
+
+            let changes: Vec<serde_json::Value> = wallet::collected_wallet_changes(setup.wallet_handle)
+                .iter()
+                .map(|change| serde_json::from_str(change).unwrap())
+                .collect();
+
+            assert_eq!(3, changes.len());
+            assert_eq!("add", changes[0]["operation"].as_str().unwrap());
+            assert_eq!("update", changes[1]["operation"].as_str().unwrap());
+            assert_eq!("delete", changes[2]["operation"].as_str().unwrap());
+            assert_eq!("type", changes[0]["type"].as_str().unwrap());
+            assert!(changes[0]["name_hash"].as_str().is_some());
+        }
+
+        #[test]
+        fn indy_set_wallet_change_callback_works_for_unsubscribe() {
+            let setup = Setup::wallet();
+
+            wallet::set_wallet_change_callback(setup.wallet_handle, Some(wallet::collect_wallet_change)).unwrap();
+            wallet::set_wallet_change_callback(setup.wallet_handle, None).unwrap();
+
+            non_secrets::add_wallet_record(setup.wallet_handle, "type", "id", "value", None).unwrap();
+
+            assert!(wallet::collected_wallet_changes(setup.wallet_handle).is_empty());
+        }
+    }
+
+    mod get_wallet_stats {
+        use super::*;
+        use crate::utils::non_secrets;
+
+        #[test]
+        fn indy_get_wallet_stats_works() {
+            let setup = Setup::wallet();
+
+            non_secrets::add_wallet_record(setup.wallet_handle, "type", "id1", "value", Some(r#"{"tagName": "tagValue"}"#)).unwrap();
+            non_secrets::add_wallet_record(setup.wallet_handle, "type", "id2", "value", None).unwrap();
+
+            let stats_json = wallet::get_wallet_stats(setup.wallet_handle).unwrap();
+            let stats: serde_json::Value = serde_json::from_str(&stats_json).unwrap();
+
+            assert_eq!(json!({"records": 2, "tags": 1}), stats["types"]["type"]);
+            assert_eq!(0, stats["corrupted_records"].as_u64().unwrap());
+            assert!(stats["storage_size"].as_u64().unwrap() > 0);
+        }
+
+        #[test]
+        fn indy_get_wallet_stats_works_for_plugged_storage_stats() {
+            Setup::empty();
+            InmemWallet::cleanup();
+
+            wallet::register_wallet_storage(INMEM_TYPE, false).unwrap();
+            wallet::register_wallet_storage_stats(INMEM_TYPE).unwrap();
+            wallet::create_wallet(INMEM_WALLET_CONFIG, WALLET_CREDENTIALS).unwrap();
+            let wallet_handle = wallet::open_wallet(INMEM_WALLET_CONFIG, WALLET_CREDENTIALS).unwrap();
+
+            non_secrets::add_wallet_record(wallet_handle, "type", "id1", "value", Some(r#"{"tagName": "tagValue"}"#)).unwrap();
+            non_secrets::add_wallet_record(wallet_handle, "type", "id2", "value", None).unwrap();
+
+            let stats_json = wallet::get_wallet_stats(wallet_handle).unwrap();
+            let stats: serde_json::Value = serde_json::from_str(&stats_json).unwrap();
+
+            assert_eq!(json!({"records": 2, "tags": 1}), stats["types"]["type"]);
+            assert!(stats["storage_size"].as_u64().unwrap() > 0);
+
+            wallet::close_wallet(wallet_handle).unwrap();
+            InmemWallet::cleanup();
+        }
+    }
+
+    mod check_wallet {
+        use super::*;
+        use crate::utils::non_secrets;
+
+        #[test]
+        fn indy_check_wallet_works() {
+            let setup = Setup::empty();
+            let config = config(&setup.name);
+
+            wallet::create_wallet(&config, WALLET_CREDENTIALS).unwrap();
+            let wallet_handle = wallet::open_wallet(&config, WALLET_CREDENTIALS).unwrap();
+            non_secrets::add_wallet_record(wallet_handle, "type", "id", "value", Some(r#"{"tagName": "tagValue"}"#)).unwrap();
+            wallet::close_wallet(wallet_handle).unwrap();
+
+            let report_json = wallet::check_wallet(&config, WALLET_CREDENTIALS, Some(r#"{"repair": true}"#)).unwrap();
+            let report: serde_json::Value = serde_json::from_str(&report_json).unwrap();
+
+            assert_eq!(json!([]), report["corrupted_items"]);
+            assert_eq!(json!([]), report["integrity_errors"]);
+            assert_eq!(0, report["quarantined_items"].as_u64().unwrap());
+            assert_eq!(0, report["orphaned_tags"].as_u64().unwrap());
+            assert!(report["total_items"].as_u64().unwrap() >= 1);
+        }
+
+        #[test]
+        fn indy_check_wallet_works_for_opened() {
+            let setup = Setup::wallet();
+
+            let res = wallet::check_wallet(&setup.wallet_config, WALLET_CREDENTIALS, None);
+            assert_code!(ErrorCode::CommonInvalidState, res);
+        }
     }
 
     mod generate_wallet_key {
@@ -473,6 +915,36 @@ mod medium_cases {
             assert_code!(ErrorCode::WalletNotFoundError, res);
         }
 
+        #[test]
+        fn indy_open_wallet_works_for_other_kek() {
+            let setup = Setup::empty();
+            let config = config(&setup.name);
+            let (credentials, path) = kek_credentials(&setup.name);
+            let (other_credentials, other_path) = kek_credentials(&format!("{}_other", setup.name));
+
+            wallet::create_wallet(&config, &credentials).unwrap();
+
+            let res = wallet::open_wallet(&config, &other_credentials);
+            assert_code!(ErrorCode::WalletAccessFailed, res);
+
+            wallet::delete_wallet(&config, &credentials).unwrap();
+            cleanup_file(&path);
+            cleanup_file(&other_path);
+        }
+
+        #[test]
+        fn indy_open_wallet_works_for_unknown_kek_provider() {
+            let setup = Setup::empty();
+            let config = config(&setup.name);
+            let credentials = json!({
+                "key": "key_id",
+                "key_derivation_method": {"KEK": "unknown"}
+            }).to_string();
+
+            let res = wallet::create_wallet(&config, &credentials);
+            assert_code!(ErrorCode::WalletUnknownTypeError, res);
+        }
+
         #[test]
         fn indy_open_wallet_works_for_twice() {
             let setup = Setup::empty();
@@ -569,6 +1041,38 @@ mod medium_cases {
         }
     }
 
+    mod get_wallet_stats {
+        use super::*;
+
+        #[test]
+        fn indy_get_wallet_stats_works_for_invalid_handle() {
+            Setup::empty();
+
+            let res = wallet::get_wallet_stats(INVALID_WALLET_HANDLE);
+            assert_code!(ErrorCode::WalletInvalidHandle, res);
+        }
+
+        #[test]
+        fn indy_register_wallet_storage_stats_works_for_unknown_type() {
+            Setup::empty();
+
+            let res = wallet::register_wallet_storage_stats("unknown_storage_type");
+            assert_eq!(ErrorCode::WalletUnknownTypeError, res.unwrap_err());
+        }
+    }
+
+    mod set_wallet_change_callback {
+        use super::*;
+
+        #[test]
+        fn indy_set_wallet_change_callback_works_for_invalid_handle() {
+            Setup::empty();
+
+            let res = wallet::set_wallet_change_callback(INVALID_WALLET_HANDLE, Some(wallet::collect_wallet_change));
+            assert_code!(ErrorCode::WalletInvalidHandle, res);
+        }
+    }
+
     mod close_wallet {
         use super::*;
 
@@ -42,6 +42,22 @@ extern {
                                                   tails_writer_handle: TailWriterHandle,
                                                   cb: Option<ResponseStringStringStringCB>) -> Error;
 
+    pub fn indy_issuer_register_revoc_reg_created_hook(command_handle: CommandHandle,
+                                                       hook: Option<RevocRegCreatedHook>,
+                                                       cb: Option<ResponseEmptyCB>) -> Error;
+
+    pub fn indy_issuer_create_revoc_reg_pool(command_handle: CommandHandle,
+                                             wallet_handle: WalletHandle,
+                                             issuer_did: CString,
+                                             cred_def_id: CString,
+                                             config_json: CString,
+                                             cb: Option<ResponseStringCB>) -> Error;
+
+    pub fn indy_issuer_get_revoc_reg_pool(command_handle: CommandHandle,
+                                          wallet_handle: WalletHandle,
+                                          cred_def_id: CString,
+                                          cb: Option<ResponseStringCB>) -> Error;
+
     pub fn indy_issuer_create_credential_offer(command_handle: CommandHandle,
                                                wallet_handle: WalletHandle,
                                                cred_def_id: CString,
@@ -63,6 +79,32 @@ extern {
                                          cred_revoc_id: CString,
                                          cb: Option<ResponseStringCB>) -> Error;
 
+    pub fn indy_issuer_create_credentials(command_handle: CommandHandle,
+                                          wallet_handle: WalletHandle,
+                                          cred_requests_json: CString,
+                                          rev_reg_id: CString,
+                                          blob_storage_reader_handle: BlobStorageReaderHandle,
+                                          cb: Option<ResponseStringStringCB>) -> Error;
+
+    pub fn indy_issuer_revoke_credentials(command_handle: CommandHandle,
+                                          wallet_handle: WalletHandle,
+                                          blob_storage_reader_cfg_handle: BlobStorageReaderCfgHandle,
+                                          rev_reg_id: CString,
+                                          cred_revoc_ids_json: CString,
+                                          cb: Option<ResponseStringCB>) -> Error;
+
+    pub fn indy_issuer_get_revoc_reg_status(command_handle: CommandHandle,
+                                            wallet_handle: WalletHandle,
+                                            rev_reg_id: CString,
+                                            cb: Option<ResponseStringCB>) -> Error;
+
+    pub fn indy_issuer_check_revoc_reg_drift(command_handle: CommandHandle,
+                                             pool_handle: PoolHandle,
+                                             wallet_handle: WalletHandle,
+                                             submitter_did: CString,
+                                             rev_reg_id: CString,
+                                             cb: Option<ResponseStringCB>) -> Error;
+
     pub fn indy_issuer_merge_revocation_registry_deltas(command_handle: CommandHandle,
                                                         rev_reg_delta_json: CString,
                                                         other_rev_reg_delta_json: CString,
@@ -152,6 +194,12 @@ extern {
                                                               search_handle: SearchHandle,
                                                               cb: Option<ResponseEmptyCB>) -> Error;
 
+    pub fn indy_prover_auto_select_credentials(command_handle: CommandHandle,
+                                               wallet_handle: WalletHandle,
+                                               proof_request_json: CString,
+                                               policy_json: CString,
+                                               cb: Option<ResponseStringCB>) -> Error;
+
     pub fn indy_prover_create_proof(command_handle: CommandHandle,
                                     wallet_handle: WalletHandle,
                                     proof_req_json: CString,
@@ -171,6 +219,24 @@ extern {
                                       rev_regs_json: CString,
                                       cb: Option<ResponseBoolCB>) -> Error;
 
+    pub fn indy_verifier_verify_proof_with_report(command_handle: CommandHandle,
+                                                  proof_request_json: CString,
+                                                  proof_json: CString,
+                                                  schemas_json: CString,
+                                                  credential_defs_json: CString,
+                                                  rev_reg_defs_json: CString,
+                                                  rev_regs_json: CString,
+                                                  cb: Option<ResponseStringCB>) -> Error;
+
+    pub fn indy_verifier_verify_proof_with_ledger(command_handle: CommandHandle,
+                                                  pool_handle: PoolHandle,
+                                                  wallet_handle: WalletHandle,
+                                                  submitter_did: CString,
+                                                  proof_request_json: CString,
+                                                  proof_json: CString,
+                                                  options_json: CString,
+                                                  cb: Option<ResponseStringCB>) -> Error;
+
     pub fn indy_create_revocation_state(command_handle: CommandHandle,
                                         blob_storage_reader_handle: BlobStorageReaderHandle,
                                         rev_reg_def_json: CString,
@@ -193,5 +259,24 @@ extern {
     pub fn indy_to_unqualified(command_handle: CommandHandle,
                                entity: CString,
                                cb: Option<ResponseStringCB>) -> Error;
+
+    pub fn indy_encode_credential_values(command_handle: CommandHandle,
+                                         raw_values_json: CString,
+                                         cb: Option<ResponseStringCB>) -> Error;
+
+    pub fn indy_anoncreds_validate(command_handle: CommandHandle,
+                                   object_type: CString,
+                                   object_json: CString,
+                                   schema_json: CString,
+                                   cred_def_json: CString,
+                                   cb: Option<ResponseStringCB>) -> Error;
+
+    pub fn indy_encode_date(command_handle: CommandHandle,
+                            date: CString,
+                            cb: Option<ResponseStringCB>) -> Error;
 }
 
+
+pub type RevocRegCreatedHook = extern fn(revoc_reg_id: CString,
+                                         revoc_reg_def_json: CString,
+                                         revoc_reg_entry_json: CString) -> Error;
@@ -1,6 +1,6 @@
 use super::*;
 
-use {CString, Error, CommandHandle};
+use {BString, CString, Error, CommandHandle, IndyHandle};
 
 extern {
 
@@ -13,5 +13,56 @@ extern {
                                          type_: CString,
                                          config_json: CString,
                                          cb: Option<ResponseI32CB>) -> Error;
+
+    pub fn indy_open_tails_reader(command_handle: CommandHandle,
+                                  rev_reg_def_json: CString,
+                                  config_json: CString,
+                                  cb: Option<ResponseI32CB>) -> Error;
+
+    pub fn indy_register_blob_storage_reader(command_handle: CommandHandle,
+                                             type_: CString,
+                                             open: Option<BlobStorageReaderOpen>,
+                                             open_blob: Option<BlobStorageReaderOpenBlob>,
+                                             read: Option<BlobStorageReaderRead>,
+                                             verify: Option<BlobStorageReaderVerify>,
+                                             close_blob: Option<BlobStorageReaderCloseBlob>,
+                                             cb: Option<ResponseEmptyCB>) -> Error;
+
+    pub fn indy_register_blob_storage_writer(command_handle: CommandHandle,
+                                             type_: CString,
+                                             open: Option<BlobStorageWriterOpen>,
+                                             create_blob: Option<BlobStorageWriterCreateBlob>,
+                                             append: Option<BlobStorageWriterAppend>,
+                                             finalize: Option<BlobStorageWriterFinalize>,
+                                             free_blob: Option<BlobStorageWriterFreeBlob>,
+                                             cb: Option<ResponseEmptyCB>) -> Error;
 }
 
+pub type BlobStorageReaderOpen = extern fn(config: CString,
+                                           reader_handle_p: *mut IndyHandle) -> Error;
+pub type BlobStorageReaderOpenBlob = extern fn(reader_handle: IndyHandle,
+                                               hash: BString,
+                                               hash_len: usize,
+                                               location: CString,
+                                               blob_handle_p: *mut IndyHandle) -> Error;
+pub type BlobStorageReaderRead = extern fn(blob_handle: IndyHandle,
+                                           offset: usize,
+                                           buf: *mut u8,
+                                           buf_len: usize,
+                                           read_len_p: *mut usize) -> Error;
+pub type BlobStorageReaderVerify = extern fn(blob_handle: IndyHandle,
+                                             valid_p: *mut bool) -> Error;
+pub type BlobStorageReaderCloseBlob = extern fn(blob_handle: IndyHandle) -> Error;
+pub type BlobStorageWriterOpen = extern fn(config: CString,
+                                           writer_handle_p: *mut IndyHandle) -> Error;
+pub type BlobStorageWriterCreateBlob = extern fn(writer_handle: IndyHandle,
+                                                 blob_handle_p: *mut IndyHandle) -> Error;
+pub type BlobStorageWriterAppend = extern fn(blob_handle: IndyHandle,
+                                             data: BString,
+                                             data_len: usize,
+                                             written_len_p: *mut usize) -> Error;
+pub type BlobStorageWriterFinalize = extern fn(blob_handle: IndyHandle,
+                                               hash: BString,
+                                               hash_len: usize,
+                                               location_p: *mut CString) -> Error;
+pub type BlobStorageWriterFreeBlob = extern fn(blob_handle: IndyHandle) -> Error;
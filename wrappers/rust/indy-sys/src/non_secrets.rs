@@ -46,6 +46,11 @@ extern {
                                      id: CString,
                                      cb: Option<ResponseEmptyCB>) -> Error;
 
+    pub fn indy_purge_expired_records(command_handle: CommandHandle,
+                                      wallet_handle: WalletHandle,
+                                      type_: CString,
+                                      cb: Option<ResponseUsizeCB>) -> Error;
+
     pub fn indy_get_wallet_record(command_handle: CommandHandle,
                                   wallet_handle: WalletHandle,
                                   type_: CString,
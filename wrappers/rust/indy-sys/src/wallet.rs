@@ -32,6 +32,19 @@ extern {
                                         free_search: Option<WalletFreeSearch>,
                                         cb: Option<ResponseEmptyCB>) -> Error;
 
+    pub fn indy_register_wallet_storage_stats(command_handle: CommandHandle,
+                                              type_: CString,
+                                              get_storage_stats: Option<WalletGetStorageStats>,
+                                              free_storage_stats: Option<WalletFreeStorageStats>,
+                                              cb: Option<ResponseEmptyCB>) -> Error;
+
+    pub fn indy_register_wallet_kek_provider(command_handle: CommandHandle,
+                                             type_: CString,
+                                             wrap_key: Option<WalletKekWrapKey>,
+                                             unwrap_key: Option<WalletKekUnwrapKey>,
+                                             free_key: Option<WalletKekFreeKey>,
+                                             cb: Option<ResponseEmptyCB>) -> Error;
+
     pub fn indy_create_wallet(command_handle: CommandHandle,
                               config: CString,
                               credentials: CString,
@@ -53,6 +66,53 @@ extern {
                               import_config: CString,
                               cb: Option<ResponseEmptyCB>) -> Error;
 
+    pub fn indy_import_wallet_increment(command_handle: CommandHandle,
+                                        wallet_handle: WalletHandle,
+                                        import_config: CString,
+                                        cb: Option<ResponseEmptyCB>) -> Error;
+
+    pub fn indy_export_wallet_to_buffer(command_handle: CommandHandle,
+                                        wallet_handle: WalletHandle,
+                                        export_config: CString,
+                                        cb: Option<ResponseSliceCB>) -> Error;
+
+    pub fn indy_export_wallet_to_sink(command_handle: CommandHandle,
+                                      wallet_handle: WalletHandle,
+                                      export_config: CString,
+                                      sink_handle: IndyHandle,
+                                      write_cb: Option<WalletExportWrite>,
+                                      cb: Option<ResponseEmptyCB>) -> Error;
+
+    pub fn indy_import_wallet_from_buffer(command_handle: CommandHandle,
+                                          config: CString,
+                                          credentials: CString,
+                                          import_config: CString,
+                                          data: BString,
+                                          data_len: u32,
+                                          cb: Option<ResponseEmptyCB>) -> Error;
+
+    pub fn indy_import_wallet_from_source(command_handle: CommandHandle,
+                                          config: CString,
+                                          credentials: CString,
+                                          import_config: CString,
+                                          source_handle: IndyHandle,
+                                          read_cb: Option<WalletImportRead>,
+                                          cb: Option<ResponseEmptyCB>) -> Error;
+
+    pub fn indy_import_wallet_increment_from_buffer(command_handle: CommandHandle,
+                                                    wallet_handle: WalletHandle,
+                                                    import_config: CString,
+                                                    data: BString,
+                                                    data_len: u32,
+                                                    cb: Option<ResponseEmptyCB>) -> Error;
+
+    pub fn indy_import_wallet_increment_from_source(command_handle: CommandHandle,
+                                                    wallet_handle: WalletHandle,
+                                                    import_config: CString,
+                                                    source_handle: IndyHandle,
+                                                    read_cb: Option<WalletImportRead>,
+                                                    cb: Option<ResponseEmptyCB>) -> Error;
+
     pub fn indy_close_wallet(command_handle: CommandHandle,
                              wallet_handle: WalletHandle,
                              cb: Option<ResponseEmptyCB>) -> Error;
@@ -62,6 +122,26 @@ extern {
                               credentials: CString,
                               cb: Option<ResponseEmptyCB>) -> Error;
 
+    pub fn indy_check_wallet(command_handle: CommandHandle,
+                             config: CString,
+                             credentials: CString,
+                             options_json: CString,
+                             cb: Option<ResponseStringCB>) -> Error;
+
+    pub fn indy_get_wallet_audit_log(command_handle: CommandHandle,
+                                     wallet_handle: WalletHandle,
+                                     query_json: CString,
+                                     cb: Option<ResponseStringCB>) -> Error;
+
+    pub fn indy_get_wallet_stats(command_handle: CommandHandle,
+                                 wallet_handle: WalletHandle,
+                                 cb: Option<ResponseStringCB>) -> Error;
+
+    pub fn indy_set_wallet_change_callback(command_handle: CommandHandle,
+                                           wallet_handle: WalletHandle,
+                                           change_cb: Option<WalletChangeCB>,
+                                           cb: Option<ResponseEmptyCB>) -> Error;
+
     pub fn indy_generate_wallet_key(command_handle: CommandHandle,
                                     config: CString,
                                     cb: Option<ResponseStringCB>) -> Error;
@@ -147,3 +227,30 @@ pub type WalletFetchSearchNextRecord = extern fn(storage_handle: StorageHandle,
                                                  record_handle_p: *mut RecordHandle) -> Error;
 pub type WalletFreeSearch = extern fn(storage_handle: StorageHandle,
                                       search_handle: SearchHandle) -> Error;
+pub type WalletGetStorageStats = extern fn(storage_handle: StorageHandle,
+                                           stats_json_p: *mut CString,
+                                           stats_handle_p: *mut IndyHandle) -> Error;
+pub type WalletFreeStorageStats = extern fn(storage_handle: StorageHandle,
+                                            stats_handle: IndyHandle) -> Error;
+pub type WalletKekWrapKey = extern fn(key_id: CString,
+                                      key: BString,
+                                      key_len: usize,
+                                      wrapped_key_p: *mut BString,
+                                      wrapped_key_len_p: *mut usize,
+                                      key_handle_p: *mut IndyHandle) -> Error;
+pub type WalletKekUnwrapKey = extern fn(key_id: CString,
+                                        wrapped_key: BString,
+                                        wrapped_key_len: usize,
+                                        key_p: *mut BString,
+                                        key_len_p: *mut usize,
+                                        key_handle_p: *mut IndyHandle) -> Error;
+pub type WalletKekFreeKey = extern fn(key_handle: IndyHandle) -> Error;
+pub type WalletChangeCB = extern fn(wallet_handle: WalletHandle,
+                                    change_json: CString);
+pub type WalletExportWrite = extern fn(sink_handle: IndyHandle,
+                                       data: BString,
+                                       data_len: usize) -> Error;
+pub type WalletImportRead = extern fn(source_handle: IndyHandle,
+                                      buf: *mut u8,
+                                      buf_len: usize,
+                                      read_len_p: *mut usize) -> Error;
@@ -15,8 +15,9 @@ use ffi::{ResponseStringStringCB,
           ResponseI32CB,
           ResponseEmptyCB,
           ResponseBoolCB};
-use crate::{CommandHandle, WalletHandle, SearchHandle, BlobStorageReaderHandle, TailsWriterHandle};
+use crate::{CommandHandle, PoolHandle, WalletHandle, SearchHandle, BlobStorageReaderHandle, TailsWriterHandle};
 use ffi::BlobStorageReaderCfgHandle;
+pub use ffi::anoncreds::RevocRegCreatedHook;
 
 /*
 These functions wrap the Ursa algorithm as documented in this paper:
@@ -297,6 +298,91 @@ fn _issuer_create_and_store_revoc_reg(command_handle: CommandHandle, wallet_hand
     })
 }
 
+/// Register the hook to be called each time a revocation registry pool creates a new revocation registry.
+///
+/// The hook is called on the libindy command thread and must not wait for the results of other libindy calls.
+/// Returning anything other than `Success` leaves the registry unpublished, the hook will be called
+/// for it again before the registry is used for issuance.
+///
+/// # Arguments
+/// * `hook` - hook taking revoc_reg_id, revoc_reg_def_json and revoc_reg_entry_json (None to unregister)
+pub fn issuer_register_revoc_reg_created_hook(hook: Option<RevocRegCreatedHook>) -> Box<dyn Future<Item=(), Error=IndyError>> {
+    let (receiver, command_handle, cb) = ClosureHandler::cb_ec();
+
+    let err = _issuer_register_revoc_reg_created_hook(command_handle, hook, cb);
+
+    ResultHandler::empty(command_handle, err, receiver)
+}
+
+fn _issuer_register_revoc_reg_created_hook(command_handle: CommandHandle, hook: Option<RevocRegCreatedHook>, cb: Option<ResponseEmptyCB>) -> ErrorCode {
+    ErrorCode::from(unsafe {
+        anoncreds::indy_issuer_register_revoc_reg_created_hook(command_handle, hook, cb)
+    })
+}
+
+/// Create a pool of revocation registries for the credential definition.
+///
+/// The pool keeps the active revocation registry used for issuance and the pre-provisioned next one.
+/// Passing the id of any pool registry to `issuer_create_credential` issues the credential in the active registry,
+/// full registries are replaced by the next one automatically.
+///
+/// # Arguments
+/// * `wallet_handle` - wallet handle (created by open_wallet).
+/// * `issuer_did` - a DID of the issuer
+/// * `cred_def_id` - id of stored in ledger credential definition
+/// * `config_json` - pool configuration as json:
+/// {
+///     "issuance_type": (optional) type of issuance of pool registries,
+///     "max_cred_num": (optional) maximum number of credentials each pool registry can process (default 100000),
+///     "tag_prefix": (optional) prefix of pool registry tags (default "pool"),
+///     "tails_writer_type": (optional) type of blob storage writer for tails (default "default"),
+///     "tails_writer_config": string - config of blob storage writer for tails,
+/// }
+///
+/// # Returns
+/// * `revoc_reg_pool_json` - state of the pool
+pub fn issuer_create_revoc_reg_pool(wallet_handle: WalletHandle, issuer_did: &str, cred_def_id: &str, config_json: &str) -> Box<dyn Future<Item=String, Error=IndyError>> {
+    let (receiver, command_handle, cb) = ClosureHandler::cb_ec_string();
+
+    let err = _issuer_create_revoc_reg_pool(command_handle, wallet_handle, issuer_did, cred_def_id, config_json, cb);
+
+    ResultHandler::str(command_handle, err, receiver)
+}
+
+fn _issuer_create_revoc_reg_pool(command_handle: CommandHandle, wallet_handle: WalletHandle, issuer_did: &str, cred_def_id: &str, config_json: &str, cb: Option<ResponseStringCB>) -> ErrorCode {
+    let issuer_did = c_str!(issuer_did);
+    let cred_def_id = c_str!(cred_def_id);
+    let config_json = c_str!(config_json);
+
+    ErrorCode::from(unsafe {
+        anoncreds::indy_issuer_create_revoc_reg_pool(command_handle, wallet_handle, issuer_did.as_ptr(), cred_def_id.as_ptr(), config_json.as_ptr(), cb)
+    })
+}
+
+/// Get the state of the revocation registry pool created for the credential definition.
+///
+/// # Arguments
+/// * `wallet_handle` - wallet handle (created by open_wallet).
+/// * `cred_def_id` - id of the credential definition the pool is created for
+///
+/// # Returns
+/// * `revoc_reg_pool_json` - state of the pool
+pub fn issuer_get_revoc_reg_pool(wallet_handle: WalletHandle, cred_def_id: &str) -> Box<dyn Future<Item=String, Error=IndyError>> {
+    let (receiver, command_handle, cb) = ClosureHandler::cb_ec_string();
+
+    let err = _issuer_get_revoc_reg_pool(command_handle, wallet_handle, cred_def_id, cb);
+
+    ResultHandler::str(command_handle, err, receiver)
+}
+
+fn _issuer_get_revoc_reg_pool(command_handle: CommandHandle, wallet_handle: WalletHandle, cred_def_id: &str, cb: Option<ResponseStringCB>) -> ErrorCode {
+    let cred_def_id = c_str!(cred_def_id);
+
+    ErrorCode::from(unsafe {
+        anoncreds::indy_issuer_get_revoc_reg_pool(command_handle, wallet_handle, cred_def_id.as_ptr(), cb)
+    })
+}
+
 /// Create credential offer that will be used by Prover for
 /// credential request creation. Offer includes nonce and key correctness proof
 /// for authentication between protocol steps and integrity checking.
@@ -353,6 +439,7 @@ fn _issuer_create_credential_offer(command_handle: CommandHandle, wallet_handle:
 ///      "attr2" : {"raw": "value1", "encoded": "value1_as_int" }
 ///     }
 ///    If you want to use empty value for some credential field, you should set "raw" to "" and "encoded" should not be empty
+///    "encoded" can be omitted: in this case it will be calculated with the canonical encoding (see `encode_credential_values`)
 /// * `rev_reg_id`: id of revocation registry stored in the wallet
 /// * `blob_storage_reader_handle`: configuration of blob storage reader handle that will allow to read revocation tails
 ///
@@ -406,6 +493,54 @@ fn _issuer_create_credential(
     })
 }
 
+/// Create credentials for a batch of credential requests in the same revocation registry.
+///
+/// The revocation registry is updated in the wallet only if all credentials are issued.
+/// A single merged revocation registry delta is returned for the whole batch.
+///
+/// # Arguments
+/// * `wallet_handle`: wallet handle (created by Wallet::open_wallet).
+/// * `cred_requests_json`: list of credentials to issue for the same credential definition
+///     [{
+///         "cred_offer": a cred offer created by issuer_create_credential_offer,
+///         "cred_req": a credential request created by prover_create_credential_req,
+///         "cred_values": credential values (see issuer_create_credential)
+///     }]
+/// * `rev_reg_id`: id of revocation registry stored in the wallet
+/// * `blob_storage_reader_handle`: configuration of blob storage reader handle that will allow to read revocation tails
+///
+/// # Returns
+/// * `creds_json`: issued credentials in the order of requests
+///     [{
+///         "cred": <credential json>,
+///         "cred_revoc_id": Optional<string> - local id for revocation info
+///     }]
+/// * `revoc_reg_delta_json`: Revocation registry delta json with all newly issued credentials
+pub fn issuer_create_credentials(wallet_handle: WalletHandle,
+                                 cred_requests_json: &str,
+                                 rev_reg_id: Option<&str>,
+                                 blob_storage_reader_handle: BlobStorageReaderHandle) -> Box<dyn Future<Item=(String, Option<String>), Error=IndyError>> {
+    let (receiver, command_handle, cb) = ClosureHandler::cb_ec_string_opt_string();
+
+    let err = _issuer_create_credentials(command_handle, wallet_handle, cred_requests_json, rev_reg_id, blob_storage_reader_handle, cb);
+
+    ResultHandler::str_optstr(command_handle, err, receiver)
+}
+
+fn _issuer_create_credentials(command_handle: CommandHandle,
+                              wallet_handle: WalletHandle,
+                              cred_requests_json: &str,
+                              rev_reg_id: Option<&str>,
+                              blob_storage_reader_handle: BlobStorageReaderHandle,
+                              cb: Option<ResponseStringStringCB>) -> ErrorCode {
+    let cred_requests_json = c_str!(cred_requests_json);
+    let rev_reg_id_str = opt_c_str!(rev_reg_id);
+
+    ErrorCode::from(unsafe {
+        anoncreds::indy_issuer_create_credentials(command_handle, wallet_handle, cred_requests_json.as_ptr(), opt_c_ptr!(rev_reg_id, rev_reg_id_str), blob_storage_reader_handle, cb)
+    })
+}
+
 /// Revoke a credential identified by a cred_revoc_id (returned by indy_issuer_create_credential).
 ///
 /// The corresponding credential definition and revocation registry must be already
@@ -444,6 +579,109 @@ fn _issuer_revoke_credential(command_handle: CommandHandle,
     })
 }
 
+/// Revoke a list of credentials identified by cred_revoc_ids in the same revocation registry.
+///
+/// The revocation registry is updated in the wallet only if all credentials are revoked.
+/// A single merged revocation registry delta is returned for the whole list.
+///
+/// # Arguments
+/// * `wallet_handle`: wallet handle (created by Wallet::open_wallet).
+/// * `blob_storage_reader_cfg_handle`: configuration of blob storage reader handle that will allow to read revocation tails
+/// * `rev_reg_id`: id of revocation registry stored in wallet
+/// * `cred_revoc_ids_json`: list of local ids for revocation info: [string]
+///
+/// # Returns
+/// * `revoc_reg_delta_json`: Revocation registry delta json with all revoked credentials
+pub fn issuer_revoke_credentials(wallet_handle: WalletHandle, blob_storage_reader_cfg_handle: BlobStorageReaderCfgHandle, rev_reg_id: &str, cred_revoc_ids_json: &str) -> Box<dyn Future<Item=String, Error=IndyError>> {
+    let (receiver, command_handle, cb) = ClosureHandler::cb_ec_string();
+
+    let err = _issuer_revoke_credentials(command_handle, wallet_handle, blob_storage_reader_cfg_handle, rev_reg_id, cred_revoc_ids_json, cb);
+
+    ResultHandler::str(command_handle, err, receiver)
+}
+
+fn _issuer_revoke_credentials(command_handle: CommandHandle,
+                              wallet_handle: WalletHandle,
+                              blob_storage_reader_cfg_handle: BlobStorageReaderCfgHandle,
+                              rev_reg_id: &str,
+                              cred_revoc_ids_json: &str,
+                              cb: Option<ResponseStringCB>) -> ErrorCode {
+    let rev_reg_id = c_str!(rev_reg_id);
+    let cred_revoc_ids_json = c_str!(cred_revoc_ids_json);
+
+    ErrorCode::from(unsafe {
+        anoncreds::indy_issuer_revoke_credentials(command_handle, wallet_handle, blob_storage_reader_cfg_handle, rev_reg_id.as_ptr(), cred_revoc_ids_json.as_ptr(), cb)
+    })
+}
+
+/// Get the state of the revocation registry stored in the issuer's wallet.
+///
+/// # Arguments
+/// * `wallet_handle`: wallet handle (created by Wallet::open_wallet).
+/// * `rev_reg_id`: id of revocation registry stored in wallet
+///
+/// # Returns
+/// * `revoc_reg_status_json`: state of the revocation registry
+/// {
+///     "rev_reg_id": string - id of the revocation registry,
+///     "issuance_type": string - ISSUANCE_BY_DEFAULT or ISSUANCE_ON_DEMAND,
+///     "max_cred_num": number - capacity of the registry,
+///     "next_index": Optional<number> - index the next credential will be issued with (null if the registry is full),
+///     "issued": number - count of issued indices,
+///     "revoked": number - count of revoked indices,
+///     "revoked_ids": [number] - revoked indices in ascending order
+/// }
+pub fn issuer_get_revoc_reg_status(wallet_handle: WalletHandle, rev_reg_id: &str) -> Box<dyn Future<Item=String, Error=IndyError>> {
+    let (receiver, command_handle, cb) = ClosureHandler::cb_ec_string();
+
+    let err = _issuer_get_revoc_reg_status(command_handle, wallet_handle, rev_reg_id, cb);
+
+    ResultHandler::str(command_handle, err, receiver)
+}
+
+fn _issuer_get_revoc_reg_status(command_handle: CommandHandle, wallet_handle: WalletHandle, rev_reg_id: &str, cb: Option<ResponseStringCB>) -> ErrorCode {
+    let rev_reg_id = c_str!(rev_reg_id);
+
+    ErrorCode::from(unsafe {
+        anoncreds::indy_issuer_get_revoc_reg_status(command_handle, wallet_handle, rev_reg_id.as_ptr(), cb)
+    })
+}
+
+/// Compare the accumulator of the revocation registry stored in the issuer's wallet
+/// with the current accumulator on the ledger.
+///
+/// # Arguments
+/// * `pool_handle`: pool handle (created by open_pool_ledger).
+/// * `wallet_handle`: wallet handle (created by Wallet::open_wallet).
+/// * `submitter_did`: (Optional) DID of the read request sender.
+/// * `rev_reg_id`: id of revocation registry stored in wallet
+///
+/// # Returns
+/// * `revoc_reg_drift_json`: result of the comparison
+/// {
+///     "rev_reg_id": string - id of the revocation registry,
+///     "in_sync": bool - whether the local accumulator matches the ledger one,
+///     "local_accum": string - accumulator stored in the wallet,
+///     "ledger_accum": string - accumulator stored on the ledger,
+///     "ledger_timestamp": number - time of the ledger revocation registry entry
+/// }
+pub fn issuer_check_revoc_reg_drift(pool_handle: PoolHandle, wallet_handle: WalletHandle, submitter_did: Option<&str>, rev_reg_id: &str) -> Box<dyn Future<Item=String, Error=IndyError>> {
+    let (receiver, command_handle, cb) = ClosureHandler::cb_ec_string();
+
+    let err = _issuer_check_revoc_reg_drift(command_handle, pool_handle, wallet_handle, submitter_did, rev_reg_id, cb);
+
+    ResultHandler::str(command_handle, err, receiver)
+}
+
+fn _issuer_check_revoc_reg_drift(command_handle: CommandHandle, pool_handle: PoolHandle, wallet_handle: WalletHandle, submitter_did: Option<&str>, rev_reg_id: &str, cb: Option<ResponseStringCB>) -> ErrorCode {
+    let submitter_did_str = opt_c_str!(submitter_did);
+    let rev_reg_id = c_str!(rev_reg_id);
+
+    ErrorCode::from(unsafe {
+        anoncreds::indy_issuer_check_revoc_reg_drift(command_handle, pool_handle, wallet_handle, opt_c_ptr!(submitter_did, submitter_did_str), rev_reg_id.as_ptr(), cb)
+    })
+}
+
 /// Merge two revocation registry deltas (returned by create_credential or revoke_credential) to accumulate common delta.
 /// Send common delta to ledger to reduce the load.
 ///
@@ -877,8 +1115,9 @@ fn _prover_close_credentials_search(command_handle: CommandHandle, search_handle
 /// `predicate_info`: Describes requested attribute predicate
 ///     {
 ///         "name": attribute name, (case insensitive and ignore spaces)
-///         "p_type": predicate type (Currently ">=" only)
+///         "p_type": predicate type (">=", ">", "<=", "<", "between")
 ///         "p_value": int predicate value
+///         "p_value_to": Optional<int>, // upper bound of "between" predicate, "p_value" is the lower one (both inclusive)
 ///         "restrictions": Optional<filter_json>, // see above
 ///         "non_revoked": Optional<<non_revoc_interval>>, // see below,
 ///                        // If specified prover must proof non-revocation
@@ -979,8 +1218,9 @@ fn _prover_get_credentials_for_proof_req(command_handle: CommandHandle, wallet_h
 /// `predicate_info`: Describes requested attribute predicate
 ///     {
 ///         "name": attribute name, (case insensitive and ignore spaces)
-///         "p_type": predicate type (Currently ">=" only)
+///         "p_type": predicate type (">=", ">", "<=", "<", "between")
 ///         "p_value": int predicate value
+///         "p_value_to": Optional<int>, // upper bound of "between" predicate, "p_value" is the lower one (both inclusive)
 ///         "restrictions": Optional<filter_json>, // see above
 ///         "non_revoked": Optional<<non_revoc_interval>>, // see below,
 ///                        // If specified prover must proof non-revocation
@@ -1082,6 +1322,44 @@ fn _prover_close_credentials_search_for_proof_req(command_handle: CommandHandle,
     })
 }
 
+/// Selects credentials from the wallet to satisfy the proof request.
+///
+/// # Arguments
+/// * `wallet_handle` - wallet handle (created by open_wallet).
+/// * `proof_request_json` - proof request json (see `prover_create_proof`)
+/// * `policy_json` - selection policy
+///     {
+///         "strategy": Optional<string> - "newest" (default) | "fewest_credentials" | "prefer_non_revocable"
+///         "self_attested_attributes": Optional<object> - {"<attr_referent>": string, ...}
+///     }
+///
+/// # Returns
+/// * `selection_json`
+///     {
+///         "requested_credentials": <requested_credentials_json>, // see `prover_create_proof`
+///         "unmet_referents": {
+///             "requested_attributes": [<attr_referent>, ...],
+///             "requested_predicates": [<predicate_referent>, ...],
+///         }
+///     }
+/// See `indy_prover_auto_select_credentials` in libindy for the details.
+pub fn prover_auto_select_credentials(wallet_handle: WalletHandle, proof_request_json: &str, policy_json: &str) -> Box<dyn Future<Item=String, Error=IndyError>> {
+    let (receiver, command_handle, cb) = ClosureHandler::cb_ec_string();
+
+    let err = _prover_auto_select_credentials(command_handle, wallet_handle, proof_request_json, policy_json, cb);
+
+    ResultHandler::str(command_handle, err, receiver)
+}
+
+fn _prover_auto_select_credentials(command_handle: CommandHandle, wallet_handle: WalletHandle, proof_request_json: &str, policy_json: &str, cb: Option<ResponseStringCB>) -> ErrorCode {
+    let proof_request_json = c_str!(proof_request_json);
+    let policy_json = c_str!(policy_json);
+
+    ErrorCode::from(unsafe {
+        anoncreds::indy_prover_auto_select_credentials(command_handle, wallet_handle, proof_request_json.as_ptr(), policy_json.as_ptr(), cb)
+    })
+}
+
 /// Creates a proof according to the given proof request
 /// Either a corresponding credential with optionally revealed attributes or self-attested attribute must be provided
 /// for each requested attribute (see indy_prover_get_credentials_for_pool_req).
@@ -1170,8 +1448,9 @@ fn _prover_close_credentials_search_for_proof_req(command_handle: CommandHandle,
 /// predicate_info: Describes requested attribute predicate
 ///     {
 ///         "name": attribute name, (case insensitive and ignore spaces)
-///         "p_type": predicate type (Currently >= only)
+///         "p_type": predicate type (">=", ">", "<=", "<", "between")
 ///         "p_value": predicate value
+///         "p_value_to": Optional<int>, // upper bound of "between" predicate, "p_value" is the lower one (both inclusive)
 ///         "restrictions": Optional<wql query>,
 ///         "non_revoked": Optional<<non_revoc_interval>>, // see below,
 ///                        // If specified prover must proof non-revocation
@@ -1345,6 +1624,75 @@ fn _verifier_verify_proof(command_handle: CommandHandle, proof_request_json: &st
     })
 }
 
+/// Verifies a proof (of multiple credential) and returns a detailed report instead of a bare boolean.
+///
+/// # Arguments
+/// The same as for `verifier_verify_proof`.
+///
+/// # Returns
+/// * `report_json`: proof verification report
+/// {
+///     "valid": bool, - true if status is "valid"
+///     "status": "valid" | "proof_invalid" | "request_not_satisfied" | "missing_ledger_data",
+///     "requested_attributes": {"<attr_referent>": <referent_report>, ...},
+///     "requested_predicates": {"<predicate_referent>": <referent_report>, ...},
+///     "sub_proofs": [<sub_proof_report>, ...],
+///     "crypto": Optional<<check>>,
+/// }
+/// See `indy_verifier_verify_proof_with_report` in libindy for the details.
+pub fn verifier_verify_proof_with_report(proof_request_json: &str, proof_json: &str, schemas_json: &str, credential_defs_json: &str, rev_reg_defs_json: &str, rev_regs_json: &str) -> Box<dyn Future<Item=String, Error=IndyError>> {
+    let (receiver, command_handle, cb) = ClosureHandler::cb_ec_string();
+
+    let err = _verifier_verify_proof_with_report(command_handle, proof_request_json, proof_json, schemas_json, credential_defs_json, rev_reg_defs_json, rev_regs_json, cb);
+
+    ResultHandler::str(command_handle, err, receiver)
+}
+
+fn _verifier_verify_proof_with_report(command_handle: CommandHandle, proof_request_json: &str, proof_json: &str, schemas_json: &str, credential_defs_json: &str, rev_reg_defs_json: &str, rev_regs_json: &str, cb: Option<ResponseStringCB>) -> ErrorCode {
+    let proof_request_json = c_str!(proof_request_json);
+    let proof_json = c_str!(proof_json);
+    let schemas_json = c_str!(schemas_json);
+    let credential_defs_json = c_str!(credential_defs_json);
+    let rev_reg_defs_json = c_str!(rev_reg_defs_json);
+    let rev_regs_json = c_str!(rev_regs_json);
+
+    ErrorCode::from(unsafe {
+        anoncreds::indy_verifier_verify_proof_with_report(command_handle, proof_request_json.as_ptr(), proof_json.as_ptr(), schemas_json.as_ptr(), credential_defs_json.as_ptr(), rev_reg_defs_json.as_ptr(), rev_regs_json.as_ptr(), cb)
+    })
+}
+
+/// Verifies a proof (of multiple credential) fetching schemas, credential definitions
+/// and revocation data it refers to from the ledger.
+///
+/// # Arguments
+/// * `pool_handle` - pool handle (created by open_pool_ledger).
+/// * `wallet_handle` - wallet handle (created by open_wallet) used for the ledger objects cache.
+/// * `submitter_did` - DID of the submitter stored in secured Wallet.
+/// * `proof_request_json` - proof request json (see `verifier_verify_proof`)
+/// * `proof_json` - created for request proof json (see `verifier_verify_proof`)
+/// * `options_json` - cache options used for schemas, credential definitions and revocation registry definitions (see `cache::get_schema`)
+///
+/// # Returns
+/// * `report_json`: proof verification report (see `verifier_verify_proof_with_report`)
+pub fn verifier_verify_proof_with_ledger(pool_handle: PoolHandle, wallet_handle: WalletHandle, submitter_did: &str, proof_request_json: &str, proof_json: &str, options_json: &str) -> Box<dyn Future<Item=String, Error=IndyError>> {
+    let (receiver, command_handle, cb) = ClosureHandler::cb_ec_string();
+
+    let err = _verifier_verify_proof_with_ledger(command_handle, pool_handle, wallet_handle, submitter_did, proof_request_json, proof_json, options_json, cb);
+
+    ResultHandler::str(command_handle, err, receiver)
+}
+
+fn _verifier_verify_proof_with_ledger(command_handle: CommandHandle, pool_handle: PoolHandle, wallet_handle: WalletHandle, submitter_did: &str, proof_request_json: &str, proof_json: &str, options_json: &str, cb: Option<ResponseStringCB>) -> ErrorCode {
+    let submitter_did = c_str!(submitter_did);
+    let proof_request_json = c_str!(proof_request_json);
+    let proof_json = c_str!(proof_json);
+    let options_json = c_str!(options_json);
+
+    ErrorCode::from(unsafe {
+        anoncreds::indy_verifier_verify_proof_with_ledger(command_handle, pool_handle, wallet_handle, submitter_did.as_ptr(), proof_request_json.as_ptr(), proof_json.as_ptr(), options_json.as_ptr(), cb)
+    })
+}
+
 
 /// Create revocation state for a credential that corresponds to a particular time.
 ///
@@ -1494,3 +1842,92 @@ fn _to_unqualified(command_handle: CommandHandle, entity: &str, cb: Option<Respo
         anoncreds::indy_to_unqualified(command_handle, entity.as_ptr(), cb)
     })
 }
+
+/// Encode raw credential attribute values with the canonical encoding:
+/// 32-bit integers are kept as is, any other value is encoded as SHA-256 hash converted to a big integer.
+///
+/// # Arguments
+/// * `raw_values_json`: raw values of credential attributes
+///     {
+///         "attr1": "value1",
+///         "attr2": "value2"
+///     }
+///
+/// # Returns
+/// * `cred_values_json`: credential values suitable for `issuer_create_credential`
+///     {
+///         "attr1": {"raw": "value1", "encoded": "value1_as_int" },
+///         "attr2": {"raw": "value2", "encoded": "value2_as_int" }
+///     }
+pub fn encode_credential_values(raw_values_json: &str) -> Box<dyn Future<Item=String, Error=IndyError>> {
+    let (receiver, command_handle, cb) = ClosureHandler::cb_ec_string();
+
+    let err = _encode_credential_values(command_handle, raw_values_json, cb);
+
+    ResultHandler::str(command_handle, err, receiver)
+}
+
+fn _encode_credential_values(command_handle: CommandHandle, raw_values_json: &str, cb: Option<ResponseStringCB>) -> ErrorCode {
+    let raw_values_json = c_str!(raw_values_json);
+
+    ErrorCode::from(unsafe {
+        anoncreds::indy_encode_credential_values(command_handle, raw_values_json.as_ptr(), cb)
+    })
+}
+
+/// Validate anoncreds object received from a remote party: identifiers format and consistency,
+/// attribute names, non-revocation intervals and other structural and semantic checks.
+///
+/// # Arguments
+/// * `object_type`: one of "schema", "cred_def", "rev_reg_def", "cred_offer", "cred_req", "cred", "proof_req", "proof"
+/// * `object_json`: object json received from a remote party
+/// * `schema_json`: (optional) trusted schema json to check object identifiers and attribute names against
+/// * `cred_def_json`: (optional) trusted credential definition json to check object identifiers
+///    (and attribute names if schema isn't set) against
+///
+/// # Returns
+/// * `problems_json`: list of human-readable problems found in the object, empty list if the object is valid
+///     [<str>, ...]
+pub fn validate(object_type: &str, object_json: &str, schema_json: Option<&str>, cred_def_json: Option<&str>) -> Box<dyn Future<Item=String, Error=IndyError>> {
+    let (receiver, command_handle, cb) = ClosureHandler::cb_ec_string();
+
+    let err = _validate(command_handle, object_type, object_json, schema_json, cred_def_json, cb);
+
+    ResultHandler::str(command_handle, err, receiver)
+}
+
+fn _validate(command_handle: CommandHandle, object_type: &str, object_json: &str, schema_json: Option<&str>, cred_def_json: Option<&str>, cb: Option<ResponseStringCB>) -> ErrorCode {
+    let object_type = c_str!(object_type);
+    let object_json = c_str!(object_json);
+    let schema_json_str = opt_c_str!(schema_json);
+    let cred_def_json_str = opt_c_str!(cred_def_json);
+
+    ErrorCode::from(unsafe {
+        anoncreds::indy_anoncreds_validate(command_handle, object_type.as_ptr(), object_json.as_ptr(), opt_c_ptr!(schema_json, schema_json_str), opt_c_ptr!(cred_def_json, cred_def_json_str), cb)
+    })
+}
+
+/// Encode a calendar date with the standard date encoding for predicates:
+/// `YYYY-MM-DD` is encoded as `YYYYMMDD` integer, so encoded dates keep their order.
+///
+/// # Arguments
+/// * `date`: calendar date in `YYYY-MM-DD` format
+///
+/// # Returns
+/// * `encoded_date`: date encoded as decimal integer string to be used as raw value of credential attribute
+///    and as `p_value` / `p_value_to` of predicates
+pub fn encode_date(date: &str) -> Box<dyn Future<Item=String, Error=IndyError>> {
+    let (receiver, command_handle, cb) = ClosureHandler::cb_ec_string();
+
+    let err = _encode_date(command_handle, date, cb);
+
+    ResultHandler::str(command_handle, err, receiver)
+}
+
+fn _encode_date(command_handle: CommandHandle, date: &str, cb: Option<ResponseStringCB>) -> ErrorCode {
+    let date = c_str!(date);
+
+    ErrorCode::from(unsafe {
+        anoncreds::indy_encode_date(command_handle, date.as_ptr(), cb)
+    })
+}
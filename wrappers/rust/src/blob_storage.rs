@@ -5,7 +5,7 @@ use crate::{ErrorCode, IndyError};
 use std::ffi::CString;
 
 use ffi::blob_storage;
-use ffi::ResponseI32CB;
+use ffi::{ResponseEmptyCB, ResponseI32CB};
 
 use crate::utils::callbacks::{ClosureHandler, ResultHandler};
 use crate::{IndyHandle, CommandHandle};
@@ -39,3 +39,115 @@ fn _open_writer(command_handle: CommandHandle, xtype: &str, config_json: &str, c
 
     ErrorCode::from(unsafe { blob_storage::indy_open_blob_storage_writer(command_handle, xtype.as_ptr(), config_json.as_ptr(), cb) })
 }
+
+/// Opens blob storage reader for the tails file of the revocation registry.
+///
+/// Tails file is looked up in the local content-addressed tails directory first.
+/// If it is missing it's fetched from `tailsLocation` of the revocation registry definition,
+/// checked against `tailsHash` and stored in the tails directory, so it's downloaded only once.
+///
+/// # Arguments
+/// * `rev_reg_def_json` - revocation registry definition json
+/// * `config_json` - tails reader config json
+///     {
+///         "tails_dir": Optional<string> - directory to store tails files in (<indy home>/tails by default),
+///         "fetcher_type": Optional<string> - blob storage reader type used to fetch missing tails file.
+///                         By default "http" type is used for http:// locations and local file is read otherwise.
+///         "fetcher_config": Optional<object> - config of the fetcher reader type (empty object by default)
+///     }
+///
+/// # Returns
+/// blob storage reader handle to use in revocation related anoncreds calls
+pub fn open_tails_reader(rev_reg_def_json: &str, config_json: &str) -> Box<dyn Future<Item=IndyHandle, Error=IndyError>> {
+    let (receiver, command_handle, cb) = ClosureHandler::cb_ec_handle();
+
+    let err = _open_tails_reader(command_handle, rev_reg_def_json, config_json, cb);
+
+    ResultHandler::handle(command_handle, err, receiver)
+}
+
+fn _open_tails_reader(command_handle: CommandHandle, rev_reg_def_json: &str, config_json: &str, cb: Option<ResponseI32CB>) -> ErrorCode {
+    let rev_reg_def_json = c_str!(rev_reg_def_json);
+    let config_json = c_str!(config_json);
+
+    ErrorCode::from(unsafe { blob_storage::indy_open_tails_reader(command_handle, rev_reg_def_json.as_ptr(), config_json.as_ptr(), cb) })
+}
+
+/// Registers custom blob storage reader implementation.
+///
+/// Registered reader type can be used in `open_reader` call the same way as built-in
+/// `default` (local files) and `http` (read-through HTTP download with local cache) types.
+///
+/// # Arguments
+/// * `xtype` - Blob storage reader type name.
+/// * `open` - Reader open operation handler
+/// * `open_blob` - Reader open blob operation handler
+/// * `read` - Reader read operation handler
+/// * `verify` - Reader verify operation handler
+/// * `close_blob` - Reader close blob operation handler
+pub fn register_reader(xtype: &str,
+                       open: Option<blob_storage::BlobStorageReaderOpen>,
+                       open_blob: Option<blob_storage::BlobStorageReaderOpenBlob>,
+                       read: Option<blob_storage::BlobStorageReaderRead>,
+                       verify: Option<blob_storage::BlobStorageReaderVerify>,
+                       close_blob: Option<blob_storage::BlobStorageReaderCloseBlob>) -> Box<dyn Future<Item=(), Error=IndyError>> {
+    let (receiver, command_handle, cb) = ClosureHandler::cb_ec();
+
+    let err = _register_reader(command_handle, xtype, open, open_blob, read, verify, close_blob, cb);
+
+    ResultHandler::empty(command_handle, err, receiver)
+}
+
+fn _register_reader(command_handle: CommandHandle,
+                    xtype: &str,
+                    open: Option<blob_storage::BlobStorageReaderOpen>,
+                    open_blob: Option<blob_storage::BlobStorageReaderOpenBlob>,
+                    read: Option<blob_storage::BlobStorageReaderRead>,
+                    verify: Option<blob_storage::BlobStorageReaderVerify>,
+                    close_blob: Option<blob_storage::BlobStorageReaderCloseBlob>,
+                    cb: Option<ResponseEmptyCB>) -> ErrorCode {
+    let xtype = c_str!(xtype);
+
+    ErrorCode::from(unsafe {
+        blob_storage::indy_register_blob_storage_reader(command_handle, xtype.as_ptr(), open, open_blob, read, verify, close_blob, cb)
+    })
+}
+
+/// Registers custom blob storage writer implementation.
+///
+/// Registered writer type can be used in `open_writer` call the same way as built-in `default` (local files) type.
+///
+/// # Arguments
+/// * `xtype` - Blob storage writer type name.
+/// * `open` - Writer open operation handler
+/// * `create_blob` - Writer create blob operation handler
+/// * `append` - Writer append operation handler
+/// * `finalize` - Writer finalize operation handler
+/// * `free_blob` - Writer free blob operation handler
+pub fn register_writer(xtype: &str,
+                       open: Option<blob_storage::BlobStorageWriterOpen>,
+                       create_blob: Option<blob_storage::BlobStorageWriterCreateBlob>,
+                       append: Option<blob_storage::BlobStorageWriterAppend>,
+                       finalize: Option<blob_storage::BlobStorageWriterFinalize>,
+                       free_blob: Option<blob_storage::BlobStorageWriterFreeBlob>) -> Box<dyn Future<Item=(), Error=IndyError>> {
+    let (receiver, command_handle, cb) = ClosureHandler::cb_ec();
+
+    let err = _register_writer(command_handle, xtype, open, create_blob, append, finalize, free_blob, cb);
+
+    ResultHandler::empty(command_handle, err, receiver)
+}
+
+fn _register_writer(command_handle: CommandHandle,
+                    xtype: &str,
+                    open: Option<blob_storage::BlobStorageWriterOpen>,
+                    create_blob: Option<blob_storage::BlobStorageWriterCreateBlob>,
+                    append: Option<blob_storage::BlobStorageWriterAppend>,
+                    finalize: Option<blob_storage::BlobStorageWriterFinalize>,
+                    free_blob: Option<blob_storage::BlobStorageWriterFreeBlob>,
+                    cb: Option<ResponseEmptyCB>) -> ErrorCode {
+    let xtype = c_str!(xtype);
+
+    ErrorCode::from(unsafe {
+        blob_storage::indy_register_blob_storage_writer(command_handle, xtype.as_ptr(), open, create_blob, append, finalize, free_blob, cb)
+    })
+}
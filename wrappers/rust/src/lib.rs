@@ -65,6 +65,8 @@ pub use ffi::{
 /// # Arguments
 /// * `config` - {
 ///     "crypto_thread_pool_size": <int> - size of thread pool for the most expensive crypto operations. (4 by default)
+///     "check_canonical_encoding": <bool> - whether proof verification should reject revealed attributes
+///         which are not encoded with the canonical encoding. (false by default)
 /// }
 pub fn set_runtime_config(config: &str) -> ErrorCode {
     let config = c_str!(config);
@@ -19,6 +19,7 @@ lazy_static! {
     static ref CALLBACKS_WALLETHANDLE: Mutex<HashMap<CommandHandle, oneshot::Sender<Result<WalletHandle, IndyError>>>> = Default::default();
     static ref CALLBACKS_BOOL: Mutex<HashMap<CommandHandle, oneshot::Sender<Result<bool, IndyError>>>> = Default::default();
     static ref CALLBACKS_STR_SLICE: Mutex<HashMap<CommandHandle, oneshot::Sender<Result<(String, Vec<u8>), IndyError>>>> = Default::default();
+    static ref CALLBACKS_USIZE: Mutex<HashMap<CommandHandle, oneshot::Sender<Result<usize, IndyError>>>> = Default::default();
     static ref CALLBACKS_HANDLE_USIZE: Mutex<HashMap<CommandHandle, oneshot::Sender<Result<(CommandHandle, usize), IndyError>>>> = Default::default();
     static ref CALLBACKS_STR_STR_U64: Mutex<HashMap<CommandHandle, oneshot::Sender<Result<(String, String, u64), IndyError>>>> = Default::default();
     static ref CALLBACKS_STR: Mutex<HashMap<CommandHandle, oneshot::Sender<Result<String, IndyError>>>> = Default::default();
@@ -70,6 +71,8 @@ impl ClosureHandler {
 
     cb_ec!(cb_ec_wallethandle(handle:WalletHandle)->WalletHandle, CALLBACKS_WALLETHANDLE, handle);
 
+    cb_ec!(cb_ec_usize(u: usize)->usize, CALLBACKS_USIZE, u);
+
     cb_ec!(cb_ec_handle_usize(handle:CommandHandle, u: usize)->(CommandHandle, usize), CALLBACKS_HANDLE_USIZE, (handle, u));
 
     cb_ec!(cb_ec_string(str1:*const c_char)->String,
@@ -137,6 +140,7 @@ impl ResultHandler {
     result_handler!(bool(bool), CALLBACKS_BOOL);
     result_handler!(str(String), CALLBACKS_STR);
     result_handler!(str_i64((String, i64)), CALLBACKS_STR_I64);
+    result_handler!(usize(usize), CALLBACKS_USIZE);
     result_handler!(handle_usize((CommandHandle, usize)), CALLBACKS_HANDLE_USIZE);
     result_handler!(str_slice((String, Vec<u8>)), CALLBACKS_STR_SLICE);
     result_handler!(str_str((String, String)), CALLBACKS_STR_STR);
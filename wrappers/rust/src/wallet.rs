@@ -2,17 +2,29 @@ use futures::Future;
 
 use crate::{ErrorCode, IndyError};
 
+use std::collections::HashMap;
 use std::ffi::CString;
+use std::io::{Read, Write};
 use std::ptr::null;
+use std::slice;
+use std::sync::Mutex;
 
 use crate::utils::callbacks::{ClosureHandler, ResultHandler};
+use crate::utils::sequence::SequenceUtils;
 
 use ffi::{wallet, non_secrets};
 use ffi::{ResponseEmptyCB,
+          ResponseSliceCB,
           ResponseStringCB,
           ResponseI32CB,
+          ResponseUsizeCB,
           ResponseWalletHandleCB};
-use crate::{CommandHandle, WalletHandle, SearchHandle};
+use crate::{CommandHandle, IndyHandle, WalletHandle, SearchHandle};
+
+lazy_static! {
+    static ref EXPORT_WRITERS: Mutex<HashMap<IndyHandle, Box<dyn Write + Send>>> = Default::default();
+    static ref IMPORT_READERS: Mutex<HashMap<IndyHandle, Box<dyn Read + Send>>> = Default::default();
+}
 
 /// Registers custom wallet implementation.
 ///
@@ -148,6 +160,71 @@ fn _register_storage(command_handle: CommandHandle,
     })
 }
 
+/// Registers statistics handlers for custom wallet storage implementation.
+///
+/// Handlers are optional. Without them `get_wallet_stats` iterates over all records of the storage.
+///
+/// # Arguments
+/// * `xtype` - Storage type name registered with `register_wallet_storage`.
+/// * `get_storage_stats` - WalletType get storage statistics operation handler
+/// * `free_storage_stats` - WalletType free storage statistics operation handler
+pub fn register_wallet_storage_stats(xtype: &str,
+                                     get_storage_stats: Option<wallet::WalletGetStorageStats>,
+                                     free_storage_stats: Option<wallet::WalletFreeStorageStats>) -> Box<dyn Future<Item=(), Error=IndyError>> {
+    let (receiver, command_handle, cb) = ClosureHandler::cb_ec();
+
+    let err = _register_wallet_storage_stats(command_handle, xtype, get_storage_stats, free_storage_stats, cb);
+
+    ResultHandler::empty(command_handle, err, receiver)
+}
+
+fn _register_wallet_storage_stats(command_handle: CommandHandle,
+                                  xtype: &str,
+                                  get_storage_stats: Option<wallet::WalletGetStorageStats>,
+                                  free_storage_stats: Option<wallet::WalletFreeStorageStats>,
+                                  cb: Option<ResponseEmptyCB>) -> ErrorCode {
+    let xtype = c_str!(xtype);
+
+    ErrorCode::from(unsafe {
+        wallet::indy_register_wallet_storage_stats(command_handle, xtype.as_ptr(), get_storage_stats, free_storage_stats, cb)
+    })
+}
+
+/// Registers custom key-encryption key (KEK) provider implementation.
+///
+/// Wallets use registered provider if `key_derivation_method` of wallet credentials
+/// is set to `{"KEK": "<provider type>"}`. In this case `key` field contains the identifier
+/// of the key-encryption key. Note `file` provider type is built-in.
+///
+/// # Arguments
+/// * `xtype` - KEK provider type name.
+/// * `wrap_key` - KEK provider wrap key operation handler
+/// * `unwrap_key` - KEK provider unwrap key operation handler
+/// * `free_key` - KEK provider free key buffer operation handler
+pub fn register_wallet_kek_provider(xtype: &str,
+                                    wrap_key: Option<wallet::WalletKekWrapKey>,
+                                    unwrap_key: Option<wallet::WalletKekUnwrapKey>,
+                                    free_key: Option<wallet::WalletKekFreeKey>) -> Box<dyn Future<Item=(), Error=IndyError>> {
+    let (receiver, command_handle, cb) = ClosureHandler::cb_ec();
+
+    let err = _register_wallet_kek_provider(command_handle, xtype, wrap_key, unwrap_key, free_key, cb);
+
+    ResultHandler::empty(command_handle, err, receiver)
+}
+
+fn _register_wallet_kek_provider(command_handle: CommandHandle,
+                                 xtype: &str,
+                                 wrap_key: Option<wallet::WalletKekWrapKey>,
+                                 unwrap_key: Option<wallet::WalletKekUnwrapKey>,
+                                 free_key: Option<wallet::WalletKekFreeKey>,
+                                 cb: Option<ResponseEmptyCB>) -> ErrorCode {
+    let xtype = c_str!(xtype);
+
+    ErrorCode::from(unsafe {
+        wallet::indy_register_wallet_kek_provider(command_handle, xtype.as_ptr(), wrap_key, unwrap_key, free_key, cb)
+    })
+}
+
 /// Creates a new secure wallet with the given unique name.
 ///
 /// # Arguments
@@ -216,6 +293,9 @@ fn _open_wallet(command_handle: CommandHandle, config: &str, credentials: &str,
 ///   {
 ///     "path": path of the file that contains exported wallet content
 ///     "key": passphrase used to derive export key
+///     "incremental": optional<bool> export only changes since the previous incremental export
+///     "filter": optional<array> export only records matching any of the filters
+///               [{"type": string, "query": optional<object> WQL query}]
 ///   }
 pub fn export_wallet(wallet_handle: WalletHandle, export_config: &str) -> Box<dyn Future<Item=(), Error=IndyError>> {
     let (receiver, command_handle, cb) = ClosureHandler::cb_ec();
@@ -274,6 +354,336 @@ fn _import_wallet(command_handle: CommandHandle, config: &str, credentials: &str
     })
 }
 
+/// Imports content of the incremental export into opened wallet.
+///
+/// Increments have to be imported in the same order as they were exported.
+///
+/// # Arguments
+/// * `wallet_handle` - wallet handle returned by open_wallet
+/// * `import_config` - JSON containing settings for input operation.
+///   {
+///     "path": path of the file that contains exported wallet increment
+///     "key": passphrase used to derive export key
+///   }
+pub fn import_wallet_increment(wallet_handle: WalletHandle, import_config: &str) -> Box<dyn Future<Item=(), Error=IndyError>> {
+    let (receiver, command_handle, cb) = ClosureHandler::cb_ec();
+
+    let err = _import_wallet_increment(command_handle, wallet_handle, import_config, cb);
+
+    ResultHandler::empty(command_handle, err, receiver)
+}
+
+fn _import_wallet_increment(command_handle: CommandHandle, wallet_handle: WalletHandle, import_config: &str, cb: Option<ResponseEmptyCB>) -> ErrorCode {
+    let import_config = c_str!(import_config);
+
+    ErrorCode::from(unsafe {
+      wallet::indy_import_wallet_increment(command_handle, wallet_handle, import_config.as_ptr(), cb)
+    })
+}
+
+/// Exports opened wallet into a memory buffer.
+///
+/// # Arguments
+/// * `wallet_handle` - wallet handle returned by indy_open_wallet
+/// * `export_config` - JSON containing settings for input operation (the same as for `export_wallet` but without `path`).
+///
+/// # Returns
+/// Exported wallet content
+pub fn export_wallet_to_buffer(wallet_handle: WalletHandle, export_config: &str) -> Box<dyn Future<Item=Vec<u8>, Error=IndyError>> {
+    let (receiver, command_handle, cb) = ClosureHandler::cb_ec_slice();
+
+    let err = _export_wallet_to_buffer(command_handle, wallet_handle, export_config, cb);
+
+    ResultHandler::slice(command_handle, err, receiver)
+}
+
+fn _export_wallet_to_buffer(command_handle: CommandHandle, wallet_handle: WalletHandle, export_config: &str, cb: Option<ResponseSliceCB>) -> ErrorCode {
+    let export_config = c_str!(export_config);
+
+    ErrorCode::from(unsafe {
+        wallet::indy_export_wallet_to_buffer(command_handle, wallet_handle, export_config.as_ptr(), cb)
+    })
+}
+
+/// Exports opened wallet into an application provided sink.
+///
+/// Note that `write_cb` is called from libindy thread.
+///
+/// # Arguments
+/// * `wallet_handle` - wallet handle returned by indy_open_wallet
+/// * `export_config` - JSON containing settings for input operation (the same as for `export_wallet` but without `path`).
+/// * `sink_handle` - application defined handle passed to `write_cb`
+/// * `write_cb` - callback that writes the next chunk of the exported wallet
+pub fn export_wallet_to_sink(wallet_handle: WalletHandle,
+                             export_config: &str,
+                             sink_handle: IndyHandle,
+                             write_cb: Option<wallet::WalletExportWrite>) -> Box<dyn Future<Item=(), Error=IndyError>> {
+    let (receiver, command_handle, cb) = ClosureHandler::cb_ec();
+
+    let err = _export_wallet_to_sink(command_handle, wallet_handle, export_config, sink_handle, write_cb, cb);
+
+    ResultHandler::empty(command_handle, err, receiver)
+}
+
+fn _export_wallet_to_sink(command_handle: CommandHandle,
+                          wallet_handle: WalletHandle,
+                          export_config: &str,
+                          sink_handle: IndyHandle,
+                          write_cb: Option<wallet::WalletExportWrite>,
+                          cb: Option<ResponseEmptyCB>) -> ErrorCode {
+    let export_config = c_str!(export_config);
+
+    ErrorCode::from(unsafe {
+        wallet::indy_export_wallet_to_sink(command_handle, wallet_handle, export_config.as_ptr(), sink_handle, write_cb, cb)
+    })
+}
+
+/// Exports opened wallet into `std::io::Write` implementation.
+///
+/// The writer is used from libindy thread and is dropped after export is finished.
+///
+/// # Arguments
+/// * `wallet_handle` - wallet handle returned by indy_open_wallet
+/// * `export_config` - JSON containing settings for input operation (the same as for `export_wallet` but without `path`).
+/// * `writer` - destination of the exported wallet
+pub fn export_wallet_to_writer<W>(wallet_handle: WalletHandle, export_config: &str, writer: W) -> Box<dyn Future<Item=(), Error=IndyError>>
+    where W: Write + Send + 'static {
+    let sink_handle = SequenceUtils::get_next_id();
+    EXPORT_WRITERS.lock().unwrap().insert(sink_handle, Box::new(writer));
+
+    let res = export_wallet_to_sink(wallet_handle, export_config, sink_handle, Some(_write_to_writer))
+        .then(move |res| {
+            let writer = EXPORT_WRITERS.lock().unwrap().remove(&sink_handle);
+
+            match (res, writer) {
+                (Ok(()), Some(mut writer)) => writer.flush().map_err(|err| IndyError {
+                    error_code: ErrorCode::CommonIOError,
+                    message: format!("Can't flush export writer: {}", err),
+                    indy_backtrace: None,
+                }),
+                (res, _) => res,
+            }
+        });
+
+    Box::new(res)
+}
+
+extern fn _write_to_writer(sink_handle: IndyHandle, data: *const u8, data_len: usize) -> i32 {
+    let mut writers = EXPORT_WRITERS.lock().unwrap();
+
+    let res = match writers.get_mut(&sink_handle) {
+        Some(writer) => writer.write_all(unsafe { slice::from_raw_parts(data, data_len) }),
+        None => return ErrorCode::CommonInvalidState as i32,
+    };
+
+    match res {
+        Ok(()) => ErrorCode::Success as i32,
+        Err(_) => ErrorCode::CommonIOError as i32,
+    }
+}
+
+/// Creates a new secure wallet and then imports its content from a memory buffer.
+///
+/// # Arguments
+/// * `config` - Wallet configuration json (the same as for `import_wallet`).
+/// * `credentials` - Wallet credentials json (the same as for `import_wallet`).
+/// * `import_config` - Import settings json: `{"key": <key used for export of the wallet>}`
+/// * `data` - exported wallet content
+pub fn import_wallet_from_buffer(config: &str, credentials: &str, import_config: &str, data: &[u8]) -> Box<dyn Future<Item=(), Error=IndyError>> {
+    let (receiver, command_handle, cb) = ClosureHandler::cb_ec();
+
+    let err = _import_wallet_from_buffer(command_handle, config, credentials, import_config, data, cb);
+
+    ResultHandler::empty(command_handle, err, receiver)
+}
+
+fn _import_wallet_from_buffer(command_handle: CommandHandle, config: &str, credentials: &str, import_config: &str, data: &[u8], cb: Option<ResponseEmptyCB>) -> ErrorCode {
+    let config = c_str!(config);
+    let credentials = c_str!(credentials);
+    let import_config = c_str!(import_config);
+
+    ErrorCode::from(unsafe {
+        wallet::indy_import_wallet_from_buffer(command_handle,
+                                               config.as_ptr(),
+                                               credentials.as_ptr(),
+                                               import_config.as_ptr(),
+                                               data.as_ptr() as *const u8,
+                                               data.len() as u32,
+                                               cb)
+    })
+}
+
+/// Creates a new secure wallet and then imports its content from an application provided source.
+///
+/// Note that `read_cb` is called from libindy thread.
+///
+/// # Arguments
+/// * `config` - Wallet configuration json (the same as for `import_wallet`).
+/// * `credentials` - Wallet credentials json (the same as for `import_wallet`).
+/// * `import_config` - Import settings json: `{"key": <key used for export of the wallet>}`
+/// * `source_handle` - application defined handle passed to `read_cb`
+/// * `read_cb` - callback that reads the next chunk of the exported wallet
+pub fn import_wallet_from_source(config: &str,
+                                 credentials: &str,
+                                 import_config: &str,
+                                 source_handle: IndyHandle,
+                                 read_cb: Option<wallet::WalletImportRead>) -> Box<dyn Future<Item=(), Error=IndyError>> {
+    let (receiver, command_handle, cb) = ClosureHandler::cb_ec();
+
+    let err = _import_wallet_from_source(command_handle, config, credentials, import_config, source_handle, read_cb, cb);
+
+    ResultHandler::empty(command_handle, err, receiver)
+}
+
+fn _import_wallet_from_source(command_handle: CommandHandle,
+                              config: &str,
+                              credentials: &str,
+                              import_config: &str,
+                              source_handle: IndyHandle,
+                              read_cb: Option<wallet::WalletImportRead>,
+                              cb: Option<ResponseEmptyCB>) -> ErrorCode {
+    let config = c_str!(config);
+    let credentials = c_str!(credentials);
+    let import_config = c_str!(import_config);
+
+    ErrorCode::from(unsafe {
+        wallet::indy_import_wallet_from_source(command_handle,
+                                               config.as_ptr(),
+                                               credentials.as_ptr(),
+                                               import_config.as_ptr(),
+                                               source_handle,
+                                               read_cb,
+                                               cb)
+    })
+}
+
+/// Creates a new secure wallet and then imports its content from `std::io::Read` implementation.
+///
+/// The reader is used from libindy thread and is dropped after import is finished.
+///
+/// # Arguments
+/// * `config` - Wallet configuration json (the same as for `import_wallet`).
+/// * `credentials` - Wallet credentials json (the same as for `import_wallet`).
+/// * `import_config` - Import settings json: `{"key": <key used for export of the wallet>}`
+/// * `reader` - source of the exported wallet
+pub fn import_wallet_from_reader<R>(config: &str, credentials: &str, import_config: &str, reader: R) -> Box<dyn Future<Item=(), Error=IndyError>>
+    where R: Read + Send + 'static {
+    let source_handle = SequenceUtils::get_next_id();
+    IMPORT_READERS.lock().unwrap().insert(source_handle, Box::new(reader));
+
+    let res = import_wallet_from_source(config, credentials, import_config, source_handle, Some(_read_from_reader))
+        .then(move |res| {
+            IMPORT_READERS.lock().unwrap().remove(&source_handle);
+            res
+        });
+
+    Box::new(res)
+}
+
+extern fn _read_from_reader(source_handle: IndyHandle, buf: *mut u8, buf_len: usize, read_len_p: *mut usize) -> i32 {
+    let mut readers = IMPORT_READERS.lock().unwrap();
+
+    let res = match readers.get_mut(&source_handle) {
+        Some(reader) => reader.read(unsafe { slice::from_raw_parts_mut(buf, buf_len) }),
+        None => return ErrorCode::CommonInvalidState as i32,
+    };
+
+    match res {
+        Ok(read_len) => {
+            unsafe { *read_len_p = read_len; }
+            ErrorCode::Success as i32
+        }
+        Err(_) => ErrorCode::CommonIOError as i32,
+    }
+}
+
+/// Imports content of the incremental export from a memory buffer into opened wallet.
+///
+/// # Arguments
+/// * `wallet_handle` - wallet handle returned by open_wallet
+/// * `import_config` - Import settings json: `{"key": <key used for export of the wallet>}`
+/// * `data` - exported wallet increment
+pub fn import_wallet_increment_from_buffer(wallet_handle: WalletHandle, import_config: &str, data: &[u8]) -> Box<dyn Future<Item=(), Error=IndyError>> {
+    let (receiver, command_handle, cb) = ClosureHandler::cb_ec();
+
+    let err = _import_wallet_increment_from_buffer(command_handle, wallet_handle, import_config, data, cb);
+
+    ResultHandler::empty(command_handle, err, receiver)
+}
+
+fn _import_wallet_increment_from_buffer(command_handle: CommandHandle, wallet_handle: WalletHandle, import_config: &str, data: &[u8], cb: Option<ResponseEmptyCB>) -> ErrorCode {
+    let import_config = c_str!(import_config);
+
+    ErrorCode::from(unsafe {
+        wallet::indy_import_wallet_increment_from_buffer(command_handle,
+                                                         wallet_handle,
+                                                         import_config.as_ptr(),
+                                                         data.as_ptr() as *const u8,
+                                                         data.len() as u32,
+                                                         cb)
+    })
+}
+
+/// Imports content of the incremental export from an application provided source into opened wallet.
+///
+/// Note that `read_cb` is called from libindy thread.
+///
+/// # Arguments
+/// * `wallet_handle` - wallet handle returned by open_wallet
+/// * `import_config` - Import settings json: `{"key": <key used for export of the wallet>}`
+/// * `source_handle` - application defined handle passed to `read_cb`
+/// * `read_cb` - callback that reads the next chunk of the exported wallet increment
+pub fn import_wallet_increment_from_source(wallet_handle: WalletHandle,
+                                           import_config: &str,
+                                           source_handle: IndyHandle,
+                                           read_cb: Option<wallet::WalletImportRead>) -> Box<dyn Future<Item=(), Error=IndyError>> {
+    let (receiver, command_handle, cb) = ClosureHandler::cb_ec();
+
+    let err = _import_wallet_increment_from_source(command_handle, wallet_handle, import_config, source_handle, read_cb, cb);
+
+    ResultHandler::empty(command_handle, err, receiver)
+}
+
+fn _import_wallet_increment_from_source(command_handle: CommandHandle,
+                                        wallet_handle: WalletHandle,
+                                        import_config: &str,
+                                        source_handle: IndyHandle,
+                                        read_cb: Option<wallet::WalletImportRead>,
+                                        cb: Option<ResponseEmptyCB>) -> ErrorCode {
+    let import_config = c_str!(import_config);
+
+    ErrorCode::from(unsafe {
+        wallet::indy_import_wallet_increment_from_source(command_handle,
+                                                         wallet_handle,
+                                                         import_config.as_ptr(),
+                                                         source_handle,
+                                                         read_cb,
+                                                         cb)
+    })
+}
+
+/// Imports content of the incremental export from `std::io::Read` implementation into opened wallet.
+///
+/// The reader is used from libindy thread and is dropped after import is finished.
+///
+/// # Arguments
+/// * `wallet_handle` - wallet handle returned by open_wallet
+/// * `import_config` - Import settings json: `{"key": <key used for export of the wallet>}`
+/// * `reader` - source of the exported wallet increment
+pub fn import_wallet_increment_from_reader<R>(wallet_handle: WalletHandle, import_config: &str, reader: R) -> Box<dyn Future<Item=(), Error=IndyError>>
+    where R: Read + Send + 'static {
+    let source_handle = SequenceUtils::get_next_id();
+    IMPORT_READERS.lock().unwrap().insert(source_handle, Box::new(reader));
+
+    let res = import_wallet_increment_from_source(wallet_handle, import_config, source_handle, Some(_read_from_reader))
+        .then(move |res| {
+            IMPORT_READERS.lock().unwrap().remove(&source_handle);
+            res
+        });
+
+    Box::new(res)
+}
+
 /// Deletes created wallet.
 pub fn delete_wallet(config: &str, credentials: &str) -> Box<dyn Future<Item=(), Error=IndyError>> {
     let (receiver, command_handle, cb) = ClosureHandler::cb_ec();
@@ -292,6 +702,38 @@ fn _delete_wallet(command_handle: CommandHandle, config: &str, credentials: &str
     })
 }
 
+/// Checks integrity of created wallet and optionally repairs it.
+///
+/// Wallet must be closed.
+///
+/// # Arguments
+/// * `config` - wallet configuration json (the same as for delete_wallet).
+/// * `credentials` - wallet credentials json (the same as for delete_wallet).
+/// * `options_json` - (optional) check options
+///   {
+///       "repair": optional<bool>, Quarantine corrupted records. Defaults to false.
+///   }
+///
+/// # Returns
+/// Wallet check report json
+pub fn check_wallet(config: &str, credentials: &str, options_json: Option<&str>) -> Box<dyn Future<Item=String, Error=IndyError>> {
+    let (receiver, command_handle, cb) = ClosureHandler::cb_ec_string();
+
+    let err = _check_wallet(command_handle, config, credentials, options_json, cb);
+
+    ResultHandler::str(command_handle, err, receiver)
+}
+
+fn _check_wallet(command_handle: CommandHandle, config: &str, credentials: &str, options_json: Option<&str>, cb: Option<ResponseStringCB>) -> ErrorCode {
+    let config = c_str!(config);
+    let credentials = c_str!(credentials);
+    let options_json = opt_c_str_json!(options_json);
+
+    ErrorCode::from(unsafe {
+      wallet::indy_check_wallet(command_handle, config.as_ptr(), credentials.as_ptr(), options_json.as_ptr(), cb)
+    })
+}
+
 /// Closes opened wallet and frees allocated resources.
 ///
 /// # Arguments
@@ -326,6 +768,9 @@ fn _close_wallet(command_handle: CommandHandle, wallet_handle: WalletHandle, cb:
 ///   If tag name starts with "~" the tag will be stored un-encrypted that will allow
 ///   usage of this tag in complex search queries (comparison, predicates)
 ///   Encrypted tags can be searched only for exact matching
+///  Reserved "~ttl": <str> tag sets time to live of the record in seconds. It is stored as
+///  "~Indy::ExpiresAt" tag with expiration time (seconds since epoch, zero padded to 20 digits).
+///  Expired records are not returned by get and search and can be removed by indy_purge_expired_records
 pub fn add_wallet_record(wallet_handle: WalletHandle, xtype: &str, id: &str, value: &str, tags_json: Option<&str>) -> Box<dyn Future<Item=(), Error=IndyError>> {
     let (receiver, command_handle, cb) = ClosureHandler::cb_ec();
 
@@ -499,6 +944,32 @@ fn _delete_wallet_record(command_handle: CommandHandle, wallet_handle: WalletHan
     })
 }
 
+/// Delete expired wallet records of the type
+///
+/// Records expire when time to live set by reserved `"~ttl"` tag passes (see add_wallet_record).
+///
+/// # Arguments
+/// * `wallet_handle` - wallet handle (created by open_wallet)
+/// * `xtype` - record type
+///
+/// # Returns
+/// Number of deleted records
+pub fn purge_expired_records(wallet_handle: WalletHandle, xtype: &str) -> Box<dyn Future<Item=usize, Error=IndyError>> {
+    let (receiver, command_handle, cb) = ClosureHandler::cb_ec_usize();
+
+    let err = _purge_expired_records(command_handle, wallet_handle, xtype, cb);
+
+    ResultHandler::usize(command_handle, err, receiver)
+}
+
+fn _purge_expired_records(command_handle: CommandHandle, wallet_handle: WalletHandle, xtype: &str, cb: Option<ResponseUsizeCB>) -> ErrorCode {
+    let xtype = c_str!(xtype);
+
+    ErrorCode::from(unsafe {
+      non_secrets::indy_purge_expired_records(command_handle, wallet_handle, xtype.as_ptr(), cb)
+    })
+}
+
 /// Get an wallet record by id
 ///
 /// # Arguments
@@ -668,3 +1139,68 @@ fn _generate_wallet_key(command_handle: CommandHandle, config: Option<&str>, cb:
 
     ErrorCode::from(unsafe { wallet::indy_generate_wallet_key(command_handle, config.as_ptr(), cb) })
 }
+
+/// Get entries of the wallet audit log.
+///
+/// Audit log is maintained only for wallets opened with `"audit_log": true` in wallet config.
+///
+/// # Arguments
+/// * `wallet_handle` - wallet handle returned by open_wallet.
+/// * `query_json` - (optional) time range of the requested entries
+///   {
+///       "from": optional<int>, Return entries created after or at this time (seconds since epoch)
+///       "to": optional<int>, Return entries created before or at this time (seconds since epoch)
+///   }
+///
+/// # Returns
+/// List of audit log entries json ordered by time
+pub fn get_wallet_audit_log(wallet_handle: WalletHandle, query_json: Option<&str>) -> Box<dyn Future<Item=String, Error=IndyError>> {
+    let (receiver, command_handle, cb) = ClosureHandler::cb_ec_string();
+
+    let err = _get_wallet_audit_log(command_handle, wallet_handle, query_json, cb);
+
+    ResultHandler::str(command_handle, err, receiver)
+}
+
+fn _get_wallet_audit_log(command_handle: CommandHandle, wallet_handle: WalletHandle, query_json: Option<&str>, cb: Option<ResponseStringCB>) -> ErrorCode {
+    let query_json = opt_c_str_json!(query_json);
+
+    ErrorCode::from(unsafe { wallet::indy_get_wallet_audit_log(command_handle, wallet_handle, query_json.as_ptr(), cb) })
+}
+
+/// Get usage statistics of the opened wallet.
+///
+/// # Arguments
+/// * `wallet_handle` - wallet handle returned by open_wallet.
+///
+/// # Returns
+/// Wallet statistics json: total and per record type numbers of records and tags,
+/// storage size and last modification time (if storage type can provide them)
+pub fn get_wallet_stats(wallet_handle: WalletHandle) -> Box<dyn Future<Item=String, Error=IndyError>> {
+    let (receiver, command_handle, cb) = ClosureHandler::cb_ec_string();
+
+    let err = _get_wallet_stats(command_handle, wallet_handle, cb);
+
+    ResultHandler::str(command_handle, err, receiver)
+}
+
+fn _get_wallet_stats(command_handle: CommandHandle, wallet_handle: WalletHandle, cb: Option<ResponseStringCB>) -> ErrorCode {
+    ErrorCode::from(unsafe { wallet::indy_get_wallet_stats(command_handle, wallet_handle, cb) })
+}
+
+/// Set callback to be notified about changes of wallet records.
+///
+/// # Arguments
+/// * `wallet_handle` - wallet handle returned by open_wallet.
+/// * `change_cb` - (optional) Change notification callback. Pass None to unsubscribe.
+pub fn set_wallet_change_callback(wallet_handle: WalletHandle, change_cb: Option<wallet::WalletChangeCB>) -> Box<dyn Future<Item=(), Error=IndyError>> {
+    let (receiver, command_handle, cb) = ClosureHandler::cb_ec();
+
+    let err = _set_wallet_change_callback(command_handle, wallet_handle, change_cb, cb);
+
+    ResultHandler::empty(command_handle, err, receiver)
+}
+
+fn _set_wallet_change_callback(command_handle: CommandHandle, wallet_handle: WalletHandle, change_cb: Option<wallet::WalletChangeCB>, cb: Option<ResponseEmptyCB>) -> ErrorCode {
+    ErrorCode::from(unsafe { wallet::indy_set_wallet_change_callback(command_handle, wallet_handle, change_cb, cb) })
+}
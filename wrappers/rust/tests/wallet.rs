@@ -491,6 +491,144 @@ mod test_wallet_export {
     }
 }
 
+#[cfg(test)]
+mod test_wallet_export_to_writer {
+    use super::*;
+    use indy::INVALID_WALLET_HANDLE;
+    use std::fs::File;
+    use std::io::{self, Write};
+
+    struct FailingWriter;
+
+    impl Write for FailingWriter {
+        fn write(&mut self, _buf: &[u8]) -> io::Result<usize> {
+            Err(io::Error::new(io::ErrorKind::Other, "write failed"))
+        }
+
+        fn flush(&mut self) -> io::Result<()> {
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn export_wallet_to_writer() {
+        let config_wallet = wallet_config::new();
+        let (_, path, _dir) = wallet_config::export::with_defaults();
+        let config_export = json!({"key": EXPORT_KEY}).to_string();
+
+        wallet::create_wallet(&config_wallet, DEFAULT_CREDENTIALS).wait().unwrap();
+        let handle = wallet::open_wallet(&config_wallet, DEFAULT_CREDENTIALS).wait().unwrap();
+
+        let file = File::create(&path).unwrap();
+        wallet::export_wallet_to_writer(handle, &config_export, file).wait().unwrap();
+
+        assert!(path.metadata().unwrap().len() > 0);
+
+        wallet::close_wallet(handle).wait().unwrap();
+        wallet::delete_wallet(&config_wallet, DEFAULT_CREDENTIALS).wait().unwrap();
+    }
+
+    #[test]
+    fn export_wallet_to_writer_failing_writer() {
+        let config_wallet = wallet_config::new();
+        let config_export = json!({"key": EXPORT_KEY}).to_string();
+
+        wallet::create_wallet(&config_wallet, DEFAULT_CREDENTIALS).wait().unwrap();
+        let handle = wallet::open_wallet(&config_wallet, DEFAULT_CREDENTIALS).wait().unwrap();
+
+        let result = wallet::export_wallet_to_writer(handle, &config_export, FailingWriter).wait();
+        assert_eq!(ErrorCode::CommonIOError, result.unwrap_err().error_code);
+
+        wallet::close_wallet(handle).wait().unwrap();
+        wallet::delete_wallet(&config_wallet, DEFAULT_CREDENTIALS).wait().unwrap();
+    }
+
+    #[test]
+    fn export_wallet_to_writer_invalid_handle() {
+        let config_export = json!({"key": EXPORT_KEY}).to_string();
+
+        let result = wallet::export_wallet_to_writer(INVALID_WALLET_HANDLE, &config_export, Vec::new()).wait();
+        assert_eq!(ErrorCode::WalletInvalidHandle, result.unwrap_err().error_code);
+    }
+}
+
+#[cfg(test)]
+mod test_wallet_import_from_reader {
+    use super::*;
+    use std::fs::File;
+    use std::io::{self, Cursor, Read};
+
+    struct FailingReader;
+
+    impl Read for FailingReader {
+        fn read(&mut self, _buf: &mut [u8]) -> io::Result<usize> {
+            Err(io::Error::new(io::ErrorKind::Other, "read failed"))
+        }
+    }
+
+    fn setup_exported_wallet(config_wallet: &str, path: &Path) -> (String, String) {
+        let config_export = json!({"key": EXPORT_KEY}).to_string();
+
+        wallet::create_wallet(&config_wallet, DEFAULT_CREDENTIALS).wait().unwrap();
+        let handle = wallet::open_wallet(&config_wallet, DEFAULT_CREDENTIALS).wait().unwrap();
+
+        let (did, _) = did::create_and_store_my_did(handle, "{}").wait().unwrap();
+        did::set_did_metadata(handle, &did, METADATA).wait().unwrap();
+        let did_with_metadata = did::get_did_metadata(handle, &did).wait().unwrap();
+
+        wallet::export_wallet_to_writer(handle, &config_export, File::create(path).unwrap()).wait().unwrap();
+
+        wallet::close_wallet(handle).wait().unwrap();
+        wallet::delete_wallet(&config_wallet, DEFAULT_CREDENTIALS).wait().unwrap();
+
+        (did, did_with_metadata)
+    }
+
+    #[test]
+    fn import_wallet_from_reader() {
+        let config_wallet = wallet_config::new();
+        let (_, path, _dir) = wallet_config::export::with_defaults();
+        let (did, did_with_metadata) = setup_exported_wallet(&config_wallet, &path);
+
+        let config_import = json!({"key": EXPORT_KEY}).to_string();
+        let file = File::open(&path).unwrap();
+
+        wallet::import_wallet_from_reader(&config_wallet, DEFAULT_CREDENTIALS, &config_import, file).wait().unwrap();
+
+        let handle = wallet::open_wallet(&config_wallet, DEFAULT_CREDENTIALS).wait().unwrap();
+
+        let imported_did_with_metadata = did::get_did_metadata(handle, &did).wait().unwrap();
+        assert_eq!(did_with_metadata, imported_did_with_metadata);
+
+        wallet::close_wallet(handle).wait().unwrap();
+        wallet::delete_wallet(&config_wallet, DEFAULT_CREDENTIALS).wait().unwrap();
+    }
+
+    #[test]
+    fn import_wallet_from_reader_invalid_data() {
+        let config_wallet = wallet_config::new();
+        let config_import = json!({"key": EXPORT_KEY}).to_string();
+
+        let result = wallet::import_wallet_from_reader(&config_wallet, DEFAULT_CREDENTIALS, &config_import, Cursor::new(vec![1, 2, 3])).wait();
+        assert!(result.is_err());
+
+        let result = wallet::open_wallet(&config_wallet, DEFAULT_CREDENTIALS).wait();
+        assert_eq!(ErrorCode::WalletNotFoundError, result.unwrap_err().error_code);
+    }
+
+    #[test]
+    fn import_wallet_from_reader_failing_reader() {
+        let config_wallet = wallet_config::new();
+        let config_import = json!({"key": EXPORT_KEY}).to_string();
+
+        let result = wallet::import_wallet_from_reader(&config_wallet, DEFAULT_CREDENTIALS, &config_import, FailingReader).wait();
+        assert_eq!(ErrorCode::CommonIOError, result.unwrap_err().error_code);
+
+        let result = wallet::open_wallet(&config_wallet, DEFAULT_CREDENTIALS).wait();
+        assert_eq!(ErrorCode::WalletNotFoundError, result.unwrap_err().error_code);
+    }
+}
+
 #[cfg(test)]
 mod test_wallet_import {
     use super::*;